@@ -0,0 +1,236 @@
+use camino::Utf8PathBuf;
+use clap::ValueEnum;
+use ecow::EcoString;
+use gleam_core::{
+    analyse::TargetSupport,
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Runtime, Target},
+    error::Error,
+    io::{Command, CommandExecutor, Stdio},
+    javascript,
+    paths::ProjectPaths,
+};
+use itertools::Itertools;
+
+use crate::fs::ProjectIO;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum BenchFormat {
+    Text,
+    Json,
+}
+
+impl BenchFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        }
+    }
+}
+
+pub fn command(
+    paths: &ProjectPaths,
+    target: Option<Target>,
+    runtime: Option<Runtime>,
+    format: BenchFormat,
+) -> Result<(), Error> {
+    let Some(command) = setup(paths, target, runtime, format)? else {
+        return Ok(());
+    };
+    let status = ProjectIO::new().exec(command)?;
+    std::process::exit(status);
+}
+
+/// Builds the project and returns the command that runs all the functions
+/// annotated with `@benchmark` in the root package. If there's no benchmark
+/// to run nothing is returned.
+///
+fn setup(
+    paths: &ProjectPaths,
+    target: Option<Target>,
+    runtime: Option<Runtime>,
+    format: BenchFormat,
+) -> Result<Option<Command>, Error> {
+    let manifest = crate::build::download_dependencies(paths, crate::cli::Reporter::new())?;
+    let config = crate::config::root_config(paths)?;
+    let target = target.unwrap_or(config.target);
+
+    let options = Options {
+        warnings_as_errors: false,
+        compile: Compile::All,
+        codegen: Codegen::All,
+        mode: Mode::Dev,
+        target: Some(target),
+        root_target_support: TargetSupport::Enforced,
+        no_print_progress: false,
+        javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+        whole_program: false,
+    };
+    let built = crate::build::main(paths, options, manifest)?;
+
+    let benchmarks = built.benchmarks();
+    if benchmarks.is_empty() {
+        match format {
+            BenchFormat::Text => println!("No benchmarks found"),
+            BenchFormat::Json => println!("[]"),
+        }
+        return Ok(None);
+    }
+
+    let command = match target {
+        Target::Erlang => match runtime {
+            Some(runtime) => {
+                return Err(Error::InvalidRuntime {
+                    target: Target::Erlang,
+                    invalid_runtime: runtime,
+                });
+            }
+            None => erlang_command(paths, &config.name, &benchmarks, format)?,
+        },
+        Target::JavaScript => {
+            let entrypoint = write_javascript_entrypoint(paths, &config.name, &benchmarks, format)?;
+            let (program, mut args) = match runtime.unwrap_or(config.javascript.runtime) {
+                Runtime::NodeJs => ("node", vec![]),
+                Runtime::Deno => ("deno", vec!["run".into()]),
+                Runtime::Bun => ("bun", vec!["run".into()]),
+            };
+            args.push(entrypoint.to_string());
+            Command {
+                program: program.into(),
+                args,
+                env: vec![],
+                cwd: None,
+                stdio: Stdio::Inherit,
+            }
+        }
+    };
+    Ok(Some(command))
+}
+
+fn erlang_command(
+    paths: &ProjectPaths,
+    package: &str,
+    benchmarks: &[(EcoString, EcoString)],
+    format: BenchFormat,
+) -> Result<Command, Error> {
+    let mut args = vec![];
+
+    // Specify locations of Erlang applications
+    let packages = paths.build_directory_for_target(Mode::Dev, Target::Erlang);
+    for entry in crate::fs::read_dir(packages)?.filter_map(Result::ok) {
+        args.push("-pa".into());
+        args.push(entry.path().join("ebin").into());
+    }
+
+    args.push("-eval".into());
+    args.push(erlang_eval(package, benchmarks, format));
+
+    // Don't run the Erlang shell
+    args.push("-noshell".into());
+
+    Ok(Command {
+        program: "erl".to_string(),
+        args,
+        env: vec![],
+        cwd: None,
+        stdio: Stdio::Inherit,
+    })
+}
+
+fn erlang_eval(
+    package: &str,
+    benchmarks: &[(EcoString, EcoString)],
+    format: BenchFormat,
+) -> String {
+    // gleam modules are separated by `/`. Erlang modules are separated by `@`.
+    let benchmarks = benchmarks
+        .iter()
+        .map(|(module, function)| format!("{{'{}', '{function}'}}", module.replace("/", "@")))
+        .join(", ");
+    format!("{package}@@main:bench([{benchmarks}], {})", format.as_str())
+}
+
+fn write_javascript_entrypoint(
+    paths: &ProjectPaths,
+    package: &str,
+    benchmarks: &[(EcoString, EcoString)],
+    format: BenchFormat,
+) -> Result<Utf8PathBuf, Error> {
+    let path = paths
+        .build_directory_for_package(Mode::Dev, Target::JavaScript, package)
+        .join("gleam.bench.mjs");
+    crate::fs::write(&path, &javascript_entrypoint(benchmarks, format))?;
+    Ok(path)
+}
+
+fn javascript_entrypoint(benchmarks: &[(EcoString, EcoString)], format: BenchFormat) -> String {
+    let mut imports = String::new();
+    let mut entries = String::new();
+    for (index, (module, function)) in benchmarks.iter().enumerate() {
+        let name = javascript::maybe_escape_identifier(function);
+        imports.push_str(&format!(
+            "import {{ {name} as benchmark{index} }} from \"./{module}.mjs\";\n"
+        ));
+        entries.push_str(&format!(
+            "  [\"{module}\", \"{function}\", benchmark{index}],\n"
+        ));
+    }
+
+    format!(
+        r#"{imports}
+// How long each benchmark is run for, in milliseconds.
+const duration = 1000;
+
+const benchmarks = [
+{entries}];
+
+// Each benchmark is run at least once, and then over and over until it has
+// been running for `duration`.
+const results = benchmarks.map(([module, name, benchmark]) => {{
+  const start = performance.now();
+  let runs = 0;
+  let elapsed = 0;
+  do {{
+    benchmark();
+    runs += 1;
+    elapsed = performance.now() - start;
+  }} while (elapsed < duration);
+  const mean_ns = Math.round((elapsed * 1_000_000) / runs);
+  return {{ module, function: name, runs, mean_ns }};
+}});
+
+const format = "{format}";
+if (format === "json") {{
+  console.log(JSON.stringify(results));
+}} else {{
+  for (const {{ module, function: name, runs, mean_ns }} of results) {{
+    console.log(`${{module}}.${{name}}: ${{mean_ns}} ns/run (${{runs}} runs)`);
+  }}
+}}
+"#,
+        format = format.as_str(),
+    )
+}
+
+#[test]
+fn erlang_eval_calls_the_bench_function() {
+    let benchmarks = vec![
+        ("sorting".into(), "sort_list".into()),
+        ("sorting/vector".into(), "sort_vector".into()),
+    ];
+    assert_eq!(
+        erlang_eval("my_package", &benchmarks, BenchFormat::Json),
+        "my_package@@main:bench([{'sorting', 'sort_list'}, {'sorting@vector', 'sort_vector'}], json)"
+    );
+}
+
+#[test]
+fn javascript_entrypoint_imports_all_benchmarks() {
+    let benchmarks = vec![
+        ("sorting".into(), "sort_list".into()),
+        ("sorting/vector".into(), "sort_vector".into()),
+        ("sorting/vector".into(), "delete".into()),
+    ];
+    insta::assert_snapshot!(javascript_entrypoint(&benchmarks, BenchFormat::Text));
+}
@@ -4,6 +4,7 @@ use gleam_core::{
     Result,
     build::{Built, Codegen, NullTelemetry, Options, ProjectCompiler, Telemetry},
     manifest::Manifest,
+    module_graph::ModuleImports,
     paths::ProjectPaths,
     warning::WarningEmitterIO,
 };
@@ -66,3 +67,30 @@ pub(crate) fn main_with_warnings(
 
     Ok(result)
 }
+
+/// Reads the imports of the modules of the project and of its dependencies,
+/// using the caches of previous builds for the modules that have not changed.
+pub fn module_imports(
+    paths: &ProjectPaths,
+    options: Options,
+    manifest: Manifest,
+    allow_cycles: bool,
+) -> Result<Vec<ModuleImports>> {
+    let root_config = crate::config::root_config(paths)?;
+    let lock = BuildLock::new_target(
+        paths,
+        options.mode,
+        options.target.unwrap_or(root_config.target),
+    )?;
+    let _guard = lock.lock(&NullTelemetry);
+    let compiler = ProjectCompiler::new(
+        root_config,
+        options,
+        manifest.packages,
+        &NullTelemetry,
+        Rc::new(ConsoleWarningEmitter),
+        paths.clone(),
+        fs::ProjectIO::new(),
+    );
+    compiler.module_imports(allow_cycles)
+}
@@ -132,6 +132,14 @@ pub(crate) fn print_exported(text: &str) {
     print_colourful_prefix("Exported", text)
 }
 
+pub(crate) fn print_generated(text: &str) {
+    print_colourful_prefix("Generated", text)
+}
+
+pub(crate) fn print_skipped(text: &str) {
+    print_colourful_prefix("Skipped", text)
+}
+
 pub(crate) fn print_checking(text: &str) {
     print_colourful_prefix("Checking", text)
 }
@@ -5,18 +5,21 @@ use std::{
 };
 
 use camino::{Utf8Path, Utf8PathBuf};
+use clap::ValueEnum;
 use ecow::{EcoString, eco_format};
 use flate2::read::GzDecoder;
 use futures::future;
 use gleam_core::{
     Error, Result,
-    build::{Mode, Target, Telemetry},
+    analyse::TargetSupport,
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Target, Telemetry},
     config::PackageConfig,
     dependency,
     error::{FileIoAction, FileKind, ShellCommandFailureReason, StandardIoAction},
     hex::{self, HEXPM_PUBLIC_KEY},
     io::{HttpClient as _, TarUnpacker, WrappedReader},
     manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
+    module_graph::ModuleGraph,
     paths::ProjectPaths,
     requirement::Requirement,
 };
@@ -29,7 +32,7 @@ use strum::IntoEnumIterator;
 mod tests;
 
 use crate::{
-    TreeOptions,
+    GraphOptions, TreeOptions,
     build_lock::BuildLock,
     cli,
     fs::{self, ProjectIO},
@@ -77,6 +80,40 @@ pub fn tree(paths: &ProjectPaths, options: TreeOptions) -> Result<()> {
     list_package_and_dependencies_tree(std::io::stdout(), options, packages.clone(), config.name)
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum GraphFormat {
+    Dot,
+    Json,
+}
+
+pub fn graph(paths: &ProjectPaths, options: GraphOptions) -> Result<()> {
+    let manifest = crate::build::download_dependencies(paths, cli::Reporter::new())?;
+    let build_options = Options {
+        mode: Mode::Dev,
+        target: None,
+        codegen: Codegen::None,
+        compile: Compile::All,
+        warnings_as_errors: false,
+        root_target_support: TargetSupport::NotEnforced,
+        no_print_progress: true,
+        javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+        whole_program: false,
+    };
+
+    // The imports are the ones used to order the modules when compiling, so
+    // an import cycle is reported the same way a build would report it.
+    let modules =
+        crate::build::module_imports(paths, build_options, manifest, options.allow_cycles)?;
+    let graph = ModuleGraph::new(modules);
+    let output = match options.format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Json => graph.to_json(),
+    };
+    println!("{output}");
+    Ok(())
+}
+
 fn get_manifest_details(paths: &ProjectPaths) -> Result<(PackageConfig, Manifest)> {
     let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
     let config = crate::config::root_config(paths)?;
@@ -8,7 +8,10 @@ use pretty_assertions::assert_eq;
 use gleam_core::{
     Error,
     build::Runtime,
-    config::{DenoConfig, DenoFlag, Docs, ErlangConfig, JavaScriptConfig, Repository},
+    config::{
+        DenoConfig, DenoFlag, Docs, ErlangConfig, JavaScriptConfig, LintsConfig,
+        OptimisationsConfig, Repository,
+    },
     manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
     requirement::Requirement,
 };
@@ -1193,6 +1196,7 @@ fn package_config(
         erlang: ErlangConfig {
             application_start_module: None,
             extra_applications: vec![],
+            minimum_otp_version: None,
         },
         javascript: JavaScriptConfig {
             typescript_declarations: false,
@@ -1213,6 +1217,10 @@ fn package_config(
         },
         target: Target::Erlang,
         internal_modules: None,
+        optimisations: OptimisationsConfig::default(),
+        lints: LintsConfig::default(),
+        features: vec![],
+        panic_handler: None,
     }
 }
 
@@ -7,9 +7,9 @@ use crate::{cli, fs::ProjectIO, http::HttpClient};
 use gleam_core::{
     Result,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Package, Target},
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Package, Target},
     config::{DocsPage, PackageConfig},
-    docs::DocContext,
+    docs::{DocContext, DocVisibility},
     error::Error,
     hex,
     io::HttpClient as _,
@@ -40,6 +40,8 @@ pub struct BuildOptions {
     /// Whether to open the docs after building.
     pub open: bool,
     pub target: Option<Target>,
+    /// Whether to include private and internal definitions in the docs.
+    pub private: bool,
 }
 
 pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
@@ -59,6 +61,8 @@ pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         crate::build::download_dependencies(paths, cli::Reporter::new())?,
     )?;
@@ -67,6 +71,11 @@ pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
         &config,
         &mut built.root_package,
         DocContext::Build,
+        if options.private {
+            DocVisibility::IncludePrivate
+        } else {
+            DocVisibility::PublicOnly
+        },
         &built.module_interfaces,
     )?;
 
@@ -108,6 +117,7 @@ pub(crate) fn build_documentation(
     config: &PackageConfig,
     compiled: &mut Package,
     is_hex_publish: DocContext,
+    visibility: DocVisibility,
     cached_modules: &im::HashMap<EcoString, type_::ModuleInterface>,
 ) -> Result<Vec<gleam_core::io::OutputFile>, Error> {
     compiled.attach_doc_and_module_comments();
@@ -126,6 +136,7 @@ pub(crate) fn build_documentation(
         ProjectIO::new(),
         SystemTime::now(),
         is_hex_publish,
+        visibility,
     );
 
     outputs.push(gleam_core::docs::generate_json_package_interface(
@@ -157,6 +168,8 @@ pub fn publish(paths: &ProjectPaths) -> Result<()> {
             mode: Mode::Prod,
             target: None,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         crate::build::download_dependencies(paths, cli::Reporter::new())?,
     )?;
@@ -165,6 +178,7 @@ pub fn publish(paths: &ProjectPaths) -> Result<()> {
         &config,
         &mut built.root_package,
         DocContext::HexPublish,
+        DocVisibility::PublicOnly,
         &built.module_interfaces,
     )?;
     let archive = crate::fs::create_tar_archive(outputs)?;
@@ -0,0 +1,41 @@
+use gleam_core::{
+    diagnostic::{Explanation, explanation},
+    error::Error,
+};
+
+/// Prints the extended explanation of the diagnostics with the given code.
+pub fn command(code: &str) -> Result<(), Error> {
+    print!("{}", render(code)?);
+    Ok(())
+}
+
+fn render(code: &str) -> Result<String, Error> {
+    let Explanation { code, title, text } =
+        explanation(code).ok_or_else(|| Error::UnknownDiagnosticCode { code: code.into() })?;
+    Ok(format!("{code}: {title}\n\n{text}\n"))
+}
+
+#[test]
+fn explain_known_code() {
+    let text = render("E0001").expect("E0001 is a known code");
+    assert!(text.starts_with("E0001: Unknown variable\n\n"));
+    assert!(text.contains("is not defined in the scope"));
+}
+
+#[test]
+fn explain_code_ignores_case() {
+    assert_eq!(
+        render("w0003").expect("W0003 is a known code"),
+        render("W0003").expect("W0003 is a known code")
+    );
+}
+
+#[test]
+fn explain_unknown_code() {
+    assert_eq!(
+        render("E9999"),
+        Err(Error::UnknownDiagnosticCode {
+            code: "E9999".into()
+        })
+    );
+}
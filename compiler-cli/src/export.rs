@@ -1,11 +1,18 @@
-use camino::Utf8PathBuf;
+use std::collections::HashSet;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
 use gleam_core::{
-    Result,
+    Error, Result,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Target},
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Origin, Target},
+    io::{Content, OutputFile},
+    package_interface::PackageInterface,
     paths::ProjectPaths,
 };
 
+use crate::fs::ProjectIO;
+
 static ENTRYPOINT_FILENAME_POWERSHELL: &str = "entrypoint.ps1";
 static ENTRYPOINT_FILENAME_POSIX_SHELL: &str = "entrypoint.sh";
 
@@ -25,6 +32,8 @@ static ENTRYPOINT_TEMPLATE_POSIX_SHELL: &str =
 /// - ebin
 /// - include
 /// - priv
+///
+/// Packages only needed by the project's dev-dependencies are left out.
 pub(crate) fn erlang_shipment(paths: &ProjectPaths) -> Result<()> {
     let target = Target::Erlang;
     let mode = Mode::Prod;
@@ -37,6 +46,17 @@ pub(crate) fn erlang_shipment(paths: &ProjectPaths) -> Result<()> {
     crate::fs::delete_directory(&build)?;
     crate::fs::delete_directory(&out)?;
 
+    let manifest = crate::build::download_dependencies(paths, crate::cli::Reporter::new())?;
+
+    // Dev-dependencies are only needed to run the tests, so they are not
+    // included in the shipment unless some other dependency needs them.
+    let config = crate::config::root_config(paths)?;
+    let dev_only_applications: HashSet<_> = manifest
+        .dev_only_packages(config.dependencies.keys())
+        .into_iter()
+        .map(|package| package.application_name().clone())
+        .collect();
+
     // Build project in production mode
     let built = crate::build::main(
         paths,
@@ -48,8 +68,10 @@ pub(crate) fn erlang_shipment(paths: &ProjectPaths) -> Result<()> {
             mode,
             target: Some(target),
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
-        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+        manifest,
     )?;
 
     for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
@@ -61,6 +83,10 @@ pub(crate) fn erlang_shipment(paths: &ProjectPaths) -> Result<()> {
         }
 
         let name = path.file_name().expect("Directory name");
+        if dev_only_applications.contains(name) {
+            continue;
+        }
+
         let build = build.join(name);
         let out = out.join(name);
         crate::fs::mkdir(&out)?;
@@ -110,7 +136,7 @@ fn write_entrypoint_script(
     entrypoint_output_path: &Utf8PathBuf,
     entrypoint_template_path: &str,
     package_name: &str,
-) -> Result<(), gleam_core::Error> {
+) -> Result<(), Error> {
     let text = entrypoint_template_path.replace("$PACKAGE_NAME_FROM_GLEAM", package_name);
     crate::fs::write(entrypoint_output_path, &text)?;
     crate::fs::make_executable(entrypoint_output_path)?;
@@ -142,6 +168,105 @@ pub fn typescript_prelude() -> Result<()> {
     Ok(())
 }
 
+pub fn package_interface_changelog(old: &Utf8Path, new: &Utf8Path) -> Result<()> {
+    let old = read_package_interface(old)?;
+    let new = read_package_interface(new)?;
+    print!(
+        "{}",
+        gleam_core::package_interface::changelog::additions_changelog(&old, &new)
+    );
+    Ok(())
+}
+
+fn read_package_interface(path: &Utf8Path) -> Result<PackageInterface> {
+    let json = crate::fs::read(path)?;
+    serde_json::from_str(&json).map_err(|error| Error::InvalidPackageInterface {
+        path: path.to_path_buf(),
+        error: error.to_string(),
+    })
+}
+
+/// Generates a skeleton for each of the JavaScript files referenced by the
+/// externals of the project's modules, with a stub for each of the functions
+/// they expect it to export. Files that already exist are left untouched, so
+/// an implementation is never overwritten.
+pub fn javascript_ffi_stubs(paths: &ProjectPaths) -> Result<()> {
+    let built = crate::build::main(
+        paths,
+        Options {
+            mode: Mode::Dev,
+            target: None,
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            warnings_as_errors: false,
+            root_target_support: TargetSupport::Enforced,
+            no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+
+    let modules = built.root_package.modules.iter().map(|module| &module.ast);
+    for (path, stubs) in gleam_core::javascript::ffi_stubs::ffi_stubs(modules) {
+        let relative_path = path.strip_prefix(paths.root()).unwrap_or(&path);
+        if path.exists() {
+            crate::cli::print_skipped(&format!("{relative_path}, it already exists"));
+        } else {
+            crate::fs::write(&path, &stubs)?;
+            crate::cli::print_generated(relative_path.as_str());
+        }
+    }
+    Ok(())
+}
+
+/// Bundles a module of the project, along with all the modules it uses and
+/// the JavaScript files of their externals, into a single JavaScript file
+/// that runs its `main` function.
+///
+/// The project is built as a whole program, so the definitions that can't be
+/// reached are removed before it is bundled.
+pub fn javascript_bundle(
+    paths: &ProjectPaths,
+    out: Utf8PathBuf,
+    module: Option<String>,
+) -> Result<()> {
+    let target = Target::JavaScript;
+    let mode = Mode::WholeProgram;
+    let build = paths.build_directory_for_target(mode, target);
+    // A whole program build usually writes ECMAScript modules, so the code
+    // of the project is regenerated as CommonJS modules to be bundled.
+    crate::fs::delete_directory(&build)?;
+
+    let built = crate::build::main(
+        paths,
+        Options {
+            mode,
+            target: Some(target),
+            codegen: Codegen::All,
+            compile: Compile::All,
+            warnings_as_errors: false,
+            root_target_support: TargetSupport::Enforced,
+            no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::CommonJs,
+            whole_program: true,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+
+    let package = &built.root_package.config.name;
+    let module = module
+        .map(EcoString::from)
+        .unwrap_or_else(|| package.clone());
+    let _ = built.get_main_function(&module, target)?;
+
+    let bundle =
+        gleam_core::javascript::bundle::bundle(&ProjectIO::new(), &build, package, &module)?;
+    crate::fs::write(&out, &bundle)?;
+    crate::cli::print_exported(package);
+    Ok(())
+}
+
 pub fn package_interface(paths: &ProjectPaths, out: Utf8PathBuf) -> Result<()> {
     // Build the project
     let mut built = crate::build::main(
@@ -154,6 +279,8 @@ pub fn package_interface(paths: &ProjectPaths, out: Utf8PathBuf) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
     )?;
@@ -167,3 +294,40 @@ pub fn package_interface(paths: &ProjectPaths, out: Utf8PathBuf) -> Result<()> {
     crate::fs::write_outputs_under(&[out], paths.root())?;
     Ok(())
 }
+
+/// Writes the interface of each of the project's modules to a file in the
+/// given directory, at the same path as the module's source within `src`.
+/// Placed in a package's `interfaces` directory, the files let the package's
+/// modules be type checked against when they have no source.
+pub fn module_interfaces(paths: &ProjectPaths, out: Utf8PathBuf) -> Result<()> {
+    let built = crate::build::main(
+        paths,
+        Options {
+            mode: Mode::Prod,
+            target: None,
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            warnings_as_errors: false,
+            root_target_support: TargetSupport::Enforced,
+            no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+
+    let package = &built.root_package.config.name;
+    let mut outputs = Vec::new();
+    for interface in built.module_interfaces.values() {
+        if &interface.package != package || interface.origin != Origin::Src {
+            continue;
+        }
+        outputs.push(OutputFile {
+            path: out.join(format!("{}.gleam_interface", interface.name)),
+            content: Content::Binary(gleam_core::metadata::encode_interface_file(interface)?),
+        });
+    }
+    crate::fs::write_outputs_under(&outputs, paths.root())?;
+    crate::cli::print_exported(package);
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use gleam_core::{
     Error, Result, Warning,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options},
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options},
     error::{FileIoAction, FileKind},
     paths::ProjectPaths,
     type_,
@@ -28,6 +28,8 @@ pub fn run(paths: &ProjectPaths) -> Result<()> {
             mode: Mode::Dev,
             target: None,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         build::download_dependencies(paths, cli::Reporter::new())?,
         warnings.clone(),
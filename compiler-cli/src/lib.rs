@@ -54,6 +54,7 @@ extern crate pretty_assertions;
 
 mod add;
 mod beam_compiler;
+mod bench;
 mod build;
 mod build_lock;
 mod cli;
@@ -61,6 +62,7 @@ mod compile_package;
 mod config;
 mod dependencies;
 mod docs;
+mod explain;
 mod export;
 mod fix;
 mod format;
@@ -74,6 +76,8 @@ mod publish;
 mod remove;
 pub mod run;
 mod shell;
+mod timings;
+mod todos;
 
 use config::root_config;
 use dependencies::UseManifest;
@@ -82,12 +86,16 @@ pub use gleam_core::error::{Error, Result};
 
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target},
+    build::{
+        Built, Codegen, Compile, JavaScriptModuleFormat, Mode, NullTelemetry, Options, Runtime,
+        Target,
+    },
     hex::RetirementReason,
     paths::ProjectPaths,
     version::COMPILER_VERSION,
+    warning::{Warning, WarningEmitter},
 };
-use std::str::FromStr;
+use std::{rc::Rc, str::FromStr};
 
 use camino::Utf8PathBuf;
 
@@ -126,6 +134,16 @@ struct TreeOptions {
     invert: Option<String>,
 }
 
+#[derive(Args, Debug, Clone)]
+struct GraphOptions {
+    /// The format to print the graph in
+    #[arg(long, ignore_case = true, default_value = "dot")]
+    format: dependencies::GraphFormat,
+    /// Print the graph even if there are import cycles, highlighting them
+    #[arg(long)]
+    allow_cycles: bool,
+}
+
 #[derive(Parser, Debug)]
 #[command(
     version,
@@ -155,12 +173,41 @@ enum Command {
         /// Don't print progress information
         #[clap(long)]
         no_print_progress: bool,
+
+        #[arg(long, ignore_case = true, default_value = "esm", help = js_module_format_doc())]
+        js_module_format: JavaScriptModuleFormat,
+
+        /// Analyse all the modules of the project together, removing the
+        /// functions and constants that can't be reached from any public
+        /// definition or `main` function. The project is built in production
+        /// mode, in its own build directory
+        #[arg(long)]
+        whole_program: bool,
+
+        /// Print how long parsing, type checking and code generation took for
+        /// each compiled module
+        #[arg(long)]
+        timings: bool,
+
+        /// The format to print the timings in
+        #[arg(long, ignore_case = true, default_value = "text", requires = "timings")]
+        format: timings::TimingsFormat,
     },
 
     /// Type check the project
     Check {
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
+
+        /// List the public functions, constants and types that are not used
+        /// by any other module of the project
+        #[arg(long)]
+        unused_public_api: bool,
+
+        /// Warn about the functions whose return value is discarded by all of
+        /// their calls in the project
+        #[arg(long)]
+        ignored_returns: bool,
     },
 
     /// Publish the project to the Hex package manager
@@ -248,6 +295,32 @@ enum Command {
         arguments: Vec<String>,
     },
 
+    /// Run the functions annotated with `@benchmark` and report their timings
+    Bench {
+        #[arg(short, long, ignore_case = true, help = target_doc())]
+        target: Option<Target>,
+
+        #[arg(long, ignore_case = true, help = runtime_doc())]
+        runtime: Option<Runtime>,
+
+        /// The format to print the timings in
+        #[arg(long, ignore_case = true, default_value = "text")]
+        format: bench::BenchFormat,
+    },
+
+    /// List the `todo` expressions in the project
+    Todos {
+        /// The format to print the `todo` expressions in
+        #[arg(long, ignore_case = true, default_value = "text")]
+        format: todos::TodosFormat,
+    },
+
+    /// Print the explanation of a diagnostic code
+    Explain {
+        /// The code to explain, like `E0001`
+        code: String,
+    },
+
     /// Compile a single Gleam package
     #[command(hide = true)]
     CompilePackage(CompilePackage),
@@ -298,6 +371,13 @@ fn runtime_doc() -> String {
     format!("The runtime to target ({})", Runtime::VARIANTS.join("|"))
 }
 
+fn js_module_format_doc() -> String {
+    format!(
+        "The module format of the generated JavaScript code ({})",
+        JavaScriptModuleFormat::VARIANTS.join("|")
+    )
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ExportTarget {
     /// Precompiled Erlang, suitable for deployment
@@ -308,12 +388,45 @@ pub enum ExportTarget {
     JavascriptPrelude,
     /// The TypeScript prelude module
     TypescriptPrelude,
+    /// Stubs for the JavaScript functions used by the project's externals,
+    /// written next to the modules using them. Existing files are not
+    /// overwritten
+    JavascriptFfiStubs,
+    /// The project and all the modules it uses bundled into a single
+    /// JavaScript file that runs the `main` function of a module
+    JavascriptBundle {
+        #[arg(long = "out", required = true)]
+        /// The path to write the JavaScript file to
+        output: Utf8PathBuf,
+        /// The module to run, defaults to the module with the same name as
+        /// the project
+        #[arg(short, long)]
+        module: Option<String>,
+    },
     /// Information on the modules, functions, and types in the project in JSON format
     PackageInterface {
         #[arg(long = "out", required = true)]
         /// The path to write the JSON file to
         output: Utf8PathBuf,
     },
+    /// The type interface of each of the project's modules, in a versioned
+    /// binary format that other projects can type check against without the
+    /// source of the modules
+    ModuleInterfaces {
+        #[arg(long = "out", required = true)]
+        /// The directory to write the module interface files to
+        output: Utf8PathBuf,
+    },
+    /// Markdown changelog entries for the public definitions added between two
+    /// package interfaces
+    PackageInterfaceChangelog {
+        #[arg(long, required = true)]
+        /// The path of the package interface JSON file of the previous version
+        old: Utf8PathBuf,
+        #[arg(long, required = true)]
+        /// The path of the package interface JSON file of the new version
+        new: Utf8PathBuf,
+    },
 }
 
 #[derive(Args, Debug, Clone)]
@@ -384,6 +497,10 @@ enum Dependencies {
 
     /// Tree of all the dependency packages
     Tree(TreeOptions),
+
+    /// Graph of the imports between the modules of the project and of all
+    /// its dependencies
+    Graph(GraphOptions),
 }
 
 #[derive(Subcommand, Debug)]
@@ -447,6 +564,10 @@ enum Docs {
 
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
+
+        /// Include private and internal definitions in the docs
+        #[arg(long)]
+        private: bool,
     },
 
     /// Publish HTML docs to HexDocs
@@ -503,19 +624,47 @@ fn parse_and_run_command() -> Result<(), Error> {
             target,
             warnings_as_errors,
             no_print_progress,
+            js_module_format,
+            whole_program,
+            timings,
+            format,
         } => {
             let paths = find_project_paths()?;
-            command_build(&paths, target, warnings_as_errors, no_print_progress)
+            let timings_format = timings.then_some(format);
+            command_build(
+                &paths,
+                target,
+                warnings_as_errors,
+                no_print_progress,
+                js_module_format,
+                whole_program,
+                timings_format,
+            )
         }
 
-        Command::Check { target } => {
+        Command::Check {
+            target,
+            unused_public_api,
+            ignored_returns,
+        } => {
             let paths = find_project_paths()?;
-            command_check(&paths, target)
+            command_check(&paths, target, unused_public_api, ignored_returns)
         }
 
-        Command::Docs(Docs::Build { open, target }) => {
+        Command::Docs(Docs::Build {
+            open,
+            target,
+            private,
+        }) => {
             let paths = find_project_paths()?;
-            docs::build(&paths, docs::BuildOptions { open, target })
+            docs::build(
+                &paths,
+                docs::BuildOptions {
+                    open,
+                    target,
+                    private,
+                },
+            )
         }
 
         Command::Docs(Docs::Publish) => {
@@ -556,6 +705,11 @@ fn parse_and_run_command() -> Result<(), Error> {
             dependencies::tree(&paths, options)
         }
 
+        Command::Deps(Dependencies::Graph(options)) => {
+            let paths = find_project_paths()?;
+            dependencies::graph(&paths, options)
+        }
+
         Command::Hex(Hex::Authenticate) => hex::authenticate(),
 
         Command::New(options) => new::create(options, COMPILER_VERSION),
@@ -601,6 +755,22 @@ fn parse_and_run_command() -> Result<(), Error> {
             )
         }
 
+        Command::Bench {
+            target,
+            runtime,
+            format,
+        } => {
+            let paths = find_project_paths()?;
+            bench::command(&paths, target, runtime, format)
+        }
+
+        Command::Todos { format } => {
+            let paths = find_project_paths()?;
+            todos::command(&paths, format)
+        }
+
+        Command::Explain { code } => explain::command(&code),
+
         Command::CompilePackage(opts) => compile_package::command(opts),
 
         Command::Publish { replace, yes } => {
@@ -659,15 +829,35 @@ fn parse_and_run_command() -> Result<(), Error> {
         }
         Command::Export(ExportTarget::JavascriptPrelude) => export::javascript_prelude(),
         Command::Export(ExportTarget::TypescriptPrelude) => export::typescript_prelude(),
+        Command::Export(ExportTarget::JavascriptFfiStubs) => {
+            let paths = find_project_paths()?;
+            export::javascript_ffi_stubs(&paths)
+        }
+        Command::Export(ExportTarget::JavascriptBundle { output, module }) => {
+            let paths = find_project_paths()?;
+            export::javascript_bundle(&paths, output, module)
+        }
         Command::Export(ExportTarget::PackageInterface { output }) => {
             let paths = find_project_paths()?;
             export::package_interface(&paths, output)
         }
+        Command::Export(ExportTarget::ModuleInterfaces { output }) => {
+            let paths = find_project_paths()?;
+            export::module_interfaces(&paths, output)
+        }
+        Command::Export(ExportTarget::PackageInterfaceChangelog { old, new }) => {
+            export::package_interface_changelog(&old, &new)
+        }
     }
 }
 
-fn command_check(paths: &ProjectPaths, target: Option<Target>) -> Result<()> {
-    let _ = build::main(
+fn command_check(
+    paths: &ProjectPaths,
+    target: Option<Target>,
+    unused_public_api: bool,
+    ignored_returns: bool,
+) -> Result<()> {
+    let built = build::main(
         paths,
         Options {
             root_target_support: TargetSupport::Enforced,
@@ -677,36 +867,113 @@ fn command_check(paths: &ProjectPaths, target: Option<Target>) -> Result<()> {
             mode: Mode::Dev,
             target,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         build::download_dependencies(paths, cli::Reporter::new())?,
     )?;
+
+    if unused_public_api {
+        print_unused_public_definitions(paths, &built);
+    }
+    if ignored_returns {
+        emit_always_ignored_returns(&built)?;
+    }
+    Ok(())
+}
+
+fn emit_always_ignored_returns(built: &Built) -> Result<()> {
+    let warnings = WarningEmitter::new(Rc::new(fs::ConsoleWarningEmitter));
+    for function in built.always_ignored_returns() {
+        let Some(module) = built.module_interfaces.get(&function.module) else {
+            continue;
+        };
+        warnings.emit(Warning::AlwaysIgnoredReturn {
+            path: module.src_path.clone(),
+            src: fs::read(&module.src_path)?.into(),
+            function,
+        });
+    }
     Ok(())
 }
 
+fn print_unused_public_definitions(paths: &ProjectPaths, built: &Built) {
+    let unused = built.unused_public_definitions();
+    if unused.is_empty() {
+        println!("No unused public definitions found");
+        return;
+    }
+
+    for definition in unused {
+        let Some(module) = built.module_interfaces.get(&definition.module) else {
+            continue;
+        };
+        let path = module
+            .src_path
+            .strip_prefix(paths.root())
+            .unwrap_or(&module.src_path);
+        let position = module
+            .line_numbers
+            .line_and_column_number(definition.location.start);
+        println!(
+            "{path}:{}:{}: unused public {} `{}.{}`",
+            position.line,
+            position.column,
+            definition.kind.as_str(),
+            definition.module,
+            definition.name
+        );
+    }
+}
+
 fn command_build(
     paths: &ProjectPaths,
     target: Option<Target>,
     warnings_as_errors: bool,
     no_print_progress: bool,
+    javascript_module_format: JavaScriptModuleFormat,
+    whole_program: bool,
+    timings_format: Option<timings::TimingsFormat>,
 ) -> Result<()> {
+    let mode = if whole_program {
+        let config = root_config(paths)?;
+        // Removing unused public definitions looks at all the modules of the
+        // project together, so none of them can be read from the cache.
+        if config.optimisations.remove_unused_public_definitions {
+            fs::delete_directory(&paths.build_directory_for_package(
+                Mode::WholeProgram,
+                target.unwrap_or(config.target),
+                &config.name,
+            ))?;
+        }
+        Mode::WholeProgram
+    } else {
+        Mode::Dev
+    };
+
     let manifest = if no_print_progress {
         build::download_dependencies(paths, NullTelemetry)?
     } else {
         build::download_dependencies(paths, cli::Reporter::new())?
     };
-    let _ = build::main(
+    let built = build::main(
         paths,
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors,
             codegen: Codegen::All,
             compile: Compile::All,
-            mode: Mode::Dev,
+            mode,
             target,
             no_print_progress,
+            javascript_module_format,
+            whole_program,
         },
         manifest,
     )?;
+    if let Some(format) = timings_format {
+        print!("{}", timings::render(&built.timings, format));
+    }
     Ok(())
 }
 
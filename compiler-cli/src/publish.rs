@@ -4,9 +4,9 @@ use flate2::{Compression, write::GzEncoder};
 use gleam_core::{
     Error, Result,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Package, Target},
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Package, Target},
     config::{PackageConfig, SpdxLicense},
-    docs::DocContext,
+    docs::{DocContext, DocVisibility},
     error::{SmallVersion, wrap},
     hex,
     paths::{self, ProjectPaths},
@@ -49,6 +49,7 @@ pub fn command(paths: &ProjectPaths, replace: bool, i_am_sure: bool) -> Result<(
         &config,
         &mut compile_result,
         DocContext::HexPublish,
+        DocVisibility::PublicOnly,
         &cached_modules,
     )?)?;
 
@@ -289,6 +290,8 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
             codegen: Codegen::All,
             compile: Compile::All,
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         build::download_dependencies(paths, cli::Reporter::new())?,
     )?;
@@ -4,7 +4,10 @@ use camino::Utf8PathBuf;
 use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Built, Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target, Telemetry},
+    build::{
+        Built, Codegen, Compile, JavaScriptModuleFormat, Mode, NullTelemetry, Options, Runtime,
+        Target, Telemetry,
+    },
     config::{DenoFlag, PackageConfig},
     error::Error,
     io::{Command, CommandExecutor, Stdio},
@@ -118,6 +121,8 @@ pub fn setup(
             PackageKind::Dependency => TargetSupport::NotEnforced,
         },
         no_print_progress,
+        javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+        whole_program: false,
     };
 
     let built = crate::build::main(paths, options, manifest)?;
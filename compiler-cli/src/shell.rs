@@ -1,6 +1,6 @@
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Target},
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options, Target},
     error::{Error, ShellCommandFailureReason},
     paths::ProjectPaths,
 };
@@ -18,6 +18,8 @@ pub fn command(paths: &ProjectPaths) -> Result<(), Error> {
             mode: Mode::Dev,
             target: Some(Target::Erlang),
             no_print_progress: false,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         },
         crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
     )?;
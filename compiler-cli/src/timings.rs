@@ -0,0 +1,137 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use gleam_core::build::{PhaseTimings, Timings};
+use itertools::Itertools;
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum TimingsFormat {
+    Text,
+    Json,
+}
+
+/// How long each phase took, in microseconds. Totals are the sum of the
+/// rounded durations so they always add up in the printed output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+struct Microseconds {
+    #[serde(rename = "parse_microseconds")]
+    parse: u128,
+    #[serde(rename = "infer_microseconds")]
+    infer: u128,
+    #[serde(rename = "codegen_microseconds")]
+    codegen: u128,
+    #[serde(rename = "total_microseconds")]
+    total: u128,
+}
+
+impl Microseconds {
+    fn new(phases: &PhaseTimings) -> Self {
+        let parse = phases.parse.as_micros();
+        let infer = phases.infer.as_micros();
+        let codegen = phases.codegen.as_micros();
+        Self {
+            parse,
+            infer,
+            codegen,
+            total: parse + infer + codegen,
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.parse += other.parse;
+        self.infer += other.infer;
+        self.codegen += other.codegen;
+        self.total += other.total;
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ModuleReport<'a> {
+    package: &'a str,
+    module: &'a str,
+    #[serde(flatten)]
+    microseconds: Microseconds,
+}
+
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    modules: Vec<ModuleReport<'a>>,
+    total: Microseconds,
+}
+
+fn report(timings: &Timings) -> Report<'_> {
+    let modules = timings
+        .modules()
+        .iter()
+        .map(|timings| ModuleReport {
+            package: &timings.package,
+            module: &timings.module,
+            microseconds: Microseconds::new(&timings.phases),
+        })
+        .sorted_by(|a, b| {
+            b.microseconds
+                .total
+                .cmp(&a.microseconds.total)
+                .then_with(|| a.module.cmp(b.module))
+        })
+        .collect_vec();
+    let mut total = Microseconds::default();
+    for module in &modules {
+        total.add(module.microseconds);
+    }
+    Report { modules, total }
+}
+
+/// Renders how long each phase of compilation took for every compiled
+/// module, slowest module first.
+pub fn render(timings: &Timings, format: TimingsFormat) -> String {
+    let report = report(timings);
+    match format {
+        TimingsFormat::Json => {
+            serde_json::to_string_pretty(&report).expect("timings JSON serialisation")
+        }
+        TimingsFormat::Text => render_text(&report),
+    }
+}
+
+fn render_text(report: &Report<'_>) -> String {
+    let width = report
+        .modules
+        .iter()
+        .map(|module| module.module.len())
+        .max()
+        .unwrap_or(0)
+        .max("Module".len());
+
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Module", "Parse", "Infer", "Codegen", "Total"
+    );
+
+    let mut row = |name: &str, timings: &Microseconds| {
+        let parse = milliseconds(timings.parse);
+        let infer = milliseconds(timings.infer);
+        let codegen = milliseconds(timings.codegen);
+        let total = milliseconds(timings.total);
+        let _ = writeln!(
+            text,
+            "{name:<width$}  {parse:>10}  {infer:>10}  {codegen:>10}  {total:>10}"
+        );
+    };
+    for module in &report.modules {
+        row(module.module, &module.microseconds);
+    }
+    row("Total", &report.total);
+    text
+}
+
+fn milliseconds(microseconds: u128) -> String {
+    format!("{:.2}ms", microseconds as f64 / 1000.)
+}
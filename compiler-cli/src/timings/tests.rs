@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use gleam_core::build::{CompilePhase, Timings};
+
+use super::{TimingsFormat, render};
+
+fn example_timings() -> Timings {
+    let package = "my_package".into();
+    let mut timings = Timings::new();
+    let mut record = |module: &str, phase, microseconds| {
+        let duration = Duration::from_micros(microseconds);
+        timings.record(&package, &module.into(), phase, duration);
+    };
+    record("wibble", CompilePhase::Parse, 1_200);
+    record("wibble", CompilePhase::Infer, 3_400);
+    record("wibble", CompilePhase::Codegen, 800);
+    record("wobble/wubble", CompilePhase::Parse, 300);
+    record("wobble/wubble", CompilePhase::Infer, 12_000);
+    record("wobble/wubble", CompilePhase::Codegen, 1_500);
+    timings
+}
+
+#[test]
+fn text_timings_are_sorted_slowest_first() {
+    insta::assert_snapshot!(render(&example_timings(), TimingsFormat::Text));
+}
+
+#[test]
+fn json_timings_totals_are_the_sum_of_the_modules() {
+    let json: serde_json::Value =
+        serde_json::from_str(&render(&example_timings(), TimingsFormat::Json))
+            .expect("timings JSON");
+    let modules = json
+        .get("modules")
+        .and_then(|modules| modules.as_array())
+        .expect("modules list");
+    let total = json.get("total").expect("total timings");
+    let field = |timings: &serde_json::Value, phase: &str| {
+        timings
+            .get(format!("{phase}_microseconds"))
+            .and_then(|duration| duration.as_u64())
+            .expect("phase duration")
+    };
+
+    for phase in ["parse", "infer", "codegen", "total"] {
+        let sum: u64 = modules.iter().map(|module| field(module, phase)).sum();
+        assert_eq!(field(total, phase), sum);
+    }
+    for module in modules {
+        let phases: u64 = ["parse", "infer", "codegen"]
+            .iter()
+            .map(|phase| field(module, phase))
+            .sum();
+        assert_eq!(field(module, "total"), phases);
+    }
+}
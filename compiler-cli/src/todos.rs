@@ -0,0 +1,161 @@
+use camino::Utf8PathBuf;
+use clap::ValueEnum;
+use ecow::EcoString;
+use gleam_core::{
+    analyse::TargetSupport,
+    build::{Codegen, Compile, JavaScriptModuleFormat, Mode, Options},
+    error::Error,
+    paths::ProjectPaths,
+    todos::{Todo, module_todos},
+};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum TodosFormat {
+    Text,
+    Json,
+}
+
+/// A `todo` expression along with the module it is in.
+#[derive(Debug, serde::Serialize)]
+struct ModuleTodo {
+    module: EcoString,
+    path: Utf8PathBuf,
+    #[serde(flatten)]
+    todo: Todo,
+}
+
+/// Prints all the `todo` expressions in the root package, with their
+/// location and message.
+pub fn command(paths: &ProjectPaths, format: TodosFormat) -> Result<(), Error> {
+    let config = crate::config::root_config(paths)?;
+
+    // Modules that have not changed would be read from the cache, without
+    // their code. The root package is reset so all of its modules are
+    // compiled and can be looked at.
+    crate::fs::delete_directory(&paths.build_directory_for_package(
+        Mode::Dev,
+        config.target,
+        &config.name,
+    ))?;
+
+    let built = crate::build::main(
+        paths,
+        Options {
+            mode: Mode::Dev,
+            target: None,
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            warnings_as_errors: false,
+            root_target_support: TargetSupport::Enforced,
+            no_print_progress: true,
+            javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+
+    let todos = built
+        .root_package
+        .modules
+        .iter()
+        .sorted_by(|one, other| one.input_path.cmp(&other.input_path))
+        .flat_map(|module| {
+            let path = module
+                .input_path
+                .strip_prefix(paths.root())
+                .unwrap_or(&module.input_path)
+                .to_path_buf();
+            module_todos(&module.ast, &module.code)
+                .into_iter()
+                .map(move |todo| ModuleTodo {
+                    module: module.name.clone(),
+                    path: path.clone(),
+                    todo,
+                })
+        })
+        .collect_vec();
+
+    match format {
+        TodosFormat::Text => print!("{}", render_text(&todos)),
+        TodosFormat::Json => println!(
+            "{}",
+            serde_json::to_string(&todos).expect("todos JSON serialisation")
+        ),
+    }
+    Ok(())
+}
+
+fn render_text(todos: &[ModuleTodo]) -> String {
+    if todos.is_empty() {
+        return "No todos found\n".into();
+    }
+
+    let mut text = String::new();
+    for ModuleTodo { path, todo, .. } in todos {
+        let Todo {
+            line,
+            column,
+            message,
+        } = todo;
+        text.push_str(&format!("{path}:{line}:{column}"));
+        if let Some(message) = message {
+            text.push_str(&format!(": {message}"));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[test]
+fn render_todos_as_text() {
+    let todos = [
+        ModuleTodo {
+            module: "wibble".into(),
+            path: "src/wibble.gleam".into(),
+            todo: Todo {
+                line: 2,
+                column: 3,
+                message: Some("make a wibble".into()),
+            },
+        },
+        ModuleTodo {
+            module: "wibble/wobble".into(),
+            path: "src/wibble/wobble.gleam".into(),
+            todo: Todo {
+                line: 10,
+                column: 14,
+                message: None,
+            },
+        },
+    ];
+    assert_eq!(
+        render_text(&todos),
+        "src/wibble.gleam:2:3: make a wibble
+src/wibble/wobble.gleam:10:14
+"
+    );
+}
+
+#[test]
+fn render_todos_as_json() {
+    let todos = [ModuleTodo {
+        module: "wibble".into(),
+        path: "src/wibble.gleam".into(),
+        todo: Todo {
+            line: 2,
+            column: 3,
+            message: Some("make a wibble".into()),
+        },
+    }];
+    assert_eq!(
+        serde_json::to_string(&todos).expect("todos JSON serialisation"),
+        r#"[{"module":"wibble","path":"src/wibble.gleam","line":2,"column":3,"message":"make a wibble"}]"#
+    );
+}
+
+#[test]
+fn render_no_todos_as_text() {
+    assert_eq!(render_text(&[]), "No todos found\n");
+}
@@ -716,11 +716,35 @@ pub mod module {
     pub fn has_references(&self) -> bool {
       !self.reader.get_pointer_field(11).is_null()
     }
+    #[inline]
+    pub fn get_benchmarks(self) -> ::capnp::Result<::capnp::text_list::Reader<'a>> {
+      ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(12), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn has_benchmarks(&self) -> bool {
+      !self.reader.get_pointer_field(12).is_null()
+    }
+    #[inline]
+    pub fn get_disabled_values(self) -> ::capnp::Result<::capnp::struct_list::Reader<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>>> {
+      ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(13), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn has_disabled_values(&self) -> bool {
+      !self.reader.get_pointer_field(13).is_null()
+    }
+    #[inline]
+    pub fn get_disabled_types(self) -> ::capnp::Result<::capnp::struct_list::Reader<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>>> {
+      ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(14), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn has_disabled_types(&self) -> bool {
+      !self.reader.get_pointer_field(14).is_null()
+    }
   }
 
   pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
   impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 12 };
+    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 15 };
   }
   impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
     const TYPE_ID: u64 = _private::TYPE_ID;
@@ -978,6 +1002,54 @@ pub mod module {
     pub fn has_references(&self) -> bool {
       !self.builder.is_pointer_field_null(11)
     }
+    #[inline]
+    pub fn get_benchmarks(self) -> ::capnp::Result<::capnp::text_list::Builder<'a>> {
+      ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(12), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn set_benchmarks(&mut self, value: impl ::capnp::traits::SetterInput<::capnp::text_list::Owned>) -> ::capnp::Result<()> {
+      ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(12), value, false)
+    }
+    #[inline]
+    pub fn init_benchmarks(self, size: u32) -> ::capnp::text_list::Builder<'a> {
+      ::capnp::traits::FromPointerBuilder::init_pointer(self.builder.get_pointer_field(12), size)
+    }
+    #[inline]
+    pub fn has_benchmarks(&self) -> bool {
+      !self.builder.is_pointer_field_null(12)
+    }
+    #[inline]
+    pub fn get_disabled_values(self) -> ::capnp::Result<::capnp::struct_list::Builder<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>>> {
+      ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(13), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn set_disabled_values(&mut self, value: ::capnp::struct_list::Reader<'_,crate::schema_capnp::property::Owned<::capnp::text::Owned>>) -> ::capnp::Result<()> {
+      ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(13), value, false)
+    }
+    #[inline]
+    pub fn init_disabled_values(self, size: u32) -> ::capnp::struct_list::Builder<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>> {
+      ::capnp::traits::FromPointerBuilder::init_pointer(self.builder.get_pointer_field(13), size)
+    }
+    #[inline]
+    pub fn has_disabled_values(&self) -> bool {
+      !self.builder.is_pointer_field_null(13)
+    }
+    #[inline]
+    pub fn get_disabled_types(self) -> ::capnp::Result<::capnp::struct_list::Builder<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>>> {
+      ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(14), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn set_disabled_types(&mut self, value: ::capnp::struct_list::Reader<'_,crate::schema_capnp::property::Owned<::capnp::text::Owned>>) -> ::capnp::Result<()> {
+      ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(14), value, false)
+    }
+    #[inline]
+    pub fn init_disabled_types(self, size: u32) -> ::capnp::struct_list::Builder<'a,crate::schema_capnp::property::Owned<::capnp::text::Owned>> {
+      ::capnp::traits::FromPointerBuilder::init_pointer(self.builder.get_pointer_field(14), size)
+    }
+    #[inline]
+    pub fn has_disabled_types(&self) -> bool {
+      !self.builder.is_pointer_field_null(14)
+    }
   }
 
   pub struct Pipeline { _typeless: ::capnp::any_pointer::Pipeline }
@@ -998,122 +1070,143 @@ pub mod module {
     }
   }
   mod _private {
-    pub static ENCODED_NODE: [::capnp::Word; 321] = [
+    pub static ENCODED_NODE: [::capnp::Word; 405] = [
       ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
       ::capnp::word(129, 5, 219, 80, 68, 149, 82, 154),
       ::capnp::word(13, 0, 0, 0, 1, 0, 1, 0),
       ::capnp::word(190, 237, 188, 253, 156, 169, 51, 181),
-      ::capnp::word(12, 0, 7, 0, 0, 0, 0, 0),
+      ::capnp::word(15, 0, 7, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(21, 0, 0, 0, 162, 0, 0, 0),
       ::capnp::word(29, 0, 0, 0, 7, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(25, 0, 0, 0, 23, 3, 0, 0),
+      ::capnp::word(25, 0, 0, 0, 191, 3, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(115, 99, 104, 101, 109, 97, 46, 99),
       ::capnp::word(97, 112, 110, 112, 58, 77, 111, 100),
       ::capnp::word(117, 108, 101, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 1, 0, 1, 0),
-      ::capnp::word(56, 0, 0, 0, 3, 0, 4, 0),
+      ::capnp::word(68, 0, 0, 0, 3, 0, 4, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(121, 1, 0, 0, 42, 0, 0, 0),
+      ::capnp::word(205, 1, 0, 0, 42, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(116, 1, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(128, 1, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(200, 1, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(212, 1, 0, 0, 2, 0, 1, 0),
       ::capnp::word(1, 0, 0, 0, 1, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 1, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(125, 1, 0, 0, 50, 0, 0, 0),
+      ::capnp::word(209, 1, 0, 0, 50, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(120, 1, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(196, 1, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(204, 1, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(24, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(2, 0, 0, 0, 2, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 2, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(193, 1, 0, 0, 58, 0, 0, 0),
+      ::capnp::word(21, 2, 0, 0, 58, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(188, 1, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(8, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(16, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(92, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(3, 0, 0, 0, 3, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 3, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(5, 2, 0, 0, 82, 0, 0, 0),
+      ::capnp::word(89, 2, 0, 0, 82, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(4, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(80, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(88, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(164, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(4, 0, 0, 0, 4, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 4, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(77, 2, 0, 0, 66, 0, 0, 0),
+      ::capnp::word(161, 2, 0, 0, 66, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(72, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(84, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(156, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(168, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(5, 0, 0, 0, 5, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 5, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(81, 2, 0, 0, 146, 0, 0, 0),
+      ::capnp::word(165, 2, 0, 0, 146, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(84, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(160, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(168, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(244, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(6, 0, 0, 0, 6, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 6, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(157, 2, 0, 0, 98, 0, 0, 0),
+      ::capnp::word(241, 2, 0, 0, 98, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(156, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(168, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(240, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(252, 2, 0, 0, 2, 0, 1, 0),
       ::capnp::word(7, 0, 0, 0, 7, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 7, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(165, 2, 0, 0, 66, 0, 0, 0),
+      ::capnp::word(249, 2, 0, 0, 66, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(160, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(172, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(244, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(0, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(8, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 8, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(169, 2, 0, 0, 90, 0, 0, 0),
+      ::capnp::word(253, 2, 0, 0, 90, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(168, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(180, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(252, 2, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(8, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(9, 0, 0, 0, 8, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 9, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(177, 2, 0, 0, 130, 0, 0, 0),
+      ::capnp::word(5, 3, 0, 0, 130, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(176, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(188, 2, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(4, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(16, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(10, 0, 0, 0, 9, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 10, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(185, 2, 0, 0, 98, 0, 0, 0),
+      ::capnp::word(13, 3, 0, 0, 98, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(184, 2, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(4, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(12, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(88, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(11, 0, 0, 0, 10, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 11, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(1, 3, 0, 0, 114, 0, 0, 0),
+      ::capnp::word(85, 3, 0, 0, 114, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(0, 3, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(28, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(84, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(112, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(12, 0, 0, 0, 1, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 12, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(25, 3, 0, 0, 106, 0, 0, 0),
+      ::capnp::word(109, 3, 0, 0, 106, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(24, 3, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(36, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(108, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(120, 3, 0, 0, 2, 0, 1, 0),
       ::capnp::word(13, 0, 0, 0, 11, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 13, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(33, 3, 0, 0, 90, 0, 0, 0),
+      ::capnp::word(117, 3, 0, 0, 90, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(116, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(128, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(14, 0, 0, 0, 12, 0, 0, 0),
+      ::capnp::word(0, 0, 1, 0, 14, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(125, 3, 0, 0, 90, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(124, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(152, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(15, 0, 0, 0, 13, 0, 0, 0),
+      ::capnp::word(0, 0, 1, 0, 15, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(149, 3, 0, 0, 122, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(32, 3, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(44, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(148, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(224, 3, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(16, 0, 0, 0, 14, 0, 0, 0),
+      ::capnp::word(0, 0, 1, 0, 16, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(221, 3, 0, 0, 114, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(220, 3, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(40, 4, 0, 0, 2, 0, 1, 0),
       ::capnp::word(110, 97, 109, 101, 0, 0, 0, 0),
       ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
@@ -1320,6 +1413,69 @@ pub mod module {
       ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(98, 101, 110, 99, 104, 109, 97, 114),
+      ::capnp::word(107, 115, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(100, 105, 115, 97, 98, 108, 101, 100),
+      ::capnp::word(86, 97, 108, 117, 101, 115, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(106, 29, 126, 201, 93, 118, 154, 200),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 1, 0),
+      ::capnp::word(1, 0, 0, 0, 31, 0, 0, 0),
+      ::capnp::word(4, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(106, 29, 126, 201, 93, 118, 154, 200),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(1, 0, 0, 0, 23, 0, 0, 0),
+      ::capnp::word(4, 0, 0, 0, 1, 0, 1, 0),
+      ::capnp::word(1, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(100, 105, 115, 97, 98, 108, 101, 100),
+      ::capnp::word(84, 121, 112, 101, 115, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(106, 29, 126, 201, 93, 118, 154, 200),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 1, 0),
+      ::capnp::word(1, 0, 0, 0, 31, 0, 0, 0),
+      ::capnp::word(4, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(106, 29, 126, 201, 93, 118, 154, 200),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(1, 0, 0, 0, 23, 0, 0, 0),
+      ::capnp::word(4, 0, 0, 0, 1, 0, 1, 0),
+      ::capnp::word(1, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
     ];
     pub fn get_field_types(index: u16) -> ::capnp::introspect::Type {
       match index {
@@ -1337,6 +1493,9 @@ pub mod module {
         11 => <::capnp::text_list::Owned as ::capnp::introspect::Introspect>::introspect(),
         12 => <bool as ::capnp::introspect::Introspect>::introspect(),
         13 => <crate::schema_capnp::references::Owned as ::capnp::introspect::Introspect>::introspect(),
+        14 => <::capnp::text_list::Owned as ::capnp::introspect::Introspect>::introspect(),
+        15 => <::capnp::struct_list::Owned<crate::schema_capnp::property::Owned<::capnp::text::Owned>> as ::capnp::introspect::Introspect>::introspect(),
+        16 => <::capnp::struct_list::Owned<crate::schema_capnp::property::Owned<::capnp::text::Owned>> as ::capnp::introspect::Introspect>::introspect(),
         _ => panic!("invalid field index {}", index),
       }
     }
@@ -1349,9 +1508,9 @@ pub mod module {
       members_by_discriminant: MEMBERS_BY_DISCRIMINANT,
       members_by_name: MEMBERS_BY_NAME,
     };
-    pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5,6,7,8,9,10,11,12,13];
+    pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16];
     pub static MEMBERS_BY_DISCRIMINANT : &[u16] = &[];
-    pub static MEMBERS_BY_NAME : &[u16] = &[3,12,11,8,6,0,4,13,9,7,10,1,5,2];
+    pub static MEMBERS_BY_NAME : &[u16] = &[3,14,12,16,15,11,8,6,0,4,13,9,7,10,1,5,2];
     pub const TYPE_ID: u64 = 0x9a52_9544_50db_0581;
   }
 }
@@ -1441,11 +1600,19 @@ pub mod references {
     pub fn has_type_references(&self) -> bool {
       !self.reader.get_pointer_field(2).is_null()
     }
+    #[inline]
+    pub fn get_discarded_value_references(self) -> ::capnp::Result<::capnp::struct_list::Reader<'a,crate::schema_capnp::reference_map::Owned>> {
+      ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(3), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn has_discarded_value_references(&self) -> bool {
+      !self.reader.get_pointer_field(3).is_null()
+    }
   }
 
   pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
   impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 0, pointers: 3 };
+    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 0, pointers: 4 };
   }
   impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
     const TYPE_ID: u64 = _private::TYPE_ID;
@@ -1543,6 +1710,22 @@ pub mod references {
     pub fn has_type_references(&self) -> bool {
       !self.builder.is_pointer_field_null(2)
     }
+    #[inline]
+    pub fn get_discarded_value_references(self) -> ::capnp::Result<::capnp::struct_list::Builder<'a,crate::schema_capnp::reference_map::Owned>> {
+      ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(3), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn set_discarded_value_references(&mut self, value: ::capnp::struct_list::Reader<'_,crate::schema_capnp::reference_map::Owned>) -> ::capnp::Result<()> {
+      ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(3), value, false)
+    }
+    #[inline]
+    pub fn init_discarded_value_references(self, size: u32) -> ::capnp::struct_list::Builder<'a,crate::schema_capnp::reference_map::Owned> {
+      ::capnp::traits::FromPointerBuilder::init_pointer(self.builder.get_pointer_field(3), size)
+    }
+    #[inline]
+    pub fn has_discarded_value_references(&self) -> bool {
+      !self.builder.is_pointer_field_null(3)
+    }
   }
 
   pub struct Pipeline { _typeless: ::capnp::any_pointer::Pipeline }
@@ -1554,45 +1737,52 @@ pub mod references {
   impl Pipeline  {
   }
   mod _private {
-    pub static ENCODED_NODE: [::capnp::Word; 77] = [
+    pub static ENCODED_NODE: [::capnp::Word; 100] = [
       ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
       ::capnp::word(101, 145, 82, 27, 227, 203, 200, 215),
       ::capnp::word(13, 0, 0, 0, 1, 0, 0, 0),
       ::capnp::word(190, 237, 188, 253, 156, 169, 51, 181),
-      ::capnp::word(3, 0, 7, 0, 0, 0, 0, 0),
+      ::capnp::word(4, 0, 7, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(21, 0, 0, 0, 194, 0, 0, 0),
       ::capnp::word(29, 0, 0, 0, 7, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(25, 0, 0, 0, 175, 0, 0, 0),
+      ::capnp::word(25, 0, 0, 0, 231, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(115, 99, 104, 101, 109, 97, 46, 99),
       ::capnp::word(97, 112, 110, 112, 58, 82, 101, 102),
       ::capnp::word(101, 114, 101, 110, 99, 101, 115, 0),
       ::capnp::word(0, 0, 0, 0, 1, 0, 1, 0),
-      ::capnp::word(12, 0, 0, 0, 3, 0, 4, 0),
+      ::capnp::word(16, 0, 0, 0, 3, 0, 4, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(69, 0, 0, 0, 130, 0, 0, 0),
+      ::capnp::word(97, 0, 0, 0, 130, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(68, 0, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(96, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(96, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(124, 0, 0, 0, 2, 0, 1, 0),
       ::capnp::word(1, 0, 0, 0, 1, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 1, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(93, 0, 0, 0, 130, 0, 0, 0),
+      ::capnp::word(121, 0, 0, 0, 130, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(92, 0, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(120, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(120, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(148, 0, 0, 0, 2, 0, 1, 0),
       ::capnp::word(2, 0, 0, 0, 2, 0, 0, 0),
       ::capnp::word(0, 0, 1, 0, 2, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(117, 0, 0, 0, 122, 0, 0, 0),
+      ::capnp::word(145, 0, 0, 0, 122, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-      ::capnp::word(116, 0, 0, 0, 3, 0, 1, 0),
-      ::capnp::word(144, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(144, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(172, 0, 0, 0, 2, 0, 1, 0),
+      ::capnp::word(3, 0, 0, 0, 3, 0, 0, 0),
+      ::capnp::word(0, 0, 1, 0, 3, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(169, 0, 0, 0, 202, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(176, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(208, 0, 0, 0, 2, 0, 1, 0),
       ::capnp::word(105, 109, 112, 111, 114, 116, 101, 100),
       ::capnp::word(77, 111, 100, 117, 108, 101, 115, 0),
       ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
@@ -1632,12 +1822,29 @@ pub mod references {
       ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(100, 105, 115, 99, 97, 114, 100, 101),
+      ::capnp::word(100, 86, 97, 108, 117, 101, 82, 101),
+      ::capnp::word(102, 101, 114, 101, 110, 99, 101, 115),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 3, 0, 1, 0),
+      ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(174, 202, 45, 66, 25, 97, 38, 203),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 1, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(14, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+      ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
     ];
     pub fn get_field_types(index: u16) -> ::capnp::introspect::Type {
       match index {
         0 => <::capnp::text_list::Owned as ::capnp::introspect::Introspect>::introspect(),
         1 => <::capnp::struct_list::Owned<crate::schema_capnp::reference_map::Owned> as ::capnp::introspect::Introspect>::introspect(),
         2 => <::capnp::struct_list::Owned<crate::schema_capnp::reference_map::Owned> as ::capnp::introspect::Introspect>::introspect(),
+        3 => <::capnp::struct_list::Owned<crate::schema_capnp::reference_map::Owned> as ::capnp::introspect::Introspect>::introspect(),
         _ => panic!("invalid field index {}", index),
       }
     }
@@ -1650,9 +1857,9 @@ pub mod references {
       members_by_discriminant: MEMBERS_BY_DISCRIMINANT,
       members_by_name: MEMBERS_BY_NAME,
     };
-    pub static NONUNION_MEMBERS : &[u16] = &[0,1,2];
+    pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3];
     pub static MEMBERS_BY_DISCRIMINANT : &[u16] = &[];
-    pub static MEMBERS_BY_NAME : &[u16] = &[0,2,1];
+    pub static MEMBERS_BY_NAME : &[u16] = &[3,0,2,1];
     pub const TYPE_ID: u64 = 0xd7c8_cbe3_1b52_9165;
   }
 }
@@ -8019,7 +8226,7 @@ pub mod value_constructor_variant {
 
   pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
   impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 8 };
+    const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 2, pointers: 10 };
   }
   impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
     const TYPE_ID: u64 = _private::TYPE_ID;
@@ -8078,6 +8285,7 @@ pub mod value_constructor_variant {
       self.builder.reborrow().get_pointer_field(3).clear();
       self.builder.reborrow().get_pointer_field(4).clear();
       self.builder.reborrow().get_pointer_field(5).clear();
+      self.builder.reborrow().get_pointer_field(8).clear();
       self.builder.into()
     }
     #[inline]
@@ -8092,6 +8300,8 @@ pub mod value_constructor_variant {
       self.builder.reborrow().get_pointer_field(5).clear();
       self.builder.reborrow().get_pointer_field(6).clear();
       self.builder.reborrow().get_pointer_field(7).clear();
+      self.builder.set_bool_field(64, false);
+      self.builder.reborrow().get_pointer_field(9).clear();
       self.builder.into()
     }
     #[inline]
@@ -8142,9 +8352,9 @@ pub mod value_constructor_variant {
     pub static ENCODED_NODE: [::capnp::Word; 45] = [
       ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
       ::capnp::word(26, 168, 208, 43, 233, 121, 76, 225),
-      ::capnp::word(13, 0, 0, 0, 1, 0, 1, 0),
+      ::capnp::word(13, 0, 0, 0, 1, 0, 2, 0),
       ::capnp::word(190, 237, 188, 253, 156, 169, 51, 181),
-      ::capnp::word(8, 0, 7, 0, 0, 0, 3, 0),
+      ::capnp::word(10, 0, 7, 0, 0, 0, 3, 0),
       ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ::capnp::word(21, 0, 0, 0, 42, 1, 0, 0),
       ::capnp::word(37, 0, 0, 0, 7, 0, 0, 0),
@@ -8325,11 +8535,19 @@ pub mod value_constructor_variant {
       pub fn has_name(&self) -> bool {
         !self.reader.get_pointer_field(5).is_null()
       }
+      #[inline]
+      pub fn get_since(self) -> ::capnp::Result<::capnp::text::Reader<'a>> {
+        ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(8), ::core::option::Option::None)
+      }
+      #[inline]
+      pub fn has_since(&self) -> bool {
+        !self.reader.get_pointer_field(8).is_null()
+      }
     }
 
     pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
     impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 8 };
+      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 2, pointers: 10 };
     }
     impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
       const TYPE_ID: u64 = _private::TYPE_ID;
@@ -8475,6 +8693,22 @@ pub mod value_constructor_variant {
       pub fn has_name(&self) -> bool {
         !self.builder.is_pointer_field_null(5)
       }
+      #[inline]
+      pub fn get_since(self) -> ::capnp::Result<::capnp::text::Builder<'a>> {
+        ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(8), ::core::option::Option::None)
+      }
+      #[inline]
+      pub fn set_since(&mut self, value: impl ::capnp::traits::SetterInput<::capnp::text::Owned>)  {
+        ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(8), value, false).unwrap()
+      }
+      #[inline]
+      pub fn init_since(self, size: u32) -> ::capnp::text::Builder<'a> {
+        self.builder.get_pointer_field(8).init_text(size)
+      }
+      #[inline]
+      pub fn has_since(&self) -> bool {
+        !self.builder.is_pointer_field_null(8)
+      }
     }
 
     pub struct Pipeline { _typeless: ::capnp::any_pointer::Pipeline }
@@ -8495,17 +8729,17 @@ pub mod value_constructor_variant {
       }
     }
     mod _private {
-      pub static ENCODED_NODE: [::capnp::Word; 113] = [
+      pub static ENCODED_NODE: [::capnp::Word; 129] = [
         ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
         ::capnp::word(208, 251, 150, 129, 105, 157, 121, 149),
-        ::capnp::word(37, 0, 0, 0, 1, 0, 1, 0),
+        ::capnp::word(37, 0, 0, 0, 1, 0, 2, 0),
         ::capnp::word(26, 168, 208, 43, 233, 121, 76, 225),
-        ::capnp::word(8, 0, 7, 0, 1, 0, 0, 0),
+        ::capnp::word(10, 0, 7, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(21, 0, 0, 0, 162, 1, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(37, 0, 0, 0, 87, 1, 0, 0),
+        ::capnp::word(37, 0, 0, 0, 143, 1, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(115, 99, 104, 101, 109, 97, 46, 99),
@@ -8515,49 +8749,56 @@ pub mod value_constructor_variant {
         ::capnp::word(105, 97, 110, 116, 46, 109, 111, 100),
         ::capnp::word(117, 108, 101, 67, 111, 110, 115, 116),
         ::capnp::word(97, 110, 116, 0, 0, 0, 0, 0),
-        ::capnp::word(24, 0, 0, 0, 3, 0, 4, 0),
+        ::capnp::word(28, 0, 0, 0, 3, 0, 4, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(153, 0, 0, 0, 66, 0, 0, 0),
+        ::capnp::word(181, 0, 0, 0, 66, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(148, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(160, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(176, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(188, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(1, 0, 0, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(157, 0, 0, 0, 74, 0, 0, 0),
+        ::capnp::word(185, 0, 0, 0, 74, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(156, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(168, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(184, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(196, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(2, 0, 0, 0, 2, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 2, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(165, 0, 0, 0, 58, 0, 0, 0),
+        ::capnp::word(193, 0, 0, 0, 58, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(160, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(172, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(188, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(200, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(4, 0, 0, 0, 3, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 14, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(169, 0, 0, 0, 114, 0, 0, 0),
+        ::capnp::word(197, 0, 0, 0, 114, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(168, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(180, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(196, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(208, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(5, 0, 0, 0, 4, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 19, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(177, 0, 0, 0, 130, 0, 0, 0),
+        ::capnp::word(205, 0, 0, 0, 130, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(176, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(188, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(204, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(216, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(3, 0, 0, 0, 5, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 22, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(185, 0, 0, 0, 42, 0, 0, 0),
+        ::capnp::word(213, 0, 0, 0, 42, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(208, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(220, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(6, 0, 0, 0, 8, 0, 0, 0),
+        ::capnp::word(0, 0, 1, 0, 24, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(180, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(192, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(217, 0, 0, 0, 50, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(212, 0, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(224, 0, 0, 0, 2, 0, 1, 0),
         ::capnp::word(108, 105, 116, 101, 114, 97, 108, 0),
         ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(106, 82, 109, 230, 111, 220, 234, 230),
@@ -8609,6 +8850,15 @@ pub mod value_constructor_variant {
         ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(115, 105, 110, 99, 101, 0, 0, 0),
+        ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(1, 0, 0, 0, 10, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ];
       pub fn get_field_types(index: u16) -> ::capnp::introspect::Type {
         match index {
@@ -8618,6 +8868,7 @@ pub mod value_constructor_variant {
           3 => <::capnp::text::Owned as ::capnp::introspect::Introspect>::introspect(),
           4 => <crate::schema_capnp::implementations::Owned as ::capnp::introspect::Introspect>::introspect(),
           5 => <::capnp::text::Owned as ::capnp::introspect::Introspect>::introspect(),
+          6 => <::capnp::text::Owned as ::capnp::introspect::Introspect>::introspect(),
           _ => panic!("invalid field index {}", index),
         }
       }
@@ -8630,9 +8881,9 @@ pub mod value_constructor_variant {
         members_by_discriminant: MEMBERS_BY_DISCRIMINANT,
         members_by_name: MEMBERS_BY_NAME,
       };
-      pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5];
+      pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5,6];
       pub static MEMBERS_BY_DISCRIMINANT : &[u16] = &[];
-      pub static MEMBERS_BY_NAME : &[u16] = &[3,4,0,1,2,5];
+      pub static MEMBERS_BY_NAME : &[u16] = &[3,4,0,1,2,5,6];
       pub const TYPE_ID: u64 = 0x9579_9d69_8196_fbd0;
     }
   }
@@ -8766,11 +9017,23 @@ pub mod value_constructor_variant {
       pub fn has_external_javascript(&self) -> bool {
         !self.reader.get_pointer_field(7).is_null()
       }
+      #[inline]
+      pub fn get_is_pure(self) -> bool {
+        self.reader.get_bool_field(64)
+      }
+      #[inline]
+      pub fn get_since(self) -> ::capnp::Result<::capnp::text::Reader<'a>> {
+        ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(9), ::core::option::Option::None)
+      }
+      #[inline]
+      pub fn has_since(&self) -> bool {
+        !self.reader.get_pointer_field(9).is_null()
+      }
     }
 
     pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
     impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 8 };
+      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 2, pointers: 10 };
     }
     impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
       const TYPE_ID: u64 = _private::TYPE_ID;
@@ -8956,6 +9219,30 @@ pub mod value_constructor_variant {
       pub fn has_external_javascript(&self) -> bool {
         !self.builder.is_pointer_field_null(7)
       }
+      #[inline]
+      pub fn get_is_pure(self) -> bool {
+        self.builder.get_bool_field(64)
+      }
+      #[inline]
+      pub fn set_is_pure(&mut self, value: bool)  {
+        self.builder.set_bool_field(64, value);
+      }
+      #[inline]
+      pub fn get_since(self) -> ::capnp::Result<::capnp::text::Builder<'a>> {
+        ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(9), ::core::option::Option::None)
+      }
+      #[inline]
+      pub fn set_since(&mut self, value: impl ::capnp::traits::SetterInput<::capnp::text::Owned>)  {
+        ::capnp::traits::SetterInput::set_pointer_builder(self.builder.reborrow().get_pointer_field(9), value, false).unwrap()
+      }
+      #[inline]
+      pub fn init_since(self, size: u32) -> ::capnp::text::Builder<'a> {
+        self.builder.get_pointer_field(9).init_text(size)
+      }
+      #[inline]
+      pub fn has_since(&self) -> bool {
+        !self.builder.is_pointer_field_null(9)
+      }
     }
 
     pub struct Pipeline { _typeless: ::capnp::any_pointer::Pipeline }
@@ -8982,17 +9269,17 @@ pub mod value_constructor_variant {
       }
     }
     mod _private {
-      pub static ENCODED_NODE: [::capnp::Word; 197] = [
+      pub static ENCODED_NODE: [::capnp::Word; 228] = [
         ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
         ::capnp::word(121, 55, 113, 152, 197, 21, 166, 174),
-        ::capnp::word(37, 0, 0, 0, 1, 0, 1, 0),
+        ::capnp::word(37, 0, 0, 0, 1, 0, 2, 0),
         ::capnp::word(26, 168, 208, 43, 233, 121, 76, 225),
-        ::capnp::word(8, 0, 7, 0, 1, 0, 0, 0),
+        ::capnp::word(10, 0, 7, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(21, 0, 0, 0, 114, 1, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(33, 0, 0, 0, 255, 1, 0, 0),
+        ::capnp::word(33, 0, 0, 0, 111, 2, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(115, 99, 104, 101, 109, 97, 46, 99),
@@ -9001,70 +9288,84 @@ pub mod value_constructor_variant {
         ::capnp::word(117, 99, 116, 111, 114, 86, 97, 114),
         ::capnp::word(105, 97, 110, 116, 46, 109, 111, 100),
         ::capnp::word(117, 108, 101, 70, 110, 0, 0, 0),
-        ::capnp::word(36, 0, 0, 0, 3, 0, 4, 0),
+        ::capnp::word(44, 0, 0, 0, 3, 0, 4, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 3, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(237, 0, 0, 0, 42, 0, 0, 0),
+        ::capnp::word(37, 1, 0, 0, 42, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(232, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(244, 0, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(32, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(44, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(1, 0, 0, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 4, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(241, 0, 0, 0, 74, 0, 0, 0),
+        ::capnp::word(41, 1, 0, 0, 74, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(240, 0, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(44, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(40, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(100, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(2, 0, 0, 0, 2, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 5, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(41, 1, 0, 0, 58, 0, 0, 0),
+        ::capnp::word(97, 1, 0, 0, 58, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(36, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(48, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(92, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(104, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(3, 0, 0, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 6, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(45, 1, 0, 0, 50, 0, 0, 0),
+        ::capnp::word(101, 1, 0, 0, 50, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(40, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(52, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(96, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(108, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(4, 0, 0, 0, 3, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 7, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(49, 1, 0, 0, 74, 0, 0, 0),
+        ::capnp::word(105, 1, 0, 0, 74, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(48, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(60, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(104, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(116, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(5, 0, 0, 0, 4, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 15, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(57, 1, 0, 0, 114, 0, 0, 0),
+        ::capnp::word(113, 1, 0, 0, 114, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(56, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(68, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(112, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(124, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(6, 0, 0, 0, 5, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 18, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(65, 1, 0, 0, 130, 0, 0, 0),
+        ::capnp::word(121, 1, 0, 0, 130, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(64, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(76, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(120, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(132, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(7, 0, 0, 0, 6, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 20, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(73, 1, 0, 0, 122, 0, 0, 0),
+        ::capnp::word(129, 1, 0, 0, 122, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(72, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(132, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(128, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(188, 1, 0, 0, 2, 0, 1, 0),
         ::capnp::word(8, 0, 0, 0, 7, 0, 0, 0),
         ::capnp::word(0, 0, 1, 0, 21, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(129, 1, 0, 0, 154, 0, 0, 0),
+        ::capnp::word(185, 1, 0, 0, 154, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(188, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(248, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(9, 0, 0, 0, 64, 0, 0, 0),
+        ::capnp::word(0, 0, 1, 0, 23, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(245, 1, 0, 0, 58, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(240, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(252, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(10, 0, 0, 0, 9, 0, 0, 0),
+        ::capnp::word(0, 0, 1, 0, 25, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
-        ::capnp::word(132, 1, 0, 0, 3, 0, 1, 0),
-        ::capnp::word(192, 1, 0, 0, 2, 0, 1, 0),
+        ::capnp::word(249, 1, 0, 0, 50, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(244, 1, 0, 0, 3, 0, 1, 0),
+        ::capnp::word(0, 2, 0, 0, 2, 0, 1, 0),
         ::capnp::word(110, 97, 109, 101, 0, 0, 0, 0),
         ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
@@ -9180,6 +9481,23 @@ pub mod value_constructor_variant {
         ::capnp::word(16, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(105, 115, 80, 117, 114, 101, 0, 0),
+        ::capnp::word(1, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(1, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(115, 105, 110, 99, 101, 0, 0, 0),
+        ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(12, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
+        ::capnp::word(1, 0, 0, 0, 10, 0, 0, 0),
+        ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
       ];
       pub fn get_field_types(index: u16) -> ::capnp::introspect::Type {
         match index {
@@ -9192,6 +9510,8 @@ pub mod value_constructor_variant {
           6 => <crate::schema_capnp::implementations::Owned as ::capnp::introspect::Introspect>::introspect(),
           7 => <crate::schema_capnp::option::Owned<crate::schema_capnp::external::Owned> as ::capnp::introspect::Introspect>::introspect(),
           8 => <crate::schema_capnp::option::Owned<crate::schema_capnp::external::Owned> as ::capnp::introspect::Introspect>::introspect(),
+          9 => <bool as ::capnp::introspect::Introspect>::introspect(),
+          10 => <::capnp::text::Owned as ::capnp::introspect::Introspect>::introspect(),
           _ => panic!("invalid field index {}", index),
         }
       }
@@ -9204,9 +9524,9 @@ pub mod value_constructor_variant {
         members_by_discriminant: MEMBERS_BY_DISCRIMINANT,
         members_by_name: MEMBERS_BY_NAME,
       };
-      pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5,6,7,8];
+      pub static NONUNION_MEMBERS : &[u16] = &[0,1,2,3,4,5,6,7,8,9,10];
       pub static MEMBERS_BY_DISCRIMINANT : &[u16] = &[];
-      pub static MEMBERS_BY_NAME : &[u16] = &[3,5,7,8,1,6,4,2,0];
+      pub static MEMBERS_BY_NAME : &[u16] = &[3,5,7,8,1,6,9,4,2,0,10];
       pub const TYPE_ID: u64 = 0xaea6_15c5_9871_3779;
     }
   }
@@ -9328,7 +9648,7 @@ pub mod value_constructor_variant {
 
     pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
     impl <> ::capnp::traits::HasStructSize for Builder<'_,>  {
-      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 1, pointers: 8 };
+      const STRUCT_SIZE: ::capnp::private::layout::StructSize = ::capnp::private::layout::StructSize { data: 2, pointers: 10 };
     }
     impl <> ::capnp::traits::HasTypeId for Builder<'_,>  {
       const TYPE_ID: u64 = _private::TYPE_ID;
@@ -9502,9 +9822,9 @@ pub mod value_constructor_variant {
       pub static ENCODED_NODE: [::capnp::Word; 158] = [
         ::capnp::word(0, 0, 0, 0, 5, 0, 6, 0),
         ::capnp::word(213, 61, 35, 233, 38, 21, 11, 240),
-        ::capnp::word(37, 0, 0, 0, 1, 0, 1, 0),
+        ::capnp::word(37, 0, 0, 0, 1, 0, 2, 0),
         ::capnp::word(26, 168, 208, 43, 233, 121, 76, 225),
-        ::capnp::word(8, 0, 7, 0, 1, 0, 0, 0),
+        ::capnp::word(10, 0, 7, 0, 1, 0, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
         ::capnp::word(21, 0, 0, 0, 98, 1, 0, 0),
         ::capnp::word(0, 0, 0, 0, 0, 0, 0, 0),
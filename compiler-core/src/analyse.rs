@@ -1,18 +1,28 @@
+mod accumulators;
+mod capabilities;
+mod constant_functions;
+mod identity_cases;
 mod imports;
+mod infinite_types;
+mod labels;
 pub(crate) mod name;
+mod overly_specific_annotations;
+mod simplifiable_bools;
+mod todo_and_panic_in_library;
 
 #[cfg(test)]
 mod tests;
 
 use crate::{
-    GLEAM_CORE_PACKAGE_NAME,
+    GLEAM_CORE_PACKAGE_NAME, STDLIB_PACKAGE_NAME,
     ast::{
-        self, Arg, BitArrayOption, CustomType, Definition, DefinitionLocation, Function,
-        GroupedStatements, Import, ModuleConstant, Publicity, RecordConstructor,
-        RecordConstructorArg, SrcSpan, Statement, TypeAlias, TypeAst, TypeAstConstructor,
-        TypeAstFn, TypeAstHole, TypeAstTuple, TypeAstVar, TypedDefinition, TypedExpr,
-        TypedFunction, TypedModule, UntypedArg, UntypedCustomType, UntypedFunction, UntypedImport,
-        UntypedModule, UntypedModuleConstant, UntypedStatement, UntypedTypeAlias,
+        self, Arg, BitArrayOption, CustomType, Definition, DefinitionLocation, ExternalArguments,
+        Function, GroupedStatements, Import, ModuleConstant, Publicity, RecordConstructor,
+        RecordConstructorArg, SrcSpan, Statement, TargetedDefinition, TypeAlias, TypeAst,
+        TypeAstConstructor, TypeAstFn, TypeAstHole, TypeAstTuple, TypeAstVar, TypedArg,
+        TypedDefinition, TypedExpr, TypedFunction, TypedModule, UntypedArg, UntypedCustomType,
+        UntypedFunction, UntypedImport, UntypedModule, UntypedModuleConstant, UntypedStatement,
+        UntypedTypeAlias,
     },
     build::{Origin, Outcome, Target},
     call_graph::{CallGraphNode, into_dependency_order},
@@ -27,28 +37,41 @@ use crate::{
         TypeValueConstructor, TypeValueConstructorField, TypeVariantConstructors, ValueConstructor,
         ValueConstructorVariant, Warning,
         environment::*,
-        error::{Error, FeatureKind, MissingAnnotation, Named, Problems, convert_unify_error},
+        error::{
+            Error, ExternalArgumentsProblem, FeatureKind, MissingAnnotation, Named, Problems,
+            convert_unify_error,
+        },
         expression::{ExprTyper, FunctionDefinition, Implementations},
         fields::FieldMapBuilder,
         hydrator::Hydrator,
         prelude::*,
     },
     uid::UniqueIdGenerator,
-    warning::TypeWarningEmitter,
+    warning::{TypeWarningEmitter, WarningEmitter},
 };
 use camino::Utf8PathBuf;
 use ecow::EcoString;
 use hexpm::version::Version;
 use itertools::Itertools;
-use name::{check_argument_names, check_name_case};
+use name::{check_argument_names, check_name_case, check_naming_conventions};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::Deref,
     sync::{Arc, OnceLock},
 };
 use vec1::Vec1;
 
+use self::accumulators::check_inefficient_accumulators;
+use self::constant_functions::check_constant_functions;
+use self::identity_cases::check_identity_cases;
 use self::imports::Importer;
+use self::infinite_types::check_infinite_types;
+use self::labels::check_unused_labels;
+use self::overly_specific_annotations::{
+    check_overly_specific_annotation, has_annotations_to_check,
+};
+use self::simplifiable_bools::check_simplifiable_bools;
+use self::todo_and_panic_in_library::check_todo_and_panic_in_library;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Inferred<T> {
@@ -173,6 +196,146 @@ impl<A> ModuleAnalyzerConstructor<'_, A> {
     }
 }
 
+/// This struct is used to take the data required to analyse a single
+/// expression on its own, outside of any module, as a REPL or a playground
+/// would need to.
+///
+#[derive(Debug)]
+pub struct ExpressionAnalyzerConstructor<'a> {
+    pub target: Target,
+    pub ids: &'a UniqueIdGenerator,
+    /// The prelude is built if it is not one of these modules.
+    pub importable_modules: &'a im::HashMap<EcoString, ModuleInterface>,
+    pub warnings: &'a WarningEmitter,
+    pub package_config: &'a PackageConfig,
+    /// The module the expression is analysed as being part of. If it is one
+    /// of the importable modules, all its values and types are in scope.
+    pub module_name: EcoString,
+    /// The imports in scope for the expression, as if it was in a module
+    /// starting with them.
+    pub imports: &'a [UntypedImport],
+}
+
+impl ExpressionAnalyzerConstructor<'_> {
+    /// Parse and type check `src` as if it were the body of a function,
+    /// returning the typed expression along with its type.
+    ///
+    /// As with a function body this can be a sequence of statements, so that
+    /// `let` and `use` can be used; they are then wrapped in a block.
+    /// Along with the prelude, the given imports and the definitions of the
+    /// module the expression is part of are in scope.
+    ///
+    pub fn infer_expression(
+        self,
+        src: &str,
+        src_path: Utf8PathBuf,
+    ) -> Result<(TypedExpr, Arc<Type>), crate::Error> {
+        let statements =
+            crate::parse::parse_statement_sequence(src).map_err(|error| crate::Error::Parse {
+                path: src_path.clone(),
+                src: src.into(),
+                error,
+            })?;
+
+        let mut importable_modules = self.importable_modules.clone();
+        if !importable_modules.contains_key(PRELUDE_MODULE_NAME) {
+            let _ = importable_modules.insert(PRELUDE_MODULE_NAME.into(), build_prelude(self.ids));
+        }
+
+        let mut env = Environment::new(
+            self.ids.clone(),
+            self.package_config.name.clone(),
+            self.package_config.gleam_version.clone(),
+            self.module_name,
+            self.target,
+            &importable_modules,
+            TargetSupport::Enforced,
+        );
+        let mut problems = Problems::new();
+        if let Some(module) = importable_modules.get(&env.current_module) {
+            register_module_definitions(&mut env, module, &mut problems);
+        }
+        let mut env = Importer::run(Origin::Src, env, self.imports, &mut problems);
+        let definition = FunctionDefinition {
+            has_body: true,
+            has_erlang_external: false,
+            has_javascript_external: false,
+            is_generated: false,
+        };
+        let statements =
+            ExprTyper::new(&mut env, definition, &mut problems).infer_statements(statements);
+
+        let location = SrcSpan::new(
+            statements.first().location().start,
+            statements.last().last_location().end,
+        );
+        let expression = match statements.split_off_first() {
+            (Statement::Expression(expression), rest) if rest.is_empty() => expression,
+            (first, rest) => {
+                let mut statements = Vec1::new(first);
+                statements.extend(rest);
+                TypedExpr::Block {
+                    location,
+                    statements,
+                }
+            }
+        };
+
+        problems.sort();
+        let warnings = TypeWarningEmitter::new(src_path.clone(), src.into(), self.warnings.clone());
+        for warning in problems.take_warnings() {
+            warnings.emit(warning);
+        }
+
+        match Vec1::try_from_vec(problems.take_errors()) {
+            Err(_) => {
+                let type_ = expression.type_();
+                Ok((expression, type_))
+            }
+            Ok(errors) => Err(crate::Error::Type {
+                path: src_path,
+                src: src.into(),
+                errors,
+                names: env.names,
+            }),
+        }
+    }
+}
+
+/// Brings all the values and types of an already analysed module into scope,
+/// as if the code being analysed was part of it.
+///
+fn register_module_definitions(
+    environment: &mut Environment<'_>,
+    module: &ModuleInterface,
+    problems: &mut Problems,
+) {
+    for (name, type_) in &module.types {
+        environment
+            .names
+            .type_in_scope(name.clone(), type_.type_.as_ref(), &type_.parameters);
+        if let Err(error) = environment.insert_type_constructor(name.clone(), type_.clone()) {
+            problems.error(error);
+        }
+    }
+    for (name, constructors) in &module.types_value_constructors {
+        environment.insert_type_to_constructors(name.clone(), constructors.clone());
+    }
+    for (name, accessors) in &module.accessors {
+        environment.insert_accessors(name.clone(), accessors.clone());
+    }
+    for (name, value) in &module.values {
+        environment.insert_variable(
+            name.clone(),
+            value.variant.clone(),
+            value.type_.clone(),
+            value.publicity,
+            value.deprecation.clone(),
+        );
+        environment.insert_module_value(name.clone(), value.clone());
+    }
+}
+
 struct ModuleAnalyzer<'a, A> {
     target: Target,
     ids: &'a UniqueIdGenerator,
@@ -200,7 +363,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
 
         let documentation = std::mem::take(&mut module.documentation);
-        let env = Environment::new(
+        let mut env = Environment::new(
             self.ids.clone(),
             self.package_config.name.clone(),
             self.package_config.gleam_version.clone(),
@@ -209,10 +372,29 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             self.importable_modules,
             self.target_support,
         );
+        env.lints = self.package_config.lints;
+
+        let (definitions, other_target_definitions): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut module.definitions)
+                .into_iter()
+                .partition(|definition| definition.is_for(self.target));
+        register_other_target_imports(&mut env, other_target_definitions);
+        let (definitions, disabled_definitions): (Vec<_>, Vec<_>) = definitions
+            .into_iter()
+            .partition(|definition| definition.is_enabled(&self.package_config.features));
+        let disabled = register_disabled_definitions(&mut env, disabled_definitions);
 
-        let statements = GroupedStatements::new(module.into_iter_statements(self.target));
+        let mut statements = GroupedStatements::new(
+            definitions
+                .into_iter()
+                .map(|definition| definition.definition),
+        );
+        let generated_functions =
+            capabilities::generate_capability_functions(&mut statements, &mut self.problems);
         let statements_count = statements.len();
 
+        check_naming_conventions(&statements, &self.package_config.lints, &mut self.problems);
+
         // Register any modules, types, and values being imported
         // We process imports first so that anything imported can be referenced
         // anywhere in the module.
@@ -239,6 +421,11 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 return self.all_errors(error);
             }
         }
+        // Generated functions are never reported as unused, as there would be
+        // nothing the programmer could remove.
+        for name in &generated_functions {
+            env.increment_usage(name);
+        }
 
         // Infer the types of each statement in the module
         let mut typed_statements = Vec::with_capacity(statements_count);
@@ -248,10 +435,21 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         for t in statements.custom_types {
             optionally_push(&mut typed_statements, self.analyse_custom_type(t, &mut env));
         }
+        check_infinite_types(&self.module_name, &typed_statements, &mut self.problems);
         for t in statements.type_aliases {
             typed_statements.push(analyse_type_alias(t, &mut env));
         }
 
+        // The benchmarks are recorded in the order they are defined in, before
+        // functions get sorted into dependency order.
+        let benchmarks = statements
+            .functions
+            .iter()
+            .filter(|function| function.benchmark)
+            .filter_map(|function| function.name.as_ref())
+            .map(|(_, name)| name.clone())
+            .collect_vec();
+
         // Sort functions and constants into dependency order for inference. Definitions that do
         // not depend on other definitions are inferred first, then ones that depend
         // on those, etc.
@@ -261,6 +459,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 Err(error) => return self.all_errors(error),
             };
         let mut working_group = vec![];
+        let mut annotated_functions = vec![];
 
         for group in definition_groups {
             // A group may have multiple functions that depend on each other through
@@ -268,7 +467,18 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
             for definition in group {
                 let def = match definition {
-                    CallGraphNode::Function(f) => self.infer_function(f, &mut env),
+                    CallGraphNode::Function(f) => {
+                        if self.package_config.lints.overly_specific_annotations
+                            && has_annotations_to_check(&f)
+                        {
+                            annotated_functions.push(f.clone());
+                        }
+                        let is_generated = f
+                            .name
+                            .as_ref()
+                            .is_some_and(|(_, name)| generated_functions.contains(name));
+                        self.infer_function(f, is_generated, &mut env)
+                    }
                     CallGraphNode::ModuleConstant(c) => self.infer_module_constant(c, &mut env),
                 };
                 working_group.push(def);
@@ -278,6 +488,31 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             for inferred in working_group.drain(..) {
                 typed_statements.push(generalise_statement(inferred, &self.module_name, &mut env));
             }
+
+            // Only once their types are generalised can the functions be
+            // inferred again without their annotations, without affecting
+            // the types of the other functions in the group.
+            for function in annotated_functions.drain(..) {
+                check_overly_specific_annotation(function, &mut env, &mut self.problems);
+            }
+        }
+
+        check_simplifiable_bools(&typed_statements, &mut self.problems);
+
+        if self.package_config.lints.unused_labels {
+            check_unused_labels(&self.module_name, &typed_statements, &mut self.problems);
+        }
+
+        if self.package_config.lints.constant_functions {
+            check_constant_functions(&typed_statements, &mut self.problems);
+        }
+
+        if self.package_config.lints.identity_cases {
+            check_identity_cases(&typed_statements, &mut self.problems);
+        }
+
+        if self.package_config.lints.todo_and_panic_in_library && self.origin.is_src() {
+            check_todo_and_panic_in_library(&typed_statements, &mut self.problems);
         }
 
         // Generate warnings for unused items
@@ -341,6 +576,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 type_aliases,
                 documentation,
                 contains_echo: echo_found,
+                benchmarks,
+                disabled_values: disabled.values,
+                disabled_types: disabled.types,
                 references: References {
                     imported_modules: env
                         .imported_modules
@@ -349,6 +587,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                         .collect(),
                     value_references: env.references.value_references,
                     type_references: env.references.type_references,
+                    discarded_value_references: env.references.discarded_value_references,
                 },
             },
             names: type_names,
@@ -371,6 +610,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
     ) -> TypedDefinition {
         let ModuleConstant {
             documentation: doc,
+            doc_attribute,
             location,
             name,
             name_location,
@@ -378,6 +618,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             publicity,
             value,
             deprecation,
+            since,
             ..
         } = c;
         self.check_name_case(name_location, &name, Named::Constant);
@@ -386,6 +627,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             has_body: true,
             has_erlang_external: false,
             has_javascript_external: false,
+            is_generated: false,
         };
         let mut expr_typer = ExprTyper::new(environment, definition, &mut self.problems);
         let typed_expr = expr_typer.infer_const(&annotation, *value);
@@ -414,6 +656,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             deprecation: deprecation.clone(),
             variant: ValueConstructorVariant::ModuleConstant {
                 documentation: doc.as_ref().map(|(_, doc)| doc.clone()),
+                since: since.clone(),
                 location,
                 literal: typed_expr.clone(),
                 module: self.module_name.clone(),
@@ -450,6 +693,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         Definition::ModuleConstant(ModuleConstant {
             documentation: doc,
+            doc_attribute,
             location,
             name,
             name_location,
@@ -459,6 +703,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             type_,
             deprecation,
             implementations,
+            since,
         })
     }
 
@@ -468,10 +713,12 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
     fn infer_function(
         &mut self,
         f: UntypedFunction,
+        is_generated: bool,
         environment: &mut Environment<'_>,
     ) -> TypedDefinition {
         let Function {
             documentation: doc,
+            doc_attribute,
             location,
             name,
             publicity,
@@ -482,8 +729,15 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             deprecation,
             external_erlang,
             external_javascript,
+            external_erlang_arguments,
+            external_javascript_arguments,
             return_type: (),
             implementations: _,
+            purity,
+            benchmark,
+            memoize,
+            should_panic,
+            since,
         } = f;
         let (name_location, name) = name.expect("Function in a definition must be named");
         let target = environment.target;
@@ -497,9 +751,26 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             .fn_types()
             .expect("Preregistered type for fn was not a fn");
 
+        // Benchmarks are called with no arguments from outside of the module,
+        // so they must be public and take no arguments.
+        if benchmark && (publicity.is_private() || !arguments.is_empty()) {
+            self.problems.error(Error::InvalidBenchmarkFunction {
+                location,
+                name: name.clone(),
+            });
+        }
+
         // Ensure that folks are not writing inline JavaScript expressions as
-        // the implementation for JS externals.
-        self.assert_valid_javascript_external(&name, external_javascript.as_ref(), location);
+        // the implementation for JS externals. Generated functions can use
+        // externals that are inlined into the generated JavaScript instead.
+        if !is_generated {
+            self.assert_valid_javascript_external(&name, external_javascript.as_ref(), location);
+        }
+
+        // Ensure that the external functions are called with all the
+        // arguments of the function.
+        self.check_external_arguments(&arguments, external_erlang_arguments.as_deref());
+        self.check_external_arguments(&arguments, external_javascript_arguments.as_deref());
 
         // Find the external implementation for the current target, if one has been given.
         let external =
@@ -526,6 +797,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             has_body,
             has_erlang_external: external_erlang.is_some(),
             has_javascript_external: external_javascript.is_some(),
+            is_generated,
         };
 
         let typed_args = arguments
@@ -588,6 +860,20 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             self.minimum_required_version = required_version;
         }
 
+        let list_module_is_stdlib = environment
+            .importable_modules
+            .get("gleam/list")
+            .is_some_and(|module| module.package == STDLIB_PACKAGE_NAME);
+        check_inefficient_accumulators(
+            &environment.current_module,
+            &name,
+            &typed_args,
+            &body,
+            &self.package_config.lints,
+            list_module_is_stdlib,
+            &mut self.problems,
+        );
+
         match publicity {
             Publicity::Private
             | Publicity::Public
@@ -611,6 +897,15 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             self.problems.error(convert_unify_error(error, location));
         }
 
+        if memoize {
+            self.check_memoized_function(
+                location,
+                &name,
+                &typed_args,
+                external_erlang.is_some() || external_javascript.is_some(),
+            );
+        }
+
         // Ensure that the current target has an implementation for the function.
         // This is done at the expression level while inferring the function body, but we do it again
         // here as externally implemented functions may not have a Gleam body.
@@ -634,18 +929,19 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         let variant = ValueConstructorVariant::ModuleFn {
             documentation: doc.as_ref().map(|(_, doc)| doc.clone()),
+            since: since.clone(),
             name: name.clone(),
-            external_erlang: external_erlang
-                .as_ref()
-                .map(|(m, f, _)| (m.clone(), f.clone())),
-            external_javascript: external_javascript
-                .as_ref()
-                .map(|(m, f, _)| (m.clone(), f.clone())),
+            external_erlang: directly_called_external(&external_erlang, &external_erlang_arguments),
+            external_javascript: directly_called_external(
+                &external_javascript,
+                &external_javascript_arguments,
+            ),
             field_map,
             module: environment.current_module.clone(),
             arity: typed_args.len(),
             location,
             implementations,
+            purity,
         };
 
         environment.insert_variable(
@@ -665,6 +961,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         Definition::Function(Function {
             documentation: doc,
+            doc_attribute,
             location,
             name: Some((name_location, name)),
             publicity,
@@ -678,10 +975,103 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             body,
             external_erlang,
             external_javascript,
+            external_erlang_arguments,
+            external_javascript_arguments,
             implementations,
+            purity,
+            benchmark,
+            memoize,
+            should_panic,
+            since,
         })
     }
 
+    /// The code caching the values returned by a memoized function is
+    /// generated around its Gleam implementation, using its arguments as the
+    /// key of the cache. So it can't have an external implementation, and its
+    /// arguments must have a type whose values are equal if and only if they
+    /// are the same key on both targets. Discarded arguments are not part of
+    /// the key, so they can have any type.
+    ///
+    fn check_memoized_function(
+        &mut self,
+        location: SrcSpan,
+        name: &EcoString,
+        arguments: &[TypedArg],
+        has_external: bool,
+    ) {
+        if has_external {
+            self.problems.error(Error::MemoizedExternalFunction {
+                location,
+                name: name.clone(),
+            });
+        }
+
+        for argument in arguments {
+            if argument.get_variable_name().is_none() {
+                continue;
+            }
+            let type_ = &argument.type_;
+            if !(type_.is_int()
+                || type_.is_float()
+                || type_.is_string()
+                || type_.is_bool()
+                || type_.is_nil())
+            {
+                self.problems.error(Error::UnmemoizableArgument {
+                    location: argument.location,
+                    type_: type_.clone(),
+                });
+            }
+        }
+    }
+
+    /// The arguments an external function is called with are the arguments
+    /// of the Gleam function, in a different order. They must each be listed
+    /// exactly once so that the external function takes as many arguments,
+    /// with the same types as the ones given in the annotations.
+    ///
+    fn check_external_arguments(
+        &mut self,
+        arguments: &[UntypedArg],
+        external_arguments: Option<&ExternalArguments>,
+    ) {
+        let Some(external_arguments) = external_arguments else {
+            return;
+        };
+        let names = arguments
+            .iter()
+            .filter_map(|argument| argument.get_variable_name())
+            .cloned()
+            .collect_vec();
+
+        let mut listed = HashSet::new();
+        let mut problem = None;
+        for (location, name) in &external_arguments.names {
+            if !names.contains(name) {
+                problem = Some((*location, ExternalArgumentsProblem::UnknownArgument));
+                break;
+            } else if !listed.insert(name) {
+                problem = Some((*location, ExternalArgumentsProblem::DuplicateArgument));
+                break;
+            }
+        }
+        if problem.is_none() && listed.len() != arguments.len() {
+            problem = Some((
+                external_arguments.location,
+                ExternalArgumentsProblem::MissingArguments,
+            ));
+        }
+
+        if let Some((location, problem)) = problem {
+            self.problems.error(Error::InvalidExternalArguments {
+                location,
+                problem,
+                arguments: names,
+            });
+        }
+    }
+
     fn assert_valid_javascript_external(
         &mut self,
         function_name: &EcoString,
@@ -770,6 +1160,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             as_name,
             unqualified_values,
             unqualified_types,
+            unqualified_types_glob,
             ..
         } = i;
         // Find imported module
@@ -801,6 +1192,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             as_name,
             unqualified_values,
             unqualified_types,
+            unqualified_types_glob,
             package: module_info.package.clone(),
         }))
     }
@@ -833,6 +1225,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         let CustomType {
             documentation: doc,
+            doc_attribute,
             location,
             end_position,
             publicity,
@@ -842,6 +1235,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             parameters,
             constructors,
             deprecation,
+            capabilities,
             ..
         } = t;
 
@@ -953,6 +1347,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         Ok(Definition::CustomType(CustomType {
             documentation: doc,
+            doc_attribute,
             location,
             end_position,
             publicity,
@@ -963,6 +1358,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             constructors,
             typed_parameters,
             deprecation,
+            capabilities,
         }))
     }
 
@@ -1278,6 +1674,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             deprecation,
             type_: _,
             documentation,
+            doc_attribute: _,
         } = t;
 
         // A type alias must not have the same name as any other type in the module.
@@ -1396,11 +1793,19 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             documentation,
             external_erlang,
             external_javascript,
+            external_erlang_arguments,
+            external_javascript_arguments,
             deprecation,
             end_position: _,
             body: _,
             return_type: _,
             implementations,
+            purity,
+            benchmark: _,
+            memoize: _,
+            should_panic: _,
+            since,
+            doc_attribute: _,
         } = f;
         let (name_location, name) = name.as_ref().expect("A module's function must be named");
 
@@ -1435,18 +1840,19 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         let variant = ValueConstructorVariant::ModuleFn {
             documentation: documentation.as_ref().map(|(_, doc)| doc.clone()),
+            since: since.clone(),
             name: name.clone(),
             field_map,
-            external_erlang: external_erlang
-                .as_ref()
-                .map(|(m, f, _)| (m.clone(), f.clone())),
-            external_javascript: external_javascript
-                .as_ref()
-                .map(|(m, f, _)| (m.clone(), f.clone())),
+            external_erlang: directly_called_external(external_erlang, external_erlang_arguments),
+            external_javascript: directly_called_external(
+                external_javascript,
+                external_javascript_arguments,
+            ),
             module: environment.current_module.clone(),
             arity: args.len(),
             location: *location,
             implementations: *implementations,
+            purity: *purity,
         };
         environment.insert_variable(
             name.clone(),
@@ -1551,6 +1957,7 @@ fn target_function_implementation<'a>(
 fn analyse_type_alias(t: UntypedTypeAlias, environment: &mut Environment<'_>) -> TypedDefinition {
     let TypeAlias {
         documentation: doc,
+        doc_attribute,
         location,
         publicity,
         alias,
@@ -1571,6 +1978,7 @@ fn analyse_type_alias(t: UntypedTypeAlias, environment: &mut Environment<'_>) ->
     };
     Definition::TypeAlias(TypeAlias {
         documentation: doc,
+        doc_attribute,
         location,
         publicity,
         alias,
@@ -1653,6 +2061,7 @@ fn generalise_module_constant(
 ) -> TypedDefinition {
     let ModuleConstant {
         documentation: doc,
+        doc_attribute,
         location,
         name,
         name_location,
@@ -1662,11 +2071,13 @@ fn generalise_module_constant(
         type_,
         deprecation,
         implementations,
+        since,
     } = constant;
     let type_ = type_.clone();
     let type_ = type_::generalise(type_);
     let variant = ValueConstructorVariant::ModuleConstant {
         documentation: doc.as_ref().map(|(_, doc)| doc.clone()),
+        since: since.clone(),
         location,
         literal: *value.clone(),
         module: module_name.clone(),
@@ -1693,6 +2104,7 @@ fn generalise_module_constant(
 
     Definition::ModuleConstant(ModuleConstant {
         documentation: doc,
+        doc_attribute,
         location,
         name,
         name_location,
@@ -1702,6 +2114,7 @@ fn generalise_module_constant(
         type_,
         deprecation,
         implementations,
+        since,
     })
 }
 
@@ -1712,6 +2125,7 @@ fn generalise_function(
 ) -> TypedDefinition {
     let Function {
         documentation: doc,
+        doc_attribute,
         location,
         name,
         publicity,
@@ -1723,7 +2137,14 @@ fn generalise_function(
         return_type,
         external_erlang,
         external_javascript,
+        external_erlang_arguments,
+        external_javascript_arguments,
         implementations,
+        purity,
+        benchmark,
+        memoize,
+        should_panic,
+        since,
     } = function;
 
     let (name_location, name) = name.expect("Function in a definition must be named");
@@ -1740,18 +2161,19 @@ fn generalise_function(
     // Insert the function into the module's interface
     let variant = ValueConstructorVariant::ModuleFn {
         documentation: doc.as_ref().map(|(_, doc)| doc.clone()),
+        since: since.clone(),
         name: name.clone(),
         field_map,
-        external_erlang: external_erlang
-            .as_ref()
-            .map(|(m, f, _)| (m.clone(), f.clone())),
-        external_javascript: external_javascript
-            .as_ref()
-            .map(|(m, f, _)| (m.clone(), f.clone())),
+        external_erlang: directly_called_external(&external_erlang, &external_erlang_arguments),
+        external_javascript: directly_called_external(
+            &external_javascript,
+            &external_javascript_arguments,
+        ),
         module: module_name.clone(),
         arity: args.len(),
         location,
         implementations,
+        purity,
     };
     environment.insert_variable(
         name.clone(),
@@ -1772,6 +2194,7 @@ fn generalise_function(
 
     Definition::Function(Function {
         documentation: doc,
+        doc_attribute,
         location,
         name: Some((name_location, name)),
         publicity,
@@ -1783,10 +2206,33 @@ fn generalise_function(
         body,
         external_erlang,
         external_javascript,
+        external_erlang_arguments,
+        external_javascript_arguments,
         implementations,
+        purity,
+        benchmark,
+        memoize,
+        should_panic,
+        since,
     })
 }
 
+/// The external function that can be called directly in place of the Gleam
+/// function wrapping it. If it takes different arguments from the Gleam
+/// function then it's the generated wrapper that has to be called instead.
+///
+fn directly_called_external(
+    external: &Option<(EcoString, EcoString, SrcSpan)>,
+    arguments: &Option<Box<ExternalArguments>>,
+) -> Option<(EcoString, EcoString)> {
+    match arguments {
+        Some(_) => None,
+        None => external
+            .as_ref()
+            .map(|(module, function, _)| (module.clone(), function.clone())),
+    }
+}
+
 fn assert_unique_name(
     names: &mut HashMap<EcoString, SrcSpan>,
     name: &EcoString,
@@ -1978,3 +2424,103 @@ fn sorted_type_aliases(aliases: &Vec<UntypedTypeAlias>) -> Result<Vec<&UntypedTy
         .sorted_by_key(|alias| sorted_deps.iter().position(|x| x == &alias.alias))
         .collect())
 }
+
+/// Records the modules that are only imported for another target, so using
+/// them can be reported with a helpful error rather than as an unknown module.
+///
+fn register_other_target_imports(
+    environment: &mut Environment<'_>,
+    definitions: Vec<TargetedDefinition>,
+) {
+    for definition in definitions {
+        let TargetedDefinition {
+            definition: Definition::Import(import),
+            target: Some(target),
+            ..
+        } = definition
+        else {
+            continue;
+        };
+        if let Some(name) = import.used_name() {
+            let _ = environment.other_target_imports.insert(name, target);
+        }
+    }
+}
+
+/// The definitions of a module that other modules can import, but that are
+/// not compiled as they require a feature that is not enabled.
+#[derive(Debug, Default)]
+struct DisabledDefinitions {
+    values: HashMap<EcoString, EcoString>,
+    types: HashMap<EcoString, EcoString>,
+}
+
+/// Registers the definitions that are not compiled because of their feature
+/// flags, so that using them reports what feature they need. The ones that
+/// can be imported are returned to be part of the module's interface.
+///
+fn register_disabled_definitions(
+    environment: &mut Environment<'_>,
+    definitions: Vec<TargetedDefinition>,
+) -> DisabledDefinitions {
+    let mut importable = DisabledDefinitions::default();
+    for TargetedDefinition {
+        definition,
+        feature,
+        ..
+    } in definitions
+    {
+        let Some(feature) = feature else {
+            continue;
+        };
+
+        match definition {
+            Definition::Function(Function {
+                name: Some((_, name)),
+                publicity,
+                ..
+            })
+            | Definition::ModuleConstant(ModuleConstant {
+                name, publicity, ..
+            }) => {
+                if publicity.is_importable() {
+                    let _ = importable.values.insert(name.clone(), feature.clone());
+                }
+                let _ = environment.disabled_values.insert(name, feature);
+            }
+            Definition::Function(Function { name: None, .. }) => (),
+            Definition::TypeAlias(TypeAlias {
+                alias, publicity, ..
+            }) => {
+                if publicity.is_importable() {
+                    let _ = importable.types.insert(alias.clone(), feature.clone());
+                }
+                let _ = environment.disabled_types.insert(alias, feature);
+            }
+            Definition::CustomType(CustomType {
+                name,
+                constructors,
+                publicity,
+                opaque,
+                ..
+            }) => {
+                for constructor in constructors {
+                    if publicity.is_importable() && !opaque {
+                        let _ = importable
+                            .values
+                            .insert(constructor.name.clone(), feature.clone());
+                    }
+                    let _ = environment
+                        .disabled_values
+                        .insert(constructor.name, feature.clone());
+                }
+                if publicity.is_importable() {
+                    let _ = importable.types.insert(name.clone(), feature.clone());
+                }
+                let _ = environment.disabled_types.insert(name, feature);
+            }
+            Definition::Import(_) => (),
+        }
+    }
+    importable
+}
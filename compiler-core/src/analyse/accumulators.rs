@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use ecow::EcoString;
+
+use crate::{
+    ast::{
+        ArgNames, BinOp, SrcSpan, TypedArg, TypedExpr, TypedStatement,
+        visit::{self, Visit},
+    },
+    config::LintsConfig,
+    type_::{
+        ModuleValueConstructor, Problems, Type, TypedCallArg, ValueConstructorVariant, Warning,
+    },
+};
+
+/// Looks for recursive calls to a function where one of its parameters is
+/// used as an accumulator that is grown in a way that copies it at each step.
+/// That is either by concatenating a string to it:
+///
+/// ```gleam
+/// fn loop(list, acc) {
+///   case list {
+///     [] -> acc
+///     [first, ..rest] -> loop(rest, acc <> first)
+///     //                            ^^^^^^^^^^^^ warning here
+///   }
+/// }
+/// ```
+///
+/// Or by appending to the end of a list with the standard library's
+/// `list.append`:
+///
+/// ```gleam
+/// fn loop(list, acc) {
+///   case list {
+///     [] -> acc
+///     [first, ..rest] -> loop(rest, list.append(acc, [first]))
+///     //                            ^^^^^^^^^^^^^^^^^^^^^^^^^ warning here
+///   }
+/// }
+/// ```
+///
+/// This is a heuristic and it's deliberately conservative: we only warn when
+/// the concatenation is passed directly as an argument of a recursive call and
+/// the grown accumulator is the very same parameter in that same position. Any
+/// other use of `<>` or `list.append` is left alone.
+///
+/// Each of the two warnings can be disabled in the `[lints]` section of the
+/// `gleam.toml`, with `inefficient_string_building` and
+/// `inefficient_list_building`.
+///
+/// `list_module_is_stdlib` tells whether the `gleam/list` module available to
+/// the analysed module is the one from the standard library, so that we don't
+/// get confused by any other package defining a module with the same name.
+///
+pub fn check_inefficient_accumulators(
+    module: &EcoString,
+    function: &EcoString,
+    arguments: &[TypedArg],
+    body: &[TypedStatement],
+    lints: &LintsConfig,
+    list_module_is_stdlib: bool,
+    problems: &mut Problems,
+) {
+    let parameters = arguments
+        .iter()
+        .map(|argument| match &argument.names {
+            ArgNames::Named { name, location }
+            | ArgNames::NamedLabelled {
+                name,
+                name_location: location,
+                ..
+            } => Some((name.clone(), *location)),
+            ArgNames::Discard { .. } | ArgNames::LabelledDiscard { .. } => None,
+        })
+        .collect();
+
+    let mut finder = AccumulatorFinder {
+        module,
+        function,
+        parameters,
+        string_building: lints.inefficient_string_building,
+        list_building: lints.inefficient_list_building,
+        list_module_is_stdlib,
+        problems,
+    };
+    for statement in body {
+        finder.visit_typed_statement(statement);
+    }
+}
+
+struct AccumulatorFinder<'a> {
+    module: &'a EcoString,
+    function: &'a EcoString,
+    /// The name and definition location of each parameter of the function,
+    /// in order. Discarded parameters are `None`.
+    parameters: Vec<Option<(EcoString, SrcSpan)>>,
+    string_building: bool,
+    list_building: bool,
+    list_module_is_stdlib: bool,
+    problems: &'a mut Problems,
+}
+
+impl AccumulatorFinder<'_> {
+    fn is_recursive_call(&self, fun: &TypedExpr) -> bool {
+        match fun {
+            TypedExpr::Var { constructor, .. } => match &constructor.variant {
+                ValueConstructorVariant::ModuleFn { name, module, .. } => {
+                    name == self.function && module == self.module
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_list_append(&self, fun: &TypedExpr) -> bool {
+        if !self.list_module_is_stdlib {
+            return false;
+        }
+
+        let (module, name) = match fun {
+            TypedExpr::ModuleSelect {
+                constructor: ModuleValueConstructor::Fn { module, name, .. },
+                ..
+            } => (module, name),
+            TypedExpr::Var { constructor, .. } => match &constructor.variant {
+                ValueConstructorVariant::ModuleFn { module, name, .. } => (module, name),
+                _ => return false,
+            },
+            _ => return false,
+        };
+        module == "gleam/list" && name == "append"
+    }
+
+    fn is_parameter(&self, expression: &TypedExpr, position: usize) -> bool {
+        let Some(Some((parameter, parameter_location))) = self.parameters.get(position) else {
+            return false;
+        };
+
+        match expression {
+            TypedExpr::Var {
+                name, constructor, ..
+            } => match &constructor.variant {
+                // We check the definition location as well so that we don't
+                // get confused by a local variable shadowing the parameter.
+                ValueConstructorVariant::LocalVariable { location, .. } => {
+                    name == parameter && location == parameter_location
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for AccumulatorFinder<'_> {
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        if self.is_recursive_call(fun) {
+            for (position, arg) in args.iter().enumerate() {
+                match &arg.value {
+                    TypedExpr::BinOp {
+                        location,
+                        name: BinOp::Concatenate,
+                        left,
+                        right,
+                        ..
+                    } if self.string_building
+                        && (self.is_parameter(left, position)
+                            || self.is_parameter(right, position)) =>
+                    {
+                        self.problems.warning(Warning::InefficientStringBuilding {
+                            location: *location,
+                        })
+                    }
+
+                    // Only appending to the end of the accumulator copies it:
+                    // `list.append(items, acc)` only copies `items`.
+                    TypedExpr::Call {
+                        location,
+                        fun,
+                        args: append_args,
+                        ..
+                    } if self.list_building
+                        && self.is_list_append(fun)
+                        && append_args
+                            .first()
+                            .is_some_and(|first| self.is_parameter(&first.value, position)) =>
+                    {
+                        self.problems.warning(Warning::InefficientListBuilding {
+                            location: *location,
+                        })
+                    }
+
+                    _ => (),
+                }
+            }
+        }
+
+        visit::visit_typed_expr_call(self, location, type_, fun, args);
+    }
+}
@@ -0,0 +1,359 @@
+use ecow::{EcoString, eco_format};
+use heck::ToSnakeCase;
+use num_bigint::BigInt;
+use vec1::{Vec1, vec1};
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        Arg, ArgNames, Assignment, AssignmentKind, Capability, Clause, ClauseGuard, Function,
+        GroupedStatements, Pattern, SrcSpan, Statement, TypeAst, TypeAstConstructor, TypeAstVar,
+        UntypedClause, UntypedCustomType, UntypedExpr, UntypedFunction, UntypedPattern,
+        UntypedStatement,
+    },
+    type_::{
+        Deprecation, Error, Problems, Purity,
+        error::{UnsupportedCapabilityProblem, VariableOrigin},
+        expression::Implementations,
+    },
+};
+
+mod json;
+
+/// Generates the functions for all the capabilities implemented by the custom
+/// types of a module with the `@implements` and `@derive` attributes. The generated functions
+/// are added to the module's functions, so they're type checked and compiled
+/// just like any function written by hand, and can be referenced by their
+/// name from any other module.
+///
+/// For example:
+///
+/// ```gleam
+/// @implements(comparable)
+/// pub type Size {
+///   Small
+///   Medium
+///   Large
+/// }
+/// ```
+///
+/// Will generate a `compare_size(a: Size, b: Size) -> order.Order` function
+/// that returns `order.Lt` if `a` comes before `b`, `order.Eq` if they are the
+/// same, and `order.Gt` if `a` comes after `b`, so it can be used with
+/// functions like `list.sort`. The module must import `gleam/order` for the
+/// generated function to refer to it. The functions generated for types
+/// deriving `json` are described in the `json` module.
+///
+/// Returns the names of the generated functions.
+///
+pub fn generate_capability_functions(
+    statements: &mut GroupedStatements,
+    problems: &mut Problems,
+) -> Vec<EcoString> {
+    let mut functions = vec![];
+    let order_module = statements
+        .imports
+        .iter()
+        .find(|import| import.module == ORDER_MODULE)
+        .and_then(|import| import.used_name());
+    let mut json = json::Generator::new(&statements.custom_types);
+    for custom_type in &statements.custom_types {
+        for (capability, location) in &custom_type.capabilities {
+            let generated = match capability {
+                Capability::Comparable => {
+                    comparable_function(custom_type, order_module.as_ref()).map(|f| vec![f])
+                }
+                Capability::Json => json.functions(custom_type),
+            };
+            match generated {
+                Ok(generated) => functions.extend(generated),
+                Err(problem) => problems.error(Error::UnsupportedCapability {
+                    location: *location,
+                    capability: *capability,
+                    type_name: custom_type.name.clone(),
+                    problem,
+                }),
+            }
+        }
+    }
+    functions.extend(json.helper_functions());
+    let names = functions
+        .iter()
+        .filter_map(|function| function.name.as_ref())
+        .map(|(_, name)| name.clone())
+        .collect();
+    statements.functions.extend(functions);
+    names
+}
+
+/// The module defining the `Order` type returned by the functions generated
+/// for the types implementing `comparable`.
+///
+const ORDER_MODULE: &str = "gleam/order";
+
+/// The name of the function generated for a type implementing `comparable`.
+///
+fn comparable_function_name(type_name: &str) -> EcoString {
+    eco_format!("compare_{}", type_name.to_snake_case())
+}
+
+/// For a type like `type Size { Small Medium Large }` this generates the
+/// following function:
+///
+/// ```gleam
+/// pub fn compare_size(a, b) {
+///   let a = case a { Small -> 0 Medium -> 1 Large -> 2 }
+///   let b = case b { Small -> 0 Medium -> 1 Large -> 2 }
+///   case a, b {
+///     _, _ if a < b -> order.Lt
+///     _, _ if a > b -> order.Gt
+///     _, _ -> order.Eq
+///   }
+/// }
+/// ```
+///
+/// Where `order` is the name the module imports `gleam/order` with.
+///
+/// All the locations of the generated code point to the head of the type
+/// definition. This way the function is never given the doc comment of the
+/// type, that comes right before it.
+///
+fn comparable_function(
+    custom_type: &UntypedCustomType,
+    order_module: Option<&EcoString>,
+) -> Result<UntypedFunction, UnsupportedCapabilityProblem> {
+    let location = custom_type.location;
+    if custom_type.constructors.is_empty() {
+        return Err(UnsupportedCapabilityProblem::NoVariants);
+    }
+    if let Some(constructor) = custom_type
+        .constructors
+        .iter()
+        .find(|constructor| !constructor.arguments.is_empty())
+    {
+        return Err(UnsupportedCapabilityProblem::VariantWithFields {
+            variant: constructor.name.clone(),
+        });
+    }
+    let Some(order_module) = order_module else {
+        return Err(UnsupportedCapabilityProblem::MissingImport {
+            module: ORDER_MODULE.into(),
+        });
+    };
+
+    let variant_index = |name: &str| {
+        let clauses = custom_type
+            .constructors
+            .iter()
+            .enumerate()
+            .map(|(index, constructor)| Clause {
+                location,
+                pattern: vec![constructor_pattern(&constructor.name, location)],
+                alternative_patterns: vec![],
+                guard: None,
+                then: int(index as i64, location),
+            })
+            .collect();
+        let_(
+            name,
+            case(vec![var(name, location)], clauses, location),
+            location,
+        )
+    };
+
+    let compare_clause = |guard, result| UntypedClause {
+        location,
+        pattern: vec![discard(location), discard(location)],
+        alternative_patterns: vec![],
+        guard,
+        then: UntypedExpr::FieldAccess {
+            location,
+            label_location: location,
+            label: EcoString::from(result),
+            container: Box::new(var(order_module, location)),
+        },
+    };
+    let guard_var = |name: &str| {
+        Box::new(ClauseGuard::Var {
+            location,
+            type_: (),
+            name: name.into(),
+            definition_location: SrcSpan::default(),
+        })
+    };
+    let comparison = UntypedExpr::Case {
+        location,
+        subjects: vec![var("a", location), var("b", location)],
+        clauses: Some(vec![
+            compare_clause(
+                Some(ClauseGuard::LtInt {
+                    location,
+                    left: guard_var("a"),
+                    right: guard_var("b"),
+                }),
+                "Lt",
+            ),
+            compare_clause(
+                Some(ClauseGuard::GtInt {
+                    location,
+                    left: guard_var("a"),
+                    right: guard_var("b"),
+                }),
+                "Gt",
+            ),
+            compare_clause(None, "Eq"),
+        ]),
+    };
+
+    let body: Vec1<UntypedStatement> = vec1![
+        variant_index("a"),
+        variant_index("b"),
+        Statement::Expression(comparison),
+    ];
+
+    // Both arguments are annotated with the type, so that if it has any type
+    // parameters the two values must have the same type.
+    let annotation = TypeAst::Constructor(TypeAstConstructor {
+        location,
+        name_location: location,
+        module: None,
+        name: custom_type.name.clone(),
+        arguments: custom_type
+            .parameters
+            .iter()
+            .map(|(_, name)| {
+                TypeAst::Var(TypeAstVar {
+                    location,
+                    name: name.clone(),
+                })
+            })
+            .collect(),
+    });
+
+    let type_name = &custom_type.name;
+    Ok(Function {
+        location,
+        end_position: location.end,
+        name: Some((location, comparable_function_name(type_name))),
+        arguments: vec![
+            argument("a", annotation.clone(), location),
+            argument("b", annotation, location),
+        ],
+        body,
+        publicity: custom_type.publicity,
+        deprecation: Deprecation::NotDeprecated,
+        return_annotation: None,
+        return_type: (),
+        documentation: Some((
+            location.start,
+            eco_format!(
+                " Compares two `{type_name}` values by the order their variants are defined in.\n"
+            ),
+        )),
+        external_erlang: None,
+        external_javascript: None,
+        external_erlang_arguments: None,
+        external_javascript_arguments: None,
+        implementations: Implementations::supporting_all(),
+        purity: Purity::Pure,
+        benchmark: false,
+        memoize: false,
+        should_panic: None,
+        since: None,
+        doc_attribute: vec![],
+    })
+}
+
+fn argument(name: &str, annotation: TypeAst, location: SrcSpan) -> Arg<()> {
+    Arg {
+        names: ArgNames::Named {
+            name: name.into(),
+            location,
+        },
+        location,
+        annotation: Some(annotation),
+        type_: (),
+    }
+}
+
+fn let_(name: &str, value: UntypedExpr, location: SrcSpan) -> UntypedStatement {
+    Statement::Assignment(Assignment {
+        location,
+        value: Box::new(value),
+        pattern: Pattern::Variable {
+            location,
+            name: name.into(),
+            type_: (),
+            origin: VariableOrigin::Generated,
+        },
+        kind: AssignmentKind::Generated,
+        annotation: None,
+    })
+}
+
+/// A case expression with a single subject and a single clause would always
+/// match, so it is generated as a `let` assignment instead, the same as the
+/// programmer would be told to write it.
+///
+fn case(subjects: Vec<UntypedExpr>, clauses: Vec<UntypedClause>, location: SrcSpan) -> UntypedExpr {
+    match (subjects.as_slice(), clauses.as_slice()) {
+        ([_], [clause]) if clause.guard.is_none() && clause.alternative_patterns.is_empty() => {
+            let subject = subjects.into_iter().next().expect("single subject");
+            let Clause {
+                mut pattern, then, ..
+            } = clauses.into_iter().next().expect("single clause");
+            let assignment = Statement::Assignment(Assignment {
+                location,
+                value: Box::new(subject),
+                pattern: pattern.remove(0),
+                kind: AssignmentKind::Generated,
+                annotation: None,
+            });
+            UntypedExpr::Block {
+                location,
+                statements: vec1![assignment, Statement::Expression(then)],
+            }
+        }
+        _ => UntypedExpr::Case {
+            location,
+            subjects,
+            clauses: Some(clauses),
+        },
+    }
+}
+
+fn var(name: &str, location: SrcSpan) -> UntypedExpr {
+    UntypedExpr::Var {
+        location,
+        name: name.into(),
+    }
+}
+
+fn int(value: i64, location: SrcSpan) -> UntypedExpr {
+    UntypedExpr::Int {
+        location,
+        value: eco_format!("{value}"),
+        int_value: BigInt::from(value),
+    }
+}
+
+fn discard(location: SrcSpan) -> UntypedPattern {
+    Pattern::Discard {
+        name: "_".into(),
+        location,
+        type_: (),
+    }
+}
+
+fn constructor_pattern(name: &EcoString, location: SrcSpan) -> UntypedPattern {
+    Pattern::Constructor {
+        location,
+        name_location: location,
+        name: name.clone(),
+        arguments: vec![],
+        module: None,
+        constructor: Inferred::Unknown,
+        spread: None,
+        type_: (),
+    }
+}
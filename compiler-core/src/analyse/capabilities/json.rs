@@ -0,0 +1,2051 @@
+use std::{
+    cell::Cell,
+    collections::{BTreeSet, HashSet},
+};
+
+use ecow::{EcoString, eco_format};
+use heck::ToSnakeCase;
+use vec1::vec1;
+
+use super::{argument, case, discard, int, let_, var};
+use crate::{
+    analyse::Inferred,
+    ast::{
+        Arg, ArgNames, BinOp, BitArrayOption, BitArraySegment, CallArg, Capability, ClauseGuard,
+        Constant, Function, FunctionLiteralKind, Pattern, Publicity, SrcSpan, Statement, TypeAst,
+        TypeAstConstructor, UntypedArg, UntypedClause, UntypedClauseGuard, UntypedCustomType,
+        UntypedExpr, UntypedExprBitArraySegment, UntypedFunction, UntypedPattern,
+        UntypedPatternBitArraySegment,
+    },
+    javascript::INLINED_EXTERNAL_MODULE,
+    type_::{
+        Deprecation, Purity, error::UnsupportedCapabilityProblem, expression::Implementations,
+    },
+};
+
+/// Generates the functions of the types deriving `json`.
+///
+/// A value is encoded as a JSON object with a `"type"` key holding the name of
+/// its variant, followed by a key for each of its fields, in the order they
+/// are defined in. A field is keyed by its label, or by its position if it
+/// has no label:
+///
+/// ```gleam
+/// @derive(json)
+/// pub type Shape {
+///   Circle(radius: Int)
+///   Rectangle(Int, Int)
+/// }
+///
+/// // {"type":"Circle","radius":1}
+/// // {"type":"Rectangle","0":1,"1":2}
+/// ```
+///
+/// `Int`s are encoded as JSON numbers, `String`s as JSON strings, `Bool`s as
+/// `true` or `false` and `List`s as JSON arrays. Fields of another type of the
+/// module are encoded with that type's own generated function, so it must
+/// derive `json` too.
+///
+/// The generated decoder accepts any JSON value with this layout: there can be
+/// whitespace around the values, the keys of an object can come in any order
+/// and the keys that don't belong to a field are ignored, whatever their
+/// value. If a key appears more than once, its last value is used.
+///
+pub struct Generator {
+    /// The names of the types of the module deriving `json`.
+    derived_types: HashSet<EcoString>,
+    /// The location given to the generated helper functions.
+    location: SrcSpan,
+    /// The helper functions used by the functions generated so far.
+    helpers: BTreeSet<Helper>,
+}
+
+impl Generator {
+    pub fn new(custom_types: &[UntypedCustomType]) -> Self {
+        let derived_types: Vec<_> = custom_types
+            .iter()
+            .filter(|custom_type| {
+                custom_type
+                    .capabilities
+                    .iter()
+                    .any(|(capability, _)| *capability == Capability::Json)
+            })
+            .collect();
+        Self {
+            location: derived_types
+                .first()
+                .map(|custom_type| custom_type.location)
+                .unwrap_or_default(),
+            derived_types: derived_types
+                .into_iter()
+                .map(|custom_type| custom_type.name.clone())
+                .collect(),
+            helpers: BTreeSet::new(),
+        }
+    }
+
+    /// For a type like `type Point { Point(x: Int) }` this generates the
+    /// following functions:
+    ///
+    /// ```gleam
+    /// pub fn point_to_json(value: Point) {
+    ///   case value {
+    ///     Point(field0) -> "{\"type\":\"Point\",\"x\":" <> derived_json_encode_int(field0) <> "}"
+    ///   }
+    /// }
+    ///
+    /// pub fn point_from_json(json: String) {
+    ///   case decode_point_json(<<json:utf8>>) {
+    ///     Ok(#(value, rest)) ->
+    ///       case derived_json_skip_whitespace(rest) {
+    ///         <<>> -> Ok(value)
+    ///         _ -> Error(Nil)
+    ///       }
+    ///     _ -> Error(Nil)
+    ///   }
+    /// }
+    ///
+    /// fn decode_point_json(json: BitArray) {
+    ///   case derived_json_decode_object(json) {
+    ///     Ok(#(members, rest)) ->
+    ///       case derived_json_decode_field(members, <<"type":utf8>>, derived_json_decode_string_bits) {
+    ///         Ok(<<"Point":utf8>>) ->
+    ///           case derived_json_decode_field(members, <<"x":utf8>>, derived_json_decode_int) {
+    ///             Ok(field0) -> Ok(#(Point(field0), rest))
+    ///             _ -> Error(Nil)
+    ///           }
+    ///         _ -> Error(Nil)
+    ///       }
+    ///     _ -> Error(Nil)
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// The helper functions the generated code relies on are generated once
+    /// for the whole module by `helper_functions`.
+    ///
+    pub fn functions(
+        &mut self,
+        custom_type: &UntypedCustomType,
+    ) -> Result<Vec<UntypedFunction>, UnsupportedCapabilityProblem> {
+        if custom_type.constructors.is_empty() {
+            return Err(UnsupportedCapabilityProblem::NoVariants);
+        }
+        if !custom_type.parameters.is_empty() {
+            return Err(UnsupportedCapabilityProblem::TypeParameters);
+        }
+
+        let code = Code::new(custom_type.location);
+        let mut encode_clauses = vec![];
+        let mut decode_clauses = vec![];
+        for constructor in &custom_type.constructors {
+            let mut encoded = vec![];
+            let mut fields = vec![];
+            let mut decode_fields = vec![];
+            let mut prefix = eco_format!("{{\"type\":\"{}\"", constructor.name);
+            for (index, argument) in constructor.arguments.iter().enumerate() {
+                let unsupported = || UnsupportedCapabilityProblem::UnsupportedField {
+                    variant: constructor.name.clone(),
+                    label: argument.label.as_ref().map(|(_, label)| label.clone()),
+                };
+                let field = eco_format!("field{index}");
+                let key = match &argument.label {
+                    Some((_, label)) => label.clone(),
+                    None => eco_format!("{index}"),
+                };
+                prefix.push_str(&eco_format!(",\"{key}\":"));
+                encoded.push(code.string(&prefix));
+                encoded.push(
+                    self.encode(&code, &argument.ast, code.var(&field))
+                        .ok_or_else(unsupported)?,
+                );
+                prefix = EcoString::new();
+
+                let decoder = self.decode(&code, &argument.ast).ok_or_else(unsupported)?;
+                decode_fields.push((key, field.clone(), decoder));
+                fields.push(field);
+            }
+            prefix.push('}');
+            encoded.push(code.string(&prefix));
+
+            encode_clauses.push(code.clause(
+                code.constructor_pattern(
+                    &constructor.name,
+                    fields.iter().map(|field| code.variable(field)).collect(),
+                ),
+                code.concat(encoded),
+            ));
+
+            // The decoder is built from the inside out: it starts from the
+            // decoded value and wraps it with a `case` for each field.
+            let value = if fields.is_empty() {
+                code.var(&constructor.name)
+            } else {
+                code.call(
+                    code.var(&constructor.name),
+                    fields.iter().map(|field| code.var(field)).collect(),
+                )
+            };
+            let mut then = code.ok(code.tuple(vec![value, code.var("rest")]));
+            for (key, field, decoder) in decode_fields.into_iter().rev() {
+                then = code.expect_ok_pattern(
+                    self.decode_field(&code, &key, decoder),
+                    code.variable(&field),
+                    then,
+                );
+            }
+            decode_clauses.push(code.clause(
+                code.ok_pattern(code.bits_pattern(vec![code.literal_segment(&constructor.name)])),
+                then,
+            ));
+        }
+        decode_clauses.push(code.clause(discard(code.location), code.error()));
+
+        let snake_name = custom_type.name.to_snake_case();
+        let type_name = &custom_type.name;
+        let annotation = code.type_(type_name);
+        let to_json = Function {
+            name: Some((code.location, eco_format!("{snake_name}_to_json"))),
+            documentation: Some((
+                code.location.start,
+                eco_format!(" Encodes a `{type_name}` value as JSON.\n"),
+            )),
+            ..code.function(
+                custom_type.publicity,
+                vec![argument("value", annotation, code.location)],
+                code.case(vec![code.var("value")], encode_clauses),
+            )
+        };
+
+        let from_json = Function {
+            name: Some((code.location, eco_format!("{snake_name}_from_json"))),
+            documentation: Some((
+                code.location.start,
+                eco_format!(
+                    " Decodes a `{type_name}` value from JSON in the format produced by\n `{snake_name}_to_json`.\n"
+                ),
+            )),
+            ..code.function(
+                custom_type.publicity,
+                vec![argument("json", code.type_("String"), code.location)],
+                code.expect_ok_pattern(
+                    code.call(
+                        code.var(&decoder_name(type_name)),
+                        vec![code.bits(vec![code.utf8_segment(code.var("json"))])],
+                    ),
+                    code.tuple_pattern(vec![code.variable("value"), code.variable("rest")]),
+                    code.case(
+                        vec![code.call(
+                            self.use_helper(&code, Helper::SkipWhitespace),
+                            vec![code.var("rest")],
+                        )],
+                        vec![
+                            code.clause(code.bits_pattern(vec![]), code.ok(code.var("value"))),
+                            code.clause(discard(code.location), code.error()),
+                        ],
+                    ),
+                ),
+            )
+        };
+
+        let decode_tag = self.use_helper(&code, Helper::DecodeStringBits);
+        let tag = self.decode_field(&code, "type", decode_tag);
+        let decoder = Function {
+            name: Some((code.location, decoder_name(type_name))),
+            ..code.function(
+                Publicity::Private,
+                vec![argument("json", code.type_("BitArray"), code.location)],
+                code.expect_ok_pattern(
+                    code.call(
+                        self.use_helper(&code, Helper::DecodeObject),
+                        vec![code.var("json")],
+                    ),
+                    code.tuple_pattern(vec![code.variable("members"), code.variable("rest")]),
+                    code.case(vec![tag], decode_clauses),
+                ),
+            )
+        };
+
+        Ok(vec![to_json, from_json, decoder])
+    }
+
+    /// Returns the expression encoding a value of the given type, or `None` if
+    /// values of that type can't be encoded.
+    ///
+    fn encode(&mut self, code: &Code, type_: &TypeAst, value: UntypedExpr) -> Option<UntypedExpr> {
+        match self.field_type(type_)? {
+            FieldType::List(element) => {
+                let encode_element = self.encoder(code, element)?;
+                let list = code.call(
+                    self.use_helper(code, Helper::EncodeList),
+                    vec![value, encode_element, code.string("")],
+                );
+                Some(code.concat(vec![code.string("["), list, code.string("]")]))
+            }
+            FieldType::Int | FieldType::String | FieldType::Bool | FieldType::Derived(_) => {
+                Some(code.call(self.encoder(code, type_)?, vec![value]))
+            }
+        }
+    }
+
+    /// Returns the function encoding a value of the given type, or `None` if
+    /// values of that type can't be encoded.
+    ///
+    fn encoder(&mut self, code: &Code, type_: &TypeAst) -> Option<UntypedExpr> {
+        let helper = match self.field_type(type_)? {
+            FieldType::Int => Helper::EncodeInt,
+            FieldType::String => Helper::EncodeString,
+            FieldType::Bool => Helper::EncodeBool,
+            FieldType::List(_) => {
+                let encoded = self.encode(code, type_, code.var("element"))?;
+                return Some(code.anonymous_function("element", encoded));
+            }
+            FieldType::Derived(name) => {
+                return Some(code.var(&eco_format!("{}_to_json", name.to_snake_case())));
+            }
+        };
+        Some(self.use_helper(code, helper))
+    }
+
+    /// Returns the function decoding a value of the given type from the start
+    /// of a bit array, or `None` if values of that type can't be decoded.
+    ///
+    fn decode(&mut self, code: &Code, type_: &TypeAst) -> Option<UntypedExpr> {
+        let helper = match self.field_type(type_)? {
+            FieldType::Int => Helper::DecodeInt,
+            FieldType::String => Helper::DecodeString,
+            FieldType::Bool => Helper::DecodeBool,
+            FieldType::List(element) => {
+                let decode_element = self.decode(code, element)?;
+                let list = code.call(
+                    self.use_helper(code, Helper::DecodeList),
+                    vec![code.var("json"), decode_element],
+                );
+                return Some(code.anonymous_function("json", list));
+            }
+            FieldType::Derived(name) => return Some(code.var(&decoder_name(name))),
+        };
+        Some(self.use_helper(code, helper))
+    }
+
+    /// `derived_json_decode_field(members, <<"key":utf8>>, decoder)`
+    ///
+    fn decode_field(&mut self, code: &Code, key: &str, decoder: UntypedExpr) -> UntypedExpr {
+        code.call(
+            self.use_helper(code, Helper::DecodeField),
+            vec![
+                code.var("members"),
+                code.bits(vec![code.utf8_segment(code.string(key))]),
+                decoder,
+            ],
+        )
+    }
+
+    fn field_type<'t>(&self, type_: &'t TypeAst) -> Option<FieldType<'t>> {
+        let TypeAst::Constructor(TypeAstConstructor {
+            module: None,
+            name,
+            arguments,
+            ..
+        }) = type_
+        else {
+            return None;
+        };
+        match (name.as_str(), arguments.as_slice()) {
+            ("Int", []) => Some(FieldType::Int),
+            ("String", []) => Some(FieldType::String),
+            ("Bool", []) => Some(FieldType::Bool),
+            ("List", [element]) => Some(FieldType::List(element)),
+            (_, []) if self.derived_types.contains(name) => Some(FieldType::Derived(name)),
+            _ => None,
+        }
+    }
+
+    /// Records that a helper function is used, along with all the helpers it
+    /// relies on, and returns a reference to it.
+    ///
+    fn use_helper(&mut self, code: &Code, helper: Helper) -> UntypedExpr {
+        if self.helpers.insert(helper) {
+            for dependency in helper.dependencies() {
+                let _ = self.use_helper(code, *dependency);
+            }
+        }
+        code.var(helper.name())
+    }
+
+    /// Generates the private helper functions used by all the functions
+    /// generated so far.
+    ///
+    pub fn helper_functions(self) -> Vec<UntypedFunction> {
+        let code = Code::new(self.location);
+        self.helpers
+            .iter()
+            .map(|helper| helper_function(&code, *helper))
+            .collect()
+    }
+}
+
+enum FieldType<'a> {
+    Int,
+    String,
+    Bool,
+    List(&'a TypeAst),
+    Derived(&'a EcoString),
+}
+
+fn decoder_name(type_name: &str) -> EcoString {
+    eco_format!("decode_{}_json", type_name.to_snake_case())
+}
+
+/// The escape sequence used in a JSON string for each byte that can't appear
+/// in it as is.
+///
+fn escape_sequences() -> impl Iterator<Item = (u8, EcoString)> {
+    (0..32).chain([b'"', b'\\']).map(|byte| {
+        let sequence = match byte {
+            b'\t' => "\\t".into(),
+            b'\n' => "\\n".into(),
+            b'\r' => "\\r".into(),
+            b'"' => "\\\"".into(),
+            b'\\' => "\\\\".into(),
+            _ => eco_format!("\\u{byte:04x}"),
+        };
+        (byte, sequence)
+    })
+}
+
+/// The escape sequences of a JSON string made of a backslash followed by a
+/// single character, along with the byte each of them stands for.
+///
+const SHORT_ESCAPE_SEQUENCES: [(char, u8); 8] = [
+    ('"', b'"'),
+    ('\\', b'\\'),
+    ('/', b'/'),
+    ('b', 8),
+    ('f', 12),
+    ('n', b'\n'),
+    ('r', b'\r'),
+    ('t', b'\t'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Helper {
+    BitsToString,
+    EncodeInt,
+    EncodeDigits,
+    EncodeString,
+    EncodeEscapes,
+    EncodeBool,
+    EncodeList,
+    SkipWhitespace,
+    SkipValue,
+    SkipNumber,
+    DecodeObject,
+    DecodeMembers,
+    DecodeField,
+    DecodeInt,
+    DecodeNatural,
+    DecodeDigits,
+    DecodeString,
+    DecodeStringBits,
+    DecodeChars,
+    DecodeUnicodeEscape,
+    DecodeHex,
+    EncodeCodepoint,
+    DecodeBool,
+    DecodeList,
+    DecodeItems,
+}
+
+impl Helper {
+    fn name(&self) -> &'static str {
+        match self {
+            Helper::BitsToString => "derived_json_bits_to_string",
+            Helper::EncodeInt => "derived_json_encode_int",
+            Helper::EncodeDigits => "derived_json_encode_digits",
+            Helper::EncodeString => "derived_json_encode_string",
+            Helper::EncodeEscapes => "derived_json_encode_escapes",
+            Helper::EncodeBool => "derived_json_encode_bool",
+            Helper::EncodeList => "derived_json_encode_list",
+            Helper::SkipWhitespace => "derived_json_skip_whitespace",
+            Helper::SkipValue => "derived_json_skip_value",
+            Helper::SkipNumber => "derived_json_skip_number",
+            Helper::DecodeObject => "derived_json_decode_object",
+            Helper::DecodeMembers => "derived_json_decode_members",
+            Helper::DecodeField => "derived_json_decode_field",
+            Helper::DecodeInt => "derived_json_decode_int",
+            Helper::DecodeNatural => "derived_json_decode_natural",
+            Helper::DecodeDigits => "derived_json_decode_digits",
+            Helper::DecodeString => "derived_json_decode_string",
+            Helper::DecodeStringBits => "derived_json_decode_string_bits",
+            Helper::DecodeChars => "derived_json_decode_chars",
+            Helper::DecodeUnicodeEscape => "derived_json_decode_unicode_escape",
+            Helper::DecodeHex => "derived_json_decode_hex",
+            Helper::EncodeCodepoint => "derived_json_encode_codepoint",
+            Helper::DecodeBool => "derived_json_decode_bool",
+            Helper::DecodeList => "derived_json_decode_list",
+            Helper::DecodeItems => "derived_json_decode_items",
+        }
+    }
+
+    fn dependencies(&self) -> &'static [Helper] {
+        match self {
+            Helper::EncodeInt => &[Helper::EncodeDigits, Helper::BitsToString],
+            Helper::EncodeString => &[Helper::EncodeEscapes, Helper::BitsToString],
+            Helper::SkipValue => &[
+                Helper::SkipWhitespace,
+                Helper::DecodeStringBits,
+                Helper::DecodeObject,
+                Helper::DecodeList,
+                Helper::SkipNumber,
+            ],
+            Helper::DecodeObject => &[Helper::SkipWhitespace, Helper::DecodeMembers],
+            Helper::DecodeMembers => &[
+                Helper::DecodeStringBits,
+                Helper::SkipWhitespace,
+                Helper::SkipValue,
+            ],
+            Helper::DecodeInt => &[Helper::SkipWhitespace, Helper::DecodeNatural],
+            Helper::DecodeNatural => &[Helper::DecodeDigits],
+            Helper::DecodeString => &[Helper::DecodeStringBits, Helper::BitsToString],
+            Helper::DecodeStringBits => &[Helper::SkipWhitespace, Helper::DecodeChars],
+            Helper::DecodeChars => &[Helper::DecodeUnicodeEscape],
+            Helper::DecodeUnicodeEscape => &[
+                Helper::DecodeHex,
+                Helper::EncodeCodepoint,
+                Helper::DecodeChars,
+            ],
+            Helper::DecodeBool => &[Helper::SkipWhitespace],
+            Helper::DecodeList => &[Helper::SkipWhitespace, Helper::DecodeItems],
+            Helper::DecodeItems => &[Helper::SkipWhitespace],
+            Helper::BitsToString
+            | Helper::EncodeDigits
+            | Helper::EncodeEscapes
+            | Helper::EncodeBool
+            | Helper::EncodeList
+            | Helper::SkipWhitespace
+            | Helper::SkipNumber
+            | Helper::DecodeField
+            | Helper::DecodeDigits
+            | Helper::DecodeHex
+            | Helper::EncodeCodepoint => &[],
+        }
+    }
+}
+
+fn helper_function(code: &Code, helper: Helper) -> UntypedFunction {
+    let location = code.location;
+    let helper_var = |helper: Helper| code.var(helper.name());
+    let call = |helper: Helper, arguments| code.call(helper_var(helper), arguments);
+    let untyped = |name: &str| code.untyped_argument(name);
+    let skip_whitespace = |json| call(Helper::SkipWhitespace, vec![json]);
+
+    let (arguments, body) = match helper {
+        // The bytes of a string are the string itself on Erlang, so the
+        // conversion only needs to do some work on JavaScript, where the
+        // compiler defines it in the generated module.
+        Helper::BitsToString => {
+            return Function {
+                name: Some((location, helper.name().into())),
+                return_annotation: Some(code.type_("String")),
+                external_erlang: Some(("erlang".into(), "iolist_to_binary".into(), location)),
+                external_javascript: Some((
+                    INLINED_EXTERNAL_MODULE.into(),
+                    helper.name().into(),
+                    location,
+                )),
+                ..code.function(
+                    Publicity::Private,
+                    vec![argument("bits", code.type_("BitArray"), location)],
+                    UntypedExpr::Placeholder { location },
+                )
+            };
+        }
+
+        // fn derived_json_encode_int(value) {
+        //   case value < 0 {
+        //     True -> "-" <> derived_json_encode_int(0 - value)
+        //     False -> derived_json_bits_to_string(derived_json_encode_digits(value, <<>>))
+        //   }
+        // }
+        Helper::EncodeInt => (
+            vec![untyped("value")],
+            code.case(
+                vec![code.binop(BinOp::LtInt, code.var("value"), int(0, location))],
+                vec![
+                    code.clause(
+                        code.constructor_pattern("True", vec![]),
+                        code.concat(vec![
+                            code.string("-"),
+                            call(
+                                Helper::EncodeInt,
+                                vec![code.binop(
+                                    BinOp::SubInt,
+                                    int(0, location),
+                                    code.var("value"),
+                                )],
+                            ),
+                        ]),
+                    ),
+                    code.clause(
+                        code.constructor_pattern("False", vec![]),
+                        call(
+                            Helper::BitsToString,
+                            vec![call(
+                                Helper::EncodeDigits,
+                                vec![code.var("value"), code.bits(vec![])],
+                            )],
+                        ),
+                    ),
+                ],
+            ),
+        ),
+
+        // fn derived_json_encode_digits(value, digits) {
+        //   let digit = value % 10 + 48
+        //   let digits = <<digit, digits:bits>>
+        //   case value < 10 {
+        //     True -> digits
+        //     False -> derived_json_encode_digits(value / 10, digits)
+        //   }
+        // }
+        Helper::EncodeDigits => {
+            let digit = code.binop(
+                BinOp::AddInt,
+                code.binop(BinOp::RemainderInt, code.var("value"), int(10, location)),
+                int(48, location),
+            );
+            let digits = code.bits(vec![
+                code.segment(code.var("digit")),
+                code.bits_segment(code.var("digits")),
+            ]);
+            let recurse = call(
+                Helper::EncodeDigits,
+                vec![
+                    code.binop(BinOp::DivInt, code.var("value"), int(10, location)),
+                    code.var("digits"),
+                ],
+            );
+            return Function {
+                body: vec1![
+                    let_("digit", digit, location),
+                    let_("digits", digits, location),
+                    Statement::Expression(code.case(
+                        vec![code.binop(BinOp::LtInt, code.var("value"), int(10, location))],
+                        vec![
+                            code.clause(
+                                code.constructor_pattern("True", vec![]),
+                                code.var("digits")
+                            ),
+                            code.clause(code.constructor_pattern("False", vec![]), recurse),
+                        ],
+                    )),
+                ],
+                ..helper_definition(code, helper, vec![untyped("value"), untyped("digits")])
+            };
+        }
+
+        // fn derived_json_encode_string(value) {
+        //   derived_json_bits_to_string(derived_json_encode_escapes(<<value:utf8>>, <<"\"":utf8>>))
+        // }
+        Helper::EncodeString => (
+            vec![untyped("value")],
+            call(
+                Helper::BitsToString,
+                vec![call(
+                    Helper::EncodeEscapes,
+                    vec![
+                        code.bits(vec![code.utf8_segment(code.var("value"))]),
+                        code.bits(vec![code.utf8_segment(code.string("\""))]),
+                    ],
+                )],
+            ),
+        ),
+
+        // fn derived_json_encode_escapes(bits, json) {
+        //   case bits {
+        //     <<34, rest:bytes>> -> derived_json_encode_escapes(rest, <<json:bits, "\\\"":utf8>>)
+        //     ...
+        //     <<byte, rest:bytes>> -> derived_json_encode_escapes(rest, <<json:bits, byte>>)
+        //     _ -> <<json:bits, "\"":utf8>>
+        //   }
+        // }
+        Helper::EncodeEscapes => {
+            let recurse = |segment| {
+                call(
+                    Helper::EncodeEscapes,
+                    vec![
+                        code.var("rest"),
+                        code.bits(vec![code.bits_segment(code.var("json")), segment]),
+                    ],
+                )
+            };
+            let mut clauses: Vec<_> = escape_sequences()
+                .map(|(byte, sequence)| {
+                    code.clause(
+                        code.bits_pattern(vec![code.byte_segment(byte), code.rest_segment()]),
+                        recurse(code.utf8_segment(code.string(&sequence))),
+                    )
+                })
+                .collect();
+            clauses.push(code.clause(
+                code.bits_pattern(vec![
+                    code.segment(code.variable("byte")),
+                    code.rest_segment(),
+                ]),
+                recurse(code.segment(code.var("byte"))),
+            ));
+            clauses.push(code.clause(
+                discard(location),
+                code.bits(vec![
+                    code.bits_segment(code.var("json")),
+                    code.utf8_segment(code.string("\"")),
+                ]),
+            ));
+            (
+                vec![untyped("bits"), untyped("json")],
+                code.case(vec![code.var("bits")], clauses),
+            )
+        }
+
+        // fn derived_json_encode_bool(value) {
+        //   case value {
+        //     True -> "true"
+        //     False -> "false"
+        //   }
+        // }
+        Helper::EncodeBool => (
+            vec![untyped("value")],
+            code.case(
+                vec![code.var("value")],
+                vec![
+                    code.clause(
+                        code.constructor_pattern("True", vec![]),
+                        code.string("true"),
+                    ),
+                    code.clause(
+                        code.constructor_pattern("False", vec![]),
+                        code.string("false"),
+                    ),
+                ],
+            ),
+        ),
+
+        // fn derived_json_encode_list(elements, encode, separator) {
+        //   case elements {
+        //     [] -> ""
+        //     [element, ..rest] ->
+        //       separator <> encode(element) <> derived_json_encode_list(rest, encode, ",")
+        //   }
+        // }
+        Helper::EncodeList => (
+            vec![untyped("elements"), untyped("encode"), untyped("separator")],
+            code.case(
+                vec![code.var("elements")],
+                vec![
+                    code.clause(code.list_pattern(vec![], None), code.string("")),
+                    code.clause(
+                        code.list_pattern(
+                            vec![code.variable("element")],
+                            Some(code.variable("rest")),
+                        ),
+                        code.concat(vec![
+                            code.var("separator"),
+                            code.call(code.var("encode"), vec![code.var("element")]),
+                            call(
+                                Helper::EncodeList,
+                                vec![code.var("rest"), code.var("encode"), code.string(",")],
+                            ),
+                        ]),
+                    ),
+                ],
+            ),
+        ),
+
+        // fn derived_json_skip_whitespace(json) {
+        //   case json {
+        //     <<32, rest:bytes>> -> derived_json_skip_whitespace(rest)
+        //     ...
+        //     _ -> json
+        //   }
+        // }
+        Helper::SkipWhitespace => {
+            let mut clauses: Vec<_> = [b' ', b'\t', b'\n', b'\r']
+                .into_iter()
+                .map(|byte| {
+                    code.clause(
+                        code.bits_pattern(vec![code.byte_segment(byte), code.rest_segment()]),
+                        skip_whitespace(code.var("rest")),
+                    )
+                })
+                .collect();
+            clauses.push(code.clause(discard(location), code.var("json")));
+            (
+                vec![untyped("json")],
+                code.case(vec![code.var("json")], clauses),
+            )
+        }
+
+        // fn derived_json_skip_value(json) {
+        //   let json = derived_json_skip_whitespace(json)
+        //   case json {
+        //     <<"\"":utf8, _:bytes>> ->
+        //       case derived_json_decode_string_bits(json) {
+        //         Ok(#(_, rest)) -> Ok(rest)
+        //         _ -> Error(Nil)
+        //       }
+        //     <<"{":utf8, _:bytes>> -> ...
+        //     <<"[":utf8, _:bytes>> -> ...
+        //     <<"true":utf8, rest:bytes>> -> Ok(rest)
+        //     <<"false":utf8, rest:bytes>> -> Ok(rest)
+        //     <<"null":utf8, rest:bytes>> -> Ok(rest)
+        //     <<"-":utf8, digit, rest:bytes>> if digit >= 48 && digit <= 57 ->
+        //       Ok(derived_json_skip_number(rest))
+        //     <<digit, rest:bytes>> if digit >= 48 && digit <= 57 ->
+        //       Ok(derived_json_skip_number(rest))
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::SkipValue => {
+            let skipped = |decoded| {
+                code.expect_ok_pattern(
+                    decoded,
+                    code.tuple_pattern(vec![discard(location), code.variable("rest")]),
+                    code.ok(code.var("rest")),
+                )
+            };
+            let skip_element = code.anonymous_function(
+                "json",
+                code.expect_ok_pattern(
+                    call(Helper::SkipValue, vec![code.var("json")]),
+                    code.variable("rest"),
+                    code.ok(code.tuple(vec![code.var("Nil"), code.var("rest")])),
+                ),
+            );
+            let mut clauses = vec![
+                code.clause(
+                    code.prefix_pattern("\""),
+                    skipped(call(Helper::DecodeStringBits, vec![code.var("json")])),
+                ),
+                code.clause(
+                    code.prefix_pattern("{"),
+                    skipped(call(Helper::DecodeObject, vec![code.var("json")])),
+                ),
+                code.clause(
+                    code.prefix_pattern("["),
+                    skipped(call(
+                        Helper::DecodeList,
+                        vec![code.var("json"), skip_element],
+                    )),
+                ),
+            ];
+            clauses.extend(["true", "false", "null"].map(|literal| {
+                code.clause(
+                    code.bits_pattern(vec![code.literal_segment(literal), code.rest_segment()]),
+                    code.ok(code.var("rest")),
+                )
+            }));
+            let number = || code.ok(call(Helper::SkipNumber, vec![code.var("rest")]));
+            clauses.push(code.guarded_clause(
+                code.bits_pattern(vec![
+                    code.literal_segment("-"),
+                    code.segment(code.variable("digit")),
+                    code.rest_segment(),
+                ]),
+                code.guard_between("digit", b'0'.into(), b'9'.into()),
+                number(),
+            ));
+            clauses.push(code.guarded_clause(
+                code.bits_pattern(vec![
+                    code.segment(code.variable("digit")),
+                    code.rest_segment(),
+                ]),
+                code.guard_between("digit", b'0'.into(), b'9'.into()),
+                number(),
+            ));
+            clauses.push(code.clause(discard(location), code.error()));
+            return Function {
+                body: vec1![
+                    let_("json", skip_whitespace(code.var("json")), location),
+                    Statement::Expression(code.case(vec![code.var("json")], clauses)),
+                ],
+                ..helper_definition(code, helper, vec![untyped("json")])
+            };
+        }
+
+        // Numbers that aren't decoded are only checked to start like a number.
+        //
+        // fn derived_json_skip_number(json) {
+        //   case json {
+        //     <<digit, rest:bytes>> if digit >= 48 && digit <= 57 ->
+        //       derived_json_skip_number(rest)
+        //     <<".":utf8, rest:bytes>> -> derived_json_skip_number(rest)
+        //     ...
+        //     _ -> json
+        //   }
+        // }
+        Helper::SkipNumber => {
+            let recurse = || call(Helper::SkipNumber, vec![code.var("rest")]);
+            let mut clauses = vec![code.guarded_clause(
+                code.bits_pattern(vec![
+                    code.segment(code.variable("digit")),
+                    code.rest_segment(),
+                ]),
+                code.guard_between("digit", b'0'.into(), b'9'.into()),
+                recurse(),
+            )];
+            clauses.extend([".", "e", "E", "+", "-"].map(|literal| {
+                code.clause(
+                    code.bits_pattern(vec![code.literal_segment(literal), code.rest_segment()]),
+                    recurse(),
+                )
+            }));
+            clauses.push(code.clause(discard(location), code.var("json")));
+            (
+                vec![untyped("json")],
+                code.case(vec![code.var("json")], clauses),
+            )
+        }
+
+        // fn derived_json_decode_object(json) {
+        //   case derived_json_skip_whitespace(json) {
+        //     <<"{":utf8, rest:bytes>> ->
+        //       case derived_json_skip_whitespace(rest) {
+        //         <<"}":utf8, rest:bytes>> -> Ok(#([], rest))
+        //         _ -> derived_json_decode_members(rest, [])
+        //       }
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeObject => (
+            vec![untyped("json")],
+            code.expect_prefix(
+                skip_whitespace(code.var("json")),
+                "{",
+                code.case(
+                    vec![skip_whitespace(code.var("rest"))],
+                    vec![
+                        code.clause(
+                            code.bits_pattern(vec![code.literal_segment("}"), code.rest_segment()]),
+                            code.ok(code.tuple(vec![code.list(vec![], None), code.var("rest")])),
+                        ),
+                        code.clause(
+                            discard(location),
+                            call(
+                                Helper::DecodeMembers,
+                                vec![code.var("rest"), code.list(vec![], None)],
+                            ),
+                        ),
+                    ],
+                ),
+            ),
+        ),
+
+        // Each member is made of its key and the JSON starting with its value,
+        // which is only decoded once the expected type of the value is known.
+        // The members are listed from the last one, so if a key appears more
+        // than once it is its last value that's found first.
+        //
+        // fn derived_json_decode_members(json, members) {
+        //   case derived_json_decode_string_bits(json) {
+        //     Ok(#(key, rest)) ->
+        //       case derived_json_skip_whitespace(rest) {
+        //         <<":":utf8, value:bytes>> ->
+        //           case derived_json_skip_value(value) {
+        //             Ok(rest) ->
+        //               case derived_json_skip_whitespace(rest) {
+        //                 <<",":utf8, rest:bytes>> ->
+        //                   derived_json_decode_members(rest, [#(key, value), ..members])
+        //                 <<"}":utf8, rest:bytes>> -> Ok(#([#(key, value), ..members], rest))
+        //                 _ -> Error(Nil)
+        //               }
+        //             _ -> Error(Nil)
+        //           }
+        //         _ -> Error(Nil)
+        //       }
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeMembers => {
+            let members = || {
+                code.list(
+                    vec![code.tuple(vec![code.var("key"), code.var("value")])],
+                    Some(code.var("members")),
+                )
+            };
+            let next = code.case(
+                vec![skip_whitespace(code.var("rest"))],
+                vec![
+                    code.clause(
+                        code.bits_pattern(vec![code.literal_segment(","), code.rest_segment()]),
+                        call(Helper::DecodeMembers, vec![code.var("rest"), members()]),
+                    ),
+                    code.clause(
+                        code.bits_pattern(vec![code.literal_segment("}"), code.rest_segment()]),
+                        code.ok(code.tuple(vec![members(), code.var("rest")])),
+                    ),
+                    code.clause(discard(location), code.error()),
+                ],
+            );
+            let value = code.case(
+                vec![skip_whitespace(code.var("rest"))],
+                vec![
+                    code.clause(
+                        code.bits_pattern(vec![
+                            code.literal_segment(":"),
+                            code.bytes_segment(code.variable("value")),
+                        ]),
+                        code.expect_ok_pattern(
+                            call(Helper::SkipValue, vec![code.var("value")]),
+                            code.variable("rest"),
+                            next,
+                        ),
+                    ),
+                    code.clause(discard(location), code.error()),
+                ],
+            );
+            (
+                vec![untyped("json"), untyped("members")],
+                code.expect_ok(
+                    call(Helper::DecodeStringBits, vec![code.var("json")]),
+                    "key",
+                    value,
+                ),
+            )
+        }
+
+        // fn derived_json_decode_field(members, key, decode) {
+        //   case members {
+        //     [#(name, value), ..] if name == key ->
+        //       case decode(value) {
+        //         Ok(#(field, _)) -> Ok(field)
+        //         _ -> Error(Nil)
+        //       }
+        //     [_, ..rest] -> derived_json_decode_field(rest, key, decode)
+        //     [] -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeField => {
+            let member = code.tuple_pattern(vec![code.variable("name"), code.variable("value")]);
+            let is_key = ClauseGuard::Equals {
+                location,
+                left: Box::new(code.guard_var("name")),
+                right: Box::new(code.guard_var("key")),
+            };
+            let decoded = code.expect_ok_pattern(
+                code.call(code.var("decode"), vec![code.var("value")]),
+                code.tuple_pattern(vec![code.variable("field"), discard(location)]),
+                code.ok(code.var("field")),
+            );
+            (
+                vec![untyped("members"), untyped("key"), untyped("decode")],
+                code.case(
+                    vec![code.var("members")],
+                    vec![
+                        code.guarded_clause(
+                            code.list_pattern(vec![member], Some(discard(location))),
+                            is_key,
+                            decoded,
+                        ),
+                        code.clause(
+                            code.list_pattern(vec![discard(location)], Some(code.variable("rest"))),
+                            call(
+                                Helper::DecodeField,
+                                vec![code.var("rest"), code.var("key"), code.var("decode")],
+                            ),
+                        ),
+                        code.clause(code.list_pattern(vec![], None), code.error()),
+                    ],
+                ),
+            )
+        }
+
+        // fn derived_json_decode_int(json) {
+        //   case derived_json_skip_whitespace(json) {
+        //     <<"-":utf8, rest:bytes>> ->
+        //       case derived_json_decode_natural(rest) {
+        //         Ok(#(value, rest)) -> Ok(#(0 - value, rest))
+        //         _ -> Error(Nil)
+        //       }
+        //     json -> derived_json_decode_natural(json)
+        //   }
+        // }
+        Helper::DecodeInt => {
+            let negated = code.ok(code.tuple(vec![
+                code.binop(BinOp::SubInt, int(0, location), code.var("value")),
+                code.var("rest"),
+            ]));
+            (
+                vec![untyped("json")],
+                code.case(
+                    vec![skip_whitespace(code.var("json"))],
+                    vec![
+                        code.clause(
+                            code.bits_pattern(vec![code.literal_segment("-"), code.rest_segment()]),
+                            code.expect_ok(
+                                call(Helper::DecodeNatural, vec![code.var("rest")]),
+                                "value",
+                                negated,
+                            ),
+                        ),
+                        code.clause(
+                            code.variable("json"),
+                            call(Helper::DecodeNatural, vec![code.var("json")]),
+                        ),
+                    ],
+                ),
+            )
+        }
+
+        // A number can't start with a zero followed by other digits.
+        //
+        // fn derived_json_decode_natural(json) {
+        //   case json {
+        //     <<48, digit, _:bytes>> if digit >= 48 && digit <= 57 -> Error(Nil)
+        //     <<digit, _:bytes>> if digit >= 48 && digit <= 57 ->
+        //       derived_json_decode_digits(json, 0)
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeNatural => (
+            vec![untyped("json")],
+            code.case(
+                vec![code.var("json")],
+                vec![
+                    code.guarded_clause(
+                        code.bits_pattern(vec![
+                            code.byte_segment(b'0'),
+                            code.segment(code.variable("digit")),
+                            code.bytes_segment(discard(location)),
+                        ]),
+                        code.guard_between("digit", b'0'.into(), b'9'.into()),
+                        code.error(),
+                    ),
+                    code.guarded_clause(
+                        code.bits_pattern(vec![
+                            code.segment(code.variable("digit")),
+                            code.bytes_segment(discard(location)),
+                        ]),
+                        code.guard_between("digit", b'0'.into(), b'9'.into()),
+                        call(
+                            Helper::DecodeDigits,
+                            vec![code.var("json"), int(0, location)],
+                        ),
+                    ),
+                    code.clause(discard(location), code.error()),
+                ],
+            ),
+        ),
+
+        // A number with a fraction or an exponent isn't an `Int`.
+        //
+        // fn derived_json_decode_digits(json, value) {
+        //   case json {
+        //     <<digit, rest:bytes>> if digit >= 48 && digit <= 57 ->
+        //       derived_json_decode_digits(rest, value * 10 + digit - 48)
+        //     <<".":utf8, _:bytes>> -> Error(Nil)
+        //     <<"e":utf8, _:bytes>> -> Error(Nil)
+        //     <<"E":utf8, _:bytes>> -> Error(Nil)
+        //     _ -> Ok(#(value, json))
+        //   }
+        // }
+        Helper::DecodeDigits => {
+            let value = code.binop(
+                BinOp::SubInt,
+                code.binop(
+                    BinOp::AddInt,
+                    code.binop(BinOp::MultInt, code.var("value"), int(10, location)),
+                    code.var("digit"),
+                ),
+                int(48, location),
+            );
+            let mut clauses = vec![code.guarded_clause(
+                code.bits_pattern(vec![
+                    code.segment(code.variable("digit")),
+                    code.rest_segment(),
+                ]),
+                code.guard_between("digit", b'0'.into(), b'9'.into()),
+                call(Helper::DecodeDigits, vec![code.var("rest"), value]),
+            )];
+            clauses.extend(
+                [".", "e", "E"]
+                    .map(|literal| code.clause(code.prefix_pattern(literal), code.error())),
+            );
+            clauses.push(code.clause(
+                discard(location),
+                code.ok(code.tuple(vec![code.var("value"), code.var("json")])),
+            ));
+            (
+                vec![untyped("json"), untyped("value")],
+                code.case(vec![code.var("json")], clauses),
+            )
+        }
+
+        // fn derived_json_decode_string(json) {
+        //   case derived_json_decode_string_bits(json) {
+        //     Ok(#(bits, rest)) -> Ok(#(derived_json_bits_to_string(bits), rest))
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeString => (
+            vec![untyped("json")],
+            code.expect_ok(
+                call(Helper::DecodeStringBits, vec![code.var("json")]),
+                "bits",
+                code.ok(code.tuple(vec![
+                    call(Helper::BitsToString, vec![code.var("bits")]),
+                    code.var("rest"),
+                ])),
+            ),
+        ),
+
+        // fn derived_json_decode_string_bits(json) {
+        //   case derived_json_skip_whitespace(json) {
+        //     <<"\"":utf8, rest:bytes>> -> derived_json_decode_chars(rest, <<>>)
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeStringBits => (
+            vec![untyped("json")],
+            code.expect_prefix(
+                skip_whitespace(code.var("json")),
+                "\"",
+                call(
+                    Helper::DecodeChars,
+                    vec![code.var("rest"), code.bits(vec![])],
+                ),
+            ),
+        ),
+
+        // fn derived_json_decode_chars(json, bits) {
+        //   case json {
+        //     <<"\"":utf8, rest:bytes>> -> Ok(#(bits, rest))
+        //     <<"\\\"":utf8, rest:bytes>> -> derived_json_decode_chars(rest, <<bits:bits, 34>>)
+        //     ...
+        //     <<"\\u":utf8, rest:bytes>> -> derived_json_decode_unicode_escape(rest, bits)
+        //     <<byte, rest:bytes>> if byte >= 32 && byte != 92 ->
+        //       derived_json_decode_chars(rest, <<bits:bits, byte>>)
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeChars => {
+            let recurse = |segment| {
+                call(
+                    Helper::DecodeChars,
+                    vec![
+                        code.var("rest"),
+                        code.bits(vec![code.bits_segment(code.var("bits")), segment]),
+                    ],
+                )
+            };
+            let mut clauses = vec![code.clause(
+                code.bits_pattern(vec![code.literal_segment("\""), code.rest_segment()]),
+                code.ok(code.tuple(vec![code.var("bits"), code.var("rest")])),
+            )];
+            clauses.extend(SHORT_ESCAPE_SEQUENCES.map(|(character, byte)| {
+                code.clause(
+                    code.bits_pattern(vec![
+                        code.literal_segment(&eco_format!("\\{character}")),
+                        code.rest_segment(),
+                    ]),
+                    recurse(code.segment(int(byte.into(), location))),
+                )
+            }));
+            clauses.push(code.clause(
+                code.bits_pattern(vec![code.literal_segment("\\u"), code.rest_segment()]),
+                call(
+                    Helper::DecodeUnicodeEscape,
+                    vec![code.var("rest"), code.var("bits")],
+                ),
+            ));
+            let is_unescaped = ClauseGuard::And {
+                location,
+                left: Box::new(ClauseGuard::GtEqInt {
+                    location,
+                    left: Box::new(code.guard_var("byte")),
+                    right: Box::new(code.guard_int(32)),
+                }),
+                right: Box::new(ClauseGuard::NotEquals {
+                    location,
+                    left: Box::new(code.guard_var("byte")),
+                    right: Box::new(code.guard_int(b'\\'.into())),
+                }),
+            };
+            clauses.push(code.guarded_clause(
+                code.bits_pattern(vec![
+                    code.segment(code.variable("byte")),
+                    code.rest_segment(),
+                ]),
+                is_unescaped,
+                recurse(code.segment(code.var("byte"))),
+            ));
+            clauses.push(code.clause(discard(location), code.error()));
+            (
+                vec![untyped("json"), untyped("bits")],
+                code.case(vec![code.var("json")], clauses),
+            )
+        }
+
+        // A codepoint outside of the Basic Multilingual Plane is escaped as a
+        // pair of surrogates, which can't appear on their own.
+        //
+        // fn derived_json_decode_unicode_escape(json, bits) {
+        //   case derived_json_decode_hex(json, 0, 0) {
+        //     Ok(#(high, <<"\\u":utf8, rest:bytes>>)) if high >= 55296 && high <= 56319 ->
+        //       case derived_json_decode_hex(rest, 0, 0) {
+        //         Ok(#(low, rest)) if low >= 56320 && low <= 57343 ->
+        //           derived_json_decode_chars(rest, <<
+        //             bits:bits,
+        //             derived_json_encode_codepoint({high - 55296} * 1024 + {low - 56320} + 65536):bits,
+        //           >>)
+        //         _ -> Error(Nil)
+        //       }
+        //     Ok(#(codepoint, rest)) if codepoint < 55296 || codepoint > 57343 ->
+        //       derived_json_decode_chars(rest, <<
+        //         bits:bits,
+        //         derived_json_encode_codepoint(codepoint):bits,
+        //       >>)
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeUnicodeEscape => {
+            let hex = |json| {
+                call(
+                    Helper::DecodeHex,
+                    vec![json, int(0, location), int(0, location)],
+                )
+            };
+            let append = |codepoint| {
+                call(
+                    Helper::DecodeChars,
+                    vec![
+                        code.var("rest"),
+                        code.bits(vec![
+                            code.bits_segment(code.var("bits")),
+                            code.bits_segment(call(Helper::EncodeCodepoint, vec![codepoint])),
+                        ]),
+                    ],
+                )
+            };
+            let offset = |name: &str, surrogates: i64| {
+                code.binop(BinOp::SubInt, code.var(name), int(surrogates, location))
+            };
+            let pair = code.binop(
+                BinOp::AddInt,
+                code.binop(
+                    BinOp::AddInt,
+                    code.binop(BinOp::MultInt, offset("high", 0xD800), int(1024, location)),
+                    offset("low", 0xDC00),
+                ),
+                int(0x10000, location),
+            );
+            let low = code.case(
+                vec![hex(code.var("rest"))],
+                vec![
+                    code.guarded_clause(
+                        code.ok_pattern(
+                            code.tuple_pattern(vec![code.variable("low"), code.variable("rest")]),
+                        ),
+                        code.guard_between("low", 0xDC00, 0xDFFF),
+                        append(pair),
+                    ),
+                    code.clause(discard(location), code.error()),
+                ],
+            );
+            let is_not_a_surrogate = ClauseGuard::Or {
+                location,
+                left: Box::new(ClauseGuard::LtInt {
+                    location,
+                    left: Box::new(code.guard_var("codepoint")),
+                    right: Box::new(code.guard_int(0xD800)),
+                }),
+                right: Box::new(ClauseGuard::GtInt {
+                    location,
+                    left: Box::new(code.guard_var("codepoint")),
+                    right: Box::new(code.guard_int(0xDFFF)),
+                }),
+            };
+            (
+                vec![untyped("json"), untyped("bits")],
+                code.case(
+                    vec![hex(code.var("json"))],
+                    vec![
+                        code.guarded_clause(
+                            code.ok_pattern(code.tuple_pattern(vec![
+                                code.variable("high"),
+                                code.bits_pattern(vec![
+                                    code.literal_segment("\\u"),
+                                    code.rest_segment(),
+                                ]),
+                            ])),
+                            code.guard_between("high", 0xD800, 0xDBFF),
+                            low,
+                        ),
+                        code.guarded_clause(
+                            code.ok_pattern(code.tuple_pattern(vec![
+                                code.variable("codepoint"),
+                                code.variable("rest"),
+                            ])),
+                            is_not_a_surrogate,
+                            append(code.var("codepoint")),
+                        ),
+                        code.clause(discard(location), code.error()),
+                    ],
+                ),
+            )
+        }
+
+        // fn derived_json_decode_hex(json, value, count) {
+        //   case json {
+        //     _ if count == 4 -> Ok(#(value, json))
+        //     <<digit, rest:bytes>> if digit >= 48 && digit <= 57 ->
+        //       derived_json_decode_hex(rest, value * 16 + digit - 48, count + 1)
+        //     <<digit, rest:bytes>> if digit >= 65 && digit <= 70 ->
+        //       derived_json_decode_hex(rest, value * 16 + digit - 55, count + 1)
+        //     <<digit, rest:bytes>> if digit >= 97 && digit <= 102 ->
+        //       derived_json_decode_hex(rest, value * 16 + digit - 87, count + 1)
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeHex => {
+            let is_done = ClauseGuard::Equals {
+                location,
+                left: Box::new(code.guard_var("count")),
+                right: Box::new(code.guard_int(4)),
+            };
+            let mut clauses = vec![code.guarded_clause(
+                discard(location),
+                is_done,
+                code.ok(code.tuple(vec![code.var("value"), code.var("json")])),
+            )];
+            clauses.extend([(b'0', b'9', 48), (b'A', b'F', 55), (b'a', b'f', 87)].map(
+                |(low, high, offset)| {
+                    let value = code.binop(
+                        BinOp::SubInt,
+                        code.binop(
+                            BinOp::AddInt,
+                            code.binop(BinOp::MultInt, code.var("value"), int(16, location)),
+                            code.var("digit"),
+                        ),
+                        int(offset, location),
+                    );
+                    code.guarded_clause(
+                        code.bits_pattern(vec![
+                            code.segment(code.variable("digit")),
+                            code.rest_segment(),
+                        ]),
+                        code.guard_between("digit", low.into(), high.into()),
+                        call(
+                            Helper::DecodeHex,
+                            vec![
+                                code.var("rest"),
+                                value,
+                                code.binop(BinOp::AddInt, code.var("count"), int(1, location)),
+                            ],
+                        ),
+                    )
+                },
+            ));
+            clauses.push(code.clause(discard(location), code.error()));
+            (
+                vec![untyped("json"), untyped("value"), untyped("count")],
+                code.case(vec![code.var("json")], clauses),
+            )
+        }
+
+        // fn derived_json_encode_codepoint(codepoint) {
+        //   case codepoint {
+        //     _ if codepoint < 128 -> <<codepoint>>
+        //     _ if codepoint < 2048 -> <<192 + codepoint / 64, 128 + codepoint % 64>>
+        //     _ if codepoint < 65536 ->
+        //       <<224 + codepoint / 4096, 128 + codepoint / 64 % 64, 128 + codepoint % 64>>
+        //     _ -> <<240 + codepoint / 262144, 128 + codepoint / 4096 % 64, ...>>
+        //   }
+        // }
+        Helper::EncodeCodepoint => {
+            // The bits of the codepoint from the given power of 64.
+            let bits = |divisor: i64| match divisor {
+                1 => code.var("codepoint"),
+                _ => code.binop(BinOp::DivInt, code.var("codepoint"), int(divisor, location)),
+            };
+            let bytes = |leading: i64, length: u32| {
+                let mut segments = vec![code.segment(code.binop(
+                    BinOp::AddInt,
+                    int(leading, location),
+                    bits(64_i64.pow(length - 1)),
+                ))];
+                segments.extend((0..length - 1).rev().map(|power| {
+                    code.segment(code.binop(
+                        BinOp::AddInt,
+                        int(128, location),
+                        code.binop(
+                            BinOp::RemainderInt,
+                            bits(64_i64.pow(power)),
+                            int(64, location),
+                        ),
+                    ))
+                }));
+                code.bits(segments)
+            };
+            let below = |limit: u32| ClauseGuard::LtInt {
+                location,
+                left: Box::new(code.guard_var("codepoint")),
+                right: Box::new(code.guard_int(limit)),
+            };
+            (
+                vec![untyped("codepoint")],
+                code.case(
+                    vec![code.var("codepoint")],
+                    vec![
+                        code.guarded_clause(
+                            discard(location),
+                            below(0x80),
+                            code.bits(vec![code.segment(code.var("codepoint"))]),
+                        ),
+                        code.guarded_clause(discard(location), below(0x800), bytes(192, 2)),
+                        code.guarded_clause(discard(location), below(0x10000), bytes(224, 3)),
+                        code.clause(discard(location), bytes(240, 4)),
+                    ],
+                ),
+            )
+        }
+
+        // fn derived_json_decode_bool(json) {
+        //   case derived_json_skip_whitespace(json) {
+        //     <<"true":utf8, rest:bytes>> -> Ok(#(True, rest))
+        //     <<"false":utf8, rest:bytes>> -> Ok(#(False, rest))
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeBool => {
+            let clause = |literal: &str, value: &str| {
+                code.clause(
+                    code.bits_pattern(vec![code.literal_segment(literal), code.rest_segment()]),
+                    code.ok(code.tuple(vec![code.var(value), code.var("rest")])),
+                )
+            };
+            (
+                vec![untyped("json")],
+                code.case(
+                    vec![skip_whitespace(code.var("json"))],
+                    vec![
+                        clause("true", "True"),
+                        clause("false", "False"),
+                        code.clause(discard(location), code.error()),
+                    ],
+                ),
+            )
+        }
+
+        // fn derived_json_decode_list(json, decode) {
+        //   case derived_json_skip_whitespace(json) {
+        //     <<"[":utf8, rest:bytes>> ->
+        //       case derived_json_skip_whitespace(rest) {
+        //         <<"]":utf8, rest:bytes>> -> Ok(#([], rest))
+        //         _ -> derived_json_decode_items(rest, decode)
+        //       }
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeList => (
+            vec![untyped("json"), untyped("decode")],
+            code.expect_prefix(
+                skip_whitespace(code.var("json")),
+                "[",
+                code.case(
+                    vec![skip_whitespace(code.var("rest"))],
+                    vec![
+                        code.clause(
+                            code.bits_pattern(vec![code.literal_segment("]"), code.rest_segment()]),
+                            code.ok(code.tuple(vec![code.list(vec![], None), code.var("rest")])),
+                        ),
+                        code.clause(
+                            discard(location),
+                            call(
+                                Helper::DecodeItems,
+                                vec![code.var("rest"), code.var("decode")],
+                            ),
+                        ),
+                    ],
+                ),
+            ),
+        ),
+
+        // fn derived_json_decode_items(json, decode) {
+        //   case decode(json) {
+        //     Ok(#(element, rest)) ->
+        //       case derived_json_skip_whitespace(rest) {
+        //         <<",":utf8, rest:bytes>> ->
+        //           case derived_json_decode_items(rest, decode) {
+        //             Ok(#(elements, rest)) -> Ok(#([element, ..elements], rest))
+        //             _ -> Error(Nil)
+        //           }
+        //         <<"]":utf8, rest:bytes>> -> Ok(#([element], rest))
+        //         _ -> Error(Nil)
+        //       }
+        //     _ -> Error(Nil)
+        //   }
+        // }
+        Helper::DecodeItems => {
+            let prepended = code.ok(code.tuple(vec![
+                code.list(vec![code.var("element")], Some(code.var("elements"))),
+                code.var("rest"),
+            ]));
+            let next = code.case(
+                vec![skip_whitespace(code.var("rest"))],
+                vec![
+                    code.clause(
+                        code.bits_pattern(vec![code.literal_segment(","), code.rest_segment()]),
+                        code.expect_ok(
+                            call(
+                                Helper::DecodeItems,
+                                vec![code.var("rest"), code.var("decode")],
+                            ),
+                            "elements",
+                            prepended,
+                        ),
+                    ),
+                    code.clause(
+                        code.bits_pattern(vec![code.literal_segment("]"), code.rest_segment()]),
+                        code.ok(code.tuple(vec![
+                            code.list(vec![code.var("element")], None),
+                            code.var("rest"),
+                        ])),
+                    ),
+                    code.clause(discard(location), code.error()),
+                ],
+            );
+            (
+                vec![untyped("json"), untyped("decode")],
+                code.expect_ok(
+                    code.call(code.var("decode"), vec![code.var("json")]),
+                    "element",
+                    next,
+                ),
+            )
+        }
+    };
+
+    Function {
+        body: vec1![Statement::Expression(body)],
+        ..helper_definition(code, helper, arguments)
+    }
+}
+
+fn helper_definition(code: &Code, helper: Helper, arguments: Vec<UntypedArg>) -> UntypedFunction {
+    Function {
+        name: Some((code.location, helper.name().into())),
+        ..code.function(
+            Publicity::Private,
+            arguments,
+            UntypedExpr::Placeholder {
+                location: code.location,
+            },
+        )
+    }
+}
+
+/// Builds the untyped AST of the generated code. All the locations of the
+/// generated code point to the head of the type definition.
+///
+struct Code {
+    location: SrcSpan,
+    /// The number of bit array patterns generated so far.
+    bit_array_patterns: Cell<u32>,
+}
+
+impl Code {
+    fn new(location: SrcSpan) -> Self {
+        Self {
+            location,
+            bit_array_patterns: Cell::new(0),
+        }
+    }
+
+    fn function(
+        &self,
+        publicity: Publicity,
+        arguments: Vec<UntypedArg>,
+        body: UntypedExpr,
+    ) -> UntypedFunction {
+        Function {
+            location: self.location,
+            end_position: self.location.end,
+            name: None,
+            arguments,
+            body: vec1![Statement::Expression(body)],
+            publicity,
+            deprecation: Deprecation::NotDeprecated,
+            return_annotation: None,
+            return_type: (),
+            documentation: None,
+            external_erlang: None,
+            external_javascript: None,
+            external_erlang_arguments: None,
+            external_javascript_arguments: None,
+            implementations: Implementations::supporting_all(),
+            purity: Purity::Pure,
+            benchmark: false,
+            memoize: false,
+            should_panic: None,
+            since: None,
+            doc_attribute: vec![],
+        }
+    }
+
+    fn untyped_argument(&self, name: &str) -> UntypedArg {
+        Arg {
+            names: ArgNames::Named {
+                name: name.into(),
+                location: self.location,
+            },
+            location: self.location,
+            annotation: None,
+            type_: (),
+        }
+    }
+
+    fn type_(&self, name: &str) -> TypeAst {
+        TypeAst::Constructor(TypeAstConstructor {
+            location: self.location,
+            name_location: self.location,
+            module: None,
+            name: name.into(),
+            arguments: vec![],
+        })
+    }
+
+    fn var(&self, name: &str) -> UntypedExpr {
+        var(name, self.location)
+    }
+
+    /// A string literal with the given value, escaped as it would be written
+    /// in the source code.
+    ///
+    fn string(&self, value: &str) -> UntypedExpr {
+        UntypedExpr::String {
+            location: self.location,
+            value: escape(value),
+        }
+    }
+
+    fn binop(&self, name: BinOp, left: UntypedExpr, right: UntypedExpr) -> UntypedExpr {
+        UntypedExpr::BinOp {
+            location: self.location,
+            name,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn concat(&self, parts: Vec<UntypedExpr>) -> UntypedExpr {
+        parts
+            .into_iter()
+            .reduce(|left, right| self.binop(BinOp::Concatenate, left, right))
+            .unwrap_or_else(|| self.string(""))
+    }
+
+    fn call(&self, fun: UntypedExpr, arguments: Vec<UntypedExpr>) -> UntypedExpr {
+        UntypedExpr::Call {
+            location: self.location,
+            fun: Box::new(fun),
+            arguments: arguments
+                .into_iter()
+                .map(|value| self.call_arg(value))
+                .collect(),
+        }
+    }
+
+    fn call_arg<A>(&self, value: A) -> CallArg<A> {
+        CallArg {
+            label: None,
+            location: self.location,
+            value,
+            implicit: None,
+        }
+    }
+
+    fn anonymous_function(&self, argument: &str, body: UntypedExpr) -> UntypedExpr {
+        UntypedExpr::Fn {
+            location: self.location,
+            kind: FunctionLiteralKind::Anonymous {
+                head: self.location,
+            },
+            end_of_head_byte_index: self.location.end,
+            arguments: vec![self.untyped_argument(argument)],
+            body: vec1![Statement::Expression(body)],
+            return_annotation: None,
+        }
+    }
+
+    fn tuple(&self, elements: Vec<UntypedExpr>) -> UntypedExpr {
+        UntypedExpr::Tuple {
+            location: self.location,
+            elements,
+        }
+    }
+
+    fn list(&self, elements: Vec<UntypedExpr>, tail: Option<UntypedExpr>) -> UntypedExpr {
+        UntypedExpr::List {
+            location: self.location,
+            elements,
+            tail: tail.map(Box::new),
+        }
+    }
+
+    fn ok(&self, value: UntypedExpr) -> UntypedExpr {
+        self.call(self.var("Ok"), vec![value])
+    }
+
+    fn error(&self) -> UntypedExpr {
+        self.call(self.var("Error"), vec![self.var("Nil")])
+    }
+
+    fn case(&self, subjects: Vec<UntypedExpr>, clauses: Vec<UntypedClause>) -> UntypedExpr {
+        case(subjects, clauses, self.location)
+    }
+
+    fn clause(&self, pattern: UntypedPattern, then: UntypedExpr) -> UntypedClause {
+        UntypedClause {
+            location: self.location,
+            pattern: vec![pattern],
+            alternative_patterns: vec![],
+            guard: None,
+            then,
+        }
+    }
+
+    fn guarded_clause(
+        &self,
+        pattern: UntypedPattern,
+        guard: UntypedClauseGuard,
+        then: UntypedExpr,
+    ) -> UntypedClause {
+        UntypedClause {
+            guard: Some(guard),
+            ..self.clause(pattern, then)
+        }
+    }
+
+    /// `case subject { <<"prefix":utf8, rest:bytes>> -> then _ -> Error(Nil) }`
+    ///
+    fn expect_prefix(&self, subject: UntypedExpr, prefix: &str, then: UntypedExpr) -> UntypedExpr {
+        self.case(
+            vec![subject],
+            vec![
+                self.clause(
+                    self.bits_pattern(vec![self.literal_segment(prefix), self.rest_segment()]),
+                    then,
+                ),
+                self.clause(discard(self.location), self.error()),
+            ],
+        )
+    }
+
+    /// `case subject { Ok(#(name, rest)) -> then _ -> Error(Nil) }`
+    ///
+    fn expect_ok(&self, subject: UntypedExpr, name: &str, then: UntypedExpr) -> UntypedExpr {
+        self.expect_ok_pattern(
+            subject,
+            self.tuple_pattern(vec![self.variable(name), self.variable("rest")]),
+            then,
+        )
+    }
+
+    /// `case subject { Ok(pattern) -> then _ -> Error(Nil) }`
+    ///
+    fn expect_ok_pattern(
+        &self,
+        subject: UntypedExpr,
+        pattern: UntypedPattern,
+        then: UntypedExpr,
+    ) -> UntypedExpr {
+        self.case(
+            vec![subject],
+            vec![
+                self.clause(self.ok_pattern(pattern), then),
+                self.clause(discard(self.location), self.error()),
+            ],
+        )
+    }
+
+    fn bits(&self, segments: Vec<UntypedExprBitArraySegment>) -> UntypedExpr {
+        UntypedExpr::BitArray {
+            location: self.location,
+            segments,
+        }
+    }
+
+    fn segment<A>(&self, value: A) -> BitArraySegment<A, ()> {
+        BitArraySegment {
+            location: self.location,
+            value: Box::new(value),
+            options: vec![],
+            type_: (),
+        }
+    }
+
+    fn utf8_segment<A>(&self, value: A) -> BitArraySegment<A, ()> {
+        BitArraySegment {
+            options: vec![BitArrayOption::Utf8 {
+                location: self.location,
+            }],
+            ..self.segment(value)
+        }
+    }
+
+    fn bits_segment(&self, value: UntypedExpr) -> UntypedExprBitArraySegment {
+        BitArraySegment {
+            options: vec![BitArrayOption::Bits {
+                location: self.location,
+            }],
+            ..self.segment(value)
+        }
+    }
+
+    fn variable(&self, name: &str) -> UntypedPattern {
+        Pattern::Variable {
+            location: self.location,
+            name: name.into(),
+            type_: (),
+            origin: crate::type_::error::VariableOrigin::Generated,
+        }
+    }
+
+    fn constructor_pattern(&self, name: &str, arguments: Vec<UntypedPattern>) -> UntypedPattern {
+        Pattern::Constructor {
+            location: self.location,
+            name_location: self.location,
+            name: name.into(),
+            arguments: arguments
+                .into_iter()
+                .map(|value| self.call_arg(value))
+                .collect(),
+            module: None,
+            constructor: Inferred::Unknown,
+            spread: None,
+            type_: (),
+        }
+    }
+
+    fn ok_pattern(&self, value: UntypedPattern) -> UntypedPattern {
+        self.constructor_pattern("Ok", vec![value])
+    }
+
+    fn tuple_pattern(&self, elements: Vec<UntypedPattern>) -> UntypedPattern {
+        Pattern::Tuple {
+            location: self.location,
+            elements,
+        }
+    }
+
+    fn list_pattern(
+        &self,
+        elements: Vec<UntypedPattern>,
+        tail: Option<UntypedPattern>,
+    ) -> UntypedPattern {
+        Pattern::List {
+            location: self.location,
+            elements,
+            tail: tail.map(Box::new),
+            type_: (),
+        }
+    }
+
+    /// The exhaustiveness checker tells bit array patterns apart by their
+    /// location, so each one is given a different empty span starting in the
+    /// head of the type definition.
+    ///
+    fn bits_pattern(&self, segments: Vec<UntypedPatternBitArraySegment>) -> UntypedPattern {
+        let index = self.bit_array_patterns.get();
+        self.bit_array_patterns.set(index + 1);
+        let start = self.location.start + index;
+        Pattern::BitArray {
+            location: SrcSpan::new(start, start),
+            segments,
+        }
+    }
+
+    /// `"literal":utf8`
+    ///
+    fn literal_segment(&self, literal: &str) -> UntypedPatternBitArraySegment {
+        self.utf8_segment(Pattern::String {
+            location: self.location,
+            value: escape(literal),
+        })
+    }
+
+    fn byte_segment(&self, byte: u8) -> UntypedPatternBitArraySegment {
+        self.segment(Pattern::Int {
+            location: self.location,
+            value: eco_format!("{byte}"),
+            int_value: byte.into(),
+        })
+    }
+
+    /// `<<"literal":utf8, _:bytes>>`
+    ///
+    fn prefix_pattern(&self, literal: &str) -> UntypedPattern {
+        self.bits_pattern(vec![
+            self.literal_segment(literal),
+            self.bytes_segment(discard(self.location)),
+        ])
+    }
+
+    /// `rest:bytes`
+    ///
+    fn rest_segment(&self) -> UntypedPatternBitArraySegment {
+        self.bytes_segment(self.variable("rest"))
+    }
+
+    fn bytes_segment(&self, pattern: UntypedPattern) -> UntypedPatternBitArraySegment {
+        BitArraySegment {
+            options: vec![BitArrayOption::Bytes {
+                location: self.location,
+            }],
+            ..self.segment(pattern)
+        }
+    }
+
+    fn guard_var(&self, name: &str) -> UntypedClauseGuard {
+        ClauseGuard::Var {
+            location: self.location,
+            type_: (),
+            name: name.into(),
+            definition_location: SrcSpan::default(),
+        }
+    }
+
+    fn guard_int(&self, value: u32) -> UntypedClauseGuard {
+        ClauseGuard::Constant(Constant::Int {
+            location: self.location,
+            value: eco_format!("{value}"),
+            int_value: value.into(),
+        })
+    }
+
+    /// `name >= low && name <= high`
+    ///
+    fn guard_between(&self, name: &str, low: u32, high: u32) -> UntypedClauseGuard {
+        ClauseGuard::And {
+            location: self.location,
+            left: Box::new(ClauseGuard::GtEqInt {
+                location: self.location,
+                left: Box::new(self.guard_var(name)),
+                right: Box::new(self.guard_int(low)),
+            }),
+            right: Box::new(ClauseGuard::LtEqInt {
+                location: self.location,
+                left: Box::new(self.guard_var(name)),
+                right: Box::new(self.guard_int(high)),
+            }),
+        }
+    }
+}
+
+/// Escapes a string the same way it would be escaped in the source code of a
+/// string literal.
+///
+fn escape(value: &str) -> EcoString {
+    value.replace('\\', "\\\\").replace('"', "\\\"").into()
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use vec1::Vec1;
+
+use crate::{
+    ast::{
+        ArgNames, FunctionLiteralKind, SrcSpan, TypeAst, TypedArg, TypedDefinition, TypedStatement,
+        visit::{self, Visit},
+    },
+    type_::{Problems, Type, Warning},
+};
+
+/// Looks for anonymous functions that discard all of their arguments, so that
+/// the value they return can't depend on any of them:
+///
+/// ```gleam
+/// list.map(items, fn(_) { 0 })
+/// //              ^^^^^ warning here
+/// ```
+///
+/// Functions with no arguments, captures, `use` callbacks and trailing blocks
+/// are never reported.
+///
+pub fn check_constant_functions(definitions: &[TypedDefinition], problems: &mut Problems) {
+    let mut finder = ConstantFunctionFinder { problems };
+    for definition in definitions {
+        finder.visit_typed_definition(definition);
+    }
+}
+
+struct ConstantFunctionFinder<'a> {
+    problems: &'a mut Problems,
+}
+
+impl<'ast> Visit<'ast> for ConstantFunctionFinder<'_> {
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        args: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        match kind {
+            FunctionLiteralKind::Anonymous { head }
+                if !args.is_empty() && args.iter().all(is_discarded) =>
+            {
+                self.problems
+                    .warning(Warning::ConstantFunction { location: *head })
+            }
+            FunctionLiteralKind::Anonymous { .. }
+            | FunctionLiteralKind::Capture { .. }
+            | FunctionLiteralKind::Use { .. }
+            | FunctionLiteralKind::TrailingBlock { .. } => (),
+        }
+
+        visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+}
+
+fn is_discarded(arg: &TypedArg) -> bool {
+    match arg.names {
+        ArgNames::Discard { .. } | ArgNames::LabelledDiscard { .. } => true,
+        ArgNames::Named { .. } | ArgNames::NamedLabelled { .. } => false,
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::{
+    ast::{
+        SrcSpan, TypedClause, TypedDefinition, TypedExpr,
+        visit::{self, Visit},
+    },
+    type_::{Problems, Type, ValueConstructorVariant, Warning},
+};
+
+/// Looks for `case` expressions matching on a single variable where every
+/// clause returns that same variable, unchanged:
+///
+/// ```gleam
+/// case x {
+/// // ^^^^ warning here
+///   Ok(_) -> x
+///   Error(_) -> x
+/// }
+/// ```
+///
+/// Such a `case` always evaluates to its subject and can be replaced by it.
+///
+pub fn check_identity_cases(definitions: &[TypedDefinition], problems: &mut Problems) {
+    let mut finder = IdentityCaseFinder { problems };
+    for definition in definitions {
+        finder.visit_typed_definition(definition);
+    }
+}
+
+struct IdentityCaseFinder<'a> {
+    problems: &'a mut Problems,
+}
+
+impl<'ast> Visit<'ast> for IdentityCaseFinder<'_> {
+    fn visit_typed_expr_case(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        subjects: &'ast [TypedExpr],
+        clauses: &'ast [TypedClause],
+    ) {
+        match subjects {
+            [subject] if is_identity_case(subject, clauses) => {
+                self.problems.warning(Warning::IdentityCase {
+                    location: SrcSpan::new(location.start, subject.location().end),
+                })
+            }
+            _ => (),
+        }
+
+        visit::visit_typed_expr_case(self, location, type_, subjects, clauses);
+    }
+}
+
+fn is_identity_case(subject: &TypedExpr, clauses: &[TypedClause]) -> bool {
+    let Some(subject) = local_variable_definition(subject) else {
+        return false;
+    };
+    clauses
+        .iter()
+        .all(|clause| local_variable_definition(&clause.then) == Some(subject))
+}
+
+/// The location where the local variable referenced by this expression is
+/// defined, if it is a local variable. A variable bound by a clause's pattern
+/// has a different definition from the subject even if it shadows it, so it
+/// is never mistaken for it.
+///
+fn local_variable_definition(expr: &TypedExpr) -> Option<SrcSpan> {
+    match expr {
+        TypedExpr::Var { constructor, .. } => match &constructor.variant {
+            ValueConstructorVariant::LocalVariable { location, .. } => Some(*location),
+            ValueConstructorVariant::ModuleConstant { .. }
+            | ValueConstructorVariant::LocalConstant { .. }
+            | ValueConstructorVariant::ModuleFn { .. }
+            | ValueConstructorVariant::Record { .. } => None,
+        },
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 use ecow::EcoString;
+use itertools::Itertools;
 
 use crate::{
     ast::{SrcSpan, UnqualifiedImport, UntypedImport},
@@ -70,6 +71,9 @@ impl<'context, 'problems> Importer<'context, 'problems> {
         }
 
         // Insert unqualified imports into scope
+        if let Some(glob_location) = import.unqualified_types_glob {
+            self.register_unqualified_types_glob(import, glob_location, module_info);
+        }
         for type_ in &import.unqualified_types {
             self.register_unqualified_type(type_, module_info);
         }
@@ -83,6 +87,15 @@ impl<'context, 'problems> Importer<'context, 'problems> {
 
         // Register the unqualified import if it is a type constructor
         let Some(type_info) = module.get_public_type(&import.name) else {
+            if let Some(feature) = module.disabled_types.get(&import.name) {
+                self.problems.error(Error::DisabledDefinition {
+                    location: import.location,
+                    name: import.name.clone(),
+                    module: Some(module.name.clone()),
+                    feature: feature.clone(),
+                });
+                return;
+            }
             // TODO: refine to a type specific error
             self.problems.error(Error::UnknownModuleType {
                 location: import.location,
@@ -125,6 +138,46 @@ impl<'context, 'problems> Importer<'context, 'problems> {
         );
     }
 
+    /// Brings all the public types of a module into scope, as requested by an
+    /// `import wibble.{type *}`.
+    ///
+    fn register_unqualified_types_glob(
+        &mut self,
+        import: &UntypedImport,
+        location: SrcSpan,
+        module: &ModuleInterface,
+    ) {
+        for name in module.public_type_names().into_iter().sorted() {
+            // Types that are also imported by name, without an alias, are
+            // registered along with all the other unqualified imports.
+            if import
+                .unqualified_types
+                .iter()
+                .any(|type_| type_.name == name && type_.as_name.is_none())
+            {
+                continue;
+            }
+
+            let Some(type_info) = module.get_public_type(&name) else {
+                continue;
+            };
+            let type_info = type_info.clone().with_location(location);
+
+            self.environment.names.type_in_scope(
+                name.clone(),
+                type_info.type_.as_ref(),
+                &type_info.parameters,
+            );
+
+            // Unlike types imported by name, the ones coming from the glob are
+            // not tracked for usage: we don't want to warn about all the types
+            // a module defines but that are never used.
+            if let Err(e) = self.environment.insert_type_constructor(name, type_info) {
+                self.problems.error(e);
+            }
+        }
+    }
+
     fn register_unqualified_value(&mut self, import: &UnqualifiedImport, module: &ModuleInterface) {
         let import_name = &import.name;
         let location = import.location;
@@ -154,6 +207,24 @@ impl<'context, 'problems> Importer<'context, 'problems> {
                 &value.variant
             }
             None => {
+                if let Some(feature) = module.disabled_values.get(import_name) {
+                    self.problems.error(Error::DisabledDefinition {
+                        location,
+                        name: import_name.clone(),
+                        module: Some(module.name.clone()),
+                        feature: feature.clone(),
+                    });
+                    return;
+                }
+                if let Some(type_name) = module.opaque_type_of_constructor(import_name) {
+                    self.problems.error(Error::OpaqueConstructorOutsideModule {
+                        location,
+                        name: import_name.clone(),
+                        module_name: module.name.clone(),
+                        type_name: type_name.clone(),
+                    });
+                    return;
+                }
                 self.problems.error(Error::UnknownModuleValue {
                     location,
                     name: import_name.clone(),
@@ -251,7 +322,10 @@ impl<'context, 'problems> Importer<'context, 'problems> {
         if let Some(used_name) = import.used_name() {
             self.check_not_a_duplicate_import(&used_name, import.location)?;
 
-            if import.unqualified_types.is_empty() && import.unqualified_values.is_empty() {
+            if import.unqualified_types.is_empty()
+                && import.unqualified_values.is_empty()
+                && import.unqualified_types_glob.is_none()
+            {
                 // When the module has no unqualified imports, we track its usage
                 // so we can warn if not used by the end of the type checking
                 let _ = self
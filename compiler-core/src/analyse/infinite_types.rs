@@ -0,0 +1,207 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+use ecow::EcoString;
+
+use crate::{
+    ast::{Definition, TypedCustomType, TypedDefinition},
+    type_::{Error, Problems, Type, TypeVar},
+};
+
+/// Looks for custom types that can never be constructed, because each of
+/// their variants contains a value of the type itself, or of another type
+/// with the same problem:
+///
+/// ```gleam
+/// pub type Wibble {
+///   Wibble(Wobble)
+/// }
+///
+/// pub type Wobble {
+///   Wobble(Wibble)
+/// }
+/// ```
+///
+/// Building a `Wibble` would require a `Wobble`, which in turn would require
+/// a `Wibble`, forever.
+///
+/// Recursion going through any other type is fine, as it can always be
+/// stopped: a `List` can be empty, a function doesn't need to be called, and
+/// so on. Only fields holding a type directly, or inside a tuple, are looked
+/// at.
+///
+pub fn check_infinite_types(
+    module_name: &EcoString,
+    definitions: &[TypedDefinition],
+    problems: &mut Problems,
+) {
+    // Types with no constructors are external types, which can always be
+    // constructed by some external code.
+    let types: HashMap<&EcoString, &TypedCustomType> = definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::CustomType(custom_type) if !custom_type.constructors.is_empty() => {
+                Some((&custom_type.name, custom_type))
+            }
+            _ => None,
+        })
+        .collect();
+    let checker = Checker { module_name, types };
+
+    let infinite = checker.infinite_types();
+    let mut reported = HashSet::new();
+    for definition in definitions {
+        let Definition::CustomType(custom_type) = definition else {
+            continue;
+        };
+        if !infinite.contains(&custom_type.name) || reported.contains(&custom_type.name) {
+            continue;
+        }
+        // A type that can't be built only because it contains one of the
+        // types in a cycle is not reported, the cycle itself is.
+        let Some(cycle) = checker.cycle(&custom_type.name, &infinite) else {
+            continue;
+        };
+        reported.extend(cycle.iter().cloned());
+        problems.error(Error::InfiniteType {
+            location: custom_type.location,
+            cycle,
+        });
+    }
+}
+
+struct Checker<'a> {
+    module_name: &'a EcoString,
+    types: HashMap<&'a EcoString, &'a TypedCustomType>,
+}
+
+impl Checker<'_> {
+    /// The names of all the module's types that can't be constructed.
+    ///
+    fn infinite_types(&self) -> HashSet<EcoString> {
+        // We start by assuming that no type can be constructed, and keep
+        // marking the ones that have a variant made only of types that can
+        // until there's no more to find.
+        let mut constructible = HashSet::new();
+        loop {
+            let mut changed = false;
+            for (name, custom_type) in &self.types {
+                if constructible.contains(*name) {
+                    continue;
+                }
+                let can_be_constructed = custom_type.constructors.iter().any(|constructor| {
+                    constructor
+                        .arguments
+                        .iter()
+                        .all(|argument| self.is_constructible(&argument.type_, &constructible))
+                });
+                if can_be_constructed {
+                    let _ = constructible.insert((*name).clone());
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        self.types
+            .keys()
+            .filter(|name| !constructible.contains(**name))
+            .map(|name| (*name).clone())
+            .collect()
+    }
+
+    fn is_constructible(&self, type_: &Type, constructible: &HashSet<EcoString>) -> bool {
+        match self.local_type_name(type_) {
+            Some(name) => constructible.contains(name),
+            None => self
+                .directly_contained(type_)
+                .iter()
+                .all(|type_| self.is_constructible(type_, constructible)),
+        }
+    }
+
+    /// If the type is one of the module's types that are being checked,
+    /// returns its name.
+    ///
+    fn local_type_name<'b>(&self, type_: &'b Type) -> Option<&'b EcoString> {
+        match type_ {
+            Type::Named { module, name, .. }
+                if module == self.module_name && self.types.contains_key(name) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// The types a value of the given type directly holds, without any
+    /// indirection that would make it possible to build it without them.
+    ///
+    fn directly_contained(&self, type_: &Type) -> Vec<Arc<Type>> {
+        match type_ {
+            Type::Tuple { elements } => elements.clone(),
+            Type::Var { type_ } => match &*type_.borrow() {
+                TypeVar::Link { type_ } => vec![type_.clone()],
+                TypeVar::Unbound { .. } | TypeVar::Generic { .. } => vec![],
+            },
+            Type::Named { .. } | Type::Fn { .. } => vec![],
+        }
+    }
+
+    /// The names of the infinite types directly held by the variants of the
+    /// given type.
+    ///
+    fn infinite_fields(&self, name: &EcoString, infinite: &HashSet<EcoString>) -> Vec<EcoString> {
+        let mut fields = vec![];
+        let Some(custom_type) = self.types.get(name) else {
+            return fields;
+        };
+        let mut types: Vec<Arc<Type>> = custom_type
+            .constructors
+            .iter()
+            .flat_map(|constructor| &constructor.arguments)
+            .map(|argument| argument.type_.clone())
+            .collect();
+        while let Some(type_) = types.pop() {
+            match self.local_type_name(&type_) {
+                Some(name) if infinite.contains(name) && !fields.contains(name) => {
+                    fields.push(name.clone())
+                }
+                Some(_) => (),
+                None => types.extend(self.directly_contained(&type_)),
+            }
+        }
+        fields
+    }
+
+    /// The shortest cycle of infinite types going from the given type back to
+    /// itself, if it is part of one.
+    ///
+    fn cycle(&self, start: &EcoString, infinite: &HashSet<EcoString>) -> Option<Vec<EcoString>> {
+        let mut previous: HashMap<EcoString, EcoString> = HashMap::new();
+        let mut queue = VecDeque::from([start.clone()]);
+        while let Some(name) = queue.pop_front() {
+            for field in self.infinite_fields(&name, infinite) {
+                if &field == start {
+                    let mut cycle = vec![name.clone()];
+                    let mut current = &name;
+                    while let Some(name) = previous.get(current) {
+                        cycle.push(name.clone());
+                        current = name;
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                if !previous.contains_key(&field) {
+                    let _ = previous.insert(field.clone(), name.clone());
+                    queue.push_back(field);
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashSet, sync::Arc};
+
+use ecow::EcoString;
+
+use crate::{
+    ast::{
+        ArgNames, Definition, SrcSpan, TypedDefinition, TypedExpr,
+        visit::{self, Visit},
+    },
+    type_::{Problems, Type, TypedCallArg, ValueConstructorVariant, Warning},
+};
+
+/// Looks for labels of private functions that are of no use to anyone. That
+/// is a labelled argument whose value is discarded by the function, and whose
+/// label is never used by any call in the module:
+///
+/// ```gleam
+/// fn wibble(wobble _) { todo }
+/// //        ^^^^^^ warning here
+///
+/// pub fn main() {
+///   wibble(1)
+/// }
+/// ```
+///
+/// Since the function is private all its calls are in the same module, so if
+/// none of them uses the label it can be safely removed.
+///
+pub fn check_unused_labels(
+    module: &EcoString,
+    definitions: &[TypedDefinition],
+    problems: &mut Problems,
+) {
+    let mut finder = LabelUsageFinder {
+        module,
+        used_labels: HashSet::new(),
+    };
+    for definition in definitions {
+        finder.visit_typed_definition(definition);
+    }
+
+    for definition in definitions {
+        let Definition::Function(function) = definition else {
+            continue;
+        };
+        if !function.publicity.is_private() {
+            continue;
+        }
+        let Some((_, name)) = &function.name else {
+            continue;
+        };
+
+        for argument in &function.arguments {
+            match &argument.names {
+                ArgNames::LabelledDiscard {
+                    label,
+                    label_location,
+                    ..
+                } if !finder.used_labels.contains(&(name.clone(), label.clone())) => problems
+                    .warning(Warning::UnusedLabel {
+                        location: *label_location,
+                        function: name.clone(),
+                        label: label.clone(),
+                    }),
+
+                ArgNames::Discard { .. }
+                | ArgNames::LabelledDiscard { .. }
+                | ArgNames::Named { .. }
+                | ArgNames::NamedLabelled { .. } => (),
+            }
+        }
+    }
+}
+
+struct LabelUsageFinder<'a> {
+    module: &'a EcoString,
+    /// The name of each function of the module that is called with a label,
+    /// paired with that label.
+    used_labels: HashSet<(EcoString, EcoString)>,
+}
+
+impl<'ast> Visit<'ast> for LabelUsageFinder<'_> {
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        if let TypedExpr::Var { constructor, .. } = fun {
+            match &constructor.variant {
+                ValueConstructorVariant::ModuleFn { name, module, .. } if module == self.module => {
+                    for label in args.iter().filter_map(|arg| arg.label.as_ref()) {
+                        let _ = self.used_labels.insert((name.clone(), label.clone()));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        visit::visit_typed_expr_call(self, location, type_, fun, args);
+    }
+}
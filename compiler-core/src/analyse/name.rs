@@ -1,11 +1,12 @@
-use std::sync::OnceLock;
+use std::{collections::HashSet, sync::OnceLock};
 
 use ecow::{EcoString, eco_format};
 use regex::Regex;
 
 use crate::{
-    ast::{ArgNames, SrcSpan},
-    type_::Problems,
+    ast::{ArgNames, GroupedStatements, SrcSpan, TypeAst},
+    config::LintsConfig,
+    type_::{Problems, Warning, error::NamingConventionProblem},
 };
 
 use super::{Error, Named};
@@ -115,3 +116,185 @@ pub fn check_argument_names(names: &ArgNames, problems: &mut Problems) {
         }
     }
 }
+
+/// Checks the names defined by a module against the naming conventions
+/// enabled in the `[lints]` section of the package's `gleam.toml`, emitting a
+/// warning for each name that doesn't follow them.
+///
+pub fn check_naming_conventions(
+    statements: &GroupedStatements,
+    lints: &LintsConfig,
+    problems: &mut Problems,
+) {
+    if *lints == LintsConfig::default() {
+        return;
+    }
+
+    let mut checker = NamingConventionChecker { lints, problems };
+
+    for function in &statements.functions {
+        if let Some((location, name)) = &function.name {
+            checker.check_function_name(*location, name);
+        }
+        for argument in &function.arguments {
+            checker.check_argument_names(&argument.names);
+        }
+
+        // The same type variable can appear many times in a function's
+        // annotations, but we only want to warn about it once.
+        let mut type_variables = HashSet::new();
+        let annotations = function
+            .arguments
+            .iter()
+            .filter_map(|argument| argument.annotation.as_ref())
+            .chain(function.return_annotation.as_ref());
+        for annotation in annotations {
+            checker.check_type_variables(annotation, &mut type_variables);
+        }
+    }
+
+    for constant in &statements.constants {
+        checker.check_length(constant.name_location, &constant.name, Named::Constant);
+    }
+
+    for custom_type in &statements.custom_types {
+        checker.check_length(custom_type.name_location, &custom_type.name, Named::Type);
+        for (location, parameter) in &custom_type.parameters {
+            checker.check_type_parameter(*location, parameter);
+        }
+        for constructor in &custom_type.constructors {
+            checker.check_length(
+                constructor.name_location,
+                &constructor.name,
+                Named::CustomTypeVariant,
+            );
+            for (location, label) in constructor
+                .arguments
+                .iter()
+                .filter_map(|argument| argument.label.as_ref())
+            {
+                checker.check_length(*location, label, Named::Label);
+            }
+        }
+    }
+
+    for type_alias in &statements.type_aliases {
+        checker.check_length(
+            type_alias.name_location,
+            &type_alias.alias,
+            Named::TypeAlias,
+        );
+        for (location, parameter) in &type_alias.parameters {
+            checker.check_type_parameter(*location, parameter);
+        }
+    }
+}
+
+struct NamingConventionChecker<'a> {
+    lints: &'a LintsConfig,
+    problems: &'a mut Problems,
+}
+
+impl NamingConventionChecker<'_> {
+    fn warn(
+        &mut self,
+        location: SrcSpan,
+        name: &EcoString,
+        kind: Named,
+        problem: NamingConventionProblem,
+    ) {
+        self.problems.warning(Warning::NamingConvention {
+            location,
+            kind,
+            name: name.clone(),
+            problem,
+        });
+    }
+
+    fn check_length(&mut self, location: SrcSpan, name: &EcoString, kind: Named) {
+        match self.lints.max_identifier_length {
+            Some(maximum_length) if name.chars().count() > maximum_length => self.warn(
+                location,
+                name,
+                kind,
+                NamingConventionProblem::TooLong { maximum_length },
+            ),
+            Some(_) | None => (),
+        }
+    }
+
+    fn check_function_name(&mut self, location: SrcSpan, name: &EcoString) {
+        if self.lints.snake_case_function_names && (name.contains("__") || name.ends_with('_')) {
+            self.warn(
+                location,
+                name,
+                Named::Function,
+                NamingConventionProblem::NotSnakeCase,
+            );
+        }
+        self.check_length(location, name, Named::Function);
+    }
+
+    fn check_argument_names(&mut self, names: &ArgNames) {
+        match names {
+            ArgNames::Discard { .. } => (),
+            ArgNames::LabelledDiscard {
+                label,
+                label_location,
+                ..
+            } => self.check_length(*label_location, label, Named::Label),
+            ArgNames::Named { name, location } => {
+                self.check_length(*location, name, Named::Argument)
+            }
+            ArgNames::NamedLabelled {
+                name,
+                name_location,
+                label,
+                label_location,
+            } => {
+                self.check_length(*label_location, label, Named::Label);
+                self.check_length(*name_location, name, Named::Argument);
+            }
+        }
+    }
+
+    fn check_type_parameter(&mut self, location: SrcSpan, name: &EcoString) {
+        match self.lints.min_type_parameter_length {
+            Some(minimum_length) if name.chars().count() < minimum_length => self.warn(
+                location,
+                name,
+                Named::TypeVariable,
+                NamingConventionProblem::TooShort { minimum_length },
+            ),
+            Some(_) | None => (),
+        }
+        self.check_length(location, name, Named::TypeVariable);
+    }
+
+    fn check_type_variables(&mut self, type_: &TypeAst, seen: &mut HashSet<EcoString>) {
+        match type_ {
+            TypeAst::Var(var) => {
+                if seen.insert(var.name.clone()) {
+                    self.check_type_parameter(var.location, &var.name);
+                }
+            }
+            TypeAst::Constructor(constructor) => {
+                for argument in &constructor.arguments {
+                    self.check_type_variables(argument, seen);
+                }
+            }
+            TypeAst::Fn(fn_) => {
+                for argument in &fn_.arguments {
+                    self.check_type_variables(argument, seen);
+                }
+                self.check_type_variables(&fn_.return_, seen);
+            }
+            TypeAst::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    self.check_type_variables(element, seen);
+                }
+            }
+            TypeAst::Hole(_) => (),
+        }
+    }
+}
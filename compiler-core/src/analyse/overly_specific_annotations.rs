@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use crate::{
+    ast::{
+        SrcSpan, TypedExpr, UntypedFunction,
+        visit::{self, Visit},
+    },
+    type_::{
+        self, Environment, Problems, Type, Warning,
+        environment::unify,
+        expression::{ExprTyper, FunctionDefinition},
+        fn_,
+        hydrator::Hydrator,
+    },
+};
+
+/// Whether the annotations of this function are worth comparing against the
+/// type it would have without them: it must have a Gleam implementation and
+/// at least one annotation.
+///
+pub fn has_annotations_to_check(function: &UntypedFunction) -> bool {
+    !function.body.first().is_placeholder()
+        && (function.return_annotation.is_some()
+            || function
+                .arguments
+                .iter()
+                .any(|argument| argument.annotation.is_some()))
+}
+
+/// Infers the type the function would have without any of its annotations
+/// and warns if the annotated one is strictly less general:
+///
+/// ```gleam
+/// fn identity(x: Int) -> Int { x }
+/// // ^^^^^^^^^^^^^^^^^^^^^^^ could be `fn(a) -> a`
+/// ```
+///
+/// Functions using `todo` or `panic` are not checked, as their inferred type
+/// is always as general as it can be and the annotation is what gives them
+/// their intended type.
+///
+/// This must be called once the function, and all the functions it depends
+/// on, have been inferred and generalised.
+///
+pub fn check_overly_specific_annotation(
+    function: UntypedFunction,
+    environment: &mut Environment<'_>,
+    problems: &mut Problems,
+) {
+    let Some((_, name)) = &function.name else {
+        return;
+    };
+    let location = function.location;
+    let Some(annotated) = environment
+        .get_variable(name)
+        .map(|function| function.type_.clone())
+    else {
+        return;
+    };
+
+    // The body is inferred a second time, so anything it records in the
+    // environment is thrown away, and so are the problems it would report.
+    let references = std::mem::take(&mut environment.references);
+    let echo_found = environment.echo_found;
+    let inferred = infer_without_annotations(function, environment);
+    environment.references = references;
+    environment.echo_found = echo_found;
+
+    let Some(inferred) = inferred else {
+        return;
+    };
+
+    // The annotated type is always an instance of the inferred one, as the
+    // function type checked with its annotations. If the inferred type is not
+    // also an instance of the annotated one then it's strictly more general.
+    let inferred = type_::generalise(inferred);
+    let annotated = environment.instantiate(annotated, &mut im::hashmap![], &Hydrator::new());
+    if unify(annotated, inferred.clone()).is_err() {
+        problems.warning(Warning::OverlySpecificAnnotation { location, inferred });
+    }
+}
+
+fn infer_without_annotations(
+    function: UntypedFunction,
+    environment: &mut Environment<'_>,
+) -> Option<Arc<Type>> {
+    let definition = FunctionDefinition {
+        has_body: true,
+        has_erlang_external: function.external_erlang.is_some(),
+        has_javascript_external: function.external_javascript.is_some(),
+        is_generated: false,
+    };
+    let mut problems = Problems::new();
+    environment
+        .in_new_scope(&mut problems, |environment, problems| {
+            let mut expr_typer = ExprTyper::new(environment, definition, problems);
+            let arguments = function
+                .arguments
+                .into_iter()
+                .map(|mut argument| {
+                    argument.annotation = None;
+                    argument.set_type(expr_typer.environment.new_unbound_var())
+                })
+                .collect();
+            let (arguments, body) =
+                expr_typer.infer_fn_with_known_types(arguments, function.body, None)?;
+
+            let mut finder = TodoOrPanicFinder { found: false };
+            for statement in &body {
+                finder.visit_typed_statement(statement);
+            }
+            if finder.found {
+                return Ok(None);
+            }
+
+            let arguments = arguments.iter().map(|argument| argument.type_.clone());
+            Ok::<_, type_::Error>(Some(fn_(arguments.collect(), body.last().type_())))
+        })
+        .ok()
+        .flatten()
+}
+
+struct TodoOrPanicFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for TodoOrPanicFinder {
+    fn visit_typed_expr(&mut self, expr: &'ast TypedExpr) {
+        match expr {
+            TypedExpr::Todo { .. } | TypedExpr::Panic { .. } => self.found = true,
+            _ => visit::visit_typed_expr(self, expr),
+        }
+    }
+
+    fn visit_typed_expr_invalid(&mut self, _location: &'ast SrcSpan, _type_: &'ast Arc<Type>) {
+        self.found = true;
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use crate::{
+    ast::{
+        BinOp, SrcSpan, Statement, TypedDefinition, TypedExpr,
+        visit::{self, Visit},
+    },
+    type_::{
+        PRELUDE_MODULE_NAME, Problems, Type, ValueConstructorVariant, Warning,
+        error::SimplifiedBool,
+    },
+};
+
+/// Looks for boolean expressions that can be written more simply in terms of
+/// one of their operands:
+///
+/// ```gleam
+/// x == True   // x
+/// x == False  // !x
+/// x != True   // !x
+/// x != False  // x
+/// !{ !x }     // x
+/// ```
+///
+/// A double negation written without a block, `!!x`, is already reported
+/// while inferring the expression so it's not reported again here.
+///
+/// `True` and `False` are only recognised if they are the prelude's
+/// constructors, so a custom type with constructors of the same name is not
+/// mistaken for a `Bool`.
+///
+pub fn check_simplifiable_bools(definitions: &[TypedDefinition], problems: &mut Problems) {
+    let mut finder = SimplifiableBoolFinder { problems };
+    for definition in definitions {
+        finder.visit_typed_definition(definition);
+    }
+}
+
+struct SimplifiableBoolFinder<'a> {
+    problems: &'a mut Problems,
+}
+
+impl<'ast> Visit<'ast> for SimplifiableBoolFinder<'_> {
+    fn visit_typed_expr_bin_op(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        name: &'ast BinOp,
+        left: &'ast TypedExpr,
+        right: &'ast TypedExpr,
+    ) {
+        let comparison = match name {
+            BinOp::Eq => Some(true),
+            BinOp::NotEq => Some(false),
+            _ => None,
+        };
+        let simplification =
+            comparison.and_then(|is_eq| match (bool_literal(left), bool_literal(right)) {
+                (None, Some(literal)) => Some((left, is_eq == literal)),
+                (Some(literal), None) => Some((right, is_eq == literal)),
+                (Some(_), Some(_)) | (None, None) => None,
+            });
+
+        if let Some((operand, keeps_value)) = simplification {
+            let simplified = if keeps_value {
+                SimplifiedBool::Operand
+            } else if needs_block_to_be_negated(operand) {
+                SimplifiedBool::NegatedBlock
+            } else {
+                SimplifiedBool::Negated
+            };
+            self.problems.warning(Warning::SimplifiableBool {
+                location: *location,
+                operand: operand.location(),
+                simplified,
+            });
+        }
+
+        visit::visit_typed_expr_bin_op(self, location, type_, name, left, right);
+    }
+
+    fn visit_typed_expr_negate_bool(&mut self, location: &'ast SrcSpan, value: &'ast TypedExpr) {
+        match negated_value(value) {
+            // We don't look for a double negation in the inner negation, or
+            // `!{ !{ !x } }` would be reported twice.
+            Some(operand) => {
+                self.problems.warning(Warning::SimplifiableBool {
+                    location: *location,
+                    operand: operand.location(),
+                    simplified: SimplifiedBool::Operand,
+                });
+                self.visit_typed_expr(operand);
+            }
+            None => visit::visit_typed_expr_negate_bool(self, location, value),
+        }
+    }
+}
+
+/// The value of the prelude's `True` or `False` constructors, if this is one
+/// of them.
+///
+fn bool_literal(expr: &TypedExpr) -> Option<bool> {
+    let TypedExpr::Var { constructor, .. } = expr else {
+        return None;
+    };
+    match &constructor.variant {
+        ValueConstructorVariant::Record { name, module, .. }
+            if module == PRELUDE_MODULE_NAME && constructor.type_.is_bool() =>
+        {
+            Some(name == "True")
+        }
+        _ => None,
+    }
+}
+
+/// If this is a negation wrapped in a block, like `{ !x }`, returns the value
+/// being negated.
+///
+fn negated_value(expr: &TypedExpr) -> Option<&TypedExpr> {
+    let TypedExpr::Block { statements, .. } = expr else {
+        return None;
+    };
+    match statements.as_slice() {
+        [Statement::Expression(TypedExpr::NegateBool { value, .. })] => Some(value),
+        [Statement::Expression(expr)] => negated_value(expr),
+        _ => None,
+    }
+}
+
+/// Whether `!` would only apply to part of this expression if written right
+/// before it, like with `!a < b`.
+///
+fn needs_block_to_be_negated(expr: &TypedExpr) -> bool {
+    matches!(
+        expr,
+        TypedExpr::BinOp { .. } | TypedExpr::Pipeline { .. } | TypedExpr::Echo { .. }
+    )
+}
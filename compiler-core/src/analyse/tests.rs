@@ -18,3 +18,193 @@ fn module_name_validation() {
 
     assert!(validate_module_name(&"ok/pub".into()).is_err());
 }
+
+fn infer_expression(src: &str) -> Result<(TypedExpr, String), crate::Error> {
+    infer_expression_with_modules(src, "", vec![])
+}
+
+/// Infers an expression with the given imports in scope, in a package where
+/// each of `modules` is a module with the given name and source code.
+///
+fn infer_expression_with_modules(
+    src: &str,
+    imports: &str,
+    modules: Vec<(&str, &str)>,
+) -> Result<(TypedExpr, String), crate::Error> {
+    let ids = UniqueIdGenerator::new();
+    let mut importable_modules = im::HashMap::new();
+    for (name, module_src) in modules {
+        let module = crate::type_::tests::compile_module(name, module_src, None, vec![])
+            .expect("module should compile");
+        let _ = importable_modules.insert(name.into(), module.type_info);
+    }
+    let imports = crate::parse::parse_module(
+        Utf8PathBuf::from("repl.gleam"),
+        imports,
+        &WarningEmitter::null(),
+    )
+    .expect("imports should parse")
+    .module
+    .definitions
+    .into_iter()
+    .filter_map(|definition| match definition.definition {
+        Definition::Import(import) => Some(import),
+        _ => None,
+    })
+    .collect_vec();
+
+    let (expression, type_) = ExpressionAnalyzerConstructor {
+        target: Target::Erlang,
+        ids: &ids,
+        importable_modules: &importable_modules,
+        warnings: &WarningEmitter::null(),
+        package_config: &PackageConfig::default(),
+        module_name: "repl".into(),
+        imports: &imports,
+    }
+    .infer_expression(src, Utf8PathBuf::from("repl.gleam"))?;
+    let type_ = type_::pretty::Printer::new().pretty_print(&type_, 0);
+    Ok((expression, type_))
+}
+
+#[test]
+fn infer_single_expression() {
+    let (expression, type_) = infer_expression("1 + 2").expect("should infer");
+    assert!(matches!(expression, TypedExpr::BinOp { .. }));
+    assert_eq!(type_, "Int");
+}
+
+#[test]
+fn infer_single_expression_pipeline() {
+    let (expression, type_) =
+        infer_expression("[1, 2] |> fn(x) { x } |> fn(x) { Ok(x) }").expect("should infer");
+    assert!(matches!(expression, TypedExpr::Pipeline { .. }));
+    assert_eq!(type_, "Result(List(Int), a)");
+}
+
+#[test]
+fn infer_single_expression_case() {
+    let (expression, type_) =
+        infer_expression("case 1 { 0 -> \"zero\" _ -> \"other\" }").expect("should infer");
+    assert!(matches!(expression, TypedExpr::Case { .. }));
+    assert_eq!(type_, "String");
+}
+
+#[test]
+fn infer_single_expression_use() {
+    let src = "
+let apply = fn(value, next) { next(value) }
+use x <- apply(1.0)
+#(x, x)
+";
+    let (expression, type_) = infer_expression(src).expect("should infer");
+    assert!(matches!(expression, TypedExpr::Block { .. }));
+    assert_eq!(type_, "#(Float, Float)");
+}
+
+#[test]
+fn infer_single_expression_type_error() {
+    let error = infer_expression("1 + 1.0").expect_err("should not infer");
+    assert!(matches!(error, crate::Error::Type { .. }));
+}
+
+#[test]
+fn infer_single_expression_syntax_error() {
+    let error = infer_expression("1 +").expect_err("should not parse");
+    assert!(matches!(error, crate::Error::Parse { .. }));
+}
+
+#[test]
+fn infer_single_expression_using_an_imported_module() {
+    let (expression, type_) = infer_expression_with_modules(
+        "[1, 2] |> wibble.double",
+        "import wibble",
+        vec![("wibble", "pub fn double(list: List(a)) -> List(a) { list }")],
+    )
+    .expect("should infer");
+    assert!(matches!(expression, TypedExpr::Pipeline { .. }));
+    assert_eq!(type_, "List(Int)");
+}
+
+#[test]
+fn infer_single_expression_using_an_unqualified_import() {
+    let (expression, type_) = infer_expression_with_modules(
+        "Wobble(double([1]))",
+        "import wibble.{Wobble, double}",
+        vec![(
+            "wibble",
+            "pub type Wibble { Wobble(List(Int)) }
+pub fn double(list: List(a)) -> List(a) { list }",
+        )],
+    )
+    .expect("should infer");
+    assert!(matches!(expression, TypedExpr::Call { .. }));
+    assert_eq!(type_, "Wibble");
+}
+
+#[test]
+fn infer_single_expression_using_the_current_module() {
+    let (_, type_) = infer_expression_with_modules(
+        "case wibble() { Wobble(x) -> x }",
+        "",
+        vec![(
+            "repl",
+            "type Wibble { Wobble(Float) }
+fn wibble() { Wobble(1.0) }",
+        )],
+    )
+    .expect("should infer");
+    assert_eq!(type_, "Float");
+}
+
+#[test]
+fn infer_single_expression_using_a_module_that_is_not_imported() {
+    let error = infer_expression_with_modules(
+        "wibble.double([1])",
+        "",
+        vec![("wibble", "pub fn double(list: List(a)) -> List(a) { list }")],
+    )
+    .expect_err("should not infer");
+    assert!(matches!(error, crate::Error::Type { .. }));
+}
+
+#[test]
+fn warnings_are_emitted_to_a_custom_sink_in_order() {
+    let emitted = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+    let sink = emitted.clone();
+    let warnings = WarningEmitter::callback(move |warning| sink.borrow_mut().push(warning));
+
+    let ids = UniqueIdGenerator::new();
+    let _ = ExpressionAnalyzerConstructor {
+        target: Target::Erlang,
+        ids: &ids,
+        importable_modules: &im::HashMap::new(),
+        warnings: &warnings,
+        package_config: &PackageConfig::default(),
+        module_name: "repl".into(),
+        imports: &[],
+    }
+    .infer_expression("1\n\"two\"\ntodo", Utf8PathBuf::from("repl.gleam"))
+    .expect("should infer");
+
+    let emitted = emitted
+        .borrow()
+        .iter()
+        .map(|warning| match warning {
+            crate::warning::Warning::Type {
+                warning: type_::Warning::UnusedLiteral { location },
+                ..
+            } => format!("unused literal at {}", location.start),
+            crate::warning::Warning::Type {
+                warning: type_::Warning::Todo { location, .. },
+                ..
+            } => format!("todo at {}", location.start),
+            _ => panic!("unexpected warning {warning:?}"),
+        })
+        .collect_vec();
+    assert_eq!(
+        emitted,
+        vec!["unused literal at 0", "unused literal at 2", "todo at 8"]
+    );
+    assert_eq!(warnings.count(), 3);
+}
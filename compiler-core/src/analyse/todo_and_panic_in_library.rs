@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    ast::{
+        SrcSpan, TodoKind, TypedDefinition, TypedExpr,
+        visit::{self, Visit},
+    },
+    type_::{Problems, Type, Warning, error::TodoOrPanic},
+};
+
+/// Looks for `todo` and `panic` expressions in the `src` modules of a
+/// package, as they would crash the programs of anyone using the package if
+/// they were ever run:
+///
+/// ```gleam
+/// pub fn parse(input: String) -> Result(Int, Nil) {
+///   todo
+/// //^^^^ warning here
+/// }
+/// ```
+///
+pub fn check_todo_and_panic_in_library(definitions: &[TypedDefinition], problems: &mut Problems) {
+    let mut finder = TodoAndPanicFinder { problems };
+    for definition in definitions {
+        finder.visit_typed_definition(definition);
+    }
+}
+
+struct TodoAndPanicFinder<'a> {
+    problems: &'a mut Problems,
+}
+
+impl<'ast> Visit<'ast> for TodoAndPanicFinder<'_> {
+    fn visit_typed_expr_todo(
+        &mut self,
+        location: &'ast SrcSpan,
+        message: &'ast Option<Box<TypedExpr>>,
+        kind: &'ast TodoKind,
+        type_: &'ast Arc<Type>,
+    ) {
+        // A function with no body is reported as a whole, like the regular
+        // `todo` warning does.
+        let warning_location = match kind {
+            TodoKind::Keyword
+            | TodoKind::IncompleteUse
+            | TodoKind::EmptyBlock
+            | TodoKind::PipelineStep => *location,
+            TodoKind::EmptyFunction { function_location } => *function_location,
+        };
+        self.problems.warning(Warning::TodoOrPanicInLibrary {
+            location: warning_location,
+            kind: TodoOrPanic::Todo,
+        });
+
+        visit::visit_typed_expr_todo(self, location, message, kind, type_);
+    }
+
+    fn visit_typed_expr_panic(
+        &mut self,
+        location: &'ast SrcSpan,
+        message: &'ast Option<Box<TypedExpr>>,
+        type_: &'ast Arc<Type>,
+    ) {
+        self.problems.warning(Warning::TodoOrPanicInLibrary {
+            location: *location,
+            kind: TodoOrPanic::Panic,
+        });
+
+        visit::visit_typed_expr_panic(self, location, message, type_);
+    }
+}
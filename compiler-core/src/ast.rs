@@ -7,7 +7,7 @@ mod tests;
 pub mod visit;
 
 pub use self::typed::TypedExpr;
-pub use self::untyped::{FunctionLiteralKind, UntypedExpr};
+pub use self::untyped::{ComprehensionQualifier, FunctionLiteralKind, UntypedExpr};
 
 pub use self::constant::{Constant, TypedConstant, UntypedConstant};
 
@@ -18,7 +18,7 @@ use crate::type_::error::VariableOrigin;
 use crate::type_::expression::Implementations;
 use crate::type_::printer::Names;
 use crate::type_::{
-    self, Deprecation, ModuleValueConstructor, PatternConstructor, Type, ValueConstructor,
+    self, Deprecation, ModuleValueConstructor, PatternConstructor, Purity, Type, ValueConstructor,
 };
 use std::sync::Arc;
 
@@ -36,6 +36,11 @@ pub const ASSERT_SUBJECT_VARIABLE: &str = "_assert_subject";
 pub const CAPTURE_VARIABLE: &str = "_capture";
 pub const BLOCK_VARIABLE: &str = "_block";
 
+/// The qualifier that can be used to refer to the values of the current
+/// module, as in `self.wibble()`, as long as there's no variable or imported
+/// module with the same name.
+pub const SELF_QUALIFIER: &str = "self";
+
 pub trait HasLocation {
     fn location(&self) -> SrcSpan;
 }
@@ -77,16 +82,32 @@ impl TypedModule {
 /// pub fn main(a) { ...}
 /// ```
 ///
+/// Likewise, the `@when(feature: "...")` attribute can be used to mark a
+/// definition as only being compiled if the given feature flag is enabled in
+/// the package's `gleam.toml`.
+///
+/// ```gleam
+/// @when(feature: "experimental")
+/// pub fn new_api() { ... }
+/// ```
+///
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TargetedDefinition {
     pub definition: UntypedDefinition,
     pub target: Option<Target>,
+    pub feature: Option<EcoString>,
 }
 
 impl TargetedDefinition {
     pub fn is_for(&self, target: Target) -> bool {
         self.target.map(|t| t == target).unwrap_or(true)
     }
+
+    pub fn is_enabled(&self, features: &[EcoString]) -> bool {
+        self.feature
+            .as_ref()
+            .is_none_or(|feature| features.contains(feature))
+    }
 }
 
 impl UntypedModule {
@@ -680,7 +701,79 @@ pub struct Function<T, Expr> {
     pub documentation: Option<(u32, EcoString)>,
     pub external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     pub external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
+    /// The arguments passed to the Erlang external function, if they are
+    /// not the same as the ones of the Gleam function.
+    pub external_erlang_arguments: Option<Box<ExternalArguments>>,
+    /// The arguments passed to the JavaScript external function, if they
+    /// are not the same as the ones of the Gleam function.
+    pub external_javascript_arguments: Option<Box<ExternalArguments>>,
     pub implementations: Implementations,
+    pub purity: Purity,
+    /// Whether the function has been annotated with `@benchmark`, and is
+    /// going to be run by `gleam bench`.
+    pub benchmark: bool,
+    /// Whether the function has been annotated with `@memoize`, so that the
+    /// generated code caches the value it returns for each of its arguments.
+    pub memoize: bool,
+    /// How the function is expected to panic, if it has been annotated with
+    /// `@test(should_panic)`. The generated code fails if running its body
+    /// doesn't panic as expected.
+    pub should_panic: Option<ShouldPanic>,
+    /// The version given to the `@since` attribute, if any: the version of
+    /// the package the function was added in.
+    pub since: Option<EcoString>,
+    /// The strings given to the `@doc` attribute, if any, as they are
+    /// written. Their content is already part of the `documentation`.
+    pub doc_attribute: Vec<EcoString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How a test function annotated with `@test(should_panic)` is expected to
+/// panic.
+///
+/// # Example(s)
+///
+/// ```gleam
+/// @test(should_panic, "division by zero")
+/// pub fn divide_by_zero_test() {
+///   // ...
+/// }
+/// ```
+///
+pub struct ShouldPanic {
+    /// A string the panic message must contain, if any.
+    pub message: Option<EcoString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The arguments an external function is called with, in order, listed as the
+/// names of the arguments of the Gleam function wrapping it.
+///
+/// # Example(s)
+///
+/// ```gleam
+/// @external(erlang, "lists", "foldl", [fun, initial, list])
+/// pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+/// ```
+pub struct ExternalArguments {
+    pub location: SrcSpan,
+    pub names: Vec<SpannedString>,
+}
+
+impl ExternalArguments {
+    /// Puts the given values, one for each of the arguments of the Gleam
+    /// function, in the order they are passed to the external function.
+    pub fn reorder<A, V: Clone>(&self, arguments: &[Arg<A>], values: &[V]) -> Vec<V> {
+        self.names
+            .iter()
+            .filter_map(|(_, name)| {
+                arguments
+                    .iter()
+                    .position(|argument| argument.get_variable_name() == Some(name))
+            })
+            .filter_map(|position| values.get(position).cloned())
+            .collect()
+    }
 }
 
 pub type TypedFunction = Function<Arc<Type>, TypedExpr>;
@@ -712,6 +805,9 @@ pub struct Import<PackageName> {
     pub as_name: Option<(AssignName, SrcSpan)>,
     pub unqualified_values: Vec<UnqualifiedImport>,
     pub unqualified_types: Vec<UnqualifiedImport>,
+    /// The location of the `type *` in an import that brings all the public
+    /// types of the module into scope: `import wibble.{type *}`.
+    pub unqualified_types_glob: Option<SrcSpan>,
     pub package: PackageName,
 }
 
@@ -754,6 +850,12 @@ pub struct ModuleConstant<T, ConstantRecordTag> {
     pub type_: T,
     pub deprecation: Deprecation,
     pub implementations: Implementations,
+    /// The version given to the `@since` attribute, if any: the version of
+    /// the package the constant was added in.
+    pub since: Option<EcoString>,
+    /// The strings given to the `@doc` attribute, if any, as they are
+    /// written. Their content is already part of the `documentation`.
+    pub doc_attribute: Vec<EcoString>,
 }
 
 pub type UntypedCustomType = CustomType<()>;
@@ -790,6 +892,53 @@ pub struct CustomType<T> {
     /// Once type checked this field will contain the type information for the
     /// type parameters.
     pub typed_parameters: Vec<T>,
+    /// The capabilities the type implements with the `@implements` and
+    /// `@derive` attributes, along with the location of the attribute.
+    pub capabilities: Vec<(Capability, SrcSpan)>,
+    /// The strings given to the `@doc` attribute, if any, as they are
+    /// written. Their content is already part of the `documentation`.
+    pub doc_attribute: Vec<EcoString>,
+}
+
+/// A fixed set of operations that the compiler can generate functions for
+/// when a custom type is annotated with `@implements(capability)` or
+/// `@derive(capability)`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Generates a `compare_<type name>` function comparing two values by the
+    /// order their variants are defined in.
+    Comparable,
+    /// Generates `<type name>_to_json` and `<type name>_from_json` functions
+    /// converting values to and from JSON.
+    Json,
+}
+
+impl Capability {
+    /// Returns the capability with the given name, if it can be used with the
+    /// given attribute.
+    pub fn from_name(attribute: &str, name: &str) -> Option<Self> {
+        match (attribute, name) {
+            ("implements", "comparable") => Some(Self::Comparable),
+            ("derive", "json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Comparable => "comparable",
+            Self::Json => "json",
+        }
+    }
+
+    /// The name of the attribute used to give a type this capability.
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            Self::Comparable => "implements",
+            Self::Json => "derive",
+        }
+    }
 }
 
 impl<T> CustomType<T> {
@@ -822,6 +971,28 @@ pub struct TypeAlias<T> {
     pub publicity: Publicity,
     pub documentation: Option<(u32, EcoString)>,
     pub deprecation: Deprecation,
+    /// The strings given to the `@doc` attribute, if any, as they are
+    /// written. Their content is already part of the `documentation`.
+    pub doc_attribute: Vec<EcoString>,
+}
+
+/// Adds the content of a definition's `@doc` attribute after its doc
+/// comments, so that they make up the documentation together.
+///
+pub fn with_doc_attribute(documentation: &str, doc_attribute: &[EcoString]) -> EcoString {
+    if doc_attribute.is_empty() {
+        return documentation.into();
+    }
+
+    let mut content = documentation.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for string in doc_attribute {
+        content.push_str(&crate::strings::convert_string_escape_chars(string));
+    }
+    content.push('\n');
+    content.into()
 }
 
 pub type TypedDefinition = Definition<Arc<Type>, TypedExpr, EcoString, EcoString>;
@@ -1044,11 +1215,29 @@ impl<A, B, C, E> Definition<A, B, C, E> {
         match self {
             Definition::Import(Import { .. }) => (),
 
-            Definition::Function(Function { documentation, .. })
-            | Definition::TypeAlias(TypeAlias { documentation, .. })
-            | Definition::CustomType(CustomType { documentation, .. })
-            | Definition::ModuleConstant(ModuleConstant { documentation, .. }) => {
-                let _ = std::mem::replace(documentation, Some(new_doc));
+            Definition::Function(Function {
+                documentation,
+                doc_attribute,
+                ..
+            })
+            | Definition::TypeAlias(TypeAlias {
+                documentation,
+                doc_attribute,
+                ..
+            })
+            | Definition::CustomType(CustomType {
+                documentation,
+                doc_attribute,
+                ..
+            })
+            | Definition::ModuleConstant(ModuleConstant {
+                documentation,
+                doc_attribute,
+                ..
+            }) => {
+                let (start, doc) = new_doc;
+                let doc = with_doc_attribute(&doc, doc_attribute);
+                let _ = std::mem::replace(documentation, Some((start, doc)));
             }
         }
     }
@@ -1099,7 +1288,9 @@ impl UnqualifiedImport {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Copy, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
 pub enum Layer {
     #[default]
     Value,
@@ -1272,6 +1463,10 @@ pub enum ImplicitCallArgOrigin {
     /// the record update synax.
     ///
     RecordUpdate,
+    /// An argument added by the compiler when desugaring a list comprehension
+    /// to calls to the `gleam/list` module.
+    ///
+    ListComprehension,
 }
 
 impl<A> CallArg<A> {
@@ -1393,6 +1588,7 @@ pub struct RecordBeingUpdated {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UntypedRecordUpdateArg {
     pub label: EcoString,
+    pub label_location: SrcSpan,
     pub location: SrcSpan,
     pub value: UntypedExpr,
 }
@@ -1768,6 +1964,13 @@ impl SrcSpan {
             end: self.end.max(with.end),
         }
     }
+
+    /// Merges all the given spans into a single one covering all of them.
+    /// Returns `None` if there's no span to merge.
+    ///
+    pub fn union_all(spans: impl IntoIterator<Item = SrcSpan>) -> Option<SrcSpan> {
+        spans.into_iter().reduce(|one, other| one.merge(&other))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -1851,6 +2054,17 @@ pub enum Pattern<Type> {
         type_: Type,
     },
 
+    /// A constant defined in another module. When the pattern is type
+    /// checked it is replaced by the literal value of the constant, so it
+    /// never ends up in a typed AST.
+    /// e.g. `assert numbers.zero = x`
+    Constant {
+        location: SrcSpan,
+        module: (EcoString, SrcSpan),
+        name: EcoString,
+        type_: Type,
+    },
+
     Tuple {
         location: SrcSpan,
         elements: Vec<Self>,
@@ -1931,6 +2145,7 @@ impl<A> Pattern<A> {
             | Pattern::Constructor { location, .. }
             | Pattern::StringPrefix { location, .. }
             | Pattern::BitArray { location, .. }
+            | Pattern::Constant { location, .. }
             | Pattern::Invalid { location, .. } => *location,
         }
     }
@@ -1966,6 +2181,7 @@ impl TypedPattern {
             | Pattern::Tuple { .. }
             | Pattern::BitArray { .. }
             | Pattern::StringPrefix { .. }
+            | Pattern::Constant { .. }
             | Pattern::Invalid { .. } => None,
 
             Pattern::Constructor { constructor, .. } => constructor.definition_location(),
@@ -1985,6 +2201,7 @@ impl TypedPattern {
             | Pattern::Tuple { .. }
             | Pattern::BitArray { .. }
             | Pattern::StringPrefix { .. }
+            | Pattern::Constant { .. }
             | Pattern::Invalid { .. } => None,
 
             Pattern::Constructor { constructor, .. } => constructor.get_documentation(),
@@ -2003,6 +2220,7 @@ impl TypedPattern {
             | Pattern::List { type_, .. }
             | Pattern::VarUsage { type_, .. }
             | Pattern::Constructor { type_, .. }
+            | Pattern::Constant { type_, .. }
             | Pattern::Invalid { type_, .. } => type_.clone(),
 
             Pattern::Assign { pattern, .. } => pattern.type_(),
@@ -2036,6 +2254,7 @@ impl TypedPattern {
             | Pattern::Assign { .. }
             | Pattern::Discard { .. }
             | Pattern::StringPrefix { .. }
+            | Pattern::Constant { .. }
             | Pattern::Invalid { .. } => Some(Located::Pattern(self)),
 
             Pattern::Constructor {
@@ -2347,9 +2566,14 @@ impl BitArrayOption<TypedPattern> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum TodoKind {
     Keyword,
-    EmptyFunction { function_location: SrcSpan },
+    EmptyFunction {
+        function_location: SrcSpan,
+    },
     IncompleteUse,
     EmptyBlock,
+    /// A `todo` written as the final step of a pipeline, as a placeholder for
+    /// the function that is yet to be written: `wibble |> todo`.
+    PipelineStep,
 }
 
 #[derive(Debug, Default)]
@@ -2407,6 +2631,56 @@ pub enum Statement<TypeT, ExpressionT> {
     Assignment(Assignment<TypeT, ExpressionT>),
     /// A `use` expression.
     Use(Use<TypeT, ExpressionT>),
+    /// A boolean assertion: `assert x > 0`.
+    Assert(Assert<ExpressionT>),
+}
+
+pub type UntypedAssert = Assert<UntypedExpr>;
+pub type TypedAssert = Assert<TypedExpr>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assert<ExpressionT> {
+    /// This is the location of the whole assertion, starting from the
+    /// `assert` keyword and ending with the asserted value.
+    ///
+    /// ```gleam
+    /// assert x > 0
+    /// ^^^^^^^^^^^^
+    /// ```
+    ///
+    pub location: SrcSpan,
+
+    /// The value that must evaluate to `True`. Its location is used to get
+    /// its source code, that is included in the error message if the
+    /// assertion fails.
+    ///
+    pub value: ExpressionT,
+}
+
+impl TypedAssert {
+    /// The message of the error raised when the assertion fails. It includes
+    /// the source code of the asserted value, escaped so that it can be used
+    /// as the content of a Gleam string literal.
+    ///
+    pub fn failure_message(&self, src: &str) -> EcoString {
+        let location = self.value.location();
+        let source = src
+            .get(location.start as usize..location.end as usize)
+            .unwrap_or_default();
+
+        let mut message = EcoString::from("Assertion failed: ");
+        for char in source.chars() {
+            match char {
+                '\\' => message.push_str("\\\\"),
+                '"' => message.push_str("\\\""),
+                '\n' => message.push_str("\\n"),
+                '\r' => message.push_str("\\r"),
+                '\t' => message.push_str("\\t"),
+                char => message.push(char),
+            }
+        }
+        message
+    }
 }
 
 pub type UntypedUse = Use<(), UntypedExpr>;
@@ -2516,6 +2790,7 @@ impl UntypedStatement {
             Statement::Expression(expression) => expression.location(),
             Statement::Assignment(assignment) => assignment.location,
             Statement::Use(use_) => use_.location,
+            Statement::Assert(assert) => assert.location,
         }
     }
 
@@ -2524,13 +2799,14 @@ impl UntypedStatement {
             Statement::Expression(expression) => expression.start_byte_index(),
             Statement::Assignment(assignment) => assignment.location.start,
             Statement::Use(use_) => use_.location.start,
+            Statement::Assert(assert) => assert.location.start,
         }
     }
 
     pub fn is_placeholder(&self) -> bool {
         match self {
             Statement::Expression(expression) => expression.is_placeholder(),
-            Statement::Assignment(_) | Statement::Use(_) => false,
+            Statement::Assignment(_) | Statement::Use(_) | Statement::Assert(_) => false,
         }
     }
 }
@@ -2541,6 +2817,7 @@ impl TypedStatement {
             Statement::Expression(e) => e.is_println(),
             Statement::Assignment(_) => false,
             Statement::Use(_) => false,
+            Statement::Assert(_) => false,
         }
     }
 
@@ -2549,6 +2826,7 @@ impl TypedStatement {
             Statement::Expression(expression) => expression.location(),
             Statement::Assignment(assignment) => assignment.location,
             Statement::Use(use_) => use_.location,
+            Statement::Assert(assert) => assert.location,
         }
     }
 
@@ -2560,6 +2838,7 @@ impl TypedStatement {
             Statement::Expression(expression) => expression.last_location(),
             Statement::Assignment(assignment) => assignment.value.last_location(),
             Statement::Use(use_) => use_.call.last_location(),
+            Statement::Assert(assert) => assert.value.last_location(),
         }
     }
 
@@ -2568,6 +2847,7 @@ impl TypedStatement {
             Statement::Expression(expression) => expression.type_(),
             Statement::Assignment(assignment) => assignment.type_(),
             Statement::Use(_use) => _use.call.type_(),
+            Statement::Assert(_) => type_::nil(),
         }
     }
 
@@ -2576,12 +2856,20 @@ impl TypedStatement {
             Statement::Expression(expression) => expression.definition_location(),
             Statement::Assignment(_) => None,
             Statement::Use(use_) => use_.call.definition_location(),
+            Statement::Assert(_) => None,
         }
     }
 
     pub fn find_node(&self, byte_index: u32) -> Option<Located<'_>> {
         match self {
             Statement::Use(use_) => use_.find_node(byte_index),
+            Statement::Assert(assert) => assert.value.find_node(byte_index).or_else(|| {
+                if assert.location.contains(byte_index) {
+                    Some(Located::Statement(self))
+                } else {
+                    None
+                }
+            }),
             Statement::Expression(expression) => expression.find_node(byte_index),
             Statement::Assignment(assignment) => assignment.find_node(byte_index).or_else(|| {
                 if assignment.location.contains(byte_index) {
@@ -2597,6 +2885,13 @@ impl TypedStatement {
         match self {
             Statement::Use(use_) => use_.call.find_statement(byte_index),
             Statement::Expression(expression) => expression.find_statement(byte_index),
+            Statement::Assert(assert) => assert.value.find_statement(byte_index).or_else(|| {
+                if assert.location.contains(byte_index) {
+                    Some(self)
+                } else {
+                    None
+                }
+            }),
             Statement::Assignment(assignment) => {
                 assignment.value.find_statement(byte_index).or_else(|| {
                     if assignment.location.contains(byte_index) {
@@ -2614,6 +2909,7 @@ impl TypedStatement {
             Statement::Expression(expression) => expression.type_defining_location(),
             Statement::Assignment(assignment) => assignment.location,
             Statement::Use(use_) => use_.location,
+            Statement::Assert(assert) => assert.location,
         }
     }
 
@@ -2626,6 +2922,8 @@ impl TypedStatement {
                 !assignment.kind.is_assert() && assignment.value.is_pure_value_constructor()
             }
             Statement::Use(Use { call, .. }) => call.is_pure_value_constructor(),
+            // Just like a let assert, an assertion could crash the program.
+            Statement::Assert(_) => false,
         }
     }
 }
@@ -2687,6 +2985,10 @@ pub struct TypedPipelineAssignment {
     pub location: SrcSpan,
     pub name: EcoString,
     pub value: Box<TypedExpr>,
+    /// The location of the `|>` operator piping this value into the next
+    /// step of the pipeline. This is `None` if the pipeline wasn't written by
+    /// the programmer and so has no operators.
+    pub operator_location: Option<SrcSpan>,
 }
 
 impl TypedPipelineAssignment {
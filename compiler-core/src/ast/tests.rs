@@ -8,7 +8,7 @@ use crate::config::PackageConfig;
 use crate::line_numbers::LineNumbers;
 use crate::type_::error::VariableOrigin;
 use crate::type_::expression::FunctionDefinition;
-use crate::type_::{Deprecation, PRELUDE_MODULE_NAME, Problems};
+use crate::type_::{Deprecation, PRELUDE_MODULE_NAME, Problems, Purity};
 use crate::warning::WarningEmitter;
 use crate::{
     ast::{SrcSpan, TypedExpr},
@@ -59,7 +59,7 @@ fn compile_module(src: &str) -> TypedModule {
 fn get_bare_expression(statement: &TypedStatement) -> &TypedExpr {
     match statement {
         Statement::Expression(expression) => expression,
-        Statement::Use(_) | Statement::Assignment(_) => {
+        Statement::Use(_) | Statement::Assignment(_) | Statement::Assert(_) => {
             panic!("Expected expression, got {statement:?}")
         }
     }
@@ -150,6 +150,7 @@ fn compile_expression(src: &str) -> TypedStatement {
             has_body: true,
             has_erlang_external: false,
             has_javascript_external: false,
+            is_generated: false,
         },
         &mut problems,
     )
@@ -373,12 +374,14 @@ fn find_node_module_select() {
         module_name: "name".into(),
         module_alias: "alias".into(),
         constructor: ModuleValueConstructor::Fn {
+            purity: Purity::Unknown,
             module: "module".into(),
             name: "function".into(),
             external_erlang: None,
             external_javascript: None,
             location: SrcSpan { start: 1, end: 55 },
             documentation: None,
+            since: None,
             field_map: None,
         },
     };
@@ -623,3 +626,70 @@ use x <- fn(f) { f(1) }
     assert!(use_.find_node(23).is_some());
     assert!(use_.find_node(26).is_some()); // The int
 }
+
+#[test]
+fn src_span_contains() {
+    let span = SrcSpan::new(2, 5);
+    assert!(!span.contains(1));
+    assert!(span.contains(2));
+    assert!(span.contains(4));
+    assert!(span.contains(5));
+    assert!(!span.contains(6));
+}
+
+#[test]
+fn src_span_contains_empty_span() {
+    let span = SrcSpan::new(3, 3);
+    assert!(!span.contains(2));
+    assert!(span.contains(3));
+    assert!(!span.contains(4));
+}
+
+#[test]
+fn src_span_merge_disjoint_spans() {
+    let one = SrcSpan::new(1, 3);
+    let other = SrcSpan::new(6, 9);
+    assert_eq!(one.merge(&other), SrcSpan::new(1, 9));
+    assert_eq!(other.merge(&one), SrcSpan::new(1, 9));
+}
+
+#[test]
+fn src_span_merge_overlapping_spans() {
+    let one = SrcSpan::new(1, 6);
+    let other = SrcSpan::new(4, 9);
+    assert_eq!(one.merge(&other), SrcSpan::new(1, 9));
+}
+
+#[test]
+fn src_span_merge_contained_span() {
+    let one = SrcSpan::new(1, 9);
+    let other = SrcSpan::new(4, 6);
+    assert_eq!(one.merge(&other), one);
+    assert_eq!(other.merge(&one), one);
+}
+
+#[test]
+fn src_span_merge_empty_span() {
+    let one = SrcSpan::new(4, 6);
+    let empty = SrcSpan::new(8, 8);
+    assert_eq!(one.merge(&empty), SrcSpan::new(4, 8));
+}
+
+#[test]
+fn src_span_union_all() {
+    let spans = [SrcSpan::new(5, 7), SrcSpan::new(1, 2), SrcSpan::new(6, 10)];
+    assert_eq!(SrcSpan::union_all(spans), Some(SrcSpan::new(1, 10)));
+}
+
+#[test]
+fn src_span_union_all_single_span() {
+    assert_eq!(
+        SrcSpan::union_all([SrcSpan::new(3, 4)]),
+        Some(SrcSpan::new(3, 4))
+    );
+}
+
+#[test]
+fn src_span_union_all_no_spans() {
+    assert_eq!(SrcSpan::union_all([]), None);
+}
@@ -238,7 +238,9 @@ impl TypedExpr {
                 .or_else(|| self.self_if_contains_location(byte_index)),
 
             Self::Todo { kind, .. } => match kind {
-                TodoKind::Keyword => self.self_if_contains_location(byte_index),
+                TodoKind::Keyword | TodoKind::PipelineStep => {
+                    self.self_if_contains_location(byte_index)
+                }
                 // We don't want to match on todos that were implicitly inserted
                 // by the compiler as it would result in confusing suggestions
                 // from the LSP.
@@ -661,6 +663,52 @@ impl TypedExpr {
         }
     }
 
+    /// If this is an `Int` expression made up only of literals and arithmetic
+    /// operators, returns the value it evaluates to. For example `1 + 2 * 3`
+    /// would return `Some(7)`.
+    ///
+    pub fn literal_int_value(&self) -> Option<BigInt> {
+        match self {
+            Self::Int { int_value, .. } => Some(int_value.clone()),
+            Self::NegateInt { value, .. } => value.literal_int_value().map(|value| -value),
+            Self::BinOp {
+                name, left, right, ..
+            } => {
+                let left = left.literal_int_value()?;
+                let right = right.literal_int_value()?;
+                match name {
+                    BinOp::AddInt => Some(left + right),
+                    BinOp::SubInt => Some(left - right),
+                    BinOp::MultInt => Some(left * right),
+                    // Dividing by zero in Gleam returns zero.
+                    BinOp::DivInt | BinOp::RemainderInt if right == BigInt::ZERO => {
+                        Some(BigInt::ZERO)
+                    }
+                    BinOp::DivInt => Some(left / right),
+                    BinOp::RemainderInt => Some(left % right),
+                    BinOp::And
+                    | BinOp::Or
+                    | BinOp::Eq
+                    | BinOp::NotEq
+                    | BinOp::LtInt
+                    | BinOp::LtEqInt
+                    | BinOp::LtFloat
+                    | BinOp::LtEqFloat
+                    | BinOp::GtEqInt
+                    | BinOp::GtInt
+                    | BinOp::GtEqFloat
+                    | BinOp::GtFloat
+                    | BinOp::AddFloat
+                    | BinOp::SubFloat
+                    | BinOp::MultFloat
+                    | BinOp::DivFloat
+                    | BinOp::Concatenate => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_literal_string(&self) -> bool {
         match self {
             Self::String { .. } => true,
@@ -708,6 +756,37 @@ impl TypedExpr {
         }
     }
 
+    /// The version given to the `@since` attribute of the function or
+    /// constant this expression refers to, if any.
+    pub(crate) fn get_since(&self) -> Option<&str> {
+        match self {
+            TypedExpr::Var { constructor, .. } => constructor.get_since(),
+            TypedExpr::ModuleSelect { constructor, .. } => constructor.get_since(),
+
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Block { .. }
+            | TypedExpr::Pipeline { .. }
+            | TypedExpr::Fn { .. }
+            | TypedExpr::List { .. }
+            | TypedExpr::Call { .. }
+            | TypedExpr::BinOp { .. }
+            | TypedExpr::Case { .. }
+            | TypedExpr::Tuple { .. }
+            | TypedExpr::TupleIndex { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Echo { .. }
+            | TypedExpr::Panic { .. }
+            | TypedExpr::BitArray { .. }
+            | TypedExpr::RecordUpdate { .. }
+            | TypedExpr::RecordAccess { .. }
+            | TypedExpr::NegateBool { .. }
+            | TypedExpr::NegateInt { .. }
+            | TypedExpr::Invalid { .. } => None,
+        }
+    }
+
     /// Returns `true` if the typed expr is [`Case`].
     ///
     /// [`Case`]: TypedExpr::Case
@@ -817,6 +896,22 @@ impl TypedExpr {
         }
     }
 
+    /// Returns true if `self` references a function annotated as `@pure`.
+    ///
+    pub fn is_pure_function(&self) -> bool {
+        match self {
+            TypedExpr::Var { constructor, .. } => match &constructor.variant {
+                ValueConstructorVariant::ModuleFn { purity, .. } => purity.is_pure(),
+                _ => false,
+            },
+            TypedExpr::ModuleSelect {
+                constructor: ModuleValueConstructor::Fn { purity, .. },
+                ..
+            } => purity.is_pure(),
+            _ => false,
+        }
+    }
+
     /// If `self` is a record constructor, returns the nuber of arguments it
     /// needs to be called. Otherwise, returns `None`.
     ///
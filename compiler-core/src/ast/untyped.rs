@@ -49,12 +49,39 @@ pub enum UntypedExpr {
         tail: Option<Box<Self>>,
     },
 
+    /// A list comprehension, turned into calls to the `map`, `filter` and
+    /// `flat_map` functions of the `gleam/list` module when type checked:
+    ///
+    /// ```gleam
+    /// [x * 2 | x <- items, x > 0]
+    /// ```
+    ///
+    ListComprehension {
+        location: SrcSpan,
+        element: Box<Self>,
+        qualifiers: Vec<ComprehensionQualifier>,
+    },
+
     Call {
         location: SrcSpan,
         fun: Box<Self>,
         arguments: Vec<CallArg<Self>>,
     },
 
+    /// A value with its type parameters given explicitly rather than
+    /// inferred, in the order they first appear in its type:
+    ///
+    /// ```gleam
+    /// list.new<Int>()
+    /// ^^^^^^^^^^^^^
+    /// ```
+    ///
+    TypeApplication {
+        location: SrcSpan,
+        fun: Box<Self>,
+        type_arguments: Vec<TypeAst>,
+    },
+
     BinOp {
         location: SrcSpan,
         name: BinOp,
@@ -64,6 +91,9 @@ pub enum UntypedExpr {
 
     PipeLine {
         expressions: Vec1<Self>,
+        /// The location of each `|>` operator, the first one being the one
+        /// between the first and second expressions of the pipeline.
+        operator_locations: Vec<SrcSpan>,
     },
 
     Case {
@@ -108,6 +138,14 @@ pub enum UntypedExpr {
         message: Option<Box<Self>>,
     },
 
+    /// A discard name used as an expression, like `_` or `_wibble`. It is
+    /// never valid, but reports the type expected where it is used.
+    ///
+    Hole {
+        location: SrcSpan,
+        name: EcoString,
+    },
+
     Echo {
         location: SrcSpan,
         expression: Option<Box<Self>>,
@@ -146,6 +184,44 @@ pub enum UntypedExpr {
     },
 }
 
+/// What comes after the `|` in a list comprehension.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComprehensionQualifier {
+    /// Takes each element of a list in turn, binding it to a name:
+    ///
+    /// ```gleam
+    /// [x * 2 | x <- items]
+    /// //       ^^^^^^^^^^
+    /// ```
+    ///
+    Generator {
+        location: SrcSpan,
+        name: EcoString,
+        name_location: SrcSpan,
+        list: UntypedExpr,
+    },
+
+    /// A condition the elements taken by the generators before it must
+    /// satisfy to be kept:
+    ///
+    /// ```gleam
+    /// [x * 2 | x <- items, x > 0]
+    /// //                   ^^^^^
+    /// ```
+    ///
+    Filter(UntypedExpr),
+}
+
+impl ComprehensionQualifier {
+    pub fn location(&self) -> SrcSpan {
+        match self {
+            ComprehensionQualifier::Generator { location, .. } => *location,
+            ComprehensionQualifier::Filter(condition) => condition.location(),
+        }
+    }
+}
+
 impl UntypedExpr {
     pub fn location(&self) -> SrcSpan {
         match self {
@@ -158,10 +234,13 @@ impl UntypedExpr {
             | Self::Var { location, .. }
             | Self::Int { location, .. }
             | Self::Todo { location, .. }
+            | Self::Hole { location, .. }
             | Self::Echo { location, .. }
             | Self::Case { location, .. }
             | Self::Call { location, .. }
+            | Self::TypeApplication { location, .. }
             | Self::List { location, .. }
+            | Self::ListComprehension { location, .. }
             | Self::Float { location, .. }
             | Self::Block { location, .. }
             | Self::BinOp { location, .. }
@@ -260,16 +339,39 @@ impl HasLocation for UntypedExpr {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FunctionLiteralKind {
-    Capture { hole: SrcSpan },
-    Anonymous { head: SrcSpan },
-    Use { location: SrcSpan },
+    Capture {
+        hole: SrcSpan,
+    },
+    Anonymous {
+        head: SrcSpan,
+    },
+    Use {
+        location: SrcSpan,
+    },
+    /// A block trailing a call, like `list.map(items) { x -> x + 1 }`, that is
+    /// passed as the last argument of the call.
+    ///
+    TrailingBlock {
+        head: SrcSpan,
+    },
 }
 
 impl FunctionLiteralKind {
+    pub fn is_trailing_block(&self) -> bool {
+        match self {
+            FunctionLiteralKind::TrailingBlock { .. } => true,
+            FunctionLiteralKind::Anonymous { .. }
+            | FunctionLiteralKind::Use { .. }
+            | FunctionLiteralKind::Capture { .. } => false,
+        }
+    }
+
     pub fn is_capture(&self) -> bool {
         match self {
             FunctionLiteralKind::Capture { .. } => true,
-            FunctionLiteralKind::Anonymous { .. } | FunctionLiteralKind::Use { .. } => false,
+            FunctionLiteralKind::Anonymous { .. }
+            | FunctionLiteralKind::Use { .. }
+            | FunctionLiteralKind::TrailingBlock { .. } => false,
         }
     }
 }
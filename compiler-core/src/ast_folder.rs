@@ -5,14 +5,15 @@ use vec1::Vec1;
 use crate::{
     analyse::Inferred,
     ast::{
-        AssignName, Assignment, BinOp, CallArg, Constant, Definition, FunctionLiteralKind, Pattern,
-        RecordBeingUpdated, SrcSpan, Statement, TargetedDefinition, TodoKind, TypeAst,
-        TypeAstConstructor, TypeAstFn, TypeAstHole, TypeAstTuple, TypeAstVar, UntypedArg,
-        UntypedAssignment, UntypedClause, UntypedConstant, UntypedConstantBitArraySegment,
-        UntypedCustomType, UntypedDefinition, UntypedExpr, UntypedExprBitArraySegment,
-        UntypedFunction, UntypedImport, UntypedModule, UntypedModuleConstant, UntypedPattern,
-        UntypedPatternBitArraySegment, UntypedRecordUpdateArg, UntypedStatement, UntypedTypeAlias,
-        UntypedUse, UntypedUseAssignment, Use, UseAssignment,
+        Assert, AssignName, Assignment, BinOp, CallArg, ComprehensionQualifier, Constant,
+        Definition, FunctionLiteralKind, Pattern, RecordBeingUpdated, SrcSpan, Statement,
+        TargetedDefinition, TodoKind, TypeAst, TypeAstConstructor, TypeAstFn, TypeAstHole,
+        TypeAstTuple, TypeAstVar, UntypedArg, UntypedAssert, UntypedAssignment, UntypedClause,
+        UntypedConstant, UntypedConstantBitArraySegment, UntypedCustomType, UntypedDefinition,
+        UntypedExpr, UntypedExprBitArraySegment, UntypedFunction, UntypedImport, UntypedModule,
+        UntypedModuleConstant, UntypedPattern, UntypedPatternBitArraySegment,
+        UntypedRecordUpdateArg, UntypedStatement, UntypedTypeAlias, UntypedUse,
+        UntypedUseAssignment, Use, UseAssignment,
     },
     build::Target,
     type_::error::VariableOrigin,
@@ -26,36 +27,60 @@ pub trait UntypedModuleFolder: TypeAstFolder + UntypedExprFolder {
             .definitions
             .into_iter()
             .map(|d| {
-                let TargetedDefinition { definition, target } = d;
+                let TargetedDefinition {
+                    definition,
+                    target,
+                    feature,
+                } = d;
                 match definition {
                     Definition::Function(f) => {
                         let f = self.fold_function_definition(f, target);
                         let definition = self.walk_function_definition(f);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            feature,
+                        }
                     }
 
                     Definition::TypeAlias(a) => {
                         let a = self.fold_type_alias(a, target);
                         let definition = self.walk_type_alias(a);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            feature,
+                        }
                     }
 
                     Definition::CustomType(t) => {
                         let t = self.fold_custom_type(t, target);
                         let definition = self.walk_custom_type(t);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            feature,
+                        }
                     }
 
                     Definition::Import(i) => {
                         let i = self.fold_import(i, target);
                         let definition = self.walk_import(i);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            feature,
+                        }
                     }
 
                     Definition::ModuleConstant(c) => {
                         let c = self.fold_module_constant(c, target);
                         let definition = self.walk_module_constant(c);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            feature,
+                        }
                     }
                 }
             })
@@ -282,12 +307,24 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 tail,
             } => self.fold_list(location, elements, tail),
 
+            UntypedExpr::ListComprehension {
+                location,
+                element,
+                qualifiers,
+            } => self.fold_list_comprehension(location, element, qualifiers),
+
             UntypedExpr::Call {
                 location,
                 fun,
                 arguments,
             } => self.fold_call(location, fun, arguments),
 
+            UntypedExpr::TypeApplication {
+                location,
+                fun,
+                type_arguments,
+            } => self.fold_type_application(location, fun, type_arguments),
+
             UntypedExpr::BinOp {
                 location,
                 name,
@@ -295,7 +332,10 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 right,
             } => self.fold_bin_op(location, name, left, right),
 
-            UntypedExpr::PipeLine { expressions } => self.fold_pipe_line(expressions),
+            UntypedExpr::PipeLine {
+                expressions,
+                operator_locations,
+            } => self.fold_pipe_line(expressions, operator_locations),
 
             UntypedExpr::Case {
                 location,
@@ -344,6 +384,8 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
 
             UntypedExpr::NegateInt { location, value } => self.fold_negate_int(location, value),
 
+            UntypedExpr::Hole { location, name } => self.fold_hole(location, name),
+
             UntypedExpr::Placeholder { location } => self.fold_placeholder(location),
         }
     }
@@ -357,6 +399,7 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
             | UntypedExpr::String { .. }
             | UntypedExpr::NegateInt { .. }
             | UntypedExpr::NegateBool { .. }
+            | UntypedExpr::Hole { .. }
             | UntypedExpr::Placeholder { .. } => expression,
 
             UntypedExpr::Todo {
@@ -431,6 +474,38 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 }
             }
 
+            UntypedExpr::ListComprehension {
+                location,
+                element,
+                qualifiers,
+            } => {
+                let element = Box::new(self.fold_expr(*element));
+                let qualifiers = qualifiers
+                    .into_iter()
+                    .map(|qualifier| match qualifier {
+                        ComprehensionQualifier::Generator {
+                            location,
+                            name,
+                            name_location,
+                            list,
+                        } => ComprehensionQualifier::Generator {
+                            location,
+                            name,
+                            name_location,
+                            list: self.fold_expr(list),
+                        },
+                        ComprehensionQualifier::Filter(condition) => {
+                            ComprehensionQualifier::Filter(self.fold_expr(condition))
+                        }
+                    })
+                    .collect();
+                UntypedExpr::ListComprehension {
+                    location,
+                    element,
+                    qualifiers,
+                }
+            }
+
             UntypedExpr::Call {
                 location,
                 fun,
@@ -451,6 +526,23 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 }
             }
 
+            UntypedExpr::TypeApplication {
+                location,
+                fun,
+                type_arguments,
+            } => {
+                let fun = Box::new(self.fold_expr(*fun));
+                let type_arguments = type_arguments
+                    .into_iter()
+                    .map(|type_| self.fold_type(type_))
+                    .collect();
+                UntypedExpr::TypeApplication {
+                    location,
+                    fun,
+                    type_arguments,
+                }
+            }
+
             UntypedExpr::BinOp {
                 location,
                 name,
@@ -467,9 +559,15 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 }
             }
 
-            UntypedExpr::PipeLine { expressions } => {
+            UntypedExpr::PipeLine {
+                expressions,
+                operator_locations,
+            } => {
                 let expressions = expressions.mapped(|e| self.fold_expr(e));
-                UntypedExpr::PipeLine { expressions }
+                UntypedExpr::PipeLine {
+                    expressions,
+                    operator_locations,
+                }
             }
 
             UntypedExpr::Case {
@@ -606,6 +704,7 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                 Statement::Assignment(self.fold_assignment(assignment))
             }
             Statement::Use(use_) => Statement::Use(self.fold_use(use_)),
+            Statement::Assert(assert) => Statement::Assert(self.fold_assert(assert)),
         }
     }
 
@@ -657,6 +756,11 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
                     assignments,
                 })
             }
+
+            Statement::Assert(Assert { location, value }) => {
+                let value = self.fold_expr(value);
+                Statement::Assert(Assert { location, value })
+            }
         }
     }
 
@@ -734,6 +838,19 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
         }
     }
 
+    fn fold_list_comprehension(
+        &mut self,
+        location: SrcSpan,
+        element: Box<UntypedExpr>,
+        qualifiers: Vec<ComprehensionQualifier>,
+    ) -> UntypedExpr {
+        UntypedExpr::ListComprehension {
+            location,
+            element,
+            qualifiers,
+        }
+    }
+
     fn fold_call(
         &mut self,
         location: SrcSpan,
@@ -747,6 +864,19 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
         }
     }
 
+    fn fold_type_application(
+        &mut self,
+        location: SrcSpan,
+        fun: Box<UntypedExpr>,
+        type_arguments: Vec<TypeAst>,
+    ) -> UntypedExpr {
+        UntypedExpr::TypeApplication {
+            location,
+            fun,
+            type_arguments,
+        }
+    }
+
     fn fold_bin_op(
         &mut self,
         location: SrcSpan,
@@ -762,8 +892,15 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
         }
     }
 
-    fn fold_pipe_line(&mut self, expressions: Vec1<UntypedExpr>) -> UntypedExpr {
-        UntypedExpr::PipeLine { expressions }
+    fn fold_pipe_line(
+        &mut self,
+        expressions: Vec1<UntypedExpr>,
+        operator_locations: Vec<SrcSpan>,
+    ) -> UntypedExpr {
+        UntypedExpr::PipeLine {
+            expressions,
+            operator_locations,
+        }
     }
 
     fn fold_case(
@@ -839,6 +976,10 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
         UntypedExpr::Panic { location, message }
     }
 
+    fn fold_hole(&mut self, location: SrcSpan, name: EcoString) -> UntypedExpr {
+        UntypedExpr::Hole { location, name }
+    }
+
     fn fold_bit_array(
         &mut self,
         location: SrcSpan,
@@ -881,6 +1022,10 @@ pub trait UntypedExprFolder: TypeAstFolder + UntypedConstantFolder + PatternFold
     fn fold_use(&mut self, use_: UntypedUse) -> UntypedUse {
         use_
     }
+
+    fn fold_assert(&mut self, assert: UntypedAssert) -> UntypedAssert {
+        assert
+    }
 }
 
 #[allow(dead_code)]
@@ -1224,6 +1369,13 @@ pub trait PatternFolder {
                 right_side_assignment,
             ),
 
+            Pattern::Constant {
+                location,
+                module,
+                name,
+                type_: (),
+            } => self.fold_pattern_constant(location, module, name),
+
             Pattern::Invalid { location, .. } => self.fold_pattern_invalid(location),
         }
     }
@@ -1363,6 +1515,20 @@ pub trait PatternFolder {
         }
     }
 
+    fn fold_pattern_constant(
+        &mut self,
+        location: SrcSpan,
+        module: (EcoString, SrcSpan),
+        name: EcoString,
+    ) -> UntypedPattern {
+        Pattern::Constant {
+            location,
+            module,
+            name,
+            type_: (),
+        }
+    }
+
     fn fold_pattern_invalid(&mut self, location: SrcSpan) -> UntypedPattern {
         Pattern::Invalid {
             location,
@@ -1380,6 +1546,7 @@ pub trait PatternFolder {
             | Pattern::Discard { .. }
             | Pattern::VarUsage { .. }
             | Pattern::StringPrefix { .. }
+            | Pattern::Constant { .. }
             | Pattern::Invalid { .. } => pattern,
 
             Pattern::Assign {
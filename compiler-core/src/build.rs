@@ -7,6 +7,7 @@ pub mod package_compiler;
 mod package_loader;
 mod project_compiler;
 mod telemetry;
+mod timings;
 
 #[cfg(test)]
 mod tests;
@@ -15,6 +16,7 @@ pub use self::package_compiler::PackageCompiler;
 pub use self::package_loader::StaleTracker;
 pub use self::project_compiler::{Built, Options, ProjectCompiler};
 pub use self::telemetry::{NullTelemetry, Telemetry};
+pub use self::timings::{CompilePhase, ModuleTimings, PhaseTimings, Timings};
 
 use crate::ast::{
     self, CallArg, CustomType, DefinitionLocation, TypeAst, TypedArg, TypedDefinition, TypedExpr,
@@ -138,11 +140,36 @@ impl Default for Runtime {
     }
 }
 
+/// The module system used by the generated JavaScript code.
+///
+#[derive(Debug, Display, EnumString, EnumVariantNames, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JavaScriptModuleFormat {
+    /// ECMAScript modules, using `import` and `export`.
+    #[default]
+    #[strum(serialize = "esm")]
+    EcmaScript,
+    /// CommonJS modules, using `require` and `module.exports`.
+    #[strum(serialize = "commonjs", serialize = "cjs")]
+    CommonJs,
+}
+
+impl JavaScriptModuleFormat {
+    /// The extension of the generated JavaScript files.
+    ///
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::EcmaScript => "mjs",
+            Self::CommonJs => "cjs",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum TargetCodegenConfiguration {
     JavaScript {
         emit_typescript_definitions: bool,
         prelude_location: Utf8PathBuf,
+        module_format: JavaScriptModuleFormat,
     },
     Erlang {
         app_file: Option<ErlangAppCodegenConfiguration>,
@@ -184,6 +211,11 @@ pub enum Mode {
     Dev,
     Prod,
     Lsp,
+    /// A production build of the whole program, made with
+    /// `gleam build --whole-program`. It has its own build directory, as its
+    /// generated code is missing the definitions that can't be reached.
+    #[strum(serialize = "whole-program")]
+    WholeProgram,
 }
 
 impl Mode {
@@ -192,14 +224,14 @@ impl Mode {
     pub fn includes_tests(&self) -> bool {
         match self {
             Self::Dev | Self::Lsp => true,
-            Self::Prod => false,
+            Self::Prod | Self::WholeProgram => false,
         }
     }
 
     pub fn includes_dev_dependencies(&self) -> bool {
         match self {
             Mode::Dev | Mode::Lsp => true,
-            Mode::Prod => false,
+            Mode::Prod | Mode::WholeProgram => false,
         }
     }
 }
@@ -209,6 +241,7 @@ fn mode_includes_tests() {
     assert!(Mode::Dev.includes_tests());
     assert!(Mode::Lsp.includes_tests());
     assert!(!Mode::Prod.includes_tests());
+    assert!(!Mode::WholeProgram.includes_tests());
 }
 
 #[derive(Debug)]
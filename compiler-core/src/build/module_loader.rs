@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests;
 
-use std::{collections::HashSet, time::SystemTime};
+use std::{
+    collections::HashSet,
+    time::{Instant, SystemTime},
+};
 
 use camino::{Utf8Path, Utf8PathBuf};
 
@@ -34,6 +37,8 @@ pub(crate) struct ModuleLoader<'a, IO> {
     /// The set of modules that have had partial compilation done since the last
     /// successful compilation.
     pub incomplete_modules: &'a HashSet<EcoString>,
+    /// The feature flags enabled for the package being loaded.
+    pub features: &'a [EcoString],
 }
 
 impl<'a, IO> ModuleLoader<'a, IO>
@@ -68,6 +73,14 @@ where
             return read_source(name).map(Input::New);
         }
 
+        // Definitions can be disabled by feature flags, so if the enabled
+        // features have changed since the cache was written then we need to
+        // recompile.
+        if meta.features != self.features {
+            tracing::debug!(?name, "features_changed_cache_stale");
+            return read_source(name).map(Input::New);
+        }
+
         // If the timestamp of the source is newer than the cache entry and
         // the hash of the source differs from the one in the cache entry,
         // then we need to recompile.
@@ -155,6 +168,7 @@ where
 {
     let code: EcoString = io.read(&path)?.into();
 
+    let start = Instant::now();
     let parsed = crate::parse::parse_module(path.clone(), &code, &emitter).map_err(|error| {
         Error::Parse {
             path: path.clone(),
@@ -162,6 +176,7 @@ where
             error,
         }
     })?;
+    let parse_duration = start.elapsed();
     let mut ast = parsed.module;
     let extra = parsed.extra;
     let dependencies = ast.dependencies(target);
@@ -177,6 +192,7 @@ where
         name,
         code,
         ast,
+        parse_duration,
     };
     Ok(module)
 }
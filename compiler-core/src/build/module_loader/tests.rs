@@ -200,6 +200,74 @@ fn cache_present_without_codegen_when_not_required() {
     assert!(result.is_cached());
 }
 
+#[test]
+fn cache_present_and_fresh_but_features_changed() {
+    let name = "package".into();
+    let src = Utf8Path::new("/src");
+    let artefact = Utf8Path::new("/artefact");
+    let fs = InMemoryFileSystem::new();
+    let warnings = WarningEmitter::null();
+    let incomplete_modules = HashSet::new();
+    let loader = make_loader(&warnings, &name, &fs, src, artefact, &incomplete_modules);
+
+    // The mtime of the source is older than that of the cache, but the cache
+    // was compiled with a feature that is no longer enabled
+    write_src(&fs, TEST_SOURCE_1, "/src/main.gleam", 0);
+    write_cache_with_features(
+        &fs,
+        TEST_SOURCE_1,
+        "/artefact/main.cache_meta",
+        1,
+        false,
+        vec!["experimental".into()],
+    );
+
+    let result = loader
+        .load(Utf8Path::new("/src/main.gleam").to_path_buf())
+        .unwrap();
+
+    assert!(result.is_new());
+}
+
+#[test]
+fn source_fingerprint_only_depends_on_the_source() {
+    // The fingerprint is used to decide whether a cache can be reused, so it
+    // must be the same for the same source on any machine and in any build of
+    // the compiler.
+    assert_eq!(
+        SourceFingerprint::new(TEST_SOURCE_1),
+        SourceFingerprint(14647280867362243545)
+    );
+}
+
+#[test]
+fn cache_present_and_source_is_the_same_copied_to_another_directory() {
+    let name = "package".into();
+    let src = Utf8Path::new("/elsewhere/src");
+    let artefact = Utf8Path::new("/elsewhere/artefact");
+    let fs = InMemoryFileSystem::new();
+    let warnings = WarningEmitter::null();
+    let incomplete_modules = HashSet::new();
+    let loader = make_loader(&warnings, &name, &fs, src, artefact, &incomplete_modules);
+
+    // A project and its build directory copied somewhere else get new mtimes,
+    // but the cache is still used as the source didn't change.
+    write_src(&fs, TEST_SOURCE_1, "/elsewhere/src/main.gleam", 20);
+    write_cache(
+        &fs,
+        TEST_SOURCE_1,
+        "/elsewhere/artefact/main.cache_meta",
+        1,
+        false,
+    );
+
+    let result = loader
+        .load(Utf8Path::new("/elsewhere/src/main.gleam").to_path_buf())
+        .unwrap();
+
+    assert!(result.is_cached());
+}
+
 const TEST_SOURCE_1: &'static str = "const x = 1";
 const TEST_SOURCE_2: &'static str = "const x = 2";
 
@@ -209,6 +277,17 @@ fn write_cache(
     path: &str,
     seconds: u64,
     codegen_performed: bool,
+) {
+    write_cache_with_features(fs, source, path, seconds, codegen_performed, vec![])
+}
+
+fn write_cache_with_features(
+    fs: &InMemoryFileSystem,
+    source: &str,
+    path: &str,
+    seconds: u64,
+    codegen_performed: bool,
+    features: Vec<EcoString>,
 ) {
     let line_numbers = LineNumbers::new(source);
     let cache_metadata = CacheMetadata {
@@ -217,6 +296,7 @@ fn write_cache(
         dependencies: vec![],
         fingerprint: SourceFingerprint::new(source),
         line_numbers,
+        features,
     };
     let path = Utf8Path::new(path);
     fs.write_bytes(&path, &cache_metadata.to_binary()).unwrap();
@@ -247,5 +327,6 @@ fn make_loader<'a>(
         artefact_directory: &artefact,
         origin: Origin::Src,
         incomplete_modules,
+        features: &[],
     }
 }
@@ -5,7 +5,8 @@ use crate::{
     Error, Result, Warning,
     ast::{SrcSpan, TypedModule, UntypedModule},
     build::{
-        Mode, Module, Origin, Outcome, Package, SourceFingerprint, Target,
+        CompilePhase, JavaScriptModuleFormat, Mode, Module, Origin, Outcome, Package,
+        SourceFingerprint, Target, Timings,
         elixir_libraries::ElixirLibraries,
         native_file_copier::NativeFileCopier,
         package_loader::{CodegenRequired, PackageLoader, StaleTracker},
@@ -14,7 +15,9 @@ use crate::{
     config::PackageConfig,
     dep_tree, error,
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
+    lint::LintRules,
     metadata::ModuleEncoder,
+    optimise,
     parse::extra::ModuleExtra,
     paths, type_,
     uid::UniqueIdGenerator,
@@ -23,7 +26,11 @@ use crate::{
 use askama::Template;
 use ecow::EcoString;
 use std::collections::HashSet;
-use std::{collections::HashMap, fmt::write, time::SystemTime};
+use std::{
+    collections::HashMap,
+    fmt::write,
+    time::{Duration, Instant, SystemTime},
+};
 use vec1::Vec1;
 
 use camino::{Utf8Path, Utf8PathBuf};
@@ -35,6 +42,9 @@ pub struct Compiled {
     pub modules: Vec<Module>,
     /// The names of all cached modules, which are not present in the `modules` field.
     pub cached_module_names: Vec<EcoString>,
+    /// How long each phase of compilation took for the modules that were
+    /// just compiled.
+    pub timings: Timings,
 }
 
 #[derive(Debug)]
@@ -62,6 +72,19 @@ pub struct PackageCompiler<'a, IO> {
     pub subprocess_stdio: Stdio,
     pub target_support: TargetSupport,
     pub cached_warnings: CachedWarnings,
+    /// If set to true the functions and constants that can't be reached from
+    /// any of the package's entry points are removed before code generation.
+    ///
+    /// When the `remove_unused_public_definitions` optimisation is enabled
+    /// this can only be done when all the modules of the package are being
+    /// compiled, as the ones read from the cache could be referencing any of
+    /// the removed public definitions.
+    ///
+    pub whole_program: bool,
+    /// The custom lint rules run over each of the modules that are compiled.
+    /// The modules read from the cache are not checked again.
+    ///
+    pub lint_rules: LintRules,
 }
 
 impl<'a, IO> PackageCompiler<'a, IO>
@@ -96,6 +119,8 @@ where
             subprocess_stdio: Stdio::Inherit,
             target_support: TargetSupport::NotEnforced,
             cached_warnings: CachedWarnings::Ignore,
+            whole_program: false,
+            lint_rules: LintRules::new(),
         }
     }
 
@@ -141,6 +166,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            &self.config.features,
         );
 
         let loaded = if self.compile_modules {
@@ -181,6 +207,7 @@ where
 
         // Type check the modules that are new or have changed
         tracing::info!(count=%loaded.to_compile.len(), "analysing_modules");
+        let mut timings = Timings::new();
         let outcome = analyse(
             &self.config,
             self.target.target(),
@@ -191,15 +218,17 @@ where
             warnings,
             self.target_support,
             incomplete_modules,
+            &mut timings,
         );
 
-        let modules = match outcome {
+        let mut modules = match outcome {
             Outcome::Ok(modules) => modules,
             Outcome::PartialFailure(modules, error) => {
                 return Outcome::PartialFailure(
                     Compiled {
                         modules,
                         cached_module_names,
+                        timings,
                     },
                     error,
                 );
@@ -207,9 +236,30 @@ where
             Outcome::TotalFailure(error) => return Outcome::TotalFailure(error),
         };
 
+        for module in &modules {
+            self.lint_rules.check(module, warnings);
+        }
+
+        if self.perform_codegen && self.config.optimisations.any_enabled() {
+            tracing::debug!("performing_optimisations");
+            for module in modules.iter_mut() {
+                optimise::optimise(&mut module.ast, self.config.optimisations);
+            }
+        }
+
+        if self.perform_codegen && self.whole_program {
+            tracing::debug!("removing_unreachable_definitions");
+            let mut asts: Vec<_> = modules.iter_mut().map(|module| &mut module.ast).collect();
+            optimise::remove_unreachable_definitions(
+                &mut asts,
+                &self.config.name,
+                self.config.optimisations.remove_unused_public_definitions,
+            );
+        }
+
         tracing::debug!("performing_code_generation");
 
-        if let Err(error) = self.perform_codegen(&modules) {
+        if let Err(error) = self.perform_codegen(&modules, &mut timings) {
             return error.into();
         }
 
@@ -220,6 +270,7 @@ where
         Outcome::Ok(Compiled {
             modules,
             cached_module_names,
+            timings,
         })
     }
 
@@ -302,6 +353,7 @@ where
                 dependencies: module.dependencies.clone(),
                 fingerprint: SourceFingerprint::new(&module.code),
                 line_numbers: module.ast.type_info.line_numbers.clone(),
+                features: self.config.features.clone(),
             };
             self.io.write_bytes(&path, &info.to_binary())?;
 
@@ -320,7 +372,7 @@ where
         Ok(())
     }
 
-    fn perform_codegen(&mut self, modules: &[Module]) -> Result<()> {
+    fn perform_codegen(&mut self, modules: &[Module], timings: &mut Timings) -> Result<()> {
         if !self.perform_codegen {
             tracing::debug!("skipping_codegen");
             return Ok(());
@@ -330,13 +382,16 @@ where
             TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions,
                 prelude_location,
+                module_format,
             } => self.perform_javascript_codegen(
                 modules,
                 *emit_typescript_definitions,
                 prelude_location,
+                *module_format,
+                timings,
             ),
             TargetCodegenConfiguration::Erlang { app_file } => {
-                self.perform_erlang_codegen(modules, app_file.as_ref())
+                self.perform_erlang_codegen(modules, app_file.as_ref(), timings)
             }
         }
     }
@@ -345,6 +400,7 @@ where
         &mut self,
         modules: &[Module],
         app_file_config: Option<&ErlangAppCodegenConfiguration>,
+        timings: &mut Timings,
     ) -> Result<(), Error> {
         let mut written = HashSet::new();
         let build_dir = self.out.join(paths::ARTEFACT_DIRECTORY_NAME);
@@ -369,7 +425,13 @@ where
         // we overwrite any precompiled Erlang that was included in the Hex
         // package. Otherwise we will build the potentially outdated precompiled
         // version and not the newly compiled version.
-        Erlang::new(&build_dir, &include_dir).render(io.clone(), modules, self.root)?;
+        Erlang::new(
+            &build_dir,
+            &include_dir,
+            self.config.erlang.minimum_otp_version,
+            self.config.panic_handler.as_ref(),
+        )
+        .render(io.clone(), modules, self.root, timings)?;
 
         let native_modules: Vec<EcoString> = if self.compile_beam_bytecode {
             written.extend(modules.iter().map(Module::compiled_erlang_path));
@@ -395,6 +457,8 @@ where
         modules: &[Module],
         typescript: bool,
         prelude_location: &Utf8Path,
+        module_format: JavaScriptModuleFormat,
+        timings: &mut Timings,
     ) -> Result<(), Error> {
         let mut written = HashSet::new();
         let typescript = if typescript {
@@ -406,11 +470,13 @@ where
         JavaScript::new(
             &self.out,
             typescript,
+            module_format,
             prelude_location,
             &self.root,
             self.target_support,
+            self.config.panic_handler.as_ref(),
         )
-        .render(&self.io, modules, self.stdlib_package())?;
+        .render(&self.io, modules, self.stdlib_package(), timings)?;
 
         if self.copy_native_files {
             self.copy_project_native_files(&self.out, &mut written)?;
@@ -490,6 +556,7 @@ fn analyse(
     warnings: &WarningEmitter,
     target_support: TargetSupport,
     incomplete_modules: &mut HashSet<EcoString>,
+    timings: &mut Timings,
 ) -> Outcome<Vec<Module>, Error> {
     let mut modules = Vec::with_capacity(parsed_modules.len() + 1);
     let direct_dependencies = package_config.dependencies_for(mode).expect("Package deps");
@@ -511,10 +578,13 @@ fn analyse(
         package,
         dependencies,
         extra,
+        parse_duration,
     } in parsed_modules
     {
         tracing::debug!(module = ?name, "Type checking");
 
+        timings.record(&package, &name, CompilePhase::Parse, parse_duration);
+        let start = Instant::now();
         let line_numbers = LineNumbers::new(&code);
 
         let analysis = crate::analyse::ModuleAnalyzerConstructor {
@@ -528,6 +598,7 @@ fn analyse(
             package_config,
         }
         .infer_module(ast, line_numbers, path.clone());
+        timings.record(&package, &name, CompilePhase::Infer, start.elapsed());
 
         match analysis {
             Outcome::Ok(ast) => {
@@ -593,7 +664,7 @@ fn analyse(
     Outcome::Ok(modules)
 }
 
-pub(crate) fn module_name(package_path: &Utf8Path, full_module_path: &Utf8Path) -> EcoString {
+pub fn module_name(package_path: &Utf8Path, full_module_path: &Utf8Path) -> EcoString {
     // /path/to/project/_build/default/lib/the_package/src/my/module.gleam
 
     // my/module.gleam
@@ -673,6 +744,7 @@ pub(crate) struct CacheMetadata {
     pub dependencies: Vec<(EcoString, SrcSpan)>,
     pub fingerprint: SourceFingerprint,
     pub line_numbers: LineNumbers,
+    pub features: Vec<EcoString>,
 }
 
 impl CacheMetadata {
@@ -711,6 +783,8 @@ pub(crate) struct UncompiledModule {
     pub dependencies: Vec<(EcoString, SrcSpan)>,
     pub ast: UntypedModule,
     pub extra: ModuleExtra,
+    /// How long it took to parse the module's source code.
+    pub parse_duration: Duration,
 }
 
 #[derive(Template)]
@@ -22,7 +22,8 @@ use crate::{
     dep_tree,
     error::{FileIoAction, FileKind, ImportCycleLocationDetails},
     io::{
-        CommandExecutor, FileSystemReader, FileSystemWriter, gleam_cache_files, gleam_source_files,
+        CommandExecutor, FileSystemReader, FileSystemWriter, gleam_cache_files,
+        gleam_interface_files, gleam_source_files,
     },
     metadata, type_,
     uid::UniqueIdGenerator,
@@ -68,6 +69,7 @@ pub struct PackageLoader<'a, IO> {
     already_defined_modules: &'a mut im::HashMap<EcoString, Utf8PathBuf>,
     incomplete_modules: &'a HashSet<EcoString>,
     cached_warnings: CachedWarnings,
+    features: &'a [EcoString],
 }
 
 impl<'a, IO> PackageLoader<'a, IO>
@@ -88,6 +90,7 @@ where
         stale_modules: &'a mut StaleTracker,
         already_defined_modules: &'a mut im::HashMap<EcoString, Utf8PathBuf>,
         incomplete_modules: &'a HashSet<EcoString>,
+        features: &'a [EcoString],
     ) -> Self {
         Self {
             io,
@@ -103,6 +106,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            features,
         }
     }
 
@@ -111,6 +115,7 @@ where
         // will check the mtimes and hashes of sources and caches to determine
         // which should be loaded.
         let mut inputs = self.read_sources_and_caches()?;
+        let interfaces = self.read_interface_files(&inputs)?;
 
         // Check for any removed modules, by looking at cache files that don't exist in inputs
         for cache_file in gleam_cache_files(&self.io, &self.artefact_directory) {
@@ -142,6 +147,7 @@ where
         // the caches need to be invalidated because their dependencies have
         // changed.
         let mut loaded = Loaded::default();
+        loaded.cached.extend(interfaces);
         for name in sequence {
             let input = inputs
                 .remove(&name)
@@ -178,6 +184,37 @@ where
         Ok(loaded)
     }
 
+    /// Reads the names of the modules imported by each of the package's
+    /// modules, the same way they are read to determine the order in which
+    /// the modules are compiled. Modules with an up to date cache are not
+    /// parsed again.
+    ///
+    /// An import cycle is reported the same way it is when compiling the
+    /// package, unless `allow_cycles` is true.
+    ///
+    pub(crate) fn module_imports(
+        self,
+        allow_cycles: bool,
+    ) -> Result<Vec<(EcoString, Vec<EcoString>)>> {
+        let inputs = self.read_sources_and_caches()?;
+        let imports = inputs
+            .values()
+            .map(|input| (input.name().clone(), input.dependencies()))
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .collect_vec();
+
+        if !allow_cycles {
+            let dep_location_map = inputs
+                .iter()
+                .map(|(name, input)| (name.clone(), input))
+                .collect();
+            let _ = dep_tree::toposort_deps(imports.clone())
+                .map_err(|e| self.convert_deps_tree_error(e, dep_location_map))?;
+        }
+
+        Ok(imports)
+    }
+
     fn load_cached_module(&self, info: CachedModule) -> Result<type_::ModuleInterface, Error> {
         let dir = self.artefact_directory;
         let name = info.name.replace("/", "@");
@@ -202,6 +239,45 @@ where
         Ok(module)
     }
 
+    /// Reads the module interface files in the package's `interfaces`
+    /// directory, as written by `gleam export module-interfaces`. They let
+    /// the package's modules be type checked against without their source,
+    /// so a module that also has a source file is compiled from it instead.
+    ///
+    fn read_interface_files(
+        &self,
+        inputs: &HashMap<EcoString, Input>,
+    ) -> Result<Vec<type_::ModuleInterface>> {
+        let directory = self.root.join("interfaces");
+        let mut interfaces = Vec::new();
+        for path in gleam_interface_files(&self.io, &directory).sorted() {
+            let name = module_name(&directory, &path);
+            if inputs.contains_key(&name) {
+                tracing::debug!(module = %name, "interface_file_shadowed_by_source");
+                continue;
+            }
+            if let Some(first) = self.already_defined_modules.get(&name) {
+                return Err(Error::DuplicateModule {
+                    module: name,
+                    first: first.to_path_buf(),
+                    second: path,
+                });
+            }
+
+            tracing::debug!(module = %name, "module_to_load_from_interface_file");
+            let bytes = self.io.read_bytes(&path)?;
+            let module = metadata::decode_interface_file(&path, &bytes, self.ids.clone())?;
+            if module.name != name {
+                return Err(Error::InvalidModuleInterfaceFile {
+                    path,
+                    error: format!("It is the interface of the `{}` module", module.name),
+                });
+            }
+            interfaces.push(module);
+        }
+        Ok(interfaces)
+    }
+
     pub fn is_gleam_path(&self, path: &Utf8Path, dir: &Utf8Path) -> bool {
         use regex::Regex;
         use std::cell::OnceCell;
@@ -240,6 +316,7 @@ where
             source_directory: &src,
             origin: Origin::Src,
             incomplete_modules: self.incomplete_modules,
+            features: self.features,
         };
 
         // Src
@@ -44,11 +44,22 @@ fn write_cache(
         dependencies: deps,
         fingerprint: SourceFingerprint::new(src),
         line_numbers: line_numbers.clone(),
+        features: vec![],
     };
     let path = Utf8Path::new("/artefact").join(format!("{name}.cache_meta"));
     fs.write_bytes(&path, &cache_metadata.to_binary()).unwrap();
 
-    let cache = crate::type_::ModuleInterface {
+    let cache = module_interface(name, line_numbers);
+    let path = Utf8Path::new("/artefact").join(format!("{name}.cache"));
+    fs.write_bytes(
+        &path,
+        &metadata::ModuleEncoder::new(&cache).encode().unwrap(),
+    )
+    .unwrap();
+}
+
+fn module_interface(name: &str, line_numbers: line_numbers::LineNumbers) -> type_::ModuleInterface {
+    type_::ModuleInterface {
         name: name.into(),
         origin: Origin::Src,
         package: "my_package".into(),
@@ -56,7 +67,7 @@ fn write_cache(
         types_value_constructors: Default::default(),
         values: Default::default(),
         accessors: Default::default(),
-        line_numbers: line_numbers.clone(),
+        line_numbers,
         is_internal: false,
         src_path: Utf8PathBuf::from(format!("/src/{}.gleam", name)),
         warnings: vec![],
@@ -64,17 +75,29 @@ fn write_cache(
         type_aliases: Default::default(),
         documentation: Default::default(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: Default::default(),
-    };
-    let path = Utf8Path::new("/artefact").join(format!("{name}.cache"));
-    fs.write_bytes(
-        &path,
-        &metadata::ModuleEncoder::new(&cache).encode().unwrap(),
-    )
-    .unwrap();
+    }
+}
+
+fn write_interface(fs: &InMemoryFileSystem, name: &str, src: &str) {
+    let interface = module_interface(name, line_numbers::LineNumbers::new(src));
+    let path = Utf8Path::new("/interfaces").join(format!("{name}.gleam_interface"));
+    fs.write_bytes(&path, &metadata::encode_interface_file(&interface).unwrap())
+        .unwrap();
 }
 
 fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> LoaderTestOutput {
+    try_run_loader(fs, root, artefact).unwrap()
+}
+
+fn try_run_loader(
+    fs: InMemoryFileSystem,
+    root: &Utf8Path,
+    artefact: &Utf8Path,
+) -> Result<LoaderTestOutput> {
     let mut defined = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     let (emitter, warnings) = WarningEmitter::vector();
@@ -93,14 +116,15 @@ fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> L
         already_defined_modules: &mut defined,
         incomplete_modules: &mut HashSet::new(),
         cached_warnings: CachedWarnings::Ignore,
+        features: &[],
     };
-    let loaded = loader.run().unwrap();
+    let loaded = loader.run()?;
 
-    LoaderTestOutput {
+    Ok(LoaderTestOutput {
         to_compile: loaded.to_compile.into_iter().map(|m| m.name).collect(),
         cached: loaded.cached.into_iter().map(|m| m.name).collect(),
         warnings: warnings.take(),
-    }
+    })
 }
 
 #[test]
@@ -361,3 +385,134 @@ fn invalid_nested_module_name_in_test() {
         }],
     );
 }
+
+#[test]
+fn reading_interface_file() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    // The interface has no source, so it is used as it is
+    write_interface(&fs, "one", TEST_SOURCE_1);
+    write_src(&fs, "/src/two.gleam", 0, "import one");
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(loaded.to_compile, vec![EcoString::from("two")]);
+    assert_eq!(loaded.cached, vec![EcoString::from("one")]);
+    assert!(loaded.warnings.is_empty());
+}
+
+#[test]
+fn source_takes_precedence_over_interface_file() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_interface(&fs, "one", TEST_SOURCE_1);
+    write_src(&fs, "/src/one.gleam", 0, TEST_SOURCE_2);
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(loaded.to_compile, vec![EcoString::from("one")]);
+    assert!(loaded.cached.is_empty());
+}
+
+#[test]
+fn interface_file_for_another_module() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    // The file is named after `two` but holds the interface of `one`
+    let interface = module_interface("one", line_numbers::LineNumbers::new(TEST_SOURCE_1));
+    let path = Utf8Path::new("/interfaces/two.gleam_interface");
+    fs.write_bytes(&path, &metadata::encode_interface_file(&interface).unwrap())
+        .unwrap();
+
+    let error = try_run_loader(fs, root, artefact).unwrap_err();
+    assert_eq!(
+        error,
+        Error::InvalidModuleInterfaceFile {
+            path: Utf8PathBuf::from("/interfaces/two.gleam_interface"),
+            error: "It is the interface of the `one` module".into(),
+        }
+    );
+}
+
+fn module_imports(
+    fs: InMemoryFileSystem,
+    allow_cycles: bool,
+) -> Result<Vec<(EcoString, Vec<EcoString>)>> {
+    let mut defined = im::HashMap::new();
+    let emitter = WarningEmitter::null();
+    let loader = PackageLoader {
+        io: fs.clone(),
+        ids: UniqueIdGenerator::new(),
+        mode: Mode::Dev,
+        root: Utf8Path::new("/"),
+        warnings: &emitter,
+        codegen: CodegenRequired::No,
+        artefact_directory: Utf8Path::new("/artefact"),
+        package_name: &"my_package".into(),
+        target: Target::JavaScript,
+        stale_modules: &mut StaleTracker::default(),
+        already_defined_modules: &mut defined,
+        incomplete_modules: &mut HashSet::new(),
+        cached_warnings: CachedWarnings::Ignore,
+        features: &[],
+    };
+    loader.module_imports(allow_cycles)
+}
+
+#[test]
+fn module_imports_of_new_modules() {
+    let fs = InMemoryFileSystem::new();
+    write_src(&fs, "/src/one.gleam", 0, "import two import gleam/list");
+    write_src(&fs, "/src/two.gleam", 0, "");
+
+    assert_eq!(
+        module_imports(fs, false).unwrap(),
+        vec![
+            ("one".into(), vec!["two".into(), "gleam/list".into()]),
+            ("two".into(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn module_imports_are_read_from_cache() {
+    let fs = InMemoryFileSystem::new();
+
+    // The module is not parsed again, so the imports are the ones recorded in
+    // its cache.
+    write_src(&fs, "/src/one.gleam", 0, TEST_SOURCE_1);
+    write_cache(
+        &fs,
+        "one",
+        0,
+        vec![("wibble".into(), SrcSpan::new(0, 0))],
+        TEST_SOURCE_1,
+    );
+
+    assert_eq!(
+        module_imports(fs, false).unwrap(),
+        vec![("one".into(), vec!["wibble".into()])]
+    );
+}
+
+#[test]
+fn module_imports_with_cycle() {
+    let fs = InMemoryFileSystem::new();
+    write_src(&fs, "/src/one.gleam", 0, "import two");
+    write_src(&fs, "/src/two.gleam", 0, "import one");
+
+    let error = module_imports(fs.clone(), false).unwrap_err();
+    assert!(matches!(error, Error::ImportCycle { .. }));
+
+    assert_eq!(
+        module_imports(fs, true).unwrap(),
+        vec![
+            ("one".into(), vec!["two".into()]),
+            ("two".into(), vec!["one".into()]),
+        ]
+    );
+}
@@ -2,9 +2,9 @@ use crate::{
     Error, Result, Warning,
     analyse::TargetSupport,
     build::{
-        Mode, Module, Origin, Package, Target,
+        JavaScriptModuleFormat, Mode, Module, Origin, Package, Target,
         package_compiler::{self, PackageCompiler},
-        package_loader::StaleTracker,
+        package_loader::{CodegenRequired, PackageLoader, StaleTracker},
         project_compiler,
         telemetry::Telemetry,
     },
@@ -13,9 +13,12 @@ use crate::{
     dep_tree,
     error::{FileIoAction, FileKind, ShellCommandFailureReason},
     io::{BeamCompiler, Command, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
+    lint::LintRules,
     manifest::{ManifestPackage, ManifestPackageSource},
     metadata,
+    module_graph::ModuleImports,
     paths::{self, ProjectPaths},
+    reference::{self, AlwaysIgnoredReturn, UnusedPublicDefinition},
     type_::{self, ModuleFunction},
     uid::UniqueIdGenerator,
     version::COMPILER_VERSION,
@@ -36,7 +39,7 @@ use std::{
 };
 
 use super::{
-    Codegen, Compile, ErlangAppCodegenConfiguration, Outcome,
+    Codegen, Compile, ErlangAppCodegenConfiguration, Outcome, Timings,
     elixir_libraries::ElixirLibraries,
     package_compiler::{CachedWarnings, Compiled},
 };
@@ -64,6 +67,10 @@ pub struct Options {
     pub warnings_as_errors: bool,
     pub root_target_support: TargetSupport,
     pub no_print_progress: bool,
+    pub javascript_module_format: JavaScriptModuleFormat,
+    /// Whether to remove the definitions of the root package that can't be
+    /// reached from any of its entry points before generating code.
+    pub whole_program: bool,
 }
 
 #[derive(Debug)]
@@ -71,6 +78,9 @@ pub struct Built {
     pub root_package: Package,
     pub module_interfaces: im::HashMap<EcoString, type_::ModuleInterface>,
     compiled_dependency_modules: Vec<Module>,
+    /// How long each phase of compilation took for the modules of all the
+    /// packages that were compiled.
+    pub timings: Timings,
 }
 
 impl Built {
@@ -88,6 +98,47 @@ impl Built {
         }
     }
 
+    /// The public definitions of the root package that are never used by
+    /// any of its other modules.
+    pub fn unused_public_definitions(&self) -> Vec<UnusedPublicDefinition> {
+        reference::find_unused_public_definitions(&self.root_module_interfaces())
+    }
+
+    /// The functions of the root package whose return value is discarded by
+    /// all of their calls in the root package.
+    pub fn always_ignored_returns(&self) -> Vec<AlwaysIgnoredReturn> {
+        reference::find_always_ignored_returns(&self.root_module_interfaces())
+    }
+
+    fn root_module_interfaces(&self) -> Vec<&type_::ModuleInterface> {
+        self.root_package
+            .modules
+            .iter()
+            .map(|module| &module.name)
+            .chain(&self.root_package.cached_module_names)
+            .filter_map(|module| self.module_interfaces.get(module))
+            .collect_vec()
+    }
+
+    /// The module and name of all the functions of the root package that are
+    /// annotated with `@benchmark`, sorted by module name.
+    pub fn benchmarks(&self) -> Vec<(EcoString, EcoString)> {
+        self.root_package
+            .modules
+            .iter()
+            .map(|module| &module.name)
+            .chain(&self.root_package.cached_module_names)
+            .sorted()
+            .filter_map(|module| self.module_interfaces.get(module))
+            .flat_map(|interface| {
+                interface
+                    .benchmarks
+                    .iter()
+                    .map(|function| (interface.name.clone(), function.clone()))
+            })
+            .collect()
+    }
+
     pub fn minimum_required_version(&self) -> Version {
         self.module_interfaces
             .values()
@@ -114,10 +165,13 @@ pub struct ProjectCompiler<IO> {
     options: Options,
     paths: ProjectPaths,
     ids: UniqueIdGenerator,
+    timings: Timings,
     pub(crate) io: IO,
     /// We may want to silence subprocess stdout if we are running in LSP mode.
     /// The language server talks over stdio so printing would break that.
     pub subprocess_stdio: Stdio,
+    /// The custom lint rules run over the modules of the root package.
+    pub lint_rules: LintRules,
 }
 
 // TODO: test that tests cannot be imported into src
@@ -147,8 +201,10 @@ where
             stale_modules: StaleTracker::default(),
             incomplete_modules: HashSet::new(),
             ids: UniqueIdGenerator::new(),
+            timings: Timings::new(),
             warnings: WarningEmitter::new(warning_emitter),
             subprocess_stdio: Stdio::Inherit,
+            lint_rules: LintRules::new(),
             telemetry,
             packages,
             options,
@@ -178,6 +234,11 @@ where
         // from a previous build. A ProjectCompiler instance is re-used by the
         // LSP engine so state could be reused if we don't reset it.
         self.stale_modules.empty();
+        self.timings = Timings::new();
+
+        // Output generated with a different JavaScript module format can't be
+        // reused.
+        self.check_javascript_module_format()?;
 
         // Each package may specify a Gleam version that it supports, so we
         // verify that this version is appropriate.
@@ -206,6 +267,7 @@ where
             root_package,
             module_interfaces: self.importable_modules,
             compiled_dependency_modules,
+            timings: self.timings,
         })
     }
 
@@ -216,10 +278,14 @@ where
                 |Compiled {
                      modules,
                      cached_module_names,
-                 }| Package {
-                    config,
-                    modules,
-                    cached_module_names,
+                     timings,
+                 }| {
+                    self.timings.extend(timings);
+                    Package {
+                        config,
+                        modules,
+                        cached_module_names,
+                    }
                 },
             )
     }
@@ -256,6 +322,31 @@ where
             })
     }
 
+    /// Checks that the JavaScript found in the build directory was generated
+    /// using the requested module format. If not, we clear the build directory
+    /// so that all the modules are generated again instead of leaving behind
+    /// a mix of the two formats.
+    fn check_javascript_module_format(&self) -> Result<(), Error> {
+        if !self.target().is_javascript() {
+            return Ok(());
+        }
+
+        let other_format = match self.options.javascript_module_format {
+            JavaScriptModuleFormat::EcmaScript => JavaScriptModuleFormat::CommonJs,
+            JavaScriptModuleFormat::CommonJs => JavaScriptModuleFormat::EcmaScript,
+        };
+        let build_path = self
+            .paths
+            .build_directory_for_target(self.mode(), self.target());
+        let other_prelude = build_path.join(format!("prelude.{}", other_format.extension()));
+        if !self.io.is_file(&other_prelude) {
+            return Ok(());
+        }
+
+        tracing::info!("removing_build_state_from_different_javascript_module_format");
+        self.io.delete_directory(&build_path)
+    }
+
     pub fn compile_dependencies(&mut self) -> Result<Vec<Module>, Error> {
         let sequence = order_packages(&self.packages)?;
         let mut modules = vec![];
@@ -268,6 +359,73 @@ where
         Ok(modules)
     }
 
+    /// Reads the imports of the modules of the root package and of all its
+    /// Gleam dependencies without compiling them. The caches written by
+    /// previous builds are used for the modules that have not changed.
+    ///
+    pub fn module_imports(mut self, allow_cycles: bool) -> Result<Vec<ModuleImports>> {
+        let mut modules = vec![];
+        for name in order_packages(&self.packages)? {
+            let package = self
+                .packages
+                .get(name.as_str())
+                .expect("Missing package")
+                .clone();
+            if usable_build_tools(&package)?.as_slice() != [BuildTool::Gleam] {
+                continue;
+            }
+            let root = self.gleam_dep_package_root(&package)?;
+            let config = PackageConfig::read(root.join("gleam.toml"), &self.io)?;
+            modules.extend(self.package_module_imports(&config, false, &root, allow_cycles)?);
+        }
+
+        let config = self.config.clone();
+        let root = self.paths.root().to_path_buf();
+        modules.extend(self.package_module_imports(&config, true, &root, allow_cycles)?);
+        Ok(modules)
+    }
+
+    fn package_module_imports(
+        &mut self,
+        config: &PackageConfig,
+        is_root: bool,
+        root: &Utf8Path,
+        allow_cycles: bool,
+    ) -> Result<Vec<ModuleImports>> {
+        let artefact_directory = self
+            .paths
+            .build_directory_for_package(self.mode(), self.target(), &config.name)
+            .join(paths::ARTEFACT_DIRECTORY_NAME);
+        let mode = if is_root { self.mode() } else { Mode::Prod };
+        let target = self.target();
+        let loader = PackageLoader::new(
+            self.io.clone(),
+            self.ids.clone(),
+            mode,
+            root,
+            CachedWarnings::Ignore,
+            &self.warnings,
+            CodegenRequired::No,
+            &artefact_directory,
+            target,
+            &config.name,
+            &mut self.stale_modules,
+            &mut self.defined_modules,
+            &self.incomplete_modules,
+            &config.features,
+        );
+        let modules = loader
+            .module_imports(allow_cycles)?
+            .into_iter()
+            .map(|(name, imports)| ModuleImports {
+                package: config.name.clone(),
+                name,
+                imports,
+            })
+            .collect();
+        Ok(modules)
+    }
+
     fn write_prelude(&self) -> Result<()> {
         // Only the JavaScript target has a prelude to write.
         if !self.target().is_javascript() {
@@ -279,9 +437,20 @@ where
             .build_directory_for_target(self.mode(), self.target());
 
         // Write the JavaScript prelude
-        let path = build.join("prelude.mjs");
-        if !self.io.is_file(&path) {
-            self.io.write(&path, crate::javascript::PRELUDE)?;
+        match self.options.javascript_module_format {
+            JavaScriptModuleFormat::EcmaScript => {
+                let path = build.join("prelude.mjs");
+                if !self.io.is_file(&path) {
+                    self.io.write(&path, crate::javascript::PRELUDE)?;
+                }
+            }
+            JavaScriptModuleFormat::CommonJs => {
+                let path = build.join("prelude.cjs");
+                if !self.io.is_file(&path) {
+                    self.io
+                        .write(&path, &crate::javascript::commonjs_prelude())?;
+                }
+            }
         }
 
         // Write the TypeScript prelude, if asked for
@@ -509,6 +678,18 @@ where
         &mut self,
         package: &ManifestPackage,
     ) -> Result<Vec<Module>, Error> {
+        let package_root = self.gleam_dep_package_root(package)?;
+        let config_path = package_root.join("gleam.toml");
+        let config = PackageConfig::read(config_path, &self.io)?;
+        self.compile_gleam_package(&config, false, package_root)
+            .into_result()
+            .map(|compiled| {
+                self.timings.extend(compiled.timings);
+                compiled.modules
+            })
+    }
+
+    fn gleam_dep_package_root(&self, package: &ManifestPackage) -> Result<Utf8PathBuf, Error> {
         // TODO: Test
         let package_root = match &package.source {
             // If the path is relative it is relative to the root of the
@@ -528,11 +709,7 @@ where
                 self.paths.build_packages_package(&package.name)
             }
         };
-        let config_path = package_root.join("gleam.toml");
-        let config = PackageConfig::read(config_path, &self.io)?;
-        self.compile_gleam_package(&config, false, package_root)
-            .into_result()
-            .map(|compiled| compiled.modules)
+        Ok(package_root)
     }
 
     fn compile_gleam_package(
@@ -569,7 +746,11 @@ where
             Target::JavaScript => super::TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions: self.config.javascript.typescript_declarations,
                 // This path is relative to each package output directory
-                prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+                prelude_location: Utf8PathBuf::from(format!(
+                    "../prelude.{}",
+                    self.options.javascript_module_format.extension()
+                )),
+                module_format: self.options.javascript_module_format,
             },
         };
 
@@ -588,6 +769,7 @@ where
         compiler.perform_codegen = self.options.codegen.should_codegen(is_root);
         compiler.compile_beam_bytecode = self.options.codegen.should_codegen(is_root);
         compiler.compile_modules = !(self.options.compile == Compile::DepsOnly && is_root);
+        compiler.whole_program = self.options.whole_program && is_root;
         compiler.subprocess_stdio = self.subprocess_stdio;
         compiler.target_support = if is_root {
             // When compiling the root package it is context specific as to whether we need to
@@ -608,6 +790,9 @@ where
         } else {
             CachedWarnings::Ignore
         };
+        if is_root {
+            compiler.lint_rules = self.lint_rules.clone();
+        }
 
         // Compile project to Erlang or JavaScript source code
         compiler.compile(
@@ -1,4 +1,19 @@
-use crate::{Error, manifest::ManifestPackage};
+use std::{collections::HashSet, time::Duration};
+
+use camino::Utf8PathBuf;
+
+use crate::{
+    Error,
+    build::{
+        Mode, NullTelemetry, PackageCompiler, PhaseTimings, StaleTracker,
+        TargetCodegenConfiguration,
+    },
+    config::PackageConfig,
+    io::{FileSystemWriter, memory::InMemoryFileSystem},
+    manifest::ManifestPackage,
+    uid::UniqueIdGenerator,
+    warning::WarningEmitter,
+};
 
 use super::project_compiler::{BuildTool, usable_build_tools};
 
@@ -55,3 +70,59 @@ fn usable_build_tool_mix_then_rebar3() {
         Ok(vec![BuildTool::Mix, BuildTool::Rebar3])
     )
 }
+
+#[test]
+fn timings_are_recorded_for_each_compiled_module() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(
+        &Utf8PathBuf::from("/src/wibble.gleam"),
+        "pub fn wibble() { 1 }",
+    )
+    .unwrap();
+    fs.write(
+        &Utf8PathBuf::from("/src/wobble.gleam"),
+        "import wibble pub fn wobble() { wibble.wibble() }",
+    )
+    .unwrap();
+
+    let mut config = PackageConfig::default();
+    config.name = "my_package".into();
+    let target = TargetCodegenConfiguration::Erlang { app_file: None };
+    let root = Utf8PathBuf::from("/");
+    let build = root.join("build");
+    let lib = root.join("lib");
+    let ids = UniqueIdGenerator::new();
+    let mut compiler =
+        PackageCompiler::new(&config, Mode::Dev, &root, &build, &lib, &target, ids, fs);
+    compiler.write_metadata = false;
+    let timings = compiler
+        .compile(
+            &WarningEmitter::null(),
+            &mut im::HashMap::new(),
+            &mut im::HashMap::new(),
+            &mut StaleTracker::default(),
+            &mut HashSet::new(),
+            &NullTelemetry,
+        )
+        .unwrap()
+        .timings;
+
+    let modules = timings
+        .modules()
+        .iter()
+        .map(|timings| (timings.package.as_str(), timings.module.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        modules,
+        vec![("my_package", "wibble"), ("my_package", "wobble")]
+    );
+
+    let total = timings.total();
+    let sum = |phase: fn(&PhaseTimings) -> Duration| -> Duration {
+        timings.modules().iter().map(|m| phase(&m.phases)).sum()
+    };
+    assert_eq!(total.parse, sum(|phases| phases.parse));
+    assert_eq!(total.infer, sum(|phases| phases.infer));
+    assert_eq!(total.codegen, sum(|phases| phases.codegen));
+    assert_eq!(total.total(), sum(PhaseTimings::total));
+}
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, time::Duration};
+
+use ecow::EcoString;
+
+/// A phase of the compilation of a single module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilePhase {
+    Parse,
+    Infer,
+    Codegen,
+}
+
+/// How long was spent in each phase of compilation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    pub parse: Duration,
+    pub infer: Duration,
+    pub codegen: Duration,
+}
+
+impl PhaseTimings {
+    pub fn add(&mut self, phase: CompilePhase, duration: Duration) {
+        match phase {
+            CompilePhase::Parse => self.parse += duration,
+            CompilePhase::Infer => self.infer += duration,
+            CompilePhase::Codegen => self.codegen += duration,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.infer + self.codegen
+    }
+}
+
+/// How long each phase of compilation took for a single module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleTimings {
+    pub package: EcoString,
+    pub module: EcoString,
+    pub phases: PhaseTimings,
+}
+
+/// The time spent in each phase of compilation by the modules that have been
+/// compiled. Modules that were read from the cache are not included.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    modules: Vec<ModuleTimings>,
+    /// The position of each module's timings in `modules`, keyed by the
+    /// package and the module name.
+    positions: HashMap<(EcoString, EcoString), usize>,
+}
+
+impl Timings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given duration to the time spent by a module in a phase.
+    pub fn record(
+        &mut self,
+        package: &EcoString,
+        module: &EcoString,
+        phase: CompilePhase,
+        duration: Duration,
+    ) {
+        let key = (package.clone(), module.clone());
+        let position = *self.positions.entry(key).or_insert_with(|| {
+            self.modules.push(ModuleTimings {
+                package: package.clone(),
+                module: module.clone(),
+                phases: PhaseTimings::default(),
+            });
+            self.modules.len() - 1
+        });
+        if let Some(timings) = self.modules.get_mut(position) {
+            timings.phases.add(phase, duration);
+        }
+    }
+
+    /// Adds all the timings recorded by `other` to these ones.
+    pub fn extend(&mut self, other: Timings) {
+        for timings in other.modules {
+            let ModuleTimings {
+                package,
+                module,
+                phases,
+            } = timings;
+            self.record(&package, &module, CompilePhase::Parse, phases.parse);
+            self.record(&package, &module, CompilePhase::Infer, phases.infer);
+            self.record(&package, &module, CompilePhase::Codegen, phases.codegen);
+        }
+    }
+
+    /// The timings of each module, in the order they were first recorded.
+    pub fn modules(&self) -> &[ModuleTimings] {
+        &self.modules
+    }
+
+    /// The time spent in each phase by all the modules together.
+    pub fn total(&self) -> PhaseTimings {
+        let mut total = PhaseTimings::default();
+        for timings in &self.modules {
+            total.parse += timings.phases.parse;
+            total.infer += timings.phases.infer;
+            total.codegen += timings.phases.codegen;
+        }
+        total
+    }
+}
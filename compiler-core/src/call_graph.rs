@@ -7,9 +7,9 @@ mod into_dependency_order_tests;
 use crate::{
     Result,
     ast::{
-        AssignName, BitArrayOption, ClauseGuard, Constant, Pattern, SrcSpan, Statement,
-        UntypedClauseGuard, UntypedExpr, UntypedFunction, UntypedModuleConstant, UntypedPattern,
-        UntypedStatement,
+        AssignName, BitArrayOption, ClauseGuard, ComprehensionQualifier, Constant, Pattern,
+        SELF_QUALIFIER, SrcSpan, Statement, UntypedClauseGuard, UntypedExpr, UntypedFunction,
+        UntypedModuleConstant, UntypedPattern, UntypedStatement,
     },
     type_::Error,
 };
@@ -20,6 +20,9 @@ use petgraph::{Directed, stable_graph::StableGraph};
 #[derive(Debug, Default)]
 struct CallGraphBuilder<'a> {
     names: im::HashMap<&'a str, Option<(NodeIndex, SrcSpan)>>,
+    /// The module level names, which can't be shadowed by local variables when
+    /// referenced with the `self` qualifier.
+    module_names: im::HashMap<&'a str, NodeIndex>,
     graph: StableGraph<(), (), Directed>,
     current_function: NodeIndex,
 }
@@ -50,6 +53,7 @@ impl<'a> CallGraphBuilder<'a> {
 
         let index = self.graph.add_node(());
         let previous = self.names.insert(name, Some((index, location)));
+        _ = self.module_names.insert(name, index);
 
         if let Some(Some((_, previous_location))) = previous {
             return Err(Error::DuplicateName {
@@ -72,6 +76,7 @@ impl<'a> CallGraphBuilder<'a> {
 
         let index = self.graph.add_node(());
         let previous = self.names.insert(name, Some((index, location)));
+        _ = self.module_names.insert(name, index);
 
         if let Some(Some((_, previous_location))) = previous {
             return Err(Error::DuplicateName {
@@ -134,6 +139,12 @@ impl<'a> CallGraphBuilder<'a> {
         _ = self.graph.add_edge(self.current_function, *target, ());
     }
 
+    fn referenced_with_self_qualifier(&mut self, name: &str) {
+        if let Some(target) = self.module_names.get(name) {
+            _ = self.graph.add_edge(self.current_function, *target, ());
+        }
+    }
+
     fn statements(&mut self, statements: &'a [UntypedStatement]) {
         let names = self.names.clone();
         for statement in statements {
@@ -157,6 +168,9 @@ impl<'a> CallGraphBuilder<'a> {
                     self.pattern(&assignment.pattern);
                 }
             }
+            Statement::Assert(assert) => {
+                self.expression(&assert.value);
+            }
         };
     }
 
@@ -165,6 +179,7 @@ impl<'a> CallGraphBuilder<'a> {
             UntypedExpr::Int { .. }
             | UntypedExpr::Float { .. }
             | UntypedExpr::String { .. }
+            | UntypedExpr::Hole { .. }
             | UntypedExpr::Placeholder { .. } => (),
 
             UntypedExpr::Todo { message, .. } => {
@@ -200,7 +215,11 @@ impl<'a> CallGraphBuilder<'a> {
                 }
             }
 
-            UntypedExpr::PipeLine { expressions } => {
+            UntypedExpr::TypeApplication { fun, .. } => {
+                self.expression(fun);
+            }
+
+            UntypedExpr::PipeLine { expressions, .. } => {
                 for expression in expressions {
                     self.expression(expression);
                 }
@@ -232,6 +251,34 @@ impl<'a> CallGraphBuilder<'a> {
                 }
             }
 
+            UntypedExpr::ListComprehension {
+                element,
+                qualifiers,
+                ..
+            } => {
+                // Each generator's name is in scope for all that comes after it.
+                let names = self.names.clone();
+                for qualifier in qualifiers {
+                    match qualifier {
+                        ComprehensionQualifier::Generator { name, list, .. } => {
+                            self.expression(list);
+                            self.define(name);
+                        }
+                        ComprehensionQualifier::Filter(condition) => self.expression(condition),
+                    }
+                }
+                self.expression(element);
+                self.names = names;
+            }
+
+            UntypedExpr::FieldAccess {
+                container, label, ..
+            } if matches!(container.as_ref(), UntypedExpr::Var { name, .. } if name == SELF_QUALIFIER)
+                && !self.names.contains_key(SELF_QUALIFIER) =>
+            {
+                self.referenced_with_self_qualifier(label);
+            }
+
             UntypedExpr::NegateInt {
                 value: expression, ..
             }
@@ -312,6 +359,7 @@ impl<'a> CallGraphBuilder<'a> {
                 right_side_assignment: AssignName::Discard(_),
                 ..
             }
+            | Pattern::Constant { .. }
             | Pattern::Invalid { .. } => (),
 
             Pattern::StringPrefix {
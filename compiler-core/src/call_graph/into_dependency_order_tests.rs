@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
     ast::{Arg, Function, ModuleConstant, Publicity},
-    type_::{Deprecation, expression::Implementations},
+    type_::{Deprecation, Purity, expression::Implementations},
 };
 use ecow::EcoString;
 
@@ -15,6 +15,12 @@ fn parse_and_order(
     let functions = functions
         .iter()
         .map(|(name, arguments, src)| Function {
+            purity: Purity::Unknown,
+            benchmark: false,
+            memoize: false,
+            should_panic: None,
+            since: None,
+            doc_attribute: vec![],
             name: Some((SrcSpan::default(), EcoString::from(*name))),
             arguments: arguments
                 .iter()
@@ -38,6 +44,8 @@ fn parse_and_order(
             documentation: None,
             external_erlang: None,
             external_javascript: None,
+            external_erlang_arguments: None,
+            external_javascript_arguments: None,
             implementations: Implementations {
                 gleam: true,
                 uses_erlang_externals: true,
@@ -53,6 +61,8 @@ fn parse_and_order(
             let const_value = crate::parse::parse_const_value(value).expect("syntax error");
             ModuleConstant {
                 documentation: None,
+                doc_attribute: vec![],
+                since: None,
                 location: Default::default(),
                 publicity: Publicity::Public,
                 name: EcoString::from(*name),
@@ -687,3 +697,40 @@ fn bug_2275() {
         vec![vec!["two"], vec!["one"]]
     );
 }
+
+#[test]
+fn self_qualified_reference() {
+    let functions = [
+        ("a", [].as_slice(), r#"self.b()"#),
+        ("b", [].as_slice(), r#"self.c"#),
+        ("c", [].as_slice(), "1"),
+    ];
+    assert_eq!(
+        parse_and_order(functions.as_slice(), [].as_slice()).unwrap(),
+        vec![vec!["c"], vec!["b"], vec!["a"]]
+    );
+}
+
+#[test]
+fn self_qualified_reference_shadowed_by_argument() {
+    let functions = [
+        ("a", ["b"].as_slice(), r#"self.b"#),
+        ("b", [].as_slice(), "1"),
+    ];
+    assert_eq!(
+        parse_and_order(functions.as_slice(), [].as_slice()).unwrap(),
+        vec![vec!["b"], vec!["a"]]
+    );
+}
+
+#[test]
+fn self_variable_is_not_a_qualifier() {
+    let functions = [
+        ("a", ["self"].as_slice(), r#"self.b"#),
+        ("b", [].as_slice(), "1"),
+    ];
+    assert_eq!(
+        parse_and_order(functions.as_slice(), [].as_slice()).unwrap(),
+        vec![vec!["a"], vec!["b"]]
+    );
+}
@@ -1,8 +1,11 @@
 use crate::{
     Result,
     analyse::TargetSupport,
-    build::{ErlangAppCodegenConfiguration, Module, package_compiler::StdlibPackage},
-    config::PackageConfig,
+    build::{
+        CompilePhase, ErlangAppCodegenConfiguration, JavaScriptModuleFormat, Module, Timings,
+        package_compiler::StdlibPackage,
+    },
+    config::{PackageConfig, PanicHandler},
     erlang,
     io::FileSystemWriter,
     javascript::{self, ModuleConfig},
@@ -11,7 +14,7 @@ use crate::{
 use ecow::EcoString;
 use erlang::escape_atom_string;
 use itertools::Itertools;
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Instant};
 
 use camino::Utf8Path;
 
@@ -21,13 +24,22 @@ use camino::Utf8Path;
 pub struct Erlang<'a> {
     build_directory: &'a Utf8Path,
     include_directory: &'a Utf8Path,
+    minimum_otp_version: Option<u32>,
+    panic_handler: Option<&'a PanicHandler>,
 }
 
 impl<'a> Erlang<'a> {
-    pub fn new(build_directory: &'a Utf8Path, include_directory: &'a Utf8Path) -> Self {
+    pub fn new(
+        build_directory: &'a Utf8Path,
+        include_directory: &'a Utf8Path,
+        minimum_otp_version: Option<u32>,
+        panic_handler: Option<&'a PanicHandler>,
+    ) -> Self {
         Self {
             build_directory,
             include_directory,
+            minimum_otp_version,
+            panic_handler,
         }
     }
 
@@ -36,11 +48,14 @@ impl<'a> Erlang<'a> {
         writer: Writer,
         modules: &[Module],
         root: &Utf8Path,
+        timings: &mut Timings,
     ) -> Result<()> {
         for module in modules {
+            let start = Instant::now();
             let erl_name = module.name.replace("/", "@");
             self.erlang_module(&writer, module, &erl_name, root)?;
             self.erlang_record_headers(&writer, module, &erl_name)?;
+            record_codegen_timing(timings, module, start);
         }
         Ok(())
     }
@@ -55,7 +70,14 @@ impl<'a> Erlang<'a> {
         let name = format!("{erl_name}.erl");
         let path = self.build_directory.join(&name);
         let line_numbers = LineNumbers::new(&module.code);
-        let output = erlang::module(&module.ast, &line_numbers, root);
+        let output = erlang::module(
+            &module.ast,
+            &line_numbers,
+            &module.code,
+            root,
+            self.minimum_otp_version,
+            self.panic_handler,
+        );
         tracing::debug!(name = ?name, "Generated Erlang module");
         writer.write(&path, &output?)
     }
@@ -169,16 +191,20 @@ pub struct JavaScript<'a> {
     prelude_location: &'a Utf8Path,
     project_root: &'a Utf8Path,
     typescript: TypeScriptDeclarations,
+    module_format: JavaScriptModuleFormat,
     target_support: TargetSupport,
+    panic_handler: Option<&'a PanicHandler>,
 }
 
 impl<'a> JavaScript<'a> {
     pub fn new(
         output_directory: &'a Utf8Path,
         typescript: TypeScriptDeclarations,
+        module_format: JavaScriptModuleFormat,
         prelude_location: &'a Utf8Path,
         project_root: &'a Utf8Path,
         target_support: TargetSupport,
+        panic_handler: Option<&'a PanicHandler>,
     ) -> Self {
         Self {
             prelude_location,
@@ -186,6 +212,8 @@ impl<'a> JavaScript<'a> {
             target_support,
             project_root,
             typescript,
+            module_format,
+            panic_handler,
         }
     }
 
@@ -194,21 +222,33 @@ impl<'a> JavaScript<'a> {
         writer: &impl FileSystemWriter,
         modules: &[Module],
         stdlib_package: StdlibPackage,
+        timings: &mut Timings,
     ) -> Result<()> {
         for module in modules {
+            let start = Instant::now();
             let js_name = module.name.clone();
             if self.typescript == TypeScriptDeclarations::Emit {
                 self.ts_declaration(writer, module, &js_name)?;
             }
-            self.js_module(writer, module, &js_name, stdlib_package)?
+            self.js_module(writer, module, &js_name, stdlib_package)?;
+            record_codegen_timing(timings, module, start);
         }
         self.write_prelude(writer)?;
         Ok(())
     }
 
     fn write_prelude(&self, writer: &impl FileSystemWriter) -> Result<()> {
-        let rexport = format!("export * from \"{}\";\n", self.prelude_location);
-        let prelude_path = &self.output_directory.join("gleam.mjs");
+        let rexport = match self.module_format {
+            JavaScriptModuleFormat::EcmaScript => {
+                format!("export * from \"{}\";\n", self.prelude_location)
+            }
+            JavaScriptModuleFormat::CommonJs => {
+                format!("module.exports = require(\"{}\");\n", self.prelude_location)
+            }
+        };
+        let prelude_path = &self
+            .output_directory
+            .join(format!("gleam.{}", self.module_format.extension()));
 
         // This check skips unnecessary `gleam.mjs` writes which confuse
         // watchers and HMR build tools
@@ -253,7 +293,7 @@ impl<'a> JavaScript<'a> {
         js_name: &str,
         stdlib_package: StdlibPackage,
     ) -> Result<()> {
-        let name = format!("{js_name}.mjs");
+        let name = format!("{js_name}.{}", self.module_format.extension());
         let path = self.output_directory.join(name);
         let line_numbers = LineNumbers::new(&module.code);
         let output = javascript::module(ModuleConfig {
@@ -264,9 +304,21 @@ impl<'a> JavaScript<'a> {
             src: &module.code,
             target_support: self.target_support,
             typescript: self.typescript,
+            module_format: self.module_format,
             stdlib_package,
+            panic_handler: self.panic_handler,
         });
         tracing::debug!(name = ?js_name, "Generated js module");
         writer.write(&path, &output?)
     }
 }
+
+fn record_codegen_timing(timings: &mut Timings, module: &Module, start: Instant) {
+    let package = &module.ast.type_info.package;
+    timings.record(
+        package,
+        &module.name,
+        CompilePhase::Codegen,
+        start.elapsed(),
+    );
+}
@@ -103,6 +103,18 @@ pub struct PackageConfig {
     pub target: Target,
     #[serde(default)]
     pub internal_modules: Option<Vec<Glob>>,
+    #[serde(default)]
+    pub optimisations: OptimisationsConfig,
+    #[serde(default)]
+    pub lints: LintsConfig,
+    /// The feature flags enabled for this package. Definitions annotated with
+    /// `@when(feature: "...")` are only compiled if their feature is in here.
+    #[serde(default)]
+    pub features: Vec<EcoString>,
+    /// A function of the package that the errors raised by `panic` and `todo`
+    /// are passed to first, so that they can be handled before crashing.
+    #[serde(default)]
+    pub panic_handler: Option<PanicHandler>,
 }
 
 pub fn serialise_range<S>(
@@ -138,7 +150,7 @@ impl PackageConfig {
     pub fn dependencies_for(&self, mode: Mode) -> Result<Dependencies> {
         match mode {
             Mode::Dev | Mode::Lsp => self.all_direct_dependencies(),
-            Mode::Prod => Ok(self.dependencies.clone()),
+            Mode::Prod | Mode::WholeProgram => Ok(self.dependencies.clone()),
         }
     }
 
@@ -583,16 +595,147 @@ impl Default for PackageConfig {
             links: Default::default(),
             internal_modules: Default::default(),
             target: Target::Erlang,
+            optimisations: Default::default(),
+            lints: Default::default(),
+            features: Default::default(),
+            panic_handler: Default::default(),
+        }
+    }
+}
+
+/// A Gleam function that is called with the error of each `panic` and `todo`
+/// of the package when they are evaluated, returning the error to raise. It can
+/// also cause a crash of its own instead, or report the error somewhere else
+/// first.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct PanicHandler {
+    /// The module defining the function, e.g. `my_app/panics`.
+    pub module: EcoString,
+    /// The name of the function, which must be public and take one argument.
+    pub function: EcoString,
+}
+
+/// Opt-in optimisations performed on the code of the package before it is
+/// compiled to Erlang or JavaScript.
+#[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct OptimisationsConfig {
+    /// Calls to functions annotated as `@pure` are removed if their value is
+    /// never used.
+    #[serde(default)]
+    pub remove_unused_pure_calls: bool,
+    /// `case` expressions whose subjects are all literal values are replaced
+    /// with the clause that would be picked at runtime.
+    #[serde(default)]
+    pub reduce_constant_cases: bool,
+    /// The steps of a pipeline are passed directly to the following step,
+    /// rather than being assigned to a variable, when doing so can't change
+    /// the order in which things are evaluated.
+    #[serde(default)]
+    pub inline_pipelines: bool,
+    /// When building with `--whole-program`, the public functions and
+    /// constants of the modules other than the package's main module are
+    /// removed too if they are never used by any Gleam code. They are kept
+    /// otherwise, as Erlang or JavaScript code could be calling them.
+    #[serde(default)]
+    pub remove_unused_public_definitions: bool,
+}
+
+impl OptimisationsConfig {
+    /// Whether any of the optimisations performed on each module on its own
+    /// are enabled.
+    pub fn any_enabled(&self) -> bool {
+        self.remove_unused_pure_calls || self.reduce_constant_cases || self.inline_pipelines
+    }
+}
+
+/// Lints checking that the code of the package follows the team's
+/// conventions. All of them are disabled by default, except for
+/// `inefficient_string_building` and `inefficient_list_building`.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LintsConfig {
+    /// Function names must be snake_case, with no repeated or trailing
+    /// underscores.
+    #[serde(default)]
+    pub snake_case_function_names: bool,
+    /// The minimum number of characters in the name of a type parameter.
+    #[serde(default)]
+    pub min_type_parameter_length: Option<usize>,
+    /// The maximum number of characters in the names of the definitions of
+    /// a module, their arguments, labels and type parameters.
+    #[serde(default)]
+    pub max_identifier_length: Option<usize>,
+    /// Private functions must not have labels for discarded arguments that
+    /// are never used by any call to the function.
+    #[serde(default)]
+    pub unused_labels: bool,
+    /// Anonymous functions must not discard all of their arguments.
+    #[serde(default)]
+    pub constant_functions: bool,
+    /// `case` expressions must not return the variable they match on,
+    /// unchanged, from all of their clauses.
+    #[serde(default)]
+    pub identity_cases: bool,
+    /// `case` expressions must not have a single clause matching their only
+    /// subject with a pattern that can't fail, as they could be a `let`.
+    #[serde(default)]
+    pub single_arm_cases: bool,
+    /// Pipelines must not be made only of steps calling a function with the
+    /// previous value, as they could be written as a composition of those
+    /// functions.
+    #[serde(default)]
+    pub composable_pipelines: bool,
+    /// Function annotations must not be less general than the type that
+    /// would be inferred for the function without them.
+    #[serde(default)]
+    pub overly_specific_annotations: bool,
+    /// The `src` modules of the package must not use `todo` or `panic`.
+    #[serde(default)]
+    pub todo_and_panic_in_library: bool,
+    /// Recursive functions must not build a string by concatenating to one
+    /// of their own parameters with `<>`. This is enabled unless it's set to
+    /// `false`.
+    #[serde(default = "enabled")]
+    pub inefficient_string_building: bool,
+    /// Recursive functions must not build a list by appending to the end of
+    /// one of their own parameters with `list.append`. This is enabled unless
+    /// it's set to `false`.
+    #[serde(default = "enabled")]
+    pub inefficient_list_building: bool,
+}
+
+impl Default for LintsConfig {
+    fn default() -> Self {
+        Self {
+            snake_case_function_names: false,
+            min_type_parameter_length: None,
+            max_identifier_length: None,
+            unused_labels: false,
+            constant_functions: false,
+            identity_cases: false,
+            single_arm_cases: false,
+            composable_pipelines: false,
+            overly_specific_annotations: false,
+            todo_and_panic_in_library: false,
+            inefficient_string_building: enabled(),
+            inefficient_list_building: enabled(),
         }
     }
 }
 
+fn enabled() -> bool {
+    true
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone)]
 pub struct ErlangConfig {
     #[serde(default)]
     pub application_start_module: Option<EcoString>,
     #[serde(default)]
     pub extra_applications: Vec<EcoString>,
+    /// The oldest OTP release the package supports. The generated Erlang
+    /// uses features introduced up to this release without checking for them.
+    #[serde(default)]
+    pub minimum_otp_version: Option<u32>,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Default, Clone)]
@@ -900,3 +1043,176 @@ name = "1"
         "Package names may only contain lowercase letters, numbers, and underscores for key `name` at line 1 column 1"
     )
 }
+
+#[test]
+fn optimisations_are_disabled_by_default() {
+    let input = r#"
+name = "wibble"
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(!config.optimisations.remove_unused_pure_calls);
+    assert!(!config.optimisations.reduce_constant_cases);
+    assert!(!config.optimisations.inline_pipelines);
+    assert!(!config.optimisations.remove_unused_public_definitions);
+}
+
+#[test]
+fn remove_unused_pure_calls_optimisation() {
+    let input = r#"
+name = "wibble"
+
+[optimisations]
+remove_unused_pure_calls = true
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(config.optimisations.remove_unused_pure_calls);
+}
+
+#[test]
+fn reduce_constant_cases_optimisation() {
+    let input = r#"
+name = "wibble"
+
+[optimisations]
+reduce_constant_cases = true
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(config.optimisations.reduce_constant_cases);
+    assert!(!config.optimisations.remove_unused_pure_calls);
+}
+
+#[test]
+fn inline_pipelines_optimisation() {
+    let input = r#"
+name = "wibble"
+
+[optimisations]
+inline_pipelines = true
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(config.optimisations.inline_pipelines);
+    assert!(config.optimisations.any_enabled());
+}
+
+#[test]
+fn remove_unused_public_definitions_optimisation() {
+    let input = r#"
+name = "wibble"
+
+[optimisations]
+remove_unused_public_definitions = true
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(config.optimisations.remove_unused_public_definitions);
+    assert!(!config.optimisations.any_enabled());
+}
+
+#[test]
+fn lints_are_disabled_by_default() {
+    let input = r#"
+name = "wibble"
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(config.lints, LintsConfig::default());
+    assert!(!config.lints.snake_case_function_names);
+    assert_eq!(config.lints.min_type_parameter_length, None);
+    assert_eq!(config.lints.max_identifier_length, None);
+    assert!(!config.lints.unused_labels);
+    assert!(!config.lints.constant_functions);
+    assert!(!config.lints.identity_cases);
+    assert!(!config.lints.single_arm_cases);
+    assert!(!config.lints.composable_pipelines);
+    assert!(!config.lints.overly_specific_annotations);
+    assert!(!config.lints.todo_and_panic_in_library);
+    assert!(config.lints.inefficient_string_building);
+    assert!(config.lints.inefficient_list_building);
+}
+
+#[test]
+fn lints_configuration() {
+    let input = r#"
+name = "wibble"
+
+[lints]
+snake_case_function_names = true
+min_type_parameter_length = 2
+max_identifier_length = 30
+unused_labels = true
+constant_functions = true
+identity_cases = true
+single_arm_cases = true
+composable_pipelines = true
+overly_specific_annotations = true
+todo_and_panic_in_library = true
+inefficient_string_building = false
+inefficient_list_building = false
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.lints,
+        LintsConfig {
+            snake_case_function_names: true,
+            min_type_parameter_length: Some(2),
+            max_identifier_length: Some(30),
+            unused_labels: true,
+            constant_functions: true,
+            identity_cases: true,
+            single_arm_cases: true,
+            composable_pipelines: true,
+            overly_specific_annotations: true,
+            todo_and_panic_in_library: true,
+            inefficient_string_building: false,
+            inefficient_list_building: false,
+        }
+    );
+}
+
+#[test]
+fn no_features_are_enabled_by_default() {
+    let input = r#"
+name = "wibble"
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert!(config.features.is_empty());
+}
+
+#[test]
+fn features() {
+    let input = r#"
+name = "wibble"
+features = ["experimental", "wobble"]
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.features,
+        vec![EcoString::from("experimental"), EcoString::from("wobble")]
+    );
+}
+
+#[test]
+fn no_panic_handler_by_default() {
+    let input = r#"
+name = "wibble"
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(config.panic_handler, None);
+}
+
+#[test]
+fn panic_handler() {
+    let input = r#"
+name = "wibble"
+
+[panic_handler]
+module = "wibble/panics"
+function = "handle"
+"#;
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.panic_handler,
+        Some(PanicHandler {
+            module: "wibble/panics".into(),
+            function: "handle".into(),
+        })
+    );
+}
@@ -139,3 +139,151 @@ impl Diagnostic {
             .expect("write_title_reset");
     }
 }
+
+/// The extended explanation of the diagnostics with a given code, printed by
+/// `gleam explain`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    pub code: &'static str,
+    /// The title of the diagnostics with this code.
+    pub title: &'static str,
+    pub text: &'static str,
+}
+
+/// The diagnostics that have a stable code, along with their explanations.
+/// Error codes start with `E` and warning codes with `W`. A code is never
+/// reused for a different diagnostic once it has been given out.
+pub const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "E0001",
+        title: "Unknown variable",
+        text: "A variable was used that is not defined in the scope it is used in.
+
+```gleam
+pub fn main() {
+  let name = \"Lucy\"
+  nmae
+}
+```
+
+Variables are only in scope after they have been assigned with `let`, or
+when they are the arguments of the function or bound by a pattern. Check the
+spelling of the name, or define the variable before it is used. To use a
+function or a constant of another module it must be qualified with the
+module's name, or imported unqualified.",
+    },
+    Explanation {
+        code: "E0002",
+        title: "Type mismatch",
+        text: "A value was used where a value of another type was expected.
+
+```gleam
+pub fn main() {
+  1 + \"one\"
+}
+```
+
+Gleam values are never converted from one type to another implicitly. Use a
+function that does the conversion, such as `int.to_string`, or check that the
+value is the one you meant to use.",
+    },
+    Explanation {
+        code: "E0003",
+        title: "Inexhaustive patterns",
+        text: "A `case` expression doesn't have a clause for every value it could
+be matching on.
+
+```gleam
+pub fn main(result: Result(Int, Nil)) {
+  case result {
+    Ok(value) -> value
+  }
+}
+```
+
+Every possible value must be handled, so the program can't crash because of
+an unexpected one. Add a clause for each of the missing patterns listed in
+the error, or a catch-all `_` clause.",
+    },
+    Explanation {
+        code: "E0004",
+        title: "Unknown module",
+        text: "A module was imported or used that can't be found.
+
+```gleam
+import gleam/lists
+```
+
+The module must be defined in the `src` directory of the package, or in one
+of its dependencies. Check the spelling of the name, and that the package
+defining it is listed in the dependencies of `gleam.toml`. A module that is
+used qualified, like `list.map`, must also be imported.",
+    },
+    Explanation {
+        code: "E0005",
+        title: "Unknown type",
+        text: "A type was used that is not defined or imported.
+
+```gleam
+pub fn name(user: Usr) -> String {
+  user.name
+}
+```
+
+Check the spelling of the type's name, and that it is defined in the module
+or imported from another one with `import module.{type Name}`.",
+    },
+    Explanation {
+        code: "W0001",
+        title: "Unused variable",
+        text: "A variable is assigned or bound by a pattern but never used.
+
+```gleam
+pub fn main() {
+  let name = \"Lucy\"
+  Nil
+}
+```
+
+Remove the variable, or prefix its name with an underscore, like `_name`, to
+show that it is intentionally not used.",
+    },
+    Explanation {
+        code: "W0002",
+        title: "Unused imported module",
+        text: "A module is imported but none of its values or types are used.
+
+```gleam
+import gleam/list
+
+pub fn main() {
+  Nil
+}
+```
+
+Remove the import, so the module doesn't depend on code it doesn't need.",
+    },
+    Explanation {
+        code: "W0003",
+        title: "Todo found",
+        text: "The code contains a `todo` expression.
+
+```gleam
+pub fn main() {
+  todo as \"Print a greeting\"
+}
+```
+
+`todo` marks code that is yet to be written, and crashes the program if it
+is ever evaluated. Replace it with the implementation before running the
+code. Packages containing `todo` can't be published.",
+    },
+];
+
+/// The explanation for a diagnostic code, like `E0001`. The case of the code
+/// doesn't matter.
+pub fn explanation(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS
+        .iter()
+        .find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
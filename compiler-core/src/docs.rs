@@ -35,6 +35,27 @@ pub enum DocContext {
     Build,
 }
 
+/// Which definitions end up in the generated documentation.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum DocVisibility {
+    /// Only the definitions that can be used by other packages.
+    PublicOnly,
+    /// Private and internal definitions are included as well, and they are
+    /// labelled as such. This is useful to produce documentation for the
+    /// maintainers of a package rather than for its users.
+    IncludePrivate,
+}
+
+impl DocVisibility {
+    fn includes(&self, publicity: Publicity) -> bool {
+        match self {
+            Self::PublicOnly => publicity.is_public(),
+            Self::IncludePrivate => true,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn generate_html<IO: FileSystemReader>(
     paths: &ProjectPaths,
     config: &PackageConfig,
@@ -43,6 +64,7 @@ pub fn generate_html<IO: FileSystemReader>(
     fs: IO,
     rendering_timestamp: SystemTime,
     is_hex_publish: DocContext,
+    visibility: DocVisibility,
 ) -> Vec<OutputFile> {
     let modules = analysed
         .iter()
@@ -173,8 +195,7 @@ pub fn generate_html<IO: FileSystemReader>(
             .ast
             .definitions
             .iter()
-            .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| function(&source_links, statement))
+            .flat_map(|statement| function(&source_links, statement, visibility))
             .sorted()
             .collect();
 
@@ -182,8 +203,7 @@ pub fn generate_html<IO: FileSystemReader>(
             .ast
             .definitions
             .iter()
-            .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| type_(&source_links, statement))
+            .flat_map(|statement| type_(&source_links, statement, visibility))
             .sorted()
             .collect();
 
@@ -191,8 +211,7 @@ pub fn generate_html<IO: FileSystemReader>(
             .ast
             .definitions
             .iter()
-            .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| constant(&source_links, statement))
+            .flat_map(|statement| constant(&source_links, statement, visibility))
             .sorted()
             .collect();
 
@@ -544,20 +563,22 @@ fn import_synonyms(parent: &str, child: &str) -> String {
 fn function<'a>(
     source_links: &SourceLinker,
     statement: &'a TypedDefinition,
+    visibility: DocVisibility,
 ) -> Option<DocsFunction<'a>> {
     let mut formatter = format::Formatter::new();
 
     match statement {
         Definition::Function(Function {
-            publicity: Publicity::Public,
+            publicity,
             name,
             documentation: doc,
             arguments: args,
             return_type: ret,
             location,
             deprecation,
+            since,
             ..
-        }) => {
+        }) if visibility.includes(*publicity) => {
             let (_, name) = name
                 .as_ref()
                 .expect("Function in a definition must be named");
@@ -568,7 +589,7 @@ fn function<'a>(
                 text_documentation: text_documentation(doc),
                 signature: print(
                     formatter
-                        .docs_fn_signature(Publicity::Public, name, args, ret.clone(), location)
+                        .docs_fn_signature(*publicity, name, args, ret.clone(), location)
                         .group(),
                 ),
                 source_url: source_links.url(*location),
@@ -576,6 +597,8 @@ fn function<'a>(
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
                 },
+                since: since.as_ref().map(EcoString::to_string).unwrap_or_default(),
+                visibility_tag: visibility_tag(*publicity),
             })
         }
 
@@ -626,47 +649,59 @@ fn render_markdown(text: &str, source: MarkdownSource) -> String {
     s
 }
 
-fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Option<Type<'a>> {
+fn type_<'a>(
+    source_links: &SourceLinker,
+    statement: &'a TypedDefinition,
+    visibility: DocVisibility,
+) -> Option<Type<'a>> {
     let mut formatter = format::Formatter::new();
 
     match statement {
-        Definition::CustomType(ct) if ct.publicity.is_importable() && !ct.opaque => Some(Type {
-            name: &ct.name,
-            // TODO: Don't use the same printer for docs as for the formatter.
-            // We are not interested in showing the exact implementation in the
-            // documentation and we could add things like colours, etc.
-            definition: print(formatter.custom_type(ct)),
-            documentation: markdown_documentation(&ct.documentation),
-            text_documentation: text_documentation(&ct.documentation),
-            deprecation_message: match &ct.deprecation {
-                Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
-            },
-            constructors: ct
-                .constructors
-                .iter()
-                .map(|constructor| TypeConstructor {
-                    definition: print(formatter.record_constructor(constructor)),
-                    documentation: markdown_documentation(&constructor.documentation),
-                    text_documentation: text_documentation(&constructor.documentation),
-                    arguments: constructor
-                        .arguments
-                        .iter()
-                        .filter_map(|arg| arg.label.as_ref().map(|(_, label)| (arg, label)))
-                        .map(|(argument, label)| TypeConstructorArg {
-                            name: label.trim_end().to_string(),
-                            doc: markdown_documentation(&argument.doc),
-                        })
-                        .filter(|arg| !arg.doc.is_empty())
-                        .collect(),
-                })
-                .collect(),
-            source_url: source_links.url(ct.location),
-            opaque: ct.opaque,
-        }),
+        Definition::CustomType(ct) if visibility.includes(ct.publicity) && !ct.opaque => {
+            Some(Type {
+                name: &ct.name,
+                // TODO: Don't use the same printer for docs as for the formatter.
+                // We are not interested in showing the exact implementation in the
+                // documentation and we could add things like colours, etc.
+                // The `@doc` attribute is already shown as the documentation,
+                // so it's left out of the definition.
+                definition: print(formatter.custom_type(&CustomType {
+                    doc_attribute: vec![],
+                    ..ct.clone()
+                })),
+                documentation: markdown_documentation(&ct.documentation),
+                text_documentation: text_documentation(&ct.documentation),
+                deprecation_message: match &ct.deprecation {
+                    Deprecation::NotDeprecated => "".to_string(),
+                    Deprecation::Deprecated { message } => message.to_string(),
+                },
+                constructors: ct
+                    .constructors
+                    .iter()
+                    .map(|constructor| TypeConstructor {
+                        definition: print(formatter.record_constructor(constructor)),
+                        documentation: markdown_documentation(&constructor.documentation),
+                        text_documentation: text_documentation(&constructor.documentation),
+                        arguments: constructor
+                            .arguments
+                            .iter()
+                            .filter_map(|arg| arg.label.as_ref().map(|(_, label)| (arg, label)))
+                            .map(|(argument, label)| TypeConstructorArg {
+                                name: label.trim_end().to_string(),
+                                doc: markdown_documentation(&argument.doc),
+                            })
+                            .filter(|arg| !arg.doc.is_empty())
+                            .collect(),
+                    })
+                    .collect(),
+                source_url: source_links.url(ct.location),
+                opaque: ct.opaque,
+                visibility_tag: visibility_tag(ct.publicity),
+            })
+        }
 
         Definition::CustomType(CustomType {
-            publicity: Publicity::Public,
+            publicity,
             opaque: true,
             name,
             parameters,
@@ -674,11 +709,11 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             location,
             deprecation,
             ..
-        }) => Some(Type {
+        }) if visibility.includes(*publicity) => Some(Type {
             name,
             definition: print(
                 formatter
-                    .docs_opaque_custom_type(Publicity::Public, name, parameters, location)
+                    .docs_opaque_custom_type(*publicity, name, parameters, location)
                     .group(),
             ),
             documentation: markdown_documentation(doc),
@@ -690,10 +725,11 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
                 Deprecation::Deprecated { message } => message.to_string(),
             },
             opaque: true,
+            visibility_tag: visibility_tag(*publicity),
         }),
 
         Definition::TypeAlias(TypeAlias {
-            publicity: Publicity::Public,
+            publicity,
             alias: name,
             type_ast: type_,
             documentation: doc,
@@ -701,11 +737,11 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             location,
             deprecation,
             ..
-        }) => Some(Type {
+        }) if visibility.includes(*publicity) => Some(Type {
             name,
             definition: print(
                 formatter
-                    .type_alias(Publicity::Public, name, args, type_, deprecation, location)
+                    .type_alias(*publicity, name, args, type_, deprecation, &[], location)
                     .group(),
             ),
             documentation: markdown_documentation(doc),
@@ -717,6 +753,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
                 Deprecation::Deprecated { message } => message.to_string(),
             },
             opaque: false,
+            visibility_tag: visibility_tag(*publicity),
         }),
 
         _ => None,
@@ -726,28 +763,43 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
 fn constant<'a>(
     source_links: &SourceLinker,
     statement: &'a TypedDefinition,
+    visibility: DocVisibility,
 ) -> Option<Constant<'a>> {
     let mut formatter = format::Formatter::new();
     match statement {
         Definition::ModuleConstant(ModuleConstant {
-            publicity: Publicity::Public,
+            publicity,
             documentation: doc,
             name,
             value,
             location,
+            since,
             ..
-        }) => Some(Constant {
+        }) if visibility.includes(*publicity) => Some(Constant {
             name,
-            definition: print(formatter.docs_const_expr(Publicity::Public, name, value)),
+            definition: print(formatter.docs_const_expr(*publicity, name, value)),
             documentation: markdown_documentation(doc),
             text_documentation: text_documentation(doc),
             source_url: source_links.url(*location),
+            since: since.as_ref().map(EcoString::to_string).unwrap_or_default(),
+            visibility_tag: visibility_tag(*publicity),
         }),
 
         _ => None,
     }
 }
 
+/// The label shown next to the definitions that are not public, so that they
+/// can't be mistaken for part of the package's API.
+///
+fn visibility_tag(publicity: Publicity) -> &'static str {
+    match publicity {
+        Publicity::Public => "",
+        Publicity::Private => "private",
+        Publicity::Internal { .. } => "internal",
+    }
+}
+
 fn print(doc: pretty::Document<'_>) -> String {
     doc.to_pretty_string(MAX_COLUMNS)
 }
@@ -766,6 +818,8 @@ struct DocsFunction<'a> {
     text_documentation: String,
     source_url: String,
     deprecation_message: String,
+    since: String,
+    visibility_tag: &'static str,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -792,6 +846,7 @@ struct Type<'a> {
     source_url: String,
     deprecation_message: String,
     opaque: bool,
+    visibility_tag: &'static str,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -801,6 +856,8 @@ struct Constant<'a> {
     documentation: String,
     text_documentation: String,
     source_url: String,
+    since: String,
+    visibility_tag: &'static str,
 }
 
 #[derive(Template)]
@@ -4,7 +4,7 @@ use super::{SearchData, SearchItem, SearchItemType, SearchProgrammingLanguage};
 use crate::{
     build::{Mode, NullTelemetry, PackageCompiler, StaleTracker, TargetCodegenConfiguration},
     config::{DocsPage, PackageConfig, Repository},
-    docs::DocContext,
+    docs::{DocContext, DocVisibility},
     io::{FileSystemWriter, memory::InMemoryFileSystem},
     paths::ProjectPaths,
     uid::UniqueIdGenerator,
@@ -19,6 +19,7 @@ use serde_json::to_string as serde_to_string;
 #[derive(Default)]
 struct CompileWithMarkdownPagesOpts {
     hex_publish: Option<DocContext>,
+    visibility: Option<DocVisibility>,
 }
 
 fn compile_with_markdown_pages(
@@ -97,6 +98,7 @@ fn compile_with_markdown_pages(
         } else {
             DocContext::HexPublish
         },
+        opts.visibility.unwrap_or(DocVisibility::PublicOnly),
     )
     .into_iter()
     .filter(|file| file.path.extension() == Some("html"))
@@ -220,6 +222,53 @@ pub fn one() { 1 }
     insta::assert_snapshot!(compile(config, modules));
 }
 
+const PRIVATE_DEFINITIONS_MODULE: &str = r#"
+pub fn wibble() { wobble() }
+
+fn wobble() { 1 }
+
+@internal
+pub fn wubble() { 2 }
+
+const private_constant = 1
+
+type PrivateType {
+  PrivateType
+}
+
+type PrivateAlias = Int
+"#;
+
+#[test]
+fn private_definitions_are_not_included_by_default() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![("app.gleam", PRIVATE_DEFINITIONS_MODULE)];
+    let docs = compile(config, modules);
+    assert!(docs.contains("wibble"));
+    assert!(!docs.contains("wobble"));
+    assert!(!docs.contains("wubble"));
+    assert!(!docs.contains("private_constant"));
+    assert!(!docs.contains("PrivateType"));
+    assert!(!docs.contains("PrivateAlias"));
+}
+
+#[test]
+fn private_definitions_are_included_when_requested() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![("app.gleam", PRIVATE_DEFINITIONS_MODULE)];
+    insta::assert_snapshot!(compile_with_markdown_pages(
+        config,
+        modules,
+        vec![],
+        CompileWithMarkdownPagesOpts {
+            visibility: Some(DocVisibility::IncludePrivate),
+            ..Default::default()
+        }
+    ));
+}
+
 // https://github.com/gleam-lang/gleam/issues/2561
 #[test]
 fn discarded_arguments_are_not_shown() {
@@ -229,6 +278,53 @@ fn discarded_arguments_are_not_shown() {
     insta::assert_snapshot!(compile(config, modules));
 }
 
+#[test]
+fn since_attribute_is_shown() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![(
+        "app.gleam",
+        r#"
+/// The answer
+@since("1.2.0")
+pub fn wibble() -> Int { 42 }
+
+@since("0.3.0")
+pub const wobble = 1
+
+pub fn wubble() -> Int { 1 }
+"#,
+    )];
+    let docs = compile(config, modules);
+    assert!(docs.contains("<b>Since:</b> 1.2.0"));
+    assert!(docs.contains("<b>Since:</b> 0.3.0"));
+    assert_eq!(docs.matches("<b>Since:</b>").count(), 2);
+}
+
+#[test]
+fn doc_attribute_is_shown() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![(
+        "app.gleam",
+        r#"
+/// Wibble from a comment
+@doc("Wibble from an " <> "attribute")
+pub fn wibble() -> Int { 42 }
+
+@doc("Wobble from an attribute")
+pub type Wobble {
+  Wobble
+}
+"#,
+    )];
+    let docs = compile(config, modules);
+    assert!(docs.contains("Wibble from a comment"));
+    assert!(docs.contains("Wibble from an attribute"));
+    assert!(docs.contains("Wobble from an attribute"));
+    assert!(!docs.contains("@doc"));
+}
+
 // https://github.com/gleam-lang/gleam/issues/2631
 #[test]
 fn docs_of_a_type_constructor_are_not_used_by_the_following_function() {
@@ -490,7 +586,8 @@ pub fn one() {
         modules,
         pages,
         CompileWithMarkdownPagesOpts {
-            hex_publish: Some(DocContext::Build)
+            hex_publish: Some(DocContext::Build),
+            ..Default::default()
         }
     ));
 }
@@ -7,6 +7,7 @@ mod pattern;
 mod tests;
 
 use crate::build::Target;
+use crate::config::PanicHandler;
 use crate::strings::convert_string_escape_chars;
 use crate::type_::is_prelude_module;
 use crate::{
@@ -49,8 +50,14 @@ struct Env<'a> {
     src_path: &'a Utf8Path,
     project_root: &'a Utf8Path,
     line_numbers: &'a LineNumbers,
+    src: &'a str,
     needs_function_docs: bool,
     echo_used: bool,
+    memoize_used: bool,
+    should_panic_used: bool,
+    /// The function `panic` and `todo` errors are passed to before being
+    /// raised, if the package has one.
+    panic_handler: Option<&'a PanicHandler>,
     current_scope_vars: im::HashMap<String, usize>,
     erl_function_scope_vars: im::HashMap<String, usize>,
 }
@@ -62,6 +69,7 @@ impl<'env> Env<'env> {
         project_root: &'env Utf8Path,
         function: &'env str,
         line_numbers: &'env LineNumbers,
+        src: &'env str,
     ) -> Self {
         let vars: im::HashMap<_, _> = std::iter::once(("_".into(), 0)).collect();
         Self {
@@ -69,9 +77,13 @@ impl<'env> Env<'env> {
             erl_function_scope_vars: vars,
             needs_function_docs: false,
             echo_used: false,
+            memoize_used: false,
+            should_panic_used: false,
+            panic_handler: None,
             src_path,
             project_root,
             line_numbers,
+            src,
             function,
             module,
         }
@@ -163,18 +175,38 @@ pub fn record_definition(name: &str, fields: &[(&str, Arc<Type>)]) -> String {
     .to_pretty_string(MAX_COLUMNS)
 }
 
+/// Generates the Erlang for a module. If the package requires a minimum OTP
+/// release then the generated code may use features only available from that
+/// release onwards. If the package has a panic handler then the errors of its
+/// `panic` and `todo` expressions are passed to it before being raised.
+///
 pub fn module<'a>(
     module: &'a TypedModule,
     line_numbers: &'a LineNumbers,
+    src: &'a str,
     root: &'a Utf8Path,
+    minimum_otp_version: Option<u32>,
+    panic_handler: Option<&'a PanicHandler>,
 ) -> Result<String> {
-    Ok(module_document(module, line_numbers, root)?.to_pretty_string(MAX_COLUMNS))
+    let doc_attributes = DocAttributes::new(minimum_otp_version);
+    Ok(module_document(
+        module,
+        line_numbers,
+        src,
+        root,
+        doc_attributes,
+        panic_handler,
+    )?
+    .to_pretty_string(MAX_COLUMNS))
 }
 
 fn module_document<'a>(
     module: &'a TypedModule,
     line_numbers: &'a LineNumbers,
+    src: &'a str,
     root: &'a Utf8Path,
+    doc_attributes: DocAttributes,
+    panic_handler: Option<&'a PanicHandler>,
 ) -> Result<Document<'a>> {
     let mut exports = vec![];
     let mut type_defs = vec![];
@@ -203,6 +235,15 @@ fn module_document<'a>(
         );
     }
 
+    // Functions are exported sorted by name and arity rather than in the
+    // order they are defined in, so that the export list is stable and doesn't
+    // change as the functions of the module get reordered.
+    let exports = exports
+        .into_iter()
+        .sorted()
+        .map(|(name, arity)| atom_string(name).append("/").append(arity))
+        .collect_vec();
+
     let exports = match (!exports.is_empty(), !type_exports.is_empty()) {
         (false, false) => return Ok(header),
         (true, false) => "-export(["
@@ -240,6 +281,8 @@ fn module_document<'a>(
 
     let mut needs_function_docs = false;
     let mut echo_used = false;
+    let mut memoize_used = false;
+    let mut should_panic_used = false;
     let mut statements = Vec::with_capacity(module.definitions.len());
     for definition in module.definitions.iter() {
         if let Some((statement_document, env)) = module_statement(
@@ -247,27 +290,34 @@ fn module_document<'a>(
             &module.name,
             module.type_info.is_internal,
             line_numbers,
+            src,
             src_path_relative,
             root,
+            doc_attributes,
+            panic_handler,
         ) {
             needs_function_docs = needs_function_docs || env.needs_function_docs;
             echo_used = echo_used || env.echo_used;
+            memoize_used = memoize_used || env.memoize_used;
+            should_panic_used = should_panic_used || env.should_panic_used;
             statements.push(statement_document);
         }
     }
 
     let module_doc = if module.type_info.is_internal {
-        Some(hidden_module_doc().append(lines(2)))
+        Some(hidden_module_doc(doc_attributes).append(lines(2)))
     } else if module.documentation.is_empty() {
         None
     } else {
-        Some(module_doc(&module.documentation).append(lines(2)))
+        Some(module_doc(&module.documentation, doc_attributes).append(lines(2)))
     };
 
     // We're going to need the documentation directives if any of the module's
     // functions need it, or if the module has a module comment that we want to
     // include in the generated Erlang source, or if the module is internal.
-    let needs_doc_directive = needs_function_docs || module_doc.is_some();
+    // When the attributes are used directly there's no macro to define.
+    let needs_doc_directive =
+        (needs_function_docs || module_doc.is_some()) && doc_attributes == DocAttributes::Macros;
     let documentation_directive = if needs_doc_directive {
         "-if(?OTP_RELEASE >= 27).
 -define(MODULEDOC(Str), -moduledoc(Str)).
@@ -301,12 +351,28 @@ fn module_document<'a>(
         module
     };
 
+    let module = if memoize_used {
+        module
+            .append(lines(2))
+            .append(std::include_str!("../templates/memoize.erl").to_doc())
+    } else {
+        module
+    };
+
+    let module = if should_panic_used {
+        module
+            .append(lines(2))
+            .append(std::include_str!("../templates/should_panic.erl").to_doc())
+    } else {
+        module
+    };
+
     Ok(module.append(line()))
 }
 
 fn register_imports(
     s: &TypedDefinition,
-    exports: &mut Vec<Document<'_>>,
+    exports: &mut Vec<(String, usize)>,
     type_exports: &mut Vec<Document<'_>>,
     type_defs: &mut Vec<Document<'_>>,
     module_name: &str,
@@ -323,11 +389,7 @@ fn register_imports(
             // If the function isn't for this target then don't attempt to export it
             if implementations.supports(Target::Erlang) {
                 let function_name = escape_erlang_existing_name(name);
-                exports.push(
-                    atom_string(function_name.to_string())
-                        .append("/")
-                        .append(args.len()),
-                )
+                exports.push((function_name.to_string(), args.len()))
             }
         }
 
@@ -419,13 +481,17 @@ fn register_imports(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn module_statement<'a>(
     statement: &'a TypedDefinition,
     module: &'a str,
     is_internal_module: bool,
     line_numbers: &'a LineNumbers,
+    src: &'a str,
     src_path: &'a Utf8Path,
     project_root: &'a Utf8Path,
+    doc_attributes: DocAttributes,
+    panic_handler: Option<&'a PanicHandler>,
 ) -> Option<(Document<'a>, Env<'a>)> {
     match statement {
         Definition::TypeAlias(TypeAlias { .. })
@@ -438,23 +504,35 @@ fn module_statement<'a>(
             module,
             is_internal_module,
             line_numbers,
+            src,
             src_path,
             project_root,
+            doc_attributes,
+            panic_handler,
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn module_function<'a>(
     function: &'a TypedFunction,
     module: &'a str,
     is_internal_module: bool,
     line_numbers: &'a LineNumbers,
+    src: &'a str,
     src_path: &'a Utf8Path,
     project_root: &'a Utf8Path,
+    doc_attributes: DocAttributes,
+    panic_handler: Option<&'a PanicHandler>,
 ) -> Option<(Document<'a>, Env<'a>)> {
     // Private external functions don't need to render anything, the underlying
-    // Erlang implementation is used directly at the call site.
-    if function.external_erlang.is_some() && function.publicity.is_private() {
+    // Erlang implementation is used directly at the call site. Unless it takes
+    // its arguments in a different order, then the function reordering them
+    // is called instead.
+    if function.external_erlang.is_some()
+        && function.external_erlang_arguments.is_none()
+        && function.publicity.is_private()
+    {
         return None;
     }
 
@@ -471,7 +549,17 @@ fn module_function<'a>(
     let function_name = escape_erlang_existing_name(function_name);
     let file_attribute = file_attribute(src_path, function, line_numbers);
 
-    let mut env = Env::new(module, src_path, project_root, function_name, line_numbers);
+    let mut env = Env::new(
+        module,
+        src_path,
+        project_root,
+        function_name,
+        line_numbers,
+        src,
+    );
+    // The handler can't have its own errors passed back to it, as they would
+    // be raised by calling it again.
+    env.panic_handler = panic_handler.filter(|handler| handler.module != module);
     let var_usages = collect_type_var_usages(
         HashMap::new(),
         std::iter::once(&function.return_type).chain(function.arguments.iter().map(|a| &a.type_)),
@@ -481,15 +569,32 @@ fn module_function<'a>(
         .arguments
         .iter()
         .map(|a| type_printer.print(&a.type_));
-    let return_spec = type_printer.print(&function.return_type);
+    // A test expected to panic returns `Nil` once it has, rather than the
+    // value returned by its body.
+    let return_spec = if function.should_panic.is_some() {
+        "nil".to_doc()
+    } else {
+        type_printer.print(&function.return_type)
+    };
 
     let spec = fun_spec(function_name, args_spec, return_spec);
-    let arguments = if function.external_erlang.is_some() {
-        external_fun_args(&function.arguments, &mut env)
+    let (arguments, external_arguments) = if function.external_erlang.is_some() {
+        let names = external_fun_args(&function.arguments, &mut env);
+        let external_arguments = match &function.external_erlang_arguments {
+            Some(external) => external.reorder(&function.arguments, &names),
+            None => names.clone(),
+        };
+        (wrap_args(names), wrap_args(external_arguments))
     } else {
-        fun_args(&function.arguments, &mut env)
+        (fun_args(&function.arguments, &mut env), nil())
     };
 
+    // The key is built before generating the body, as the arguments could be
+    // shadowed by variables with the same name in there.
+    let memoize_key = function
+        .memoize
+        .then(|| memoize_key(function_name, &function.arguments, &mut env));
+
     let body = function
         .external_erlang
         .as_ref()
@@ -498,18 +603,28 @@ fn module_function<'a>(
                 atom(module),
                 ":",
                 atom(escape_erlang_existing_name(function)),
-                arguments.clone()
+                external_arguments
             ]
         })
         .unwrap_or_else(|| statement_sequence(&function.body, &mut env));
 
+    let body = match memoize_key {
+        Some(key) => memoized(key, body, &mut env),
+        None => body,
+    };
+
+    let body = match &function.should_panic {
+        Some(should_panic) => expecting_panic(should_panic, function.location, body, &mut env),
+        None => body,
+    };
+
     let attributes = file_attribute;
     let attributes = if is_internal_module || function.publicity.is_internal() {
         // If a function is marked as internal or comes from an internal module
         // we want to hide its documentation in the Erlang shell!
         // So the doc directive will look like this: `-doc(false).`
         env.needs_function_docs = true;
-        docvec![attributes, line(), hidden_function_doc()]
+        docvec![attributes, line(), hidden_function_doc(doc_attributes)]
     } else {
         match &function.documentation {
             Some((_, documentation)) => {
@@ -519,7 +634,7 @@ fn module_function<'a>(
                     .split('\n')
                     .map(EcoString::from)
                     .collect_vec();
-                docvec![attributes, line(), function_doc(&doc_lines)]
+                docvec![attributes, line(), function_doc(&doc_lines, doc_attributes)]
             }
             _ => attributes,
         }
@@ -550,6 +665,29 @@ fn file_attribute<'a>(
     docvec!["-file(\"", path, "\", ", line, ")."]
 }
 
+/// The first OTP release with the `-moduledoc` and `-doc` attributes.
+const DOC_ATTRIBUTES_OTP_RELEASE: u32 = 27;
+
+/// How the documentation attributes are written in the generated Erlang.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocAttributes {
+    /// Using the `?MODULEDOC` and `?DOC` macros, which do nothing on OTP
+    /// releases that don't have the attributes.
+    Macros,
+    /// Using the `-moduledoc` and `-doc` attributes directly, as the package
+    /// requires an OTP release that has them.
+    Direct,
+}
+
+impl DocAttributes {
+    fn new(minimum_otp_version: Option<u32>) -> Self {
+        match minimum_otp_version {
+            Some(version) if version >= DOC_ATTRIBUTES_OTP_RELEASE => Self::Direct,
+            Some(_) | None => Self::Macros,
+        }
+    }
+}
+
 enum DocCommentKind {
     Module,
     Function,
@@ -560,26 +698,44 @@ enum DocCommentContent<'a> {
     False,
 }
 
-fn hidden_module_doc<'a>() -> Document<'a> {
-    doc_attribute(DocCommentKind::Module, DocCommentContent::False)
+fn hidden_module_doc<'a>(attributes: DocAttributes) -> Document<'a> {
+    doc_attribute(DocCommentKind::Module, DocCommentContent::False, attributes)
 }
 
-fn module_doc<'a>(content: &Vec<EcoString>) -> Document<'a> {
-    doc_attribute(DocCommentKind::Module, DocCommentContent::String(content))
+fn module_doc<'a>(content: &Vec<EcoString>, attributes: DocAttributes) -> Document<'a> {
+    doc_attribute(
+        DocCommentKind::Module,
+        DocCommentContent::String(content),
+        attributes,
+    )
 }
 
-fn hidden_function_doc<'a>() -> Document<'a> {
-    doc_attribute(DocCommentKind::Function, DocCommentContent::False)
+fn hidden_function_doc<'a>(attributes: DocAttributes) -> Document<'a> {
+    doc_attribute(
+        DocCommentKind::Function,
+        DocCommentContent::False,
+        attributes,
+    )
 }
 
-fn function_doc<'a>(content: &Vec<EcoString>) -> Document<'a> {
-    doc_attribute(DocCommentKind::Function, DocCommentContent::String(content))
+fn function_doc<'a>(content: &Vec<EcoString>, attributes: DocAttributes) -> Document<'a> {
+    doc_attribute(
+        DocCommentKind::Function,
+        DocCommentContent::String(content),
+        attributes,
+    )
 }
 
-fn doc_attribute<'a>(kind: DocCommentKind, content: DocCommentContent<'_>) -> Document<'a> {
-    let prefix = match kind {
-        DocCommentKind::Module => "?MODULEDOC",
-        DocCommentKind::Function => "?DOC",
+fn doc_attribute<'a>(
+    kind: DocCommentKind,
+    content: DocCommentContent<'_>,
+    attributes: DocAttributes,
+) -> Document<'a> {
+    let prefix = match (kind, attributes) {
+        (DocCommentKind::Module, DocAttributes::Macros) => "?MODULEDOC",
+        (DocCommentKind::Function, DocAttributes::Macros) => "?DOC",
+        (DocCommentKind::Module, DocAttributes::Direct) => "-moduledoc",
+        (DocCommentKind::Function, DocAttributes::Direct) => "-doc",
     };
 
     match content {
@@ -603,20 +759,22 @@ fn doc_attribute<'a>(kind: DocCommentKind, content: DocCommentContent<'_>) -> Do
     }
 }
 
-fn external_fun_args<'a>(args: &'a [TypedArg], env: &mut Env<'a>) -> Document<'a> {
-    wrap_args(args.iter().map(|a| {
-        let name = match &a.names {
-            ArgNames::Discard { name, .. }
-            | ArgNames::LabelledDiscard { name, .. }
-            | ArgNames::Named { name, .. }
-            | ArgNames::NamedLabelled { name, .. } => name,
-        };
-        if name.chars().all(|c| c == '_') {
-            env.next_local_var_name("argument")
-        } else {
-            env.next_local_var_name(name)
-        }
-    }))
+fn external_fun_args<'a>(args: &'a [TypedArg], env: &mut Env<'a>) -> Vec<Document<'a>> {
+    args.iter()
+        .map(|a| {
+            let name = match &a.names {
+                ArgNames::Discard { name, .. }
+                | ArgNames::LabelledDiscard { name, .. }
+                | ArgNames::Named { name, .. }
+                | ArgNames::NamedLabelled { name, .. } => name,
+            };
+            if name.chars().all(|c| c == '_') {
+                env.next_local_var_name("argument")
+            } else {
+                env.next_local_var_name(name)
+            }
+        })
+        .collect()
 }
 
 fn fun_args<'a>(args: &'a [TypedArg], env: &mut Env<'a>) -> Document<'a> {
@@ -628,6 +786,71 @@ fn fun_args<'a>(args: &'a [TypedArg], env: &mut Env<'a>) -> Document<'a> {
     }))
 }
 
+/// The key used to cache the results of a memoized function: a tuple with the
+/// name of the function and the arguments it's called with. Discarded
+/// arguments can't change the returned value so they're not part of the key.
+///
+fn memoize_key<'a>(
+    function_name: &'a str,
+    args: &'a [TypedArg],
+    env: &mut Env<'a>,
+) -> Document<'a> {
+    let arguments = args
+        .iter()
+        .filter_map(|arg| arg.get_variable_name())
+        .map(|name| env.local_var_name(name))
+        .collect_vec();
+    tuple(std::iter::once(atom(function_name)).chain(arguments))
+}
+
+/// Wraps the body of a memoized function so that it's only evaluated the
+/// first time the function is called with a given key. The cached values are
+/// stored in the process dictionary by the `memoize@lookup` helper.
+///
+fn memoized<'a>(key: Document<'a>, body: Document<'a>, env: &mut Env<'a>) -> Document<'a> {
+    env.memoize_used = true;
+    docvec![
+        "memoize@lookup(",
+        key,
+        ", fun() ->",
+        line().append(body).nest(INDENT),
+        line(),
+        "end)"
+    ]
+}
+
+/// Wraps the body of a test function annotated with `@test(should_panic)` so
+/// that it panics if the body doesn't panic, or panics with a message that
+/// doesn't contain the expected one. This is checked by the
+/// `should_panic@run` helper.
+///
+fn expecting_panic<'a>(
+    should_panic: &'a ShouldPanic,
+    location: SrcSpan,
+    body: Document<'a>,
+    env: &mut Env<'a>,
+) -> Document<'a> {
+    env.should_panic_used = true;
+    let message = match &should_panic.message {
+        Some(message) => tuple(["some".to_doc(), string(message)]),
+        None => "none".to_doc(),
+    };
+    docvec![
+        "should_panic@run(fun() ->",
+        line().append(body).nest(INDENT),
+        line(),
+        "end, ",
+        message,
+        ", ",
+        env.module.to_doc().surround("<<\"", "\"/utf8>>"),
+        ", ",
+        string(env.function),
+        ", ",
+        env.line_numbers.line_number(location.start),
+        ")"
+    ]
+}
+
 fn wrap_args<'a, I>(args: I) -> Document<'a>
 where
     I: IntoIterator<Item = Document<'a>>,
@@ -889,9 +1112,33 @@ fn statement<'a>(statement: &'a TypedStatement, env: &mut Env<'a>) -> Document<'
         Statement::Expression(e) => expr(e, env),
         Statement::Assignment(a) => assignment(a, env),
         Statement::Use(use_) => expr(&use_.call, env),
+        Statement::Assert(assert) => self::assert(assert, env),
     }
 }
 
+fn assert<'a>(assert: &'a TypedAssert, env: &mut Env<'a>) -> Document<'a> {
+    let message = docvec!["<<\"", assert.failure_message(env.src), "\"/utf8>>"];
+    let value = maybe_block_expr(&assert.value, env);
+    let clauses = docvec![
+        "true -> nil;",
+        line(),
+        "false ->",
+        docvec![
+            line(),
+            erlang_error("assert", &message, assert.location, vec![], env)
+        ]
+        .nest(INDENT)
+    ];
+    docvec![
+        "case ",
+        value,
+        " of",
+        docvec![line(), clauses].nest(INDENT),
+        line(),
+        "end"
+    ]
+}
+
 fn expr_segment<'a>(
     value: &'a TypedExpr,
     options: &'a [BitArrayOption<TypedExpr>],
@@ -1902,7 +2149,7 @@ fn todo<'a>(message: Option<&'a TypedExpr>, location: SrcSpan, env: &mut Env<'a>
         Some(m) => expr(m, env),
         None => string("`todo` expression evaluated. This code has not yet been implemented."),
     };
-    erlang_error("todo", &message, location, vec![], env)
+    panic_error("todo", &message, location, env)
 }
 
 fn panic<'a>(location: SrcSpan, message: Option<&'a TypedExpr>, env: &mut Env<'a>) -> Document<'a> {
@@ -1910,7 +2157,29 @@ fn panic<'a>(location: SrcSpan, message: Option<&'a TypedExpr>, env: &mut Env<'a
         Some(m) => expr(m, env),
         None => string("`panic` expression evaluated."),
     };
-    erlang_error("panic", &message, location, vec![], env)
+    panic_error("panic", &message, location, env)
+}
+
+/// Raises the error of a `panic` or `todo` expression, after passing it to
+/// the package's panic handler if it has one.
+///
+fn panic_error<'a>(
+    name: &'a str,
+    message: &Document<'a>,
+    location: SrcSpan,
+    env: &Env<'a>,
+) -> Document<'a> {
+    let error = error_map(name, message, location, vec![], env);
+    let error = match env.panic_handler {
+        None => error,
+        Some(PanicHandler { module, function }) => docvec![
+            module_name_atom(module),
+            ":",
+            atom(function),
+            wrap_args([error])
+        ],
+    };
+    docvec!["erlang:error", wrap_args([error.group()])]
 }
 
 fn echo<'a>(body: Document<'a>, location: &SrcSpan, env: &mut Env<'a>) -> Document<'a> {
@@ -1941,6 +2210,17 @@ fn erlang_error<'a>(
     location: SrcSpan,
     fields: Vec<(&'a str, Document<'a>)>,
     env: &Env<'a>,
+) -> Document<'a> {
+    let error = error_map(name, message, location, fields, env);
+    docvec!["erlang:error", wrap_args([error.group()])]
+}
+
+fn error_map<'a>(
+    name: &'a str,
+    message: &Document<'a>,
+    location: SrcSpan,
+    fields: Vec<(&'a str, Document<'a>)>,
+    env: &Env<'a>,
 ) -> Document<'a> {
     let mut fields_doc = docvec![
         "gleam_error => ",
@@ -1972,11 +2252,9 @@ fn erlang_error<'a>(
         .append(line())
         .append("line => ")
         .append(env.line_numbers.line_number(location.start));
-    let error = "#{"
-        .to_doc()
+    "#{".to_doc()
         .append(fields_doc.group().nest(INDENT))
-        .append("}");
-    docvec!["erlang:error", wrap_args([error.group()])]
+        .append("}")
 }
 
 fn expr<'a>(expression: &'a TypedExpr, env: &mut Env<'a>) -> Document<'a> {
@@ -147,6 +147,7 @@ fn print<'a>(
         }
 
         Pattern::Invalid { .. } => panic!("invalid patterns should not reach code generation"),
+        Pattern::Constant { .. } => panic!("constant patterns should not reach code generation"),
     }
 }
 
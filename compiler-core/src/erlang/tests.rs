@@ -4,7 +4,7 @@ use camino::Utf8PathBuf;
 
 use crate::analyse::TargetSupport;
 use crate::build;
-use crate::config::PackageConfig;
+use crate::config::{PackageConfig, PanicHandler};
 use crate::type_::PRELUDE_MODULE_NAME;
 use crate::warning::WarningEmitter;
 use crate::{
@@ -16,23 +16,28 @@ use crate::{
 };
 use camino::Utf8Path;
 
+mod assert;
 mod bit_arrays;
 mod case;
 mod conditional_compilation;
 mod consts;
 mod custom_types;
+mod derive_json;
 mod documentation;
 mod echo;
 mod external_fn;
 mod functions;
 mod guards;
 mod let_assert;
+mod list_comprehensions;
+mod memoize;
 mod numbers;
 mod panic;
 mod patterns;
 mod pipes;
 mod records;
 mod reserved;
+mod should_panic;
 mod strings;
 mod todo;
 mod type_params;
@@ -40,6 +45,48 @@ mod use_;
 mod variables;
 
 pub fn compile_test_project(src: &str, src_path: &str, dep: Option<(&str, &str, &str)>) -> String {
+    compile_test_project_for_otp(src, src_path, dep, None)
+}
+
+pub fn compile_test_project_for_otp(
+    src: &str,
+    src_path: &str,
+    dep: Option<(&str, &str, &str)>,
+    minimum_otp_version: Option<u32>,
+) -> String {
+    compile_test_project_with_options(src, src_path, dep, minimum_otp_version, None)
+}
+
+pub fn compile_test_project_with_options(
+    src: &str,
+    src_path: &str,
+    dep: Option<(&str, &str, &str)>,
+    minimum_otp_version: Option<u32>,
+    panic_handler: Option<&PanicHandler>,
+) -> String {
+    let built_module = analyse_test_project(src, src_path, dep);
+    let root = Utf8Path::new("/root");
+    let line_numbers = LineNumbers::new(src);
+    module(
+        &built_module.ast,
+        &line_numbers,
+        src,
+        root,
+        minimum_otp_version,
+        panic_handler,
+    )
+    .unwrap()
+    .replace(
+        std::include_str!("../../templates/echo.erl"),
+        "% ...omitted code from `templates/echo.erl`...",
+    )
+}
+
+fn analyse_test_project(
+    src: &str,
+    src_path: &str,
+    dep: Option<(&str, &str, &str)>,
+) -> build::Module {
     let mut modules = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     // DUPE: preludeinsertion
@@ -114,16 +161,20 @@ pub fn compile_test_project(src: &str, src_path: &str, dep: Option<(&str, &str,
         extra: parsed.extra,
         dependencies: vec![],
     };
-    let root = Utf8Path::new("/root");
     built_module.attach_doc_and_module_comments();
+    built_module
+}
 
-    let line_numbers = LineNumbers::new(src);
-    module(&built_module.ast, &line_numbers, root)
-        .unwrap()
-        .replace(
-            std::include_str!("../../templates/echo.erl"),
-            "% ...omitted code from `templates/echo.erl`...",
-        )
+/// Generates the `.hrl` record headers for a module, each one preceded by the
+/// name of the file it would be written to.
+///
+pub fn compile_test_project_records(src: &str) -> String {
+    let built_module = analyse_test_project(src, "/root/project/test/my/mod.gleam", None);
+    crate::erlang::records(&built_module.ast)
+        .into_iter()
+        .map(|(name, text)| format!("// my@mod_{name}.hrl\n{text}"))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[macro_export]
@@ -141,6 +192,39 @@ macro_rules! assert_erl {
         insta::assert_snapshot!(insta::internals::AutoName, output, $src);
     }};
 
+    (minimum_otp_version: $version:expr, $src:expr $(,)?) => {{
+        let compiled = $crate::erlang::tests::compile_test_project_for_otp(
+            $src,
+            "/root/project/test/my/mod.gleam",
+            None,
+            Some($version),
+        );
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED ERLANG\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
+    (panic_handler: ($module:expr, $function:expr), $src:expr $(,)?) => {{
+        let handler = $crate::config::PanicHandler {
+            module: $module.into(),
+            function: $function.into(),
+        };
+        let compiled = $crate::erlang::tests::compile_test_project_with_options(
+            $src,
+            "/root/project/test/my/mod.gleam",
+            None,
+            None,
+            Some(&handler),
+        );
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED ERLANG\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
     ($src:expr $(,)?) => {{
         let compiled = $crate::erlang::tests::compile_test_project(
             $src,
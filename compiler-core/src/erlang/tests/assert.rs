@@ -0,0 +1,67 @@
+use crate::assert_erl;
+
+#[test]
+fn assert_variable() {
+    assert_erl!(
+        r#"
+pub fn main() {
+  let x = True
+  assert x
+}
+"#
+    );
+}
+
+#[test]
+fn assert_binary_operation() {
+    assert_erl!(
+        r#"
+pub fn main() {
+  let x = 1
+  assert x > 0
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn assert_function_call() {
+    assert_erl!(
+        r#"
+fn is_even(x) {
+  x % 2 == 0
+}
+
+pub fn main() {
+  assert is_even(3)
+}
+"#
+    );
+}
+
+#[test]
+fn assert_message_is_escaped() {
+    assert_erl!(
+        r#"
+pub fn main() {
+  assert "wibble\"wobble" == "wibble\\wobble"
+}
+"#
+    );
+}
+
+#[test]
+fn assert_in_block() {
+    assert_erl!(
+        r#"
+pub fn main() {
+  let x = {
+    assert 1 == 1
+    2
+  }
+  x
+}
+"#
+    );
+}
@@ -204,3 +204,60 @@ pub fn main() {
 }"#
     );
 }
+
+#[test]
+fn header_followed_by_variable_length_payload() {
+    assert_erl!(
+        r#"
+pub fn parse(packet: BitArray) -> Result(#(Int, BitArray, BitArray), Nil) {
+  case packet {
+    <<version:8, length:8, payload:bytes-size(length), rest:bytes>> ->
+      Ok(#(version, payload, rest))
+    _ -> Error(Nil)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn header_followed_by_rest_of_bits() {
+    assert_erl!(
+        r#"
+pub fn parse(packet: BitArray) -> Result(#(Int, BitArray), Nil) {
+  case packet {
+    <<flags:4, rest:bits>> -> Ok(#(flags, rest))
+    _ -> Error(Nil)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn length_prefixed_payloads() {
+    assert_erl!(
+        r#"
+pub fn parse(packet: BitArray) -> Result(#(BitArray, BitArray), Nil) {
+  case packet {
+    <<a:8, b:8, first:bytes-size(a), second:bytes-size(b)>> -> Ok(#(first, second))
+    _ -> Error(Nil)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn length_prefixed_payload_with_unit() {
+    assert_erl!(
+        r#"
+pub fn parse(packet: BitArray) -> Result(#(BitArray, BitArray), Nil) {
+  case packet {
+    <<length:8, payload:bits-size(length)-unit(8), rest:bits>> -> Ok(#(payload, rest))
+    _ -> Error(Nil)
+  }
+}
+"#
+    );
+}
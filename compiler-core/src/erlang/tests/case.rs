@@ -101,3 +101,25 @@ pub fn main() {
 "#,
     );
 }
+
+#[test]
+fn imported_constant_pattern() {
+    assert_erl!(
+        (
+            "thepackage",
+            "numbers",
+            r#"pub const zero = 0
+pub const name = "Lucy""#
+        ),
+        r#"
+import numbers
+
+pub fn main(x, y) {
+  case x, y {
+    numbers.zero, numbers.name -> True
+    _, _ -> False
+  }
+}
+"#
+    );
+}
@@ -0,0 +1,26 @@
+use crate::assert_erl;
+
+#[test]
+fn record_deriving_json() {
+    assert_erl!(
+        r#"
+@derive(json)
+pub type Setting {
+  Setting(name: String, enabled: Bool)
+}
+"#
+    );
+}
+
+#[test]
+fn type_deriving_json_with_a_list_of_ints() {
+    assert_erl!(
+        r#"
+@derive(json)
+pub type Scores {
+  Scores(List(Int))
+  NoScores
+}
+"#
+    );
+}
@@ -89,3 +89,40 @@ fn internal_function_has_no_documentation() {
 pub fn main() { 1 }"#
     );
 }
+
+#[test]
+fn documentation_attributes_are_used_directly_on_otp_27() {
+    assert_erl!(
+        minimum_otp_version: 27,
+        r#"
+//// Module doc!
+
+/// Function doc!
+pub fn documented() { 1 }
+
+/// hidden!
+@internal
+pub fn internal() { 2 }"#
+    );
+}
+
+#[test]
+fn documentation_attributes_use_macros_before_otp_27() {
+    assert_erl!(
+        minimum_otp_version: 26,
+        r#"
+//// Module doc!
+
+/// Function doc!
+pub fn documented() { 1 }"#
+    );
+}
+
+#[test]
+fn no_documentation_attributes_on_otp_27() {
+    assert_erl!(
+        minimum_otp_version: 27,
+        r#"
+    pub fn undocumented() { 1 }"#
+    );
+}
@@ -124,6 +124,22 @@ pub fn one(x: Int) -> Int {
     );
 }
 
+#[test]
+fn erlang_external_with_gleam_fallback() {
+    assert_erl!(
+        r#"
+@external(erlang, "math", "double")
+pub fn double(x: Int) -> Int {
+  x * 2
+}
+
+pub fn main() {
+  double(1)
+}
+"#
+    );
+}
+
 #[test]
 fn erlang_and_javascript() {
     assert_erl!(
@@ -378,3 +394,88 @@ pub fn woo(__: a, _two: b) -> Nil
 "#
     );
 }
+
+#[test]
+fn external_with_reordered_arguments() {
+    assert_erl!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, initial, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+
+pub fn main() {
+  fold([1, 2], 0, fn(x, acc) { x + acc })
+}
+"#
+    );
+}
+
+#[test]
+fn private_external_with_reordered_arguments() {
+    assert_erl!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, initial, list])
+fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+
+pub fn main() {
+  fold([1, 2], 0, fn(x, acc) { x + acc })
+}
+"#
+    );
+}
+
+#[test]
+fn imported_external_with_reordered_arguments() {
+    assert_erl!(
+        (
+            "package",
+            "wibble",
+            r#"
+@external(erlang, "lists", "foldl", [fun, initial, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+        ),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.fold([1, 2], 0, fn(x, acc) { x + acc })
+}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_external_function_name() {
+    assert_erl!(
+        r#"
+@external(erlang, "erlang", "type")
+pub fn `type`(value: a) -> String
+
+pub fn main() {
+  `type`(1)
+}
+"#
+    );
+}
+
+#[test]
+fn imported_raw_identifier_external_function_name() {
+    assert_erl!(
+        (
+            "package",
+            "wibble",
+            r#"
+@external(erlang, "erlang", "case")
+pub fn `case`(value: a) -> String
+"#
+        ),
+        r#"
+import wibble.{`case`}
+
+pub fn main() {
+  wibble.`case`(1)
+  `case`(2)
+}
+"#
+    );
+}
@@ -137,3 +137,46 @@ pub fn main() {
 "
     );
 }
+
+#[test]
+fn exports_are_sorted_by_name() {
+    assert_erl!(
+        "
+pub fn wobble(a, b) {
+  wibble(a) + b
+}
+
+pub fn wibble(a) {
+  a
+}
+
+pub fn main() {
+  wobble(1, 2)
+}
+
+pub fn apply(f, a) {
+  f(a)
+}
+
+fn private() {
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn self_qualified_private_function() {
+    assert_erl!(
+        r#"
+fn helper(x) {
+  x
+}
+
+pub fn main() {
+  let helper = self.helper
+  self.helper(helper(1))
+}
+"#
+    );
+}
@@ -79,3 +79,17 @@ pub fn expect(value, message) {
 // }"#
 //     );
 // }
+
+// The value that failed to match is included in the error so that the crash
+// report can show what the `Error` contained.
+#[test]
+fn unmatched_error_value_is_in_the_error_payload() {
+    assert_erl!(
+        r#"
+pub fn unwrap(result: Result(Int, String)) {
+  let assert Ok(x) = result
+  x
+}
+"#
+    );
+}
@@ -0,0 +1,23 @@
+use crate::assert_erl;
+
+#[test]
+fn list_comprehension_is_compiled_to_list_module_calls() {
+    assert_erl!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            r#"
+pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+pub fn filter(list: List(a), f: fn(a) -> Bool) -> List(a) { todo }
+pub fn flat_map(list: List(a), f: fn(a) -> List(b)) -> List(b) { todo }
+"#
+        ),
+        r#"
+import gleam/list
+
+pub fn main(xs, ys) {
+  [#(x, y) | x <- xs, x > 0, y <- ys]
+}
+"#
+    );
+}
@@ -0,0 +1,73 @@
+use crate::assert_erl;
+
+#[test]
+fn memoized_function() {
+    assert_erl!(
+        r#"
+@memoize
+pub fn fib(n: Int) -> Int {
+  case n {
+    0 | 1 -> n
+    _ -> fib(n - 1) + fib(n - 2)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_discarded_argument() {
+    assert_erl!(
+        r#"
+@memoize
+pub fn wibble(a: Int, _b: String, c: Bool) -> Int {
+  case c {
+    True -> a
+    False -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_shadowing_its_arguments() {
+    assert_erl!(
+        r#"
+@memoize
+pub fn wibble(a: Int) -> Int {
+  let a = a + 1
+  let a = a * 2
+  a
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_no_arguments() {
+    assert_erl!(
+        r#"
+@memoize
+pub fn wibble() -> Int {
+  1 + 2
+}
+"#
+    );
+}
+
+#[test]
+fn calls_to_memoized_function_are_not_changed() {
+    assert_erl!(
+        r#"
+@memoize
+fn wibble(a: Int) -> Int {
+  a
+}
+
+pub fn main() {
+  wibble(1) + wibble(2)
+}
+"#
+    );
+}
@@ -61,3 +61,52 @@ fn piped_chain() {
     "#
     );
 }
+
+#[test]
+fn panic_with_panic_handler() {
+    assert_erl!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main() {
+  panic as "oh no"
+}
+"#
+    );
+}
+
+#[test]
+fn todo_with_panic_handler() {
+    assert_erl!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn let_assert_does_not_use_panic_handler() {
+    assert_erl!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main(x) {
+  let assert Ok(y) = x
+  y
+}
+"#
+    );
+}
+
+#[test]
+fn panic_in_panic_handler_module_does_not_use_panic_handler() {
+    assert_erl!(
+        panic_handler: ("my/mod", "handle"),
+        r#"
+pub fn handle(error) {
+  panic
+}
+"#
+    );
+}
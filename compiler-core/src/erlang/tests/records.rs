@@ -83,6 +83,29 @@ fn long_definition_formatting() {
     ));
 }
 
+#[test]
+fn module_record_headers() {
+    // Headers are only generated for public constructors where every field is
+    // labelled, with names matching the runtime representation
+    insta::assert_snapshot!(tests::compile_test_project_records(
+        r#"
+pub type PetCat(a) {
+  PetCat(name: String, is_cute: Bool, receive: List(a))
+  StrayCat(String)
+  NoCat
+}
+
+pub type HTTPRequest {
+  HTTPRequest(method: String, body: BitArray)
+}
+
+type Private {
+  Private(value: Int)
+}
+"#
+    ));
+}
+
 #[test]
 fn record_accessors() {
     // We can use record accessors for types with only one constructor
@@ -400,3 +423,17 @@ pub fn main(a: A) {
 }"
     )
 }
+
+#[test]
+fn raw_identifier_labels() {
+    assert_erl!(
+        "pub type Token {
+  Token(`type`: String, `case`: Int)
+}
+
+pub fn main(token: Token) {
+  let Token(`type`:, ..) = token
+  #(`type`, token.`case`, Token(..token, `case`: 1))
+}"
+    );
+}
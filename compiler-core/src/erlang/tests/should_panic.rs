@@ -0,0 +1,57 @@
+use crate::assert_erl;
+
+#[test]
+fn expected_to_panic() {
+    assert_erl!(
+        r#"
+@test(should_panic)
+pub fn divide_test() {
+  panic as "Division by zero"
+}
+"#
+    );
+}
+
+#[test]
+fn expected_to_panic_with_message() {
+    assert_erl!(
+        r#"
+@test(should_panic, "by zero")
+pub fn divide_test() {
+  let x = 1
+  case x {
+    0 -> x
+    _ -> panic as "Division by zero"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn expected_to_panic_with_escaped_message() {
+    assert_erl!(
+        r#"
+@test(should_panic, "\"quoted\"")
+pub fn wibble_test() {
+  panic as "\"quoted\""
+}
+"#
+    );
+}
+
+#[test]
+fn private_test_expected_to_panic() {
+    assert_erl!(
+        r#"
+@test(should_panic)
+fn wibble_test() {
+  todo
+}
+
+pub fn main() {
+  wibble_test()
+}
+"#
+    );
+}
@@ -101,6 +101,17 @@ pub fn unicode_escape_sequence_6_digits() -> String {
     );
 }
 
+#[test]
+fn unicode_escape_sequences_around_surrogates() {
+    assert_erl!(
+        r#"
+pub fn main() -> String {
+  "\u{d7ff} \u{e000} \u{10ffff}"
+}
+"#,
+    );
+}
+
 #[test]
 fn ascii_as_unicode_escape_sequence() {
     assert_erl!(
@@ -3,12 +3,16 @@ use crate::build::{Outcome, Runtime, Target};
 use crate::diagnostic::{Diagnostic, ExtraLabel, Label, Location};
 use crate::type_::collapse_links;
 use crate::type_::error::{
-    MissingAnnotation, ModuleValueUsageContext, Named, UnknownField, UnknownTypeHint,
-    UnsafeRecordUpdateReason,
+    ExternalArgumentsProblem, MissingAnnotation, ModuleValueUsageContext, Named, UnknownField,
+    UnknownTypeHint, UnsafeRecordUpdateReason, UnsupportedCapabilityProblem,
 };
 use crate::type_::printer::{Names, Printer};
 use crate::type_::{FieldAccessUsage, error::PatternMatchKind};
-use crate::{ast::BinOp, parse::error::ParseErrorType, type_::Type};
+use crate::{
+    ast::{BinOp, Capability},
+    parse::error::ParseErrorType,
+    type_::Type,
+};
 use crate::{bit_array, diagnostic::Level, javascript, type_::UnifyErrorSituation};
 use ecow::EcoString;
 use heck::{ToSnakeCase, ToTitleCase, ToUpperCamelCase};
@@ -108,6 +112,9 @@ pub enum Error {
 
     #[error("cyclical module imports")]
     ImportCycle {
+        /// The modules making up the cycle, along with the location of their
+        /// import of the module before them. The first module imports the
+        /// last one.
         modules: Vec1<(EcoString, ImportCycleLocationDetails)>,
     },
 
@@ -274,6 +281,15 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("Opening docs at {path} failed: {error}")]
     FailedToOpenDocs { path: Utf8PathBuf, error: String },
 
+    #[error("{path} is not a valid package interface: {error}")]
+    InvalidPackageInterface { path: Utf8PathBuf, error: String },
+
+    #[error("{path} is not a valid module interface file: {error}")]
+    InvalidModuleInterfaceFile { path: Utf8PathBuf, error: String },
+
+    #[error("{path} is not a CommonJS module and so cannot be bundled")]
+    CannotBundleJavaScriptModule { path: Utf8PathBuf },
+
     #[error(
         "The package {package} requires a Gleam version satisfying \
 {required_version} and you are using v{gleam_version}"
@@ -321,6 +337,9 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
 
     #[error("Failed to decrypt data")]
     FailedToDecrypt { detail: String },
+
+    #[error("Unknown diagnostic code {code}")]
+    UnknownDiagnosticCode { code: EcoString },
 }
 
 /// This is to make clippy happy and not make the error variant too big by
@@ -807,6 +826,13 @@ fn did_you_mean(name: &str, options: &[EcoString]) -> Option<String> {
             .map(|option| format!("Did you mean `{option}`?"));
     }
 
+    did_you_mean_close_match(name, options)
+}
+
+/// Like `did_you_mean`, but an option is only ever suggested if it is close
+/// enough to the given name, even if it's the only one.
+///
+fn did_you_mean_close_match(name: &str, options: &[EcoString]) -> Option<String> {
     // Check for case-insensitive matches.
     // This solves the comparison to small and single character terms,
     // such as the test on `type_vars_must_be_declared`.
@@ -932,7 +958,7 @@ forward slash and must not end with a slash."
                 location: None,
                 hint: Some(format!(
                     "Add a public `main` function to \
-to `src/{module}.gleam`."
+`src/{module}.gleam`."
                 )),
             }],
 
@@ -1487,6 +1513,16 @@ The error from the encryption library was:
                 }]
             }
 
+            Error::UnknownDiagnosticCode { code } => vec![Diagnostic {
+                title: "Unknown diagnostic code".into(),
+                text: format!("There is no explanation for the diagnostic code `{code}`."),
+                hint: Some(
+                    "Diagnostic codes are a letter followed by four digits, like `E0001`.".into(),
+                ),
+                level: Level::Error,
+                location: None,
+            }],
+
             Error::NonUtf8Path { path } => {
                 let text = format!(
                     "Encountered a non UTF-8 path '{}', but only UTF-8 paths are supported.",
@@ -1875,10 +1911,17 @@ Hint: Add some type annotations and try again.")
                     let mut printer = Printer::new(names);
 
                     // Give a hint about what type this value has.
-                    let mut text = format!(
-                        "The value being accessed has this type:\n\n    {}\n",
-                        printer.print_type(type_)
-                    );
+                    let mut text = match usage {
+                        FieldAccessUsage::RecordUpdate => format!(
+                            "The record being updated has this type:\n\n    {}\n\n\
+It does not have a field named `{label}`.\n",
+                            printer.print_type(type_)
+                        ),
+                        FieldAccessUsage::MethodCall | FieldAccessUsage::Other => format!(
+                            "The value being accessed has this type:\n\n    {}\n",
+                            printer.print_type(type_)
+                        ),
+                    };
 
                     // Give a hint about what record fields this value has, if any.
                     if fields.is_empty() {
@@ -1937,7 +1980,7 @@ to call a method on this value you may want to use the function syntax instead."
                         FieldAccessUsage::Other | FieldAccessUsage::RecordUpdate => (),
                     }
 
-                    let label = did_you_mean(label, fields)
+                    let label = did_you_mean_close_match(label, fields)
                         .unwrap_or_else(|| "This field does not exist".into());
                     Diagnostic {
                         title: "Unknown record field".into(),
@@ -2001,7 +2044,7 @@ But this argument has this type:
                     location,
                     expected,
                     given,
-                    situation: Some(UnifyErrorSituation::PipeTypeMismatch),
+                    situation: Some(UnifyErrorSituation::PipeTypeMismatch { label }),
                 } => {
                     // Remap the pipe function type into just the type expected by the pipe.
                     let expected = expected
@@ -2014,19 +2057,25 @@ But this argument has this type:
                         .and_then(|(args, _)| args.first().cloned())
                         .unwrap_or_else(|| given.clone());
 
+                    // This is only reported for a step like `a |> b`, where
+                    // `b` must be a function taking the piped value as its
+                    // only argument. A function capture such as `b(1, _)` is
+                    // checked as a call instead, with the mismatch reported on
+                    // the captured `_` argument.
                     let mut printer = Printer::new(names);
                     let text = format!(
-                        "The argument is:
+                        "The previous step of the pipeline produced a value of type:
 
     {given}
 
-But function expects:
+But this function expects its first argument{labelled} to be of type:
 
     {expected}",
                         expected = expected
                             .map(|v| printer.print_type(&v))
                             .unwrap_or_else(|| "    No arguments".into()),
-                        given = printer.print_type(&given)
+                        given = printer.print_type(&given),
+                        labelled = labelled_suffix(label.as_ref()),
                     );
 
                     Diagnostic {
@@ -2046,6 +2095,43 @@ But function expects:
                     }
                 }
 
+                TypeError::CouldNotUnify {
+                    location,
+                    expected,
+                    given,
+                    situation: Some(UnifyErrorSituation::PipeArgumentMismatch { label }),
+                } => {
+                    let mut printer = Printer::new(names);
+                    let text = format!(
+                        "The previous step of the pipeline produced a value of type:
+
+    {given}
+
+But this function expects its first argument{labelled} to be of type:
+
+    {expected}",
+                        expected = printer.print_type(expected),
+                        given = printer.print_type(given),
+                        labelled = labelled_suffix(label.as_ref()),
+                    );
+
+                    Diagnostic {
+                        title: "Type mismatch".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::CouldNotUnify {
                     location,
                     expected,
@@ -2061,10 +2147,20 @@ But function expects:
                     } else {
                         "".into()
                     };
+                    let printed_expected = printer.print_type(expected);
+                    let printed_given = printer.print_type(given);
                     text.push_str("Expected type:\n\n    ");
-                    text.push_str(&printer.print_type(expected));
+                    text.push_str(&printed_expected);
                     text.push_str("\n\nFound type:\n\n    ");
-                    text.push_str(&printer.print_type(given));
+                    text.push_str(&printed_given);
+                    let is_long = printed_expected.len().max(printed_given.len()) >= LONG_TYPE_LENGTH;
+                    if let Some(difference) = is_long
+                        .then(|| type_difference(&mut printer, expected, given))
+                        .flatten()
+                    {
+                        text.push_str("\n\n");
+                        text.push_str(&difference);
+                    }
 
                     let (main_message_location, main_message_text, extra_labels) = match situation {
                         // When the mismatch error comes from a case clause we want to highlight the
@@ -2073,6 +2169,27 @@ But function expects:
                         // or `Error` we want to add an additional label with this hint below the
                         // offending value.
                         Some(UnifyErrorSituation::CaseClauseMismatch{ clause_location }) => (clause_location, None, vec![]),
+                        // When the callback of a `use` returns the wrong type we highlight
+                        // the value it returns, and point to the function that expects
+                        // a different type.
+                        Some(UnifyErrorSituation::UseCallbackResultMismatch { function_location }) => (
+                            location,
+                            hint_wrap_value_in_result(expected, given),
+                            vec![ExtraLabel {
+                                src_info: None,
+                                label: Label {
+                                    text: Some("The callback is passed to this function".into()),
+                                    span: *function_location,
+                                },
+                            }],
+                        ),
+                        // When a number of the wrong type is written we suggest writing
+                        // it with the expected type.
+                        Some(UnifyErrorSituation::NumericLiteralMismatch { replacement }) => (
+                            location,
+                            Some(format!("Did you mean `{replacement}`?")),
+                            vec![],
+                        ),
                         // In all other cases we just highlight the offending expression, optionally
                         // adding the wrapping hint if it makes sense.
                         Some(_) | None =>
@@ -2126,6 +2243,80 @@ called with their expected number of arguments.");
                     }
                 }
 
+                TypeError::IncorrectTypeArgumentCount {
+                    location,
+                    type_,
+                    expected,
+                    given,
+                } => {
+                    let mut printer = Printer::new(names);
+                    let parameters = match expected {
+                        0 => "no type parameters".into(),
+                        1 => "1 type parameter".into(),
+                        _ => format!("{expected} type parameters"),
+                    };
+                    let text = format!(
+                        "This value has {parameters}, its type is:
+
+    {}
+
+Type arguments are given in the order the type parameters first appear in
+the type.",
+                        printer.print_type(type_)
+                    );
+                    Diagnostic {
+                        title: "Incorrect number of type arguments".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(format!("Expected {expected}, got {given}")),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::IncorrectNumericOperator {
+                    location,
+                    operator,
+                    alternative,
+                    type_,
+                } => {
+                    let (given, expected) = if type_.is_int() {
+                        ("Int", "Float")
+                    } else {
+                        ("Float", "Int")
+                    };
+                    let text = format!(
+                        "The `{operator}` operator can only be used with {expected}s, \
+but both of its arguments are {given}s.",
+                        operator = operator.name(),
+                    );
+                    Diagnostic {
+                        title: "Type mismatch".into(),
+                        text: wrap(&text),
+                        hint: Some(format!(
+                            "Use the `{}` operator to work with {given}s.",
+                            alternative.name()
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(format!("Did you mean `{}`?", alternative.name())),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::IncorrectArity {
                     labels,
                     location,
@@ -2187,6 +2378,32 @@ assigned variables to all of them."
                     }
                 }
 
+                TypeError::NonLiteralConstantPattern {
+                    location,
+                    module,
+                    name,
+                } => {
+                    let text = wrap_format!(
+                        "`{module}.{name}` is not a constant with an Int, Float \
+or String value, so it can't be used in a pattern."
+                    );
+                    Diagnostic {
+                        title: "Invalid constant pattern".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some("Only literal constants can be used here".into()),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::UnsafeRecordUpdate { location, reason } =>
                     match reason {
                         UnsafeRecordUpdateReason::UnknownVariant {constructed_variant} => {
@@ -2306,6 +2523,42 @@ but no type in scope with that name."
                     }
                 }
 
+                TypeError::DisabledDefinition {
+                    location,
+                    name,
+                    module,
+                    feature,
+                } => {
+                    let text = match module {
+                        None => wrap_format!(
+                            "`{name}` is defined in this module, but it is only \
+compiled when the `{feature}` feature is enabled."
+                        ),
+                        Some(module) => wrap_format!(
+                            "`{name}` is defined in the `{module}` module, but it is \
+only compiled when the `{feature}` feature is enabled."
+                        ),
+                    };
+                    Diagnostic {
+                        title: "Disabled definition".into(),
+                        text,
+                        hint: Some(wrap_format!(
+                            "Add `\"{feature}\"` to the `features` list in your gleam.toml \
+to enable it."
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some("This requires a feature that is not enabled".into()),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::UnknownVariable {
                     location,
                     variables,
@@ -2394,6 +2647,63 @@ Private types can only be used within the module that defines them.",
                     }),
                 },
 
+                TypeError::ModuleImportedForOtherTarget {
+                    location,
+                    name,
+                    target,
+                } => {
+                    let target = match target {
+                        Target::Erlang => "Erlang",
+                        Target::JavaScript => "JavaScript",
+                    };
+                    let text = wrap_format!(
+                        "The module `{name}` is only imported when compiling to \
+{target}, so it can't be used here."
+                    );
+                    Diagnostic {
+                        title: "Module not imported for this target".into(),
+                        text,
+                        hint: Some(wrap_format!(
+                            "Import a module for this target too, or use `@target({})` \
+on this code so it is only compiled for {target}.",
+                            target.to_lowercase()
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(format!("Only imported for {target}")),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::TypedHole { location, type_ } => {
+                    let mut printer = Printer::new(names);
+                    let type_ = printer.print_type(type_);
+                    Diagnostic {
+                        title: "Typed hole".into(),
+                        text: format!(
+                            "A discard name can't be used as a value. \
+The type expected here is:\n\n    {type_}"
+                        ),
+                        hint: Some("Replace it with a value of this type.".into()),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(format!("Expected type `{type_}`")),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::UnknownModuleType {
                     location,
                     name,
@@ -2466,6 +2776,59 @@ Private types can only be used within the module that defines them.",
                     }
                 }
 
+                TypeError::OpaqueConstructorOutsideModule {
+                    location,
+                    name,
+                    module_name,
+                    type_name,
+                } => {
+                    let text = wrap_format!(
+                        "`{name}` is a constructor of the opaque type `{type_name}`. \
+The constructors of an opaque type can only be used inside the module where \
+the type is defined, `{module_name}`."
+                    );
+                    Diagnostic {
+                        title: "Opaque type constructor".into(),
+                        text,
+                        hint: Some(wrap_format!(
+                            "Use the functions exposed by the `{module_name}` module to \
+create values of this type."
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some("This constructor is private".into()),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::ListComprehensionWithoutListModule { location } => {
+                    let text = wrap(
+                        "List comprehensions are implemented using the functions of \
+the `gleam/list` module, so it has to be imported to use them.",
+                    );
+                    Diagnostic {
+                        title: "Missing `gleam/list` import".into(),
+                        text,
+                        hint: Some("Add `import gleam/list` to the top of this module.".into()),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::ModuleAliasUsedAsName {
                     location,
                     name
@@ -2586,7 +2949,11 @@ as the initial pattern, but the `{name}` variable is missing.",
                     }
                 }
 
-                TypeError::DuplicateVarInPattern { location, name } => {
+                TypeError::DuplicateVarInPattern {
+                    location,
+                    first_location,
+                    name,
+                } => {
                     let text = wrap_format!(
                         "Variables can only be used once per pattern. This \
 variable `{name}` appears multiple times.
@@ -2606,7 +2973,13 @@ e.g. (x, y) if x == y -> ...",
                             },
                             path: path.clone(),
                             src: src.clone(),
-                            extra_labels: vec![],
+                            extra_labels: vec![ExtraLabel {
+                                src_info: None,
+                                label: Label {
+                                    text: Some("First used here".into()),
+                                    span: *first_location,
+                                },
+                            }],
                         }),
                     }
                 }
@@ -2983,6 +3356,35 @@ forever in a loop, and we'd never get the final type.",
                     }
                 }
 
+                TypeError::InfiniteType { location, cycle } => {
+                    let mut text = "This type can never be constructed, as each of its variants
+holds a value of the type itself.\n"
+                        .into();
+                    write_cycle(&mut text, cycle);
+                    text.push_str(
+                        "Building a value of this type would require another value of
+the same type first, forever.",
+                    );
+                    Diagnostic {
+                        title: "Infinite type".into(),
+                        text,
+                        hint: Some(wrap(
+                            "Add a variant that doesn't hold this type, or hold it in \
+a `List` or a function so that it can be left out.",
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::ExternalMissingAnnotation { location, kind } => {
                     let kind = match kind {
                         MissingAnnotation::Parameter => "parameter",
@@ -3011,6 +3413,189 @@ so we can tell what type of values they accept and return.",
                     }
                 }
 
+                TypeError::InvalidBenchmarkFunction { location, name } => {
+                    let text = wrap_format!(
+                        "The `{name}` function is annotated with `@benchmark` but \
+it can't be run as a benchmark. Benchmark functions must be public and take \
+no arguments."
+                    );
+                    Diagnostic {
+                        title: "Invalid benchmark function".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::UnsupportedCapability {
+                    location,
+                    capability,
+                    type_name,
+                    problem,
+                } => {
+                    let reason = match problem {
+                        UnsupportedCapabilityProblem::NoVariants => {
+                            format!("the `{type_name}` type has no variants")
+                        }
+                        UnsupportedCapabilityProblem::VariantWithFields { variant } => {
+                            format!("the `{variant}` variant has some fields")
+                        }
+                        UnsupportedCapabilityProblem::TypeParameters => {
+                            format!("the `{type_name}` type has type parameters")
+                        }
+                        UnsupportedCapabilityProblem::UnsupportedField {
+                            variant,
+                            label: Some(label),
+                        } => format!(
+                            "the `{label}` field of the `{variant}` variant can't be \
+converted to JSON"
+                        ),
+                        UnsupportedCapabilityProblem::UnsupportedField {
+                            variant,
+                            label: None,
+                        } => format!(
+                            "a field of the `{variant}` variant can't be converted to JSON"
+                        ),
+                        UnsupportedCapabilityProblem::MissingImport { module } => {
+                            format!("the `{module}` module is not imported")
+                        }
+                    };
+                    let hint = match problem {
+                        UnsupportedCapabilityProblem::MissingImport { module } => {
+                            Some(format!("Add `import {module}` to the module."))
+                        }
+                        UnsupportedCapabilityProblem::NoVariants
+                        | UnsupportedCapabilityProblem::VariantWithFields { .. }
+                        | UnsupportedCapabilityProblem::TypeParameters
+                        | UnsupportedCapabilityProblem::UnsupportedField { .. } => None,
+                    };
+                    let text = match capability {
+                        Capability::Comparable => wrap_format!(
+                            "The `{type_name}` type can't implement `comparable` because \
+{reason}. Only types whose variants all have no fields can implement it, \
+their values are compared by the order the variants are defined in, and the \
+generated function returns a `gleam/order.Order`."
+                        ),
+                        Capability::Json => wrap_format!(
+                            "The `{type_name}` type can't derive `json` because {reason}. \
+Only types with no type parameters can derive it, and all their fields must \
+be an `Int`, a `String`, a `Bool`, a `List` of those, or a type defined in \
+the same module that also derives `json`."
+                        ),
+                    };
+                    Diagnostic {
+                        title: "Unsupported capability".into(),
+                        text,
+                        hint,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::MemoizedExternalFunction { location, name } => {
+                    let text = wrap_format!(
+                        "The `{name}` function is annotated with `@memoize` but \
+it has an external implementation. Only functions implemented in Gleam can be \
+memoized."
+                    );
+                    Diagnostic {
+                        title: "Memoized external function".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::InvalidExternalArguments {
+                    location,
+                    problem,
+                    arguments,
+                } => {
+                    let label = match problem {
+                        ExternalArgumentsProblem::UnknownArgument => "Unknown argument",
+                        ExternalArgumentsProblem::DuplicateArgument => "Listed more than once",
+                        ExternalArgumentsProblem::MissingArguments => "Some arguments are missing",
+                    };
+                    let mut text = wrap(
+                        "The arguments the external function is called with must be \
+the arguments of this function, in the order the external function expects \
+them. Each of them must be listed exactly once.",
+                    );
+                    if arguments.is_empty() {
+                        text.push_str("\n\nThis function has no named arguments.");
+                    } else {
+                        text.push_str("\n\nThis function has these arguments:\n\n    ");
+                        text.push_str(&arguments.join(", "));
+                    }
+                    Diagnostic {
+                        title: "Invalid external arguments".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(label.into()),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::UnmemoizableArgument { location, type_ } => {
+                    let mut printer = Printer::new(names);
+                    let text = wrap_format!(
+                        "This function is annotated with `@memoize`, so its \
+arguments are used to look up the values it has already returned. This \
+argument has type `{}`, but only `Int`, `Float`, `String`, `Bool` and `Nil` \
+arguments can be used, unless they are discarded.",
+                        printer.print_type(type_)
+                    );
+                    Diagnostic {
+                        title: "Unmemoizable argument".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some("Can't be memoized".into()),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::NoImplementation { location } => {
                     let text = "We can't compile this function as it doesn't have an
 implementation. Add a body or an external implementation
@@ -3115,7 +3700,11 @@ The missing patterns are:\n");
                     }
                 }
 
-                TypeError::InexhaustiveCaseExpression { location, missing } => {
+                TypeError::InexhaustiveCaseExpression {
+                    location,
+                    missing,
+                    missing_string_catch_all: false,
+                } => {
                     let mut text =wrap(
                         "This case expression does not have a pattern \
 for all possible values. If it is run on one of the \
@@ -3144,6 +3733,39 @@ The missing patterns are:\n"
                     }
                 }
 
+                TypeError::InexhaustiveCaseExpression {
+                    location,
+                    missing,
+                    missing_string_catch_all: true,
+                } => {
+                    let mut text = wrap(
+                        "This case expression matches on a `String` using \
+prefix patterns, but prefixes can never cover all possible strings. If it is \
+run on a string that doesn't match any of the patterns then it will crash.
+
+The missing patterns are:\n"
+                    );
+                    for missing in missing {
+                        text.push_str("\n    ");
+                        text.push_str(missing);
+                    }
+                    Diagnostic {
+                        title: "Missing catch-all for String".into(),
+                        text,
+                        hint: Some("Add a `_` pattern to handle all other strings.".into()),
+                        level: Level::Error,
+                        location: Some(Location {
+                            src: src.clone(),
+                            path: path.to_path_buf(),
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
                 TypeError::MissingCaseBody { location } => {
                     let text = wrap(
                         "This case expression is missing its body."
@@ -3548,6 +4170,20 @@ Consider removing the deprecation attribute on the variant.");
                 let (label, extra) = error.details();
                 let text = extra.join("\n");
 
+                let extra_labels = match &error.error {
+                    ParseErrorType::DuplicateExternalForTarget {
+                        previous_location,
+                        ..
+                    } => vec![ExtraLabel {
+                        src_info: None,
+                        label: Label {
+                            text: Some("First given here".into()),
+                            span: *previous_location,
+                        },
+                    }],
+                    _ => vec![],
+                };
+
                 let adjusted_location = if error.error == ParseErrorType::UnexpectedEof {
                     crate::ast::SrcSpan {
                         start: (src.len() - 1) as u32,
@@ -3569,24 +4205,35 @@ Consider removing the deprecation attribute on the variant.");
                         },
                         path: path.clone(),
                         src: src.clone(),
-                        extra_labels: vec![],
+                        extra_labels,
                     }),
                 }]
             }
 
             Error::ImportCycle { modules } => {
-                let first_location = &modules.first().1;
-                let rest_locations = modules.iter().skip(1).map(|(_, l)| ExtraLabel {
-                    label: Label {
-                        text: Some("Imported here".into()),
-                        span: l.location
-                    },
-                    src_info: Some((l.src.clone(), l.path.clone())),
-                }).collect_vec();
+                // Each module imports the one before it, so they're reversed to
+                // be shown in the order they import each other.
+                let cycle = modules.iter().rev().collect_vec();
+                let imported = cycle.iter().cycle().skip(1);
+                let mut labels = cycle.iter().zip(imported).map(|((name, details), (imported, _))| {
+                    let label = Label {
+                        text: Some(format!("`{name}` imports `{imported}` here")),
+                        span: details.location,
+                    };
+                    (label, details)
+                });
+                let (first_label, first_location) =
+                    labels.next().expect("an import cycle can't be empty");
+                let rest_locations = labels
+                    .map(|(label, details)| ExtraLabel {
+                        label,
+                        src_info: Some((details.src.clone(), details.path.clone())),
+                    })
+                    .collect_vec();
                 let mut text = "The import statements for these modules form a cycle:
 "
                 .into();
-                let mod_names = modules.iter().map(|m| m.0.clone()).collect_vec();
+                let mod_names = cycle.iter().map(|(name, _)| name.clone()).collect_vec();
                 write_cycle(&mut text, &mod_names);
                 text.push_str(
                     "Gleam doesn't support dependency cycles like these, please break the
@@ -3598,10 +4245,7 @@ cycle to continue.",
                     hint: None,
                     level: Level::Error,
                     location: Some(Location {
-                        label: Label {
-                            text: Some("Imported here".into()),
-                            span: first_location.location,
-                        },
+                        label: first_label,
                         path: first_location.path.clone(),
                         src: first_location.src.clone(),
                         extra_labels: rest_locations,
@@ -3730,6 +4374,29 @@ Fix the warnings and try again."
                         extra_labels: vec![],
                     }),
                 }],
+                javascript::Error::EsModuleExternalInCommonJs { module, location } => {
+                    vec![Diagnostic {
+                        title: "ES module external in CommonJS module".into(),
+                        text: wrap_format!(
+                            "This function is implemented in the ES module `{module}`, \
+but the project is being compiled to CommonJS modules, which can't require \
+ES modules on all versions of Node.js."
+                        ),
+                        hint: Some(
+                            "Implement the external in a CommonJS `.cjs` file instead.".into(),
+                        ),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }]
+                }
             },
 
             Error::DownloadPackageError {
@@ -3946,6 +4613,66 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                 }]
             }
 
+            Error::InvalidPackageInterface { path, error } => {
+                let text = format!(
+                    "This file could not be read as a package interface:
+
+    {path}
+
+The error message from the JSON decoder was:
+
+    {error}
+
+Package interfaces can be created with `gleam export package-interface`."
+                );
+                vec![Diagnostic {
+                    title: "Invalid package interface".into(),
+                    text,
+                    hint: None,
+                    level: Level::Error,
+                    location: None,
+                }]
+            }
+
+            Error::InvalidModuleInterfaceFile { path, error } => {
+                let text = format!(
+                    "This file could not be read as a module interface:
+
+    {path}
+
+{error}.
+
+Module interface files can be created with `gleam export module-interfaces`."
+                );
+                vec![Diagnostic {
+                    title: "Invalid module interface file".into(),
+                    text,
+                    hint: None,
+                    level: Level::Error,
+                    location: None,
+                }]
+            }
+
+            Error::CannotBundleJavaScriptModule { path } => {
+                let text = format!(
+                    "This JavaScript module is used by the project but can't be put in
+a bundle:
+
+    {path}
+
+Only CommonJS modules can be bundled. The JavaScript files used by
+externals have to use `require` and `module.exports` rather than
+`import` and `export`, and be given a `.cjs` or `.js` extension."
+                );
+                vec![Diagnostic {
+                    title: "Cannot bundle JavaScript module".into(),
+                    text,
+                    hint: None,
+                    level: Level::Error,
+                    location: None,
+                }]
+            }
+
             Error::IncompatibleCompilerVersion {
                 package,
                 required_version,
@@ -4075,6 +4802,71 @@ fn write_cycle(buffer: &mut String, cycle: &[EcoString]) {
     buffer.push_str("    └─────┘\n");
 }
 
+/// Types at least this long are hard to compare by eye, so type mismatch errors
+/// involving them also show where exactly the two types differ.
+const LONG_TYPE_LENGTH: usize = 30;
+
+/// Shows where two types that could not be unified differ, with the parts
+/// that are the same in both collapsed to `_`. If they're functions that
+/// only differ in one of their arguments or in their return type, that is
+/// pointed out as well.
+///
+fn type_difference(printer: &mut Printer<'_>, expected: &Type, given: &Type) -> Option<String> {
+    let (expected_difference, given_difference) = printer.print_type_difference(expected, given)?;
+
+    let description = match (expected.fn_types(), given.fn_types()) {
+        (Some((arguments, return_)), Some((given_arguments, given_return)))
+            if arguments.len() == given_arguments.len() =>
+        {
+            let mut different_arguments = arguments
+                .iter()
+                .zip(&given_arguments)
+                .positions(|(argument, given)| !argument.same_as(given));
+            let different_return = !return_.same_as(&given_return);
+            match (different_arguments.next(), different_arguments.next()) {
+                (Some(index), None) if !different_return => format!(
+                    "Only the {} argument of the functions has a different type:",
+                    ordinal(index + 1)
+                ),
+                (None, _) if different_return => {
+                    "Only the return type of the functions is different:".into()
+                }
+                _ => "Only these parts of the types are different:".into(),
+            }
+        }
+        _ => "Only these parts of the types are different:".into(),
+    };
+
+    Some(format!(
+        "{description}
+
+    Expected: {expected_difference}
+    Found:    {given_difference}"
+    ))
+}
+
+/// The ordinal of a number, to say which argument is being talked about:
+/// `1st`, `2nd`, `3rd`, `4th`...
+fn ordinal(number: usize) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{number}{suffix}")
+}
+
+/// Describes the label of the argument a piped value is passed to, to be
+/// used right after "first argument" in an error message.
+fn labelled_suffix(label: Option<&EcoString>) -> String {
+    match label {
+        Some(label) => format!(", labelled `{label}`,"),
+        None => String::new(),
+    }
+}
+
 fn hint_alternative_operator(op: &BinOp, given: &Type) -> Option<String> {
     match op {
         BinOp::AddInt if given.is_float() => Some(hint_numeric_message("+.", "Float")),
@@ -743,6 +743,19 @@ impl Decision {
     fn exhaustive_switch(var: Variable, choices: Vec<(RuntimeCheck, Box<Decision>)>) -> Decision {
         Self::ExhaustiveSwitch { var, choices }
     }
+
+    /// Returns true if this decision fails unless one of its guards (if any)
+    /// evaluates to `True`.
+    ///
+    fn always_fails_without_guards(&self) -> bool {
+        match self {
+            Decision::Fail => true,
+            Decision::Guard { if_false, .. } => if_false.always_fails_without_guards(),
+            Decision::Run { .. } | Decision::Switch { .. } | Decision::ExhaustiveSwitch { .. } => {
+                false
+            }
+        }
+    }
 }
 
 /// The `case` compiler itself (shocking, I know).
@@ -794,6 +807,17 @@ pub struct Diagnostics {
     /// A flag indicating the match is missing one or more pattern.
     pub missing: bool,
 
+    /// A flag indicating that a `String` was matched using prefix patterns,
+    /// but there's no catch-all pattern to handle all the other strings:
+    ///
+    /// ```gleam
+    /// case string {
+    ///   "data:" <> rest -> rest
+    /// }
+    /// ```
+    ///
+    pub missing_string_catch_all: bool,
+
     /// The right-hand sides that are reachable.
     /// If a right-hand side isn't in this list it means its pattern is
     /// redundant.
@@ -812,6 +836,7 @@ impl<'a> Compiler<'a> {
             variable_id,
             diagnostics: Diagnostics {
                 missing: false,
+                missing_string_catch_all: false,
                 reachable: HashSet::new(),
                 match_impossible_variants: HashSet::new(),
             },
@@ -960,6 +985,9 @@ impl<'a> Compiler<'a> {
             // a fallback (imagine you're pattern matching on an `Int` and put no
             // `_` at the end of the case expression).
             let fallback = self.compile(splitter.fallback);
+            if fallback.always_fails_without_guards() {
+                self.check_string_catch_all(&pivot_var, &choices);
+            }
             Decision::switch(pivot_var, choices, fallback)
         } else if choices.is_empty() {
             // If the branching doesn't need any fallback but we ended up with no
@@ -975,6 +1003,24 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// When a `String` is matched using prefix patterns and there's no
+    /// fallback, we want to let the user know a catch-all is needed: no amount
+    /// of prefixes could ever cover all possible strings!
+    ///
+    fn check_string_catch_all(
+        &mut self,
+        var: &Variable,
+        choices: &[(RuntimeCheck, Box<Decision>)],
+    ) {
+        let has_prefix_check = choices
+            .iter()
+            .any(|(check, _)| matches!(check, RuntimeCheck::StringPrefix { .. }));
+
+        if var.type_.is_string() && has_prefix_check {
+            self.diagnostics.missing_string_catch_all = true;
+        }
+    }
+
     fn split_branches(
         &mut self,
         splitter: &mut BranchSplitter,
@@ -1644,7 +1690,9 @@ impl CaseToCompile {
     ///
     fn register(&mut self, pattern: &TypedPattern) -> Id<Pattern> {
         match pattern {
-            TypedPattern::Invalid { .. } => self.insert(Pattern::Discard),
+            TypedPattern::Invalid { .. } | TypedPattern::Constant { .. } => {
+                self.insert(Pattern::Discard)
+            }
             TypedPattern::Discard { .. } => self.insert(Pattern::Discard),
 
             TypedPattern::Int { value, .. } => {
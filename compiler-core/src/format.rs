@@ -10,8 +10,8 @@ use crate::{
     build::Target,
     docvec,
     io::Utf8Writer,
-    parse::SpannedString,
     parse::extra::{Comment, ModuleExtra},
+    parse::{Parsed, SpannedString, lexer::str_to_keyword},
     pretty::{self, *},
     type_::{self, Type},
     warning::WarningEmitter,
@@ -21,22 +21,164 @@ use itertools::Itertools;
 use std::{cmp::Ordering, sync::Arc};
 use vec1::Vec1;
 
-use crate::type_::Deprecation;
+use crate::type_::{Deprecation, Purity};
 use camino::Utf8Path;
 
-const INDENT: isize = 2;
+/// The number of spaces used for each level of indentation, unless a
+/// different one is given to the formatter.
+pub const DEFAULT_INDENT: isize = 2;
+
+/// The maximum width of a line the formatter tries to stay within, unless a
+/// different one is given to the formatter.
+pub const DEFAULT_LINE_WIDTH: isize = 80;
 
 pub fn pretty(writer: &mut impl Utf8Writer, src: &EcoString, path: &Utf8Path) -> Result<()> {
-    let parsed = crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
-        .map_err(|error| Error::Parse {
+    pretty_with_indent(writer, src, path, DEFAULT_INDENT)
+}
+
+/// Formats the given source code just like `pretty`, using `indent` spaces for
+/// each level of indentation.
+///
+pub fn pretty_with_indent(
+    writer: &mut impl Utf8Writer,
+    src: &EcoString,
+    path: &Utf8Path,
+    indent: isize,
+) -> Result<()> {
+    pretty_with_options(writer, src, path, indent, DEFAULT_LINE_WIDTH)
+}
+
+/// Formats the given source code just like `pretty`, using `indent` spaces for
+/// each level of indentation and breaking lines longer than `line_width`.
+///
+pub fn pretty_with_options(
+    writer: &mut impl Utf8Writer,
+    src: &EcoString,
+    path: &Utf8Path,
+    indent: isize,
+    line_width: isize,
+) -> Result<()> {
+    let parsed = parse(src, path)?;
+    let intermediate = Intermediate::from_extra(&parsed.extra, src);
+    Formatter::with_comments(&intermediate)
+        .with_indent(indent)
+        .module(&parsed.module)
+        .pretty_print(line_width, writer)
+}
+
+/// Formats only the definitions of a module that overlap with the given
+/// `range`, as an editor would do when asked to format a selection. The rest
+/// of the source code is left untouched.
+///
+/// Returns the span of the original source code to replace, along with its
+/// formatted replacement. If the range doesn't touch any definition there's
+/// nothing to format and `None` is returned.
+///
+/// Imports are never formatted on their own, as they're sorted as a group
+/// when formatting the whole module.
+///
+pub fn range_to_string(
+    src: &EcoString,
+    path: &Utf8Path,
+    range: SrcSpan,
+) -> Result<Option<(SrcSpan, String)>> {
+    let parsed = parse(src, path)?;
+    let formatted: EcoString = module_to_string(&parsed, src)?.into();
+    let formatted_parsed = parse(&formatted, path)?;
+
+    // Formatting never adds, removes, or reorders definitions other than
+    // imports, so each of the module's definitions can be matched to its
+    // formatted version by its position.
+    let mut selected = definition_spans(&parsed.module, src)
+        .into_iter()
+        .zip(definition_spans(&formatted_parsed.module, &formatted))
+        .filter(|(span, _)| span.start <= range.end && range.start <= span.end);
+
+    let Some((first, formatted_first)) = selected.next() else {
+        return Ok(None);
+    };
+    let (last, formatted_last) = selected.next_back().unwrap_or((first, formatted_first));
+
+    let replaced = SrcSpan::new(first.start, last.end);
+    let replacement = formatted
+        .get(formatted_first.start as usize..formatted_last.end as usize)
+        .expect("formatted definition span")
+        .to_string();
+    Ok(Some((replaced, replacement)))
+}
+
+fn parse(src: &EcoString, path: &Utf8Path) -> Result<Parsed> {
+    crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null()).map_err(|error| {
+        Error::Parse {
             path: path.to_path_buf(),
             src: src.clone(),
             error,
-        })?;
+        }
+    })
+}
+
+/// The span of each of the module's definitions that are not imports,
+/// including the comments, doc comments, and attributes coming before it.
+///
+fn definition_spans(module: &UntypedModule, src: &str) -> Vec<SrcSpan> {
+    let mut spans = vec![];
+    let mut previous_end = 0;
+    for definition in &module.definitions {
+        let definition = &definition.definition;
+        let end = match definition {
+            Definition::Function(function) => function.end_position,
+            Definition::CustomType(custom_type) => custom_type.end_position,
+            Definition::Import(_) | Definition::TypeAlias(_) | Definition::ModuleConstant(_) => {
+                definition.location().end
+            }
+        };
+        if !definition.is_import() {
+            spans.push(SrcSpan::new(definition_start(src, previous_end), end));
+        }
+        previous_end = end;
+    }
+    spans
+}
+
+/// Anything between the end of the previous definition and a definition
+/// belongs to it, apart from whitespace and module comments, which are always
+/// moved to the top of the module by the formatter.
+///
+fn definition_start(src: &str, previous_end: u32) -> u32 {
+    let mut position = previous_end as usize;
+    loop {
+        let rest = src.get(position..).unwrap_or_default();
+        let trimmed = rest.trim_start();
+        position += rest.len() - trimmed.len();
+        if !trimmed.starts_with("////") {
+            return position as u32;
+        }
+        position += trimmed.find('\n').unwrap_or(trimmed.len());
+    }
+}
+
+/// Formats a module that has already been parsed with
+/// `parse::parse_module`. `src` must be the source code the module was
+/// parsed from, as it's needed to put its comments back in place.
+///
+pub fn module_to_string(parsed: &Parsed, src: &EcoString) -> Result<String> {
+    let mut writer = String::new();
     let intermediate = Intermediate::from_extra(&parsed.extra, src);
     Formatter::with_comments(&intermediate)
         .module(&parsed.module)
-        .pretty_print(80, writer)
+        .pretty_print(DEFAULT_LINE_WIDTH, &mut writer)?;
+    Ok(writer)
+}
+
+/// Formats a sequence of statements that has already been parsed with
+/// `parse::parse_statement_sequence`. Comments are not preserved.
+///
+pub fn statements_to_string(statements: &Vec1<UntypedStatement>) -> Result<String> {
+    let mut writer = String::new();
+    Formatter::new()
+        .statements(statements)
+        .pretty_print(DEFAULT_LINE_WIDTH, &mut writer)?;
+    Ok(writer)
 }
 
 pub(crate) struct Intermediate<'a> {
@@ -96,13 +238,28 @@ impl HasLocation for RecordUpdatePiece<'_> {
 }
 
 /// Hayleigh's bane
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Formatter<'a> {
     comments: &'a [Comment<'a>],
     doc_comments: &'a [Comment<'a>],
     module_comments: &'a [Comment<'a>],
     empty_lines: &'a [u32],
     new_lines: &'a [u32],
+    /// The number of spaces used for each level of indentation.
+    indent: isize,
+}
+
+impl Default for Formatter<'_> {
+    fn default() -> Self {
+        Self {
+            comments: &[],
+            doc_comments: &[],
+            module_comments: &[],
+            empty_lines: &[],
+            new_lines: &[],
+            indent: DEFAULT_INDENT,
+        }
+    }
 }
 
 impl<'comments> Formatter<'comments> {
@@ -117,9 +274,31 @@ impl<'comments> Formatter<'comments> {
             module_comments: &extra.module_comments,
             empty_lines: extra.empty_lines,
             new_lines: extra.new_lines,
+            indent: DEFAULT_INDENT,
         }
     }
 
+    pub fn with_indent(mut self, indent: isize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    fn break_block<'a>(&self, doc: Document<'a>) -> Document<'a> {
+        "{".to_doc()
+            .append(line().append(doc).nest(self.indent))
+            .append(line())
+            .append("}")
+            .force_break()
+    }
+
+    fn wrap_block<'a>(&self, doc: Document<'a>) -> Document<'a> {
+        break_("{", "{ ")
+            .append(doc)
+            .nest(self.indent)
+            .append(break_("", " "))
+            .append("}")
+    }
+
     fn any_comments(&self, limit: u32) -> bool {
         self.comments
             .first()
@@ -187,12 +366,17 @@ impl<'comments> Formatter<'comments> {
 
     fn targeted_definition<'a>(&mut self, definition: &'a TargetedDefinition) -> Document<'a> {
         let target = definition.target;
+        let feature = &definition.feature;
         let definition = &definition.definition;
         let start = definition.location().start;
 
         let comments = self.pop_comments_with_position(start);
         let comments = self.printed_documented_comments(comments);
         let document = self.documented_definition(definition);
+        let document = match feature {
+            None => document,
+            Some(feature) => docvec!["@when(feature: \"", feature, "\")", line(), document],
+        };
         let document = match target {
             None => document,
             Some(Target::Erlang) => docvec!["@target(erlang)", line(), document],
@@ -369,6 +553,7 @@ impl<'comments> Formatter<'comments> {
                 publicity,
                 deprecation,
                 location,
+                doc_attribute,
                 ..
             }) => self.type_alias(
                 *publicity,
@@ -376,6 +561,7 @@ impl<'comments> Formatter<'comments> {
                 args,
                 resolved_type,
                 deprecation,
+                doc_attribute,
                 location,
             ),
 
@@ -386,15 +572,23 @@ impl<'comments> Formatter<'comments> {
                 as_name,
                 unqualified_values,
                 unqualified_types,
+                unqualified_types_glob,
                 ..
             }) => {
-                let second = if unqualified_values.is_empty() && unqualified_types.is_empty() {
+                let second = if unqualified_values.is_empty()
+                    && unqualified_types.is_empty()
+                    && unqualified_types_glob.is_none()
+                {
                     nil()
                 } else {
+                    // The `type *` glob always comes first, before all the
+                    // types imported by name.
+                    let types_glob = unqualified_types_glob.map(|_| "type *".to_doc());
                     let unqualified_types = unqualified_types
                         .iter()
                         .sorted_by(|a, b| a.name.cmp(&b.name))
                         .map(|type_| docvec!["type ", type_]);
+                    let unqualified_types = types_glob.into_iter().chain(unqualified_types);
                     let unqualified_values = unqualified_values
                         .iter()
                         .sorted_by(|a, b| a.name.cmp(&b.name))
@@ -405,7 +599,7 @@ impl<'comments> Formatter<'comments> {
                     );
                     let unqualified = break_("", "")
                         .append(unqualified)
-                        .nest(INDENT)
+                        .nest(self.indent)
                         .append(break_(",", ""))
                         .group();
                     ".{".to_doc().append(unqualified).append("}")
@@ -427,7 +621,7 @@ impl<'comments> Formatter<'comments> {
                     }
                     (_, None) => doc,
                     (_, Some((AssignName::Variable(name) | AssignName::Discard(name), _))) => {
-                        doc.append(" as ").append(name)
+                        doc.append(" as ").append(escaped_name(name))
                     }
                 }
             }
@@ -437,13 +631,19 @@ impl<'comments> Formatter<'comments> {
                 name,
                 annotation,
                 value,
+                since,
+                doc_attribute,
                 ..
             }) => {
-                let attributes = AttributesPrinter::new().set_internal(*publicity).to_doc();
+                let attributes = AttributesPrinter::new()
+                    .set_doc(doc_attribute, self.indent)
+                    .set_internal(*publicity)
+                    .set_since(since)
+                    .to_doc();
                 let head = attributes
                     .append(pub_(*publicity))
                     .append("const ")
-                    .append(name.as_str());
+                    .append(escaped_name(name));
                 let head = match annotation {
                     None => head,
                     Some(t) => head.append(": ").append(self.type_ast(t)),
@@ -529,18 +729,18 @@ impl<'comments> Formatter<'comments> {
 
             Constant::Var {
                 name, module: None, ..
-            } => name.to_doc(),
+            } => escaped_name(name),
 
             Constant::Var {
                 name,
                 module: Some((module, _)),
                 ..
-            } => docvec![module, ".", name],
+            } => docvec![module, ".", escaped_name(name)],
 
             Constant::StringConcatenation { left, right, .. } => self
                 .const_expr(left)
                 .append(break_("", " ").append("<>".to_doc()))
-                .nest(INDENT)
+                .nest(self.indent)
                 .append(" ")
                 .append(self.const_expr(right)),
 
@@ -564,7 +764,7 @@ impl<'comments> Formatter<'comments> {
                 None => "[]".to_doc(),
                 Some(comments) => "["
                     .to_doc()
-                    .append(break_("", "").nest(INDENT))
+                    .append(break_("", "").nest(self.indent))
                     .append(comments)
                     .append(break_("", ""))
                     .append("]")
@@ -604,7 +804,7 @@ impl<'comments> Formatter<'comments> {
             comma,
         );
 
-        let doc = break_("[", "[").append(elements).nest(INDENT);
+        let doc = break_("[", "[").append(elements).nest(self.indent);
 
         // We get all remaining comments that come before the list's closing
         // square bracket.
@@ -615,7 +815,7 @@ impl<'comments> Formatter<'comments> {
         match printed_comments(comments, false) {
             None => doc.append(break_(",", "")).append("]").group(),
             Some(comment) => doc
-                .append(break_(",", "").nest(INDENT))
+                .append(break_(",", "").nest(self.indent))
                 // ^ See how here we're adding the missing indentation to the
                 //   final break so that the final comment is as indented as the
                 //   list's items.
@@ -639,7 +839,7 @@ impl<'comments> Formatter<'comments> {
                 None => "#()".to_doc(),
                 Some(comments) => "#("
                     .to_doc()
-                    .append(break_("", "").nest(INDENT))
+                    .append(break_("", "").nest(self.indent))
                     .append(comments)
                     .append(break_("", ""))
                     .append(")")
@@ -653,13 +853,13 @@ impl<'comments> Formatter<'comments> {
         let args_docs = elements.iter().map(|element| self.const_expr(element));
         let tuple_doc = break_("#(", "#(")
             .append(join(args_docs, break_(",", ", ")).next_break_fits(NextBreakFitsMode::Disabled))
-            .nest(INDENT);
+            .nest(self.indent);
 
         let comments = self.pop_comments(location.end);
         match printed_comments(comments, false) {
             None => tuple_doc.append(break_(",", "")).append(")").group(),
             Some(comments) => tuple_doc
-                .append(break_(",", "").nest(INDENT))
+                .append(break_(",", "").nest(self.indent))
                 .append(comments)
                 .append(line())
                 .append(")")
@@ -740,9 +940,13 @@ impl<'comments> Formatter<'comments> {
                 .append(self.type_arguments(args, location))
                 .group()
                 .append(" ->")
-                .append(break_("", " ").append(self.type_ast(return_)).nest(INDENT)),
+                .append(
+                    break_("", " ")
+                        .append(self.type_ast(return_))
+                        .nest(self.indent),
+                ),
 
-            TypeAst::Var(TypeAstVar { name, .. }) => name.to_doc(),
+            TypeAst::Var(TypeAstVar { name, .. }) => escaped_name(name),
 
             TypeAst::Tuple(TypeAstTuple { elements, location }) => {
                 "#".to_doc().append(self.type_arguments(elements, location))
@@ -756,6 +960,7 @@ impl<'comments> Formatter<'comments> {
         self.wrap_args(args, location.end)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn type_alias<'a>(
         &mut self,
         publicity: Publicity,
@@ -763,9 +968,11 @@ impl<'comments> Formatter<'comments> {
         args: &'a [SpannedString],
         type_: &'a TypeAst,
         deprecation: &'a Deprecation,
+        doc_attribute: &'a [EcoString],
         location: &SrcSpan,
     ) -> Document<'a> {
         let attributes = AttributesPrinter::new()
+            .set_doc(doc_attribute, self.indent)
             .set_deprecation(deprecation)
             .set_internal(publicity)
             .to_doc();
@@ -778,8 +985,12 @@ impl<'comments> Formatter<'comments> {
             head.append(self.wrap_args(args, location.end).group())
         };
 
-        head.append(" =")
-            .append(line().append(self.type_ast(type_)).group().nest(INDENT))
+        head.append(" =").append(
+            line()
+                .append(self.type_ast(type_))
+                .group()
+                .nest(self.indent),
+        )
     }
 
     fn fn_arg<'a, A>(&mut self, arg: &'a Arg<A>) -> Document<'a> {
@@ -794,10 +1005,22 @@ impl<'comments> Formatter<'comments> {
 
     fn statement_fn<'a>(&mut self, function: &'a UntypedFunction) -> Document<'a> {
         let attributes = AttributesPrinter::new()
+            .set_doc(&function.doc_attribute, self.indent)
             .set_deprecation(&function.deprecation)
             .set_internal(function.publicity)
-            .set_external_erlang(&function.external_erlang)
-            .set_external_javascript(&function.external_javascript)
+            .set_purity(function.purity)
+            .set_benchmark(function.benchmark)
+            .set_memoize(function.memoize)
+            .set_should_panic(&function.should_panic)
+            .set_since(&function.since)
+            .set_external_erlang(
+                &function.external_erlang,
+                &function.external_erlang_arguments,
+            )
+            .set_external_javascript(
+                &function.external_javascript,
+                &function.external_javascript_arguments,
+            )
             .to_doc();
 
         // Fn name and args
@@ -808,13 +1031,13 @@ impl<'comments> Formatter<'comments> {
             .collect_vec();
         let signature = pub_(function.publicity)
             .append("fn ")
-            .append(
+            .append(escaped_name(
                 &function
                     .name
                     .as_ref()
                     .expect("Function in a statement must be named")
                     .1,
-            )
+            ))
             .append(self.wrap_args(args, function.location.end));
 
         // Add return annotation
@@ -842,7 +1065,7 @@ impl<'comments> Formatter<'comments> {
 
         // Stick it all together
         head.append(" {")
-            .append(line().append(body).nest(INDENT).group())
+            .append(line().append(body).nest(self.indent).group())
             .append(line())
             .append("}")
     }
@@ -889,7 +1112,7 @@ impl<'comments> Formatter<'comments> {
             Some(comments) => statements.append(line()).append(comments).force_break(),
         };
 
-        header.append(" ").append(wrap_block(body)).group()
+        header.append(" ").append(self.wrap_block(body)).group()
     }
 
     fn statements<'a>(&mut self, statements: &'a Vec1<UntypedStatement>) -> Document<'a> {
@@ -938,7 +1161,7 @@ impl<'comments> Formatter<'comments> {
                 "let assert ",
                 message.as_ref().map(|message| {
                     break_("", " ")
-                        .nest(INDENT)
+                        .nest(self.indent)
                         .append("as ".to_doc().append(self.expr(message).group()))
                 }),
             ),
@@ -973,6 +1196,8 @@ impl<'comments> Formatter<'comments> {
 
             UntypedExpr::Todo { message: None, .. } => "todo".to_doc(),
 
+            UntypedExpr::Hole { name, .. } => name.to_doc(),
+
             UntypedExpr::Todo {
                 message: Some(l), ..
             } => docvec!["todo as ", self.expr(l)],
@@ -998,7 +1223,7 @@ impl<'comments> Formatter<'comments> {
 
             UntypedExpr::Var { name, .. } if name == CAPTURE_VARIABLE => "_".to_doc(),
 
-            UntypedExpr::Var { name, .. } => name.to_doc(),
+            UntypedExpr::Var { name, .. } => escaped_name(name),
 
             UntypedExpr::TupleIndex { tuple, index, .. } => self.tuple_index(tuple, *index),
 
@@ -1031,6 +1256,12 @@ impl<'comments> Formatter<'comments> {
                 location,
             } => self.list(elements, tail.as_deref(), location),
 
+            UntypedExpr::ListComprehension {
+                element,
+                qualifiers,
+                ..
+            } => self.list_comprehension(element, qualifiers),
+
             UntypedExpr::Call {
                 fun,
                 arguments: args,
@@ -1038,6 +1269,21 @@ impl<'comments> Formatter<'comments> {
                 ..
             } => self.call(fun, args, location),
 
+            UntypedExpr::TypeApplication {
+                fun,
+                type_arguments,
+                ..
+            } => {
+                let type_arguments = type_arguments
+                    .iter()
+                    .map(|type_| self.type_ast(type_))
+                    .collect_vec();
+                self.expr(fun)
+                    .append("<")
+                    .append(join(type_arguments, ", ".to_doc()))
+                    .append(">")
+            }
+
             UntypedExpr::BinOp {
                 name, left, right, ..
             } => self.bin_op(name, left, right, false),
@@ -1056,7 +1302,7 @@ impl<'comments> Formatter<'comments> {
                 self.expr(container)
             }
             .append(".")
-            .append(label.as_str()),
+            .append(escaped_name(label)),
 
             UntypedExpr::Tuple { elements, location } => self.tuple(elements, location),
 
@@ -1231,7 +1477,10 @@ impl<'comments> Formatter<'comments> {
         let expr = match fun {
             UntypedExpr::Placeholder { .. } => panic!("Placeholders should not be formatted"),
 
-            UntypedExpr::PipeLine { .. } => break_block(self.expr(fun)),
+            UntypedExpr::PipeLine { .. } => {
+                let fun = self.expr(fun);
+                self.break_block(fun)
+            }
 
             UntypedExpr::BinOp { .. }
             | UntypedExpr::Int { .. }
@@ -1241,12 +1490,15 @@ impl<'comments> Formatter<'comments> {
             | UntypedExpr::Var { .. }
             | UntypedExpr::Fn { .. }
             | UntypedExpr::List { .. }
+            | UntypedExpr::ListComprehension { .. }
             | UntypedExpr::Call { .. }
+            | UntypedExpr::TypeApplication { .. }
             | UntypedExpr::Case { .. }
             | UntypedExpr::FieldAccess { .. }
             | UntypedExpr::Tuple { .. }
             | UntypedExpr::TupleIndex { .. }
             | UntypedExpr::Todo { .. }
+            | UntypedExpr::Hole { .. }
             | UntypedExpr::Echo { .. }
             | UntypedExpr::Panic { .. }
             | UntypedExpr::BitArray { .. }
@@ -1256,13 +1508,66 @@ impl<'comments> Formatter<'comments> {
         };
 
         let arity = args.len();
-        self.append_inlinable_wrapped_args(
-            expr,
-            args,
-            location,
-            |arg| &arg.value,
-            |self_, arg| self_.call_arg(arg, arity),
-        )
+        match args.split_last() {
+            Some((
+                CallArg {
+                    value:
+                        UntypedExpr::Fn {
+                            kind,
+                            arguments,
+                            body,
+                            location: block_location,
+                            ..
+                        },
+                    ..
+                },
+                args,
+            )) if kind.is_trailing_block() => {
+                let location = SrcSpan::new(location.start, block_location.start);
+                let call = self.append_inlinable_wrapped_args(
+                    expr,
+                    args,
+                    &location,
+                    |arg| &arg.value,
+                    |self_, arg| self_.call_arg(arg, arity),
+                );
+                call.append(" ")
+                    .append(self.trailing_block(arguments, body, block_location))
+            }
+
+            _ => self.append_inlinable_wrapped_args(
+                expr,
+                args,
+                location,
+                |arg| &arg.value,
+                |self_, arg| self_.call_arg(arg, arity),
+            ),
+        }
+    }
+
+    fn trailing_block<'a>(
+        &mut self,
+        args: &'a [UntypedArg],
+        body: &'a Vec1<UntypedStatement>,
+        location: &SrcSpan,
+    ) -> Document<'a> {
+        let head = if args.is_empty() {
+            "{ ->".to_doc()
+        } else {
+            let args = join(args.iter().map(|arg| self.fn_arg(arg)), ", ".to_doc());
+            docvec!["{ ", args, " ->"]
+        };
+
+        let statements = self.statements(body);
+        let body = match printed_comments(self.pop_comments(location.end), false) {
+            None => statements,
+            Some(comments) => statements.append(line()).append(comments).force_break(),
+        };
+
+        head.append(break_("", " ").append(body).nest(self.indent))
+            .append(break_("", " "))
+            .append("}")
+            .group()
     }
 
     fn tuple<'a>(&mut self, elements: &'a [UntypedExpr], location: &SrcSpan) -> Document<'a> {
@@ -1274,7 +1579,7 @@ impl<'comments> Formatter<'comments> {
                 None => "#()".to_doc(),
                 Some(comments) => "#("
                     .to_doc()
-                    .append(break_("", "").nest(INDENT))
+                    .append(break_("", "").nest(self.indent))
                     .append(comments)
                     .append(break_("", ""))
                     .append(")")
@@ -1352,7 +1657,7 @@ impl<'comments> Formatter<'comments> {
                 subjects.iter().map(|s| self.expr(s).group()),
                 break_(",", ", "),
             ))
-            .nest(INDENT)
+            .nest(self.indent)
             .append(break_("", " "))
             .append("{")
             .next_break_fits(NextBreakFitsMode::Disabled)
@@ -1373,13 +1678,13 @@ impl<'comments> Formatter<'comments> {
         let closing_bracket = match printed_comments(comments, false) {
             None => docvec![line(), "}"],
             Some(comment) => docvec![line(), comment]
-                .nest(INDENT)
+                .nest(self.indent)
                 .append(line())
                 .append("}"),
         };
 
         subjects_doc
-            .append(line().append(clauses_doc).nest(INDENT))
+            .append(line().append(clauses_doc).nest(self.indent))
             .append(closing_bracket)
             .force_break()
     }
@@ -1430,7 +1735,7 @@ impl<'comments> Formatter<'comments> {
 
         left_side
             .append(if nest_steps {
-                name_doc.nest(INDENT)
+                name_doc.nest(self.indent)
             } else {
                 name_doc
             })
@@ -1472,7 +1777,7 @@ impl<'comments> Formatter<'comments> {
 
     pub fn operator_side<'a>(&self, doc: Document<'a>, op: u8, side: u8) -> Document<'a> {
         if op > side {
-            wrap_block(doc).group()
+            self.wrap_block(doc).group()
         } else {
             doc
         }
@@ -1519,14 +1824,22 @@ impl<'comments> Formatter<'comments> {
                 }
                 _ => self.expr(expr),
             };
-            let doc = if nest_pipe { doc.nest(INDENT) } else { doc };
+            let doc = if nest_pipe {
+                doc.nest(self.indent)
+            } else {
+                doc
+            };
             let space = if try_to_keep_on_one_line {
                 break_("", " ")
             } else {
                 line()
             };
             let pipe = space.append(commented("|> ".to_doc(), comments));
-            let pipe = if nest_pipe { pipe.nest(INDENT) } else { pipe };
+            let pipe = if nest_pipe {
+                pipe.nest(self.indent)
+            } else {
+                pipe
+            };
             docs.push(pipe);
             docs.push(self.operator_side(doc, 4, expr.bin_op_precedence()));
         }
@@ -1650,7 +1963,7 @@ impl<'comments> Formatter<'comments> {
                      }| {
                         let arg_comments = self.pop_comments(location.start);
                         let arg = match label {
-                            Some((_, l)) => l.to_doc().append(": ").append(self.type_ast(ast)),
+                            Some((_, l)) => escaped_name(l).append(": ").append(self.type_ast(ast)),
                             None => self.type_ast(ast),
                         };
 
@@ -1674,8 +1987,10 @@ impl<'comments> Formatter<'comments> {
         let _ = self.pop_empty_lines(ct.location.end);
 
         let attributes = AttributesPrinter::new()
+            .set_doc(&ct.doc_attribute, self.indent)
             .set_deprecation(&ct.deprecation)
             .set_internal(ct.publicity)
+            .set_capabilities(&ct.capabilities)
             .to_doc();
 
         let doc = attributes
@@ -1684,7 +1999,11 @@ impl<'comments> Formatter<'comments> {
             .append(if ct.parameters.is_empty() {
                 ct.name.clone().to_doc()
             } else {
-                let args = ct.parameters.iter().map(|(_, e)| e.to_doc()).collect_vec();
+                let args = ct
+                    .parameters
+                    .iter()
+                    .map(|(_, e)| escaped_name(e))
+                    .collect_vec();
                 ct.name
                     .clone()
                     .to_doc()
@@ -1711,7 +2030,7 @@ impl<'comments> Formatter<'comments> {
             Some(comments) => inner.append(line()).append(comments),
             None => inner,
         }
-        .nest(INDENT)
+        .nest(self.indent)
         .group();
 
         doc.append(inner).append(line()).append("}")
@@ -1782,8 +2101,10 @@ impl<'comments> Formatter<'comments> {
 
     fn docs_fn_arg_name<'a>(&mut self, arg: &'a TypedArg) -> Document<'a> {
         match &arg.names {
-            ArgNames::Named { name, .. } => name.to_doc(),
-            ArgNames::NamedLabelled { label, name, .. } => docvec![label, " ", name],
+            ArgNames::Named { name, .. } => escaped_name(name),
+            ArgNames::NamedLabelled { label, name, .. } => {
+                docvec![escaped_name(label), " ", escaped_name(name)]
+            }
             // We remove the underscore from discarded function arguments since we don't want to
             // expose this kind of detail: https://github.com/gleam-lang/gleam/issues/2561
             ArgNames::Discard { name, .. } => match name.strip_prefix('_').unwrap_or(name) {
@@ -1791,7 +2112,11 @@ impl<'comments> Formatter<'comments> {
                 name => name.to_doc(),
             },
             ArgNames::LabelledDiscard { label, name, .. } => {
-                docvec![label, " ", name.strip_prefix('_').unwrap_or(name).to_doc()]
+                docvec![
+                    escaped_name(label),
+                    " ",
+                    name.strip_prefix('_').unwrap_or(name).to_doc()
+                ]
             }
         }
     }
@@ -1816,12 +2141,12 @@ impl<'comments> Formatter<'comments> {
             CallArgFormatting::Unlabelled(value) => format_value(self, value),
             CallArgFormatting::ShorthandLabelled(label) => {
                 let comments = self.pop_comments(arg.location.start);
-                let label = label.as_ref().to_doc().append(":");
+                let label = escaped_name(label).append(":");
                 commented(label, comments)
             }
             CallArgFormatting::Labelled(label, value) => {
                 let comments = self.pop_comments(arg.location.start);
-                let label = label.as_ref().to_doc().append(": ");
+                let label = escaped_name(label).append(": ");
                 let value = format_value(self, value);
                 commented(label, comments).append(value)
             }
@@ -1833,20 +2158,17 @@ impl<'comments> Formatter<'comments> {
         match arg {
             // Argument supplied with a label shorthand.
             _ if arg.uses_label_shorthand() => {
-                commented(arg.label.as_str().to_doc().append(":"), comments)
+                commented(escaped_name(&arg.label).append(":"), comments)
             }
             // Labelled argument.
             _ => {
-                let doc = arg
-                    .label
-                    .as_str()
-                    .to_doc()
+                let doc = escaped_name(&arg.label)
                     .append(": ")
                     .append(self.expr(&arg.value))
                     .group();
 
                 if arg.value.is_binop() || arg.value.is_pipeline() {
-                    commented(doc, comments).nest(INDENT)
+                    commented(doc, comments).nest(self.indent)
                 } else {
                     commented(doc, comments)
                 }
@@ -1888,12 +2210,15 @@ impl<'comments> Formatter<'comments> {
                 let expression_comments = self.pop_comments(expr.location().start);
                 let expression_doc = self.expr(expr);
                 match printed_comments(expression_comments, true) {
-                    Some(comments) => line().append(comments).append(expression_doc).nest(INDENT),
+                    Some(comments) => line()
+                        .append(comments)
+                        .append(expression_doc)
+                        .nest(self.indent),
                     None => " ".to_doc().append(expression_doc),
                 }
             }
 
-            UntypedExpr::Case { .. } => line().append(self.expr(expr)).nest(INDENT),
+            UntypedExpr::Case { .. } => line().append(self.expr(expr)).nest(self.indent),
 
             UntypedExpr::Block {
                 statements,
@@ -1901,7 +2226,9 @@ impl<'comments> Formatter<'comments> {
                 ..
             } => " ".to_doc().append(self.block(location, statements, true)),
 
-            _ => break_("", " ").append(self.expr(expr).group()).nest(INDENT),
+            _ => break_("", " ")
+                .append(self.expr(expr).group())
+                .nest(self.indent),
         }
         .next_break_fits(NextBreakFitsMode::Disabled)
         .group()
@@ -1922,9 +2249,9 @@ impl<'comments> Formatter<'comments> {
             None => self.alternative_patterns(clause),
             Some(guard) => self
                 .alternative_patterns(clause)
-                .append(break_("", " ").nest(INDENT))
+                .append(break_("", " ").nest(self.indent))
                 .append("if ")
-                .append(self.clause_guard(guard).group().nest(INDENT)),
+                .append(self.clause_guard(guard).group().nest(self.indent)),
         };
 
         // In case there's a guard or multiple subjects, if we decide to break
@@ -1974,6 +2301,7 @@ impl<'comments> Formatter<'comments> {
     fn alternative_patterns<'a>(&mut self, clause: &'a UntypedClause) -> Document<'a> {
         let has_guard = clause.guard.is_some();
         let has_multiple_subjects = clause.pattern.len() > 1;
+        let indent = self.indent;
 
         // In case there's an `if` guard but no multiple subjects we want to add
         // additional indentation before the vartical bar separating alternative
@@ -1990,7 +2318,7 @@ impl<'comments> Formatter<'comments> {
         // }
         // ```
         let alternatives_separator = if has_guard && !has_multiple_subjects {
-            break_("", " ").nest(INDENT).append("| ")
+            break_("", " ").nest(self.indent).append("| ")
         } else {
             break_("", " ").append("| ")
         };
@@ -2023,13 +2351,13 @@ impl<'comments> Formatter<'comments> {
                     if is_first_pattern_of_clause {
                         subject_doc
                     } else {
-                        subject_doc.nest(INDENT)
+                        subject_doc.nest(indent)
                     }
                 });
                 // We join all subjects with a breakable comma (that's also
                 // going to be nested) and make the subjects into a group to
                 // make sure the formatter tries to keep them on a single line.
-                join(subject_docs, break_(",", ", ").nest(INDENT)).group()
+                join(subject_docs, break_(",", ", ").nest(indent)).group()
             });
         // Last, we make sure that the formatter tries to keep each
         // alternative on a single line by making it a group!
@@ -2052,7 +2380,7 @@ impl<'comments> Formatter<'comments> {
                     None => "[]".to_doc(),
                     Some(comments) => "["
                         .to_doc()
-                        .append(break_("", "").nest(INDENT))
+                        .append(break_("", "").nest(self.indent))
                         .append(comments)
                         .append(break_("", ""))
                         .append("]")
@@ -2090,13 +2418,13 @@ impl<'comments> Formatter<'comments> {
         // bracket we want to add indentation (to just that break). Otherwise,
         // the final comment would be less indented than list's elements.
         let (doc, last_break) = match tail {
-            None => (doc.nest(INDENT), break_(",", "")),
+            None => (doc.nest(self.indent), break_(",", "")),
 
             Some(tail) => {
                 let comments = self.pop_comments(tail.location().start);
                 let tail = commented(docvec!["..", self.expr(tail)], comments);
                 (
-                    doc.append(break_(",", ", ")).append(tail).nest(INDENT),
+                    doc.append(break_(",", ", ")).append(tail).nest(self.indent),
                     break_("", ""),
                 )
             }
@@ -2111,7 +2439,7 @@ impl<'comments> Formatter<'comments> {
         match printed_comments(comments, false) {
             None => doc.append(last_break).append("]").group(),
             Some(comment) => doc
-                .append(last_break.nest(INDENT))
+                .append(last_break.nest(self.indent))
                 // ^ See how here we're adding the missing indentation to the
                 //   final break so that the final comment is as indented as the
                 //   list's items.
@@ -2122,6 +2450,45 @@ impl<'comments> Formatter<'comments> {
         }
     }
 
+    /// Pretty prints a list comprehension. If it doesn't fit on a single line
+    /// the qualifiers are aligned after the `|`:
+    ///
+    /// ```gleam
+    /// [
+    ///   x * 2
+    ///   | x <- items,
+    ///     x > 0
+    /// ]
+    /// ```
+    ///
+    fn list_comprehension<'a>(
+        &mut self,
+        element: &'a UntypedExpr,
+        qualifiers: &'a [ComprehensionQualifier],
+    ) -> Document<'a> {
+        let element = self.expr(element).group();
+        let qualifiers = join(
+            qualifiers.iter().map(|qualifier| match qualifier {
+                ComprehensionQualifier::Generator { name, list, .. } => {
+                    docvec![name, " <- ", self.expr(list).group()]
+                }
+                ComprehensionQualifier::Filter(condition) => self.expr(condition).group(),
+            }),
+            break_(",", ", "),
+        )
+        .nest(self.indent);
+
+        break_("[", "[")
+            .append(element)
+            .append(break_("", " "))
+            .append("| ")
+            .append(qualifiers)
+            .nest(self.indent)
+            .append(break_("", ""))
+            .append("]")
+            .group()
+    }
+
     /// Pretty prints an expression to be used in a comma separated list; for
     /// example as a list item, a tuple item or as an argument of a function call.
     fn comma_separated_item<'a>(
@@ -2141,7 +2508,7 @@ impl<'comments> Formatter<'comments> {
                 let doc = self.bin_op(name, left, right, true).group();
                 commented(doc, comments)
             }
-            UntypedExpr::PipeLine { expressions } if siblings > 1 => {
+            UntypedExpr::PipeLine { expressions, .. } if siblings > 1 => {
                 let comments = self.pop_comments(expression.start_byte_index());
                 let doc = self.pipeline(expressions, true).group();
                 commented(doc, comments)
@@ -2159,13 +2526,14 @@ impl<'comments> Formatter<'comments> {
 
             Pattern::String { value, .. } => self.string(value),
 
-            Pattern::Variable { name, .. } => name.to_doc(),
+            Pattern::Variable { name, .. } => escaped_name(name),
 
-            Pattern::VarUsage { name, .. } => name.to_doc(),
+            Pattern::VarUsage { name, .. } => escaped_name(name),
 
-            Pattern::Assign { name, pattern, .. } => {
-                self.pattern(pattern).append(" as ").append(name.as_str())
-            }
+            Pattern::Assign { name, pattern, .. } => self
+                .pattern(pattern)
+                .append(" as ")
+                .append(escaped_name(name)),
 
             Pattern::Discard { name, .. } => name.to_doc(),
 
@@ -2211,15 +2579,21 @@ impl<'comments> Formatter<'comments> {
             } => {
                 let left = self.string(left);
                 let right = match right {
-                    AssignName::Variable(name) => name.to_doc(),
+                    AssignName::Variable(name) => escaped_name(name),
                     AssignName::Discard(name) => name.to_doc(),
                 };
                 match left_assign {
-                    Some((name, _)) => docvec![left, " as ", name, " <> ", right],
+                    Some((name, _)) => docvec![left, " as ", escaped_name(name), " <> ", right],
                     None => docvec![left, " <> ", right],
                 }
             }
 
+            Pattern::Constant {
+                module: (module, _),
+                name,
+                ..
+            } => docvec![module, ".", escaped_name(name)],
+
             Pattern::Invalid { .. } => panic!("invalid patterns can not be in an untyped ast"),
         };
         commented(doc, comments)
@@ -2242,7 +2616,7 @@ impl<'comments> Formatter<'comments> {
         );
         let doc = break_("[", "[").append(elements);
         match tail {
-            None => doc.nest(INDENT).append(break_(",", "")),
+            None => doc.nest(self.indent).append(break_(",", "")),
 
             Some(tail) => {
                 let comments = self.pop_comments(tail.location().start);
@@ -2254,7 +2628,7 @@ impl<'comments> Formatter<'comments> {
                 let tail = commented(tail, comments);
                 doc.append(break_(",", ", "))
                     .append(tail)
-                    .nest(INDENT)
+                    .nest(self.indent)
                     .append(break_("", ""))
             }
         }
@@ -2375,7 +2749,7 @@ impl<'comments> Formatter<'comments> {
                 self.clause_guard_bin_op(&BinOp::RemainderInt, left, right)
             }
 
-            ClauseGuard::Var { name, .. } => name.to_doc(),
+            ClauseGuard::Var { name, .. } => escaped_name(name),
 
             ClauseGuard::TupleIndex { tuple, index, .. } => {
                 self.clause_guard(tuple).append(".").append(*index).to_doc()
@@ -2386,12 +2760,11 @@ impl<'comments> Formatter<'comments> {
             } => self
                 .clause_guard(container)
                 .append(".")
-                .append(label)
-                .to_doc(),
+                .append(escaped_name(label)),
 
             ClauseGuard::ModuleSelect {
                 module_name, label, ..
-            } => module_name.to_doc().append(".").append(label).to_doc(),
+            } => module_name.to_doc().append(".").append(escaped_name(label)),
 
             ClauseGuard::Constant(constant) => self.const_expr(constant),
 
@@ -2407,7 +2780,10 @@ impl<'comments> Formatter<'comments> {
 
     fn negate_bool<'a>(&mut self, expr: &'a UntypedExpr) -> Document<'a> {
         match expr {
-            UntypedExpr::BinOp { .. } => "!".to_doc().append(wrap_block(self.expr(expr))),
+            UntypedExpr::BinOp { .. } => {
+                let expr = self.expr(expr);
+                "!".to_doc().append(self.wrap_block(expr))
+            }
             _ => docvec!["!", self.expr(expr)],
         }
     }
@@ -2428,7 +2804,7 @@ impl<'comments> Formatter<'comments> {
         let call = if use_.call.is_call() {
             docvec![" ", self.expr(&use_.call)]
         } else {
-            docvec![break_("", " "), self.expr(&use_.call)].nest(INDENT)
+            docvec![break_("", " "), self.expr(&use_.call)].nest(self.indent)
         }
         .group();
 
@@ -2448,7 +2824,10 @@ impl<'comments> Formatter<'comments> {
             let left = ["use".to_doc(), break_("", " ")]
                 .into_iter()
                 .chain(assignments);
-            let left = concat(left).nest(INDENT).append(break_("", " ")).group();
+            let left = concat(left)
+                .nest(self.indent)
+                .append(break_("", " "))
+                .group();
             docvec![left, "<-", call].group()
         };
 
@@ -2474,7 +2853,7 @@ impl<'comments> Formatter<'comments> {
                 None => "<<>>".to_doc(),
                 Some(comments) => "<<"
                     .to_doc()
-                    .append(break_("", "").nest(INDENT))
+                    .append(break_("", "").nest(self.indent))
                     .append(comments)
                     .append(break_("", ""))
                     .append(">>")
@@ -2493,16 +2872,16 @@ impl<'comments> Formatter<'comments> {
         let last_break = break_(",", "");
         let doc = break_("<<", "<<")
             .append(join(segments, comma))
-            .nest(INDENT);
+            .nest(self.indent);
 
         match comments_doc {
             None => doc.append(last_break).append(">>").group(),
             Some(comments) => doc
-                .append(last_break.nest(INDENT))
+                .append(last_break.nest(self.indent))
                 // ^ Notice how in this case we nest the final break before
                 //   adding it: this way the comments are going to be as
                 //   indented as the bit array items.
-                .append(comments.nest(INDENT))
+                .append(comments.nest(self.indent))
                 .append(line())
                 .append(">>")
                 .force_break()
@@ -2514,7 +2893,10 @@ impl<'comments> Formatter<'comments> {
         match expr {
             UntypedExpr::Placeholder { .. } => panic!("Placeholders should not be formatted"),
 
-            UntypedExpr::BinOp { .. } => wrap_block(self.expr(expr)),
+            UntypedExpr::BinOp { .. } => {
+                let expr = self.expr(expr);
+                self.wrap_block(expr)
+            }
 
             UntypedExpr::Int { .. }
             | UntypedExpr::Float { .. }
@@ -2522,13 +2904,16 @@ impl<'comments> Formatter<'comments> {
             | UntypedExpr::Var { .. }
             | UntypedExpr::Fn { .. }
             | UntypedExpr::List { .. }
+            | UntypedExpr::ListComprehension { .. }
             | UntypedExpr::Call { .. }
+            | UntypedExpr::TypeApplication { .. }
             | UntypedExpr::PipeLine { .. }
             | UntypedExpr::Case { .. }
             | UntypedExpr::FieldAccess { .. }
             | UntypedExpr::Tuple { .. }
             | UntypedExpr::TupleIndex { .. }
             | UntypedExpr::Todo { .. }
+            | UntypedExpr::Hole { .. }
             | UntypedExpr::Panic { .. }
             | UntypedExpr::Echo { .. }
             | UntypedExpr::BitArray { .. }
@@ -2544,24 +2929,47 @@ impl<'comments> Formatter<'comments> {
             Statement::Expression(expression) => self.expr(expression),
             Statement::Assignment(assignment) => self.assignment(assignment),
             Statement::Use(use_) => self.use_(use_),
+            Statement::Assert(assert) => self.assert(assert),
         }
     }
 
+    fn assert<'a>(&mut self, assert: &'a UntypedAssert) -> Document<'a> {
+        let comments = self.pop_comments(assert.location.start);
+        let value = match &assert.value {
+            // Just like with `echo`, when a pipeline or a binary operation is
+            // broken on multiple lines it is nested so that it's clear the
+            // assertion is about the whole thing and not just the first line:
+            //
+            // ```gleam
+            // assert wibble
+            //   == wobble
+            // ```
+            //
+            UntypedExpr::PipeLine { .. } | UntypedExpr::BinOp { .. } => {
+                self.expr(&assert.value).nest(self.indent)
+            }
+            _ => self.expr(&assert.value),
+        };
+        let doc = docvec!["assert ", value.group()];
+        commented(doc, comments)
+    }
+
     fn block<'a>(
         &mut self,
         location: &SrcSpan,
         statements: &'a Vec1<UntypedStatement>,
         force_breaks: bool,
     ) -> Document<'a> {
-        let statements_doc = docvec![break_("", " "), self.statements(statements)].nest(INDENT);
+        let statements_doc =
+            docvec![break_("", " "), self.statements(statements)].nest(self.indent);
         let trailing_comments = self.pop_comments(location.end);
         let trailing_comments = printed_comments(trailing_comments, false);
         let block_doc = match trailing_comments {
             Some(trailing_comments_doc) => docvec![
                 "{",
                 statements_doc,
-                line().nest(INDENT),
-                trailing_comments_doc.nest(INDENT),
+                line().nest(self.indent),
+                trailing_comments_doc.nest(self.indent),
                 line(),
                 "}"
             ]
@@ -2587,7 +2995,7 @@ impl<'comments> Formatter<'comments> {
 
         let args_doc = break_("", "")
             .append(join(args, break_(",", ", ")))
-            .nest_if_broken(INDENT);
+            .nest_if_broken(self.indent);
 
         // We get all remaining comments that come before the call's closing
         // parenthesis.
@@ -2598,7 +3006,7 @@ impl<'comments> Formatter<'comments> {
         let closing_parens = match printed_comments(comments, false) {
             None => docvec![break_(",", ""), ")"],
             Some(comment) => {
-                docvec![break_(",", "").nest(INDENT), comment, line(), ")"].force_break()
+                docvec![break_(",", "").nest(self.indent), comment, line(), ")"].force_break()
             }
         };
 
@@ -2617,7 +3025,7 @@ impl<'comments> Formatter<'comments> {
                     .to_doc()
                     .append(break_("", ""))
                     .append(comments)
-                    .nest_if_broken(INDENT)
+                    .nest_if_broken(self.indent)
                     .force_break()
                     .append(break_("", ""))
                     .append(")"),
@@ -2632,12 +3040,12 @@ impl<'comments> Formatter<'comments> {
             Some(comments) => doc
                 .append(break_(",", ""))
                 .append(comments)
-                .nest_if_broken(INDENT)
+                .nest_if_broken(self.indent)
                 .force_break()
                 .append(break_("", ""))
                 .append(")"),
             None => doc
-                .nest_if_broken(INDENT)
+                .nest_if_broken(self.indent)
                 .append(break_(",", ""))
                 .append(")"),
         }
@@ -2662,12 +3070,12 @@ impl<'comments> Formatter<'comments> {
             Some(comments) => doc
                 .append(break_(",", ""))
                 .append(comments)
-                .nest_if_broken(INDENT)
+                .nest_if_broken(self.indent)
                 .force_break()
                 .append(break_("", ""))
                 .append(")"),
             None => doc
-                .nest_if_broken(INDENT)
+                .nest_if_broken(self.indent)
                 .append(break_(",", ""))
                 .append(")"),
         }
@@ -2755,7 +3163,9 @@ impl<'comments> Formatter<'comments> {
             // |> wibble
             // ```
             //
-            UntypedExpr::PipeLine { .. } => docvec!["echo ", self.expr(expression).nest(INDENT)],
+            UntypedExpr::PipeLine { .. } => {
+                docvec!["echo ", self.expr(expression).nest(self.indent)]
+            }
             _ => docvec!["echo ", self.expr(expression)],
         }
     }
@@ -2774,15 +3184,29 @@ fn init_and_last<T>(vec: &[T]) -> Option<(&[T], &T)> {
 impl<'a> Documentable<'a> for &'a ArgNames {
     fn to_doc(self) -> Document<'a> {
         match self {
-            ArgNames::Named { name, .. } | ArgNames::Discard { name, .. } => name.to_doc(),
-            ArgNames::LabelledDiscard { label, name, .. }
-            | ArgNames::NamedLabelled { label, name, .. } => {
-                docvec![label, " ", name]
+            ArgNames::Named { name, .. } => escaped_name(name),
+            ArgNames::Discard { name, .. } => name.to_doc(),
+            ArgNames::LabelledDiscard { label, name, .. } => {
+                docvec![escaped_name(label), " ", name]
+            }
+            ArgNames::NamedLabelled { label, name, .. } => {
+                docvec![escaped_name(label), " ", escaped_name(name)]
             }
         }
     }
 }
 
+/// A lowercase name, wrapped in backticks if it would otherwise be read as a
+/// keyword: `` `type` ``.
+///
+fn escaped_name(name: &str) -> Document<'_> {
+    if str_to_keyword(name).is_some() {
+        docvec!["`", name, "`"]
+    } else {
+        name.to_doc()
+    }
+}
+
 fn pub_(publicity: Publicity) -> Document<'static> {
     match publicity {
         Publicity::Public | Publicity::Internal { .. } => "pub ".to_doc(),
@@ -2792,9 +3216,9 @@ fn pub_(publicity: Publicity) -> Document<'static> {
 
 impl<'a> Documentable<'a> for &'a UnqualifiedImport {
     fn to_doc(self) -> Document<'a> {
-        self.name.as_str().to_doc().append(match &self.as_name {
+        escaped_name(&self.name).append(match &self.as_name {
             None => nil(),
-            Some(s) => " as ".to_doc().append(s.as_str()),
+            Some(s) => " as ".to_doc().append(escaped_name(s)),
         })
     }
 }
@@ -2829,22 +3253,6 @@ impl<'a> Documentable<'a> for &'a BinOp {
     }
 }
 
-pub fn break_block(doc: Document<'_>) -> Document<'_> {
-    "{".to_doc()
-        .append(line().append(doc).nest(INDENT))
-        .append(line())
-        .append("}")
-        .force_break()
-}
-
-pub fn wrap_block(doc: Document<'_>) -> Document<'_> {
-    break_("{", "{ ")
-        .append(doc)
-        .nest(INDENT)
-        .append(break_("", " "))
-        .append("}")
-}
-
 fn printed_comments<'a, 'comments>(
     comments: impl IntoIterator<Item = Option<&'comments str>>,
     trailing_newline: bool,
@@ -3090,8 +3498,18 @@ fn constant_call_arg_formatting<A, B>(
 struct AttributesPrinter<'a> {
     external_erlang: &'a Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    external_erlang_arguments: &'a Option<Box<ExternalArguments>>,
+    external_javascript_arguments: &'a Option<Box<ExternalArguments>>,
     deprecation: &'a Deprecation,
     internal: bool,
+    purity: Purity,
+    benchmark: bool,
+    memoize: bool,
+    should_panic: &'a Option<ShouldPanic>,
+    since: &'a Option<EcoString>,
+    capabilities: &'a [(Capability, SrcSpan)],
+    doc: &'a [EcoString],
+    indent: isize,
 }
 
 impl<'a> AttributesPrinter<'a> {
@@ -3099,24 +3517,38 @@ impl<'a> AttributesPrinter<'a> {
         Self {
             external_erlang: &None,
             external_javascript: &None,
+            external_erlang_arguments: &None,
+            external_javascript_arguments: &None,
             deprecation: &Deprecation::NotDeprecated,
             internal: false,
+            purity: Purity::Unknown,
+            benchmark: false,
+            memoize: false,
+            should_panic: &None,
+            since: &None,
+            capabilities: &[],
+            doc: &[],
+            indent: DEFAULT_INDENT,
         }
     }
 
     pub fn set_external_erlang(
         mut self,
         external: &'a Option<(EcoString, EcoString, SrcSpan)>,
+        arguments: &'a Option<Box<ExternalArguments>>,
     ) -> Self {
         self.external_erlang = external;
+        self.external_erlang_arguments = arguments;
         self
     }
 
     pub fn set_external_javascript(
         mut self,
         external: &'a Option<(EcoString, EcoString, SrcSpan)>,
+        arguments: &'a Option<Box<ExternalArguments>>,
     ) -> Self {
         self.external_javascript = external;
+        self.external_javascript_arguments = arguments;
         self
     }
 
@@ -3129,24 +3561,114 @@ impl<'a> AttributesPrinter<'a> {
         self.deprecation = deprecation;
         self
     }
+
+    pub fn set_purity(mut self, purity: Purity) -> Self {
+        self.purity = purity;
+        self
+    }
+
+    pub fn set_benchmark(mut self, benchmark: bool) -> Self {
+        self.benchmark = benchmark;
+        self
+    }
+
+    pub fn set_memoize(mut self, memoize: bool) -> Self {
+        self.memoize = memoize;
+        self
+    }
+
+    pub fn set_should_panic(mut self, should_panic: &'a Option<ShouldPanic>) -> Self {
+        self.should_panic = should_panic;
+        self
+    }
+
+    pub fn set_since(mut self, since: &'a Option<EcoString>) -> Self {
+        self.since = since;
+        self
+    }
+
+    pub fn set_capabilities(mut self, capabilities: &'a [(Capability, SrcSpan)]) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Sets the strings of the `@doc` attribute, along with the indentation
+    /// used when they have to be split over multiple lines.
+    pub fn set_doc(mut self, doc: &'a [EcoString], indent: isize) -> Self {
+        self.doc = doc;
+        self.indent = indent;
+        self
+    }
+}
+
+/// The list of arguments of an `@external` attribute, if it has one:
+/// `, [fun, initial, list]`.
+///
+fn external_arguments(arguments: &Option<Box<ExternalArguments>>) -> Document<'_> {
+    match arguments {
+        Some(arguments) => {
+            let names = arguments.names.iter().map(|(_, name)| escaped_name(name));
+            docvec![", [", join(names, ", ".to_doc()), "]"]
+        }
+        None => nil(),
+    }
 }
 
 impl<'a> Documentable<'a> for AttributesPrinter<'a> {
     fn to_doc(self) -> Document<'a> {
         let mut attributes = vec![];
 
+        // @doc attribute. Strings joined with `<>` are always put on their own
+        // line, as that's what they're split for.
+        match self.doc {
+            [] => (),
+            [string] => attributes.push(docvec!["@doc(\"", string, "\")"]),
+            strings => {
+                let strings = strings.iter().map(|string| docvec!["\"", string, "\""]);
+                attributes.push(docvec![
+                    "@doc(",
+                    docvec![line(), join(strings, docvec![line(), "<> "])].nest(self.indent),
+                    line(),
+                    ")"
+                ]);
+            }
+        }
+
         // @deprecated attribute
         if let Deprecation::Deprecated { message } = self.deprecation {
             attributes.push(docvec!["@deprecated(\"", message, "\")"])
         };
 
+        // @since attribute
+        if let Some(version) = self.since {
+            attributes.push(docvec!["@since(\"", version, "\")"])
+        };
+
         // @external attributes
         if let Some((m, f, _)) = self.external_erlang {
-            attributes.push(docvec!["@external(erlang, \"", m, "\", \"", f, "\")"])
+            let arguments = external_arguments(self.external_erlang_arguments);
+            attributes.push(docvec![
+                "@external(erlang, \"",
+                m,
+                "\", \"",
+                f,
+                "\"",
+                arguments,
+                ")"
+            ])
         };
 
         if let Some((m, f, _)) = self.external_javascript {
-            attributes.push(docvec!["@external(javascript, \"", m, "\", \"", f, "\")"])
+            let arguments = external_arguments(self.external_javascript_arguments);
+            attributes.push(docvec![
+                "@external(javascript, \"",
+                m,
+                "\", \"",
+                f,
+                "\"",
+                arguments,
+                ")"
+            ])
         };
 
         // @internal attribute
@@ -3154,6 +3676,41 @@ impl<'a> Documentable<'a> for AttributesPrinter<'a> {
             attributes.push("@internal".to_doc());
         };
 
+        // @pure attribute
+        if self.purity.is_pure() {
+            attributes.push("@pure".to_doc());
+        };
+
+        // @benchmark attribute
+        if self.benchmark {
+            attributes.push("@benchmark".to_doc());
+        };
+
+        // @memoize attribute
+        if self.memoize {
+            attributes.push("@memoize".to_doc());
+        };
+
+        // @test attribute
+        match self.should_panic {
+            Some(ShouldPanic {
+                message: Some(message),
+            }) => attributes.push(docvec!["@test(should_panic, \"", message, "\")"]),
+            Some(ShouldPanic { message: None }) => attributes.push("@test(should_panic)".to_doc()),
+            None => (),
+        };
+
+        // @implements attributes
+        for (capability, _) in self.capabilities {
+            attributes.push(docvec![
+                "@",
+                capability.attribute(),
+                "(",
+                capability.name(),
+                ")"
+            ]);
+        }
+
         if attributes.is_empty() {
             nil()
         } else {
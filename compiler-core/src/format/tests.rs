@@ -12,8 +12,15 @@ mod external_types;
 mod function;
 mod guards;
 mod imports;
+mod indentation;
+mod line_width;
+mod list_comprehension;
 mod pipeline;
+mod range;
+mod raw_identifiers;
 mod record_update;
+mod round_trip;
+mod trailing_block;
 mod tuple;
 mod use_;
 
@@ -5762,6 +5769,238 @@ pub const wibble = 1
     );
 }
 
+#[test]
+fn pure_attribute_on_function() {
+    assert_format!(
+        r#"@pure
+pub fn main() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn pure_attribute_with_other_attributes() {
+    assert_format!(
+        r#"@deprecated("wobble")
+@internal
+@pure
+pub fn main() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn benchmark_attribute_on_function() {
+    assert_format!(
+        r#"@benchmark
+pub fn sort_list() {
+  list.sort(numbers, int.compare)
+}
+"#
+    );
+}
+
+#[test]
+fn benchmark_attribute_with_other_attributes() {
+    assert_format!(
+        r#"@internal
+@pure
+@benchmark
+pub fn sort_list() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn memoize_attribute_on_function() {
+    assert_format!(
+        r#"@memoize
+pub fn fib(n: Int) -> Int {
+  fib(n - 1) + fib(n - 2)
+}
+"#
+    );
+}
+
+#[test]
+fn memoize_attribute_with_other_attributes() {
+    assert_format!(
+        r#"@internal
+@pure
+@memoize
+pub fn fib(n: Int) -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn test_attribute_expecting_panic() {
+    assert_format!(
+        r#"@test(should_panic)
+pub fn wibble_test() {
+  panic
+}
+"#
+    );
+}
+
+#[test]
+fn test_attribute_expecting_panic_with_message() {
+    assert_format!(
+        r#"@internal
+@test(should_panic, "\"wibble\" and wobble")
+pub fn wibble_test() {
+  panic
+}
+"#
+    );
+}
+
+#[test]
+fn since_attribute() {
+    assert_format!(
+        r#"@deprecated("Use wobble instead")
+@since("1.2.0")
+@internal
+pub fn wibble() -> Int {
+  1
+}
+
+@since("0.1.0")
+pub const wobble = 1
+"#
+    );
+}
+
+#[test]
+fn doc_attribute() {
+    assert_format!(
+        r#"/// Wibble
+@doc("Wobble")
+@deprecated("Use wobble instead")
+pub fn wibble() -> Int {
+  1
+}
+
+@doc("The \"wobble\" constant")
+pub const wobble = 1
+
+@doc("Wubble")
+pub type Wubble {
+  Wubble
+}
+
+@doc("An alias")
+pub type Alias =
+  Int
+"#
+    );
+}
+
+#[test]
+fn doc_attribute_with_multiple_strings() {
+    assert_format!(
+        r#"@doc(
+  "This is a long piece of documentation, "
+  <> "split over multiple lines."
+)
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn doc_attribute_with_multiple_strings_on_one_line() {
+    assert_format_rewrite!(
+        r#"@doc("Wibble " <> "wobble")
+pub fn wibble() -> Int {
+  1
+}
+"#,
+        r#"@doc(
+  "Wibble "
+  <> "wobble"
+)
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn implements_attribute_on_type() {
+    assert_format!(
+        r#"@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+"#
+    );
+}
+
+#[test]
+fn implements_attribute_with_other_attributes() {
+    assert_format!(
+        r#"@internal
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+"#
+    );
+}
+
+#[test]
+fn derive_attribute_on_type() {
+    assert_format!(
+        r#"@implements(comparable)
+@derive(json)
+pub type Size {
+  Small
+  Large
+}
+"#
+    );
+}
+
+#[test]
+fn type_application() {
+    assert_format!(
+        r#"pub fn main() {
+  list.new<Int>()
+  pair<Int, #(String, fn() -> Nil)>
+}
+"#
+    );
+}
+
+#[test]
+fn comparison_with_no_spaces_is_not_a_type_application() {
+    assert_format_rewrite!(
+        r#"pub fn main() {
+  a<b
+}
+"#,
+        r#"pub fn main() {
+  a < b
+}
+"#
+    );
+}
+
 #[test]
 fn comments_inside_contant_list() {
     assert_format!(
@@ -6540,3 +6779,50 @@ fn echo_printing_one_line_pipeline() {
 "#
     );
 }
+
+#[test]
+fn assert_statement() {
+    assert_format!(
+        "pub fn main() {
+  let x = 1
+  assert x > 0
+  assert is_even(x) || x == 1
+}
+"
+    );
+}
+
+#[test]
+fn assert_statement_long_value() {
+    assert_format!(
+        "pub fn main() {
+  assert wibble_wobble_wibble_wobble(1, 2, 3)
+    == wibble_wobble_wibble_wobble_wibble_wobble(4, 5, 6)
+}
+"
+    );
+}
+
+#[test]
+fn assert_statement_pipeline() {
+    assert_format!(
+        "pub fn main() {
+  assert wibble_wobble_wibble_wobble
+    |> wibble_wobble_wibble_wobble_wibble_wobble
+    |> wibble_wobble_wibble_wobble_wibble_wobble
+}
+"
+    );
+}
+
+#[test]
+fn typed_holes() {
+    assert_format!(
+        r#"pub fn main() {
+  let x = _
+  wibble(_value, x)
+  |> _
+}
+"#
+    );
+}
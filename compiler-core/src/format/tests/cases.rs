@@ -1,4 +1,4 @@
-use crate::assert_format;
+use crate::{assert_format, assert_format_rewrite};
 
 #[test]
 fn case_with_two_long_subjects() {
@@ -193,3 +193,84 @@ fn case_in_call_is_not_broken_if_it_goes_over_the_limit_with_branches() {
 "#
     );
 }
+
+#[test]
+fn block_bodied_clause_with_bindings() {
+    assert_format!(
+        r#"fn main() {
+  case x {
+    Ok(x) if x > 1 -> {
+      let y = x + 1
+      y * 2
+    }
+    _ -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn block_bodied_clause_on_a_single_line_is_broken() {
+    assert_format_rewrite!(
+        r#"fn main() {
+  case x {
+    Ok(x) -> { let y = x + 1 y * 2 }
+    _ -> 0
+  }
+}
+"#,
+        r#"fn main() {
+  case x {
+    Ok(x) -> {
+      let y = x + 1
+      y * 2
+    }
+    _ -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn comment_before_block_bodied_clause_is_moved_inside_the_block() {
+    assert_format_rewrite!(
+        r#"fn main() {
+  case x {
+    Ok(x) ->
+      // The next value
+      { let y = x + 1 y }
+    _ -> 0
+  }
+}
+"#,
+        r#"fn main() {
+  case x {
+    Ok(x) -> {
+      // The next value
+      let y = x + 1
+      y
+    }
+    _ -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn constant_patterns() {
+    assert_format!(
+        r#"import numbers
+
+pub fn main() {
+  case x {
+    numbers.zero -> 0
+    [numbers.one, ..] | #(numbers.two, _) as value -> 1
+    _ -> 2
+  }
+}
+"#
+    );
+}
@@ -47,3 +47,57 @@ fn wibble() {
 "
     );
 }
+
+#[test]
+fn when_feature() {
+    assert_format!(
+        r#"@when(feature: "experimental")
+pub fn wibble() {
+  todo
+}
+
+@when(feature: "experimental")
+import wibble/wobble
+
+@when(feature: "experimental")
+pub type Wibble {
+  Wibble
+}
+
+@when(feature: "experimental")
+const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn when_feature_and_target() {
+    assert_format!(
+        r#"@target(erlang)
+@when(feature: "experimental")
+pub fn wibble() {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn when_feature_and_other_attributes() {
+    assert_format_rewrite!(
+        r#"@when(feature: "experimental")
+@deprecated("Use wobble instead")
+@target(erlang)
+pub fn wibble() {
+  todo
+}
+"#,
+        r#"@target(erlang)
+@when(feature: "experimental")
+@deprecated("Use wobble instead")
+pub fn wibble() {
+  todo
+}
+"#
+    );
+}
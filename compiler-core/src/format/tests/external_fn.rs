@@ -1,4 +1,4 @@
-use crate::assert_format;
+use crate::{assert_format, assert_format_rewrite};
 
 #[test]
 fn no_body_erlang() {
@@ -90,3 +90,25 @@ pub fn register(
 "#
     );
 }
+
+#[test]
+fn external_with_reordered_arguments() {
+    assert_format!(
+        r#"@external(erlang, "lists", "foldl", [fun, initial, list])
+@external(javascript, "./ffi.mjs", "fold", [list, fun, initial])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+    );
+}
+
+#[test]
+fn external_with_reordered_arguments_trailing_comma_is_removed() {
+    assert_format_rewrite!(
+        r#"@external(erlang, "lists", "foldl", [fun, initial, list,],)
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#,
+        r#"@external(erlang, "lists", "foldl", [fun, initial, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+    );
+}
@@ -330,3 +330,38 @@ fn comment_middle_of_inline_function_body() {
 "#
     );
 }
+
+#[test]
+fn comments_between_call_arguments_are_kept() {
+    assert_format!(
+        r#"pub fn main() {
+  wibble(
+    // first
+    1,
+    // second
+    fn(x) { x },
+    // after last
+  )
+}
+"#
+    );
+}
+
+#[test]
+fn comment_after_call_argument_breaks_the_call() {
+    assert_format_rewrite!(
+        r#"pub fn main() {
+  wibble(1, // after first
+  2)
+}
+"#,
+        r#"pub fn main() {
+  wibble(
+    1,
+    // after first
+    2,
+  )
+}
+"#,
+    );
+}
@@ -218,3 +218,20 @@ import gleam/list
 "#
     );
 }
+
+#[test]
+fn importing_all_types() {
+    assert_format!(
+        "import one/two.{type *}
+"
+    );
+}
+
+#[test]
+fn importing_all_types_comes_before_other_types() {
+    assert_format_rewrite!(
+        "import one/two.{abc, type Abc, type *, Abc}",
+        "import one/two.{type *, type Abc, Abc, abc}
+"
+    );
+}
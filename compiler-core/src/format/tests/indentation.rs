@@ -0,0 +1,108 @@
+use pretty_assertions::assert_eq;
+
+use crate::format::{DEFAULT_INDENT, pretty_with_indent};
+
+fn format_with_indent(src: &str, indent: isize) -> String {
+    let mut writer = String::new();
+    pretty_with_indent(
+        &mut writer,
+        &src.into(),
+        camino::Utf8Path::new("<stdin>"),
+        indent,
+    )
+    .unwrap();
+    writer
+}
+
+const SRC: &str = r#"pub type Wibble { Wibble(a: Int) Wobble }
+pub fn main(x) { case x { Wibble(a) -> { let b = a + 1 [a, b] } Wobble -> [] } }
+"#;
+
+#[test]
+fn default_indentation_is_two_spaces() {
+    assert_eq!(DEFAULT_INDENT, 2);
+    let mut writer = String::new();
+    crate::format::pretty(&mut writer, &SRC.into(), camino::Utf8Path::new("<stdin>")).unwrap();
+    assert_eq!(writer, format_with_indent(SRC, 2));
+}
+
+#[test]
+fn two_spaces_indentation() {
+    assert_eq!(
+        format_with_indent(SRC, 2),
+        r#"pub type Wibble {
+  Wibble(a: Int)
+  Wobble
+}
+
+pub fn main(x) {
+  case x {
+    Wibble(a) -> {
+      let b = a + 1
+      [a, b]
+    }
+    Wobble -> []
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn four_spaces_indentation() {
+    assert_eq!(
+        format_with_indent(SRC, 4),
+        r#"pub type Wibble {
+    Wibble(a: Int)
+    Wobble
+}
+
+pub fn main(x) {
+    case x {
+        Wibble(a) -> {
+            let b = a + 1
+            [a, b]
+        }
+        Wobble -> []
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn four_spaces_indentation_of_broken_arguments() {
+    assert_eq!(
+        format_with_indent(
+            "pub fn main() { wibble(wobble_wobble_wobble, wubble_wubble_wubble, wabble_wabble_wabble_wabble) }\n",
+            4
+        ),
+        r#"pub fn main() {
+    wibble(
+        wobble_wobble_wobble,
+        wubble_wubble_wubble,
+        wabble_wabble_wabble_wabble,
+    )
+}
+"#
+    );
+}
+
+#[test]
+fn four_spaces_indentation_of_broken_list_comprehension() {
+    assert_eq!(
+        format_with_indent(
+            "pub fn main() { [wibble_wobble(x, y) | x <- some_very_long_list_of_items, y <- another_list_of_items, x != y] }\n",
+            4
+        ),
+        r#"pub fn main() {
+    [
+        wibble_wobble(x, y)
+        | x <- some_very_long_list_of_items,
+            y <- another_list_of_items,
+            x != y
+    ]
+}
+"#
+    );
+}
@@ -0,0 +1,54 @@
+use pretty_assertions::assert_eq;
+
+use crate::format::{DEFAULT_INDENT, DEFAULT_LINE_WIDTH, pretty_with_options};
+
+fn format_with_line_width(src: &str, line_width: isize) -> String {
+    let mut writer = String::new();
+    pretty_with_options(
+        &mut writer,
+        &src.into(),
+        camino::Utf8Path::new("<stdin>"),
+        DEFAULT_INDENT,
+        line_width,
+    )
+    .unwrap();
+    writer
+}
+
+const SRC: &str = r#"pub fn main() {
+  wibble(wobble_wobble_wobble, wubble_wubble_wubble, wabble_wabble_wabble_wabble)
+  |> wibble_wobble(wubble_wubble_wubble_wubble, wabble_wabble_wabble_wabble_wabble)
+}
+"#;
+
+#[test]
+fn default_line_width_is_eighty() {
+    assert_eq!(DEFAULT_LINE_WIDTH, 80);
+    let mut writer = String::new();
+    crate::format::pretty(&mut writer, &SRC.into(), camino::Utf8Path::new("<stdin>")).unwrap();
+    assert_eq!(writer, format_with_line_width(SRC, 80));
+}
+
+#[test]
+fn wide_call_at_line_width_80() {
+    assert_eq!(
+        format_with_line_width(SRC, 80),
+        r#"pub fn main() {
+  wibble(
+    wobble_wobble_wobble,
+    wubble_wubble_wubble,
+    wabble_wabble_wabble_wabble,
+  )
+  |> wibble_wobble(
+    wubble_wubble_wubble_wubble,
+    wabble_wabble_wabble_wabble_wabble,
+  )
+}
+"#
+    );
+}
+
+#[test]
+fn wide_call_at_line_width_120() {
+    assert_eq!(format_with_line_width(SRC, 120), SRC);
+}
@@ -0,0 +1,50 @@
+use crate::{assert_format, assert_format_rewrite};
+
+#[test]
+fn list_comprehension() {
+    assert_format!(
+        r#"pub fn main() {
+  [x * 2 | x <- items]
+}
+"#
+    );
+}
+
+#[test]
+fn list_comprehension_with_filters_and_generators() {
+    assert_format!(
+        r#"pub fn main() {
+  [#(x, y) | x <- xs, x > 0, y <- ys, x != y]
+}
+"#
+    );
+}
+
+#[test]
+fn long_list_comprehension_is_broken() {
+    assert_format!(
+        r#"pub fn main() {
+  [
+    wibble_wobble(x, y)
+    | x <- some_very_long_list_of_items,
+      y <- another_list_of_items,
+      x != y
+  ]
+}
+"#
+    );
+}
+
+#[test]
+fn list_comprehension_spacing_is_normalised() {
+    assert_format_rewrite!(
+        r#"pub fn main() {
+  [ x|x<-items,x>0, ]
+}
+"#,
+        r#"pub fn main() {
+  [x | x <- items, x > 0]
+}
+"#
+    );
+}
@@ -132,3 +132,86 @@ fn main() {
 "
     );
 }
+
+#[test]
+fn comments_between_pipeline_steps_are_kept() {
+    assert_format!(
+        r#"pub fn main() {
+  x
+  // before echo
+  |> echo
+  // before capture
+  |> wibble(1, _)
+  // before anonymous function
+  |> fn(a) { a }
+  // before module function
+  |> list.map(wobble)
+}
+"#
+    );
+}
+
+#[test]
+fn comment_after_pipe_operator_is_moved_before_the_step() {
+    assert_format_rewrite!(
+        r#"pub fn main() {
+  x
+  |> // after pipe
+  wibble
+  |>
+  // before step
+  wobble
+}
+"#,
+        r#"pub fn main() {
+  x
+  // after pipe
+  |> wibble
+  // before step
+  |> wobble
+}
+"#,
+    );
+}
+
+#[test]
+fn comments_between_steps_of_nested_pipelines_are_kept() {
+    assert_format!(
+        r#"pub fn main() {
+  let y =
+    x
+    // between steps
+    |> wobble
+
+  y
+  |> wibble(
+    a
+    // inner
+    |> b,
+  )
+  // outer
+  |> fn(x) {
+    x
+    // in function
+    |> wubble
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn comments_in_arguments_of_pipeline_steps_are_kept() {
+    assert_format!(
+        r#"pub fn main() {
+  x
+  |> wibble
+  |> wobble(
+    // first
+    1,
+    // last
+  )
+}
+"#
+    );
+}
@@ -0,0 +1,156 @@
+use ecow::EcoString;
+use pretty_assertions::assert_eq;
+
+use crate::ast::SrcSpan;
+
+/// Formats the definitions of `src` overlapping with the first occurrence of
+/// `selection`, returning the whole source code with the edit applied.
+///
+fn format_selection(src: &str, selection: &str) -> String {
+    let start = src.find(selection).expect("selection in source") as u32;
+    let range = SrcSpan::new(start, start + selection.len() as u32);
+    let src = EcoString::from(src);
+    match crate::format::range_to_string(&src, "<stdin>".into(), range).unwrap() {
+        None => src.to_string(),
+        Some((span, replacement)) => format!(
+            "{}{replacement}{}",
+            &src[..span.start as usize],
+            &src[span.end as usize..]
+        ),
+    }
+}
+
+#[test]
+fn format_single_function() {
+    assert_eq!(
+        format_selection(
+            r#"import gleam/io
+pub fn   wibble( ) { 1+2 }
+
+
+
+pub fn wobble() {
+  io.println(  "Hello"  )
+}
+pub fn   wubble( ) { 1+2 }
+"#,
+            "io.println"
+        ),
+        r#"import gleam/io
+pub fn   wibble( ) { 1+2 }
+
+
+
+pub fn wobble() {
+  io.println("Hello")
+}
+pub fn   wubble( ) { 1+2 }
+"#
+    );
+}
+
+#[test]
+fn format_function_keeps_its_comments_and_attributes() {
+    assert_eq!(
+        format_selection(
+            r#"pub fn   wibble( ) { 1+2 }
+
+// A comment
+/// Some documentation
+@deprecated("Use something else")
+pub fn wobble(  ) {
+  // Inside
+  1+2
+}
+
+pub fn   wubble( ) { 1+2 }
+"#,
+            "Inside"
+        ),
+        r#"pub fn   wibble( ) { 1+2 }
+
+// A comment
+/// Some documentation
+@deprecated("Use something else")
+pub fn wobble() {
+  // Inside
+  1 + 2
+}
+
+pub fn   wubble( ) { 1+2 }
+"#
+    );
+}
+
+#[test]
+fn format_selection_over_several_definitions() {
+    assert_eq!(
+        format_selection(
+            r#"pub fn   wibble( ) { 1+2 }
+pub const   one=1
+pub type   Wobble{Wobble(a:Int)}
+type   Alias=Int
+pub fn   wubble( ) { 1+2 }
+"#,
+            "one=1\npub type   Wobble{Wobble(a:Int)}\ntype   Alias"
+        ),
+        r#"pub fn   wibble( ) { 1+2 }
+pub const one = 1
+
+pub type Wobble {
+  Wobble(a: Int)
+}
+
+type Alias =
+  Int
+pub fn   wubble( ) { 1+2 }
+"#
+    );
+}
+
+#[test]
+fn format_first_definition_after_module_comments() {
+    assert_eq!(
+        format_selection(
+            r#"//// Module documentation
+
+pub fn   wibble( ) { 1+2 }
+pub fn   wubble( ) { 1+2 }
+"#,
+            "wibble"
+        ),
+        r#"//// Module documentation
+
+pub fn wibble() {
+  1 + 2
+}
+pub fn   wubble( ) { 1+2 }
+"#
+    );
+}
+
+#[test]
+fn format_empty_selection_inside_definition() {
+    assert_eq!(
+        format_selection(
+            r#"pub fn   wibble( ) { 1+2 }
+pub fn   wubble( ) { 1+2 }
+"#,
+            ""
+        ),
+        r#"pub fn wibble() {
+  1 + 2
+}
+pub fn   wubble( ) { 1+2 }
+"#
+    );
+}
+
+#[test]
+fn selection_outside_of_definitions_is_not_formatted() {
+    let src = r#"import   gleam/io
+
+pub fn   wibble( ) { 1+2 }
+"#;
+    assert_eq!(format_selection(src, "gleam/io"), src);
+}
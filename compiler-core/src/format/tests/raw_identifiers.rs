@@ -0,0 +1,80 @@
+use crate::{assert_format, assert_format_rewrite};
+
+#[test]
+fn raw_identifier_function_name() {
+    assert_format!(
+        r#"@external(erlang, "wibble", "type")
+pub fn `type`(value: a) -> String
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_labels_and_arguments() {
+    assert_format!(
+        r#"pub type Token {
+  Token(`type`: String, `fn`: Int)
+}
+
+pub fn new(`type` `type`: String, `fn` `fn`: Int) -> Token {
+  let token = Token(`type`:, `fn`:)
+  Token(..token, `type`: `type`)
+}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_field_access() {
+    assert_format!(
+        r#"pub fn main(token) {
+  token.`type`
+}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_patterns() {
+    assert_format!(
+        r#"pub fn main(x) {
+  case x {
+    Token(`type`:, ..) if `type` == "wibble" -> `type`
+    [`let`, ..] as `case` -> `let`
+    _ -> ""
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_unqualified_import() {
+    assert_format!(
+        r#"import wibble.{`type`, wobble as `use`}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_external_arguments() {
+    assert_format!(
+        r#"@external(erlang, "wibble", "wobble", [`fn`, list])
+pub fn wobble(list: List(a), `fn`: fn(a) -> a) -> List(a)
+"#
+    );
+}
+
+#[test]
+fn unneeded_raw_identifiers_are_removed() {
+    assert_format_rewrite!(
+        r#"pub fn `main`(`x`) {
+  `x`
+}
+"#,
+        r#"pub fn main(x) {
+  x
+}
+"#
+    );
+}
@@ -0,0 +1,91 @@
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+
+use crate::{
+    format::{module_to_string, statements_to_string},
+    parse::{parse_module, parse_statement_sequence},
+    warning::WarningEmitter,
+};
+
+/// Small programs that are unformatted (or formatted in a slightly unusual
+/// way) so that formatting them has some work to do.
+const MODULES: &[&str] = &[
+    "",
+    "import gleam/io\npub fn main() { io.println(\"Hello\") }",
+    "// A comment\npub type Wibble { Wibble(a: Int)  Wobble }",
+    "pub const x = [1,2,3]\n\n\n\nconst y: #(Int, Float) = #(1, 2.0)",
+    "pub fn main(x) { case x { 1 | 2 if x > 1 -> \"a\" _ -> { \"b\" } } }",
+    "/// Docs\npub fn wibble(a a: Int, b b) -> Int { a |> int.add(b) |> fn(x) { x * 2 } }",
+    "fn main() { use x <- result.try(Ok(1))\n  let assert Ok(y) = x as \"oops\"\n  echo y }",
+    "type Alias(a) = List(#(a, a))\n@external(erlang, \"m\", \"f\") fn f() -> Nil",
+    "fn bits() { <<1:size(8), \"a\":utf8, rest:bits>> }",
+    "fn main() { let r = Record(..r, a: 1, b:) { r.a }.b }",
+];
+
+const STATEMENTS: &[&str] = &[
+    "1 + 2",
+    "let x = 1\nx * x",
+    "[1, 2, 3] |> list.map(fn(x) { x + 1 })",
+    "case x { [] -> 0 [_, ..rest] -> 1 }",
+    "use x <- wibble\nx",
+];
+
+const MALFORMED: &[&str] = &[
+    "fn",
+    "pub fn main( {",
+    "pub fn main() { case x { }",
+    "\"unterminated",
+    "<<1:",
+    "import",
+    "type Wibble(",
+    "fn main() { 1 + }",
+    "fn main() { let = 1 }",
+    "]]]",
+    "@",
+    "fn main() { x.1.2.. }",
+];
+
+fn format_module(src: &str) -> String {
+    let src = EcoString::from(src);
+    let parsed = parse_module(
+        Utf8PathBuf::from("main.gleam"),
+        &src,
+        &WarningEmitter::null(),
+    )
+    .expect("should parse");
+    module_to_string(&parsed, &src).expect("should format")
+}
+
+fn format_statements(src: &str) -> String {
+    let statements = parse_statement_sequence(src).expect("should parse");
+    statements_to_string(&statements).expect("should format")
+}
+
+#[test]
+fn formatting_parsed_modules_is_idempotent() {
+    for src in MODULES {
+        let formatted = format_module(src);
+        assert_eq!(format_module(&formatted), formatted, "source: {src:?}");
+    }
+}
+
+#[test]
+fn formatting_parsed_statements_is_idempotent() {
+    for src in STATEMENTS {
+        let formatted = format_statements(src);
+        assert_eq!(format_statements(&formatted), formatted, "source: {src:?}");
+    }
+}
+
+#[test]
+fn parsing_malformed_input_returns_an_error() {
+    for src in MALFORMED {
+        let module = parse_module(
+            Utf8PathBuf::from("main.gleam"),
+            &EcoString::from(*src),
+            &WarningEmitter::null(),
+        );
+        assert!(module.is_err(), "source: {src:?}");
+        assert!(parse_statement_sequence(src).is_err(), "source: {src:?}");
+    }
+}
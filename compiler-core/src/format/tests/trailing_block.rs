@@ -0,0 +1,85 @@
+use crate::{assert_format, assert_format_rewrite};
+
+#[test]
+fn trailing_block() {
+    assert_format!(
+        r#"pub fn main() {
+  list.map(items) { x -> x + 1 }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_with_multiple_arguments() {
+    assert_format!(
+        r#"pub fn main() {
+  list.fold(items, 0) { acc, x -> acc + x }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_without_arguments() {
+    assert_format!(
+        r#"pub fn main() {
+  lazy() { -> 1 }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_with_multiple_statements() {
+    assert_format!(
+        r#"pub fn main() {
+  list.map(items) { x ->
+    let y = x + 1
+    y * 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn long_trailing_block_is_split() {
+    assert_format_rewrite!(
+        r#"pub fn main() {
+  list.map(items) { x -> wibble_wobble_wabble(x, 1) + wibble_wobble_wabble(x, 2) }
+}
+"#,
+        r#"pub fn main() {
+  list.map(items) { x ->
+    wibble_wobble_wabble(x, 1) + wibble_wobble_wabble(x, 2)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_with_comment() {
+    assert_format!(
+        r#"pub fn main() {
+  list.map(items) { x ->
+    // Increment!
+    x + 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_in_pipeline() {
+    assert_format!(
+        r#"pub fn main() {
+  items
+  |> list.map() { x -> x + 1 }
+  |> list.filter() { x -> x > 1 }
+}
+"#
+    );
+}
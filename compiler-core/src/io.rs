@@ -284,6 +284,16 @@ pub fn gleam_source_files<'a>(
     files_with_extension(io, dir, "gleam")
 }
 
+/// Iterates over Gleam module interface files (`.gleam_interface`) in a
+/// certain directory. Symlinks are followed.
+pub fn gleam_interface_files<'a>(
+    io: &'a impl FileSystemReader,
+    dir: &'a Utf8Path,
+) -> impl Iterator<Item = Utf8PathBuf> + 'a {
+    tracing::trace!("gleam_interface_files {:?}", dir);
+    files_with_extension(io, dir, "gleam_interface")
+}
+
 /// Iterates over Gleam cache files (`.cache`) in a certain directory.
 /// Symlinks are followed.
 pub fn gleam_cache_files<'a>(
@@ -448,7 +458,10 @@ pub trait TarUnpacker {
 
 #[inline]
 pub fn is_native_file_extension(extension: &str) -> bool {
-    matches!(extension, "erl" | "hrl" | "ex" | "js" | "mjs" | "ts")
+    matches!(
+        extension,
+        "erl" | "hrl" | "ex" | "js" | "mjs" | "cjs" | "ts"
+    )
 }
 
 pub fn ordered_map<S, K, V>(value: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
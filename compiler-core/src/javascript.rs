@@ -1,5 +1,7 @@
+pub mod bundle;
 mod endianness;
 mod expression;
+pub mod ffi_stubs;
 mod import;
 mod pattern;
 #[cfg(test)]
@@ -10,9 +12,10 @@ use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 
 use crate::analyse::TargetSupport;
-use crate::build::Target;
 use crate::build::package_compiler::StdlibPackage;
+use crate::build::{JavaScriptModuleFormat, Target};
 use crate::codegen::TypeScriptDeclarations;
+use crate::config::PanicHandler;
 use crate::type_::PRELUDE_MODULE_NAME;
 use crate::{
     ast::{CustomType, Function, Import, ModuleConstant, TypeAlias, *},
@@ -32,6 +35,53 @@ const INDENT: isize = 2;
 pub const PRELUDE: &str = include_str!("../templates/prelude.mjs");
 pub const PRELUDE_TS_DEF: &str = include_str!("../templates/prelude.d.mts");
 
+/// The module of the external functions that the compiler implements in the
+/// generated module itself, rather than importing them. It isn't a valid
+/// module path, so only the functions generated by the compiler can use it.
+pub const INLINED_EXTERNAL_MODULE: &str = "$inlined";
+
+/// The implementation of an external function from the
+/// `INLINED_EXTERNAL_MODULE`, which defines a function with the same name.
+///
+fn inlined_external(function: &str) -> &'static str {
+    match function {
+        "derived_json_bits_to_string" => include_str!("../templates/derived_json.mjs"),
+        _ => panic!("Unknown inlined external function {function}"),
+    }
+}
+
+/// The prelude as a CommonJS module. It is derived from the ECMAScript one,
+/// where everything is exported with a top level `export class` or
+/// `export function` definition.
+///
+pub fn commonjs_prelude() -> String {
+    let mut prelude = String::from("\"use strict\";\n\n");
+    let mut exports = vec![];
+    for line in PRELUDE.lines() {
+        let line = match line.strip_prefix("export ") {
+            Some(definition) => {
+                let name = definition
+                    .split_whitespace()
+                    .nth(1)
+                    .expect("Prelude export name")
+                    .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .next()
+                    .expect("Prelude export name");
+                exports.push(name);
+                definition
+            }
+            None => line,
+        };
+        prelude.push_str(line);
+        prelude.push('\n');
+    }
+    prelude.push_str(&format!(
+        "\nmodule.exports = {{ {} }};\n",
+        exports.join(", ")
+    ));
+    prelude
+}
+
 pub type Output<'a> = Result<Document<'a>, Error>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +93,7 @@ pub enum JavaScriptCodegenTarget {
 #[derive(Debug)]
 pub struct Generator<'a> {
     line_numbers: &'a LineNumbers,
+    src: &'a EcoString,
     module: &'a TypedModule,
     project_root: &'a Utf8Path,
     tracker: UsageTracker,
@@ -50,7 +101,14 @@ pub struct Generator<'a> {
     current_module_name_segments_count: usize,
     target_support: TargetSupport,
     typescript: TypeScriptDeclarations,
+    module_format: JavaScriptModuleFormat,
     stdlib_package: StdlibPackage,
+    /// The names of the public definitions of a CommonJS module, which are
+    /// all exported at once at the end of the module.
+    commonjs_exports: Vec<EcoString>,
+    /// The function `panic` and `todo` errors are passed to before being
+    /// thrown, if the package has one.
+    panic_handler: Option<&'a PanicHandler>,
 }
 
 impl<'a> Generator<'a> {
@@ -58,25 +116,34 @@ impl<'a> Generator<'a> {
         let ModuleConfig {
             target_support,
             typescript,
+            module_format,
             stdlib_package,
             module,
             line_numbers,
-            src: _,
+            src,
             path: _,
             project_root,
+            panic_handler,
         } = config;
         let current_module_name_segments_count = module.name.split('/').count();
+        // The handler can't have its own errors passed back to it, as they
+        // would be thrown by calling it again.
+        let panic_handler = panic_handler.filter(|handler| handler.module != module.name);
 
         Self {
             current_module_name_segments_count,
             line_numbers,
+            src,
             project_root,
             module,
             tracker: UsageTracker::default(),
             module_scope: Default::default(),
             target_support,
             typescript,
+            module_format,
             stdlib_package,
+            commonjs_exports: vec![],
+            panic_handler,
         }
     }
 
@@ -98,6 +165,10 @@ impl<'a> Generator<'a> {
     }
 
     pub fn compile(&mut self) -> Output<'a> {
+        if self.module_format == JavaScriptModuleFormat::CommonJs {
+            self.check_commonjs_externals()?;
+        }
+
         let type_reference = self.type_reference();
 
         // Determine what JavaScript imports we need to generate
@@ -146,6 +217,18 @@ impl<'a> Generator<'a> {
             self.register_prelude_usage(&mut imports, "makeError", None);
         };
 
+        match self.panic_handler {
+            Some(PanicHandler { module, function }) if self.tracker.panic_handler_used => {
+                let path = self.import_path(&self.module.type_info.package, module);
+                let member = Member {
+                    name: maybe_escape_identifier(function).to_doc(),
+                    alias: Some("$panicHandler".to_doc()),
+                };
+                imports.register_module(path, [], [member]);
+            }
+            Some(_) | None => (),
+        };
+
         if self.tracker.int_remainder_used {
             self.register_prelude_usage(&mut imports, "remainderInt", None);
         };
@@ -154,6 +237,14 @@ impl<'a> Generator<'a> {
             self.register_prelude_usage(&mut imports, "divideFloat", None);
         };
 
+        if self.tracker.memoize_used {
+            self.register_prelude_usage(&mut imports, "memoize", Some("$memoize"));
+        };
+
+        if self.tracker.should_panic_used {
+            self.register_prelude_usage(&mut imports, "shouldPanic", Some("$shouldPanic"));
+        };
+
         if self.tracker.int_division_used {
             self.register_prelude_usage(&mut imports, "divideInt", None);
         };
@@ -215,31 +306,108 @@ impl<'a> Generator<'a> {
             nil()
         };
 
+        // The externals implemented by the compiler itself are defined at the
+        // end of the module, like `echo`.
+        let templates = docvec![echo, self.inlined_externals()];
+
         // Put it all together
 
+        if self.module_format == JavaScriptModuleFormat::CommonJs {
+            return Ok(self.commonjs_module(type_reference, imports, statements, templates));
+        }
+
         if imports.is_empty() && statements.is_empty() {
-            Ok(docvec![type_reference, "export {}", line(), echo])
+            Ok(docvec![type_reference, "export {}", line(), templates])
         } else if imports.is_empty() {
             statements.push(line());
-            Ok(docvec![type_reference, statements, echo])
+            Ok(docvec![type_reference, statements, templates])
         } else if statements.is_empty() {
             Ok(docvec![
                 type_reference,
-                imports.into_doc(JavaScriptCodegenTarget::JavaScript),
-                echo,
+                imports.into_doc(
+                    JavaScriptCodegenTarget::JavaScript,
+                    JavaScriptModuleFormat::EcmaScript
+                ),
+                templates,
             ])
         } else {
             Ok(docvec![
                 type_reference,
-                imports.into_doc(JavaScriptCodegenTarget::JavaScript),
+                imports.into_doc(
+                    JavaScriptCodegenTarget::JavaScript,
+                    JavaScriptModuleFormat::EcmaScript
+                ),
                 line(),
                 statements,
                 line(),
-                echo
+                templates
             ])
         }
     }
 
+    /// Puts together a CommonJS module. Definitions declared with `const` or
+    /// `class` can't be referenced before they are defined, so everything is
+    /// exported at the very end of the module.
+    ///
+    fn commonjs_module(
+        &mut self,
+        type_reference: Document<'a>,
+        mut imports: Imports<'a>,
+        mut statements: Vec<Document<'a>>,
+        templates: Document<'a>,
+    ) -> Document<'a> {
+        let mut exports = std::mem::take(&mut self.commonjs_exports);
+        exports.extend(imports.take_exports());
+
+        let mut sections = vec![docvec![type_reference, "\"use strict\";", line()]];
+        if !imports.is_empty() {
+            sections.push(imports.into_doc(
+                JavaScriptCodegenTarget::JavaScript,
+                JavaScriptModuleFormat::CommonJs,
+            ));
+        }
+        if !statements.is_empty() {
+            statements.push(line());
+            sections.push(statements.to_doc());
+        }
+        if !exports.is_empty() {
+            sections.push(import::commonjs_exports(exports));
+        }
+        docvec![join(sections, line()), templates]
+    }
+
+    /// The definitions of the external functions implemented in this module
+    /// by the compiler instead of being imported from another module.
+    ///
+    fn inlined_externals(&self) -> Document<'a> {
+        let externals = self
+            .module
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Function(Function {
+                    external_javascript: Some((module, function, _)),
+                    ..
+                }) if module == INLINED_EXTERNAL_MODULE => Some(inlined_external(function)),
+                _ => None,
+            });
+        concat(externals.map(|external| docvec![line(), external, line()]))
+    }
+
+    /// Returns the keyword a public top level definition starts with. In
+    /// CommonJS modules there's no such keyword, so the name is recorded to
+    /// be exported at the end of the module instead.
+    ///
+    fn export_keyword(&mut self, name: EcoString) -> &'static str {
+        match self.module_format {
+            JavaScriptModuleFormat::EcmaScript => "export ",
+            JavaScriptModuleFormat::CommonJs => {
+                self.commonjs_exports.push(name);
+                ""
+            }
+        }
+    }
+
     fn register_prelude_usage(
         &self,
         imports: &mut Imports<'a>,
@@ -273,9 +441,18 @@ impl<'a> Generator<'a> {
 
             Definition::Function(function) => {
                 // If there's an external JavaScript implementation then it will be imported,
-                // so we don't need to generate a function definition.
-                if function.external_javascript.is_some() {
-                    return None;
+                // so we don't need to generate a function definition. Unless it takes its
+                // arguments in a different order, then a function reordering them is
+                // generated around it.
+                match (
+                    &function.external_javascript,
+                    &function.external_javascript_arguments,
+                ) {
+                    (Some(_), Some(arguments)) => {
+                        return Some(Ok(self.reordered_external_function(function, arguments)));
+                    }
+                    (Some(_), None) => return None,
+                    (None, _) => (),
                 }
 
                 // If the function does not support JavaScript then we don't need to generate
@@ -308,7 +485,7 @@ impl<'a> Generator<'a> {
     }
 
     fn record_definition(
-        &self,
+        &mut self,
         constructor: &'a TypedRecordConstructor,
         publicity: Publicity,
         opaque: bool,
@@ -321,12 +498,17 @@ impl<'a> Generator<'a> {
                 .to_doc()
         }
 
-        let head = if publicity.is_private() || opaque {
-            "class "
+        let export = if publicity.is_private() || opaque {
+            ""
         } else {
-            "export class "
+            self.export_keyword(constructor.name.clone())
         };
-        let head = docvec![head, &constructor.name, " extends $CustomType {"];
+        let head = docvec![
+            export,
+            "class ",
+            &constructor.name,
+            " extends $CustomType {"
+        ];
 
         if constructor.arguments.is_empty() {
             return head.append("}");
@@ -399,6 +581,26 @@ impl<'a> Generator<'a> {
                     self.register_import(&mut imports, package, module, as_name, unqualified);
                 }
 
+                // The compiler defines these functions in the module itself,
+                // so there's nothing to import.
+                Definition::Function(Function {
+                    external_javascript: Some((module, _, _)),
+                    ..
+                }) if module == INLINED_EXTERNAL_MODULE => (),
+
+                Definition::Function(Function {
+                    name: Some((_, name)),
+                    external_javascript: Some((module, function, _location)),
+                    external_javascript_arguments: Some(_),
+                    ..
+                }) => {
+                    let member = Member {
+                        name: function.to_doc(),
+                        alias: Some(reordered_external_function_alias(name)),
+                    };
+                    imports.register_module(EcoString::from(module), [], [member]);
+                }
+
                 Definition::Function(Function {
                     name: Some((_, name)),
                     publicity,
@@ -424,22 +626,45 @@ impl<'a> Generator<'a> {
         imports
     }
 
+    /// A CommonJS module can't `require` an ES module on the versions of
+    /// Node.js that need CommonJS, so externals can't be implemented in a
+    /// `.mjs` file.
+    ///
+    fn check_commonjs_externals(&self) -> Result<(), Error> {
+        for definition in &self.module.definitions {
+            match definition {
+                Definition::Function(Function {
+                    external_javascript: Some((module, _, location)),
+                    ..
+                }) if module.ends_with(".mjs") => {
+                    return Err(Error::EsModuleExternalInCommonJs {
+                        module: module.clone(),
+                        location: *location,
+                    });
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
     fn import_path(&self, package: &'a str, module: &'a str) -> EcoString {
         // TODO: strip shared prefixed between current module and imported
         // module to avoid descending and climbing back out again
+        let extension = self.module_format.extension();
         if package == self.module.type_info.package || package.is_empty() {
             // Same package
             match self.current_module_name_segments_count {
-                1 => eco_format!("./{module}.mjs"),
+                1 => eco_format!("./{module}.{extension}"),
                 _ => {
                     let prefix = "../".repeat(self.current_module_name_segments_count - 1);
-                    eco_format!("{prefix}{module}.mjs")
+                    eco_format!("{prefix}{module}.{extension}")
                 }
             }
         } else {
             // Different package
             let prefix = "../".repeat(self.current_module_name_segments_count);
-            eco_format!("{prefix}{package}/{module}.mjs")
+            eco_format!("{prefix}{package}/{module}.{extension}")
         }
     }
 
@@ -504,23 +729,67 @@ impl<'a> Generator<'a> {
         imports.register_module(EcoString::from(module), [], [member]);
     }
 
+    /// An external function taking its arguments in a different order is
+    /// imported under another name, and called by a function with the name
+    /// of the Gleam one that passes it the arguments in the expected order.
+    ///
+    fn reordered_external_function(
+        &mut self,
+        function: &'a TypedFunction,
+        external_arguments: &'a ExternalArguments,
+    ) -> Document<'a> {
+        let (_, name) = function
+            .name
+            .as_ref()
+            .expect("A module's function must be named");
+        let export = if function.publicity.is_private() {
+            ""
+        } else {
+            self.export_keyword(maybe_escape_identifier(name))
+        };
+        let call_arguments = wrap_args(
+            external_arguments
+                .names
+                .iter()
+                .map(|(_, name)| maybe_escape_identifier(name).to_doc()),
+        );
+        docvec![
+            export,
+            "function ",
+            maybe_escape_identifier(name),
+            fun_args(function.arguments.as_slice(), false),
+            " {",
+            docvec![
+                line(),
+                "return ",
+                reordered_external_function_alias(name),
+                call_arguments,
+                ";"
+            ]
+            .nest(INDENT),
+            line(),
+            "}",
+        ]
+    }
+
     fn module_constant(
         &mut self,
         publicity: Publicity,
         name: &'a EcoString,
         value: &'a TypedConstant,
     ) -> Output<'a> {
-        let head = if publicity.is_private() {
-            "const "
+        let export = if publicity.is_private() {
+            ""
         } else {
-            "export const "
+            self.export_keyword(maybe_escape_identifier(name))
         };
 
         let document =
             expression::constant_expression(Context::Constant, &mut self.tracker, value)?;
 
         Ok(docvec![
-            head,
+            export,
+            "const ",
             maybe_escape_identifier(name),
             " = ",
             document,
@@ -547,17 +816,13 @@ impl<'a> Generator<'a> {
             &self.module.type_info.src_path,
             self.project_root,
             self.line_numbers,
+            self.src,
             name.clone(),
             argument_names,
             &mut self.tracker,
             self.module_scope.clone(),
+            self.panic_handler.is_some(),
         );
-        let head = if function.publicity.is_private() {
-            "function "
-        } else {
-            "export function "
-        };
-
         let body = match generator.function_body(&function.body, function.arguments.as_slice()) {
             // No error, let's continue!
             Ok(body) => body,
@@ -573,10 +838,35 @@ impl<'a> Generator<'a> {
             Err(error) => return Some(Err(error)),
         };
 
+        let tail_recursion_used = generator.tail_recursion_used;
+        let arguments = fun_args(function.arguments.as_slice(), tail_recursion_used);
+        let export = if function.publicity.is_private() {
+            ""
+        } else {
+            self.export_keyword(maybe_escape_identifier(name))
+        };
+        let (cache, body) = if function.memoize {
+            self.tracker.memoize_used = true;
+            let cache = eco_format!("memoize${name}");
+            let key = memoize_key(function.arguments.as_slice(), tail_recursion_used);
+            let body = memoized(cache.clone(), key, body);
+            (docvec!["const ", cache, " = new Map();", lines(2)], body)
+        } else {
+            (nil(), body)
+        };
+        let body = match &function.should_panic {
+            Some(should_panic) => {
+                self.tracker.should_panic_used = true;
+                self.expecting_panic(should_panic, name, function.location, body)
+            }
+            None => body,
+        };
         let document = docvec![
-            head,
+            cache,
+            export,
+            "function ",
             maybe_escape_identifier(name.as_str()),
-            fun_args(function.arguments.as_slice(), generator.tail_recursion_used),
+            arguments,
             " {",
             docvec![line(), body].nest(INDENT).group(),
             line(),
@@ -585,6 +875,38 @@ impl<'a> Generator<'a> {
         Some(Ok(document))
     }
 
+    /// Wraps the body of a test function annotated with `@test(should_panic)`
+    /// so that it throws if the body doesn't panic, or panics with a message
+    /// that doesn't contain the expected one, using the prelude's
+    /// `shouldPanic` helper.
+    ///
+    fn expecting_panic(
+        &self,
+        should_panic: &'a ShouldPanic,
+        name: &'a str,
+        location: SrcSpan,
+        body: Document<'a>,
+    ) -> Document<'a> {
+        let message = match &should_panic.message {
+            Some(message) => expression::string(message),
+            None => "undefined".to_doc(),
+        };
+        docvec![
+            "return $shouldPanic(() => {",
+            docvec![line(), body].nest(INDENT),
+            line(),
+            "}, ",
+            message,
+            ", ",
+            self.module.name.clone().to_doc().surround("\"", "\""),
+            ", ",
+            self.line_numbers.line_number(location.start),
+            ", ",
+            name.to_doc().surround("\"", "\""),
+            ");"
+        ]
+    }
+
     fn register_module_definitions_in_scope(&mut self) {
         for statement in self.module.definitions.iter() {
             match statement {
@@ -619,9 +941,11 @@ pub struct ModuleConfig<'a> {
     pub src: &'a EcoString,
     pub target_support: TargetSupport,
     pub typescript: TypeScriptDeclarations,
+    pub module_format: JavaScriptModuleFormat,
     pub stdlib_package: StdlibPackage,
     pub path: &'a Utf8Path,
     pub project_root: &'a Utf8Path,
+    pub panic_handler: Option<&'a PanicHandler>,
 }
 
 pub fn module(config: ModuleConfig<'_>) -> Result<String, crate::Error> {
@@ -650,7 +974,14 @@ pub fn ts_declaration(
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    Unsupported { feature: String, location: SrcSpan },
+    Unsupported {
+        feature: String,
+        location: SrcSpan,
+    },
+    EsModuleExternalInCommonJs {
+        module: EcoString,
+        location: SrcSpan,
+    },
 }
 
 impl Error {
@@ -663,6 +994,14 @@ impl Error {
     }
 }
 
+/// The name an external function taking its arguments in a different order
+/// is imported with. `$` can't be used in Gleam names, so this can't clash
+/// with any of the module's definitions.
+///
+fn reordered_external_function_alias(name: &str) -> Document<'_> {
+    eco_format!("{name}$external").to_doc()
+}
+
 fn fun_args(args: &'_ [TypedArg], tail_recursion_used: bool) -> Document<'_> {
     let mut discards = 0;
     wrap_args(args.iter().map(|a| match a.get_variable_name() {
@@ -680,6 +1019,41 @@ fn fun_args(args: &'_ [TypedArg], tail_recursion_used: bool) -> Document<'_> {
     }))
 }
 
+/// The arguments used as the key of the cache of a memoized function, with
+/// the same names as in `fun_args`. Discarded arguments can't change the
+/// value returned by the function so they're not part of the key.
+///
+fn memoize_key(args: &'_ [TypedArg], tail_recursion_used: bool) -> Document<'_> {
+    let arguments = args
+        .iter()
+        .filter_map(|arg| arg.get_variable_name())
+        .map(|name| {
+            if tail_recursion_used {
+                eco_format!("loop${name}").to_doc()
+            } else {
+                maybe_escape_identifier(name).to_doc()
+            }
+        });
+    join(arguments, ", ".to_doc()).surround("[", "]")
+}
+
+/// Wraps the body of a memoized function so that it's only evaluated the
+/// first time the function is called with a given key, using the prelude's
+/// `memoize` helper and the cache declared next to the function.
+///
+fn memoized<'a>(cache: EcoString, key: Document<'a>, body: Document<'a>) -> Document<'a> {
+    docvec![
+        "return $memoize(",
+        cache,
+        ", ",
+        key,
+        ", () => {",
+        docvec![line(), body].nest(INDENT),
+        line(),
+        "});"
+    ]
+}
+
 fn wrap_args<'a, I>(args: I) -> Document<'a>
 where
     I: IntoIterator<Item = Document<'a>>,
@@ -808,7 +1182,10 @@ fn escape_identifier(word: &str) -> EcoString {
     eco_format!("{word}$")
 }
 
-fn maybe_escape_identifier(word: &str) -> EcoString {
+/// Returns the name a Gleam identifier has in the generated JavaScript code.
+/// Names that clash with JavaScript's reserved words get a `$` suffix.
+///
+pub fn maybe_escape_identifier(word: &str) -> EcoString {
     if is_usable_js_identifier(word) {
         EcoString::from(word)
     } else {
@@ -832,6 +1209,7 @@ pub(crate) struct UsageTracker {
     pub error_used: bool,
     pub int_remainder_used: bool,
     pub make_error_used: bool,
+    pub panic_handler_used: bool,
     pub custom_type_used: bool,
     pub int_division_used: bool,
     pub float_division_used: bool,
@@ -845,6 +1223,8 @@ pub(crate) struct UsageTracker {
     pub codepoint_bit_array_segment_used: bool,
     pub float_bit_array_segment_used: bool,
     pub echo_used: bool,
+    pub memoize_used: bool,
+    pub should_panic_used: bool,
 }
 
 fn bool(bool: bool) -> Document<'static> {
@@ -0,0 +1,425 @@
+//! Putting the CommonJS modules of a project together into a single
+//! JavaScript file, for it to be deployed to places where shipping a single
+//! file is easier than shipping a directory of modules.
+//!
+//! Each module is wrapped in a function that runs it the first time it is
+//! required, as Node does with CommonJS modules. These functions are given a
+//! name made from the path of their module, so the modules of different
+//! packages never collide, and each `require` call with a relative path is
+//! replaced with a call to the function of the module it refers to. Any other
+//! `require`, like the ones of the Node standard library, is left as it is.
+//!
+//! Only the modules that can be reached from the entry module are included.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use ecow::{EcoString, eco_format};
+
+use crate::{Error, Result, io::FileSystemReader};
+
+/// The function each module is wrapped with, which only runs the module the
+/// first time it is required and then returns the same exports.
+const MODULE_FUNCTION: &str = r#"function $bundle(define) {
+  let module;
+  return () => {
+    if (module === undefined) {
+      module = { exports: {} };
+      define(module, module.exports);
+    }
+    return module.exports;
+  };
+}
+"#;
+
+/// Bundles the given module of a package and all the modules it requires,
+/// found in the build directory for CommonJS JavaScript, into a single file
+/// that runs the `main` function of the module.
+///
+pub fn bundle(
+    io: &impl FileSystemReader,
+    build_directory: &Utf8Path,
+    package: &str,
+    module: &str,
+) -> Result<String> {
+    let entry = build_directory.join(package).join(format!("{module}.cjs"));
+    let mut bundler = Bundler {
+        build_directory,
+        names: HashMap::new(),
+        used_names: HashSet::new(),
+        to_bundle: VecDeque::new(),
+    };
+    let entry_name = bundler.module_name(&entry);
+
+    let mut bundle = String::from("\"use strict\";\n\n");
+    bundle.push_str(MODULE_FUNCTION);
+    while let Some(path) = bundler.to_bundle.pop_front() {
+        match path.extension() {
+            Some("cjs" | "js") => (),
+            _ => return Err(Error::CannotBundleJavaScriptModule { path }),
+        }
+        let code = bundler.replace_requires(&path, &io.read(&path)?);
+        let name = bundler.module_name(&path);
+        bundle.push_str(&format!(
+            "\nconst {name} = $bundle((module, exports) => {{\n{}\n}});\n",
+            code.trim_end()
+        ));
+    }
+    bundle.push_str(&format!("\n{entry_name}().main();\n"));
+    Ok(bundle)
+}
+
+struct Bundler<'a> {
+    build_directory: &'a Utf8Path,
+    /// The name of the function of each module found so far.
+    names: HashMap<Utf8PathBuf, EcoString>,
+    /// The names given to the functions of the modules so far.
+    used_names: HashSet<EcoString>,
+    /// The modules that have been found but not bundled yet.
+    to_bundle: VecDeque<Utf8PathBuf>,
+}
+
+impl Bundler<'_> {
+    /// Returns the name of the function a module is wrapped with, adding the
+    /// module to the ones to bundle the first time it is seen.
+    ///
+    fn module_name(&mut self, path: &Utf8Path) -> EcoString {
+        if let Some(name) = self.names.get(path) {
+            return name.clone();
+        }
+
+        let relative_path = path.strip_prefix(self.build_directory).unwrap_or(path);
+        let mut name = EcoString::from("$bundle");
+        for component in relative_path.with_extension("").components() {
+            name.push('$');
+            name.push_str(
+                &component
+                    .as_str()
+                    .chars()
+                    .map(|char| {
+                        if char.is_ascii_alphanumeric() {
+                            char
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>(),
+            );
+        }
+
+        // Files with the same name but a different extension would be given
+        // the same name, so a number is added to tell them apart.
+        if self.used_names.contains(&name) {
+            name = (2..)
+                .map(|number| eco_format!("{name}${number}"))
+                .find(|name| !self.used_names.contains(name))
+                .expect("Unused module name");
+        }
+
+        let _ = self.used_names.insert(name.clone());
+        let _ = self.names.insert(path.to_path_buf(), name.clone());
+        self.to_bundle.push_back(path.to_path_buf());
+        name
+    }
+
+    /// Replaces each `require` of a relative path in the code of a module
+    /// with a call to the function of the required module.
+    ///
+    fn replace_requires(&mut self, path: &Utf8Path, code: &str) -> String {
+        let directory = path.parent().unwrap_or(path);
+        let mut replaced = String::with_capacity(code.len());
+        let mut copied = 0;
+        for Require { start, end, path } in Requires::new(code) {
+            if !is_relative(path) {
+                continue;
+            }
+            let name = self.module_name(&normalise(&directory.join(path)));
+            replaced.push_str(&code[copied..start]);
+            replaced.push_str(&eco_format!("{name}()"));
+            copied = end;
+        }
+        replaced.push_str(&code[copied..]);
+        replaced
+    }
+}
+
+/// A call to `require` with a string literal, like `require("./wibble.cjs")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Require<'a> {
+    /// The index of the start of the call in the code.
+    start: usize,
+    /// The index of the end of the call in the code, after the closing
+    /// parenthesis.
+    end: usize,
+    path: &'a str,
+}
+
+/// The calls to `require` in some JavaScript code.
+///
+/// This is not a full JavaScript parser, but it knows enough of the syntax to
+/// skip strings, template literals, comments and regular expressions, so
+/// anything looking like a `require` inside of them is not mistaken for a
+/// call.
+///
+struct Requires<'a> {
+    code: &'a str,
+    position: usize,
+    /// Whether a `/` at the current position would start a regular expression
+    /// rather than be a division, based on the token before it.
+    regex_allowed: bool,
+    /// Whether the previous token is a `.`, in which case a name is a property
+    /// and not a variable.
+    after_dot: bool,
+    /// The number of braces opened and not closed yet in each of the template
+    /// literal substitutions, `${...}`, the current position is in.
+    substitutions: Vec<usize>,
+}
+
+impl<'a> Requires<'a> {
+    fn new(code: &'a str) -> Self {
+        Self {
+            code,
+            position: 0,
+            regex_allowed: true,
+            after_dot: false,
+            substitutions: vec![],
+        }
+    }
+
+    fn byte(&self, position: usize) -> Option<u8> {
+        self.code.as_bytes().get(position).copied()
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            match (self.byte(self.position), self.byte(self.position + 1)) {
+                (Some(byte), _) if byte.is_ascii_whitespace() => self.position += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    self.position = self.code[self.position..]
+                        .find('\n')
+                        .map_or(self.code.len(), |end| self.position + end);
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.position = self.code[self.position + 2..]
+                        .find("*/")
+                        .map_or(self.code.len(), |end| self.position + 2 + end + 2);
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Skips a string literal or a regular expression starting at the current
+    /// position, up to and including the closing delimiter.
+    fn skip_quoted(&mut self, delimiter: u8) {
+        let mut in_class = false;
+        self.position += 1;
+        while let Some(byte) = self.byte(self.position) {
+            self.position += 1;
+            match byte {
+                b'\\' => self.position += 1,
+                b'\n' => return,
+                b'[' if delimiter == b'/' => in_class = true,
+                b']' if delimiter == b'/' => in_class = false,
+                _ if byte == delimiter && !in_class => return,
+                _ => (),
+            }
+        }
+    }
+
+    /// Skips the text of a template literal, from the current position up to
+    /// and including either its closing backtick or the start of a
+    /// substitution.
+    fn skip_template(&mut self) {
+        while let Some(byte) = self.byte(self.position) {
+            self.position += 1;
+            match byte {
+                b'\\' => self.position += 1,
+                b'`' => return,
+                b'$' if self.byte(self.position) == Some(b'{') => {
+                    self.position += 1;
+                    self.substitutions.push(0);
+                    return;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn skip_name(&mut self) -> &'a str {
+        let start = self.position;
+        while self.byte(self.position).is_some_and(|byte| {
+            byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$') || byte >= 0x80
+        }) {
+            self.position += 1;
+        }
+        &self.code[start..self.position]
+    }
+
+    /// Parses the arguments of a call to `require`, `("./path")`, from the
+    /// current position. If they are not a single string literal, the position
+    /// is left unchanged.
+    fn require_arguments(&mut self) -> Option<&'a str> {
+        let start = self.position;
+        let path = self.string_argument();
+        if path.is_none() {
+            self.position = start;
+        }
+        path
+    }
+
+    fn string_argument(&mut self) -> Option<&'a str> {
+        self.skip_trivia();
+        if self.byte(self.position) != Some(b'(') {
+            return None;
+        }
+        self.position += 1;
+        self.skip_trivia();
+        let quote = self
+            .byte(self.position)
+            .filter(|byte| matches!(byte, b'"' | b'\''))?;
+        let path_start = self.position + 1;
+        self.skip_quoted(quote);
+        let path = self.code.get(path_start..self.position - 1)?;
+        if self.byte(self.position - 1) != Some(quote) || path.contains(['\\', '\n']) {
+            return None;
+        }
+        self.skip_trivia();
+        if self.byte(self.position) != Some(b')') {
+            return None;
+        }
+        self.position += 1;
+        Some(path)
+    }
+}
+
+impl<'a> Iterator for Requires<'a> {
+    type Item = Require<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.skip_trivia();
+            let start = self.position;
+            let byte = self.byte(start)?;
+            let after_dot = std::mem::replace(&mut self.after_dot, false);
+            match byte {
+                b'"' | b'\'' => {
+                    self.skip_quoted(byte);
+                    self.regex_allowed = false;
+                }
+                b'`' => {
+                    self.position += 1;
+                    self.skip_template();
+                    self.regex_allowed = false;
+                }
+                b'/' if self.regex_allowed => {
+                    self.skip_quoted(b'/');
+                    let _flags = self.skip_name();
+                    self.regex_allowed = false;
+                }
+                b'{' => {
+                    if let Some(braces) = self.substitutions.last_mut() {
+                        *braces += 1;
+                    }
+                    self.position += 1;
+                    self.regex_allowed = true;
+                }
+                b'}' => {
+                    self.position += 1;
+                    match self.substitutions.last_mut() {
+                        Some(0) => {
+                            let _ = self.substitutions.pop();
+                            self.skip_template();
+                            self.regex_allowed = false;
+                        }
+                        Some(braces) => {
+                            *braces -= 1;
+                            self.regex_allowed = true;
+                        }
+                        None => self.regex_allowed = true,
+                    }
+                }
+                b'.' if !self
+                    .byte(start + 1)
+                    .is_some_and(|byte| byte.is_ascii_digit()) =>
+                {
+                    self.position += 1;
+                    self.after_dot = true;
+                    self.regex_allowed = true;
+                }
+                b')' | b']' => {
+                    self.position += 1;
+                    self.regex_allowed = false;
+                }
+                _ if byte.is_ascii_alphanumeric()
+                    || matches!(byte, b'_' | b'$' | b'.')
+                    || byte >= 0x80 =>
+                {
+                    let name = self.skip_name();
+                    if name.is_empty() {
+                        // A number starting with a `.`, like `.5`.
+                        self.position += 1;
+                        let _ = self.skip_name();
+                    }
+                    self.regex_allowed = matches!(
+                        name,
+                        "await"
+                            | "case"
+                            | "delete"
+                            | "do"
+                            | "else"
+                            | "in"
+                            | "instanceof"
+                            | "new"
+                            | "of"
+                            | "return"
+                            | "throw"
+                            | "typeof"
+                            | "void"
+                            | "yield"
+                    );
+                    let path = match name {
+                        "require" if !after_dot => self.require_arguments(),
+                        _ => None,
+                    };
+                    if let Some(path) = path {
+                        self.regex_allowed = false;
+                        return Some(Require {
+                            start,
+                            end: self.position,
+                            path,
+                        });
+                    }
+                }
+                _ => {
+                    self.position += 1;
+                    self.regex_allowed = true;
+                }
+            }
+        }
+    }
+}
+
+fn is_relative(path: &str) -> bool {
+    path.starts_with("./") || path.starts_with("../")
+}
+
+/// Removes the `.` and `..` components of a path, without looking at the file
+/// system.
+///
+fn normalise(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalised = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => (),
+            Utf8Component::ParentDir => {
+                let _ = normalised.pop();
+            }
+            Utf8Component::Prefix(_) | Utf8Component::RootDir | Utf8Component::Normal(_) => {
+                normalised.push(component)
+            }
+        }
+    }
+    normalised
+}
@@ -86,6 +86,7 @@ pub(crate) struct Generator<'module, 'ast> {
     src_path: &'module Utf8Path,
     project_root: &'module Utf8Path,
     line_numbers: &'module LineNumbers,
+    src: &'module EcoString,
     function_name: Option<EcoString>,
     function_arguments: Vec<Option<&'module EcoString>>,
     current_scope_vars: im::HashMap<EcoString, usize>,
@@ -94,6 +95,9 @@ pub(crate) struct Generator<'module, 'ast> {
     // We register whether these features are used within an expression so that
     // the module generator can output a suitable function if it is needed.
     pub tracker: &'module mut UsageTracker,
+    /// Whether `panic` and `todo` errors are passed to the package's panic
+    /// handler before being thrown.
+    has_panic_handler: bool,
     // We track whether tail call recursion is used so that we can render a loop
     // at the top level of the function to use in place of pushing new stack
     // frames.
@@ -130,10 +134,12 @@ impl<'module, 'a> Generator<'module, 'a> {
         src_path: &'module Utf8Path,
         project_root: &'module Utf8Path,
         line_numbers: &'module LineNumbers,
+        src: &'module EcoString,
         function_name: EcoString,
         function_arguments: Vec<Option<&'module EcoString>>,
         tracker: &'module mut UsageTracker,
         mut current_scope_vars: im::HashMap<EcoString, usize>,
+        has_panic_handler: bool,
     ) -> Self {
         let mut function_name = Some(function_name);
         for &name in function_arguments.iter().flatten() {
@@ -148,10 +154,12 @@ impl<'module, 'a> Generator<'module, 'a> {
         }
         Self {
             tracker,
+            has_panic_handler,
             module_name,
             src_path,
             project_root,
             line_numbers,
+            src,
             function_name,
             function_arguments,
             tail_recursion_used: false,
@@ -211,6 +219,7 @@ impl<'module, 'a> Generator<'module, 'a> {
             Statement::Expression(expression) => self.expression(expression),
             Statement::Assignment(assignment) => self.assignment(assignment),
             Statement::Use(_use) => self.expression(&_use.call),
+            Statement::Assert(assert) => self.assert(assert),
         }?;
         if self.statement_level.is_empty() {
             Ok(expression_doc)
@@ -733,7 +742,17 @@ impl<'module, 'a> Generator<'module, 'a> {
     }
 
     fn block(&mut self, statements: &'a Vec1<TypedStatement>) -> Output<'a> {
-        if statements.len() == 1 {
+        // An assertion can't be compiled to a single expression, so a block
+        // made of just an assertion is treated like any other block with
+        // multiple statements.
+        let is_single_expression = match statements.first() {
+            Statement::Assert(_) => false,
+            Statement::Expression(_) | Statement::Assignment(_) | Statement::Use(_) => {
+                statements.len() == 1
+            }
+        };
+
+        if is_single_expression {
             match statements.first() {
                 Statement::Expression(expression) => self.child_expression(expression),
 
@@ -742,6 +761,8 @@ impl<'module, 'a> Generator<'module, 'a> {
                 }
 
                 Statement::Use(use_) => self.child_expression(&use_.call),
+
+                Statement::Assert(_) => unreachable!("Assertions are never single expressions"),
             }
         } else {
             match &self.scope_position {
@@ -974,6 +995,40 @@ impl<'module, 'a> Generator<'module, 'a> {
         Ok(docvec![subject_assignments, doc].force_break())
     }
 
+    fn assert(&mut self, assert: &'a TypedAssert) -> Output<'a> {
+        let value = self.not_in_tail_position(Some(Ordering::Loose), |this| {
+            this.wrap_expression(&assert.value)
+        })?;
+        let value = match &assert.value {
+            TypedExpr::BinOp { .. } => docvec!["(", value, ")"],
+            _ => value,
+        };
+
+        let message = assert
+            .failure_message(self.src)
+            .to_doc()
+            .surround("\"", "\"");
+        let error = self.throw_error("assert", &message, assert.location, vec![]);
+        let check = docvec![
+            "if (!",
+            value,
+            ") {",
+            docvec![line(), error, ";"].nest(INDENT),
+            line(),
+            "}"
+        ];
+
+        // An assertion always evaluates to `Nil`.
+        match &self.scope_position {
+            Position::NotTail(_) => Ok(check),
+            Position::Tail | Position::Assign(_) => Ok(docvec![
+                check,
+                line(),
+                self.wrap_return("undefined".to_doc())
+            ]),
+        }
+    }
+
     fn assignment_no_match(
         &mut self,
         location: SrcSpan,
@@ -1270,7 +1325,7 @@ impl<'module, 'a> Generator<'module, 'a> {
             Some(m) => self.not_in_tail_position(None, |this| this.expression(m))?,
             None => string("`todo` expression evaluated. This code has not yet been implemented."),
         };
-        let doc = self.throw_error("todo", &message, *location, vec![]);
+        let doc = self.throw_panic_error("todo", &message, *location);
 
         Ok(doc)
     }
@@ -1280,11 +1335,29 @@ impl<'module, 'a> Generator<'module, 'a> {
             Some(m) => self.not_in_tail_position(None, |this| this.expression(m))?,
             None => string("`panic` expression evaluated."),
         };
-        let doc = self.throw_error("panic", &message, *location, vec![]);
+        let doc = self.throw_panic_error("panic", &message, *location);
 
         Ok(doc)
     }
 
+    /// Throws the error of a `panic` or `todo` expression, after passing it to
+    /// the package's panic handler if it has one.
+    ///
+    fn throw_panic_error(
+        &mut self,
+        error_name: &'a str,
+        message: &Document<'a>,
+        location: SrcSpan,
+    ) -> Document<'a> {
+        let error = self.make_error(error_name, message, location, vec![]);
+        if self.has_panic_handler {
+            self.tracker.panic_handler_used = true;
+            docvec!["throw $panicHandler", wrap_args([error])]
+        } else {
+            docvec!["throw ", error]
+        }
+    }
+
     fn throw_error<Fields>(
         &mut self,
         error_name: &'a str,
@@ -1292,6 +1365,20 @@ impl<'module, 'a> Generator<'module, 'a> {
         location: SrcSpan,
         fields: Fields,
     ) -> Document<'a>
+    where
+        Fields: IntoIterator<Item = (&'a str, Document<'a>)>,
+    {
+        let error = self.make_error(error_name, message, location, fields);
+        docvec!["throw ", error]
+    }
+
+    fn make_error<Fields>(
+        &mut self,
+        error_name: &'a str,
+        message: &Document<'a>,
+        location: SrcSpan,
+        fields: Fields,
+    ) -> Document<'a>
     where
         Fields: IntoIterator<Item = (&'a str, Document<'a>)>,
     {
@@ -1307,7 +1394,7 @@ impl<'module, 'a> Generator<'module, 'a> {
         let fields = wrap_object(fields.into_iter().map(|(k, v)| (k.to_doc(), Some(v))));
 
         docvec![
-            "throw makeError",
+            "makeError",
             wrap_args([
                 string(error_name),
                 module,
@@ -2103,6 +2190,7 @@ fn requires_semicolon(statement: &TypedStatement) -> bool {
 
         Statement::Assignment(_) => false,
         Statement::Use(_) => false,
+        Statement::Assert(_) => false,
     }
 }
 
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use ecow::{EcoString, eco_format};
+use itertools::Itertools;
+
+use crate::{
+    ast::{Definition, TypedFunction, TypedModule},
+    type_::{self, pretty},
+};
+
+use super::maybe_escape_identifier;
+
+/// A JavaScript function used by an external Gleam function, which its stub
+/// is generated for.
+#[derive(Debug)]
+struct Stub {
+    /// The name the function is exported with.
+    name: EcoString,
+    /// The names of the arguments the function is called with, in the order
+    /// they are passed to it.
+    arguments: Vec<EcoString>,
+    /// The Gleam function using it, e.g. `wibble/wobble.do_thing`.
+    used_by: EcoString,
+    /// The type of the Gleam function, as written in its module.
+    type_: String,
+}
+
+/// Generates a skeleton for each of the JavaScript files referenced by the
+/// externals of the given modules, with a stub throwing an error for each
+/// of the functions they use from it. Files are returned along with their
+/// path, in the same directory as the modules using them.
+///
+/// Only the externals importing a file with a path relative to their module
+/// are looked at, as the others import a JavaScript package, for which
+/// there's nothing to generate.
+///
+pub fn ffi_stubs<'a>(
+    modules: impl IntoIterator<Item = &'a TypedModule>,
+) -> Vec<(Utf8PathBuf, String)> {
+    let mut files: BTreeMap<Utf8PathBuf, Vec<Stub>> = BTreeMap::new();
+    for module in modules {
+        let directory = module
+            .type_info
+            .src_path
+            .parent()
+            .unwrap_or(Utf8Path::new(""));
+        for definition in &module.definitions {
+            let Definition::Function(function) = definition else {
+                continue;
+            };
+            let Some((path, function_name, _)) = &function.external_javascript else {
+                continue;
+            };
+            if !path.starts_with("./") && !path.starts_with("../") {
+                continue;
+            }
+
+            let stubs = files.entry(join_relative(directory, path)).or_default();
+            // The same JavaScript function can be used by more than one
+            // Gleam function, but it should only be defined once.
+            if stubs.iter().all(|stub| &stub.name != function_name) {
+                stubs.push(stub(module, function, function_name));
+            }
+        }
+    }
+
+    files
+        .into_iter()
+        .map(|(path, stubs)| (path, stubs.iter().map(render_stub).join("\n")))
+        .collect()
+}
+
+fn stub(module: &TypedModule, function: &TypedFunction, name: &EcoString) -> Stub {
+    let arguments = function
+        .arguments
+        .iter()
+        .enumerate()
+        .map(|(index, argument)| match argument.get_variable_name() {
+            Some(name) => maybe_escape_identifier(name),
+            None => eco_format!("argument{}", index + 1),
+        })
+        .collect_vec();
+    let arguments = match &function.external_javascript_arguments {
+        Some(external) => external.reorder(&function.arguments, &arguments),
+        None => arguments,
+    };
+
+    let (_, function_name) = function
+        .name
+        .as_ref()
+        .expect("A module's function must be named");
+    let type_ = type_::fn_(
+        function
+            .arguments
+            .iter()
+            .map(|argument| argument.type_.clone())
+            .collect(),
+        function.return_type.clone(),
+    );
+
+    Stub {
+        name: name.clone(),
+        arguments,
+        used_by: eco_format!("{}.{function_name}", module.name),
+        type_: pretty::print(&type_, &module.names),
+    }
+}
+
+fn render_stub(stub: &Stub) -> String {
+    let Stub {
+        name,
+        arguments,
+        used_by,
+        type_,
+    } = stub;
+    let arguments = arguments.join(", ");
+    format!(
+        "// Used by `{used_by}`: {type_}
+export function {name}({arguments}) {{
+  throw new Error(\"`{name}` is not implemented\");
+}}
+"
+    )
+}
+
+/// Joins a path relative to a directory, resolving any `.` and `..` in it so
+/// that the same file always ends up with the same path.
+///
+fn join_relative(directory: &Utf8Path, path: &str) -> Utf8PathBuf {
+    let mut joined = directory.to_path_buf();
+    for component in Utf8Path::new(path).components() {
+        match component {
+            Utf8Component::CurDir => (),
+            Utf8Component::ParentDir => {
+                let _ = joined.pop();
+            }
+            Utf8Component::Normal(_) | Utf8Component::RootDir | Utf8Component::Prefix(_) => {
+                joined.push(component)
+            }
+        }
+    }
+    joined
+}
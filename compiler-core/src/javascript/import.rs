@@ -4,6 +4,7 @@ use ecow::EcoString;
 use itertools::Itertools;
 
 use crate::{
+    build::JavaScriptModuleFormat,
     docvec,
     javascript::{INDENT, JavaScriptCodegenTarget},
     pretty::{Document, Documentable, break_, concat, join, line},
@@ -41,43 +42,64 @@ impl<'a> Imports<'a> {
         import.unqualified.extend(unqualified_imports)
     }
 
-    pub fn into_doc(self, codegen_target: JavaScriptCodegenTarget) -> Document<'a> {
+    pub fn into_doc(
+        self,
+        codegen_target: JavaScriptCodegenTarget,
+        module_format: JavaScriptModuleFormat,
+    ) -> Document<'a> {
         let imports = concat(
             self.imports
                 .into_values()
                 .sorted_by(|a, b| a.path.cmp(&b.path))
-                .map(|import| Import::into_doc(import, codegen_target)),
+                .map(|import| match module_format {
+                    JavaScriptModuleFormat::EcmaScript => Import::into_doc(import, codegen_target),
+                    JavaScriptModuleFormat::CommonJs => Import::into_commonjs_doc(import),
+                }),
         );
 
         if self.exports.is_empty() {
             imports
         } else {
-            let names = join(
-                self.exports
-                    .into_iter()
-                    .sorted()
-                    .map(|string| string.to_doc()),
-                break_(",", ", "),
-            );
-            let names = docvec![
-                docvec![break_("", " "), names].nest(INDENT),
-                break_(",", " ")
-            ]
-            .group();
             imports
                 .append(line())
                 .append("export {")
-                .append(names)
+                .append(exported_names(self.exports))
                 .append("};")
                 .append(line())
         }
     }
 
+    /// Removes the names registered to be exported, for them to be exported
+    /// by other means than an `export` statement.
+    ///
+    pub fn take_exports(&mut self) -> HashSet<EcoString> {
+        std::mem::take(&mut self.exports)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.imports.is_empty() && self.exports.is_empty()
     }
 }
 
+/// Renders a CommonJS `module.exports` assignment exporting all the given
+/// names.
+///
+pub(crate) fn commonjs_exports<'a>(names: impl IntoIterator<Item = EcoString>) -> Document<'a> {
+    docvec!["module.exports = {", exported_names(names), "};", line()]
+}
+
+fn exported_names<'a>(names: impl IntoIterator<Item = EcoString>) -> Document<'a> {
+    let names = join(
+        names.into_iter().sorted().map(|string| string.to_doc()),
+        break_(",", ", "),
+    );
+    docvec![
+        docvec![break_("", " "), names].nest(INDENT),
+        break_(",", " ")
+    ]
+    .group()
+}
+
 #[derive(Debug)]
 struct Import<'a> {
     path: EcoString,
@@ -136,6 +158,43 @@ impl<'a> Import<'a> {
             ]
         }
     }
+
+    /// Renders the import as CommonJS `require` calls, which have no notion
+    /// of type only imports.
+    ///
+    fn into_commonjs_doc(self) -> Document<'a> {
+        let path = self.path.to_doc();
+        let alias_imports = concat(self.aliases.into_iter().sorted().map(|alias| {
+            docvec![
+                "const ",
+                alias,
+                " = require(\"",
+                path.clone(),
+                r#"");"#,
+                line()
+            ]
+        }));
+        if self.unqualified.is_empty() {
+            alias_imports
+        } else {
+            let members = self.unqualified.into_iter().map(Member::into_commonjs_doc);
+            let members = join(members, break_(",", ", "));
+            let members = docvec![
+                docvec![break_("", " "), members].nest(INDENT),
+                break_(",", " ")
+            ]
+            .group();
+            docvec![
+                alias_imports,
+                "const {",
+                members,
+                "} = require(\"",
+                path,
+                r#"");"#,
+                line()
+            ]
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -151,6 +210,13 @@ impl<'a> Member<'a> {
             Some(alias) => docvec![self.name, " as ", alias],
         }
     }
+
+    fn into_commonjs_doc(self) -> Document<'a> {
+        match self.alias {
+            None => self.name,
+            Some(alias) => docvec![self.name, ": ", alias],
+        }
+    }
 }
 
 #[test]
@@ -223,7 +289,10 @@ fn into_doc() {
 
     assert_eq!(
         line()
-            .append(imports.into_doc(JavaScriptCodegenTarget::JavaScript))
+            .append(imports.into_doc(
+                JavaScriptCodegenTarget::JavaScript,
+                JavaScriptModuleFormat::EcmaScript
+            ))
             .to_pretty_string(40),
         r#"
 import * as wibble from "./multiple/times";
@@ -1,5 +1,5 @@
 use num_bigint::BigInt;
-use std::sync::OnceLock;
+use std::{collections::HashMap, sync::OnceLock};
 
 use super::{expression::is_js_scalar, *};
 use crate::{
@@ -37,6 +37,12 @@ pub(crate) struct Generator<'module_ctx, 'expression_gen, 'a> {
     path: Vec<Index<'a>>,
     checks: Vec<Check<'a>>,
     assignments: Vec<Assignment<'a>>,
+    /// The code reading the value of the variables bound by the int segments
+    /// of a bit array pattern. The checks of a pattern are all performed
+    /// before any of its variables is assigned, so when a later segment uses
+    /// one of those variables as its size the value is read straight from
+    /// the bit array instead.
+    bit_array_segment_values: HashMap<&'a EcoString, EcoString>,
 }
 
 #[derive(Debug)]
@@ -182,6 +188,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
             path: vec![],
             checks: vec![],
             assignments: vec![],
+            bit_array_segment_values: HashMap::new(),
             expression_generator,
         }
     }
@@ -194,6 +201,15 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
         self.expression_generator.local_var(name)
     }
 
+    /// The code to use for a variable used as the size of a bit array
+    /// segment.
+    fn bit_array_size_var(&mut self, name: &EcoString) -> EcoString {
+        match self.bit_array_segment_values.get(name) {
+            Some(value) => value.clone(),
+            None => self.local_var(name),
+        }
+    }
+
     fn push_string(&mut self, s: &'a str) {
         self.path.push(Index::String(s));
     }
@@ -335,6 +351,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
     }
 
     pub fn take_compiled(&mut self) -> CompiledPattern<'a> {
+        self.bit_array_segment_values.clear();
         CompiledPattern {
             checks: std::mem::take(&mut self.checks),
             assignments: std::mem::take(&mut self.assignments),
@@ -771,6 +788,16 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                                     );
                                 }
 
+                                if let Pattern::Variable { name, .. }
+                                | Pattern::Assign { name, .. } = segment.value.as_ref()
+                                {
+                                    let value = self
+                                        .apply_path_to_subject(subject.clone())
+                                        .to_pretty_string(isize::MAX);
+                                    let _ =
+                                        self.bit_array_segment_values.insert(name, value.into());
+                                }
+
                                 self.traverse_pattern(subject, &segment.value)?;
                                 self.pop();
                                 offset.bits.increment(size);
@@ -803,7 +830,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                                 }
 
                                 Pattern::VarUsage { name, .. } => {
-                                    let name = self.expression_generator.local_var(name);
+                                    let name = self.bit_array_size_var(name);
                                     let start = offset.bits.clone();
 
                                     offset.bits.increment(BitArraySize::Variable(name));
@@ -847,7 +874,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
 
                                 Pattern::VarUsage { name, .. } => {
                                     let start = offset.bits.clone();
-                                    let mut name = self.expression_generator.local_var(name);
+                                    let mut name = self.bit_array_size_var(name);
                                     name.push_str(" * 8");
                                     offset.bits.increment(BitArraySize::Variable(name));
                                     let end = offset.bits.clone();
@@ -894,10 +921,39 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                                 }),
                             },
 
-                            _ => Err(Error::Unsupported {
-                                feature: "This bit array segment option in patterns".into(),
-                                location: segment.location,
-                            }),
+                            options => match bits_size_and_unit(options) {
+                                Some((Pattern::Int { value, .. }, unit)) => {
+                                    let start = offset.bits.clone();
+                                    let increment = value.parse::<usize>().expect(
+                                        "part of an Int node should always parse as integer",
+                                    ) * unit as usize;
+                                    offset.bits.increment(BitArraySize::Literal(increment));
+                                    let end = offset.bits.clone();
+
+                                    self.push_bit_array_slice(start, Some(end));
+                                    self.traverse_pattern(subject, &segment.value)?;
+                                    self.pop();
+                                    Ok(())
+                                }
+
+                                Some((Pattern::VarUsage { name, .. }, unit)) => {
+                                    let start = offset.bits.clone();
+                                    let name =
+                                        eco_format!("{} * {unit}", self.bit_array_size_var(name));
+                                    offset.bits.increment(BitArraySize::Variable(name));
+                                    let end = offset.bits.clone();
+
+                                    self.push_bit_array_slice(start, Some(end));
+                                    self.traverse_pattern(subject, &segment.value)?;
+                                    self.pop();
+                                    Ok(())
+                                }
+
+                                Some(_) | None => Err(Error::Unsupported {
+                                    feature: "This bit array segment option in patterns".into(),
+                                    location: segment.location,
+                                }),
+                            },
                         }?;
                     }
                 }
@@ -914,6 +970,9 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 location: *location,
             }),
             Pattern::Invalid { .. } => panic!("invalid patterns should not reach code generation"),
+            Pattern::Constant { .. } => {
+                panic!("constant patterns should not reach code generation")
+            }
         }
     }
 
@@ -966,7 +1025,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                         * unit as usize,
                 )),
                 Pattern::VarUsage { name, .. } => {
-                    let mut variable = self.expression_generator.local_var(name);
+                    let mut variable = self.bit_array_size_var(name);
                     if unit != 1 {
                         variable.push_str(&eco_format!(" * {unit}"));
                     }
@@ -1205,15 +1264,18 @@ impl<'a> Check<'a> {
                         // When the tail spread is for bytes rather than bits,
                         // check that there is a whole number of bytes left in
                         // the bit array
+                        let remaining_bit_size = if expected_bit_size.variables.is_empty() {
+                            docvec![bit_size.clone(), " - ", expected_bit_size.clone()]
+                        } else {
+                            docvec![bit_size.clone(), " - (", expected_bit_size.clone(), ")"]
+                        };
                         docvec![
                             "(",
-                            bit_size.clone(),
+                            bit_size,
                             " >= ",
                             expected_bit_size,
                             " && (",
-                            bit_size,
-                            " - ",
-                            expected_bit_size,
+                            remaining_bit_size,
                             ") % 8 === 0)"
                         ]
                     }
@@ -1292,3 +1354,23 @@ pub(crate) fn assign_subjects<'a>(
 fn utf16_no_escape_len(str: &EcoString) -> usize {
     convert_string_escape_chars(str).encode_utf16().count()
 }
+
+/// If a bit array segment's options are `bits`, `size`, and `unit`, in any
+/// order, returns its size and unit.
+fn bits_size_and_unit(options: &[BitArrayOption<TypedPattern>]) -> Option<(&TypedPattern, u8)> {
+    let mut bits = false;
+    let mut size = None;
+    let mut unit = None;
+    for option in options {
+        match option {
+            BitArrayOption::Bits { .. } if !bits => bits = true,
+            BitArrayOption::Size { value, .. } if size.is_none() => size = Some(value.as_ref()),
+            BitArrayOption::Unit { value, .. } if unit.is_none() => unit = Some(*value),
+            _ => return None,
+        }
+    }
+    match (bits, size, unit) {
+        (true, Some(size), Some(unit)) => Some((size, unit)),
+        _ => None,
+    }
+}
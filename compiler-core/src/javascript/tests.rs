@@ -1,26 +1,32 @@
 use crate::{
     analyse::TargetSupport,
     build::{Origin, Target},
-    config::PackageConfig,
+    config::{PackageConfig, PanicHandler},
     javascript::*,
     uid::UniqueIdGenerator,
     warning::{TypeWarningEmitter, WarningEmitter},
 };
 use camino::{Utf8Path, Utf8PathBuf};
 
+mod assert;
 mod assignments;
 mod bit_arrays;
 mod blocks;
 mod bools;
+mod bundle;
 mod case;
 mod case_clause_guards;
 mod consts;
 mod custom_types;
+mod derive_json;
 mod echo;
 mod externals;
+mod ffi_stubs;
 mod functions;
 mod generics;
 mod lists;
+mod memoize;
+mod module_formats;
 mod modules;
 mod numbers;
 mod panic;
@@ -28,6 +34,7 @@ mod prelude;
 mod records;
 mod recursion;
 mod results;
+mod should_panic;
 mod strings;
 mod todo;
 mod tuples;
@@ -70,6 +77,25 @@ macro_rules! assert_js {
         assert_eq!(($src, output), ($src, $js.to_string()));
     }};
 
+    (panic_handler: ($module:expr, $function:expr), $src:expr $(,)?) => {{
+        let handler = $crate::config::PanicHandler {
+            module: $module.into(),
+            function: $function.into(),
+        };
+        let compiled = $crate::javascript::tests::compile_js_with_options(
+            $src,
+            vec![],
+            $crate::build::JavaScriptModuleFormat::EcmaScript,
+            Some(&handler),
+        )
+        .expect("compilation failed");
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
     ($src:expr $(,)?) => {{
         let compiled =
             $crate::javascript::tests::compile_js($src, vec![]).expect("compilation failed");
@@ -193,18 +219,37 @@ pub fn compile(src: &str, deps: Vec<(&str, &str, &str)>) -> TypedModule {
 }
 
 pub fn compile_js(src: &str, deps: Vec<(&str, &str, &str)>) -> Result<String, crate::Error> {
+    compile_js_with_module_format(src, deps, JavaScriptModuleFormat::EcmaScript)
+}
+
+pub fn compile_js_with_module_format(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    module_format: JavaScriptModuleFormat,
+) -> Result<String, crate::Error> {
+    compile_js_with_options(src, deps, module_format, None)
+}
+
+pub fn compile_js_with_options(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    module_format: JavaScriptModuleFormat,
+    panic_handler: Option<&PanicHandler>,
+) -> Result<String, crate::Error> {
     let ast = compile(src, deps);
     let line_numbers = LineNumbers::new(src);
     let stdlib_package = StdlibPackage::Present;
     let output = module(ModuleConfig {
         module: &ast,
         line_numbers: &line_numbers,
-        src: &"".into(),
+        src: &src.into(),
         target_support: TargetSupport::Enforced,
         typescript: TypeScriptDeclarations::None,
+        module_format,
         stdlib_package,
         path: Utf8Path::new("src/module.gleam"),
         project_root: "project/root".into(),
+        panic_handler,
     })?;
 
     Ok(output.replace(
@@ -249,3 +249,17 @@ pub fn expect(value, message) {
 "#
     );
 }
+
+// The value that failed to match is included in the error so that the crash
+// report can show what the `Error` contained.
+#[test]
+fn let_assert_unmatched_error_value_is_in_the_error_payload() {
+    assert_js!(
+        r#"
+pub fn unwrap(result: Result(Int, String)) {
+  let assert Ok(x) = result
+  x
+}
+"#
+    );
+}
@@ -0,0 +1,140 @@
+use camino::Utf8Path;
+
+use crate::{
+    Error,
+    build::JavaScriptModuleFormat,
+    io::{FileSystemWriter, memory::InMemoryFileSystem},
+    javascript::{bundle::bundle, tests::compile_js_with_module_format},
+};
+
+const GREETING: &str = r#"
+@external(javascript, "./my/console.cjs", "log")
+pub fn log(message: String) -> Nil
+
+pub fn hello(name: String) -> String {
+  "Hello, " <> name <> "!"
+}
+"#;
+
+const MAIN: &str = r#"
+import greeting
+
+@external(javascript, "./console.cjs", "log")
+fn log(message: String) -> Nil
+
+pub fn main() {
+  log(greeting.hello("Lucy"))
+  greeting.log("Goodbye!")
+}
+"#;
+
+const CONSOLE: &str = r#""use strict";
+
+const util = require("node:util");
+
+function log(message) {
+  console.log(util.format("%s", message));
+}
+
+module.exports = { log };
+"#;
+
+fn write_module(fs: &InMemoryFileSystem, path: &str, src: &str) {
+    let compiled = compile_js_with_module_format(src, vec![], JavaScriptModuleFormat::CommonJs)
+        .expect("compilation failed");
+    fs.write(Utf8Path::new(path), &compiled).unwrap();
+}
+
+fn project() -> InMemoryFileSystem {
+    let fs = InMemoryFileSystem::new();
+    write_module(&fs, "/build/thepackage/greeting.cjs", GREETING);
+    let main = compile_js_with_module_format(
+        MAIN,
+        vec![("thepackage", "greeting", GREETING)],
+        JavaScriptModuleFormat::CommonJs,
+    )
+    .expect("compilation failed");
+    fs.write(Utf8Path::new("/build/thepackage/my/mod.cjs"), &main)
+        .unwrap();
+    fs.write(Utf8Path::new("/build/thepackage/my/console.cjs"), CONSOLE)
+        .unwrap();
+    fs
+}
+
+#[test]
+fn bundle_of_two_modules() {
+    let fs = project();
+    let bundle = bundle(&fs, Utf8Path::new("/build"), "thepackage", "my/mod").unwrap();
+    insta::assert_snapshot!(bundle);
+}
+
+#[test]
+fn ecmascript_modules_cannot_be_bundled() {
+    let fs = project();
+    let ffi = r#"export function log(message) { console.log(message) }"#;
+    fs.write(
+        Utf8Path::new("/build/thepackage/greeting.cjs"),
+        "const { log } = require(\"./ffi.mjs\");\nmodule.exports = { log };\n",
+    )
+    .unwrap();
+    fs.write(Utf8Path::new("/build/thepackage/ffi.mjs"), ffi)
+        .unwrap();
+
+    let error = bundle(&fs, Utf8Path::new("/build"), "thepackage", "my/mod").unwrap_err();
+    assert_eq!(
+        error,
+        Error::CannotBundleJavaScriptModule {
+            path: "/build/thepackage/ffi.mjs".into()
+        }
+    );
+}
+
+fn bundle_module(code: &str) -> String {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/build/thepackage/app.cjs"), code)
+        .unwrap();
+    fs.write(
+        Utf8Path::new("/build/thepackage/wibble.cjs"),
+        "module.exports = { wibble: 1 };\n",
+    )
+    .unwrap();
+    bundle(&fs, Utf8Path::new("/build"), "thepackage", "app").unwrap()
+}
+
+#[test]
+fn require_with_whitespace_and_comments_is_replaced() {
+    insta::assert_snapshot!(bundle_module(
+        r#"const one = require ("./wibble.cjs");
+const two = require(
+  // The wibble module
+  './wibble.cjs' /* again */
+);
+module.exports = { main() {} };
+"#
+    ));
+}
+
+#[test]
+fn require_in_strings_and_comments_is_not_replaced() {
+    insta::assert_snapshot!(bundle_module(
+        r#"// const wibble = require("./wibble.cjs");
+/* require("./wibble.cjs") */
+const double = "require(\"./wibble.cjs\")";
+const single = 'require("./wibble.cjs")';
+const template = `require("./wibble.cjs")`;
+const regex = /require\("\.\/wibble\.cjs"\)/;
+const divided = (1) / 2 / require("./wibble.cjs").wibble;
+const property = module.require("./wibble.cjs");
+module.exports = { main() {} };
+"#
+    ));
+}
+
+#[test]
+fn require_in_template_substitution_is_replaced() {
+    insta::assert_snapshot!(bundle_module(
+        r#"const template = `${ { a: require("./wibble.cjs") }.a } require("./wibble.cjs") ${`${require("./wibble.cjs")}`}`;
+module.exports = { main() {} };
+"#
+    ));
+}
@@ -312,3 +312,25 @@ pub fn main() {
 "#
     )
 }
+
+#[test]
+fn imported_constant_pattern() {
+    assert_js!(
+        (
+            "thepackage",
+            "numbers",
+            r#"pub const zero = 0
+pub const name = "Lucy""#
+        ),
+        r#"
+import numbers
+
+pub fn main(x, y) {
+  case x, y {
+    numbers.zero, numbers.name -> True
+    _, _ -> False
+  }
+}
+"#
+    );
+}
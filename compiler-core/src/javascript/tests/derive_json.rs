@@ -0,0 +1,26 @@
+use crate::assert_js;
+
+#[test]
+fn record_deriving_json() {
+    assert_js!(
+        r#"
+@derive(json)
+pub type Setting {
+  Setting(name: String, enabled: Bool)
+}
+"#
+    );
+}
+
+#[test]
+fn type_deriving_json_with_a_list_of_ints() {
+    assert_js!(
+        r#"
+@derive(json)
+pub type Scores {
+  Scores(List(Int))
+  NoScores
+}
+"#
+    );
+}
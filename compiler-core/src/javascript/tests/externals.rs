@@ -268,6 +268,22 @@ pub fn one(x: Int) -> Int {
     );
 }
 
+#[test]
+fn erlang_external_with_gleam_fallback() {
+    assert_js!(
+        r#"
+@external(erlang, "math", "double")
+pub fn double(x: Int) -> Int {
+  x * 2
+}
+
+pub fn main() {
+  double(1)
+}
+"#
+    );
+}
+
 #[test]
 fn erlang_only() {
     assert_js!(
@@ -309,3 +325,67 @@ pub fn woo(_ignored: a) -> Nil
 "#
     );
 }
+
+#[test]
+fn external_with_reordered_arguments() {
+    assert_js!(
+        r#"
+@external(javascript, "./ffi.mjs", "fold", [fun, initial, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+
+pub fn main() {
+  fold([1, 2], 0, fn(x, acc) { x + acc })
+}
+"#
+    );
+}
+
+#[test]
+fn private_external_with_reordered_arguments() {
+    assert_js!(
+        r#"
+@external(javascript, "./ffi.mjs", "fold", [fun, initial, list])
+fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+
+pub fn main() {
+  fold([1, 2], 0, fn(x, acc) { x + acc })
+}
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_external_function_name() {
+    assert_js!(
+        r#"
+@external(javascript, "./ffi.mjs", "type")
+pub fn `type`(value: a) -> String
+
+pub fn main() {
+  `type`(1)
+}
+"#
+    );
+}
+
+#[test]
+fn imported_raw_identifier_external_function_name() {
+    assert_js!(
+        (
+            "package",
+            "wibble",
+            r#"
+@external(javascript, "./ffi.mjs", "case")
+pub fn `case`(value: a) -> String
+"#
+        ),
+        r#"
+import wibble.{`case`}
+
+pub fn main() {
+  wibble.`case`(1)
+  `case`(2)
+}
+"#
+    );
+}
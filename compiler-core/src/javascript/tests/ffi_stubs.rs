@@ -0,0 +1,85 @@
+use crate::{
+    analyse::TargetSupport, ast::TypedModule, build::Target, javascript::ffi_stubs::ffi_stubs,
+    type_::tests::compile_module_with_opts,
+};
+
+fn module(name: &str, src_path: &str, src: &str) -> TypedModule {
+    let mut module = compile_module_with_opts(
+        name,
+        src,
+        None,
+        vec![],
+        Target::JavaScript,
+        TargetSupport::Enforced,
+        None,
+    )
+    .expect("compilation failed");
+    module.type_info.src_path = src_path.into();
+    module
+}
+
+fn stubs(modules: &[TypedModule]) -> String {
+    ffi_stubs(modules)
+        .into_iter()
+        .map(|(path, stub)| format!("-- {path}\n{stub}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn stubs_for_two_javascript_externals() {
+    let src = r#"
+@external(javascript, "./my_ffi.mjs", "doThing")
+pub fn do_thing(count: Int, name: String) -> Bool
+
+@external(javascript, "./my_ffi.mjs", "now")
+pub fn now() -> Float
+"#;
+    insta::assert_snapshot!(stubs(&[module("wibble", "/project/src/wibble.gleam", src)]));
+}
+
+#[test]
+fn stubs_are_grouped_by_file() {
+    let first = r#"
+@external(javascript, "./ffi.mjs", "length")
+pub fn length(list: List(a)) -> Int
+
+@external(javascript, "./other.mjs", "other")
+fn other(_ignored: Int) -> Nil
+"#;
+    let second = r#"
+@external(javascript, "../ffi.mjs", "length")
+pub fn size(list: List(a)) -> Int
+
+@external(javascript, "../ffi.mjs", "reverse")
+pub fn reverse(list: List(a)) -> List(a)
+"#;
+    insta::assert_snapshot!(stubs(&[
+        module("wibble", "/project/src/wibble.gleam", first),
+        module("wibble/wobble", "/project/src/wibble/wobble.gleam", second),
+    ]));
+}
+
+#[test]
+fn stubs_use_the_order_of_reordered_arguments() {
+    let src = r#"
+@external(javascript, "./ffi.mjs", "fold", [fun, initial, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#;
+    insta::assert_snapshot!(stubs(&[module("wibble", "/project/src/wibble.gleam", src)]));
+}
+
+#[test]
+fn no_stubs_for_package_externals() {
+    let src = r#"
+@external(javascript, "node:process", "exit")
+pub fn exit(code: Int) -> Nil
+
+@external(erlang, "erlang", "halt")
+fn halt(code: Int) -> Nil
+"#;
+    assert_eq!(
+        stubs(&[module("wibble", "/project/src/wibble.gleam", src)]),
+        ""
+    );
+}
@@ -0,0 +1,100 @@
+use crate::assert_js;
+
+#[test]
+fn memoized_function() {
+    assert_js!(
+        r#"
+@memoize
+pub fn fib(n: Int) -> Int {
+  case n {
+    0 | 1 -> n
+    _ -> fib(n - 1) + fib(n - 2)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_discarded_argument() {
+    assert_js!(
+        r#"
+@memoize
+pub fn wibble(a: Int, _b: String, c: Bool) -> Int {
+  case c {
+    True -> a
+    False -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_shadowing_its_arguments() {
+    assert_js!(
+        r#"
+@memoize
+pub fn wibble(a: Int) -> Int {
+  let a = a + 1
+  let a = a * 2
+  a
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_no_arguments() {
+    assert_js!(
+        r#"
+@memoize
+pub fn wibble() -> Int {
+  1 + 2
+}
+"#
+    );
+}
+
+#[test]
+fn calls_to_memoized_function_are_not_changed() {
+    assert_js!(
+        r#"
+@memoize
+fn wibble(a: Int) -> Int {
+  a
+}
+
+pub fn main() {
+  wibble(1) + wibble(2)
+}
+"#
+    );
+}
+
+#[test]
+fn tail_recursive_memoized_function() {
+    assert_js!(
+        r#"
+@memoize
+pub fn sum(n: Int, total: Int) -> Int {
+  case n {
+    0 -> total
+    _ -> sum(n - 1, total + n)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_reserved_name() {
+    assert_js!(
+        r#"
+@memoize
+pub fn class(new: Int) -> Int {
+  new
+}
+"#
+    );
+}
@@ -0,0 +1,95 @@
+use crate::build::JavaScriptModuleFormat;
+use crate::javascript::commonjs_prelude;
+use crate::javascript::tests::{CURRENT_PACKAGE, compile_js_with_module_format};
+
+const DEPENDENCY: &str = r#"
+pub fn launch() { 1 }
+pub fn land() { 2 }
+"#;
+
+const MODULE: &str = r#"
+import rocket_ship.{launch as go}
+
+pub type Fuel {
+  Fuel(litres: Int)
+}
+
+pub const fuel = Fuel(100)
+
+@external(javascript, "./ffi.mjs", "countdown")
+pub fn countdown() -> Int
+
+fn secret() {
+  Ok(go())
+}
+
+pub fn main() {
+  let _ = secret()
+  rocket_ship.land()
+}
+"#;
+
+fn compile(src: &str, module_format: JavaScriptModuleFormat) -> String {
+    let compiled = compile_js_with_module_format(
+        src,
+        vec![(CURRENT_PACKAGE, "rocket_ship", DEPENDENCY)],
+        module_format,
+    )
+    .expect("compilation failed");
+    format!("----- SOURCE CODE\n{src}\n\n----- COMPILED JAVASCRIPT\n{compiled}")
+}
+
+#[test]
+fn ecmascript_module() {
+    insta::assert_snapshot!(compile(MODULE, JavaScriptModuleFormat::EcmaScript));
+}
+
+#[test]
+fn commonjs_module() {
+    insta::assert_snapshot!(compile(
+        &MODULE.replace("./ffi.mjs", "./ffi.cjs"),
+        JavaScriptModuleFormat::CommonJs
+    ));
+}
+
+#[test]
+fn commonjs_module_with_es_module_external() {
+    let error = compile_js_with_module_format(
+        MODULE,
+        vec![(CURRENT_PACKAGE, "rocket_ship", DEPENDENCY)],
+        JavaScriptModuleFormat::CommonJs,
+    )
+    .expect_err("compilation should fail");
+    insta::assert_snapshot!(error.pretty_string());
+}
+
+#[test]
+fn commonjs_empty_module() {
+    insta::assert_snapshot!(compile("", JavaScriptModuleFormat::CommonJs));
+}
+
+#[test]
+fn commonjs_module_deriving_json() {
+    insta::assert_snapshot!(compile(
+        r#"
+@derive(json)
+pub type Setting {
+  Setting(name: String)
+}
+"#,
+        JavaScriptModuleFormat::CommonJs
+    ));
+}
+
+#[test]
+fn commonjs_prelude_exports_everything() {
+    let prelude = commonjs_prelude();
+    assert!(!prelude.lines().any(|line| line.starts_with("export ")));
+    insta::assert_snapshot!(
+        prelude
+            .lines()
+            .last()
+            .expect("CommonJS prelude exports")
+            .replace(", ", ",\n")
+    );
+}
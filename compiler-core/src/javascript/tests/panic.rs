@@ -81,3 +81,52 @@ fn go(x) {
 "#,
     );
 }
+
+#[test]
+fn panic_with_panic_handler() {
+    assert_js!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main() {
+  panic as "oh no"
+}
+"#
+    );
+}
+
+#[test]
+fn todo_with_panic_handler() {
+    assert_js!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn let_assert_does_not_use_panic_handler() {
+    assert_js!(
+        panic_handler: ("my_app/panics", "handle"),
+        r#"
+pub fn main(x) {
+  let assert Ok(y) = x
+  y
+}
+"#
+    );
+}
+
+#[test]
+fn panic_handler_with_reserved_name() {
+    assert_js!(
+        panic_handler: ("my_app/panics", "class"),
+        r#"
+pub fn main() {
+  panic
+}
+"#
+    );
+}
@@ -68,3 +68,17 @@ pub fn get_name(person: Person) { person.name }
 pub fn get_age(person: Person) { person.age }"
     );
 }
+
+#[test]
+fn raw_identifier_labels() {
+    assert_js!(
+        "pub type Token {
+  Token(`type`: String, `case`: Int)
+}
+
+pub fn main(token: Token) {
+  let Token(`type`:, ..) = token
+  #(`type`, token.`case`, Token(..token, `case`: 1))
+}"
+    );
+}
@@ -44,6 +44,17 @@ pub fn unicode_escape_sequence_6_digits() -> String {
     );
 }
 
+#[test]
+fn unicode_escape_sequences_around_surrogates() {
+    assert_js!(
+        r#"
+pub fn main() -> String {
+  "\u{d7ff} \u{e000} \u{10ffff}"
+}
+"#,
+    );
+}
+
 #[test]
 fn string_literals() {
     assert_js!(
@@ -18,6 +18,7 @@ use crate::{
         CustomType, Definition, Function, Import, ModuleConstant, TypeAlias, TypedArg,
         TypedConstant, TypedDefinition, TypedModule, TypedRecordConstructor,
     },
+    build::JavaScriptModuleFormat,
     docvec,
     javascript::JavaScriptCodegenTarget,
     pretty::{Document, Documentable, break_},
@@ -216,10 +217,16 @@ impl<'a> TypeScriptGenerator<'a> {
             statements.push(line());
             Ok(statements.to_doc())
         } else if statements.is_empty() {
-            Ok(imports.into_doc(JavaScriptCodegenTarget::TypeScriptDeclarations))
+            Ok(imports.into_doc(
+                JavaScriptCodegenTarget::TypeScriptDeclarations,
+                JavaScriptModuleFormat::EcmaScript,
+            ))
         } else {
             Ok(docvec![
-                imports.into_doc(JavaScriptCodegenTarget::TypeScriptDeclarations),
+                imports.into_doc(
+                    JavaScriptCodegenTarget::TypeScriptDeclarations,
+                    JavaScriptModuleFormat::EcmaScript,
+                ),
                 line(),
                 statements,
                 line()
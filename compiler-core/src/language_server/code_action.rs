@@ -395,6 +395,245 @@ impl<'a> LetAssertToCase<'a> {
     }
 }
 
+/// Builder for code action to replace a case expression that has a single
+/// clause, with a pattern that always matches, with a `let` assignment. It is
+/// offered for the single clause case warning:
+///
+/// ```gleam
+/// case wibble {
+///   #(a, b) -> a + b
+/// }
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// let #(a, b) = wibble
+/// a + b
+/// ```
+///
+/// If the case expression isn't the last statement of a function or a block,
+/// for example if it's the value of a `let` or if it's followed by other
+/// statements, the assignment is wrapped in a block. This way the variables it
+/// introduces can't shadow the ones used by the code after it.
+///
+pub struct CaseToLet<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+    /// The start of each case expression that could be a `let` assignment.
+    single_clause_cases: HashSet<u32>,
+    /// The start of each case expression that is the last statement of a
+    /// function or a block.
+    tail_cases: HashSet<u32>,
+}
+
+impl<'ast> ast::visit::Visit<'ast> for CaseToLet<'_> {
+    fn visit_typed_function(&mut self, fun: &'ast ast::TypedFunction) {
+        self.register_tail_case(&fun.body);
+        ast::visit::visit_typed_function(self, fun);
+    }
+
+    fn visit_typed_expr_block(
+        &mut self,
+        location: &'ast SrcSpan,
+        statements: &'ast [TypedStatement],
+    ) {
+        self.register_tail_case(statements);
+        ast::visit::visit_typed_expr_block(self, location, statements);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        args: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<ast::TypeAst>,
+    ) {
+        self.register_tail_case(body);
+        ast::visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+
+    fn visit_typed_expr_case(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        subjects: &'ast [TypedExpr],
+        clauses: &'ast [ast::TypedClause],
+    ) {
+        // If the cursor is over nested cases only the outermost one is
+        // rewritten.
+        if self.edits.edits.is_empty() {
+            self.convert_to_let(*location, subjects, clauses);
+        }
+        ast::visit::visit_typed_expr_case(self, location, type_, subjects, clauses)
+    }
+}
+
+impl<'a> CaseToLet<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+    ) -> Self {
+        let single_clause_cases = module
+            .ast
+            .type_info
+            .warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                type_::Warning::SingleArmCase { location } => Some(location.start),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+            single_clause_cases,
+            tail_cases: HashSet::new(),
+        }
+    }
+
+    fn register_tail_case(&mut self, statements: &[TypedStatement]) {
+        if let Some(ast::Statement::Expression(TypedExpr::Case { location, .. })) =
+            statements.last()
+        {
+            let _ = self.tail_cases.insert(location.start);
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        if self.single_clause_cases.is_empty() {
+            return vec![];
+        }
+
+        self.visit_typed_module(&self.module.ast);
+        if self.edits.edits.is_empty() {
+            return vec![];
+        }
+
+        let mut actions = vec![];
+        CodeActionBuilder::new("Convert to `let`")
+            .kind(CodeActionKind::QUICKFIX)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(true)
+            .push_to(&mut actions);
+        actions
+    }
+
+    fn convert_to_let(
+        &mut self,
+        location: SrcSpan,
+        subjects: &[TypedExpr],
+        clauses: &[ast::TypedClause],
+    ) {
+        let ([subject], [clause]) = (subjects, clauses) else {
+            return;
+        };
+        let Some(pattern) = clause.pattern.first() else {
+            return;
+        };
+
+        // The code action is only offered over the `case subject` part of the
+        // expression, where the warning is reported.
+        let subject_location = subject.location();
+        let head = SrcSpan::new(location.start, subject_location.end);
+        if !self.single_clause_cases.contains(&location.start)
+            || !overlaps(self.params.range, self.edits.src_span_to_lsp_range(head))
+        {
+            return;
+        }
+
+        // If the clause's body is a block its statements are moved out of it.
+        let body = match &clause.then {
+            TypedExpr::Block { location, .. } => {
+                let inner = SrcSpan::new(location.start + 1, location.end - 1);
+                let code = self.code(inner);
+                let leading = code.len() - code.trim_start().len();
+                let trailing = code.len() - code.trim_end().len();
+                SrcSpan::new(inner.start + leading as u32, inner.end - trailing as u32)
+            }
+            then => then.location(),
+        };
+
+        let line_indentation = self.line_indentation(location.start);
+        let is_tail = self.tail_cases.contains(&location.start);
+        let indentation = if is_tail {
+            line_indentation
+        } else {
+            line_indentation + 2
+        };
+        let indent = " ".repeat(indentation);
+
+        let pattern = self.code(pattern.location());
+        let assignment = if is_tail {
+            format!("let {pattern} = ")
+        } else {
+            format!("{{\n{indent}let {pattern} = ")
+        };
+        self.edits.replace(
+            SrcSpan::new(location.start, subject_location.start),
+            assignment,
+        );
+        self.edits.replace(
+            SrcSpan::new(subject_location.end, body.start),
+            format!("\n{indent}"),
+        );
+
+        // The lines of the body are moved to the same indentation as the new
+        // assignment.
+        let extra_indentation = self
+            .line_indentation(body.start)
+            .saturating_sub(indentation);
+        if extra_indentation > 0 {
+            let newlines = self
+                .code(body)
+                .match_indices('\n')
+                .map(|(index, _)| body.start + index as u32 + 1)
+                .collect_vec();
+            for line_start in newlines {
+                let line = &self.module.code[line_start as usize..];
+                let spaces = line
+                    .chars()
+                    .take_while(|char| *char == ' ')
+                    .count()
+                    .min(extra_indentation);
+                self.edits
+                    .delete(SrcSpan::new(line_start, line_start + spaces as u32));
+            }
+        }
+
+        let closing = if is_tail {
+            String::new()
+        } else {
+            format!("\n{}}}", " ".repeat(line_indentation))
+        };
+        self.edits
+            .replace(SrcSpan::new(body.end, location.end), closing);
+    }
+
+    fn code(&self, location: SrcSpan) -> &'a str {
+        self.module
+            .code
+            .get(location.start as usize..location.end as usize)
+            .expect("Location must be valid")
+    }
+
+    /// The number of spaces at the start of the line the given byte is on.
+    fn line_indentation(&self, byte_index: u32) -> usize {
+        let code = &self.module.code[..byte_index as usize];
+        let line_start = code.rfind('\n').map_or(0, |index| index + 1);
+        code[line_start..]
+            .chars()
+            .take_while(|char| *char == ' ')
+            .count()
+    }
+}
+
 struct PatternVariableFinder {
     pattern_variables: Vec<EcoString>,
 }
@@ -928,9 +1167,9 @@ pub fn code_action_add_missing_patterns(
     let missing_patterns = errors
         .iter()
         .filter_map(|error| match error {
-            type_::Error::InexhaustiveCaseExpression { location, missing } => {
-                Some((*location, missing))
-            }
+            type_::Error::InexhaustiveCaseExpression {
+                location, missing, ..
+            } => Some((*location, missing)),
             _ => None,
         })
         .collect_vec();
@@ -1155,7 +1394,8 @@ impl<'ast> ast::visit::Visit<'ast> for AddAnnotations<'_> {
         let location = match kind {
             // Function captures don't need any type annotations
             FunctionLiteralKind::Capture { .. } => return,
-            FunctionLiteralKind::Anonymous { head } => head,
+            FunctionLiteralKind::Anonymous { head }
+            | FunctionLiteralKind::TrailingBlock { head } => head,
             FunctionLiteralKind::Use { location } => location,
         };
 
@@ -2609,7 +2849,7 @@ impl<'ast> ast::visit::Visit<'ast> for ConvertToUse<'ast> {
 
 fn turn_statement_into_use(statement: &TypedStatement) -> Option<CallLocations> {
     match statement {
-        ast::Statement::Use(_) | ast::Statement::Assignment(_) => None,
+        ast::Statement::Use(_) | ast::Statement::Assignment(_) | ast::Statement::Assert(_) => None,
         ast::Statement::Expression(expression) => turn_expression_into_use(expression),
     }
 }
@@ -2643,10 +2883,7 @@ fn turn_expression_into_use(expr: &TypedExpr) -> Option<CallLocations> {
         return None;
     };
 
-    let callback_args_span = match (callback_args.first(), callback_args.last()) {
-        (Some(first), Some(last)) => Some(first.location.merge(&last.location)),
-        _ => None,
-    };
+    let callback_args_span = SrcSpan::union_all(callback_args.iter().map(|arg| arg.location));
 
     let arg_before_callback_span = if args.len() >= 2 {
         args.get(args.len() - 2).map(|call_arg| call_arg.location)
@@ -2861,9 +3098,9 @@ impl<'ast> ast::visit::Visit<'ast> for ExtractVariable<'ast> {
             // just a single expression statement. When visiting we must record
             // we're inside a capture body.
             FunctionLiteralKind::Capture { .. } => Some(ExtractVariablePosition::InsideCaptureBody),
-            FunctionLiteralKind::Anonymous { .. } | FunctionLiteralKind::Use { .. } => {
-                self.position
-            }
+            FunctionLiteralKind::Anonymous { .. }
+            | FunctionLiteralKind::Use { .. }
+            | FunctionLiteralKind::TrailingBlock { .. } => self.position,
         };
 
         self.at_optional_position(position, |this| {
@@ -3286,6 +3523,278 @@ impl<'ast> ast::visit::Visit<'ast> for ExtractConstant<'ast> {
     }
 }
 
+/// Builder for code action to extract the selected expression into a new
+/// function. Any local variable used by the expression, but defined outside
+/// of it, becomes an argument of the new function:
+///
+/// ```gleam
+/// pub fn main() {
+///   let x = 1
+///   x * 2 + 1
+/// //^^^^^^^^^ selected
+/// }
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// pub fn main() {
+///   let x = 1
+///   function(x)
+/// }
+///
+/// fn function(x: Int) -> Int {
+///   x * 2 + 1
+/// }
+/// ```
+///
+pub struct ExtractFunction<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+    last_visited_function_end: Option<u32>,
+    selected_expression: Option<ExpressionToExtract<'a>>,
+}
+
+struct ExpressionToExtract<'a> {
+    expression: &'a TypedExpr,
+    function_end: u32,
+}
+
+impl<'a> ExtractFunction<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+    ) -> Self {
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+            last_visited_function_end: None,
+            selected_expression: None,
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        // The action is only offered for an actual selection, otherwise it
+        // would pop up for any expression under the cursor.
+        if self.params.range.start == self.params.range.end {
+            return vec![];
+        }
+
+        self.visit_typed_module(&self.module.ast);
+
+        let Some(ExpressionToExtract {
+            expression,
+            function_end,
+        }) = self.selected_expression
+        else {
+            return vec![];
+        };
+
+        let location = expression.location();
+        let mut free_variables = FreeVariables {
+            extracted: location,
+            variables: vec![],
+            can_be_extracted: true,
+        };
+        free_variables.visit_typed_expr(expression);
+        if !free_variables.can_be_extracted {
+            return vec![];
+        }
+
+        let name = generate_new_name_for_function(self.module);
+        let mut printer = Printer::new(&self.module.ast.names);
+        let parameters = free_variables
+            .variables
+            .iter()
+            .map(|(name, type_)| format!("{name}: {}", printer.print_type(type_)))
+            .join(", ");
+        let return_type = printer.print_type(&expression.type_());
+        let arguments = free_variables
+            .variables
+            .iter()
+            .map(|(name, _)| name)
+            .join(", ");
+
+        // If the selection is a block there's no need to keep its braces
+        // around: its statements become the body of the new function.
+        let body_location = match expression {
+            TypedExpr::Block { statements, .. } => SrcSpan::new(
+                statements.first().location().start,
+                statements.last().location().end,
+            ),
+            _ => location,
+        };
+        let code = self.module.code.as_str();
+        let line_start = code[..body_location.start as usize]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        let nesting = code[line_start..]
+            .chars()
+            .take_while(|char| *char == ' ')
+            .count();
+        let body = code
+            .get(body_location.start as usize..body_location.end as usize)
+            .expect("selected expression")
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| {
+                // The first line starts right at the selection, the following
+                // ones keep their indentation relative to it.
+                let line = if i == 0 {
+                    line
+                } else {
+                    let indentation = line.len() - line.trim_start_matches(' ').len();
+                    &line[indentation.min(nesting)..]
+                };
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("  {line}")
+                }
+            })
+            .join("\n");
+
+        self.edits.replace(location, format!("{name}({arguments})"));
+        self.edits.insert(
+            function_end,
+            format!("\n\nfn {name}({parameters}) -> {return_type} {{\n{body}\n}}"),
+        );
+
+        let mut action = Vec::with_capacity(1);
+        CodeActionBuilder::new("Extract function")
+            .kind(CodeActionKind::REFACTOR_EXTRACT)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(false)
+            .push_to(&mut action);
+        action
+    }
+}
+
+impl<'ast> ast::visit::Visit<'ast> for ExtractFunction<'ast> {
+    fn visit_typed_function(&mut self, fun: &'ast ast::TypedFunction) {
+        self.last_visited_function_end = Some(fun.end_position);
+        ast::visit::visit_typed_function(self, fun);
+    }
+
+    fn visit_typed_expr(&mut self, expr: &'ast TypedExpr) {
+        if self.selected_expression.is_some() {
+            return;
+        }
+
+        // We extract the outermost expression that is inside the selection,
+        // variables and module selects by themselves are not worth turning
+        // into a function.
+        let expr_range = self.edits.src_span_to_lsp_range(expr.location());
+        match (expr, self.last_visited_function_end) {
+            (TypedExpr::Var { .. } | TypedExpr::ModuleSelect { .. }, _) | (_, None) => (),
+            (_, Some(function_end)) if within(expr_range, self.params.range) => {
+                self.selected_expression = Some(ExpressionToExtract {
+                    expression: expr,
+                    function_end,
+                });
+                return;
+            }
+            (_, Some(_)) => (),
+        }
+
+        ast::visit::visit_typed_expr(self, expr);
+    }
+}
+
+/// Collects the local variables that are used by an expression but defined
+/// outside of it, in the order they first appear in.
+///
+struct FreeVariables {
+    extracted: SrcSpan,
+    variables: Vec<(EcoString, Arc<Type>)>,
+    /// This is false if the expression references a variable generated by the
+    /// compiler, for example if it's a single step of a pipeline, or if it
+    /// contains invalid code.
+    can_be_extracted: bool,
+}
+
+impl FreeVariables {
+    fn register(&mut self, name: &EcoString, type_: &Arc<Type>, definition: SrcSpan) {
+        if self.extracted.contains(definition.start) {
+            return;
+        }
+        if name.starts_with('_') {
+            self.can_be_extracted = false;
+        } else if self.variables.iter().all(|(used, _)| used != name) {
+            self.variables.push((name.clone(), type_.clone()));
+        }
+    }
+}
+
+impl<'ast> ast::visit::Visit<'ast> for FreeVariables {
+    fn visit_typed_expr_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        name: &'ast EcoString,
+    ) {
+        if let type_::ValueConstructorVariant::LocalVariable { location, .. } = &constructor.variant
+        {
+            self.register(name, &constructor.type_, *location);
+        }
+    }
+
+    fn visit_typed_clause_guard_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        type_: &'ast Arc<Type>,
+        definition_location: &'ast SrcSpan,
+    ) {
+        self.register(name, type_, *definition_location);
+    }
+
+    fn visit_typed_pattern_var_usage(
+        &mut self,
+        _location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        constructor: &'ast Option<ValueConstructor>,
+        type_: &'ast Arc<Type>,
+    ) {
+        if let Some(ValueConstructor {
+            variant: type_::ValueConstructorVariant::LocalVariable { location, .. },
+            ..
+        }) = constructor
+        {
+            self.register(name, type_, *location);
+        }
+    }
+
+    fn visit_typed_expr_invalid(&mut self, _location: &'ast SrcSpan, _type_: &'ast Arc<Type>) {
+        self.can_be_extracted = false;
+    }
+}
+
+fn generate_new_name_for_function(module: &Module) -> EcoString {
+    let mut name_generator = NameGenerator::new();
+    let already_taken_names = VariablesNames {
+        names: module
+            .ast
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ast::Definition::ModuleConstant(constant) => Some(constant.name.clone()),
+                ast::Definition::Function(function) => function.name.as_ref().map(|n| n.1.clone()),
+
+                ast::Definition::TypeAlias(_)
+                | ast::Definition::CustomType(_)
+                | ast::Definition::Import(_) => None,
+            })
+            .chain(module.ast.type_info.values.keys().cloned())
+            .collect(),
+    };
+    name_generator.reserve_variable_names(already_taken_names);
+    name_generator.rename_to_avoid_shadowing("function".into())
+}
+
 /// Builder for code action to apply the "expand function capture" action.
 ///
 pub struct ExpandFunctionCapture<'a> {
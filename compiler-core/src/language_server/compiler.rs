@@ -72,6 +72,8 @@ where
             compile: build::Compile::All,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            javascript_module_format: build::JavaScriptModuleFormat::EcmaScript,
+            whole_program: false,
         };
         let mut project_compiler = ProjectCompiler::new(
             config,
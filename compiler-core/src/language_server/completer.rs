@@ -1,11 +1,11 @@
 use std::{collections::HashMap, sync::Arc};
 
-use ecow::EcoString;
+use ecow::{EcoString, eco_format};
 use itertools::Itertools;
 use lsp_types::{
     CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
-    Documentation, MarkupContent, MarkupKind, Position, Range, TextDocumentPositionParams,
-    TextEdit,
+    Documentation, InsertTextFormat, MarkupContent, MarkupKind, Position, Range,
+    TextDocumentPositionParams, TextEdit,
 };
 use strum::IntoEnumIterator;
 use vec1::Vec1;
@@ -21,8 +21,8 @@ use crate::{
     line_numbers::LineNumbers,
     type_::{
         self, FieldMap, ModuleInterface, PRELUDE_MODULE_NAME, PreludeType, RecordAccessor, Type,
-        TypeConstructor, ValueConstructorVariant, collapse_links, error::VariableOrigin,
-        pretty::Printer,
+        TypeConstructor, TypeValueConstructor, ValueConstructorVariant, collapse_links,
+        error::VariableOrigin, pretty::Printer,
     },
 };
 
@@ -41,6 +41,8 @@ use super::{
 enum CompletionKind {
     // A label for a function or type definition
     Label,
+    // A constructor of the type expected where the cursor is
+    ExpectedConstructor,
     // A field of a record
     FieldAccessor,
     // Values or types defined in the current module
@@ -58,7 +60,7 @@ enum CompletionKind {
 // less specific ones..
 fn sort_text(kind: CompletionKind, label: &str) -> String {
     let priority: u8 = match kind {
-        CompletionKind::Label => 0,
+        CompletionKind::Label | CompletionKind::ExpectedConstructor => 0,
         CompletionKind::FieldAccessor => 1,
         CompletionKind::LocallyDefined => 2,
         CompletionKind::ImportedModule => 3,
@@ -827,6 +829,110 @@ where
             .collect()
     }
 
+    /// Provides completions constructing a value of the type of the argument
+    /// the cursor is at, when the context being edited is a call.
+    pub fn completion_argument_constructors(
+        &'a self,
+        fun: &TypedExpr,
+        existing_args: &[CallArg<TypedExpr>],
+    ) -> Vec<CompletionItem> {
+        let Some((args_types, _)) = fun.type_().fn_types() else {
+            return vec![];
+        };
+        // Labelled arguments are put back in the position of their
+        // parameter, so the argument being written can't be told which one
+        // it is if there are any.
+        if existing_args.iter().any(|arg| arg.label.is_some()) {
+            return vec![];
+        }
+
+        let cursor = self
+            .module_line_numbers
+            .byte_index(self.cursor_position.line, self.cursor_position.character);
+        let position = existing_args
+            .iter()
+            .filter(|arg| arg.location.end <= cursor)
+            .count();
+        match args_types.get(position) {
+            Some(type_) => self.completion_constructors(type_.clone()),
+            None => vec![],
+        }
+    }
+
+    /// Provides completions constructing a value of the type expected where
+    /// the cursor is, with a placeholder for each of the fields of the
+    /// constructor.
+    pub fn completion_constructors(&'a self, expected_type: Arc<Type>) -> Vec<CompletionItem> {
+        let (insert_range, module_select) = self.get_phrase_surrounding_completion();
+        if module_select.is_some() {
+            return vec![];
+        }
+
+        let expected_type = collapse_links(expected_type);
+        let Type::Named { module, name, .. } = expected_type.as_ref() else {
+            return vec![];
+        };
+        let constructors = if module == &self.module.name {
+            self.module.ast.type_info.types_value_constructors.get(name)
+        } else {
+            self.compiler
+                .get_module_interface(module)
+                .and_then(|module| module.types_value_constructors.get(name))
+        };
+        let Some(constructors) = constructors else {
+            return vec![];
+        };
+
+        let detail = Printer::new().pretty_print(&expected_type, 0);
+        constructors
+            .variants
+            .iter()
+            // A constructor with no fields is already offered by the value
+            // completions.
+            .filter(|constructor| !constructor.parameters.is_empty())
+            .filter_map(|constructor| {
+                let name = self.constructor_name(module, &constructor.name)?;
+                Some(constructor_completion(
+                    &name,
+                    constructor,
+                    &detail,
+                    insert_range,
+                ))
+            })
+            .collect()
+    }
+
+    /// The name a constructor can be referred to with in the current module,
+    /// if it can be used without a new import.
+    fn constructor_name(&self, module: &EcoString, constructor: &EcoString) -> Option<EcoString> {
+        if module == &self.module.name || module == PRELUDE_MODULE_NAME {
+            return Some(constructor.clone());
+        }
+
+        let interface = self.compiler.get_module_interface(module)?;
+        let value = interface.get_public_value(constructor)?;
+        if !self.is_suggestable_import(&value.publicity, interface.package.as_str()) {
+            return None;
+        }
+        let import = self
+            .module
+            .ast
+            .definitions
+            .iter()
+            .filter_map(get_import)
+            .find(|import| &import.module == module)?;
+        match import
+            .unqualified_values
+            .iter()
+            .find(|unqualified| &unqualified.name == constructor)
+        {
+            Some(unqualified) => Some(unqualified.used_name().clone()),
+            None => import
+                .used_name()
+                .map(|module| eco_format!("{module}.{constructor}")),
+        }
+    }
+
     fn root_package_name(&self) -> &str {
         self.compiler.project_compiler.config.name.as_str()
     }
@@ -945,6 +1051,43 @@ fn value_completion(
     }
 }
 
+fn constructor_completion(
+    name: &str,
+    constructor: &TypeValueConstructor,
+    detail: &str,
+    insert_range: Range,
+) -> CompletionItem {
+    let field = |label: &Option<EcoString>, placeholder: &str| match label {
+        Some(label) => format!("{label}: {placeholder}"),
+        None => placeholder.into(),
+    };
+    let fields = constructor
+        .parameters
+        .iter()
+        .map(|parameter| field(&parameter.label, "_"))
+        .join(", ");
+    let snippet_fields = constructor
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| field(&parameter.label, &format!("${}", index + 1)))
+        .join(", ");
+
+    let label = format!("{name}({fields})");
+    CompletionItem {
+        label: label.clone(),
+        kind: Some(CompletionItemKind::SNIPPET),
+        detail: Some(detail.into()),
+        sort_text: Some(sort_text(CompletionKind::ExpectedConstructor, &label)),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+            range: insert_range,
+            new_text: format!("{name}({snippet_fields})"),
+        })),
+        ..Default::default()
+    }
+}
+
 fn local_value_completion(
     module_name: &str,
     name: &str,
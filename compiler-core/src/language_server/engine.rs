@@ -16,7 +16,7 @@ use crate::{
     type_::{
         self, Deprecation, ModuleInterface, Type, TypeConstructor, ValueConstructor,
         ValueConstructorVariant,
-        error::{Named, VariableOrigin},
+        error::{Named, UnifyErrorSituation, VariableOrigin},
         printer::Printer,
     },
 };
@@ -34,10 +34,10 @@ use std::{collections::HashSet, sync::Arc};
 use super::{
     DownloadDependencies, MakeLocker,
     code_action::{
-        AddAnnotations, CodeActionBuilder, ConvertFromUse, ConvertToFunctionCall, ConvertToPipe,
-        ConvertToUse, ExpandFunctionCapture, ExtractConstant, ExtractVariable,
-        FillInMissingLabelledArgs, FillUnusedFields, GenerateDynamicDecoder, GenerateFunction,
-        GenerateJsonEncoder, InlineVariable, InterpolateString, LetAssertToCase,
+        AddAnnotations, CaseToLet, CodeActionBuilder, ConvertFromUse, ConvertToFunctionCall,
+        ConvertToPipe, ConvertToUse, ExpandFunctionCapture, ExtractConstant, ExtractFunction,
+        ExtractVariable, FillInMissingLabelledArgs, FillUnusedFields, GenerateDynamicDecoder,
+        GenerateFunction, GenerateJsonEncoder, InlineVariable, InterpolateString, LetAssertToCase,
         PatternMatchOnValue, RedundantTupleInCaseSubject, RemoveEchos, UseLabelShorthandSyntax,
         code_action_add_missing_patterns, code_action_convert_qualified_constructor_to_unqualified,
         code_action_convert_unqualified_constructor_to_qualified, code_action_import_module,
@@ -290,6 +290,15 @@ where
                     let mut completions = vec![];
                     completions.append(&mut completer.completion_values());
                     completions.append(&mut completer.completion_labels(fun, args));
+                    completions.append(&mut completer.completion_argument_constructors(fun, args));
+                    Some(completions)
+                }
+                // The type of code that could not be type checked, like a name
+                // that is still being written, is the one expected where it is.
+                Located::Expression(TypedExpr::Invalid { type_, .. }) => {
+                    let mut completions = vec![];
+                    completions.append(&mut completer.completion_values());
+                    completions.append(&mut completer.completion_constructors(type_.clone()));
                     Some(completions)
                 }
                 Located::Expression(TypedExpr::RecordAccess { record, .. }) => {
@@ -356,7 +365,10 @@ where
             let lines = LineNumbers::new(&module.code);
 
             code_action_unused_values(module, &lines, &params, &mut actions);
+            code_action_simplify_bools(module, &lines, &params, &mut actions);
+            code_action_remove_function_wrappers(module, &lines, &params, &mut actions);
             code_action_unused_imports(module, &lines, &params, &mut actions);
+            code_action_import_only_type(module, &lines, &params, &mut actions);
             code_action_convert_qualified_constructor_to_unqualified(
                 module,
                 &lines,
@@ -370,6 +382,7 @@ where
                 &mut actions,
             );
             code_action_fix_names(&lines, &params, &this.error, &mut actions);
+            code_action_fix_numeric_literals(&lines, &params, &this.error, &mut actions);
             code_action_import_module(module, &lines, &params, &this.error, &mut actions);
             code_action_add_missing_patterns(module, &lines, &params, &this.error, &mut actions);
             code_action_inexhaustive_let_to_case(
@@ -380,6 +393,7 @@ where
                 &mut actions,
             );
             actions.extend(LetAssertToCase::new(module, &lines, &params).code_actions());
+            actions.extend(CaseToLet::new(module, &lines, &params).code_actions());
             actions
                 .extend(RedundantTupleInCaseSubject::new(module, &lines, &params).code_actions());
             actions.extend(UseLabelShorthandSyntax::new(module, &lines, &params).code_actions());
@@ -392,6 +406,7 @@ where
             actions.extend(InterpolateString::new(module, &lines, &params).code_actions());
             actions.extend(ExtractVariable::new(module, &lines, &params).code_actions());
             actions.extend(ExtractConstant::new(module, &lines, &params).code_actions());
+            actions.extend(ExtractFunction::new(module, &lines, &params).code_actions());
             actions.extend(GenerateFunction::new(module, &lines, &params).code_actions());
             actions.extend(ConvertToPipe::new(module, &lines, &params).code_actions());
             actions.extend(ConvertToFunctionCall::new(module, &lines, &params).code_actions());
@@ -1144,13 +1159,14 @@ fn hover_for_function_head(
         .as_ref()
         .map(|(_, doc)| doc)
         .unwrap_or(&empty_str);
+    let since = since_section(fun.since.as_deref());
     let function_type = get_function_type(fun);
     let formatted_type = Printer::new(&module.ast.names).print_type(&function_type);
     let contents = format!(
         "```gleam
 {formatted_type}
 ```
-{documentation}"
+{documentation}{since}"
     );
     Hover {
         contents: HoverContents::Scalar(MarkedString::String(contents)),
@@ -1225,7 +1241,8 @@ fn hover_for_module_constant(
         .as_ref()
         .map(|(_, doc)| doc)
         .unwrap_or(&empty_str);
-    let contents = format!("```gleam\n{type_}\n```\n{documentation}");
+    let since = since_section(constant.since.as_deref());
+    let contents = format!("```gleam\n{type_}\n```\n{documentation}{since}");
     Hover {
         contents: HoverContents::Scalar(MarkedString::String(contents)),
         range: Some(src_span_to_lsp_range(constant.location, &line_numbers)),
@@ -1239,6 +1256,7 @@ fn hover_for_expression(
     hex_deps: &HashSet<EcoString>,
 ) -> Hover {
     let documentation = expression.get_documentation().unwrap_or_default();
+    let since = since_section(expression.get_since());
 
     let link_section = get_expr_qualified_name(expression)
         .and_then(|(module_name, name)| {
@@ -1252,7 +1270,7 @@ fn hover_for_expression(
         "```gleam
 {type_}
 ```
-{documentation}{link_section}"
+{documentation}{since}{link_section}"
     );
     Hover {
         contents: HoverContents::Scalar(MarkedString::String(contents)),
@@ -1269,6 +1287,7 @@ fn hover_for_imported_value(
     module: &Module,
 ) -> Hover {
     let documentation = value.get_documentation().unwrap_or_default();
+    let since = since_section(value.get_since());
 
     let link_section = hex_module_imported_from.map_or("".to_string(), |m| {
         format_hexdocs_link_section(m.package.as_str(), m.name.as_str(), Some(name))
@@ -1280,7 +1299,7 @@ fn hover_for_imported_value(
         "```gleam
 {type_}
 ```
-{documentation}{link_section}"
+{documentation}{since}{link_section}"
     );
     Hover {
         contents: HoverContents::Scalar(MarkedString::String(contents)),
@@ -1288,6 +1307,16 @@ fn hover_for_imported_value(
     }
 }
 
+/// The line of a hover saying in which version of its package a value was
+/// added, if it has a `@since` attribute.
+///
+fn since_section(since: Option<&str>) -> String {
+    match since {
+        Some(version) => format!("\n\nSince: `{version}`\n"),
+        None => String::new(),
+    }
+}
+
 fn hover_for_module(
     module: &ModuleInterface,
     location: SrcSpan,
@@ -1395,6 +1424,102 @@ fn code_action_unused_values(
     }
 }
 
+/// Builds the code action to replace a boolean expression with its simpler
+/// form, like `x == True` with `x` or `!!x` with `x`.
+///
+fn code_action_simplify_bools(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &lsp::CodeActionParams,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    let source = |location: &SrcSpan| {
+        module
+            .code
+            .get(location.start as usize..location.end as usize)
+            .unwrap_or_default()
+    };
+
+    for warning in &module.ast.type_info.warnings {
+        let (location, new_text) = match warning {
+            type_::Warning::SimplifiableBool {
+                location,
+                operand,
+                simplified,
+            } => (location, simplified.replacement(source(operand))),
+
+            type_::Warning::UnnecessaryDoubleBoolNegation { location } => {
+                // Both negations are removed, but not any other one the
+                // operand might start with, as in `!!!x`.
+                let operand = source(location)
+                    .strip_prefix('!')
+                    .map(str::trim_start)
+                    .and_then(|rest| rest.strip_prefix('!'))
+                    .unwrap_or_default();
+                (location, operand.trim_start().into())
+            }
+
+            _ => continue,
+        };
+
+        let range = src_span_to_lsp_range(*location, line_numbers);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        let edit = TextEdit {
+            range,
+            new_text: new_text.into(),
+        };
+
+        CodeActionBuilder::new("Simplify boolean expression")
+            .kind(lsp_types::CodeActionKind::QUICKFIX)
+            .changes(uri.clone(), vec![edit])
+            .preferred(true)
+            .push_to(actions);
+    }
+}
+
+/// Builds the code action to replace an anonymous function that only calls
+/// another function with that function, like `fn(x) { wibble(x) }` with
+/// `wibble`.
+///
+fn code_action_remove_function_wrappers(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &lsp::CodeActionParams,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+
+    for warning in &module.ast.type_info.warnings {
+        let type_::Warning::UnnecessaryFunctionWrapper { location, function } = warning else {
+            continue;
+        };
+
+        let range = src_span_to_lsp_range(*location, line_numbers);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        let function = module
+            .code
+            .get(function.start as usize..function.end as usize)
+            .unwrap_or_default();
+        let edit = TextEdit {
+            range,
+            new_text: function.into(),
+        };
+
+        CodeActionBuilder::new("Remove unnecessary function wrapper")
+            .kind(lsp_types::CodeActionKind::QUICKFIX)
+            .changes(uri.clone(), vec![edit])
+            .preferred(true)
+            .push_to(actions);
+    }
+}
+
 /// Code action to remove unused imports.
 ///
 fn code_action_unused_imports(
@@ -1457,6 +1582,75 @@ fn code_action_unused_imports(
         .push_to(actions);
 }
 
+/// Code action to remove a constructor from an import when only the type
+/// with the same name is used:
+///
+/// ```gleam
+/// import wibble.{type Wibble, Wibble}
+/// // becomes
+/// import wibble.{type Wibble}
+/// ```
+///
+fn code_action_import_only_type(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &lsp::CodeActionParams,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    for warning in &module.ast.type_info.warnings {
+        let type_::Warning::ImportedConstructorOnlyUsedAsType { location, .. } = warning else {
+            continue;
+        };
+        let range = src_span_to_lsp_range(*location, line_numbers);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        // The constructor is removed along with the comma separating it from
+        // the item that follows it or, if it's the last one, precedes it.
+        let Some(items) = module.ast.definitions.iter().find_map(|definition| {
+            let Definition::Import(import) = definition else {
+                return None;
+            };
+            import.location.contains(location.start).then(|| {
+                import
+                    .unqualified_values
+                    .iter()
+                    .chain(&import.unqualified_types)
+                    .map(|item| item.location)
+                    .sorted_by_key(|item| item.start)
+                    .collect_vec()
+            })
+        }) else {
+            continue;
+        };
+        let Some(index) = items.iter().position(|item| item == location) else {
+            continue;
+        };
+        let removed = match (items.get(index + 1), index.checked_sub(1)) {
+            (Some(next), _) => SrcSpan::new(location.start, next.start),
+            (None, Some(previous)) => match items.get(previous) {
+                Some(previous) => SrcSpan::new(previous.end, location.end),
+                None => continue,
+            },
+            (None, None) => continue,
+        };
+
+        CodeActionBuilder::new("Import only the type")
+            .kind(lsp_types::CodeActionKind::QUICKFIX)
+            .changes(
+                uri.clone(),
+                vec![TextEdit {
+                    range: src_span_to_lsp_range(removed, line_numbers),
+                    new_text: "".into(),
+                }],
+            )
+            .preferred(true)
+            .push_to(actions);
+    }
+}
+
 struct NameCorrection {
     pub location: SrcSpan,
     pub correction: EcoString,
@@ -1514,6 +1708,44 @@ fn code_action_fix_names(
     }
 }
 
+fn code_action_fix_numeric_literals(
+    line_numbers: &LineNumbers,
+    params: &lsp::CodeActionParams,
+    error: &Option<Error>,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    let Some(Error::Type { errors, .. }) = error else {
+        return;
+    };
+
+    for error in errors {
+        let type_::Error::CouldNotUnify {
+            location,
+            situation: Some(UnifyErrorSituation::NumericLiteralMismatch { replacement }),
+            ..
+        } = error
+        else {
+            continue;
+        };
+
+        let range = src_span_to_lsp_range(*location, line_numbers);
+        // Check if the user's cursor is on the mistyped literal
+        if overlaps(params.range, range) {
+            let edit = TextEdit {
+                range,
+                new_text: replacement.to_string(),
+            };
+
+            CodeActionBuilder::new(&format!("Replace with `{replacement}`"))
+                .kind(lsp_types::CodeActionKind::QUICKFIX)
+                .changes(uri.clone(), vec![edit])
+                .preferred(true)
+                .push_to(actions);
+        }
+    }
+}
+
 // Check if the edit empties a whole line; if so, delete the line.
 fn delete_line(span: &SrcSpan, line_numbers: &LineNumbers) -> bool {
     line_numbers.line_starts.iter().any(|&line_start| {
@@ -8,7 +8,8 @@ use lsp_types::{
     notification::{DidChangeTextDocument, DidCloseTextDocument, DidSaveTextDocument},
     request::{
         CodeActionRequest, Completion, DocumentSymbolRequest, Formatting, GotoTypeDefinition,
-        HoverRequest, PrepareRenameRequest, References, Rename, SignatureHelpRequest,
+        HoverRequest, PrepareRenameRequest, RangeFormatting, References, Rename,
+        SignatureHelpRequest,
     },
 };
 use std::time::Duration;
@@ -22,6 +23,7 @@ pub enum Message {
 #[derive(Debug)]
 pub enum Request {
     Format(lsp::DocumentFormattingParams),
+    RangeFormat(lsp::DocumentRangeFormattingParams),
     Hover(lsp::HoverParams),
     GoToDefinition(lsp::GotoDefinitionParams),
     GoToTypeDefinition(lsp::GotoDefinitionParams),
@@ -42,6 +44,10 @@ impl Request {
                 let params = cast_request::<Formatting>(request);
                 Some(Message::Request(id, Request::Format(params)))
             }
+            "textDocument/rangeFormatting" => {
+                let params = cast_request::<RangeFormatting>(request);
+                Some(Message::Request(id, Request::RangeFormat(params)))
+            }
             "textDocument/hover" => {
                 let params = cast_request::<HoverRequest>(request);
                 Some(Message::Request(id, Request::Hover(params)))
@@ -8,6 +8,7 @@ use crate::{
     ast::{self, SrcSpan},
     build::Module,
     line_numbers::LineNumbers,
+    parse::lexer::str_to_keyword,
     reference::ReferenceKind,
     type_::{ModuleInterface, error::Named},
 };
@@ -28,6 +29,20 @@ fn workspace_edit(uri: Url, edits: Vec<TextEdit>) -> WorkspaceEdit {
     }
 }
 
+/// The new name as it is written in the code. Names that would otherwise be
+/// read as a keyword are wrapped in backticks: `` `type` ``.
+///
+/// The location of a raw identifier includes its backticks, so replacing it
+/// with the escaped name keeps the code valid whether or not either name is a
+/// keyword.
+fn escaped_name(name: &str) -> String {
+    if str_to_keyword(name).is_some() {
+        format!("`{name}`")
+    } else {
+        name.into()
+    }
+}
+
 pub enum VariableRenameKind {
     Variable,
     LabelShorthand,
@@ -52,18 +67,19 @@ pub fn rename_local_variable(
 
     let uri = params.text_document_position.text_document.uri.clone();
     let mut edits = TextEdits::new(line_numbers);
+    let new_name = escaped_name(&params.new_name);
 
     let references = find_variable_references(&module.ast, definition_location);
 
     match kind {
-        VariableRenameKind::Variable => edits.replace(definition_location, params.new_name.clone()),
+        VariableRenameKind::Variable => edits.replace(definition_location, new_name.clone()),
         VariableRenameKind::LabelShorthand => {
-            edits.insert(definition_location.end, format!(" {}", params.new_name))
+            edits.insert(definition_location.end, format!(" {new_name}"))
         }
     }
 
     for location in references {
-        edits.replace(location, params.new_name.clone());
+        edits.replace(location, new_name.clone());
     }
 
     Some(workspace_edit(uri, edits.edits))
@@ -138,7 +154,7 @@ pub fn rename_module_entity(
                 &mut workspace_edit,
                 renamed.module_name,
                 renamed.name,
-                params.new_name.clone(),
+                escaped_name(&params.new_name),
                 renamed.layer,
             );
         }
@@ -202,15 +218,16 @@ fn alias_references_in_module(
     let references = reference_map.get(&(module_name.clone(), name.clone()))?;
 
     let mut edits = TextEdits::new(&module.ast.type_info.line_numbers);
+    let new_name = escaped_name(&params.new_name);
 
     for reference in references {
         match reference.kind {
             ReferenceKind::Qualified => {}
             ReferenceKind::Unqualified | ReferenceKind::Alias => {
-                edits.replace(reference.location, params.new_name.clone())
+                edits.replace(reference.location, new_name.clone())
             }
             ReferenceKind::Import => {
-                edits.insert(reference.location.end, format!(" as {}", params.new_name))
+                edits.insert(reference.location.end, format!(" as {new_name}"))
             }
             ReferenceKind::Definition => {}
         }
@@ -11,6 +11,7 @@ use crate::{
         engine::{self, LanguageServerEngine},
         feedback::{Feedback, FeedbackBookKeeper},
         files::FileSystemProxy,
+        lsp_range_to_src_span,
         router::Router,
         src_span_to_lsp_range,
     },
@@ -18,6 +19,7 @@ use crate::{
 };
 use camino::{Utf8Path, Utf8PathBuf};
 use debug_ignore::DebugIgnore;
+use ecow::EcoString;
 use itertools::Itertools;
 use lsp_types::{
     self as lsp, HoverProviderCapability, InitializeParams, Position, PublishDiagnosticsParams,
@@ -100,6 +102,7 @@ where
     fn handle_request(&mut self, id: lsp_server::RequestId, request: Request) {
         let (payload, feedback) = match request {
             Request::Format(param) => self.format(param),
+            Request::RangeFormat(param) => self.range_format(param),
             Request::Hover(param) => self.hover(param),
             Request::GoToDefinition(param) => self.goto_definition(param),
             Request::Completion(param) => self.completion(param),
@@ -301,6 +304,29 @@ where
         (json, Feedback::default())
     }
 
+    fn range_format(&mut self, params: lsp::DocumentRangeFormattingParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+
+        let src: EcoString = match self.io.read(&path) {
+            Ok(src) => src.into(),
+            Err(error) => return self.path_error_response(path, error),
+        };
+
+        let line_numbers = LineNumbers::new(&src);
+        let range = lsp_range_to_src_span(params.range, &line_numbers);
+        let edits = match crate::format::range_to_string(&src, &path, range) {
+            Ok(Some((location, new_text))) => vec![TextEdit {
+                range: src_span_to_lsp_range(location, &line_numbers),
+                new_text,
+            }],
+            Ok(None) => vec![],
+            Err(error) => return self.path_error_response(path, error),
+        };
+        let json = serde_json::to_value(edits).expect("to JSON value");
+
+        (json, Feedback::default())
+    }
+
     fn hover(&mut self, params: lsp::HoverParams) -> (Json, Feedback) {
         let path = super::path(&params.text_document_position_params.text_document.uri);
         self.respond_with_engine(path, |engine| engine.hover(params))
@@ -443,7 +469,7 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
         code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
         code_lens_provider: None,
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
-        document_range_formatting_provider: None,
+        document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
         document_on_type_formatting_provider: None,
         rename_provider: Some(lsp::OneOf::Right(RenameOptions {
             prepare_provider: Some(true),
@@ -23,7 +23,7 @@ use lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams, Ur
 
 use crate::{
     Result,
-    config::PackageConfig,
+    config::{LintsConfig, PackageConfig},
     io::{
         BeamCompiler, Command, CommandExecutor, FileSystemReader, FileSystemWriter, ReadDir,
         WrappedReader, memory::InMemoryFileSystem,
@@ -397,6 +397,7 @@ struct TestProject<'a> {
     hex_modules: Vec<(&'a str, &'a str)>,
     dev_hex_modules: Vec<(&'a str, &'a str)>,
     indirect_hex_modules: Vec<(&'a str, &'a str)>,
+    lints: LintsConfig,
 }
 
 impl<'a> TestProject<'a> {
@@ -409,6 +410,7 @@ impl<'a> TestProject<'a> {
             hex_modules: vec![],
             dev_hex_modules: vec![],
             indirect_hex_modules: vec![],
+            lints: LintsConfig::default(),
         }
     }
 
@@ -475,6 +477,11 @@ impl<'a> TestProject<'a> {
         self
     }
 
+    pub fn with_lints(mut self, lints: LintsConfig) -> Self {
+        self.lints = lints;
+        self
+    }
+
     pub fn build_engine(
         &self,
         io: &mut LanguageServerTestIO,
@@ -493,6 +500,7 @@ impl<'a> TestProject<'a> {
         });
 
         let mut engine = setup_engine(io);
+        engine.compiler.project_compiler.config.lints = self.lints;
 
         // Add an external dependency and all its modules
         add_path_dep(&mut engine, "dep");
@@ -70,6 +70,7 @@ const CONVERT_FROM_USE: &str = "Convert from `use`";
 const CONVERT_TO_USE: &str = "Convert to `use`";
 const EXTRACT_VARIABLE: &str = "Extract variable";
 const EXTRACT_CONSTANT: &str = "Extract constant";
+const EXTRACT_FUNCTION: &str = "Extract function";
 const EXPAND_FUNCTION_CAPTURE: &str = "Expand function capture";
 const GENERATE_DYNAMIC_DECODER: &str = "Generate dynamic decoder";
 const GENERATE_JSON_ENCODER: &str = "Generate JSON encoder";
@@ -82,6 +83,10 @@ const CONVERT_TO_PIPE: &str = "Convert to pipe";
 const INTERPOLATE_STRING: &str = "Interpolate string";
 const FILL_UNUSED_FIELDS: &str = "Fill unused fields";
 const REMOVE_ALL_ECHOS_FROM_THIS_MODULE: &str = "Remove all `echo`s from this module";
+const CONVERT_TO_LET: &str = "Convert to `let`";
+const SIMPLIFY_BOOLEAN_EXPRESSION: &str = "Simplify boolean expression";
+const IMPORT_ONLY_THE_TYPE: &str = "Import only the type";
+const REMOVE_UNNECESSARY_FUNCTION_WRAPPER: &str = "Remove unnecessary function wrapper";
 
 macro_rules! assert_code_action {
     ($title:expr, $code:literal, $range:expr $(,)?) => {
@@ -491,6 +496,55 @@ pub fn main() {
     );
 }
 
+#[test]
+fn import_only_the_type() {
+    let src = "
+import wibble.{type Wibble, Wibble}
+
+pub fn main() -> Wibble {
+  todo
+}
+";
+    assert_code_action!(
+        IMPORT_ONLY_THE_TYPE,
+        TestProject::for_source(src).add_hex_module("wibble", "pub type Wibble { Wibble }"),
+        find_position_of("Wibble}").select_until(find_position_of("}")),
+    );
+}
+
+#[test]
+fn import_only_the_type_when_constructor_comes_first() {
+    let src = "
+import wibble.{Wibble, type Wibble, wobble}
+
+pub fn main() -> Wibble {
+  wobble
+}
+";
+    assert_code_action!(
+        IMPORT_ONLY_THE_TYPE,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub type Wibble { Wibble }\npub const wobble = 1"),
+        find_position_of("Wibble,").to_selection(),
+    );
+}
+
+#[test]
+fn no_import_only_the_type_when_constructor_is_used() {
+    let src = "
+import wibble.{type Wibble, Wibble}
+
+pub fn main() -> Wibble {
+  Wibble
+}
+";
+    assert_no_code_actions!(
+        IMPORT_ONLY_THE_TYPE,
+        TestProject::for_source(src).add_hex_module("wibble", "pub type Wibble { Wibble }"),
+        find_position_of("Wibble}").to_selection(),
+    );
+}
+
 #[test]
 fn test_remove_redundant_tuple_in_case_subject_simple() {
     assert_code_action!(
@@ -981,6 +1035,24 @@ fn rename_invalid_function_type_parameter_name() {
     );
 }
 
+#[test]
+fn replace_int_literal_with_float() {
+    assert_code_action!(
+        "Replace with `5.0`",
+        "pub fn main() { let x: Float = 5 x }",
+        find_position_of("5").to_selection()
+    );
+}
+
+#[test]
+fn replace_float_literal_with_int() {
+    assert_code_action!(
+        "Replace with `1_000`",
+        "pub const x: Int = 1_000.0",
+        find_position_of("1_000").to_selection()
+    );
+}
+
 #[test]
 fn test_convert_assert_result_to_case() {
     assert_code_action!(
@@ -7104,3 +7176,369 @@ pub fn main() {
         find_position_of("c1,").to_selection()
     );
 }
+
+fn single_arm_cases_lint() -> LintsConfig {
+    LintsConfig {
+        single_arm_cases: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn convert_single_clause_case_to_let() {
+    assert_code_action!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  case x {
+    #(a, b) -> a + b
+  }
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn convert_single_clause_case_with_block_body_to_let() {
+    assert_code_action!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  let y = 1
+  case x {
+    #(a, b) -> {
+      let c = a + b
+      c * y
+    }
+  }
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("x {").to_selection()
+    );
+}
+
+#[test]
+fn convert_single_clause_case_used_as_a_value_to_let() {
+    assert_code_action!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  let y = case x {
+    #(a, b) -> {
+      let c = a + b
+      c * 2
+    }
+  }
+  y
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn convert_single_clause_case_in_a_function_argument_to_let() {
+    assert_code_action!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  wibble(case x {
+    value -> value
+  })
+}
+
+fn wibble(x) { x }
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn convert_single_clause_case_followed_by_other_statements_to_let() {
+    assert_code_action!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  let a = 1
+  case x {
+    #(a, b) -> echo a + b
+  }
+  a
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn no_convert_to_let_if_the_lint_is_disabled() {
+    assert_no_code_actions!(
+        CONVERT_TO_LET,
+        "
+pub fn main(x) {
+  case x {
+    value -> value
+  }
+}
+",
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn no_convert_to_let_for_case_with_multiple_clauses() {
+    assert_no_code_actions!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  case x {
+    [] -> 0
+    _ -> 1
+  }
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("case").to_selection()
+    );
+}
+
+#[test]
+fn no_convert_to_let_outside_of_the_case_head() {
+    assert_no_code_actions!(
+        CONVERT_TO_LET,
+        TestProject::for_source(
+            "
+pub fn main(x) {
+  case x {
+    value -> value
+  }
+}
+",
+        )
+        .with_lints(single_arm_cases_lint()),
+        find_position_of("-> value").to_selection()
+    );
+}
+
+#[test]
+fn extract_function_capturing_a_local_variable() {
+    assert_code_action!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main() {
+  let x = 1
+  let y = x * 2 + 1
+  y
+}"#,
+        find_position_of("x *").select_until(
+            find_position_of("1\n")
+                .nth_occurrence(2)
+                .with_char_offset(1)
+        ),
+    );
+}
+
+#[test]
+fn extract_function_without_free_variables() {
+    assert_code_action!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main() {
+  wibble("Hello")
+}
+
+fn wibble(string) { string }"#,
+        find_position_of("wibble").select_until(find_position_of("\")").with_char_offset(2)),
+    );
+}
+
+#[test]
+fn extract_function_does_not_turn_bound_variables_into_arguments() {
+    assert_code_action!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main(list) {
+  let total = {
+    let first = 1
+    first + list
+  }
+  total
+}"#,
+        find_position_of("{\n    let")
+            .select_until(find_position_of("}\n  total").with_char_offset(1)),
+    );
+}
+
+#[test]
+fn extract_function_with_a_whole_pipeline() {
+    assert_code_action!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main(x) {
+  let result =
+    x
+    |> add(1)
+    |> add(x)
+  result
+}
+
+fn add(a, b) { a + b }"#,
+        find_position_of("x\n").select_until(find_position_of("add(x)").with_char_offset(6)),
+    );
+}
+
+#[test]
+fn extract_function_does_not_extract_a_single_pipeline_step() {
+    assert_no_code_actions!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main(x) {
+  x
+  |> add(1)
+  |> add(2)
+}
+
+fn add(a, b) { a + b }"#,
+        find_position_of("add(1)").select_until(find_position_of("add(1)").with_char_offset(6)),
+    );
+}
+
+#[test]
+fn extract_function_picks_a_name_that_is_not_taken() {
+    assert_code_action!(
+        EXTRACT_FUNCTION,
+        r#"pub fn main(x) {
+  x + 1
+}
+
+fn function() { Nil }"#,
+        find_position_of("x +").select_until(find_position_of("1\n").with_char_offset(1)),
+    );
+}
+
+#[test]
+fn simplify_comparison_with_true() {
+    assert_code_action!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  x == True
+}"#,
+        find_position_of("==").to_selection(),
+    );
+}
+
+#[test]
+fn simplify_comparison_with_false() {
+    assert_code_action!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  False != is_even(x)
+}
+
+fn is_even(x) { x % 2 == 0 }"#,
+        find_position_of("False").to_selection(),
+    );
+}
+
+#[test]
+fn simplify_negated_comparison_wraps_the_operand_in_a_block() {
+    assert_code_action!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  x > 1 == False
+}"#,
+        find_position_of("==").to_selection(),
+    );
+}
+
+#[test]
+fn simplify_double_negation() {
+    assert_code_action!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  !{ !x }
+}"#,
+        find_position_of("!").to_selection(),
+    );
+}
+
+#[test]
+fn simplify_double_negation_without_block() {
+    assert_code_action!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  !!!x
+}"#,
+        find_position_of("!").to_selection(),
+    );
+}
+
+#[test]
+fn no_simplify_boolean_expression_when_not_hovering_it() {
+    assert_no_code_actions!(
+        SIMPLIFY_BOOLEAN_EXPRESSION,
+        r#"pub fn main(x) {
+  let y = x == True
+  y
+}"#,
+        find_position_of("y\n}").to_selection(),
+    );
+}
+
+#[test]
+fn remove_unnecessary_function_wrapper() {
+    assert_code_action!(
+        REMOVE_UNNECESSARY_FUNCTION_WRAPPER,
+        r#"pub fn main() {
+  apply(fn(x) { double(x) }, 1)
+}
+
+fn apply(f, x) { f(x) }
+fn double(x) { x * 2 }"#,
+        find_position_of("fn(x)").to_selection(),
+    );
+}
+
+#[test]
+fn remove_unnecessary_function_wrapper_around_qualified_function() {
+    let src = r#"import list
+
+pub fn main(items) {
+  list.map(items, fn(a) { list.length(a) })
+}"#;
+
+    assert_code_action!(
+        REMOVE_UNNECESSARY_FUNCTION_WRAPPER,
+        TestProject::for_source(src).add_module(
+            "list",
+            "pub fn map(xs, f) { todo }\npub fn length(xs) { 0 }"
+        ),
+        find_position_of("length").to_selection(),
+    );
+}
+
+#[test]
+fn remove_unnecessary_function_wrapper_is_not_offered_for_reordered_arguments() {
+    assert_no_code_actions!(
+        REMOVE_UNNECESSARY_FUNCTION_WRAPPER,
+        r#"pub fn main() {
+  fn(a, b) { wibble(b, a) }
+}
+
+fn wibble(a, b) { a - b }"#,
+        find_position_of("fn(a, b)").to_selection(),
+    );
+}
@@ -117,7 +117,6 @@ fn format_completion_results(completions: Vec<CompletionItem>) -> EcoString {
         assert!(preselect.is_none());
         assert!(filter_text.is_none());
         assert!(insert_text.is_none());
-        assert!(insert_text_format.is_none());
         assert!(insert_text_mode.is_none());
         assert!(command.is_none());
         assert!(commit_characters.is_none());
@@ -138,6 +137,10 @@ fn format_completion_results(completions: Vec<CompletionItem>) -> EcoString {
             write!(buffer, "\n  sort:   {sort_text}").unwrap();
         }
 
+        if let Some(insert_text_format) = insert_text_format {
+            write!(buffer, "\n  format: {insert_text_format:?}").unwrap();
+        }
+
         if let Some(label_details) = label_details {
             assert!(label_details.detail.is_none());
             if let Some(desc) = label_details.description {
@@ -1905,3 +1908,130 @@ pub fn main(something: Bool) {
         Position::new(2, 9)
     );
 }
+
+#[test]
+fn constructor_snippets_for_record_argument() {
+    let code = "
+pub type Wibble {
+  Wibble(wibble: Int, wobble: String)
+  Wobble(Int)
+  Wubble
+}
+
+fn wibble(a: Int, b: Wibble) { todo }
+
+pub fn main() {
+  wibble(1, )
+}
+";
+
+    assert_completion!(TestProject::for_source(code), Position::new(10, 12));
+}
+
+#[test]
+fn constructor_snippets_for_result_argument() {
+    let code = "
+fn wibble(result: Result(Int, String)) { todo }
+
+pub fn main() {
+  wibble()
+}
+";
+
+    assert_completion!(TestProject::for_source(code), Position::new(4, 9));
+}
+
+#[test]
+fn apply_constructor_snippet_for_record_argument() {
+    let code = "
+pub type Wibble {
+  Wibble(wibble: Int, wobble: String)
+}
+
+fn wibble(a: Wibble) { todo }
+
+pub fn main() {
+  wibble()
+}
+";
+
+    assert_apply_completion!(
+        TestProject::for_source(code),
+        "Wibble(wibble: _, wobble: _)",
+        Position::new(8, 9)
+    );
+}
+
+#[test]
+fn apply_constructor_snippet_for_name_being_written() {
+    let code = "
+fn wibble(result: Result(Int, String)) { todo }
+
+pub fn main() {
+  wibble(Er)
+}
+";
+
+    assert_apply_completion!(
+        TestProject::for_source(code),
+        "Error(_)",
+        Position::new(4, 11)
+    );
+}
+
+#[test]
+fn constructor_snippets_for_imported_record_argument() {
+    let code = "
+import dep
+
+pub fn main() {
+  dep.wibble()
+}
+";
+    let dep = "
+pub type Wibble {
+  Wibble(wibble: Int)
+}
+
+pub fn wibble(a: Wibble) { todo }
+";
+
+    assert_apply_completion!(
+        TestProject::for_source(code).add_dep_module("dep", dep),
+        "dep.Wibble(wibble: _)",
+        Position::new(4, 13)
+    );
+}
+
+#[test]
+fn no_constructor_snippets_for_unimported_module() {
+    let code = "
+import dep
+
+pub fn main() {
+  dep.wibble()
+}
+";
+    let dep = "
+import other
+
+pub fn wibble(a: other.Wibble) { todo }
+";
+    let other = "
+pub type Wibble {
+  Wibble(wibble: Int)
+}
+";
+
+    let completions = completion(
+        TestProject::for_source(code)
+            .add_dep_module("dep", dep)
+            .add_dep_module("other", other),
+        Position::new(4, 13),
+    );
+    assert!(
+        completions
+            .iter()
+            .all(|completion| completion.insert_text_format.is_none())
+    );
+}
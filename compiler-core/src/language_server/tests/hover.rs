@@ -389,6 +389,85 @@ fn append(x, y) {
     );
 }
 
+#[test]
+fn hover_function_definition_with_since() {
+    assert_hover!(
+        r#"
+/// Exciting documentation
+@since("1.2.0")
+pub fn append(x, y) {
+  x <> y
+}
+"#,
+        find_position_of("append")
+    );
+}
+
+#[test]
+fn hover_imported_function_with_since() {
+    let code = "
+import example_module
+fn main() {
+  example_module.my_fn
+}
+";
+
+    assert_hover!(
+        TestProject::for_source(code).add_module(
+            "example_module",
+            "/// Some docs\n@since(\"0.3.0\")\npub fn my_fn() { Nil }"
+        ),
+        find_position_of("my_fn").under_char('_'),
+    );
+}
+
+#[test]
+fn hover_module_constant_with_since() {
+    assert_hover!(
+        r#"
+@since("1.2.0")
+pub const wibble = 1
+
+pub fn main() {
+  wibble
+}
+"#,
+        find_position_of("wibble").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn hover_function_definition_with_doc_attribute() {
+    assert_hover!(
+        r#"
+/// Exciting documentation
+@doc("Even more " <> "documentation")
+pub fn append(x, y) {
+  x <> y
+}
+"#,
+        find_position_of("append")
+    );
+}
+
+#[test]
+fn hover_imported_function_with_doc_attribute() {
+    let code = "
+import example_module
+fn main() {
+  example_module.my_fn
+}
+";
+
+    assert_hover!(
+        TestProject::for_source(code).add_module(
+            "example_module",
+            "@doc(\"Some docs\")\npub fn my_fn() { Nil }"
+        ),
+        find_position_of("my_fn").under_char('_'),
+    );
+}
+
 #[test]
 fn hover_function_argument() {
     assert_hover!(
@@ -1572,6 +1651,36 @@ import wibble
     );
 }
 
+#[test]
+fn hover_raw_identifier_variable() {
+    assert_hover!(
+        "
+pub fn main() {
+  let `type` = 10
+  `type` + 1
+}
+",
+        find_position_of("type").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn hover_raw_identifier_function() {
+    assert_hover!(
+        "
+/// Exciting documentation
+pub fn `type`(x: Int) -> Int {
+  x
+}
+
+pub fn main() {
+  `type`(1)
+}
+",
+        find_position_of("type").nth_occurrence(2)
+    );
+}
+
 #[test]
 fn no_hexdocs_link_when_hovering_over_local_module() {
     let src = "
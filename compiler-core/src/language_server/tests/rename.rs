@@ -361,6 +361,52 @@ pub fn starts_with(bits: BitArray, prefix: BitArray) -> Bool {
     );
 }
 
+#[test]
+fn rename_raw_identifier_local_variable() {
+    assert_rename!(
+        "
+pub fn main() {
+  let `type` = 10
+  `type` + 1
+}
+",
+        "kind",
+        find_position_of("type").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn rename_local_variable_to_keyword() {
+    assert_rename!(
+        "
+pub fn main() {
+  let wibble = 10
+  wibble + 1
+}
+",
+        "type",
+        find_position_of("wibble").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn rename_local_variable_label_shorthand_to_keyword() {
+    assert_rename!(
+        "
+type Wibble {
+  Wibble(wibble: Int)
+}
+
+pub fn main() {
+  let Wibble(wibble:) = Wibble(10)
+  wibble + 1
+}
+",
+        "type",
+        find_position_of("wibble +")
+    );
+}
+
 #[test]
 fn no_rename_keyword() {
     assert_no_rename!(
@@ -463,6 +509,53 @@ pub fn main() {
     );
 }
 
+#[test]
+fn rename_raw_identifier_function_from_qualified_reference() {
+    assert_rename!(
+        (
+            "mod",
+            "
+pub fn `type`() {
+  `type`()
+}
+"
+        ),
+        "
+import mod
+
+pub fn main() {
+  mod.`type`()
+}
+",
+        "kind",
+        find_position_of("type")
+    );
+}
+
+#[test]
+fn rename_function_to_keyword() {
+    assert_rename!(
+        (
+            "mod",
+            "
+pub fn wibble() {
+  wibble()
+}
+"
+        ),
+        "
+import mod.{wibble}
+
+pub fn main() {
+  mod.wibble()
+  wibble()
+}
+",
+        "type",
+        find_position_of("wibble").nth_occurrence(2)
+    );
+}
+
 #[test]
 fn rename_function_from_unqualified_reference() {
     assert_rename!(
@@ -77,14 +77,18 @@ pub mod io;
 pub mod javascript;
 pub mod language_server;
 pub mod line_numbers;
+pub mod lint;
 pub mod manifest;
 pub mod metadata;
+pub mod module_graph;
+pub mod optimise;
 pub mod package_interface;
 pub mod parse;
 pub mod paths;
 pub mod pretty;
 pub mod requirement;
 pub mod strings;
+pub mod todos;
 pub mod type_;
 pub mod uid;
 pub mod version;
@@ -95,7 +99,7 @@ mod call_graph;
 mod dep_tree;
 mod exhaustiveness;
 pub(crate) mod graph;
-mod reference;
+pub mod reference;
 
 pub use error::{Error, Result};
 pub use warning::Warning;
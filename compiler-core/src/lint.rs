@@ -0,0 +1,83 @@
+//! Custom lint rules, which a consumer of the compiler can register to run
+//! their own checks over the modules of a package once they have been type
+//! checked.
+//!
+//! A rule looks at a typed module, usually with the [`Visit`] trait, and
+//! returns the problems it found. These are emitted as warnings along with
+//! all the others the compiler reports.
+//!
+//! [`Visit`]: crate::ast::visit::Visit
+
+#[cfg(test)]
+mod tests;
+
+use std::rc::Rc;
+
+use debug_ignore::DebugIgnore;
+use ecow::EcoString;
+
+use crate::{
+    ast::{SrcSpan, TypedModule},
+    build::Module,
+    warning::{Warning, WarningEmitter},
+};
+
+/// A custom check run over each module of a package after it has been type
+/// checked.
+///
+pub trait LintRule {
+    /// The name of the rule, which is shown with each of the problems it
+    /// reports.
+    fn name(&self) -> EcoString;
+
+    /// Returns the problems found in the given module.
+    fn check(&self, module: &TypedModule) -> Vec<Lint>;
+}
+
+/// A problem found in a module by a lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// The code the problem is about.
+    pub location: SrcSpan,
+    /// What is wrong with the code, shown where the code is.
+    pub message: EcoString,
+    /// How the problem could be fixed, if the rule has a suggestion.
+    pub hint: Option<EcoString>,
+}
+
+/// The lint rules registered to run when compiling a package.
+///
+#[derive(Debug, Clone, Default)]
+pub struct LintRules {
+    rules: DebugIgnore<Vec<Rc<dyn LintRule>>>,
+}
+
+impl LintRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a rule, which is run after the ones registered before it.
+    pub fn register(&mut self, rule: impl LintRule + 'static) {
+        self.rules.push(Rc::new(rule));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Runs all the rules over a module, emitting a warning for each problem
+    /// they find.
+    pub fn check(&self, module: &Module, warnings: &WarningEmitter) {
+        for rule in self.rules.iter() {
+            for lint in rule.check(&module.ast) {
+                warnings.emit(Warning::Lint {
+                    path: module.input_path.clone(),
+                    src: module.code.clone(),
+                    rule: rule.name(),
+                    lint,
+                });
+            }
+        }
+    }
+}
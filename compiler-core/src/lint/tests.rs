@@ -0,0 +1,213 @@
+use std::{collections::HashSet, rc::Rc, sync::Arc};
+
+use camino::Utf8PathBuf;
+use ecow::{EcoString, eco_format};
+use itertools::Itertools;
+
+use super::{Lint, LintRule, LintRules};
+use crate::{
+    ast::{
+        SrcSpan, TypedModule,
+        visit::{self, Visit},
+    },
+    build::{Mode, NullTelemetry, PackageCompiler, StaleTracker, TargetCodegenConfiguration},
+    config::PackageConfig,
+    io::{FileSystemWriter, memory::InMemoryFileSystem},
+    type_::{ModuleValueConstructor, Type, ValueConstructor, ValueConstructorVariant},
+    uid::UniqueIdGenerator,
+    warning::{VectorWarningEmitterIO, Warning, WarningEmitter},
+};
+
+/// A sample rule, reporting each use of a function that must not be used.
+struct BannedFunction {
+    module: EcoString,
+    function: EcoString,
+}
+
+impl LintRule for BannedFunction {
+    fn name(&self) -> EcoString {
+        "banned_function".into()
+    }
+
+    fn check(&self, module: &TypedModule) -> Vec<Lint> {
+        let mut finder = BannedFunctionFinder {
+            rule: self,
+            lints: vec![],
+        };
+        for definition in &module.definitions {
+            finder.visit_typed_definition(definition);
+        }
+        finder.lints
+    }
+}
+
+struct BannedFunctionFinder<'a> {
+    rule: &'a BannedFunction,
+    lints: Vec<Lint>,
+}
+
+impl BannedFunctionFinder<'_> {
+    fn check(&mut self, location: SrcSpan, module: &EcoString, name: &EcoString) {
+        if module != &self.rule.module || name != &self.rule.function {
+            return;
+        }
+        self.lints.push(Lint {
+            location,
+            message: eco_format!("`{module}.{name}` must not be used"),
+            hint: Some("Ask the team which function to use instead.".into()),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for BannedFunctionFinder<'_> {
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        name: &'ast EcoString,
+    ) {
+        if let ValueConstructorVariant::ModuleFn { module, name, .. } = &constructor.variant {
+            self.check(*location, module, name);
+        }
+        visit::visit_typed_expr_var(self, location, constructor, name);
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        location: &'ast SrcSpan,
+        field_start: &'ast u32,
+        type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        module_alias: &'ast EcoString,
+        constructor: &'ast ModuleValueConstructor,
+    ) {
+        if let ModuleValueConstructor::Fn { module, name, .. } = constructor {
+            self.check(*location, module, name);
+        }
+        visit::visit_typed_expr_module_select(
+            self,
+            location,
+            field_start,
+            type_,
+            label,
+            module_name,
+            module_alias,
+            constructor,
+        );
+    }
+}
+
+fn compile_with_rules(modules: &[(&str, &str)], rules: LintRules) -> Vec<Warning> {
+    let fs = InMemoryFileSystem::new();
+    for (name, src) in modules {
+        fs.write(&Utf8PathBuf::from(format!("/src/{name}.gleam")), src)
+            .unwrap();
+    }
+
+    let mut config = PackageConfig::default();
+    config.name = "my_package".into();
+    let target = TargetCodegenConfiguration::Erlang { app_file: None };
+    let root = Utf8PathBuf::from("/");
+    let build = root.join("build");
+    let lib = root.join("lib");
+    let ids = UniqueIdGenerator::new();
+    let mut compiler =
+        PackageCompiler::new(&config, Mode::Dev, &root, &build, &lib, &target, ids, fs);
+    compiler.write_metadata = false;
+    compiler.lint_rules = rules;
+
+    let emitted = VectorWarningEmitterIO::new();
+    let _ = compiler
+        .compile(
+            &WarningEmitter::new(Rc::new(emitted.clone())),
+            &mut im::HashMap::new(),
+            &mut im::HashMap::new(),
+            &mut StaleTracker::default(),
+            &mut HashSet::new(),
+            &NullTelemetry,
+        )
+        .unwrap();
+    emitted.take()
+}
+
+fn banned_logging_debug() -> LintRules {
+    let mut rules = LintRules::new();
+    rules.register(BannedFunction {
+        module: "logging".into(),
+        function: "debug".into(),
+    });
+    rules
+}
+
+const LOGGING: &str = "pub fn debug(x) { x }
+pub fn println(x) { x }
+";
+
+#[test]
+fn banned_function_is_reported() {
+    let main = "import logging.{debug}
+
+pub fn main() {
+  logging.println(\"Hello\")
+  logging.debug(1)
+  debug(2)
+}
+";
+    let warnings = compile_with_rules(
+        &[("logging", LOGGING), ("main", main)],
+        banned_logging_debug(),
+    );
+    insta::assert_snapshot!(warnings.iter().map(Warning::to_pretty_string).join("\n"));
+}
+
+#[test]
+fn rules_are_only_run_when_registered() {
+    let main = "import logging
+
+pub fn main() {
+  logging.debug(1)
+}
+";
+    let warnings = compile_with_rules(&[("logging", LOGGING), ("main", main)], LintRules::new());
+    assert_eq!(warnings, vec![]);
+}
+
+#[test]
+fn code_without_problems_has_no_lints() {
+    let main = "import logging
+
+pub fn main() {
+  logging.println(1)
+}
+";
+    let warnings = compile_with_rules(
+        &[("logging", LOGGING), ("main", main)],
+        banned_logging_debug(),
+    );
+    assert_eq!(warnings, vec![]);
+}
+
+#[test]
+fn lints_are_warnings_like_any_other() {
+    let main = "import logging
+
+pub fn main() {
+  let x = 1
+  logging.debug(1)
+}
+";
+    let warnings = compile_with_rules(
+        &[("logging", LOGGING), ("main", main)],
+        banned_logging_debug(),
+    );
+    let kinds = warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::Type { .. } => "type",
+            Warning::Lint { rule, .. } if rule == "banned_function" => "lint",
+            _ => panic!("unexpected warning {warning:?}"),
+        })
+        .collect_vec();
+    assert_eq!(kinds, vec!["type", "lint"]);
+}
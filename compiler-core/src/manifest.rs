@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::Result;
 use crate::io::{make_relative, ordered_map};
@@ -17,6 +17,41 @@ pub struct Manifest {
 }
 
 impl Manifest {
+    /// The packages that are only needed by the dev-dependencies of the root
+    /// package, and not by any of the given production dependencies or their
+    /// own dependencies. These are not needed to run the package in
+    /// production.
+    ///
+    /// A package that is both a dev-dependency and required by a production
+    /// dependency is needed in production and so it is not returned.
+    ///
+    pub fn dev_only_packages<'a>(
+        &'a self,
+        production_dependencies: impl IntoIterator<Item = &'a EcoString>,
+    ) -> Vec<&'a ManifestPackage> {
+        let packages: HashMap<_, _> = self
+            .packages
+            .iter()
+            .map(|package| (&package.name, package))
+            .collect();
+
+        let mut needed = HashSet::new();
+        let mut to_visit: Vec<&EcoString> = production_dependencies.into_iter().collect();
+        while let Some(name) = to_visit.pop() {
+            if !needed.insert(name) {
+                continue;
+            }
+            if let Some(package) = packages.get(name) {
+                to_visit.extend(package.requirements.iter());
+            }
+        }
+
+        self.packages
+            .iter()
+            .filter(|package| !needed.contains(&package.name))
+            .collect()
+    }
+
     // Rather than using the toml library to do serialization we implement it
     // manually so that we can control the formatting.
     // We want to keep entries on a single line each so that they are more
@@ -447,6 +482,55 @@ zzz = { version = "> 0.0.0" }
         );
     }
 
+    #[test]
+    fn dev_only_packages() {
+        let package = |name: &str, requirements: &[&str]| ManifestPackage {
+            name: name.into(),
+            requirements: requirements.iter().map(|name| (*name).into()).collect(),
+            ..Default::default()
+        };
+        let manifest = Manifest {
+            requirements: HashMap::new(),
+            packages: vec![
+                package("gleam_stdlib", &[]),
+                package("wibble", &["gleam_stdlib", "wobble"]),
+                package("wobble", &[]),
+                package("gleeunit", &["gleam_stdlib", "test_helpers"]),
+                package("test_helpers", &[]),
+            ],
+        };
+
+        let production_dependencies: Vec<EcoString> = vec!["gleam_stdlib".into(), "wibble".into()];
+        let dev_only = manifest
+            .dev_only_packages(&production_dependencies)
+            .into_iter()
+            .map(|package| package.name.as_str())
+            .collect_vec();
+        assert_eq!(dev_only, vec!["gleeunit", "test_helpers"]);
+    }
+
+    #[test]
+    fn dev_dependency_also_required_by_a_dependency_is_not_dev_only() {
+        let package = |name: &str, requirements: &[&str]| ManifestPackage {
+            name: name.into(),
+            requirements: requirements.iter().map(|name| (*name).into()).collect(),
+            ..Default::default()
+        };
+        let manifest = Manifest {
+            requirements: HashMap::new(),
+            packages: vec![package("wibble", &["wobble"]), package("wobble", &[])],
+        };
+
+        // `wobble` is a dev-dependency of the root package, but it's also
+        // needed by `wibble` at runtime.
+        let production_dependencies: Vec<EcoString> = vec!["wibble".into()];
+        assert!(
+            manifest
+                .dev_only_packages(&production_dependencies)
+                .is_empty()
+        );
+    }
+
     impl Default for ManifestPackage {
         fn default() -> Self {
             Self {
@@ -1,10 +1,15 @@
 //! Seriaisation and deserialisation of Gleam compiler metadata into binary files
 //! using the Cap'n Proto schema.
 
+mod interface_file;
 mod module_decoder;
 mod module_encoder;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{module_decoder::ModuleDecoder, module_encoder::ModuleEncoder};
+pub use self::{
+    interface_file::{INTERFACE_FILE_FORMAT_VERSION, decode_interface_file, encode_interface_file},
+    module_decoder::ModuleDecoder,
+    module_encoder::ModuleEncoder,
+};
@@ -0,0 +1,77 @@
+use std::io::BufReader;
+
+use camino::Utf8Path;
+
+use crate::{Error, Result, type_::ModuleInterface, uid::UniqueIdGenerator};
+
+use super::{ModuleDecoder, ModuleEncoder};
+
+/// The bytes every module interface file starts with, so that any other file
+/// can be told apart from one.
+const MAGIC: &[u8; 8] = b"GLEAMMIF";
+
+/// The version of the module interface file format.
+///
+/// The interface itself is encoded with the same Cap'n Proto schema used for
+/// the build cache, which can have fields added to it while still being
+/// readable by older compilers, so this only has to change if a file written
+/// with the new format could not be read correctly by a compiler expecting
+/// the old one.
+///
+pub const INTERFACE_FILE_FORMAT_VERSION: u32 = 1;
+
+/// The length of the header written before the interface.
+const HEADER_LENGTH: usize = MAGIC.len() + 4;
+
+/// Encodes a module's interface into a file that can be shared with another
+/// project, so that it can type check code importing the module without
+/// having its source.
+///
+pub fn encode_interface_file(interface: &ModuleInterface) -> Result<Vec<u8>> {
+    let encoded = ModuleEncoder::new(interface).encode()?;
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH + encoded.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&INTERFACE_FILE_FORMAT_VERSION.to_le_bytes());
+    bytes.extend(encoded);
+    Ok(bytes)
+}
+
+/// Decodes a module interface file written by `encode_interface_file`, for
+/// the module to be imported by the modules being type checked.
+///
+pub fn decode_interface_file(
+    path: &Utf8Path,
+    bytes: &[u8],
+    ids: UniqueIdGenerator,
+) -> Result<ModuleInterface> {
+    let invalid = |error: String| Error::InvalidModuleInterfaceFile {
+        path: path.to_path_buf(),
+        error,
+    };
+
+    let (magic, rest) = bytes.split_at(MAGIC.len().min(bytes.len()));
+    if magic != MAGIC {
+        return Err(invalid("It is not a module interface file".into()));
+    }
+
+    let (version, encoded) = rest.split_at(4.min(rest.len()));
+    let version = match <[u8; 4]>::try_from(version) {
+        Ok(version) => u32::from_le_bytes(version),
+        Err(_) => return Err(invalid("The file is truncated".into())),
+    };
+    if version > INTERFACE_FILE_FORMAT_VERSION {
+        return Err(invalid(format!(
+            "It uses version {version} of the format, but this version of \
+Gleam only supports up to version {INTERFACE_FILE_FORMAT_VERSION}"
+        )));
+    }
+
+    ModuleDecoder::new(ids)
+        .read(BufReader::new(encoded))
+        .map_err(|error| match error {
+            Error::MetadataDecodeError { error } => {
+                invalid(error.unwrap_or_else(|| "The interface could not be decoded".into()))
+            }
+            error => error,
+        })
+}
@@ -15,7 +15,7 @@ use crate::{
     reference::{Reference, ReferenceKind, ReferenceMap},
     schema_capnp::{self as schema, *},
     type_::{
-        self, AccessorsMap, Deprecation, FieldMap, ModuleInterface, Opaque, RecordAccessor,
+        self, AccessorsMap, Deprecation, FieldMap, ModuleInterface, Opaque, Purity, RecordAccessor,
         References, Type, TypeAliasConstructor, TypeConstructor, TypeValueConstructor,
         TypeValueConstructorField, TypeVariantConstructors, ValueConstructor,
         ValueConstructorVariant, expression::Implementations,
@@ -88,6 +88,9 @@ impl ModuleDecoder {
             type_aliases: read_hashmap!(reader.get_type_aliases()?, self, type_alias_constructor),
             documentation: self.string_list(reader.get_documentation()?)?,
             contains_echo: reader.get_contains_echo(),
+            benchmarks: self.string_list(reader.get_benchmarks()?)?,
+            disabled_values: read_hashmap!(reader.get_disabled_values()?, self, feature),
+            disabled_types: read_hashmap!(reader.get_disabled_types()?, self, feature),
             references: self.references(reader.get_references()?)?,
         })
     }
@@ -115,6 +118,8 @@ impl ModuleDecoder {
             imported_modules: self.string_set(reader.get_imported_modules()?)?,
             value_references: self.reference_map(reader.get_value_references()?)?,
             type_references: self.reference_map(reader.get_type_references()?)?,
+            discarded_value_references: self
+                .reference_map(reader.get_discarded_value_references()?)?,
         })
     }
 
@@ -574,6 +579,7 @@ impl ModuleDecoder {
     ) -> Result<ValueConstructorVariant> {
         Ok(ValueConstructorVariant::ModuleConstant {
             documentation: self.optional_string(self.str(reader.get_documentation()?)?),
+            since: self.optional_string(self.str(reader.get_since()?)?),
             location: self.src_span(&reader.get_location()?)?,
             literal: self.constant(&reader.get_literal()?)?,
             module: self.string(reader.get_module()?)?,
@@ -608,9 +614,15 @@ impl ModuleDecoder {
             field_map: self.field_map(&reader.get_field_map()?)?,
             location: self.src_span(&reader.get_location()?)?,
             documentation: self.optional_string(self.str(reader.get_documentation()?)?),
+            since: self.optional_string(self.str(reader.get_since()?)?),
             implementations: self.implementations(reader.get_implementations()?),
             external_erlang: self.optional_external(reader.get_external_erlang()?)?,
             external_javascript: self.optional_external(reader.get_external_javascript()?)?,
+            purity: if reader.get_is_pure() {
+                Purity::Pure
+            } else {
+                Purity::Unknown
+            },
         })
     }
 
@@ -654,6 +666,10 @@ impl ModuleDecoder {
         })
     }
 
+    fn feature(&self, reader: &text::Reader<'_>) -> Result<EcoString> {
+        self.string(*reader)
+    }
+
     fn u32(&self, i: &boxed_u_int32::Reader<'_>) -> Result<u32> {
         Ok(i.get_value())
     }
@@ -54,6 +54,8 @@ impl<'a> ModuleEncoder<'a> {
         self.set_module_documentation(&mut module);
         self.set_module_type_aliases(&mut module);
         self.set_module_references(&mut module);
+        self.set_module_benchmarks(&mut module);
+        self.set_module_disabled_definitions(&mut module);
 
         capnp::serialize_packed::write_message(&mut buffer, &message).expect("capnp encode");
         Ok(buffer)
@@ -78,6 +80,35 @@ impl<'a> ModuleEncoder<'a> {
         }
     }
 
+    fn set_module_benchmarks(&mut self, module: &mut module::Builder<'_>) {
+        let mut benchmarks = module
+            .reborrow()
+            .init_benchmarks(self.data.benchmarks.len() as u32);
+        for (i, benchmark) in self.data.benchmarks.iter().enumerate() {
+            benchmarks.set(i as u32, benchmark.as_str());
+        }
+    }
+
+    fn set_module_disabled_definitions(&mut self, module: &mut module::Builder<'_>) {
+        let mut values = module
+            .reborrow()
+            .init_disabled_values(self.data.disabled_values.len() as u32);
+        for (i, (name, feature)) in self.data.disabled_values.iter().enumerate() {
+            let mut property = values.reborrow().get(i as u32);
+            property.set_key(name);
+            property.set_value(feature.as_str()).expect("capnp encode");
+        }
+
+        let mut types = module
+            .reborrow()
+            .init_disabled_types(self.data.disabled_types.len() as u32);
+        for (i, (name, feature)) in self.data.disabled_types.iter().enumerate() {
+            let mut property = types.reborrow().get(i as u32);
+            property.set_key(name);
+            property.set_value(feature.as_str()).expect("capnp encode");
+        }
+    }
+
     fn set_module_accessors(&mut self, module: &mut module::Builder<'_>) {
         let mut builder = module
             .reborrow()
@@ -217,6 +248,13 @@ impl<'a> ModuleEncoder<'a> {
             .reborrow()
             .init_type_references(references.type_references.len() as u32);
         self.build_reference_map(type_references, &references.type_references);
+        let discarded_value_references = builder
+            .reborrow()
+            .init_discarded_value_references(references.discarded_value_references.len() as u32);
+        self.build_reference_map(
+            discarded_value_references,
+            &references.discarded_value_references,
+        );
     }
 
     fn build_reference_map(
@@ -387,11 +425,13 @@ impl<'a> ModuleEncoder<'a> {
                 location,
                 module,
                 documentation: doc,
+                since,
                 implementations,
                 name,
             } => {
                 let mut builder = builder.init_module_constant();
                 builder.set_documentation(doc.as_ref().map(EcoString::as_str).unwrap_or_default());
+                builder.set_since(since.as_ref().map(EcoString::as_str).unwrap_or_default());
                 self.build_src_span(builder.reborrow().init_location(), *location);
                 self.build_constant(builder.reborrow().init_literal(), literal);
                 builder.reborrow().set_module(module);
@@ -427,15 +467,19 @@ impl<'a> ModuleEncoder<'a> {
                 name,
                 location,
                 documentation: doc,
+                since,
                 implementations,
                 external_erlang,
                 external_javascript,
+                purity,
             } => {
                 let mut builder = builder.init_module_fn();
                 builder.set_name(name);
+                builder.set_is_pure(purity.is_pure());
                 builder.set_module(module);
                 builder.set_arity(*arity as u16);
                 builder.set_documentation(doc.as_ref().map(EcoString::as_str).unwrap_or_default());
+                builder.set_since(since.as_ref().map(EcoString::as_str).unwrap_or_default());
                 self.build_external(builder.reborrow().init_external_erlang(), external_erlang);
                 self.build_external(
                     builder.reborrow().init_external_javascript(),
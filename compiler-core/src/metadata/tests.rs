@@ -4,19 +4,22 @@ use type_::{AccessorsMap, FieldMap, RecordAccessor};
 
 use super::*;
 use crate::{
+    analyse::{ModuleAnalyzerConstructor, TargetSupport},
     ast::{
         BitArrayOption, BitArraySegment, CallArg, Constant, Publicity, SrcSpan, TypedConstant,
         TypedConstantBitArraySegmentOption,
     },
-    build::Origin,
+    build::{Origin, Outcome, Target},
+    config::PackageConfig,
     line_numbers::LineNumbers,
     reference::{Reference, ReferenceKind},
     type_::{
-        self, Deprecation, ModuleInterface, Opaque, References, Type, TypeAliasConstructor,
+        self, Deprecation, ModuleInterface, Opaque, Purity, References, Type, TypeAliasConstructor,
         TypeConstructor, TypeValueConstructor, TypeValueConstructorField, TypeVariantConstructors,
         ValueConstructor, ValueConstructorVariant, expression::Implementations, prelude,
     },
     uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, WarningEmitter},
 };
 use std::{collections::HashMap, io::BufReader, sync::Arc};
 
@@ -48,6 +51,7 @@ fn constant_module(constant: TypedConstant) -> ModuleInterface {
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleConstant {
                     documentation: Some("Some documentation".into()),
+                    since: None,
                     literal: constant,
                     location: SrcSpan::default(),
                     module: "one/two".into(),
@@ -69,6 +73,9 @@ fn constant_module(constant: TypedConstant) -> ModuleInterface {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     }
@@ -108,6 +115,67 @@ fn empty_module() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
+
+        references: References::default(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn module_with_benchmarks() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: true,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "one/two".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        type_aliases: HashMap::new(),
+        documentation: Vec::new(),
+        contains_echo: false,
+        benchmarks: vec!["sort_list".into(), "sort_vector".into()],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
+
+        references: References::default(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn module_with_disabled_definitions() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "one/two".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        type_aliases: HashMap::new(),
+        documentation: Vec::new(),
+        contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: [
+            ("wibble".into(), "experimental".into()),
+            ("Wobble".into(), "experimental".into()),
+        ]
+        .into(),
+        disabled_types: [("Wobble".into(), "experimental".into())].into(),
 
         references: References::default(),
     };
@@ -136,6 +204,9 @@ fn with_line_numbers() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -172,6 +243,9 @@ fn module_with_private_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -208,6 +282,9 @@ fn module_with_app_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -244,6 +321,9 @@ fn module_with_fn_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -280,6 +360,9 @@ fn module_with_tuple_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -322,6 +405,9 @@ fn module_with_generic_type() {
             type_aliases: HashMap::new(),
             documentation: Vec::new(),
             contains_echo: false,
+            benchmarks: vec![],
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
             references: References::default(),
         }
     }
@@ -363,6 +449,9 @@ fn module_with_type_links() {
             type_aliases: HashMap::new(),
             documentation: Vec::new(),
             contains_echo: false,
+            benchmarks: vec![],
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
             references: References::default(),
         }
     }
@@ -404,6 +493,9 @@ fn module_with_type_constructor_documentation() {
             type_aliases: HashMap::new(),
             documentation: Vec::new(),
             contains_echo: false,
+            benchmarks: vec![],
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
             references: References::default(),
         }
     }
@@ -448,6 +540,9 @@ fn module_with_type_constructor_origin() {
             type_aliases: HashMap::new(),
             documentation: Vec::new(),
             contains_echo: false,
+            benchmarks: vec![],
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
             references: References::default(),
         }
     }
@@ -485,6 +580,9 @@ fn module_type_to_constructors_mapping() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -510,7 +608,123 @@ fn module_fn_value() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
+                    documentation: Some("wobble!".into()),
+                    since: None,
+                    name: "one".into(),
+                    field_map: None,
+                    module: "a".into(),
+                    arity: 5,
+                    location: SrcSpan {
+                        start: 535,
+                        end: 1100,
+                    },
+                    external_erlang: None,
+                    external_javascript: None,
+                    implementations: Implementations {
+                        gleam: true,
+                        uses_erlang_externals: false,
+                        uses_javascript_externals: false,
+                        can_run_on_erlang: true,
+                        can_run_on_javascript: true,
+                    },
+                },
+            },
+        )]
+        .into(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        type_aliases: HashMap::new(),
+        documentation: Vec::new(),
+        contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
+
+        references: References::default(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn pure_module_fn_value() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        accessors: HashMap::new(),
+        values: [(
+            "one".into(),
+            ValueConstructor {
+                publicity: Publicity::Public,
+                deprecation: Deprecation::NotDeprecated,
+                type_: type_::int(),
+                variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Pure,
+                    documentation: Some("wobble!".into()),
+                    since: None,
+                    name: "one".into(),
+                    field_map: None,
+                    module: "a".into(),
+                    arity: 5,
+                    location: SrcSpan {
+                        start: 535,
+                        end: 1100,
+                    },
+                    external_erlang: None,
+                    external_javascript: None,
+                    implementations: Implementations {
+                        gleam: true,
+                        uses_erlang_externals: false,
+                        uses_javascript_externals: false,
+                        can_run_on_erlang: true,
+                        can_run_on_javascript: true,
+                    },
+                },
+            },
+        )]
+        .into(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        type_aliases: HashMap::new(),
+        documentation: Vec::new(),
+        contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
+
+        references: References::default(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn since_module_fn_value() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        accessors: HashMap::new(),
+        values: [(
+            "one".into(),
+            ValueConstructor {
+                publicity: Publicity::Public,
+                deprecation: Deprecation::NotDeprecated,
+                type_: type_::int(),
+                variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wobble!".into()),
+                    since: Some("1.2.0".into()),
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -538,6 +752,9 @@ fn module_fn_value() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -564,7 +781,9 @@ fn deprecated_module_fn_value() {
                 },
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wobble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -592,6 +811,9 @@ fn deprecated_module_fn_value() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -616,7 +838,9 @@ fn private_module_fn_value() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wobble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -644,6 +868,9 @@ fn private_module_fn_value() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -670,7 +897,9 @@ fn module_fn_value_regression() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wabble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -698,6 +927,9 @@ fn module_fn_value_regression() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -723,7 +955,9 @@ fn module_fn_value_with_field_map() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wubble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: Some(FieldMap {
                         arity: 20,
@@ -751,6 +985,9 @@ fn module_fn_value_with_field_map() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -799,6 +1036,9 @@ fn record_value() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -851,6 +1091,9 @@ fn record_value_with_field_map() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -924,6 +1167,9 @@ fn accessors() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -970,6 +1216,9 @@ fn private_accessors() {
         types: HashMap::new(),
         types_value_constructors: HashMap::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         values: HashMap::new(),
         accessors: [
             (
@@ -1014,6 +1263,22 @@ fn constant_int() {
     assert_eq!(roundtrip(&module), module);
 }
 
+#[test]
+fn since_constant() {
+    let mut module = constant_module(Constant::Int {
+        location: Default::default(),
+        value: "100".into(),
+        int_value: 100.into(),
+    });
+    for value in module.values.values_mut() {
+        if let ValueConstructorVariant::ModuleConstant { since, .. } = &mut value.variant {
+            *since = Some("1.2.0".into());
+        }
+    }
+
+    assert_eq!(roundtrip(&module), module);
+}
+
 #[test]
 fn constant_float() {
     let module = constant_module(Constant::Float {
@@ -1149,6 +1414,7 @@ fn constant_var() {
             type_: type_::int(),
             variant: ValueConstructorVariant::ModuleConstant {
                 documentation: Some("some doc".into()),
+                since: None,
                 literal: one_original.clone(),
                 location: SrcSpan::default(),
                 module: "one/two".into(),
@@ -1182,6 +1448,7 @@ fn constant_var() {
                     type_: type_::int(),
                     variant: ValueConstructorVariant::ModuleConstant {
                         documentation: Some("some doc!!!!!!!!!".into()),
+                        since: None,
                         literal: one,
                         location: SrcSpan::default(),
                         module: "one/two".into(),
@@ -1204,6 +1471,7 @@ fn constant_var() {
                     type_: type_::int(),
                     variant: ValueConstructorVariant::ModuleConstant {
                         documentation: Some("some doc yeah".into()),
+                        since: None,
                         literal: one_original,
                         location: SrcSpan::default(),
                         module: "one/two".into(),
@@ -1226,6 +1494,9 @@ fn constant_var() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1423,6 +1694,9 @@ fn deprecated_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1447,7 +1721,9 @@ fn module_fn_value_with_external_implementations() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wabble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -1475,6 +1751,9 @@ fn module_fn_value_with_external_implementations() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: References::default(),
     };
 
@@ -1499,6 +1778,9 @@ fn module_containing_echo() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: true,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: References::default(),
     };
 
@@ -1525,7 +1807,9 @@ fn internal_module_fn() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wabble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -1553,6 +1837,9 @@ fn internal_module_fn() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1583,7 +1870,9 @@ fn internal_annotated_module_fn() {
                 deprecation: Deprecation::NotDeprecated,
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
+                    purity: Purity::Unknown,
                     documentation: Some("wabble!".into()),
+                    since: None,
                     name: "one".into(),
                     field_map: None,
                     module: "a".into(),
@@ -1611,6 +1900,9 @@ fn internal_annotated_module_fn() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1661,6 +1953,9 @@ fn type_variable_ids_in_constructors_are_shared() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1701,6 +1996,9 @@ fn type_with_inferred_variant() {
         is_internal: false,
         package: "some_package".into(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         origin: Origin::Src,
         name: "a/b".into(),
         types: [(
@@ -1766,6 +2064,9 @@ fn module_with_type_aliases() {
         .into(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1794,6 +2095,9 @@ fn module_with_documentation() {
             "And finally, a third".into(),
         ],
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
 
         references: References::default(),
     };
@@ -1830,6 +2134,9 @@ fn module_with_opaque_type() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: References::default(),
     };
 
@@ -1854,6 +2161,9 @@ fn module_with_references() {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: References {
             imported_modules: ["some_module".into(), "some_other_module".into()].into(),
             value_references: [
@@ -1920,8 +2230,124 @@ fn module_with_references() {
                 ],
             )]
             .into(),
+            discarded_value_references: [(
+                ("some_module".into(), "some_function".into()),
+                vec![Reference {
+                    location: SrcSpan::new(7, 11),
+                    kind: ReferenceKind::Unqualified,
+                }],
+            )]
+            .into(),
         },
     };
 
     assert_eq!(roundtrip(&module), module);
 }
+
+#[test]
+fn interface_file_roundtrip() {
+    let module = constant_module(Constant::Int {
+        location: Default::default(),
+        value: "100".into(),
+        int_value: 100.into(),
+    });
+    let bytes = encode_interface_file(&module).unwrap();
+    let decoded =
+        decode_interface_file("a.gleam_interface".into(), &bytes, UniqueIdGenerator::new())
+            .unwrap();
+    assert_eq!(decoded, module);
+}
+
+#[test]
+fn interface_file_can_be_type_checked_against() {
+    let library = type_::tests::compile_module(
+        "wibble",
+        "pub type Wobble { Wobble(Int) }
+pub fn wobble(x: Int) -> Wobble { Wobble(x) }",
+        None,
+        vec![],
+    )
+    .unwrap();
+    let bytes = encode_interface_file(&library.type_info).unwrap();
+
+    let ids = UniqueIdGenerator::new();
+    let interface =
+        decode_interface_file("wibble.gleam_interface".into(), &bytes, ids.clone()).unwrap();
+    let mut modules = im::HashMap::new();
+    let _ = modules.insert(
+        prelude::PRELUDE_MODULE_NAME.into(),
+        prelude::build_prelude(&ids),
+    );
+    let _ = modules.insert("wibble".into(), interface);
+
+    let src = "import wibble
+pub fn main() { wibble.wobble(1) }";
+    let parsed =
+        crate::parse::parse_module("src/main.gleam".into(), src, &WarningEmitter::null()).unwrap();
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    let result = ModuleAnalyzerConstructor::<()> {
+        target: Target::Erlang,
+        ids: &ids,
+        origin: Origin::Src,
+        importable_modules: &modules,
+        warnings: &TypeWarningEmitter::null(),
+        direct_dependencies: &HashMap::new(),
+        target_support: TargetSupport::Enforced,
+        package_config: &config,
+    }
+    .infer_module(parsed.module, LineNumbers::new(src), "".into());
+
+    let Outcome::Ok(module) = result else {
+        panic!("The module should type check against the interface");
+    };
+    let main = module.type_info.values.get("main").unwrap();
+    assert_eq!(
+        type_::pretty::Printer::new().pretty_print(&main.type_, 0),
+        "fn() -> Wobble"
+    );
+}
+
+#[test]
+fn interface_file_with_no_header_is_rejected() {
+    let module = constant_module(Constant::Int {
+        location: Default::default(),
+        value: "100".into(),
+        int_value: 100.into(),
+    });
+    let bytes = ModuleEncoder::new(&module).encode().unwrap();
+    let error = decode_interface_file("a.gleam_interface".into(), &bytes, UniqueIdGenerator::new())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        crate::Error::InvalidModuleInterfaceFile {
+            path: "a.gleam_interface".into(),
+            error: "It is not a module interface file".into(),
+        }
+    );
+}
+
+#[test]
+fn interface_file_with_a_newer_version_is_rejected() {
+    let module = constant_module(Constant::Int {
+        location: Default::default(),
+        value: "100".into(),
+        int_value: 100.into(),
+    });
+    let mut bytes = encode_interface_file(&module).unwrap();
+    let newer = (INTERFACE_FILE_FORMAT_VERSION + 1).to_le_bytes();
+    bytes[8..12].copy_from_slice(&newer);
+    let error = decode_interface_file("a.gleam_interface".into(), &bytes, UniqueIdGenerator::new())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        crate::Error::InvalidModuleInterfaceFile {
+            path: "a.gleam_interface".into(),
+            error: format!(
+                "It uses version {} of the format, but this version of Gleam only supports up \
+to version {INTERFACE_FILE_FORMAT_VERSION}",
+                INTERFACE_FILE_FORMAT_VERSION + 1
+            ),
+        }
+    );
+}
@@ -0,0 +1,160 @@
+//! The graph of the imports between the modules of a project and of all its
+//! dependencies, as shown by the `gleam deps graph` command.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use ecow::EcoString;
+use itertools::Itertools;
+use serde::Serialize;
+
+/// A module and the names of all the modules it imports.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleImports {
+    pub package: EcoString,
+    pub name: EcoString,
+    pub imports: Vec<EcoString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleGraph {
+    pub modules: Vec<Module>,
+    pub imports: Vec<Import>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Module {
+    pub name: EcoString,
+    pub package: EcoString,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Import {
+    pub from: EcoString,
+    pub to: EcoString,
+    /// True if the imported module belongs to a different package than the
+    /// importing one.
+    pub external: bool,
+    /// True if this import is part of an import cycle. A project with cycles
+    /// can't be compiled, but we still want to be able to show them.
+    pub cyclic: bool,
+}
+
+impl ModuleGraph {
+    /// Builds the graph out of the imports of each module. Imports of modules
+    /// that are not in the given list (like the `gleam` prelude) are ignored.
+    ///
+    pub fn new(modules: Vec<ModuleImports>) -> Self {
+        let modules = modules
+            .into_iter()
+            .sorted_by(|one, other| one.name.cmp(&other.name))
+            .collect_vec();
+
+        let mut graph = petgraph::Graph::<(), ()>::new();
+        let indexes: HashMap<_, _> = modules
+            .iter()
+            .map(|module| (module.name.clone(), graph.add_node(())))
+            .collect();
+        let packages: HashMap<_, _> = modules
+            .iter()
+            .map(|module| (module.name.clone(), module.package.clone()))
+            .collect();
+
+        let mut edges = vec![];
+        for module in &modules {
+            for import in module.imports.iter().sorted().dedup() {
+                let Some(&to) = indexes.get(import) else {
+                    continue;
+                };
+                let from = *indexes.get(&module.name).expect("Finding index for module");
+                let _ = graph.add_edge(from, to, ());
+                edges.push((module, import, from, to));
+            }
+        }
+
+        // Two modules are part of the same cycle if they are in the same
+        // strongly connected component.
+        let mut components = HashMap::new();
+        for (component_index, component) in
+            petgraph::algo::tarjan_scc(&graph).into_iter().enumerate()
+        {
+            let size = component.len();
+            for node in component {
+                let _ = components.insert(node, (component_index, size));
+            }
+        }
+
+        let imports = edges
+            .into_iter()
+            .map(|(module, import, from, to)| {
+                let &(from_component, size) = components.get(&from).expect("Finding component");
+                let &(to_component, _) = components.get(&to).expect("Finding component");
+                Import {
+                    from: module.name.clone(),
+                    to: import.clone(),
+                    external: packages.get(import) != Some(&module.package),
+                    cyclic: from == to || (from_component == to_component && size > 1),
+                }
+            })
+            .collect();
+
+        let modules = modules
+            .into_iter()
+            .map(|module| Module {
+                name: module.name,
+                package: module.package,
+            })
+            .collect();
+
+        Self { modules, imports }
+    }
+
+    pub fn has_cycles(&self) -> bool {
+        self.imports.iter().any(|import| import.cyclic)
+    }
+
+    /// Renders the graph in the DOT language. Each package is drawn as a
+    /// cluster containing all its modules, imports that cross a package
+    /// boundary are dashed and imports that are part of a cycle are red.
+    ///
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n");
+
+        let packages = self
+            .modules
+            .iter()
+            .into_group_map_by(|module| &module.package)
+            .into_iter()
+            .sorted_by(|(one, _), (other, _)| one.cmp(other));
+        for (package, modules) in packages {
+            dot.push_str(&format!("  subgraph \"cluster_{package}\" {{\n"));
+            dot.push_str(&format!("    label = \"{package}\";\n"));
+            for module in modules {
+                dot.push_str(&format!("    \"{}\";\n", module.name));
+            }
+            dot.push_str("  }\n");
+        }
+
+        for import in &self.imports {
+            let attributes = match (import.external, import.cyclic) {
+                (_, true) => " [color = red]",
+                (true, false) => " [style = dashed]",
+                (false, false) => "",
+            };
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\"{attributes};\n",
+                import.from, import.to
+            ));
+        }
+
+        dot.push('}');
+        dot
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("module graph serialisation")
+    }
+}
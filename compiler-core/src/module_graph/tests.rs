@@ -0,0 +1,93 @@
+use super::*;
+
+fn module(package: &str, name: &str, imports: &[&str]) -> ModuleImports {
+    ModuleImports {
+        package: package.into(),
+        name: name.into(),
+        imports: imports.iter().map(|import| (*import).into()).collect(),
+    }
+}
+
+fn project() -> ModuleGraph {
+    ModuleGraph::new(vec![
+        module("app", "app", &["app/router", "gleam/list", "gleam"]),
+        module("app", "app/router", &["gleam/list", "gleam/string"]),
+        module("gleam_stdlib", "gleam/list", &[]),
+        module("gleam_stdlib", "gleam/string", &["gleam/list"]),
+    ])
+}
+
+#[test]
+fn imports_of_unknown_modules_are_ignored() {
+    let graph = project();
+    assert!(!graph.imports.iter().any(|import| import.to == "gleam"));
+}
+
+#[test]
+fn imports_across_packages_are_external() {
+    let graph = project();
+    let external = graph
+        .imports
+        .iter()
+        .filter(|import| import.external)
+        .map(|import| (import.from.as_str(), import.to.as_str()))
+        .collect_vec();
+    assert_eq!(
+        external,
+        vec![
+            ("app", "gleam/list"),
+            ("app/router", "gleam/list"),
+            ("app/router", "gleam/string"),
+        ]
+    );
+}
+
+#[test]
+fn graph_without_cycles() {
+    let graph = project();
+    assert!(!graph.has_cycles());
+}
+
+#[test]
+fn graph_with_cycle() {
+    let graph = ModuleGraph::new(vec![
+        module("app", "a", &["b"]),
+        module("app", "b", &["c"]),
+        module("app", "c", &["a"]),
+        module("app", "d", &["a"]),
+    ]);
+    assert!(graph.has_cycles());
+    let cyclic = graph
+        .imports
+        .iter()
+        .filter(|import| import.cyclic)
+        .map(|import| (import.from.as_str(), import.to.as_str()))
+        .collect_vec();
+    assert_eq!(cyclic, vec![("a", "b"), ("b", "c"), ("c", "a")]);
+}
+
+#[test]
+fn module_importing_itself_is_cyclic() {
+    let graph = ModuleGraph::new(vec![module("app", "a", &["a"])]);
+    assert!(graph.has_cycles());
+}
+
+#[test]
+fn dot_output() {
+    insta::assert_snapshot!(project().to_dot());
+}
+
+#[test]
+fn dot_output_with_cycle() {
+    let graph = ModuleGraph::new(vec![
+        module("app", "a", &["b"]),
+        module("app", "b", &["a", "gleam/list"]),
+        module("gleam_stdlib", "gleam/list", &[]),
+    ]);
+    insta::assert_snapshot!(graph.to_dot());
+}
+
+#[test]
+fn json_output() {
+    insta::assert_snapshot!(project().to_json());
+}
@@ -0,0 +1,625 @@
+//! Optional transformations of the typed AST that are performed right before
+//! code generation. These are opt-in and configured with the
+//! `[optimisations]` section of a package's `gleam.toml`.
+
+#[cfg(test)]
+mod tests;
+mod whole_program;
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        AssignmentKind, BitArrayOption, CallArg, ClauseGuard, Constant, Definition,
+        ImplicitCallArgOrigin, PIPE_VARIABLE, Pattern, PipelineAssignmentKind, Statement,
+        TypedClause, TypedClauseGuard, TypedConstant, TypedExpr, TypedModule, TypedPattern,
+        TypedPipelineAssignment, TypedStatement,
+    },
+    config::OptimisationsConfig,
+    type_::{ModuleValueConstructor, PatternConstructor, ValueConstructorVariant},
+};
+use ecow::EcoString;
+use num_bigint::BigInt;
+use vec1::Vec1;
+
+pub use whole_program::remove_unreachable_definitions;
+
+/// Performs all the optimisations enabled in the given configuration on the
+/// module.
+///
+pub fn optimise(module: &mut TypedModule, config: OptimisationsConfig) {
+    let optimiser = Optimiser { config };
+    for definition in module.definitions.iter_mut() {
+        match definition {
+            Definition::Function(function) => optimiser.optimise_statements(&mut function.body),
+            Definition::TypeAlias(_)
+            | Definition::CustomType(_)
+            | Definition::Import(_)
+            | Definition::ModuleConstant(_) => (),
+        }
+    }
+}
+
+struct Optimiser {
+    config: OptimisationsConfig,
+}
+
+impl Optimiser {
+    fn optimise_statements(&self, statements: &mut Vec1<TypedStatement>) {
+        if self.config.remove_unused_pure_calls {
+            remove_unused_pure_calls(statements);
+        }
+
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Expression(expression) => self.optimise_expression(expression),
+                Statement::Assignment(assignment) => {
+                    self.optimise_expression(&mut assignment.value);
+                    match &mut assignment.kind {
+                        AssignmentKind::Assert {
+                            message: Some(message),
+                            ..
+                        } => self.optimise_expression(message),
+                        AssignmentKind::Assert { message: None, .. }
+                        | AssignmentKind::Let
+                        | AssignmentKind::Generated => (),
+                    }
+                }
+                Statement::Use(use_) => self.optimise_expression(&mut use_.call),
+                Statement::Assert(assert) => self.optimise_expression(&mut assert.value),
+            }
+        }
+    }
+
+    fn optimise_expression(&self, expression: &mut TypedExpr) {
+        match expression {
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Invalid { .. } => (),
+
+            TypedExpr::Block { statements, .. } => self.optimise_statements(statements),
+            TypedExpr::Fn { body, .. } => self.optimise_statements(body),
+
+            TypedExpr::Pipeline {
+                first_value,
+                assignments,
+                finally,
+                ..
+            } => {
+                self.optimise_expression(&mut first_value.value);
+                for (assignment, _kind) in assignments {
+                    self.optimise_expression(&mut assignment.value);
+                }
+                self.optimise_expression(finally);
+            }
+
+            TypedExpr::List { elements, tail, .. } => {
+                elements
+                    .iter_mut()
+                    .for_each(|expression| self.optimise_expression(expression));
+                if let Some(tail) = tail {
+                    self.optimise_expression(tail);
+                }
+            }
+            TypedExpr::Tuple { elements, .. } => elements
+                .iter_mut()
+                .for_each(|expression| self.optimise_expression(expression)),
+
+            TypedExpr::Call { fun, args, .. } => {
+                self.optimise_expression(fun);
+                self.optimise_call_args(args);
+            }
+            TypedExpr::RecordUpdate {
+                record,
+                constructor,
+                args,
+                ..
+            } => {
+                self.optimise_expression(&mut record.value);
+                self.optimise_expression(constructor);
+                self.optimise_call_args(args);
+            }
+
+            TypedExpr::BinOp { left, right, .. } => {
+                self.optimise_expression(left);
+                self.optimise_expression(right);
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => {
+                subjects
+                    .iter_mut()
+                    .for_each(|expression| self.optimise_expression(expression));
+                clauses
+                    .iter_mut()
+                    .for_each(|clause| self.optimise_clause(clause));
+            }
+
+            TypedExpr::RecordAccess { record, .. } => self.optimise_expression(record),
+            TypedExpr::TupleIndex { tuple, .. } => self.optimise_expression(tuple),
+            TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+                self.optimise_expression(value)
+            }
+
+            TypedExpr::Todo { message, .. } | TypedExpr::Panic { message, .. } => {
+                if let Some(message) = message {
+                    self.optimise_expression(message);
+                }
+            }
+            TypedExpr::Echo {
+                expression: value, ..
+            } => {
+                if let Some(value) = value {
+                    self.optimise_expression(value);
+                }
+            }
+
+            TypedExpr::BitArray { segments, .. } => {
+                for segment in segments {
+                    self.optimise_expression(&mut segment.value);
+                    for option in segment.options.iter_mut() {
+                        if let BitArrayOption::Size { value, .. } = option {
+                            self.optimise_expression(value);
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.config.reduce_constant_cases {
+            reduce_constant_case(expression);
+        }
+
+        if self.config.inline_pipelines {
+            inline_pipeline(expression);
+        }
+    }
+
+    fn optimise_call_args(&self, args: &mut [CallArg<TypedExpr>]) {
+        for arg in args {
+            self.optimise_expression(&mut arg.value);
+        }
+    }
+
+    fn optimise_clause(&self, clause: &mut TypedClause) {
+        self.optimise_expression(&mut clause.then);
+    }
+}
+
+/// Removes all the calls to functions annotated as `@pure` whose value is
+/// never used. For example:
+///
+/// ```gleam
+/// pub fn main() {
+///   wibble(1)
+///   //^^^^^^ if `wibble` is pure and its result is discarded this call is
+///   //       removed.
+///   let _ = wibble(2)
+///   //^^^^^^^^^^^^^^^ same goes for values assigned to a discard pattern.
+///   wobble()
+/// }
+/// ```
+///
+/// The compiler does not check that functions annotated as `@pure` really
+/// have no side effects: if they do, those effects will be lost.
+///
+/// A call is only removed if evaluating its arguments couldn't have any side
+/// effects either.
+///
+fn remove_unused_pure_calls(statements: &mut Vec1<TypedStatement>) {
+    let last = statements.len() - 1;
+    let mut index = 0;
+    statements
+        .retain(|statement| {
+            let keep = index == last || !is_unused_pure_call(statement);
+            index += 1;
+            keep
+        })
+        .expect("The last statement is always kept");
+}
+
+fn is_unused_pure_call(statement: &TypedStatement) -> bool {
+    match statement {
+        Statement::Expression(expression) => is_pure_call(expression),
+        Statement::Assignment(assignment) => match (&assignment.kind, &assignment.pattern) {
+            (AssignmentKind::Let, Pattern::Discard { .. }) => is_pure_call(&assignment.value),
+            _ => false,
+        },
+        Statement::Use(_) | Statement::Assert(_) => false,
+    }
+}
+
+fn is_pure_call(expression: &TypedExpr) -> bool {
+    match expression {
+        TypedExpr::Call { fun, args, .. } => {
+            fun.is_pure_function() && args.iter().all(|arg| has_no_side_effects(&arg.value))
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if evaluating the expression can never have any side effects,
+/// including crashing the program.
+///
+fn has_no_side_effects(expression: &TypedExpr) -> bool {
+    match expression {
+        TypedExpr::Int { .. }
+        | TypedExpr::Float { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::Var { .. }
+        | TypedExpr::Fn { .. }
+        | TypedExpr::ModuleSelect { .. } => true,
+
+        TypedExpr::List { elements, tail, .. } => {
+            elements.iter().all(has_no_side_effects)
+                && tail.as_ref().is_none_or(|tail| has_no_side_effects(tail))
+        }
+        TypedExpr::Tuple { elements, .. } => elements.iter().all(has_no_side_effects),
+
+        TypedExpr::BinOp { left, right, .. } => {
+            has_no_side_effects(left) && has_no_side_effects(right)
+        }
+        TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+            has_no_side_effects(value)
+        }
+        TypedExpr::RecordAccess { record, .. } => has_no_side_effects(record),
+        TypedExpr::TupleIndex { tuple, .. } => has_no_side_effects(tuple),
+
+        TypedExpr::Call { fun, args, .. } => {
+            (fun.is_record_builder() || fun.is_pure_function())
+                && args.iter().all(|arg| has_no_side_effects(&arg.value))
+        }
+
+        // Bit arrays can fail to be built at runtime, and all the remaining
+        // expressions could contain any arbitrary code: we're conservative
+        // and assume they could have side effects.
+        TypedExpr::BitArray { .. }
+        | TypedExpr::Block { .. }
+        | TypedExpr::Pipeline { .. }
+        | TypedExpr::Case { .. }
+        | TypedExpr::RecordUpdate { .. }
+        | TypedExpr::Todo { .. }
+        | TypedExpr::Panic { .. }
+        | TypedExpr::Echo { .. }
+        | TypedExpr::Invalid { .. } => false,
+    }
+}
+
+/// Passes the value of each step of a pipeline directly to the following one,
+/// instead of assigning it to a variable first. For example:
+///
+/// ```gleam
+/// 1 |> wibble |> wobble
+/// // is compiled as if it were:
+/// wobble(wibble(1))
+/// // rather than:
+/// let _pipe = 1
+/// let _pipe = wibble(_pipe)
+/// wobble(_pipe)
+/// ```
+///
+/// A step is only inlined if the function of the following step, and all of
+/// its other arguments, can't have any side effects. Otherwise the targets
+/// could end up evaluating them before the inlined step.
+///
+/// Echoes in a pipeline print the variable assigned by the previous step, so
+/// the steps right before and after an echo are never inlined.
+///
+fn inline_pipeline(expression: &mut TypedExpr) {
+    if !matches!(expression, TypedExpr::Pipeline { .. }) {
+        return;
+    }
+    let placeholder = TypedExpr::Invalid {
+        location: expression.location(),
+        type_: expression.type_(),
+    };
+    let TypedExpr::Pipeline {
+        location,
+        first_value,
+        assignments,
+        mut finally,
+        finally_kind,
+    } = std::mem::replace(expression, placeholder)
+    else {
+        unreachable!("the expression is a pipeline")
+    };
+
+    // The steps that still have to be assigned to a variable, each with its
+    // kind. The first step of a pipeline doesn't have one.
+    let mut steps: Vec<(TypedPipelineAssignment, Option<PipelineAssignmentKind>)> =
+        vec![(first_value, None)];
+    for (mut assignment, kind) in assignments {
+        inline_previous_step(&mut steps, &mut assignment.value);
+        let kind = if steps.is_empty() { None } else { Some(kind) };
+        steps.push((assignment, kind));
+    }
+    inline_previous_step(&mut steps, &mut finally);
+
+    let mut steps = steps.into_iter();
+    *expression = match steps.next() {
+        None => *finally,
+        Some((first_value, _)) => TypedExpr::Pipeline {
+            location,
+            first_value,
+            assignments: steps
+                .map(|(assignment, kind)| {
+                    (assignment, kind.expect("Only the first step has no kind"))
+                })
+                .collect(),
+            finally,
+            finally_kind,
+        },
+    };
+}
+
+/// If it's safe to do so, removes the last of the steps and puts its value in
+/// place of the pipe variable passed to the given step.
+///
+fn inline_previous_step(
+    steps: &mut Vec<(TypedPipelineAssignment, Option<PipelineAssignmentKind>)>,
+    step: &mut TypedExpr,
+) {
+    let Some((previous, _)) = steps.last() else {
+        return;
+    };
+    if let TypedExpr::Echo {
+        expression: None, ..
+    } = previous.value.as_ref()
+    {
+        return;
+    }
+    let TypedExpr::Call { fun, args, .. } = step else {
+        return;
+    };
+    if !has_no_side_effects(fun) {
+        return;
+    }
+
+    let mut pipe_argument = None;
+    for (index, arg) in args.iter().enumerate() {
+        match (&arg.implicit, &arg.value) {
+            (Some(ImplicitCallArgOrigin::Pipe), TypedExpr::Var { name, .. })
+                if name == PIPE_VARIABLE && pipe_argument.is_none() =>
+            {
+                pipe_argument = Some(index)
+            }
+            _ if has_no_side_effects(&arg.value) => (),
+            _ => return,
+        }
+    }
+    let Some(arg) = pipe_argument.and_then(|index| args.get_mut(index)) else {
+        return;
+    };
+
+    let (previous, _) = steps.pop().expect("There is a previous step");
+    arg.value = *previous.value;
+}
+
+/// Replaces a `case` expression whose subjects are all literal values with the
+/// body of the clause that would be picked at runtime. For example:
+///
+/// ```gleam
+/// case True {
+///   False -> "wibble"
+///   True -> "wobble"
+/// }
+/// // is replaced with:
+/// "wobble"
+/// ```
+///
+/// This is conservative: the expression is left untouched as soon as we
+/// reach a clause we can't decide on, for example because its patterns
+/// would bind variables or because its guard isn't a literal `True` or
+/// `False`.
+///
+fn reduce_constant_case(expression: &mut TypedExpr) {
+    let TypedExpr::Case {
+        subjects, clauses, ..
+    } = expression
+    else {
+        return;
+    };
+
+    let Some(subjects) = subjects
+        .iter()
+        .map(literal_value)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+
+    let mut selected = None;
+    for (index, clause) in clauses.iter().enumerate() {
+        match (clause_match(clause, &subjects), &clause.guard) {
+            (MatchOutcome::NoMatch, _) => continue,
+            (MatchOutcome::Unknown, _) => return,
+            (MatchOutcome::Match, None) => (),
+            (MatchOutcome::Match, Some(guard)) => match guard_value(guard) {
+                Some(true) => (),
+                Some(false) => continue,
+                None => return,
+            },
+        }
+        selected = Some(index);
+        break;
+    }
+
+    if let Some(index) = selected {
+        *expression = clauses.swap_remove(index).then;
+    }
+}
+
+/// A value known at compile time that can be matched against a pattern.
+///
+#[derive(Debug)]
+enum LiteralValue {
+    Int(BigInt),
+    Float(f64),
+    String(EcoString),
+    /// A record with no fields, like `True` or `Nil`.
+    Constructor {
+        module: EcoString,
+        name: EcoString,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchOutcome {
+    Match,
+    NoMatch,
+    /// We can't tell at compile time whether the pattern would match, or it
+    /// matches but binds some variables.
+    Unknown,
+}
+
+fn literal_value(expression: &TypedExpr) -> Option<LiteralValue> {
+    match expression {
+        TypedExpr::Int { int_value, .. } => Some(LiteralValue::Int(int_value.clone())),
+        TypedExpr::Float { value, .. } => parse_float(value).map(LiteralValue::Float),
+        TypedExpr::String { value, .. } => Some(LiteralValue::String(value.clone())),
+        TypedExpr::Var { constructor, .. } => match &constructor.variant {
+            ValueConstructorVariant::Record {
+                module,
+                name,
+                arity: 0,
+                ..
+            } => Some(LiteralValue::Constructor {
+                module: module.clone(),
+                name: name.clone(),
+            }),
+            ValueConstructorVariant::ModuleConstant { literal, .. } => {
+                constant_literal_value(literal)
+            }
+            _ => None,
+        },
+        TypedExpr::ModuleSelect {
+            constructor: ModuleValueConstructor::Constant { literal, .. },
+            ..
+        } => constant_literal_value(literal),
+        _ => None,
+    }
+}
+
+fn constant_literal_value(constant: &TypedConstant) -> Option<LiteralValue> {
+    match constant {
+        Constant::Int { int_value, .. } => Some(LiteralValue::Int(int_value.clone())),
+        Constant::Float { value, .. } => parse_float(value).map(LiteralValue::Float),
+        Constant::String { value, .. } => Some(LiteralValue::String(value.clone())),
+        Constant::Record {
+            args, tag, type_, ..
+        } if args.is_empty() => {
+            let (module, _) = type_.named_type_name()?;
+            Some(LiteralValue::Constructor {
+                module,
+                name: tag.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    value.replace('_', "").parse().ok()
+}
+
+fn clause_match(clause: &TypedClause, subjects: &[LiteralValue]) -> MatchOutcome {
+    let mut outcome = MatchOutcome::NoMatch;
+    for patterns in std::iter::once(&clause.pattern).chain(&clause.alternative_patterns) {
+        match multi_pattern_match(patterns, subjects) {
+            // If any of the alternatives matches without binding any variable
+            // then the clause is picked no matter what the others would do.
+            MatchOutcome::Match => return MatchOutcome::Match,
+            MatchOutcome::Unknown => outcome = MatchOutcome::Unknown,
+            MatchOutcome::NoMatch => (),
+        }
+    }
+    outcome
+}
+
+fn multi_pattern_match(patterns: &[TypedPattern], subjects: &[LiteralValue]) -> MatchOutcome {
+    let mut outcome = MatchOutcome::Match;
+    for (pattern, subject) in patterns.iter().zip(subjects) {
+        match pattern_match(pattern, subject) {
+            MatchOutcome::NoMatch => return MatchOutcome::NoMatch,
+            MatchOutcome::Unknown => outcome = MatchOutcome::Unknown,
+            MatchOutcome::Match => (),
+        }
+    }
+    outcome
+}
+
+fn pattern_match(pattern: &TypedPattern, subject: &LiteralValue) -> MatchOutcome {
+    let matches = |matches| {
+        if matches {
+            MatchOutcome::Match
+        } else {
+            MatchOutcome::NoMatch
+        }
+    };
+
+    match (pattern, subject) {
+        (Pattern::Discard { .. }, _) => MatchOutcome::Match,
+
+        (Pattern::Int { int_value, .. }, LiteralValue::Int(subject)) => {
+            matches(int_value == subject)
+        }
+
+        (Pattern::Float { value, .. }, LiteralValue::Float(subject)) => match parse_float(value) {
+            Some(value) => matches(value == *subject),
+            None => MatchOutcome::Unknown,
+        },
+
+        // Strings are compared as they are written in the source code, so two
+        // different strings might still be the same once their escape
+        // sequences are taken into account.
+        (Pattern::String { value, .. }, LiteralValue::String(subject)) if value == subject => {
+            MatchOutcome::Match
+        }
+        (Pattern::String { value, .. }, LiteralValue::String(subject))
+            if !value.contains('\\') && !subject.contains('\\') =>
+        {
+            MatchOutcome::NoMatch
+        }
+
+        // A string prefix pattern always binds a variable, so the best we can
+        // do is telling when it doesn't match.
+        (
+            Pattern::StringPrefix {
+                left_side_string, ..
+            },
+            LiteralValue::String(subject),
+        ) if !left_side_string.contains('\\')
+            && !subject.contains('\\')
+            && !subject.starts_with(left_side_string.as_str()) =>
+        {
+            MatchOutcome::NoMatch
+        }
+
+        (
+            Pattern::Constructor {
+                constructor: Inferred::Known(PatternConstructor { module, name, .. }),
+                ..
+            },
+            LiteralValue::Constructor {
+                module: subject_module,
+                name: subject_name,
+            },
+        ) => matches(module == subject_module && name == subject_name),
+
+        _ => MatchOutcome::Unknown,
+    }
+}
+
+/// Returns the value of a guard if it's a literal `True` or `False`.
+///
+fn guard_value(guard: &TypedClauseGuard) -> Option<bool> {
+    match guard {
+        ClauseGuard::Constant(Constant::Record { tag, type_, .. }) if type_.is_bool() => {
+            Some(tag == "True")
+        }
+        _ => None,
+    }
+}
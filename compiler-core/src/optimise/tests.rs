@@ -0,0 +1,767 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    analyse::TargetSupport,
+    ast::TypedModule,
+    build::{Origin, Target},
+    config::{OptimisationsConfig, PackageConfig},
+    erlang::module,
+    line_numbers::LineNumbers,
+    type_::{ModuleInterface, PRELUDE_MODULE_NAME},
+    uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, WarningEmitter},
+};
+
+fn compile_optimised(src: &str, optimisations: OptimisationsConfig) -> String {
+    let mut modules = im::HashMap::new();
+    let ids = UniqueIdGenerator::new();
+    let _ = modules.insert(
+        PRELUDE_MODULE_NAME.into(),
+        crate::type_::build_prelude(&ids),
+    );
+    let mut ast = analyse("my/mod", src, &modules, &ids);
+
+    super::optimise(&mut ast, optimisations);
+
+    module(
+        &ast,
+        &LineNumbers::new(src),
+        src,
+        Utf8Path::new("/root"),
+        None,
+        None,
+    )
+    .expect("Erlang codegen")
+}
+
+/// Compiles the given `src` modules, in order, to Erlang after removing the
+/// definitions that can't be reached from the public definitions, or only the
+/// ones of the `thepackage` module when `remove_unused_public` is set.
+///
+fn compile_whole_program(sources: &[(&str, &str)], remove_unused_public: bool) -> String {
+    let mut modules = im::HashMap::new();
+    let ids = UniqueIdGenerator::new();
+    let _ = modules.insert(
+        PRELUDE_MODULE_NAME.into(),
+        crate::type_::build_prelude(&ids),
+    );
+    let mut asts = vec![];
+    for (name, src) in sources {
+        let ast = analyse(name, src, &modules, &ids);
+        let _ = modules.insert(ast.name.clone(), ast.type_info.clone());
+        asts.push(ast);
+    }
+
+    super::remove_unreachable_definitions(
+        &mut asts.iter_mut().collect_vec(),
+        "thepackage",
+        remove_unused_public,
+    );
+
+    asts.iter()
+        .zip(sources)
+        .map(|(ast, (_, src))| {
+            module(
+                ast,
+                &LineNumbers::new(src),
+                src,
+                Utf8Path::new("/root"),
+                None,
+                None,
+            )
+            .expect("Erlang codegen")
+        })
+        .join("\n")
+}
+
+fn analyse(
+    name: &str,
+    src: &str,
+    modules: &im::HashMap<EcoString, ModuleInterface>,
+    ids: &UniqueIdGenerator,
+) -> TypedModule {
+    let path = Utf8PathBuf::from(format!("/root/project/src/{name}.gleam"));
+    let parsed = crate::parse::parse_module(path.clone(), src, &WarningEmitter::null())
+        .expect("syntax error");
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    let mut ast = parsed.module;
+    ast.name = name.into();
+    crate::analyse::ModuleAnalyzerConstructor::<()> {
+        target: Target::Erlang,
+        ids,
+        origin: Origin::Src,
+        importable_modules: modules,
+        warnings: &TypeWarningEmitter::null(),
+        direct_dependencies: &std::collections::HashMap::new(),
+        target_support: TargetSupport::NotEnforced,
+        package_config: &config,
+    }
+    .infer_module(ast, LineNumbers::new(src), path)
+    .expect("should successfully infer")
+}
+
+macro_rules! assert_optimised {
+    ($src:expr $(,)?) => {{
+        let optimisations = OptimisationsConfig {
+            remove_unused_pure_calls: true,
+            ..Default::default()
+        };
+        assert_optimised!(optimisations, $src);
+    }};
+
+    ($optimisations:expr, $src:expr $(,)?) => {{
+        let compiled = compile_optimised($src, $optimisations);
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED ERLANG\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+macro_rules! assert_whole_program {
+    (remove_unused_public, $(($name:expr, $src:expr)),+ $(,)?) => {{
+        assert_whole_program!(@compile true, $(($name, $src)),+);
+    }};
+
+    ($(($name:expr, $src:expr)),+ $(,)?) => {{
+        assert_whole_program!(@compile false, $(($name, $src)),+);
+    }};
+
+    (@compile $remove_unused_public:expr, $(($name:expr, $src:expr)),+) => {{
+        let sources = [$(($name, $src)),+];
+        let compiled = compile_whole_program(&sources, $remove_unused_public);
+        let source = sources
+            .iter()
+            .map(|(name, src)| format!("-- {name}.gleam\n{src}"))
+            .join("\n");
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED ERLANG\n{}",
+            source, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, &source);
+    }};
+}
+
+#[test]
+fn unused_pure_call_is_removed() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  wibble(1)
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn pure_call_assigned_to_discard_is_removed() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  let _ = wibble(1)
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn pure_call_assigned_to_variable_is_kept() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  let x = wibble(1)
+  x + 2
+}
+"#
+    );
+}
+
+#[test]
+fn returned_pure_call_is_kept() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  wibble(1)
+}
+"#
+    );
+}
+
+#[test]
+fn unused_impure_call_is_kept() {
+    assert_optimised!(
+        r#"
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  wibble(1)
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn pure_call_with_impure_argument_is_kept() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+fn wobble() { 1 }
+
+pub fn main() {
+  wibble(wobble())
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn pure_call_with_pure_arguments_is_removed() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  wibble(wibble(1) * 2)
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn unused_pure_call_in_nested_blocks_is_removed() {
+    assert_optimised!(
+        r#"
+@pure
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  let f = fn(x) {
+    wibble(x)
+    x
+  }
+  case f(1) {
+    1 -> {
+      wibble(2)
+      3
+    }
+    _ -> 4
+  }
+}
+"#
+    );
+}
+
+fn reduce_constant_cases() -> OptimisationsConfig {
+    OptimisationsConfig {
+        reduce_constant_cases: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn case_on_int_literal_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 2 {
+    1 -> "one"
+    2 -> "two"
+    _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_float_literal_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 1.0 {
+    1.5 -> "wibble"
+    1.0 -> "wobble"
+    _ -> "wubble"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_string_literal_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case "wobble" {
+    "wibble" -> 1
+    "wib" <> rest -> 2
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_bool_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case True {
+    False -> "wibble"
+    True -> "wobble"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_custom_type_variant_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub type Wibble {
+  Wibble
+  Wobble
+  Wubble(Int)
+}
+
+pub fn main() {
+  case Wobble {
+    Wibble -> 1
+    Wubble(_) -> 2
+    Wobble -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_module_constant_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+const debug = False
+
+pub fn main() {
+  case debug {
+    True -> "debug"
+    False -> "release"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_with_multiple_subjects_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 1, "wibble" {
+    1, "wobble" -> 1
+    2, _ -> 2
+    1, _ -> 3
+    _, _ -> 4
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_with_alternative_patterns_is_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 3 {
+    1 | 2 -> "small"
+    3 | 4 -> "medium"
+    _ -> "large"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn clause_with_false_guard_is_skipped() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 1 {
+    1 if False -> "wibble"
+    1 if True -> "wobble"
+    _ -> "wubble"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_with_non_literal_guard_is_kept() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main(x) {
+  case 1 {
+    1 if x -> "wibble"
+    _ -> "wobble"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_binding_a_variable_is_kept() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  case 1 {
+    2 -> 0
+    n -> n + 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_a_variable_is_kept() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main(x) {
+  case x {
+    1 -> "one"
+    _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn nested_constant_cases_are_reduced() {
+    assert_optimised!(
+        reduce_constant_cases(),
+        r#"
+pub fn main() {
+  let x = case True {
+    True -> case 1 {
+      1 -> "wibble"
+      _ -> "wobble"
+    }
+    False -> "wubble"
+  }
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn constant_cases_are_not_reduced_by_default() {
+    assert_optimised!(
+        OptimisationsConfig::default(),
+        r#"
+pub fn main() {
+  case True {
+    False -> "wibble"
+    True -> "wobble"
+  }
+}
+"#
+    );
+}
+
+fn inline_pipelines() -> OptimisationsConfig {
+    OptimisationsConfig {
+        inline_pipelines: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn two_steps_pipeline_is_inlined() {
+    assert_optimised!(
+        inline_pipelines(),
+        r#"
+fn wibble(a) { a + 1 }
+fn wobble(a) { a * 2 }
+
+pub fn main() {
+  1 |> wibble |> wobble
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_into_calls_with_arguments_is_inlined() {
+    assert_optimised!(
+        inline_pipelines(),
+        r#"
+fn wibble(a, b) { a + b }
+
+pub fn main(x) {
+  x |> wibble(2) |> wibble(3, _)
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_step_is_not_inlined_before_side_effects() {
+    assert_optimised!(
+        inline_pipelines(),
+        r#"
+fn wibble(a, b) { a + b }
+fn wobble() { 2 }
+
+pub fn main(x) {
+  x |> wibble(1) |> wibble(wobble())
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_step_is_not_inlined_into_impure_function_call() {
+    assert_optimised!(
+        inline_pipelines(),
+        r#"
+fn make_adder(a) { fn(b) { a + b } }
+
+pub fn main(x) {
+  x |> make_adder(1)
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_steps_around_echo_are_not_inlined() {
+    assert_optimised!(
+        inline_pipelines(),
+        r#"
+fn wibble(a) { a + 1 }
+
+pub fn main(x) {
+  x |> wibble |> echo |> wibble |> wibble
+}
+"#
+    );
+}
+
+#[test]
+fn pipelines_are_not_inlined_by_default() {
+    assert_optimised!(
+        OptimisationsConfig::default(),
+        r#"
+fn wibble(a) { a + 1 }
+
+pub fn main() {
+  1 |> wibble |> wibble
+}
+"#
+    );
+}
+
+#[test]
+fn whole_program_keeps_public_definitions_by_default() {
+    assert_whole_program!(
+        (
+            "wibble",
+            r#"
+pub fn used() { helper() }
+
+pub fn unused() { unused_helper() }
+
+@internal
+pub fn internal() { 1 }
+
+pub const unused_constant = 1
+
+fn helper() { 1 }
+
+fn unused_helper() { 2 }
+"#
+        ),
+        (
+            "thepackage",
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.used()
+}
+"#
+        ),
+    );
+}
+
+#[test]
+fn whole_program_removes_unreachable_private_definitions() {
+    assert_whole_program!(
+        (
+            "wibble",
+            r#"
+pub fn used() { helper() }
+
+fn helper() { 1 }
+
+fn unused() { unused_helper() }
+
+fn unused_helper() { unused() }
+
+const unused_constant = 1
+"#
+        ),
+        (
+            "thepackage",
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.used()
+}
+"#
+        ),
+    );
+}
+
+#[test]
+fn whole_program_removes_definitions_unused_by_other_modules() {
+    assert_whole_program!(
+        remove_unused_public,
+        (
+            "wibble",
+            r#"
+pub fn used() { helper() }
+
+pub fn unused() { helper() }
+
+pub const unused_constant = 1
+
+fn helper() { 1 }
+"#
+        ),
+        (
+            "thepackage",
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.used()
+}
+"#
+        ),
+    );
+}
+
+#[test]
+fn whole_program_keeps_definitions_referenced_by_constants() {
+    assert_whole_program!(
+        remove_unused_public,
+        (
+            "wibble",
+            r#"
+pub fn referenced() { 1 }
+
+pub fn unreferenced() { 2 }
+
+pub const constant = referenced
+"#
+        ),
+        (
+            "thepackage",
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.constant()
+}
+"#
+        ),
+    );
+}
+
+#[test]
+fn whole_program_keeps_public_definitions_of_the_main_module() {
+    assert_whole_program!(
+        remove_unused_public,
+        (
+            "wibble",
+            r#"
+pub fn used() { 1 }
+
+pub fn unused() { 2 }
+"#
+        ),
+        (
+            "thepackage",
+            r#"
+import wibble
+
+pub fn wobble() {
+  wibble.used()
+}
+
+fn unused() { 3 }
+"#
+        ),
+    );
+}
+
+#[test]
+fn whole_program_keeps_main_functions_of_all_modules() {
+    assert_whole_program!(
+        (
+            "wibble",
+            r#"
+pub fn main() { helper() }
+
+fn helper() { 1 }
+"#
+        ),
+        ("thepackage", r#"pub fn other() { 1 }"#),
+    );
+}
@@ -0,0 +1,217 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    ast::{
+        ClauseGuard, Constant, Definition, SrcSpan, TypedClauseGuard, TypedConstant, TypedModule,
+        TypedModuleConstant,
+        visit::{self, Visit},
+    },
+    build::Origin,
+    type_::{ModuleValueConstructor, Type, ValueConstructor, ValueConstructorVariant},
+};
+use ecow::EcoString;
+
+/// A function or constant, identified by its module and name.
+type DefinitionName = (EcoString, EcoString);
+
+/// Removes all the functions and constants of the given modules that can't be
+/// reached from any of the entry points of the program:
+///
+/// - The public definitions of every module.
+/// - The `main` function of any module, as it can be run with `gleam run`.
+/// - All the definitions of the `test` and `dev` modules.
+///
+/// Erlang or JavaScript code could be calling any public function, so they
+/// are all kept unless `remove_unused_public` is set. In that case only the
+/// public definitions of the `entrypoint_module` are entry points, and a
+/// public function that is never used outside of the module it is defined in
+/// is removed as well. This is only safe on programs whose native code
+/// doesn't call back into Gleam.
+///
+pub fn remove_unreachable_definitions(
+    modules: &mut [&mut TypedModule],
+    entrypoint_module: &str,
+    remove_unused_public: bool,
+) {
+    let mut references = HashMap::new();
+    let mut reachable = HashSet::new();
+    let mut to_visit = vec![];
+
+    for module in modules.iter() {
+        let is_entrypoint_module = module.name == entrypoint_module;
+        let is_src_module = module.type_info.origin == Origin::Src;
+        for definition in &module.definitions {
+            let (name, is_public) = match definition {
+                Definition::Function(function) => match &function.name {
+                    Some((_, name)) => (name, function.publicity.is_importable()),
+                    None => continue,
+                },
+                Definition::ModuleConstant(constant) => {
+                    (&constant.name, constant.publicity.is_importable())
+                }
+                Definition::TypeAlias(_) | Definition::CustomType(_) | Definition::Import(_) => {
+                    continue;
+                }
+            };
+
+            let key = (module.name.clone(), name.clone());
+            let is_entrypoint = !is_src_module
+                || name == "main"
+                || (is_public && (is_entrypoint_module || !remove_unused_public));
+            if is_entrypoint && reachable.insert(key.clone()) {
+                to_visit.push(key.clone());
+            }
+
+            let mut finder = ReferenceFinder { found: vec![] };
+            finder.visit_typed_definition(definition);
+            _ = references.insert(key, finder.found);
+        }
+    }
+
+    while let Some(definition) = to_visit.pop() {
+        for referenced in references.get(&definition).into_iter().flatten() {
+            if reachable.insert(referenced.clone()) {
+                to_visit.push(referenced.clone());
+            }
+        }
+    }
+
+    for module in modules.iter_mut() {
+        let module_name = module.name.clone();
+        let is_reachable =
+            |name: &EcoString| reachable.contains(&(module_name.clone(), name.clone()));
+        module.definitions.retain(|definition| match definition {
+            Definition::Function(function) => match &function.name {
+                Some((_, name)) => is_reachable(name),
+                None => true,
+            },
+            Definition::ModuleConstant(constant) => is_reachable(&constant.name),
+            Definition::TypeAlias(_) | Definition::CustomType(_) | Definition::Import(_) => true,
+        });
+    }
+}
+
+/// Collects all the module functions and constants referenced by a
+/// definition.
+///
+struct ReferenceFinder {
+    found: Vec<DefinitionName>,
+}
+
+impl ReferenceFinder {
+    fn register_variant(&mut self, variant: &ValueConstructorVariant) {
+        match variant {
+            ValueConstructorVariant::ModuleFn { module, name, .. }
+            | ValueConstructorVariant::ModuleConstant { module, name, .. } => {
+                self.found.push((module.clone(), name.clone()))
+            }
+            ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::LocalConstant { .. }
+            | ValueConstructorVariant::Record { .. } => (),
+        }
+    }
+
+    fn register_constant(&mut self, constant: &TypedConstant) {
+        match constant {
+            Constant::Int { .. }
+            | Constant::Float { .. }
+            | Constant::String { .. }
+            | Constant::Invalid { .. } => (),
+
+            Constant::Var { constructor, .. } => {
+                if let Some(constructor) = constructor {
+                    self.register_variant(&constructor.variant);
+                }
+            }
+
+            Constant::Tuple { elements, .. } | Constant::List { elements, .. } => {
+                for element in elements {
+                    self.register_constant(element);
+                }
+            }
+
+            Constant::Record { args, .. } => {
+                for argument in args {
+                    self.register_constant(&argument.value);
+                }
+            }
+
+            Constant::BitArray { segments, .. } => {
+                for segment in segments {
+                    self.register_constant(&segment.value);
+                    for option in &segment.options {
+                        if let Some(value) = option.value() {
+                            self.register_constant(value);
+                        }
+                    }
+                }
+            }
+
+            Constant::StringConcatenation { left, right, .. } => {
+                self.register_constant(left);
+                self.register_constant(right);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ReferenceFinder {
+    fn visit_typed_module_constant(&mut self, constant: &'ast TypedModuleConstant) {
+        self.register_constant(&constant.value);
+    }
+
+    fn visit_typed_expr_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        _name: &'ast EcoString,
+    ) {
+        self.register_variant(&constructor.variant);
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _field_start: &'ast u32,
+        _type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        _module_alias: &'ast EcoString,
+        constructor: &'ast ModuleValueConstructor,
+    ) {
+        match constructor {
+            ModuleValueConstructor::Fn { module, name, .. } => {
+                self.found.push((module.clone(), name.clone()))
+            }
+            ModuleValueConstructor::Constant { .. } => {
+                self.found.push((module_name.clone(), label.clone()))
+            }
+            ModuleValueConstructor::Record { .. } => (),
+        }
+    }
+
+    fn visit_typed_pattern_var_usage(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _name: &'ast EcoString,
+        constructor: &'ast Option<ValueConstructor>,
+        _type_: &'ast Arc<Type>,
+    ) {
+        if let Some(constructor) = constructor {
+            self.register_variant(&constructor.variant);
+        }
+    }
+
+    fn visit_typed_clause_guard(&mut self, guard: &'ast TypedClauseGuard) {
+        match guard {
+            ClauseGuard::Constant(constant) => self.register_constant(constant),
+            ClauseGuard::ModuleSelect {
+                label, module_name, ..
+            } => self.found.push((module_name.clone(), label.clone())),
+            _ => visit::visit_typed_clause_guard(self, guard),
+        }
+    }
+}
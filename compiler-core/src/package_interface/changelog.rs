@@ -0,0 +1,211 @@
+use ecow::{EcoString, eco_format};
+use itertools::Itertools;
+
+use super::{
+    ModuleInterface, PackageInterface, ParameterInterface, TypeConstructorInterface, TypeInterface,
+};
+
+/// Lists the public definitions added to a package going from the `old` to
+/// the `new` interface, formatted as markdown changelog entries grouped by
+/// module:
+///
+/// ```markdown
+/// ### `wibble/wobble`
+///
+/// - Added the `new` function: `pub fn new(name: String) -> Wobble`.
+/// ```
+///
+/// Definitions that have been removed or changed are not included.
+///
+pub fn additions_changelog(old: &PackageInterface, new: &PackageInterface) -> String {
+    let mut changelog = String::new();
+
+    for (module_name, module) in new.modules.iter().sorted_by_key(|(name, _)| *name) {
+        let old_module = old.modules.get(module_name);
+        let mut entries = vec![];
+        if old_module.is_none() {
+            entries.push(eco_format!("Added the `{module_name}` module."));
+        }
+        entries.extend(module_additions(module_name, old_module, module));
+
+        if entries.is_empty() {
+            continue;
+        }
+
+        if !changelog.is_empty() {
+            changelog.push('\n');
+        }
+        changelog.push_str(&format!("### `{module_name}`\n\n"));
+        for entry in entries {
+            changelog.push_str("- ");
+            changelog.push_str(&entry);
+            changelog.push('\n');
+        }
+    }
+
+    changelog
+}
+
+fn module_additions(
+    module_name: &EcoString,
+    old: Option<&ModuleInterface>,
+    new: &ModuleInterface,
+) -> Vec<EcoString> {
+    let printer = Printer { module_name };
+    let mut entries = vec![];
+
+    for (name, type_) in new.types.iter().sorted_by_key(|(name, _)| *name) {
+        let old_type = old.and_then(|old| old.types.get(name));
+        if old_type.is_none() {
+            let parameters = printer.type_parameters(type_.parameters);
+            entries.push(eco_format!(
+                "Added the `{name}` type: `pub type {name}{parameters}`."
+            ));
+        }
+
+        for constructor in &type_.constructors {
+            let is_new = old_type.is_none_or(|old_type| {
+                old_type
+                    .constructors
+                    .iter()
+                    .all(|old| old.name != constructor.name)
+            });
+            if is_new {
+                entries.push(eco_format!(
+                    "Added the `{}` constructor to the `{name}` type: `{}`.",
+                    constructor.name,
+                    printer.constructor(constructor)
+                ));
+            }
+        }
+    }
+
+    for (name, alias) in new.type_aliases.iter().sorted_by_key(|(name, _)| *name) {
+        if old.is_none_or(|old| !old.type_aliases.contains_key(name)) {
+            entries.push(eco_format!(
+                "Added the `{name}` type alias: `pub type {name}{} = {}`.",
+                printer.type_parameters(alias.parameters),
+                printer.type_(&alias.alias)
+            ));
+        }
+    }
+
+    for (name, constant) in new.constants.iter().sorted_by_key(|(name, _)| *name) {
+        if old.is_none_or(|old| !old.constants.contains_key(name)) {
+            entries.push(eco_format!(
+                "Added the `{name}` constant: `pub const {name}: {}`.",
+                printer.type_(&constant.type_)
+            ));
+        }
+    }
+
+    for (name, function) in new.functions.iter().sorted_by_key(|(name, _)| *name) {
+        if old.is_none_or(|old| !old.functions.contains_key(name)) {
+            entries.push(eco_format!(
+                "Added the `{name}` function: `pub fn {name}({}) -> {}`.",
+                printer.parameters(&function.parameters),
+                printer.type_(&function.return_)
+            ));
+        }
+    }
+
+    entries
+}
+
+/// Prints types the way they would be written in the module they are defined
+/// in: types coming from other modules are qualified with the last segment of
+/// the module's name.
+///
+struct Printer<'a> {
+    module_name: &'a EcoString,
+}
+
+impl Printer<'_> {
+    fn type_(&self, type_: &TypeInterface) -> EcoString {
+        match type_ {
+            TypeInterface::Tuple { elements } => {
+                eco_format!("#({})", elements.iter().map(|e| self.type_(e)).join(", "))
+            }
+
+            TypeInterface::Fn {
+                parameters,
+                return_,
+            } => eco_format!(
+                "fn({}) -> {}",
+                parameters.iter().map(|p| self.type_(p)).join(", "),
+                self.type_(return_)
+            ),
+
+            TypeInterface::Variable { id } => type_variable_name(*id),
+
+            TypeInterface::Named {
+                name,
+                module,
+                parameters,
+                ..
+            } => {
+                let name = if module == self.module_name || module == "gleam" {
+                    name.clone()
+                } else {
+                    let qualifier = module.split('/').next_back().unwrap_or(module);
+                    eco_format!("{qualifier}.{name}")
+                };
+                if parameters.is_empty() {
+                    name
+                } else {
+                    let parameters = parameters.iter().map(|p| self.type_(p)).join(", ");
+                    eco_format!("{name}({parameters})")
+                }
+            }
+        }
+    }
+
+    fn parameters(&self, parameters: &[ParameterInterface]) -> EcoString {
+        parameters
+            .iter()
+            .map(|parameter| match &parameter.label {
+                Some(label) => eco_format!("{label}: {}", self.type_(&parameter.type_)),
+                None => self.type_(&parameter.type_),
+            })
+            .join(", ")
+            .into()
+    }
+
+    fn constructor(&self, constructor: &TypeConstructorInterface) -> EcoString {
+        if constructor.parameters.is_empty() {
+            constructor.name.clone()
+        } else {
+            eco_format!(
+                "{}({})",
+                constructor.name,
+                self.parameters(&constructor.parameters)
+            )
+        }
+    }
+
+    fn type_parameters(&self, count: usize) -> EcoString {
+        if count == 0 {
+            EcoString::new()
+        } else {
+            let parameters = (0..count as u64).map(type_variable_name).join(", ");
+            eco_format!("({parameters})")
+        }
+    }
+}
+
+/// Turns the id of a type variable in a package interface into a name: `a`,
+/// `b`, ..., `z`, `aa`, `ab`, ...
+///
+fn type_variable_name(id: u64) -> EcoString {
+    let mut chars = vec![];
+    let mut rest = id;
+    loop {
+        chars.push((b'a' + (rest % 26) as u8) as char);
+        rest /= 26;
+        if rest == 0 {
+            break;
+        }
+        rest -= 1;
+    }
+    chars.into_iter().rev().collect()
+}
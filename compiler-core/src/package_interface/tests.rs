@@ -8,7 +8,10 @@ use hexpm::version::Identifier;
 use crate::{
     analyse::TargetSupport,
     build::{Module, Origin, Package, Target},
-    config::{Docs, ErlangConfig, JavaScriptConfig, PackageConfig, Repository},
+    config::{
+        Docs, ErlangConfig, JavaScriptConfig, LintsConfig, OptimisationsConfig, PackageConfig,
+        Repository,
+    },
     line_numbers::LineNumbers,
     type_::PRELUDE_MODULE_NAME,
     uid::UniqueIdGenerator,
@@ -176,6 +179,10 @@ fn package_from_module(module: Module) -> Package {
                     .build()
                     .expect("internals glob"),
             ]),
+            optimisations: OptimisationsConfig::default(),
+            lints: LintsConfig::default(),
+            features: vec![],
+            panic_handler: None,
         },
         cached_module_names: Vec::new(),
         modules: vec![module],
@@ -337,3 +344,75 @@ pub type Wibble {
 "#
     );
 }
+
+fn changelog(old: (&str, &str), new: (&str, &str)) -> String {
+    let old = compile_package(Some(old.0), old.1, None);
+    let new = compile_package(Some(new.0), new.1, None);
+    let old: PackageInterface = serde_json::from_str(&old).expect("old interface");
+    let new: PackageInterface = serde_json::from_str(&new).expect("new interface");
+    super::changelog::additions_changelog(&old, &new)
+}
+
+#[test]
+pub fn changelog_of_additions() {
+    let old = "
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub type Ints = List(Int)
+
+pub fn keep() -> Nil { Nil }
+
+pub fn changed(a: Int) -> Int { a }
+
+pub fn removed() -> Nil { Nil }
+";
+    let new = "
+pub type Wibble {
+  Wibble(Int)
+  Wobble(wobble: String, result: Result(Box(Int), Nil))
+}
+
+pub type Box(a) {
+  Box(value: a)
+}
+
+pub opaque type Secret {
+  Secret(String)
+}
+
+pub type Ints = List(Int)
+
+pub type Pairs(a, b) = List(#(a, b))
+
+pub const default_box: Box(Int) = Box(1)
+
+pub fn keep() -> Nil { Nil }
+
+pub fn changed(a: Int, b: Int) -> Int { a + b }
+
+pub fn map(box: Box(a), with fun: fn(a) -> b) -> Box(b) {
+  Box(fun(box.value))
+}
+
+fn private() -> Nil { Nil }
+";
+    insta::assert_snapshot!(changelog(("wibble", old), ("wibble", new)));
+}
+
+#[test]
+pub fn changelog_of_new_module() {
+    let new = "
+pub type Wibble
+
+pub fn wibble() -> Nil { Nil }
+";
+    insta::assert_snapshot!(changelog(("wibble", ""), ("wobble", new)));
+}
+
+#[test]
+pub fn changelog_with_no_additions() {
+    let src = "pub fn wibble() -> Nil { Nil }";
+    assert_eq!(changelog(("wibble", src), ("wibble", src)), "");
+}
@@ -57,21 +57,23 @@ mod token;
 use crate::Warning;
 use crate::analyse::Inferred;
 use crate::ast::{
-    Arg, ArgNames, AssignName, Assignment, AssignmentKind, BinOp, BitArrayOption, BitArraySegment,
-    CAPTURE_VARIABLE, CallArg, Clause, ClauseGuard, Constant, CustomType, Definition, Function,
+    Arg, ArgNames, Assert, AssignName, Assignment, AssignmentKind, BinOp, BitArrayOption,
+    BitArraySegment, CAPTURE_VARIABLE, CallArg, Capability, Clause, ClauseGuard,
+    ComprehensionQualifier, Constant, CustomType, Definition, ExternalArguments, Function,
     FunctionLiteralKind, HasLocation, Import, Module, ModuleConstant, Pattern, Publicity,
-    RecordBeingUpdated, RecordConstructor, RecordConstructorArg, SrcSpan, Statement,
+    RecordBeingUpdated, RecordConstructor, RecordConstructorArg, ShouldPanic, SrcSpan, Statement,
     TargetedDefinition, TodoKind, TypeAlias, TypeAst, TypeAstConstructor, TypeAstFn, TypeAstHole,
     TypeAstTuple, TypeAstVar, UnqualifiedImport, UntypedArg, UntypedClause, UntypedClauseGuard,
     UntypedConstant, UntypedDefinition, UntypedExpr, UntypedModule, UntypedPattern,
     UntypedRecordUpdateArg, UntypedStatement, UntypedUseAssignment, Use, UseAssignment,
+    with_doc_attribute,
 };
 use crate::build::Target;
 use crate::error::wrap;
 use crate::parse::extra::ModuleExtra;
-use crate::type_::Deprecation;
 use crate::type_::error::VariableOrigin;
 use crate::type_::expression::Implementations;
+use crate::type_::{Deprecation, Purity};
 use crate::warning::{DeprecatedSyntaxWarning, WarningEmitter};
 use camino::Utf8PathBuf;
 use ecow::EcoString;
@@ -121,19 +123,37 @@ struct Attributes {
     deprecated: Deprecation,
     external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
+    external_erlang_arguments: Option<ExternalArguments>,
+    external_javascript_arguments: Option<ExternalArguments>,
     internal: InternalAttribute,
+    purity: Purity,
+    feature: Option<EcoString>,
+    benchmark: bool,
+    memoize: bool,
+    should_panic: Option<ShouldPanic>,
+    capabilities: Vec<(Capability, SrcSpan)>,
+    since: Option<(EcoString, SrcSpan)>,
+    doc: Option<(Vec<EcoString>, SrcSpan)>,
 }
 
 impl Attributes {
     fn has_function_only(&self) -> bool {
-        self.external_erlang.is_some() || self.external_javascript.is_some()
+        self.external_erlang.is_some()
+            || self.external_javascript.is_some()
+            || self.purity.is_pure()
+            || self.benchmark
+            || self.memoize
+            || self.should_panic.is_some()
     }
 
-    fn has_external_for(&self, target: Target) -> bool {
-        match target {
-            Target::Erlang => self.external_erlang.is_some(),
-            Target::JavaScript => self.external_javascript.is_some(),
-        }
+    /// The location of the `@external` attribute given for a target, if
+    /// there is one.
+    fn external_location_for(&self, target: Target) -> Option<SrcSpan> {
+        let external = match target {
+            Target::Erlang => &self.external_erlang,
+            Target::JavaScript => &self.external_javascript,
+        };
+        external.as_ref().map(|(_, _, location)| *location)
     }
 
     fn set_external_for(&mut self, target: Target, ext: Option<(EcoString, EcoString, SrcSpan)>) {
@@ -142,6 +162,13 @@ impl Attributes {
             Target::JavaScript => self.external_javascript = ext,
         }
     }
+
+    fn set_external_arguments_for(&mut self, target: Target, arguments: Option<ExternalArguments>) {
+        match target {
+            Target::Erlang => self.external_erlang_arguments = arguments,
+            Target::JavaScript => self.external_javascript_arguments = arguments,
+        }
+    }
 }
 
 //
@@ -172,10 +199,9 @@ pub fn parse_module(
     Ok(parsed)
 }
 
-//
-// Test Interface
-//
-#[cfg(test)]
+/// Parse a sequence of statements, such as the body of a function, that makes
+/// up the whole of `src`.
+///
 pub fn parse_statement_sequence(src: &str) -> Result<Vec1<UntypedStatement>, ParseError> {
     let lex = lexer::make_tokenizer(src);
     let mut parser = Parser::new(lex);
@@ -212,8 +238,17 @@ pub struct Parser<T: Iterator<Item = LexResult>> {
     warnings: Vec<DeprecatedSyntaxWarning>,
     tok0: Option<Spanned>,
     tok1: Option<Spanned>,
+    /// The token following `tok1`. This is only read from the token stream
+    /// when needed by `peek_tok2`, as almost all of the parser only needs two
+    /// tokens of lookahead.
+    tok2: Option<Spanned>,
+    tok2_peeked: bool,
     extra: ModuleExtra,
     doc_comments: VecDeque<(u32, EcoString)>,
+    /// Whether a call can be followed by a trailing block, like
+    /// `list.map(items) { x -> x + 1 }`. This is not allowed when parsing the
+    /// subjects of a case expression, as there the `{` would be ambiguous.
+    trailing_blocks_allowed: bool,
 }
 impl<T> Parser<T>
 where
@@ -226,8 +261,11 @@ where
             warnings: vec![],
             tok0: None,
             tok1: None,
+            tok2: None,
+            tok2_peeked: false,
             extra: ModuleExtra::new(),
             doc_comments: VecDeque::new(),
+            trailing_blocks_allowed: true,
         };
         parser.advance();
         parser.advance();
@@ -310,12 +348,12 @@ where
             // Module Constants
             (Some((start, Token::Const, _)), _) => {
                 self.advance();
-                self.parse_module_const(start, false, &attributes)
+                self.parse_module_const(start, false, &mut attributes)
             }
             (Some((start, Token::Pub, _)), Some((_, Token::Const, _))) => {
                 self.advance();
                 self.advance();
-                self.parse_module_const(start, true, &attributes)
+                self.parse_module_const(start, true, &mut attributes)
             }
 
             // Function
@@ -352,15 +390,39 @@ where
             }
         }?;
 
+        // Capabilities can only be implemented by custom types, which take
+        // them out of the attributes when they're parsed.
+        if let (Some((_, location)), Some(_)) = (attributes.capabilities.first(), &def) {
+            return parse_error(ParseErrorType::ExpectedCustomTypeDefinition, *location);
+        }
+
+        // Only functions and constants can say which version they were added in.
+        if let (
+            Some((_, location)),
+            Some(Definition::CustomType(_) | Definition::TypeAlias(_) | Definition::Import(_)),
+        ) = (&attributes.since, &def)
+        {
+            return parse_error(ParseErrorType::ExpectedValueDefinition, *location);
+        }
+
+        // Functions, constants, and types take the `@doc` attribute out of the
+        // attributes when they're parsed, so the only definitions left with
+        // one are the ones that can't be documented.
+        if let (Some((_, location)), Some(_)) = (&attributes.doc, &def) {
+            return parse_error(ParseErrorType::ExpectedDocumentedDefinition, *location);
+        }
+
         match (def, location) {
             (Some(definition), _) if definition.is_function() => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                feature: attributes.feature,
             })),
 
             (Some(definition), None) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                feature: attributes.feature,
             })),
 
             (_, Some(location)) if attributes.has_function_only() => {
@@ -370,6 +432,7 @@ where
             (Some(definition), _) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                feature: attributes.feature,
             })),
 
             (_, Some(location)) => parse_error(ParseErrorType::ExpectedDefinition, location),
@@ -523,6 +586,23 @@ where
                 }
             }
 
+            // A discard used as a function is much more likely to be a
+            // function whose name starts with an underscore than a hole.
+            Some((start, Token::DiscardName { .. }, end))
+                if matches!(self.tok1, Some((_, Token::LeftParen, _))) =>
+            {
+                return parse_error(ParseErrorType::IncorrectName, SrcSpan { start, end });
+            }
+
+            // Typed hole
+            Some((start, Token::DiscardName { name }, end)) => {
+                self.advance();
+                UntypedExpr::Hole {
+                    location: SrcSpan { start, end },
+                    name,
+                }
+            }
+
             Some((start, Token::Todo, mut end)) => {
                 self.advance();
                 let mut message = None;
@@ -580,8 +660,9 @@ where
             Some((start, Token::Hash, _)) => {
                 self.advance();
                 let _ = self.expect_one(&Token::LeftParen)?;
-                let elements =
-                    Parser::series_of(self, &Parser::parse_expression, Some(&Token::Comma))?;
+                let elements = self.with_trailing_blocks(true, |this| {
+                    Parser::series_of(this, &Parser::parse_expression, Some(&Token::Comma))
+                })?;
                 let (_, end) =
                     self.expect_one_following_series(&Token::RightParen, "an expression")?;
                 UntypedExpr::Tuple {
@@ -593,10 +674,23 @@ where
             // list
             Some((start, Token::LeftSquare, _)) => {
                 self.advance();
-                let (elements, elements_end_with_comma) = self.series_of_has_trailing_separator(
-                    &Parser::parse_expression,
-                    Some(&Token::Comma),
-                )?;
+                let (elements, elements_end_with_comma) =
+                    self.with_trailing_blocks(true, |this| {
+                        this.series_of_has_trailing_separator(
+                            &Parser::parse_expression,
+                            Some(&Token::Comma),
+                        )
+                    })?;
+
+                // A single element followed by a `|` is a list comprehension,
+                // like `[x * 2 | x <- items]`
+                if elements.len() == 1
+                    && !elements_end_with_comma
+                    && self.maybe_one(&Token::Vbar).is_some()
+                {
+                    let element = elements.into_iter().next().expect("one element");
+                    return self.parse_list_comprehension(start, element);
+                }
 
                 // Parse an optional tail
                 let mut tail = None;
@@ -728,8 +822,9 @@ where
             // case
             Some((start, Token::Case, case_e)) => {
                 self.advance();
-                let subjects =
-                    Parser::series_of(self, &Parser::parse_expression, Some(&Token::Comma))?;
+                let subjects = self.with_trailing_blocks(false, |this| {
+                    Parser::series_of(this, &Parser::parse_expression, Some(&Token::Comma))
+                })?;
                 if self.maybe_one(&Token::LeftBrace).is_some() {
                     let clauses = Parser::series_of(self, &Parser::parse_case_clause, None)?;
                     let (_, end) =
@@ -896,6 +991,9 @@ where
                         }
                     }
                 }
+                _ if self.is_type_application(&expr) => {
+                    expr = self.parse_type_application(expr)?;
+                }
                 _ => {
                     if self.maybe_one(&Token::LeftParen).is_some() {
                         let start = expr.location().start;
@@ -930,8 +1028,17 @@ where
                             }
                             _ => {
                                 // Call
-                                let args = self.parse_fn_args()?;
-                                let (_, end) = self.expect_one(&Token::RightParen)?;
+                                let mut args = self.parse_fn_args()?;
+                                let (_, mut end) = self.expect_one(&Token::RightParen)?;
+                                if let Some(trailing_block) = self.parse_trailing_block()? {
+                                    end = trailing_block.location().end;
+                                    args.push(ParserArg::Arg(Box::new(CallArg {
+                                        implicit: None,
+                                        label: None,
+                                        location: trailing_block.location(),
+                                        value: trailing_block,
+                                    })));
+                                }
                                 expr = make_call(expr, args, start, end)?;
                             }
                         }
@@ -946,6 +1053,119 @@ where
         Ok(Some(expr))
     }
 
+    // Explicit type arguments can only be given to a value referenced by name.
+    // So that they can't be confused with a comparison, the `<` must come right
+    // after the name and be followed by a type starting with an uppercase name,
+    // a tuple or a function, none of which could be compared with `<`.
+    //
+    // examples:
+    //   list.new<Int>()
+    //   identity<#(Int, String)>
+    fn is_type_application(&self, expr: &UntypedExpr) -> bool {
+        let is_reference = match expr {
+            UntypedExpr::Var { .. } => true,
+            UntypedExpr::FieldAccess { container, .. } => {
+                matches!(container.as_ref(), UntypedExpr::Var { .. })
+            }
+            _ => false,
+        };
+        match (&self.tok0, &self.tok1) {
+            (
+                Some((less_start, Token::Less, less_end)),
+                Some((type_start, Token::UpName { .. } | Token::Hash | Token::Fn, _)),
+            ) => is_reference && *less_start == expr.location().end && type_start == less_end,
+            _ => false,
+        }
+    }
+
+    fn parse_type_application(&mut self, fun: UntypedExpr) -> Result<UntypedExpr, ParseError> {
+        let _ = self.expect_one(&Token::Less)?;
+        let type_arguments = self.parse_types()?;
+        let (_, end) = self.expect_one(&Token::Greater)?;
+        Ok(UntypedExpr::TypeApplication {
+            location: SrcSpan {
+                start: fun.location().start,
+                end,
+            },
+            fun: Box::new(fun),
+            type_arguments,
+        })
+    }
+
+    // A block trailing a call, which is sugar for an anonymous function passed
+    // as the last argument of the call. It's only parsed if the block starts
+    // with its arguments followed by an arrow, so that it can't be confused
+    // with a regular block expression following a call.
+    //
+    // examples:
+    //   list.map(items) { x -> x + 1 }
+    //   list.fold(items, 0) { acc, x -> acc + x }
+    //   lazy() { -> 1 }
+    fn parse_trailing_block(&mut self) -> Result<Option<UntypedExpr>, ParseError> {
+        if !self.trailing_blocks_allowed {
+            return Ok(None);
+        }
+        let is_trailing_block = match (&self.tok0, &self.tok1) {
+            (Some((_, Token::LeftBrace, _)), Some((_, Token::RArrow, _))) => true,
+            (
+                Some((_, Token::LeftBrace, _)),
+                Some((_, Token::Name { .. } | Token::DiscardName { .. }, _)),
+            ) => matches!(
+                self.peek_tok2(),
+                Some(Token::Comma | Token::Colon | Token::RArrow)
+            ),
+            _ => false,
+        };
+        if !is_trailing_block {
+            return Ok(None);
+        }
+
+        let (start, _) = self.expect_one(&Token::LeftBrace)?;
+        let arguments = Parser::series_of(
+            self,
+            &|parser| Parser::parse_fn_param(parser, true),
+            Some(&Token::Comma),
+        )?;
+        let (_, arrow_end) =
+            self.expect_one_following_series(&Token::RArrow, "a function parameter")?;
+        let head = SrcSpan::new(start, arrow_end);
+        let body = self.parse_statement_seq()?;
+        let (_, end) = self.expect_one(&Token::RightBrace)?;
+        let location = SrcSpan::new(start, end);
+        let body = match body {
+            Some((body, _)) => body,
+            None => vec1![Statement::Expression(UntypedExpr::Todo {
+                kind: TodoKind::EmptyFunction {
+                    function_location: head,
+                },
+                location: SrcSpan::new(arrow_end, end - 1),
+                message: None
+            })],
+        };
+
+        Ok(Some(UntypedExpr::Fn {
+            location,
+            end_of_head_byte_index: arrow_end,
+            kind: FunctionLiteralKind::TrailingBlock { head },
+            arguments,
+            body,
+            return_annotation: None,
+        }))
+    }
+
+    // Runs the given parsing function allowing or disallowing trailing blocks
+    // after calls, restoring the previous setting afterwards.
+    fn with_trailing_blocks<A>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> Result<A, ParseError>,
+    ) -> Result<A, ParseError> {
+        let previous = std::mem::replace(&mut self.trailing_blocks_allowed, allowed);
+        let result = parse(self);
+        self.trailing_blocks_allowed = previous;
+        result
+    }
+
     // A `use` expression
     // use <- function
     // use <- function()
@@ -964,13 +1184,9 @@ where
         _ = self.expect_one_following_series(&Token::LArrow, "a use variable assignment")?;
         let call = self.expect_expression()?;
 
-        let assignments_location = match (assignments.first(), assignments.last()) {
-            (Some(first), Some(last)) => SrcSpan {
-                start: first.location.start,
-                end: last.location.end,
-            },
-            (_, _) => SrcSpan { start, end },
-        };
+        let assignments_location =
+            SrcSpan::union_all(assignments.iter().map(|assignment| assignment.location))
+                .unwrap_or(SrcSpan { start, end });
 
         Ok(Statement::Use(Use {
             location: SrcSpan::new(start, call.location().end),
@@ -1002,6 +1218,65 @@ where
         }))
     }
 
+    // A list comprehension, with everything up to the `|` already consumed
+    // [x * 2 | x <- items]
+    // [x * 2 | x <- items, x > 0]
+    // [#(x, y) | x <- xs, y <- ys, x != y]
+    fn parse_list_comprehension(
+        &mut self,
+        start: u32,
+        element: UntypedExpr,
+    ) -> Result<Option<UntypedExpr>, ParseError> {
+        let qualifiers = self.with_trailing_blocks(true, |this| {
+            this.series_of(&Parser::parse_comprehension_qualifier, Some(&Token::Comma))
+        })?;
+        let (_, end) = self.expect_one(&Token::RightSquare)?;
+
+        match qualifiers.first() {
+            Some(ComprehensionQualifier::Generator { .. }) => (),
+            Some(ComprehensionQualifier::Filter(_)) | None => {
+                return parse_error(
+                    ParseErrorType::ListComprehensionWithoutGenerator,
+                    SrcSpan { start, end },
+                );
+            }
+        }
+
+        Ok(Some(UntypedExpr::ListComprehension {
+            location: SrcSpan { start, end },
+            element: Box::new(element),
+            qualifiers,
+        }))
+    }
+
+    // A single qualifier of a list comprehension, either a generator or a filter
+    // x <- items
+    // x > 0
+    fn parse_comprehension_qualifier(
+        &mut self,
+    ) -> Result<Option<ComprehensionQualifier>, ParseError> {
+        match (self.tok0.take(), &self.tok1) {
+            (
+                Some((start, Token::Name { name } | Token::DiscardName { name }, end)),
+                Some((_, Token::LArrow, _)),
+            ) => {
+                self.advance();
+                self.advance();
+                let list = self.expect_expression()?;
+                Ok(Some(ComprehensionQualifier::Generator {
+                    location: SrcSpan::new(start, list.location().end),
+                    name,
+                    name_location: SrcSpan { start, end },
+                    list,
+                }))
+            }
+            (t0, _) => {
+                self.tok0 = t0;
+                Ok(self.parse_expression()?.map(ComprehensionQualifier::Filter))
+            }
+        }
+    }
+
     // An assignment, with `Let` already consumed
     fn parse_assignment(&mut self, start: u32) -> Result<UntypedStatement, ParseError> {
         let mut kind = match self.tok0 {
@@ -1067,6 +1342,19 @@ where
         }))
     }
 
+    // An assert statement, with `Assert` already consumed
+    fn parse_assert(&mut self, start: u32, end: u32) -> Result<UntypedStatement, ParseError> {
+        let value = self.parse_expression()?.ok_or(ParseError {
+            error: ParseErrorType::ExpectedValue,
+            location: SrcSpan { start, end },
+        })?;
+
+        Ok(Statement::Assert(Assert {
+            location: SrcSpan::new(start, value.location().end),
+            value,
+        }))
+    }
+
     // examples:
     //   expr
     //   expr expr..
@@ -1075,6 +1363,10 @@ where
     //   assignment expr..
     //   assignment assignment..
     fn parse_statement_seq(&mut self) -> Result<Option<(Vec1<UntypedStatement>, u32)>, ParseError> {
+        self.with_trailing_blocks(true, Parser::parse_statements)
+    }
+
+    fn parse_statements(&mut self) -> Result<Option<(Vec1<UntypedStatement>, u32)>, ParseError> {
         let mut statements = vec![];
         let mut start = None;
         let mut end = 0;
@@ -1106,6 +1398,11 @@ where
                 Ok(Some(self.parse_assignment(start)?))
             }
 
+            Some((start, Token::Assert, end)) => {
+                self.advance();
+                Ok(Some(self.parse_assert(start, end)?))
+            }
+
             token => {
                 self.tok0 = token;
                 self.parse_statement_errors()?;
@@ -1157,20 +1454,31 @@ where
                 }
 
                 if self.maybe_one(&Token::Dot).is_some() {
-                    // We're doing this to get a better error message instead of a generic
-                    // `I was expecting a type`, you can have a look at this issue to get
-                    // a better idea: https://github.com/gleam-lang/gleam/issues/2841.
-                    match self.expect_constructor_pattern(Some((start, name, end))) {
-                        Ok(result) => result,
-                        Err(ParseError {
-                            location: SrcSpan { end, .. },
-                            ..
-                        }) => {
-                            return parse_error(
-                                ParseErrorType::InvalidModuleTypePattern,
-                                SrcSpan { start, end },
-                            );
-                        }
+                    match self.maybe_name() {
+                        // A lowercase name after the dot is a constant from
+                        // another module: `numbers.zero`.
+                        Some((_, constant, constant_end)) => Pattern::Constant {
+                            location: SrcSpan::new(start, constant_end),
+                            module: (name, SrcSpan::new(start, end)),
+                            name: constant,
+                            type_: (),
+                        },
+
+                        // We're doing this to get a better error message instead of a generic
+                        // `I was expecting a type`, you can have a look at this issue to get
+                        // a better idea: https://github.com/gleam-lang/gleam/issues/2841.
+                        None => match self.expect_constructor_pattern(Some((start, name, end))) {
+                            Ok(result) => result,
+                            Err(ParseError {
+                                location: SrcSpan { end, .. },
+                                ..
+                            }) => {
+                                return parse_error(
+                                    ParseErrorType::InvalidModuleTypePattern,
+                                    SrcSpan { start, end },
+                                );
+                            }
+                        },
                     }
                 } else {
                     match name.as_str() {
@@ -1883,12 +2191,14 @@ where
     //   a:
     fn parse_record_update_arg(&mut self) -> Result<Option<UntypedRecordUpdateArg>, ParseError> {
         match self.maybe_name() {
-            Some((start, label, _)) => {
+            Some((start, label, label_end)) => {
+                let label_location = SrcSpan::new(start, label_end);
                 let (_, end) = self.expect_one(&Token::Colon)?;
                 let value = self.parse_expression()?;
                 match value {
                     Some(value) => Ok(Some(UntypedRecordUpdateArg {
                         label,
+                        label_location,
                         location: SrcSpan {
                             start,
                             end: value.location().end,
@@ -1899,6 +2209,7 @@ where
                         // Argument supplied with a label shorthand.
                         Ok(Some(UntypedRecordUpdateArg {
                             label: label.clone(),
+                            label_location,
                             location: SrcSpan { start, end },
                             value: UntypedExpr::Var {
                                 name: label,
@@ -1928,10 +2239,10 @@ where
         is_anon: bool,
         attributes: &mut Attributes,
     ) -> Result<Option<UntypedDefinition>, ParseError> {
-        let documentation = if is_anon {
-            None
+        let (documentation, doc_attribute) = if is_anon {
+            (None, vec![])
         } else {
-            self.take_documentation(start)
+            self.take_definition_documentation(start, attributes)
         };
         let mut name = None;
         if !is_anon {
@@ -2009,6 +2320,11 @@ where
             deprecation: std::mem::take(&mut attributes.deprecated),
             external_erlang: attributes.external_erlang.take(),
             external_javascript: attributes.external_javascript.take(),
+            external_erlang_arguments: attributes.external_erlang_arguments.take().map(Box::new),
+            external_javascript_arguments: attributes
+                .external_javascript_arguments
+                .take()
+                .map(Box::new),
             implementations: Implementations {
                 gleam: true,
                 can_run_on_erlang: true,
@@ -2016,6 +2332,12 @@ where
                 uses_erlang_externals: false,
                 uses_javascript_externals: false,
             },
+            purity: std::mem::take(&mut attributes.purity),
+            benchmark: std::mem::take(&mut attributes.benchmark),
+            memoize: std::mem::take(&mut attributes.memoize),
+            should_panic: attributes.should_panic.take(),
+            since: attributes.since.take().map(|(version, _)| version),
+            doc_attribute,
         })))
     }
 
@@ -2171,8 +2493,9 @@ where
     //   a: _, expr
     //   a: expr, _, b: _
     fn parse_fn_args(&mut self) -> Result<Vec<ParserArg>, ParseError> {
-        let args = Parser::series_of(self, &Parser::parse_fn_arg, Some(&Token::Comma))?;
-        Ok(args)
+        self.with_trailing_blocks(true, |this| {
+            Parser::series_of(this, &Parser::parse_fn_arg, Some(&Token::Comma))
+        })
     }
 
     // Parse a single function call arg
@@ -2197,6 +2520,25 @@ where
         };
 
         match self.parse_expression()? {
+            // A lone `_` argument turns the call into a function capture,
+            // any other discard name is a typed hole.
+            Some(UntypedExpr::Hole {
+                location: discard_location,
+                name,
+            }) if name == "_" => {
+                let arg_location = match &label {
+                    Some((label_start, _, _)) => SrcSpan {
+                        start: *label_start,
+                        end: discard_location.end,
+                    },
+                    None => discard_location,
+                };
+                Ok(Some(ParserArg::Hole {
+                    label: label.map(|(_, label, _)| label),
+                    arg_location,
+                    discard_location,
+                }))
+            }
             Some(value) => {
                 let arg = match label {
                     Some((start, label, _)) => CallArg {
@@ -2218,54 +2560,20 @@ where
                 Ok(Some(ParserArg::Arg(Box::new(arg))))
             }
             _ => {
-                match self.maybe_discard_name() {
-                    Some((name_start, name, name_end)) => {
-                        let arg = match label {
-                            Some((label_start, label, _)) => ParserArg::Hole {
-                                label: Some(label),
-                                arg_location: SrcSpan {
-                                    start: label_start,
-                                    end: name_end,
-                                },
-                                discard_location: SrcSpan {
-                                    start: name_start,
-                                    end: name_end,
-                                },
-                                name,
-                            },
-                            _ => ParserArg::Hole {
-                                label: None,
-                                arg_location: SrcSpan {
-                                    start: name_start,
-                                    end: name_end,
-                                },
-                                discard_location: SrcSpan {
-                                    start: name_start,
-                                    end: name_end,
-                                },
-                                name,
+                match label {
+                    Some((start, label, end)) => {
+                        // Argument supplied with a label shorthand.
+                        Ok(Some(ParserArg::Arg(Box::new(CallArg {
+                            implicit: None,
+                            label: Some(label.clone()),
+                            location: SrcSpan { start, end },
+                            value: UntypedExpr::Var {
+                                name: label,
+                                location: SrcSpan { start, end },
                             },
-                        };
-
-                        Ok(Some(arg))
-                    }
-                    _ => {
-                        match label {
-                            Some((start, label, end)) => {
-                                // Argument supplied with a label shorthand.
-                                Ok(Some(ParserArg::Arg(Box::new(CallArg {
-                                    implicit: None,
-                                    label: Some(label.clone()),
-                                    location: SrcSpan { start, end },
-                                    value: UntypedExpr::Var {
-                                        name: label,
-                                        location: SrcSpan { start, end },
-                                    },
-                                }))))
-                            }
-                            _ => Ok(None),
-                        }
+                        }))))
                     }
+                    _ => Ok(None),
                 }
             }
         }
@@ -2287,7 +2595,7 @@ where
         opaque: bool,
         attributes: &mut Attributes,
     ) -> Result<Option<UntypedDefinition>, ParseError> {
-        let documentation = self.take_documentation(start);
+        let (documentation, doc_attribute) = self.take_definition_documentation(start, attributes);
         let (name_start, name, parameters, end, name_end) = self.expect_type_name()?;
         let name_location = SrcSpan::new(name_start, name_end);
         let (constructors, end_position) = if self.maybe_one(&Token::LeftBrace).is_some() {
@@ -2305,6 +2613,14 @@ where
                             || attributes.external_javascript.is_some()
                             || attributes.target.is_some()
                             || attributes.internal != InternalAttribute::Missing
+                            || attributes.purity.is_pure()
+                            || attributes.feature.is_some()
+                            || attributes.benchmark
+                            || attributes.memoize
+                            || attributes.should_panic.is_some()
+                            || attributes.since.is_some()
+                            || attributes.doc.is_some()
+                            || !attributes.capabilities.is_empty()
                         {
                             return parse_error(
                                 ParseErrorType::UnknownAttributeRecordVariant,
@@ -2362,6 +2678,7 @@ where
                                 type_ast: t,
                                 type_: (),
                                 deprecation: std::mem::take(&mut attributes.deprecated),
+                                doc_attribute,
                             })));
                         }
                         _ => {
@@ -2388,6 +2705,8 @@ where
             constructors,
             typed_parameters: vec![],
             deprecation: std::mem::take(&mut attributes.deprecated),
+            capabilities: std::mem::take(&mut attributes.capabilities),
+            doc_attribute,
         })))
     }
 
@@ -2685,12 +3004,14 @@ where
         // Gather imports
         let mut unqualified_values = vec![];
         let mut unqualified_types = vec![];
+        let mut unqualified_types_glob = None;
 
         if self.maybe_one(&Token::Dot).is_some() {
             let _ = self.expect_one(&Token::LeftBrace)?;
             let parsed = self.parse_unqualified_imports()?;
             unqualified_types = parsed.types;
             unqualified_values = parsed.values;
+            unqualified_types_glob = parsed.types_glob;
             let (_, e) = self.expect_one(&Token::RightBrace)?;
             end = e;
         }
@@ -2718,13 +3039,14 @@ where
             },
             unqualified_values,
             unqualified_types,
+            unqualified_types_glob,
             module: module.into(),
             as_name,
             package: (),
         })))
     }
 
-    // [Name (as Name)? | UpName (as Name)? ](, [Name (as Name)? | UpName (as Name)?])*,?
+    // [Name (as Name)? | UpName (as Name)? | type *](, [Name (as Name)? | UpName (as Name)? | type *])*,?
     fn parse_unqualified_imports(&mut self) -> Result<ParsedUnqualifiedImports, ParseError> {
         let mut imports = ParsedUnqualifiedImports::default();
         loop {
@@ -2764,6 +3086,13 @@ where
                     imports.values.push(import)
                 }
 
+                // `type *` imports all the public types of the module.
+                Some((start, Token::Type, _)) if matches!(self.tok1, Some((_, Token::Star, _))) => {
+                    self.advance();
+                    let (_, end) = self.expect_one(&Token::Star)?;
+                    imports.types_glob = Some(SrcSpan { start, end });
+                }
+
                 Some((start, Token::Type, _)) => {
                     self.advance();
                     let (name_start, name, end) = self.expect_upname()?;
@@ -2810,10 +3139,11 @@ where
         &mut self,
         start: u32,
         public: bool,
-        attributes: &Attributes,
+        attributes: &mut Attributes,
     ) -> Result<Option<UntypedDefinition>, ParseError> {
         let (name_start, name, name_end) = self.expect_name()?;
-        let documentation = self.take_documentation(name_start);
+        let (documentation, doc_attribute) =
+            self.take_definition_documentation(name_start, attributes);
 
         let annotation = self.parse_type_annotation(&Token::Colon)?;
 
@@ -2846,6 +3176,11 @@ where
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
                     },
+                    since: attributes
+                        .since
+                        .as_ref()
+                        .map(|(version, _)| version.clone()),
+                    doc_attribute,
                 })))
             }
             _ => parse_error(
@@ -3528,6 +3863,14 @@ functions are declared separately from types.";
         self.tok1.as_ref().map(|(_, token, _)| token)
     }
 
+    fn peek_tok2(&mut self) -> Option<&Token> {
+        if !self.tok2_peeked {
+            self.tok2 = self.next_token_from_stream();
+            self.tok2_peeked = true;
+        }
+        self.tok2.as_ref().map(|(_, token, _)| token)
+    }
+
     // If the next token matches the requested, consume it and return (start, end)
     fn maybe_one(&mut self, tok: &Token) -> Option<(u32, u32)> {
         match self.tok0.take() {
@@ -3609,20 +3952,6 @@ functions are declared separately from types.";
         }
     }
 
-    // if next token is a DiscardName, consume it and return relevant info, otherwise, return none
-    fn maybe_discard_name(&mut self) -> Option<(u32, EcoString, u32)> {
-        match self.tok0.take() {
-            Some((s, Token::DiscardName { name }, e)) => {
-                self.advance();
-                Some((s, name, e))
-            }
-            t0 => {
-                self.tok0 = t0;
-                None
-            }
-        }
-    }
-
     // Unexpected token error on the next token or EOF
     fn next_tok_unexpected<A>(&mut self, expected: Vec<EcoString>) -> Result<A, ParseError> {
         match self.next_tok() {
@@ -3647,8 +3976,22 @@ functions are declared separately from types.";
     // returns old tok0
     fn next_tok(&mut self) -> Option<Spanned> {
         let t = self.tok0.take();
+        let nxt = if self.tok2_peeked {
+            self.tok2_peeked = false;
+            self.tok2.take()
+        } else {
+            self.next_token_from_stream()
+        };
+        self.tok0 = self.tok1.take();
+        self.tok1 = nxt;
+        t
+    }
+
+    // Reads the next token from the token stream, skipping and recording
+    // comments and newlines along the way
+    fn next_token_from_stream(&mut self) -> Option<Spanned> {
         let mut previous_newline = None;
-        let mut nxt;
+        let nxt;
         loop {
             match self.tokens.next() {
                 // gather and skip extra
@@ -3694,9 +4037,26 @@ functions are declared separately from types.";
                 }
             }
         }
-        self.tok0 = self.tok1.take();
-        self.tok1 = nxt.take();
-        t
+        nxt
+    }
+
+    /// Takes the documentation of a definition: its doc comments, followed by
+    /// the content of its `@doc` attribute if it has one. Also returns the
+    /// strings of the attribute, as they are written.
+    ///
+    fn take_definition_documentation(
+        &mut self,
+        until: u32,
+        attributes: &mut Attributes,
+    ) -> (Option<(u32, EcoString)>, Vec<EcoString>) {
+        let documentation = self.take_documentation(until);
+        let Some((strings, location)) = attributes.doc.take() else {
+            return (documentation, vec![]);
+        };
+
+        let (start, content) = documentation.unwrap_or_else(|| (location.start, "".into()));
+        let content = with_doc_attribute(&content, &strings);
+        (Some((start, content)), strings)
     }
 
     fn take_documentation(&mut self, until: u32) -> Option<(u32, EcoString)> {
@@ -3752,9 +4112,17 @@ functions are declared separately from types.";
         start: u32,
         attributes: &mut Attributes,
     ) -> Result<u32, ParseError> {
-        // Parse the name of the attribute.
+        // Parse the name of the attribute. `derive` and `test` are reserved
+        // words, so they aren't lexed as names.
 
-        let (_, name, end) = self.expect_name()?;
+        let (name, end) = if let Some((_, end)) = self.maybe_one(&Token::Derive) {
+            ("derive".into(), end)
+        } else if let Some((_, end)) = self.maybe_one(&Token::Test) {
+            ("test".into(), end)
+        } else {
+            let (_, name, end) = self.expect_name()?;
+            (name, end)
+        };
 
         let end = match name.as_str() {
             "external" => {
@@ -3770,6 +4138,29 @@ functions are declared separately from types.";
                 self.parse_deprecated_attribute(start, end, attributes)
             }
             "internal" => self.parse_internal_attribute(start, end, attributes),
+            "pure" => self.parse_pure_attribute(start, end, attributes),
+            "benchmark" => self.parse_benchmark_attribute(start, end, attributes),
+            "memoize" => self.parse_memoize_attribute(start, end, attributes),
+            "test" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_test_attribute(start, attributes)
+            }
+            "since" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_since_attribute(start, end, attributes)
+            }
+            "doc" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_doc_attribute(start, end, attributes)
+            }
+            "implements" | "derive" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_capability_attribute(start, &name, attributes)
+            }
+            "when" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_when_attribute(start, attributes)
+            }
             _ => parse_error(ParseErrorType::UnknownAttribute, SrcSpan { start, end }),
         }?;
 
@@ -3794,6 +4185,56 @@ functions are declared separately from types.";
         Ok(end)
     }
 
+    // Parses the options of a `@test` attribute: `should_panic`, optionally
+    // followed by a string the panic message must contain.
+    fn parse_test_attribute(
+        &mut self,
+        start: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        let (name_start, name, name_end) = self.expect_name()?;
+        if name != "should_panic" {
+            return parse_error(
+                ParseErrorType::UnknownTestOption,
+                SrcSpan::new(name_start, name_end),
+            );
+        }
+        let message = if self.maybe_one(&Token::Comma).is_some() {
+            let (_, message, _) = self.expect_string()?;
+            Some(message)
+        } else {
+            None
+        };
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        if attributes.should_panic.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan { start, end });
+        }
+        attributes.should_panic = Some(ShouldPanic { message });
+        Ok(end)
+    }
+
+    fn parse_when_attribute(
+        &mut self,
+        start: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        let (name_start, name, name_end) = self.expect_name()?;
+        if name != "feature" {
+            return parse_error(
+                ParseErrorType::UnknownWhenCondition,
+                SrcSpan::new(name_start, name_end),
+            );
+        }
+        let _ = self.expect_one(&Token::Colon)?;
+        let (_, feature, _) = self.expect_string()?;
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        if attributes.feature.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan { start, end });
+        }
+        attributes.feature = Some(feature);
+        Ok(end)
+    }
+
     fn parse_external_attribute(
         &mut self,
         start: u32,
@@ -3812,17 +4253,51 @@ functions are declared separately from types.";
         let (_, module, _) = self.expect_string()?;
         let _ = self.expect_one(&Token::Comma)?;
         let (_, function, _) = self.expect_string()?;
-        let _ = self.maybe_one(&Token::Comma);
+        let arguments = if self.maybe_one(&Token::Comma).is_some() {
+            self.parse_external_arguments()?
+        } else {
+            None
+        };
         let (_, end) = self.expect_one(&Token::RightParen)?;
 
-        if attributes.has_external_for(target) {
-            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan { start, end });
+        if let Some(previous_location) = attributes.external_location_for(target) {
+            return parse_error(
+                ParseErrorType::DuplicateExternalForTarget {
+                    target,
+                    previous_location,
+                },
+                SrcSpan { start, end },
+            );
         }
 
         attributes.set_external_for(target, Some((module, function, SrcSpan { start, end })));
+        attributes.set_external_arguments_for(target, arguments);
         Ok(end)
     }
 
+    // Parses the optional list of arguments an external function is called
+    // with: `[fun, initial, list]`, followed by an optional trailing comma.
+    fn parse_external_arguments(&mut self) -> Result<Option<ExternalArguments>, ParseError> {
+        let Some((start, _)) = self.maybe_one(&Token::LeftSquare) else {
+            return Ok(None);
+        };
+        let names = Parser::series_of(
+            self,
+            &|parser| {
+                Ok(parser
+                    .maybe_name()
+                    .map(|(start, name, end)| (SrcSpan::new(start, end), name)))
+            },
+            Some(&Token::Comma),
+        )?;
+        let (_, end) = self.expect_one_following_series(&Token::RightSquare, "a name")?;
+        let _ = self.maybe_one(&Token::Comma);
+        Ok(Some(ExternalArguments {
+            location: SrcSpan::new(start, end),
+            names,
+        }))
+    }
+
     fn parse_deprecated_attribute(
         &mut self,
         start: u32,
@@ -3860,6 +4335,111 @@ functions are declared separately from types.";
             }
         }
     }
+
+    fn parse_pure_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.purity.is_pure() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.purity = Purity::Pure;
+        Ok(end)
+    }
+
+    fn parse_benchmark_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.benchmark {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.benchmark = true;
+        Ok(end)
+    }
+
+    fn parse_memoize_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.memoize {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.memoize = true;
+        Ok(end)
+    }
+
+    fn parse_since_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.since.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        let (_, version, _) = self.expect_string().map_err(|_| ParseError {
+            error: ParseErrorType::ExpectedSinceVersion,
+            location: SrcSpan { start, end },
+        })?;
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        attributes.since = Some((version, SrcSpan::new(start, end)));
+        Ok(end)
+    }
+
+    // Parses the documentation given to a `@doc` attribute: a string, or
+    // strings joined with `<>` so that it can be split over multiple lines.
+    fn parse_doc_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.doc.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        let (_, string, _) = self.expect_string()?;
+        let mut strings = vec![string];
+        while self.maybe_one(&Token::LtGt).is_some() {
+            let (_, string, _) = self.expect_string()?;
+            strings.push(string);
+        }
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        attributes.doc = Some((strings, SrcSpan::new(start, end)));
+        Ok(end)
+    }
+
+    fn parse_capability_attribute(
+        &mut self,
+        start: u32,
+        attribute: &str,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        let (name_start, name, name_end) = self.expect_name()?;
+        let Some(capability) = Capability::from_name(attribute, &name) else {
+            return parse_error(
+                ParseErrorType::UnknownCapability,
+                SrcSpan::new(name_start, name_end),
+            );
+        };
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        let location = SrcSpan::new(start, end);
+        if attributes
+            .capabilities
+            .iter()
+            .any(|(existing, _)| *existing == capability)
+        {
+            return parse_error(ParseErrorType::DuplicateAttribute, location);
+        }
+        attributes.capabilities.push((capability, location));
+        Ok(end)
+    }
 }
 
 fn concat_pattern_variable_left_hand_side_error<T>(start: u32, end: u32) -> Result<T, ParseError> {
@@ -3979,18 +4559,24 @@ fn do_reduce_clause_guard(op: Spanned, estack: &mut Vec<UntypedClauseGuard>) {
     }
 }
 
-fn expr_op_reduction((_, token, _): Spanned, l: UntypedExpr, r: UntypedExpr) -> UntypedExpr {
+fn expr_op_reduction((start, token, end): Spanned, l: UntypedExpr, r: UntypedExpr) -> UntypedExpr {
     if token == Token::Pipe {
-        let expressions = match l {
-            UntypedExpr::PipeLine { mut expressions } => {
+        let operator_location = SrcSpan { start, end };
+        let (expressions, operator_locations) = match l {
+            UntypedExpr::PipeLine {
+                mut expressions,
+                mut operator_locations,
+            } => {
                 expressions.push(r);
-                expressions
-            }
-            _ => {
-                vec1![l, r]
+                operator_locations.push(operator_location);
+                (expressions, operator_locations)
             }
+            _ => (vec1![l, r], vec![operator_location]),
         };
-        UntypedExpr::PipeLine { expressions }
+        UntypedExpr::PipeLine {
+            expressions,
+            operator_locations,
+        }
     } else {
         match tok_to_binop(&token) {
             Some(bin_op) => UntypedExpr::BinOp {
@@ -4227,7 +4813,6 @@ fn parse_error<T>(error: ParseErrorType, location: SrcSpan) -> Result<T, ParseEr
 pub enum ParserArg {
     Arg(Box<CallArg<UntypedExpr>>),
     Hole {
-        name: EcoString,
         /// The whole span of the argument.
         arg_location: SrcSpan,
         /// Just the span of the ignore name.
@@ -4251,7 +4836,6 @@ pub fn make_call(
             ParserArg::Hole {
                 arg_location,
                 discard_location,
-                name,
                 label,
             } => {
                 if hole_location.is_some() {
@@ -4259,16 +4843,6 @@ pub fn make_call(
                 }
 
                 hole_location = Some(discard_location);
-                if name != "_" {
-                    return parse_error(
-                        ParseErrorType::UnexpectedToken {
-                            token: Token::Name { name },
-                            expected: vec!["An expression".into(), "An underscore".into()],
-                            hint: None,
-                        },
-                        arg_location,
-                    );
-                }
 
                 Ok(CallArg {
                     implicit: None,
@@ -4319,6 +4893,7 @@ pub fn make_call(
 struct ParsedUnqualifiedImports {
     types: Vec<UnqualifiedImport>,
     values: Vec<UnqualifiedImport>,
+    types_glob: Option<SrcSpan>,
 }
 
 /// Parses an Int value to a bigint.
@@ -1,4 +1,5 @@
 use crate::ast::{SrcSpan, TypeAst};
+use crate::build::Target;
 use crate::error::wrap;
 use crate::parse::Token;
 use ecow::EcoString;
@@ -28,6 +29,7 @@ pub enum LexicalErrorType {
     UnexpectedStringEnd, // Unterminated string literal
     UnrecognizedToken { tok: char },
     InvalidTripleEqual,
+    InvalidRawIdentifier, // `type`, where the backticks don't wrap a lowercase name
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -57,9 +59,17 @@ impl ParseError {
                 "A deprecation attribute must have a string message.",
                 vec![],
             ),
+            ParseErrorType::ExpectedSinceVersion => (
+                "A since attribute must have a string version.",
+                vec!["Hint: For example `@since(\"1.2.0\")`.".into()],
+            ),
             ParseErrorType::ExpectedFunctionDefinition => {
                 ("I was expecting a function definition after this", vec![])
             }
+            ParseErrorType::ExpectedValueDefinition => (
+                "I was expecting a function or constant definition after this",
+                vec!["Only functions and constants can have a `@since` attribute.".into()],
+            ),
             ParseErrorType::ExtraSeparator => (
                 "This is an extra delimiter",
                 vec!["Hint: Try removing it?".into()],
@@ -234,6 +244,13 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "Functions can only be called within other functions",
                 vec![],
             ),
+            ParseErrorType::ListComprehensionWithoutGenerator => (
+                "I was expecting a generator here",
+                vec![
+                    "A list comprehension must start with a generator, like `x <- items`."
+                        .into(),
+                ],
+            ),
             ParseErrorType::ListSpreadWithoutTail => (
                 "I was expecting a value after this spread",
                 vec!["If a list expression has a spread then a tail must also be given.".into()],
@@ -246,6 +263,32 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "Duplicate attribute",
                 vec!["This attribute has already been given.".into()],
             ),
+            ParseErrorType::DuplicateExternalForTarget { target, .. } => (
+                "Duplicate external",
+                vec![format!(
+                    "An external implementation for the {target} target has already been given."
+                )],
+            ),
+            ParseErrorType::UnknownWhenCondition => (
+                "I don't recognise this condition",
+                vec!["Try `@when(feature: \"name\")` instead.".into()],
+            ),
+            ParseErrorType::UnknownTestOption => (
+                "I don't recognise this test option",
+                vec!["Try `@test(should_panic)` instead.".into()],
+            ),
+            ParseErrorType::UnknownCapability => (
+                "I don't recognise this capability",
+                vec!["Try `@implements(comparable)` or `@derive(json)` instead.".into()],
+            ),
+            ParseErrorType::ExpectedDocumentedDefinition => (
+                "I was expecting a function, constant, or type definition after this",
+                vec!["Only functions, constants, and types can have a `@doc` attribute.".into()],
+            ),
+            ParseErrorType::ExpectedCustomTypeDefinition => (
+                "I was expecting a custom type definition after this",
+                vec!["Only custom types can implement capabilities.".into()],
+            ),
             ParseErrorType::UnknownTarget => (
                 "I don't recognise this target",
                 vec!["Try `erlang`, `javascript`.".into()],
@@ -340,29 +383,33 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseErrorType {
-    ExpectedEqual,              // expect "="
-    ExpectedExpr,               // after "->" in a case clause
-    ExpectedName,               // any token used when a Name was expected
-    ExpectedPattern,            // after ':' where a pattern is expected
-    ExpectedType,               // after ':' or '->' where a type annotation is expected
-    ExpectedUpName,             // any token used when a UpName was expected
-    ExpectedValue,              // no value after "="
-    ExpectedStatement,          // no statement after "@<name>"
-    ExpectedDefinition,         // after attributes
-    ExpectedDeprecationMessage, // after "deprecated"
-    ExpectedFunctionDefinition, // after function-only attributes
-    ExprLparStart,              // it seems "(" was used to start an expression
-    ExtraSeparator,             // #(1,,) <- the 2nd comma is an extra separator
-    IncorrectName,              // UpName or DiscardName used when Name was expected
-    IncorrectUpName,            // Name or DiscardName used when UpName was expected
-    InvalidBitArraySegment,     // <<7:hello>> `hello` is an invalid BitArray segment
-    InvalidBitArrayUnit,        // in <<1:unit(x)>> x must be 1 <= x <= 256
-    InvalidTailPattern,         // only name and _name are allowed after ".." in list pattern
-    InvalidTupleAccess,         // only positive int literals for tuple access
+    ExpectedEqual,                // expect "="
+    ExpectedExpr,                 // after "->" in a case clause
+    ExpectedName,                 // any token used when a Name was expected
+    ExpectedPattern,              // after ':' where a pattern is expected
+    ExpectedType,                 // after ':' or '->' where a type annotation is expected
+    ExpectedUpName,               // any token used when a UpName was expected
+    ExpectedValue,                // no value after "="
+    ExpectedStatement,            // no statement after "@<name>"
+    ExpectedDefinition,           // after attributes
+    ExpectedDeprecationMessage,   // after "deprecated"
+    ExpectedSinceVersion,         // after "since"
+    ExpectedFunctionDefinition,   // after function-only attributes
+    ExpectedCustomTypeDefinition, // after custom type only attributes
+    ExpectedValueDefinition,      // after function and constant only attributes
+    ExpectedDocumentedDefinition, // after "doc"
+    ExprLparStart,                // it seems "(" was used to start an expression
+    ExtraSeparator,               // #(1,,) <- the 2nd comma is an extra separator
+    IncorrectName,                // UpName or DiscardName used when Name was expected
+    IncorrectUpName,              // Name or DiscardName used when UpName was expected
+    InvalidBitArraySegment,       // <<7:hello>> `hello` is an invalid BitArray segment
+    InvalidBitArrayUnit,          // in <<1:unit(x)>> x must be 1 <= x <= 256
+    InvalidTailPattern,           // only name and _name are allowed after ".." in list pattern
+    InvalidTupleAccess,           // only positive int literals for tuple access
     LexError {
         error: LexicalError,
     },
-    NestedBitArrayPattern,        // <<<<1>>, 2>>, <<1>> is not allowed in there
+    NestedBitArrayPattern, // <<<<1>>, 2>>, <<1>> is not allowed in there
     NoExpression, // between "{" and "}" in expression position, there must be an expression
     NoLetBinding, // Bindings and rebinds always require let and must always bind to a value.
     NoValueAfterEqual, // = <something other than a value>
@@ -371,12 +418,19 @@ pub enum ParseErrorType {
     OpaqueTypeAlias, // Type aliases cannot be opaque
     TooManyArgHoles, // a function call can have at most 1 arg hole
     DuplicateAttribute, // an attribute was used more than once
+    DuplicateExternalForTarget {
+        target: Target,
+        previous_location: SrcSpan,
+    }, // two `@external` attributes were given for the same target
     UnknownAttribute, // an attribute was used that is not known
     UnknownTarget, // an unknown target was used
+    UnknownWhenCondition, // `@when` was used with something other than `feature: "..."`
+    UnknownTestOption, // `@test` was used with something other than `should_panic`
+    UnknownCapability, // `@implements` or `@derive` was used with something other than a known capability
     ListSpreadWithoutElements, // Pointless spread: `[..xs]`
     ListSpreadFollowedByElements, // trying to append something after the spread: `[..xs, x]`
     LowcaseBooleanPattern, // most likely user meant True or False in patterns
-    UnexpectedLabel, // argument labels were provided, but are not supported in this context
+    UnexpectedLabel,   // argument labels were provided, but are not supported in this context
     UnexpectedEof,
     UnexpectedReservedWord, // reserved word used when a name was expected
     UnexpectedToken {
@@ -389,6 +443,7 @@ pub enum ParseErrorType {
     // A variable was assigned or discarded on the left hand side of a <> pattern
     ConcatPatternVariableLeftHandSide,
     ListSpreadWithoutTail,               // let x = [1, ..]
+    ListComprehensionWithoutGenerator,   // let x = [x | x > 0]
     ExpectedFunctionBody,                // let x = fn()
     RedundantInternalAttribute,          // for a private definition marked as internal
     InvalidModuleTypePattern,            // for patterns that have a dot like: `name.thing`
@@ -479,6 +534,13 @@ impl LexicalError {
                     "See: https://tour.gleam.run/basics/equality".into(),
                 ],
             ),
+            LexicalErrorType::InvalidRawIdentifier => (
+                "This is not a valid raw identifier",
+                vec![wrap(
+                    "Hint: Backticks can only wrap a name that starts with a \
+lowercase letter, and can contain a-z, 0-9, or _. For example: `type`.",
+                )],
+            ),
         }
     }
 }
@@ -182,6 +182,10 @@ where
                 let string = self.lex_string()?;
                 self.emit(string);
             }
+            '`' => {
+                let name = self.lex_raw_identifier()?;
+                self.emit(name);
+            }
             '=' => {
                 let tok_start = self.get_pos();
                 let _ = self.next_char();
@@ -485,6 +489,41 @@ where
             }
         }
     }
+
+    // A name wrapped in backticks, which can be used to give something a name
+    // that would otherwise be a reserved word: `type`
+    //
+    // The token's span includes the backticks so that it always covers the
+    // code as written, while the name doesn't. Anything editing the name in
+    // place, like the language server's rename, must write the backticks back
+    // when the new name is a keyword.
+    fn lex_raw_identifier(&mut self) -> LexResult {
+        let start_pos = self.get_pos();
+        let _ = self.next_char(); // opening backtick
+
+        let mut name = String::new();
+        if self.chr0.is_some_and(|c| c.is_ascii_lowercase()) {
+            while self.is_name_continuation() {
+                name.push(self.next_char().expect("lex_raw_identifier continue"));
+            }
+        }
+
+        if self.chr0 != Some('`') || name.is_empty() {
+            let end_pos = self.get_pos();
+            return Err(LexicalError {
+                error: LexicalErrorType::InvalidRawIdentifier,
+                location: SrcSpan {
+                    start: start_pos,
+                    end: end_pos,
+                },
+            });
+        }
+
+        let _ = self.next_char(); // closing backtick
+        let end_pos = self.get_pos();
+        Ok((start_pos, Token::Name { name: name.into() }, end_pos))
+    }
+
     // A type name or constructor
     fn lex_upname(&mut self) -> LexResult {
         let mut name = String::new();
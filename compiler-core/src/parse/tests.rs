@@ -1,4 +1,4 @@
-use crate::ast::SrcSpan;
+use crate::ast::{Definition, SrcSpan};
 use crate::parse::error::{
     InvalidUnicodeEscapeError, LexicalError, LexicalErrorType, ParseError, ParseErrorType,
 };
@@ -286,6 +286,47 @@ fn string_invalid_unicode_escape_sequence() {
     );
 }
 
+#[test]
+fn string_surrogate_unicode_escape_sequence() {
+    assert_error!(
+        r#""\u{d800}""#,
+        ParseError {
+            error: ParseErrorType::LexError {
+                error: LexicalError {
+                    error: LexicalErrorType::InvalidUnicodeEscape(
+                        InvalidUnicodeEscapeError::InvalidCodepoint,
+                    ),
+                    location: SrcSpan { start: 1, end: 9 },
+                }
+            },
+            location: SrcSpan { start: 1, end: 9 },
+        }
+    );
+}
+
+#[test]
+fn string_last_surrogate_unicode_escape_sequence() {
+    assert_error!(
+        r#""wibble \u{DFFF}""#,
+        ParseError {
+            error: ParseErrorType::LexError {
+                error: LexicalError {
+                    error: LexicalErrorType::InvalidUnicodeEscape(
+                        InvalidUnicodeEscapeError::InvalidCodepoint,
+                    ),
+                    location: SrcSpan { start: 8, end: 16 },
+                }
+            },
+            location: SrcSpan { start: 8, end: 16 },
+        }
+    );
+}
+
+#[test]
+fn string_unicode_escape_sequences_around_surrogates() {
+    assert_parse!(r#""\u{d7ff} \u{e000} \u{10ffff}""#);
+}
+
 #[test]
 fn bit_array() {
     // non int value in bit array unit option
@@ -351,6 +392,37 @@ fn triple_equals_with_whitespace() {
     );
 }
 
+#[test]
+fn raw_identifier() {
+    assert_parse!("let `type` = 1");
+}
+
+#[test]
+fn unterminated_raw_identifier() {
+    assert_error!(
+        "let `type = 1",
+        ParseError {
+            error: ParseErrorType::LexError {
+                error: LexicalError {
+                    error: LexicalErrorType::InvalidRawIdentifier,
+                    location: SrcSpan { start: 4, end: 9 },
+                }
+            },
+            location: SrcSpan { start: 4, end: 9 },
+        }
+    );
+}
+
+#[test]
+fn empty_raw_identifier() {
+    assert_error!("let `` = 1");
+}
+
+#[test]
+fn raw_identifier_must_be_lowercase() {
+    assert_error!("let `Type` = 1");
+}
+
 // https://github.com/gleam-lang/gleam/issues/1231
 #[test]
 fn pointless_spread() {
@@ -648,6 +720,59 @@ pub fn one(x: Int) -> Int {
     );
 }
 
+#[test]
+fn multiple_external_for_same_target_with_another_in_between() {
+    assert_module_error!(
+        r#"
+@external(erlang, "one", "two")
+@external(javascript, "./one.mjs", "two")
+@external(erlang, "three", "four")
+pub fn one(x: Int) -> Int {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn externals_for_different_targets() {
+    let module = crate::parse::parse_module(
+        Utf8PathBuf::from("test/path"),
+        r#"
+@external(erlang, "one", "two")
+@external(javascript, "./one.mjs", "two")
+pub fn one(x: Int) -> Int {
+  todo
+}
+"#,
+        &WarningEmitter::null(),
+    )
+    .expect("should parse")
+    .module;
+
+    let Some(Definition::Function(function)) = module
+        .definitions
+        .first()
+        .map(|definition| &definition.definition)
+    else {
+        panic!("expected a function");
+    };
+    assert_eq!(
+        function
+            .external_erlang
+            .as_ref()
+            .map(|(module, function, _)| (module.as_str(), function.as_str())),
+        Some(("one", "two"))
+    );
+    assert_eq!(
+        function
+            .external_javascript
+            .as_ref()
+            .map(|(module, function, _)| (module.as_str(), function.as_str())),
+        Some(("./one.mjs", "two"))
+    );
+}
+
 #[test]
 fn unknown_external_target() {
     assert_module_error!(
@@ -710,6 +835,352 @@ pub fn main() -> Nil {
     );
 }
 
+#[test]
+fn multiple_pure_attributes() {
+    assert_module_error!(
+        r#"
+@pure
+@pure
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn pure_attribute_on_constant() {
+    assert_module_error!(
+        r#"
+@pure
+pub const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn pure_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @pure
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_benchmark_attributes() {
+    assert_module_error!(
+        r#"
+@benchmark
+@benchmark
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn benchmark_attribute_on_constant() {
+    assert_module_error!(
+        r#"
+@benchmark
+pub const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn benchmark_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @benchmark
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_memoize_attributes() {
+    assert_module_error!(
+        r#"
+@memoize
+@memoize
+pub fn fib(n: Int) -> Int {
+  n
+}
+"#
+    );
+}
+
+#[test]
+fn memoize_attribute_on_constant() {
+    assert_module_error!(
+        r#"
+@memoize
+pub const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn memoize_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @memoize
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_test_attributes() {
+    assert_module_error!(
+        r#"
+@test(should_panic)
+@test(should_panic, "wibble")
+pub fn wibble_test() {
+  panic
+}
+"#
+    );
+}
+
+#[test]
+fn unknown_test_attribute_option() {
+    assert_module_error!(
+        r#"
+@test(should_fail)
+pub fn wibble_test() {
+  panic
+}
+"#
+    );
+}
+
+#[test]
+fn should_panic_attribute_on_constant() {
+    assert_module_error!(
+        r#"
+@test(should_panic)
+pub const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn multiple_since_attributes() {
+    assert_module_error!(
+        r#"
+@since("1.0.0")
+@since("1.2.0")
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn since_attribute_without_a_version() {
+    assert_module_error!(
+        r#"
+@since(1)
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn since_attribute_on_type() {
+    assert_module_error!(
+        r#"
+@since("1.2.0")
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn since_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @since("1.2.0")
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_doc_attributes() {
+    assert_module_error!(
+        r#"
+@doc("Wibble")
+@doc("Wobble")
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn doc_attribute_without_a_string() {
+    assert_module_error!(
+        r#"
+@doc(wibble)
+pub fn wibble() -> Int {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn doc_attribute_on_import() {
+    assert_module_error!(
+        r#"
+@doc("Wibble")
+import wibble
+"#
+    );
+}
+
+#[test]
+fn doc_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @doc("Wibble")
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn unknown_capability() {
+    assert_module_error!(
+        r#"
+@implements(sortable)
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn capability_used_with_the_wrong_attribute() {
+    assert_module_error!(
+        r#"
+@derive(comparable)
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_implements_attributes() {
+    assert_module_error!(
+        r#"
+@implements(comparable)
+@implements(comparable)
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn implements_attribute_on_function() {
+    assert_module_error!(
+        r#"
+@implements(comparable)
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn implements_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @implements(comparable)
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_when_attributes() {
+    assert_module_error!(
+        r#"
+@when(feature: "wibble")
+@when(feature: "wobble")
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn unknown_when_condition() {
+    assert_module_error!(
+        r#"
+@when(target: "erlang")
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn when_attribute_without_feature_name() {
+    assert_module_error!(
+        r#"
+@when(feature: wibble)
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn when_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @when(feature: "wibble")
+  Wibble
+}
+"#
+    );
+}
+
 #[test]
 fn attributes_with_no_definition() {
     assert_module_error!(
@@ -765,6 +1236,16 @@ fn import_type() {
     assert_parse_module!(r#"import wibble.{type Wobble, Wobble, type Wabble}"#);
 }
 
+#[test]
+fn import_all_types() {
+    assert_parse_module!(r#"import wibble.{type *, Wobble, type Wabble}"#);
+}
+
+#[test]
+fn import_glob_without_type_keyword() {
+    assert_module_error!(r#"import wibble.{*}"#);
+}
+
 #[test]
 fn reserved_auto() {
     assert_module_error!(r#"const auto = 1"#);
@@ -805,21 +1286,6 @@ fn reserved_echo() {
     assert_module_error!(r#"const echo = 1"#);
 }
 
-#[test]
-fn capture_with_name() {
-    assert_module_error!(
-        r#"
-pub fn main() {
-  add(_name, 1)
-}
-
-fn add(x, y) {
-  x + y
-}
-"#
-    );
-}
-
 #[test]
 fn list_spread_with_no_tail_in_the_middle_of_a_list() {
     assert_module_error!(
@@ -961,19 +1427,6 @@ type Wibble {
     );
 }
 
-#[test]
-fn wrong_record_access_pattern() {
-    assert_module_error!(
-        "
-pub fn main() {
-  case wibble {
-    wibble.thing -> 1
-  }
-}
-"
-    );
-}
-
 #[test]
 fn tuple_invalid_expr() {
     assert_module_error!(
@@ -1446,6 +1899,62 @@ fn first_parsed_docstring(src: &str) -> EcoString {
         .expect("definition without doc")
 }
 
+#[test]
+fn doc_attribute_is_used_as_documentation() {
+    assert_eq!(
+        first_parsed_docstring(
+            r#"
+@doc("Doc!")
+pub fn wibble() {}
+"#
+        ),
+        "Doc!\n"
+    );
+}
+
+#[test]
+fn doc_attribute_strings_are_joined() {
+    assert_eq!(
+        first_parsed_docstring(
+            r#"
+@doc("Wibble\nwobble" <> " and \"wubble\"")
+pub const wibble = 1
+"#
+        ),
+        "Wibble\nwobble and \"wubble\"\n"
+    );
+}
+
+#[test]
+fn doc_attribute_comes_after_doc_comments() {
+    assert_eq!(
+        first_parsed_docstring(
+            r#"
+/// Wibble
+@doc("Wobble")
+pub type Wibble {
+  Wibble
+}
+"#
+        ),
+        " Wibble\nWobble\n"
+    );
+}
+
+#[test]
+fn doc_attribute_on_type_alias() {
+    assert_eq!(
+        first_parsed_docstring(
+            r#"
+@doc("Wobble")
+pub type Wibble =
+  Int
+"#
+        ),
+        "Wobble\n"
+    );
+}
+
 #[test]
 fn doc_comment_before_comment_is_not_attached_to_following_function() {
     assert_eq!(
@@ -1739,3 +2248,54 @@ fn nested_tuple_access_after_function() {
 fn case_expression_without_body() {
     assert_parse!("case a");
 }
+
+#[test]
+fn assert_statement() {
+    assert_parse!("assert x > 1");
+}
+
+#[test]
+fn assert_statement_without_value() {
+    assert_error!(
+        "assert",
+        ParseError {
+            location: SrcSpan { start: 0, end: 6 },
+            error: ParseErrorType::ExpectedValue
+        }
+    );
+}
+
+#[test]
+fn trailing_block_call() {
+    assert_parse!("list.map(items) { x -> x + 1 }");
+}
+
+#[test]
+fn trailing_block_call_with_multiple_arguments() {
+    assert_parse!("list.fold(items, 0) { acc, x -> acc + x }");
+}
+
+#[test]
+fn trailing_block_call_without_arguments() {
+    assert_parse!("lazy() { -> 1 }");
+}
+
+#[test]
+fn trailing_block_call_with_annotated_argument() {
+    assert_parse!("list.map(items) { x: Int -> x }");
+}
+
+#[test]
+fn block_following_call_is_not_a_trailing_block() {
+    assert_parse!("wibble(1) { wobble }");
+}
+
+#[test]
+fn call_in_case_subject_is_not_followed_by_trailing_block() {
+    assert_parse!("case wibble(1) { x -> x }");
+}
+
+#[test]
+fn trailing_block_in_case_subject_argument() {
+    assert_parse!("case wibble(list.map(items) { x -> x }) { y -> y }");
+}
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
-use crate::ast::SrcSpan;
+use crate::{
+    ast::SrcSpan,
+    build::Origin,
+    type_::{ModuleInterface, ValueConstructorVariant},
+};
 use ecow::EcoString;
+use itertools::Itertools;
 use petgraph::{
     Directed,
     stable_graph::{NodeIndex, StableGraph},
@@ -24,6 +29,9 @@ pub struct Reference {
 
 pub type ReferenceMap = HashMap<(EcoString, EcoString), Vec<Reference>>;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Default)]
 pub struct ReferenceTracker {
     /// A call-graph which tracks which values are referenced by which other value,
@@ -38,6 +46,10 @@ pub struct ReferenceTracker {
     /// The locations of the references to each type in this module, used for
     /// renaming and go-to reference.
     pub type_references: ReferenceMap,
+    /// The references to functions called in this module whose return value
+    /// is discarded, used to find the functions whose return value is never
+    /// used.
+    pub discarded_value_references: ReferenceMap,
 }
 
 impl ReferenceTracker {
@@ -79,6 +91,21 @@ impl ReferenceTracker {
         _ = self.graph.add_edge(self.current_function, target, ());
     }
 
+    /// Registers a reference, already registered with
+    /// `register_value_reference`, as a call whose return value is discarded.
+    pub fn register_discarded_value_reference(
+        &mut self,
+        module: EcoString,
+        name: EcoString,
+        location: SrcSpan,
+        kind: ReferenceKind,
+    ) {
+        self.discarded_value_references
+            .entry((module, name))
+            .or_default()
+            .push(Reference { location, kind });
+    }
+
     pub fn register_type_reference(
         &mut self,
         module: EcoString,
@@ -92,3 +119,201 @@ impl ReferenceTracker {
             .push(Reference { location, kind });
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicDefinitionKind {
+    Function,
+    Constant,
+    Type,
+}
+
+impl PublicDefinitionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublicDefinitionKind::Function => "function",
+            PublicDefinitionKind::Constant => "constant",
+            PublicDefinitionKind::Type => "type",
+        }
+    }
+}
+
+/// A public definition that no other module references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedPublicDefinition {
+    pub module: EcoString,
+    pub name: EcoString,
+    pub kind: PublicDefinitionKind,
+    pub location: SrcSpan,
+}
+
+/// Finds the public functions, constants and types defined in the `src`
+/// modules that are never referenced by any of the other given modules. These
+/// are candidates to be made private, or removed altogether.
+///
+/// Only actual uses count as references, importing a definition without ever
+/// using it doesn't. A definition that is only used to define another public
+/// definition, like a constant aliasing a function of another module, still
+/// counts as used: if the alias is never used it is reported in its place.
+///
+/// `main` functions are never reported, as they are meant to be run rather
+/// than referenced.
+///
+pub fn find_unused_public_definitions(modules: &[&ModuleInterface]) -> Vec<UnusedPublicDefinition> {
+    let is_referenced_elsewhere = |module: &EcoString, name: &EcoString, type_: bool| {
+        let key = (module.clone(), name.clone());
+        modules
+            .iter()
+            .filter(|other| &other.name != module)
+            .filter_map(|other| {
+                let references = if type_ {
+                    &other.references.type_references
+                } else {
+                    &other.references.value_references
+                };
+                references.get(&key)
+            })
+            .flatten()
+            .any(|reference| match reference.kind {
+                ReferenceKind::Qualified | ReferenceKind::Unqualified | ReferenceKind::Alias => {
+                    true
+                }
+                ReferenceKind::Import | ReferenceKind::Definition => false,
+            })
+    };
+
+    let mut unused = vec![];
+    for module in modules.iter().filter(|module| module.origin == Origin::Src) {
+        for (name, value) in &module.values {
+            let kind = match &value.variant {
+                _ if !value.publicity.is_importable() => continue,
+                ValueConstructorVariant::ModuleFn { .. } if name == "main" => continue,
+                ValueConstructorVariant::ModuleFn { .. } => PublicDefinitionKind::Function,
+                ValueConstructorVariant::ModuleConstant { .. } => PublicDefinitionKind::Constant,
+                ValueConstructorVariant::LocalVariable { .. }
+                | ValueConstructorVariant::LocalConstant { .. }
+                | ValueConstructorVariant::Record { .. } => continue,
+            };
+            if !is_referenced_elsewhere(&module.name, name, false) {
+                unused.push(UnusedPublicDefinition {
+                    module: module.name.clone(),
+                    name: name.clone(),
+                    kind,
+                    location: value.variant.definition_location(),
+                });
+            }
+        }
+
+        for (name, type_) in &module.types {
+            if !type_.publicity.is_importable() {
+                continue;
+            }
+            // Using any of the constructors of a type is using the type, even
+            // if its name never appears in the other modules.
+            let constructor_is_used =
+                module
+                    .types_value_constructors
+                    .get(name)
+                    .is_some_and(|constructors| {
+                        constructors.variants.iter().any(|variant| {
+                            is_referenced_elsewhere(&module.name, &variant.name, false)
+                        })
+                    });
+            if !constructor_is_used && !is_referenced_elsewhere(&module.name, name, true) {
+                unused.push(UnusedPublicDefinition {
+                    module: module.name.clone(),
+                    name: name.clone(),
+                    kind: PublicDefinitionKind::Type,
+                    location: type_.origin,
+                });
+            }
+        }
+    }
+
+    unused
+        .into_iter()
+        .sorted_by(|one, other| {
+            (&one.module, one.location.start).cmp(&(&other.module, other.location.start))
+        })
+        .collect()
+}
+
+/// A function whose return value is discarded by every one of its calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlwaysIgnoredReturn {
+    pub module: EcoString,
+    pub name: EcoString,
+    pub location: SrcSpan,
+    /// How many times the function is called.
+    pub calls: usize,
+}
+
+/// Finds the functions defined in the `src` modules that return a value no
+/// call in the given modules ever uses. Such a return value is likely a
+/// leftover and the function could return `Nil` instead.
+///
+/// A function whose value is ever used in any other way, like being passed
+/// to another function, counts as having its return value used. A pipeline
+/// ending with a call discards the value of that call only, the values
+/// returned by its other steps are passed along to the next one.
+///
+/// Functions returning `Nil` and functions that are never called are never
+/// reported.
+///
+pub fn find_always_ignored_returns(modules: &[&ModuleInterface]) -> Vec<AlwaysIgnoredReturn> {
+    let calls = |key: &(EcoString, EcoString)| {
+        let mut calls = 0;
+        for module in modules {
+            let Some(references) = module.references.value_references.get(key) else {
+                continue;
+            };
+            let discarded = module
+                .references
+                .discarded_value_references
+                .get(key)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for reference in references {
+                match reference.kind {
+                    ReferenceKind::Qualified
+                    | ReferenceKind::Unqualified
+                    | ReferenceKind::Alias => {}
+                    ReferenceKind::Import | ReferenceKind::Definition => continue,
+                }
+                if !discarded.contains(reference) {
+                    return None;
+                }
+                calls += 1;
+            }
+        }
+        Some(calls)
+    };
+
+    let mut ignored = vec![];
+    for module in modules.iter().filter(|module| module.origin == Origin::Src) {
+        for (name, value) in &module.values {
+            let ValueConstructorVariant::ModuleFn { location, .. } = &value.variant else {
+                continue;
+            };
+            if value.type_.return_type().is_none_or(|type_| type_.is_nil()) {
+                continue;
+            }
+            let key = (module.name.clone(), name.clone());
+            match calls(&key) {
+                None | Some(0) => {}
+                Some(calls) => ignored.push(AlwaysIgnoredReturn {
+                    module: module.name.clone(),
+                    name: name.clone(),
+                    location: *location,
+                    calls,
+                }),
+            }
+        }
+    }
+
+    ignored
+        .into_iter()
+        .sorted_by(|one, other| {
+            (&one.module, one.location.start).cmp(&(&other.module, other.location.start))
+        })
+        .collect()
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use camino::Utf8PathBuf;
+use itertools::Itertools;
+
+use crate::{
+    analyse::TargetSupport,
+    build::{Origin, Target},
+    config::PackageConfig,
+    line_numbers::LineNumbers,
+    type_::{ModuleInterface, PRELUDE_MODULE_NAME, build_prelude},
+    uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, Warning, WarningEmitter},
+};
+
+use super::{find_always_ignored_returns, find_unused_public_definitions};
+
+/// Analyses the given modules, in order, returning their interfaces.
+fn analyse_modules(modules: &[(&str, Origin, &str)]) -> Vec<ModuleInterface> {
+    let ids = UniqueIdGenerator::new();
+    let mut importable_modules = im::HashMap::new();
+    let _ = importable_modules.insert(PRELUDE_MODULE_NAME.into(), build_prelude(&ids));
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+
+    let mut interfaces = vec![];
+    for (name, origin, src) in modules {
+        let parsed = crate::parse::parse_module(
+            Utf8PathBuf::from("test/path"),
+            src,
+            &WarningEmitter::null(),
+        )
+        .expect("syntax error");
+        let mut ast = parsed.module;
+        ast.name = (*name).into();
+        let module = crate::analyse::ModuleAnalyzerConstructor::<()> {
+            target: Target::Erlang,
+            ids: &ids,
+            origin: *origin,
+            importable_modules: &importable_modules,
+            warnings: &TypeWarningEmitter::null(),
+            direct_dependencies: &HashMap::new(),
+            target_support: TargetSupport::Enforced,
+            package_config: &config,
+        }
+        .infer_module(ast, LineNumbers::new(src), "".into())
+        .expect("should successfully infer");
+        let _ = importable_modules.insert((*name).into(), module.type_info.clone());
+        interfaces.push(module.type_info);
+    }
+    interfaces
+}
+
+/// Analyses the given modules, in order, and prints the unused public
+/// definitions found across all of them.
+fn unused_public_definitions(modules: &[(&str, Origin, &str)]) -> String {
+    let interfaces = analyse_modules(modules);
+    let sources: HashMap<_, _> = modules.iter().map(|(name, _, src)| (*name, *src)).collect();
+    find_unused_public_definitions(&interfaces.iter().collect_vec())
+        .into_iter()
+        .map(|unused| {
+            let src = sources.get(unused.module.as_str()).expect("module source");
+            let line = LineNumbers::new(src).line_number(unused.location.start);
+            format!(
+                "{}:{line} {} {}",
+                unused.module,
+                unused.kind.as_str(),
+                unused.name
+            )
+        })
+        .join("\n")
+}
+
+#[test]
+fn unused_public_definitions_across_modules() {
+    let output = unused_public_definitions(&[
+        (
+            "wibble",
+            Origin::Src,
+            r#"
+pub type Used {
+  Used
+}
+
+pub type UsedThroughConstructor {
+  UsedThroughConstructor
+}
+
+pub type Unused {
+  Unused
+}
+
+pub const used_constant = 1
+
+pub const unused_constant = 2
+
+pub fn used_qualified() { 1 }
+
+pub fn used_unqualified() { 1 }
+
+pub fn used_in_a_pipe(x) { x }
+
+pub fn used_through_an_alias() { 1 }
+
+pub fn only_imported() { 1 }
+
+pub fn only_used_in_its_module() { 1 }
+
+pub fn unused() { only_used_in_its_module() }
+
+fn private() { 1 }
+"#,
+        ),
+        (
+            "wobble",
+            Origin::Src,
+            r#"
+import wibble.{type Used, only_imported, used_unqualified, used_through_an_alias as alias}
+
+pub fn main() {
+  let _ = wibble.used_constant
+  let _ = wibble.UsedThroughConstructor
+  let _ = wibble.used_qualified()
+  let _ = used_unqualified()
+  let _ = 1 |> wibble.used_in_a_pipe
+  let _ = alias()
+  Nil
+}
+
+pub fn reexported_but_unused() -> Used {
+  wibble.Used
+}
+"#,
+        ),
+    ]);
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn definitions_used_by_test_modules_are_used() {
+    let output = unused_public_definitions(&[
+        (
+            "wibble",
+            Origin::Src,
+            r#"
+pub fn used_in_tests() { 1 }
+
+pub fn unused() { 1 }
+"#,
+        ),
+        (
+            "wibble_test",
+            Origin::Test,
+            r#"
+import wibble
+
+pub fn wibble_test() {
+  wibble.used_in_tests()
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(output, "wibble:4 function unused");
+}
+
+/// Analyses the given modules, in order, and prints the functions whose
+/// return value is discarded by all of their calls.
+fn always_ignored_returns(modules: &[(&str, Origin, &str)]) -> String {
+    let interfaces = analyse_modules(modules);
+    let sources: HashMap<_, _> = modules.iter().map(|(name, _, src)| (*name, *src)).collect();
+    find_always_ignored_returns(&interfaces.iter().collect_vec())
+        .into_iter()
+        .map(|ignored| {
+            let src = sources.get(ignored.module.as_str()).expect("module source");
+            let line = LineNumbers::new(src).line_number(ignored.location.start);
+            format!(
+                "{}:{line} {} ({} calls)",
+                ignored.module, ignored.name, ignored.calls
+            )
+        })
+        .join("\n")
+}
+
+#[test]
+fn return_ignored_by_every_call() {
+    let output = always_ignored_returns(&[
+        (
+            "wibble",
+            Origin::Src,
+            r#"
+pub fn save(x) {
+  x + 1
+}
+
+pub fn log(x) {
+  Nil
+}
+"#,
+        ),
+        (
+            "wobble",
+            Origin::Src,
+            r#"
+import wibble.{save, save as store}
+
+pub fn main() {
+  wibble.save(1)
+  save(2)
+  store(3)
+  3 |> wibble.save
+  4 |> save()
+  wibble.log(1)
+  Nil
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(output, "wibble:2 save (5 calls)");
+}
+
+#[test]
+fn return_used_by_a_call() {
+    let output = always_ignored_returns(&[
+        (
+            "wibble",
+            Origin::Src,
+            r#"
+pub fn save(x) {
+  x + 1
+}
+"#,
+        ),
+        (
+            "wobble",
+            Origin::Src,
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.save(1)
+  let x = wibble.save(2)
+  x
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(output, "");
+}
+
+#[test]
+fn return_used_by_a_pipeline() {
+    let output = always_ignored_returns(&[(
+        "wibble",
+        Origin::Src,
+        r#"
+fn save(x) {
+  x + 1
+}
+
+fn add(x, y) {
+  x + y
+}
+
+pub fn main() {
+  1 |> save |> add(2)
+  Nil
+}
+"#,
+    )]);
+
+    assert_eq!(output, "wibble:6 add (1 calls)");
+}
+
+#[test]
+fn return_used_by_passing_the_function() {
+    let output = always_ignored_returns(&[
+        (
+            "wibble",
+            Origin::Src,
+            r#"
+pub fn save(x) {
+  x + 1
+}
+"#,
+        ),
+        (
+            "wobble",
+            Origin::Test,
+            r#"
+import wibble
+
+pub fn main() {
+  wibble.save(1)
+  let f = wibble.save
+  f
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(output, "");
+}
+
+#[test]
+fn always_ignored_return_warning() {
+    let src = r#"
+pub fn save(x) {
+  x + 1
+}
+
+pub fn main() {
+  save(1)
+  save(2)
+  Nil
+}
+"#;
+    let interfaces = analyse_modules(&[("wibble", Origin::Src, src)]);
+    let warnings = find_always_ignored_returns(&interfaces.iter().collect_vec())
+        .into_iter()
+        .map(|function| {
+            Warning::AlwaysIgnoredReturn {
+                path: "src/wibble.gleam".into(),
+                src: src.into(),
+                function,
+            }
+            .to_pretty_string()
+        })
+        .join("\n");
+    insta::assert_snapshot!(warnings);
+}
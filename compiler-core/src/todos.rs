@@ -0,0 +1,99 @@
+//! Listing the `todo` expressions of a project, so that the work left to do
+//! can be tracked.
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use ecow::EcoString;
+
+use crate::{
+    ast::{
+        SrcSpan, TodoKind, TypedExpr, TypedModule,
+        visit::{self, Visit},
+    },
+    line_numbers::LineNumbers,
+    type_::Type,
+};
+
+/// A `todo` expression found in a module.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Todo {
+    /// The line the `todo` is on, starting from 1.
+    pub line: u32,
+    /// The column the `todo` starts at, starting from 1.
+    pub column: u32,
+    /// The message given to the `todo` with `as`. If it's a string literal
+    /// this is its value, otherwise the source code of the expression.
+    pub message: Option<EcoString>,
+}
+
+/// Returns all the `todo` expressions in a module, in the order they appear
+/// in its source code.
+///
+/// This includes the ones the compiler adds for code that is not written
+/// yet, like the body of a function with no body.
+///
+pub fn module_todos(module: &TypedModule, code: &str) -> Vec<Todo> {
+    let mut finder = TodoFinder {
+        code,
+        line_numbers: &module.type_info.line_numbers,
+        todos: vec![],
+    };
+    for definition in &module.definitions {
+        finder.visit_typed_definition(definition);
+    }
+    finder.todos.sort_by_key(|todo| (todo.line, todo.column));
+    finder.todos
+}
+
+struct TodoFinder<'a> {
+    code: &'a str,
+    line_numbers: &'a LineNumbers,
+    todos: Vec<Todo>,
+}
+
+impl TodoFinder<'_> {
+    fn message(&self, message: &TypedExpr) -> Option<EcoString> {
+        match message {
+            TypedExpr::String { value, .. } => Some(value.clone()),
+            _ => {
+                let location = message.location();
+                self.code
+                    .get(location.start as usize..location.end as usize)
+                    .map(EcoString::from)
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for TodoFinder<'_> {
+    fn visit_typed_expr_todo(
+        &mut self,
+        location: &'ast SrcSpan,
+        message: &'ast Option<Box<TypedExpr>>,
+        kind: &'ast TodoKind,
+        type_: &'ast Arc<Type>,
+    ) {
+        // A function with no body is reported where the function is, like
+        // the `todo` warning does.
+        let todo_location = match kind {
+            TodoKind::Keyword
+            | TodoKind::IncompleteUse
+            | TodoKind::EmptyBlock
+            | TodoKind::PipelineStep => *location,
+            TodoKind::EmptyFunction { function_location } => *function_location,
+        };
+        let position = self
+            .line_numbers
+            .line_and_column_number(todo_location.start);
+        self.todos.push(Todo {
+            line: position.line,
+            column: position.column,
+            message: message.as_deref().and_then(|message| self.message(message)),
+        });
+
+        visit::visit_typed_expr_todo(self, location, message, kind, type_);
+    }
+}
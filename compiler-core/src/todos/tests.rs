@@ -0,0 +1,94 @@
+use super::{Todo, module_todos};
+use crate::type_::tests::compile_module;
+
+fn todos(module_name: &str, src: &str, deps: Vec<(&str, &str, &str)>) -> Vec<Todo> {
+    let module = compile_module(module_name, src, None, deps).expect("should successfully infer");
+    module_todos(&module, src)
+}
+
+#[test]
+fn module_without_todos() {
+    assert_eq!(todos("wibble", "pub fn main() { 1 }", vec![]), vec![]);
+}
+
+#[test]
+fn todos_across_modules() {
+    let wibble = r#"pub fn wibble() {
+  todo as "make a wibble"
+}
+
+pub fn wobble(x) {
+  case x {
+    True -> 1
+    False -> todo
+  }
+}
+"#;
+    let main = r#"import wibble
+
+pub fn main() {
+  wibble.wibble()
+  todo as { "not " <> "done" }
+}
+"#;
+
+    assert_eq!(
+        todos("wibble", wibble, vec![]),
+        vec![
+            Todo {
+                line: 2,
+                column: 3,
+                message: Some("make a wibble".into()),
+            },
+            Todo {
+                line: 8,
+                column: 14,
+                message: None,
+            },
+        ]
+    );
+    assert_eq!(
+        todos("main", main, vec![("thepackage", "wibble", wibble)]),
+        vec![Todo {
+            line: 5,
+            column: 3,
+            message: Some(r#"{ "not " <> "done" }"#.into()),
+        }]
+    );
+}
+
+#[test]
+fn function_without_a_body() {
+    assert_eq!(
+        todos("wibble", "pub fn wibble() {}", vec![]),
+        vec![Todo {
+            line: 1,
+            column: 1,
+            message: None,
+        }]
+    );
+}
+
+#[test]
+fn nested_todos_are_in_source_order() {
+    let src = r#"pub fn main() {
+  let f = fn() { todo as "inner" }
+  todo as "outer"
+}
+"#;
+    assert_eq!(
+        todos("wibble", src, vec![]),
+        vec![
+            Todo {
+                line: 2,
+                column: 18,
+                message: Some("inner".into()),
+            },
+            Todo {
+                line: 3,
+                column: 3,
+                message: Some("outer".into()),
+            },
+        ]
+    );
+}
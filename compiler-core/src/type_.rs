@@ -7,7 +7,7 @@ pub(crate) mod pattern;
 pub(crate) mod pipe;
 pub(crate) mod prelude;
 pub mod pretty;
-pub(crate) mod printer;
+pub mod printer;
 #[cfg(test)]
 pub mod tests;
 
@@ -172,6 +172,47 @@ impl Type {
         }
     }
 
+    /// The unbound type variables of this type, in the order they first
+    /// appear in it. Each variable is only returned once.
+    ///
+    pub fn unbound_type_variables(&self) -> Vec<Arc<Type>> {
+        let mut ids = vec![];
+        let mut variables = vec![];
+        self.collect_unbound_type_variables(&mut ids, &mut variables);
+        variables
+    }
+
+    fn collect_unbound_type_variables(&self, ids: &mut Vec<u64>, variables: &mut Vec<Arc<Type>>) {
+        match self {
+            Self::Named { args, .. } => {
+                for argument in args {
+                    argument.collect_unbound_type_variables(ids, variables);
+                }
+            }
+            Self::Fn { args, return_ } => {
+                for argument in args {
+                    argument.collect_unbound_type_variables(ids, variables);
+                }
+                return_.collect_unbound_type_variables(ids, variables);
+            }
+            Self::Tuple { elements } => {
+                for element in elements {
+                    element.collect_unbound_type_variables(ids, variables);
+                }
+            }
+            Self::Var { type_ } => match &*type_.borrow() {
+                TypeVar::Unbound { id } if !ids.contains(id) => {
+                    ids.push(*id);
+                    variables.push(Arc::new(Type::Var {
+                        type_: type_.clone(),
+                    }));
+                }
+                TypeVar::Unbound { .. } | TypeVar::Generic { .. } => {}
+                TypeVar::Link { type_ } => type_.collect_unbound_type_variables(ids, variables),
+            },
+        }
+    }
+
     pub fn return_type(&self) -> Option<Arc<Self>> {
         match self {
             Self::Fn { return_, .. } => Some(return_.clone()),
@@ -522,7 +563,11 @@ impl Type {
                     && package == other_package
                     && module == other_module
                     && name == other_name
-                    && args == other_args
+                    && args.len() == other_args.len()
+                    && args
+                        .iter()
+                        .zip(other_args)
+                        .all(|(one, other)| one.same_as(other))
             }
 
             (Type::Fn { .. }, Type::Named { .. } | Type::Tuple { .. }) => false,
@@ -649,6 +694,8 @@ pub enum ValueConstructorVariant {
     /// A module constant
     ModuleConstant {
         documentation: Option<EcoString>,
+        /// The version given to the constant's `@since` attribute, if any.
+        since: Option<EcoString>,
         location: SrcSpan,
         module: EcoString,
         name: EcoString,
@@ -669,9 +716,12 @@ pub enum ValueConstructorVariant {
         arity: usize,
         location: SrcSpan,
         documentation: Option<EcoString>,
+        /// The version given to the function's `@since` attribute, if any.
+        since: Option<EcoString>,
         implementations: Implementations,
         external_erlang: Option<(EcoString, EcoString)>,
         external_javascript: Option<(EcoString, EcoString)>,
+        purity: Purity,
     },
 
     /// A constructor for a custom type
@@ -714,6 +764,7 @@ impl ValueConstructorVariant {
             // TODO: remove this clone with an rc clone
             Self::ModuleConstant {
                 documentation,
+                since,
                 literal,
                 location,
                 ..
@@ -721,12 +772,14 @@ impl ValueConstructorVariant {
                 literal: literal.clone(),
                 location: *location,
                 documentation: documentation.clone(),
+                since: since.clone(),
             },
 
             Self::LocalConstant { literal } => ModuleValueConstructor::Constant {
                 literal: literal.clone(),
                 location: literal.location(),
                 documentation: None,
+                since: None,
             },
 
             Self::LocalVariable { location, .. } => ModuleValueConstructor::Fn {
@@ -735,8 +788,10 @@ impl ValueConstructorVariant {
                 external_erlang: None,
                 external_javascript: None,
                 documentation: None,
+                since: None,
                 location: *location,
                 field_map: None,
+                purity: Purity::Unknown,
             },
 
             Self::ModuleFn {
@@ -744,18 +799,22 @@ impl ValueConstructorVariant {
                 module,
                 location,
                 documentation,
+                since,
                 field_map,
                 external_erlang,
                 external_javascript,
+                purity,
                 ..
             } => ModuleValueConstructor::Fn {
                 name: name.clone(),
                 module: module.clone(),
                 documentation: documentation.clone(),
+                since: since.clone(),
                 external_erlang: external_erlang.clone(),
                 external_javascript: external_javascript.clone(),
                 location: *location,
                 field_map: field_map.clone(),
+                purity: *purity,
             },
         }
     }
@@ -855,12 +914,15 @@ pub enum ModuleValueConstructor {
         external_javascript: Option<(EcoString, EcoString)>,
         field_map: Option<FieldMap>,
         documentation: Option<EcoString>,
+        since: Option<EcoString>,
+        purity: Purity,
     },
 
     Constant {
         literal: TypedConstant,
         location: SrcSpan,
         documentation: Option<EcoString>,
+        since: Option<EcoString>,
     },
 }
 
@@ -880,6 +942,14 @@ impl ModuleValueConstructor {
             | ModuleValueConstructor::Constant { documentation, .. } => documentation.as_deref(),
         }
     }
+
+    pub fn get_since(&self) -> Option<&str> {
+        match self {
+            ModuleValueConstructor::Record { .. } => None,
+            ModuleValueConstructor::Fn { since, .. }
+            | ModuleValueConstructor::Constant { since, .. } => since.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -913,6 +983,15 @@ pub struct ModuleInterface {
     /// Wether there's any echo in the module.
     pub contains_echo: bool,
     pub references: References,
+    /// The names of the functions annotated with `@benchmark`, in the order
+    /// they are defined in the module.
+    pub benchmarks: Vec<EcoString>,
+    /// The public values of the module that are not compiled as they require
+    /// a feature that is not enabled, with the name of that feature.
+    pub disabled_values: HashMap<EcoString, EcoString>,
+    /// The public types of the module that are not compiled as they require
+    /// a feature that is not enabled, with the name of that feature.
+    pub disabled_types: HashMap<EcoString, EcoString>,
 }
 
 impl ModuleInterface {
@@ -926,6 +1005,9 @@ pub struct References {
     pub imported_modules: HashSet<EcoString>,
     pub value_references: ReferenceMap,
     pub type_references: ReferenceMap,
+    /// The references to functions that are called without their return
+    /// value being used. These are also part of `value_references`.
+    pub discarded_value_references: ReferenceMap,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1038,6 +1120,22 @@ impl ModuleInterface {
         })
     }
 
+    /// If the given name is a constructor of an opaque type defined in this
+    /// module, returns the name of that type.
+    ///
+    pub fn opaque_type_of_constructor(&self, name: &str) -> Option<&EcoString> {
+        self.types_value_constructors
+            .iter()
+            .find(|(_, constructors)| {
+                constructors.opaque == Opaque::Opaque
+                    && constructors
+                        .variants
+                        .iter()
+                        .any(|variant| variant.name == name)
+            })
+            .map(|(type_name, _)| type_name)
+    }
+
     pub fn public_value_names(&self) -> Vec<EcoString> {
         self.values
             .iter()
@@ -1297,6 +1395,31 @@ impl Default for Deprecation {
     }
 }
 
+/// Whether a function has been annotated with `@pure`.
+///
+/// The annotation is a promise made by the programmer that calling the
+/// function has no side effects, so a call whose result is never used can be
+/// safely removed. The compiler trusts this promise and never checks it: if a
+/// function annotated as pure does have side effects those could be silently
+/// discarded when optimising.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum Purity {
+    Pure,
+    #[default]
+    Unknown,
+}
+
+impl Purity {
+    /// Returns `true` if the purity is [`Pure`].
+    ///
+    /// [`Pure`]: Purity::Pure
+    #[must_use]
+    pub fn is_pure(&self) -> bool {
+        matches!(self, Self::Pure)
+    }
+}
+
 impl ValueConstructor {
     pub fn is_local_variable(&self) -> bool {
         self.variant.is_local_variable()
@@ -1341,6 +1464,19 @@ impl ValueConstructor {
             }
         }
     }
+
+    /// The version given to the `@since` attribute of the function or
+    /// constant this value refers to, if any.
+    pub(crate) fn get_since(&self) -> Option<&str> {
+        match &self.variant {
+            ValueConstructorVariant::LocalConstant { .. }
+            | ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::Record { .. } => None,
+
+            ValueConstructorVariant::ModuleFn { since, .. }
+            | ValueConstructorVariant::ModuleConstant { since, .. } => since.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1543,7 +1679,11 @@ fn assert_suitable_main_function(
         module: module_name.clone(),
     };
 
-    // The value must be a module function
+    // The value must be a module function that can be called from outside
+    // the module
+    if value.publicity.is_private() {
+        return Err(not_found());
+    }
     let ValueConstructorVariant::ModuleFn {
         arity,
         implementations,
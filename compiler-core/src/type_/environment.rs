@@ -2,15 +2,20 @@ use pubgrub::range::Range;
 
 use crate::{
     analyse::TargetSupport,
-    ast::{PIPE_VARIABLE, Publicity},
+    ast::{Layer, PIPE_VARIABLE, Publicity},
     build::Target,
+    config::LintsConfig,
     error::edit_distance,
     reference::ReferenceTracker,
     uid::UniqueIdGenerator,
 };
 
 use super::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// The entities that can be used in a scope, keyed by their name and the layer
+/// they live in. The bool tracks if the entity has been used.
+pub type EntityUsages = HashMap<(EcoString, Layer), (EntityKind, SrcSpan, bool)>;
 
 #[derive(Debug)]
 pub struct Environment<'a> {
@@ -59,8 +64,9 @@ pub struct Environment<'a> {
     /// entity_usages is a stack of scopes. When an entity is created it is
     /// added to the top scope. When an entity is used we crawl down the scope
     /// stack for an entity with that name and mark it as used.
-    /// NOTE: The bool in the tuple here tracks if the entity has been used
-    pub entity_usages: Vec<HashMap<EcoString, (EntityKind, SrcSpan, bool)>>,
+    /// Types and values live in different layers, so a type and a value with
+    /// the same name are tracked separately.
+    pub entity_usages: Vec<EntityUsages>,
 
     /// Used to determine if all functions/constants need to support the current
     /// compilation target.
@@ -72,6 +78,20 @@ pub struct Environment<'a> {
     pub echo_found: bool,
 
     pub references: ReferenceTracker,
+
+    /// Values and types of the current module that are not being compiled
+    /// because the feature flag they require is not enabled, along with the
+    /// name of that feature.
+    pub disabled_values: HashMap<EcoString, EcoString>,
+    pub disabled_types: HashMap<EcoString, EcoString>,
+
+    /// The aliases of modules that are only imported when compiling for a
+    /// different target, along with that target.
+    pub other_target_imports: HashMap<EcoString, Target>,
+
+    /// The opt-in lints enabled in the `[lints]` section of the package's
+    /// `gleam.toml` that are checked while inferring expressions.
+    pub lints: LintsConfig,
 }
 
 impl<'a> Environment<'a> {
@@ -126,6 +146,10 @@ impl<'a> Environment<'a> {
             module_type_aliases: HashMap::new(),
             echo_found: false,
             references: ReferenceTracker::new(),
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
+            other_target_imports: HashMap::new(),
+            lints: LintsConfig::default(),
         }
     }
 }
@@ -154,6 +178,21 @@ pub enum EntityKind {
     },
 }
 
+impl EntityKind {
+    /// Whether the entity is a type or a value.
+    fn layer(&self) -> Layer {
+        match self {
+            EntityKind::ImportedType | EntityKind::PrivateType => Layer::Type,
+            EntityKind::PrivateConstant
+            | EntityKind::PrivateTypeConstructor(_)
+            | EntityKind::PrivateFunction
+            | EntityKind::ImportedConstructor
+            | EntityKind::ImportedValue
+            | EntityKind::Variable { .. } => Layer::Value,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ScopeResetData {
     local_values: im::HashMap<EcoString, ValueConstructor>,
@@ -390,32 +429,54 @@ impl Environment<'_> {
         name: &EcoString,
     ) -> Result<&TypeConstructor, UnknownTypeConstructorError> {
         match module_alias {
-            None => self
-                .module_types
-                .get(name)
-                .ok_or_else(|| UnknownTypeConstructorError::Type {
-                    name: name.clone(),
-                    hint: self.unknown_type_hint(name),
-                }),
+            None => {
+                self.module_types
+                    .get(name)
+                    .ok_or_else(|| match self.disabled_types.get(name) {
+                        Some(feature) => UnknownTypeConstructorError::Disabled {
+                            name: name.clone(),
+                            module: None,
+                            feature: feature.clone(),
+                        },
+                        None => UnknownTypeConstructorError::Type {
+                            name: name.clone(),
+                            hint: self.unknown_type_hint(name),
+                        },
+                    })
+            }
 
             Some((module_name, _)) => {
-                let (_, module) = self.imported_modules.get(module_name).ok_or_else(|| {
-                    UnknownTypeConstructorError::Module {
-                        name: module_name.clone(),
-                        suggestions: self
-                            .suggest_modules(module_name, Imported::Type(name.clone())),
-                    }
-                })?;
+                let (_, module) =
+                    self.imported_modules.get(module_name).ok_or_else(|| {
+                        match self.other_target_imports.get(module_name) {
+                            Some(target) => UnknownTypeConstructorError::OtherTargetModule {
+                                name: module_name.clone(),
+                                target: *target,
+                            },
+                            None => UnknownTypeConstructorError::Module {
+                                name: module_name.clone(),
+                                suggestions: self
+                                    .suggest_modules(module_name, Imported::Type(name.clone())),
+                            },
+                        }
+                    })?;
                 let _ = self.unused_modules.remove(module_name);
                 let _ = self.unused_module_aliases.remove(module_name);
-                module.get_public_type(name).ok_or_else(|| {
-                    UnknownTypeConstructorError::ModuleType {
-                        name: name.clone(),
-                        module_name: module.name.clone(),
-                        type_constructors: module.public_type_names(),
-                        imported_type_as_value: false,
-                    }
-                })
+                module
+                    .get_public_type(name)
+                    .ok_or_else(|| match module.disabled_types.get(name) {
+                        Some(feature) => UnknownTypeConstructorError::Disabled {
+                            name: name.clone(),
+                            module: Some(module.name.clone()),
+                            feature: feature.clone(),
+                        },
+                        None => UnknownTypeConstructorError::ModuleType {
+                            name: name.clone(),
+                            module_name: module.name.clone(),
+                            type_constructors: module.public_type_names(),
+                            imported_type_as_value: false,
+                        },
+                    })
             }
         }
     }
@@ -474,31 +535,62 @@ impl Environment<'_> {
         name: &EcoString,
     ) -> Result<&ValueConstructor, UnknownValueConstructorError> {
         match module {
-            None => self.scope.get(name).ok_or_else(|| {
-                let type_with_name_in_scope = self.module_types.keys().any(|type_| type_ == name);
-                UnknownValueConstructorError::Variable {
-                    name: name.clone(),
-                    variables: self.local_value_names(),
-                    type_with_name_in_scope,
-                }
-            }),
+            None => self
+                .scope
+                .get(name)
+                .ok_or_else(|| match self.disabled_values.get(name) {
+                    Some(feature) => UnknownValueConstructorError::Disabled {
+                        name: name.clone(),
+                        module: None,
+                        feature: feature.clone(),
+                    },
+                    None => UnknownValueConstructorError::Variable {
+                        name: name.clone(),
+                        variables: self.local_value_names(),
+                        type_with_name_in_scope: self
+                            .module_types
+                            .keys()
+                            .any(|type_| type_ == name),
+                    },
+                }),
 
             Some(module_name) => {
-                let (_, module) = self.imported_modules.get(module_name).ok_or_else(|| {
-                    UnknownValueConstructorError::Module {
-                        name: module_name.clone(),
-                        suggestions: self
-                            .suggest_modules(module_name, Imported::Value(name.clone())),
-                    }
-                })?;
+                let (_, module) =
+                    self.imported_modules.get(module_name).ok_or_else(|| {
+                        match self.other_target_imports.get(module_name) {
+                            Some(target) => UnknownValueConstructorError::OtherTargetModule {
+                                name: module_name.clone(),
+                                target: *target,
+                            },
+                            None => UnknownValueConstructorError::Module {
+                                name: module_name.clone(),
+                                suggestions: self
+                                    .suggest_modules(module_name, Imported::Value(name.clone())),
+                            },
+                        }
+                    })?;
                 let _ = self.unused_modules.remove(module_name);
                 let _ = self.unused_module_aliases.remove(module_name);
                 module.get_public_value(name).ok_or_else(|| {
-                    UnknownValueConstructorError::ModuleValue {
-                        name: name.clone(),
-                        module_name: module.name.clone(),
-                        value_constructors: module.public_value_names(),
-                        imported_value_as_type: false,
+                    if let Some(feature) = module.disabled_values.get(name) {
+                        return UnknownValueConstructorError::Disabled {
+                            name: name.clone(),
+                            module: Some(module.name.clone()),
+                            feature: feature.clone(),
+                        };
+                    }
+                    match module.opaque_type_of_constructor(name) {
+                        Some(type_name) => UnknownValueConstructorError::OpaqueConstructor {
+                            name: name.clone(),
+                            module_name: module.name.clone(),
+                            type_name: type_name.clone(),
+                        },
+                        None => UnknownValueConstructorError::ModuleValue {
+                            name: name.clone(),
+                            module_name: module.name.clone(),
+                            value_constructors: module.public_value_names(),
+                            imported_value_as_type: false,
+                        },
                     }
                 })
             }
@@ -623,48 +715,65 @@ impl Environment<'_> {
         location: SrcSpan,
         problems: &mut Problems,
     ) {
-        use EntityKind::*;
-
-        match self
+        let key = (name.clone(), kind.layer());
+        if let Some((kind, location, false)) = self
             .entity_usages
             .last_mut()
             .expect("Attempted to access non-existent entity usages scope")
-            .insert(name.clone(), (kind, location, false))
+            .insert(key.clone(), (kind, location, false))
         {
-            // Private types can be shadowed by a constructor with the same name
-            //
-            // TODO: Improve this so that we can tell if an imported overridden
-            // type is actually used or not by tracking whether usages apply to
-            // the value or type scope
-            Some((ImportedType | PrivateType, _, _)) => {}
-
-            Some((kind, location, false)) => {
-                // an entity was overwritten in the top most scope without being used
-                let mut unused = HashMap::with_capacity(1);
-                let _ = unused.insert(name, (kind, location, false));
-                self.handle_unused(unused, problems);
-            }
-
-            _ => {}
+            // an entity was overwritten in the top most scope without being used
+            let mut unused = HashMap::with_capacity(1);
+            let _ = unused.insert(key, (kind, location, false));
+            self.handle_unused(unused, problems);
         }
     }
 
-    /// Increments an entity's usage in the current or nearest enclosing scope
+    /// Increments a value's usage in the current or nearest enclosing scope
     pub fn increment_usage(&mut self, name: &EcoString) {
-        let mut name = name.clone();
+        self.increment_usage_in_layer(name, Layer::Value)
+    }
+
+    /// Increments a type's usage in the current or nearest enclosing scope
+    pub fn increment_type_usage(&mut self, name: &EcoString) {
+        self.increment_usage_in_layer(name, Layer::Type)
+    }
+
+    fn increment_usage_in_layer(&mut self, name: &EcoString, layer: Layer) {
+        let mut key = (name.clone(), layer);
 
         while let Some((kind, _, used)) = self
             .entity_usages
             .iter_mut()
             .rev()
-            .find_map(|scope| scope.get_mut(&name))
+            .find_map(|scope| scope.get_mut(&key))
         {
             *used = true;
 
             match kind {
                 // If a type constructor is used, we consider its type also used
-                EntityKind::PrivateTypeConstructor(type_name) if *type_name != name => {
-                    name = type_name.clone();
+                EntityKind::PrivateTypeConstructor(type_name) => {
+                    key = (type_name.clone(), Layer::Type);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Marks a module level value as used, even if there's a local variable
+    /// shadowing it.
+    pub fn increment_module_usage(&mut self, name: &EcoString) {
+        let Some(module_scope) = self.entity_usages.first_mut() else {
+            return;
+        };
+        let mut key = (name.clone(), Layer::Value);
+        while let Some((kind, _, used)) = module_scope.get_mut(&key) {
+            *used = true;
+
+            match kind {
+                // If a type constructor is used, we consider its type also used
+                EntityKind::PrivateTypeConstructor(type_name) => {
+                    key = (type_name.clone(), Layer::Type);
                 }
                 _ => break,
             }
@@ -698,18 +807,26 @@ impl Environment<'_> {
         }
     }
 
-    fn handle_unused(
-        &mut self,
-        unused: HashMap<EcoString, (EntityKind, SrcSpan, bool)>,
-        problems: &mut Problems,
-    ) {
-        for (name, (kind, location, _)) in unused.into_iter().filter(|(_, (_, _, used))| !used) {
+    fn handle_unused(&mut self, unused: EntityUsages, problems: &mut Problems) {
+        // Constructors imported along with their type are reported differently
+        // if the type is used, as the import can be narrowed to the type only.
+        let used_imported_types: HashSet<_> = unused
+            .iter()
+            .filter(|(_, (kind, _, used))| *used && *kind == EntityKind::ImportedType)
+            .map(|((name, _), _)| name.clone())
+            .collect();
+
+        for ((name, _), (kind, location, _)) in unused.into_iter().filter(|(_, (_, _, used))| !used)
+        {
             let warning = match kind {
                 EntityKind::ImportedType => Warning::UnusedType {
                     name,
                     imported: true,
                     location,
                 },
+                EntityKind::ImportedConstructor if used_imported_types.contains(&name) => {
+                    Warning::ImportedConstructorOnlyUsedAsType { name, location }
+                }
                 EntityKind::ImportedConstructor => Warning::UnusedConstructor {
                     name,
                     imported: true,
@@ -745,6 +862,28 @@ impl Environment<'_> {
             .collect()
     }
 
+    /// The error for using a module alias that is not imported.
+    ///
+    pub fn unknown_module_error(
+        &self,
+        name: &EcoString,
+        location: SrcSpan,
+        imported: Imported,
+    ) -> Error {
+        match self.other_target_imports.get(name) {
+            Some(target) => Error::ModuleImportedForOtherTarget {
+                location,
+                name: name.clone(),
+                target: *target,
+            },
+            None => Error::UnknownModule {
+                location,
+                name: name.clone(),
+                suggestions: self.suggest_modules(name, imported),
+            },
+        }
+    }
+
     /// Suggest modules to import or use, for an unknown module
     pub fn suggest_modules(&self, module: &str, imported: Imported) -> Vec<ModuleSuggestion> {
         let mut suggestions = self
@@ -3,13 +3,13 @@ use super::{
     expression::{ArgumentKind, CallKind},
 };
 use crate::{
-    ast::{BinOp, Layer, SrcSpan, TodoKind},
+    ast::{BinOp, Capability, Layer, SrcSpan, TodoKind},
     build::Target,
     type_::Type,
 };
 
 use camino::Utf8PathBuf;
-use ecow::EcoString;
+use ecow::{EcoString, eco_format};
 use hexpm::version::Version;
 use num_bigint::BigInt;
 #[cfg(test)]
@@ -143,6 +143,36 @@ impl ModuleSuggestion {
     }
 }
 
+/// Why the arguments given to an `@external` attribute don't match the ones
+/// of the function.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ExternalArgumentsProblem {
+    /// A name that is not one of the function's arguments.
+    UnknownArgument,
+    /// An argument that is listed more than once.
+    DuplicateArgument,
+    /// Some of the function's arguments are not listed.
+    MissingArguments,
+}
+
+/// Why a custom type can't implement a capability.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum UnsupportedCapabilityProblem {
+    /// The type has no variants, so there's no values to work with.
+    NoVariants,
+    /// The capability only works on types whose variants have no fields.
+    VariantWithFields { variant: EcoString },
+    /// The capability only works on types with no type parameters.
+    TypeParameters,
+    /// A field has a type that the generated functions can't work with.
+    UnsupportedField {
+        variant: EcoString,
+        label: Option<EcoString>,
+    },
+    /// The generated functions refer to a module that is not imported.
+    MissingImport { module: EcoString },
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Error {
     SrcImportingTest {
@@ -175,12 +205,62 @@ pub enum Error {
         hint: UnknownTypeHint,
     },
 
+    /// A value or type is being used, but it is not being compiled as it
+    /// requires a feature that is not enabled.
+    ///
+    /// ```gleam
+    /// @when(feature: "experimental")
+    /// fn wibble() { 1 }
+    ///
+    /// pub fn main() { wibble() }
+    /// //              ^^^^^^ `experimental` is not enabled in `gleam.toml`
+    /// ```
+    ///
+    /// `module` is the module defining it, if it's not the current one.
+    ///
+    DisabledDefinition {
+        location: SrcSpan,
+        name: EcoString,
+        module: Option<EcoString>,
+        feature: EcoString,
+    },
+
     UnknownModule {
         location: SrcSpan,
         name: EcoString,
         suggestions: Vec<ModuleSuggestion>,
     },
 
+    /// A module is used, but it's only imported when compiling for a
+    /// different target.
+    ///
+    /// ```gleam
+    /// @target(javascript)
+    /// import wibble
+    ///
+    /// pub fn main() { wibble.wobble() }
+    /// //              ^^^^^^ Not imported when compiling to Erlang
+    /// ```
+    ///
+    ModuleImportedForOtherTarget {
+        location: SrcSpan,
+        name: EcoString,
+        target: Target,
+    },
+
+    /// A discard name used in place of an expression. It can be used to find
+    /// out the type that is expected in that position:
+    ///
+    /// ```gleam
+    /// pub fn main() -> Int { wibble(_value) }
+    /// //                            ^^^^^^ Expected type `String`
+    /// ```
+    ///
+    TypedHole {
+        location: SrcSpan,
+        type_: Arc<Type>,
+    },
+
     UnknownModuleType {
         location: SrcSpan,
         name: EcoString,
@@ -198,6 +278,44 @@ pub enum Error {
         context: ModuleValueUsageContext,
     },
 
+    /// A constructor of an opaque type is used outside of the module where
+    /// the type is defined:
+    ///
+    /// ```gleam
+    /// // wibble.gleam
+    /// pub opaque type Wibble {
+    ///   Wobble
+    /// }
+    ///
+    /// // main.gleam
+    /// import wibble
+    /// pub fn main() {
+    ///   wibble.Wobble
+    ///   // ^^^^^^ This constructor is not accessible here
+    /// }
+    /// ```
+    ///
+    OpaqueConstructorOutsideModule {
+        location: SrcSpan,
+        name: EcoString,
+        module_name: EcoString,
+        type_name: EcoString,
+    },
+
+    /// List comprehensions are desugared to calls to the `gleam/list` module,
+    /// so it must be imported to use them:
+    ///
+    /// ```gleam
+    /// pub fn main() {
+    ///   [x * 2 | x <- [1, 2, 3]]
+    ///   // ^^^^^^^^^^^^^^^^^^^^^ `gleam/list` is not imported
+    /// }
+    /// ```
+    ///
+    ListComprehensionWithoutListModule {
+        location: SrcSpan,
+    },
+
     ModuleAliasUsedAsName {
         location: SrcSpan,
         name: EcoString,
@@ -217,6 +335,22 @@ pub enum Error {
         unknown_field: UnknownField,
     },
 
+    /// Both operands of a numeric operator have the same type, but it's the
+    /// wrong one for that operator. A common mistake is using an `Int`
+    /// operator with `Float`s or vice versa:
+    ///
+    /// ```gleam
+    /// 1.0 + 2.0
+    /// // ^ `+.` should be used instead
+    /// ```
+    ///
+    IncorrectNumericOperator {
+        location: SrcSpan,
+        operator: BinOp,
+        alternative: BinOp,
+        type_: Arc<Type>,
+    },
+
     IncorrectArity {
         location: SrcSpan,
         expected: usize,
@@ -234,6 +368,26 @@ pub enum Error {
         arity: usize,
     },
 
+    /// A constant used as a pattern must have an `Int`, `Float` or `String`
+    /// value, as any other value can't be matched on.
+    ///
+    /// ```gleam
+    /// import wibble
+    /// // wibble.wobble is `const wobble = [1, 2]`
+    ///
+    /// case [] {
+    ///   wibble.wobble -> 1
+    /// //^^^^^^^^^^^^^ Only literal constants can be used here
+    ///   _ -> 2
+    /// }
+    /// ```
+    ///
+    NonLiteralConstantPattern {
+        location: SrcSpan,
+        module: EcoString,
+        name: EcoString,
+    },
+
     IncorrectTypeArity {
         location: SrcSpan,
         name: EcoString,
@@ -241,6 +395,20 @@ pub enum Error {
         given: usize,
     },
 
+    /// A value is given a different number of explicit type arguments than
+    /// the number of type parameters it has:
+    ///
+    /// ```gleam
+    /// list.new<Int, String>()
+    /// ```
+    ///
+    IncorrectTypeArgumentCount {
+        location: SrcSpan,
+        type_: Arc<Type>,
+        expected: usize,
+        given: usize,
+    },
+
     CouldNotUnify {
         location: SrcSpan,
         situation: Option<UnifyErrorSituation>,
@@ -317,6 +485,7 @@ pub enum Error {
 
     DuplicateVarInPattern {
         location: SrcSpan,
+        first_location: SrcSpan,
         name: EcoString,
     },
 
@@ -393,6 +562,16 @@ pub enum Error {
         cycle: Vec<EcoString>,
     },
 
+    /// A custom type can never be constructed, as each of its variants holds
+    /// a value of the type itself, either directly or through other types
+    /// with the same problem.
+    /// e.g.
+    ///     type Wibble { Wibble(Wibble) }
+    InfiniteType {
+        location: SrcSpan,
+        cycle: Vec<EcoString>,
+    },
+
     /// A function has been given an external implementation but not all the
     /// type annotations have been given. The annotations are required as we
     /// cannot infer the types of external implementations.
@@ -401,6 +580,61 @@ pub enum Error {
         kind: MissingAnnotation,
     },
 
+    /// A function annotated with `@benchmark` that can't be run by
+    /// `gleam bench`, as it's either private or takes some arguments.
+    /// e.g.
+    ///     @benchmark
+    ///     fn sort_list(list) { ... }
+    InvalidBenchmarkFunction {
+        location: SrcSpan,
+        name: EcoString,
+    },
+
+    /// A custom type annotated with `@implements` for a capability the
+    /// compiler can't generate a function for.
+    /// e.g.
+    ///     @implements(comparable)
+    ///     type Wibble { Wibble(Int) }
+    UnsupportedCapability {
+        location: SrcSpan,
+        capability: Capability,
+        type_name: EcoString,
+        problem: UnsupportedCapabilityProblem,
+    },
+
+    /// The arguments an external function is called with must be the
+    /// arguments of the Gleam function, each listed exactly once.
+    /// e.g.
+    ///     @external(erlang, "lists", "foldl", [fun, initial])
+    ///     fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+    InvalidExternalArguments {
+        location: SrcSpan,
+        problem: ExternalArgumentsProblem,
+        arguments: Vec<EcoString>,
+    },
+
+    /// A function annotated with `@memoize` that has an external
+    /// implementation, so there's no Gleam code to cache the results of.
+    /// e.g.
+    ///     @memoize
+    ///     @external(erlang, "wibble", "wobble")
+    ///     fn wobble(x: Int) -> Int
+    MemoizedExternalFunction {
+        location: SrcSpan,
+        name: EcoString,
+    },
+
+    /// An argument of a function annotated with `@memoize` whose type can't
+    /// be used as a key of the cache. Only `Int`, `Float`, `String`, `Bool`
+    /// and `Nil` arguments are allowed, unless the argument is discarded.
+    /// e.g.
+    ///     @memoize
+    ///     fn sum(list: List(Int)) -> Int { ... }
+    UnmemoizableArgument {
+        location: SrcSpan,
+        type_: Arc<Type>,
+    },
+
     /// A function has been given without either a Gleam implementation or an
     /// external one.
     NoImplementation {
@@ -435,6 +669,9 @@ pub enum Error {
     InexhaustiveCaseExpression {
         location: SrcSpan,
         missing: Vec<EcoString>,
+        /// Whether the case matches on a `String` using prefix patterns
+        /// without having a catch-all pattern for all the other strings.
+        missing_string_catch_all: bool,
     },
 
     /// A case expression is missing its body.
@@ -632,7 +869,7 @@ pub enum LiteralCollectionKind {
     Record,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Named {
     Type,
     TypeAlias,
@@ -711,6 +948,25 @@ pub enum Warning {
         location: SrcSpan,
     },
 
+    /// A block ending with a `use` evaluates to the value returned by the
+    /// `use` callback, which is lost if the block itself is discarded:
+    ///
+    /// ```gleam
+    /// pub fn main() {
+    ///   {
+    ///     use x <- result.try(wibble())
+    ///  // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ the value of this is never used
+    ///     Ok(x + 1)
+    ///   }
+    ///   Nil
+    /// }
+    /// ```
+    ///
+    UnusedUseValue {
+        location: SrcSpan,
+        type_: Arc<Type>,
+    },
+
     NoFieldsRecordUpdate {
         location: SrcSpan,
     },
@@ -736,6 +992,20 @@ pub enum Warning {
         name: EcoString,
     },
 
+    /// A constructor imported along with its type, when only the type is used:
+    ///
+    /// ```gleam
+    /// import wibble.{type Wibble, Wibble}
+    /// //                          ^^^^^^ only the type is used
+    ///
+    /// pub fn main(x: Wibble) { x }
+    /// ```
+    ///
+    ImportedConstructorOnlyUsedAsType {
+        location: SrcSpan,
+        name: EcoString,
+    },
+
     UnusedImportedModule {
         location: SrcSpan,
         name: EcoString,
@@ -918,6 +1188,246 @@ pub enum Warning {
     JavaScriptIntUnsafe {
         location: SrcSpan,
     },
+
+    /// When targeting JavaScript and an arithmetic expression made only of
+    /// `Int` literals evaluates to a value that lies outside the range
+    /// `Number.MIN_SAFE_INTEGER` - `Number.MAX_SAFE_INTEGER`, even if all of
+    /// its literals are safe:
+    ///
+    /// ```gleam
+    /// 9_007_199_254_740_991 + 1
+    /// ```
+    ///
+    IntegerOverflow {
+        location: SrcSpan,
+    },
+
+    /// When a recursive function builds up a string by appending to one of
+    /// its own parameters with `<>`:
+    ///
+    /// ```gleam
+    /// fn join(list, acc) {
+    ///   case list {
+    ///     [] -> acc
+    ///     [first, ..rest] -> join(rest, acc <> first)
+    ///     //                            ^^^^^^^^^^^^
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// On some targets this copies the accumulated string at each step. It can
+    /// be disabled by setting `inefficient_string_building` to `false` in the
+    /// `[lints]` section of the package's `gleam.toml`.
+    ///
+    InefficientStringBuilding {
+        location: SrcSpan,
+    },
+
+    /// A recursive function grows a list it accumulates in one of its own
+    /// parameters by appending to its end with `list.append`:
+    ///
+    /// ```gleam
+    /// fn double(list, acc) {
+    ///   case list {
+    ///     [] -> acc
+    ///     [first, ..rest] -> double(rest, list.append(acc, [first * 2]))
+    ///     //                              ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// This copies the accumulated list at each step. It can be disabled by
+    /// setting `inefficient_list_building` to `false` in the `[lints]` section
+    /// of the package's `gleam.toml`.
+    ///
+    InefficientListBuilding {
+        location: SrcSpan,
+    },
+
+    /// A name that doesn't follow one of the naming conventions enabled in
+    /// the `[lints]` section of the package's `gleam.toml`. For example, with
+    /// `snake_case_function_names = true`:
+    ///
+    /// ```gleam
+    /// pub fn wibble__wobble() { todo }
+    /// //     ^^^^^^^^^^^^^^ two underscores in a row
+    /// ```
+    ///
+    NamingConvention {
+        location: SrcSpan,
+        kind: Named,
+        name: EcoString,
+        problem: NamingConventionProblem,
+    },
+
+    /// A label of a private function that is given to an argument the
+    /// function discards, and that is never used by any of its calls. Only
+    /// reported if `unused_labels` is enabled in the `[lints]` section of the
+    /// package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// fn wibble(wobble _) { todo }
+    /// //        ^^^^^^
+    /// ```
+    ///
+    UnusedLabel {
+        location: SrcSpan,
+        function: EcoString,
+        label: EcoString,
+    },
+
+    /// An anonymous function that discards all of its arguments, so the value
+    /// it returns can't depend on them. Only reported if `constant_functions`
+    /// is enabled in the `[lints]` section of the package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// list.map(items, fn(_) { 0 })
+    /// //              ^^^^^
+    /// ```
+    ///
+    ConstantFunction {
+        location: SrcSpan,
+    },
+
+    /// A `case` expression matching on a single variable where every clause
+    /// returns that variable unchanged, so it always evaluates to its
+    /// subject. Only reported if `identity_cases` is enabled in the `[lints]`
+    /// section of the package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// case x { Ok(_) -> x  Error(_) -> x }
+    /// // ^^^^^^
+    /// ```
+    ///
+    IdentityCase {
+        location: SrcSpan,
+    },
+
+    /// A `todo` or `panic` in one of the `src` modules of a package, which
+    /// would crash the programs of anyone using the package if it were run.
+    /// Only reported if `todo_and_panic_in_library` is enabled in the
+    /// `[lints]` section of the package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// pub fn parse(input: String) -> Result(Int, Nil) { todo }
+    /// //                                                 ^^^^
+    /// ```
+    ///
+    TodoOrPanicInLibrary {
+        location: SrcSpan,
+        kind: TodoOrPanic,
+    },
+
+    /// A pipeline where every step is just a function referenced by name,
+    /// called with the previous value, so it could be written as a
+    /// composition of those functions. Only reported if
+    /// `composable_pipelines` is enabled in the `[lints]` section of the
+    /// package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// x |> list.reverse |> list.length
+    /// // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    PipelineComposable {
+        location: SrcSpan,
+    },
+
+    /// A function whose annotations make its type less general than the one
+    /// that would be inferred for it without them. Only reported if
+    /// `overly_specific_annotations` is enabled in the `[lints]` section of
+    /// the package's `gleam.toml`:
+    ///
+    /// ```gleam
+    /// fn identity(x: Int) -> Int { x }
+    /// // ^^^^^^^^^^^^^^^^^^^^^^^ Could be `fn(a) -> a`
+    /// ```
+    ///
+    OverlySpecificAnnotation {
+        location: SrcSpan,
+        inferred: Arc<Type>,
+    },
+
+    /// A `case` expression with a single clause, matching its only subject
+    /// with a pattern that can't fail and no guard. This is just a `let`
+    /// assignment. Only reported if `single_arm_cases` is enabled in the
+    /// `[lints]` section of the package's config:
+    ///
+    /// ```gleam
+    /// case wibble { #(a, b) -> a + b }
+    /// // Could be written as
+    /// let #(a, b) = wibble
+    /// a + b
+    /// ```
+    ///
+    SingleArmCase {
+        location: SrcSpan,
+    },
+
+    /// A boolean expression that can be written in a simpler way, in terms of
+    /// one of its operands:
+    ///
+    /// ```gleam
+    /// x == True  // Could be written as `x`
+    /// x != True  // Could be written as `!x`
+    /// !!x        // Could be written as `x`
+    /// ```
+    ///
+    SimplifiableBool {
+        location: SrcSpan,
+        operand: SrcSpan,
+        simplified: SimplifiedBool,
+    },
+
+    /// An anonymous function that only calls another function with all of
+    /// its arguments, in the same order. That function can be used directly:
+    ///
+    /// ```gleam
+    /// list.map(items, fn(x) { int.to_string(x) })
+    /// // Could be written as
+    /// list.map(items, int.to_string)
+    /// ```
+    ///
+    UnnecessaryFunctionWrapper {
+        location: SrcSpan,
+        /// The location of the function being called.
+        function: SrcSpan,
+    },
+}
+
+/// How a boolean expression can be simplified, in terms of its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SimplifiedBool {
+    /// `x == True` is just `x`.
+    Operand,
+    /// `x == False` is `!x`.
+    Negated,
+    /// `a < b == False` is `!{ a < b }`, the operand needs to be wrapped in a
+    /// block for the negation to apply to all of it.
+    NegatedBlock,
+}
+
+impl SimplifiedBool {
+    /// The simplified expression, given the source code of the operand.
+    pub fn replacement(&self, operand: &str) -> EcoString {
+        match self {
+            SimplifiedBool::Operand => operand.into(),
+            SimplifiedBool::Negated => eco_format!("!{operand}"),
+            SimplifiedBool::NegatedBlock => eco_format!("!{{ {operand} }}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum NamingConventionProblem {
+    /// The name has repeated or trailing underscores.
+    NotSnakeCase,
+    TooShort {
+        minimum_length: usize,
+    },
+    TooLong {
+        maximum_length: usize,
+    },
 }
 
 #[derive(Debug, Eq, Copy, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -1006,16 +1516,24 @@ impl Error {
             | Error::BitArraySegmentError { location, .. }
             | Error::UnknownVariable { location, .. }
             | Error::UnknownType { location, .. }
+            | Error::DisabledDefinition { location, .. }
             | Error::UnknownModule { location, .. }
+            | Error::ModuleImportedForOtherTarget { location, .. }
+            | Error::TypedHole { location, .. }
             | Error::UnknownModuleType { location, .. }
             | Error::UnknownModuleValue { location, .. }
+            | Error::OpaqueConstructorOutsideModule { location, .. }
             | Error::ModuleAliasUsedAsName { location, .. }
+            | Error::ListComprehensionWithoutListModule { location }
             | Error::NotFn { location, .. }
             | Error::UnknownRecordField { location, .. }
+            | Error::IncorrectNumericOperator { location, .. }
             | Error::IncorrectArity { location, .. }
             | Error::UnsafeRecordUpdate { location, .. }
             | Error::UnnecessarySpreadOperator { location, .. }
+            | Error::NonLiteralConstantPattern { location, .. }
             | Error::IncorrectTypeArity { location, .. }
+            | Error::IncorrectTypeArgumentCount { location, .. }
             | Error::CouldNotUnify { location, .. }
             | Error::RecursiveType { location, .. }
             | Error::DuplicateName {
@@ -1044,7 +1562,13 @@ impl Error {
             | Error::ArgumentNameAlreadyUsed { location, .. }
             | Error::UnlabelledAfterlabelled { location, .. }
             | Error::RecursiveTypeAlias { location, .. }
+            | Error::InfiniteType { location, .. }
             | Error::ExternalMissingAnnotation { location, .. }
+            | Error::InvalidBenchmarkFunction { location, .. }
+            | Error::InvalidExternalArguments { location, .. }
+            | Error::MemoizedExternalFunction { location, .. }
+            | Error::UnmemoizableArgument { location, .. }
+            | Error::UnsupportedCapability { location, .. }
             | Error::NoImplementation { location, .. }
             | Error::UnsupportedExpressionTarget { location, .. }
             | Error::InvalidExternalJavascriptModule { location, .. }
@@ -1104,11 +1628,13 @@ impl Warning {
             | Warning::ImplicitlyDiscardedResult { location, .. }
             | Warning::UnusedLiteral { location, .. }
             | Warning::UnusedValue { location, .. }
+            | Warning::UnusedUseValue { location, .. }
             | Warning::NoFieldsRecordUpdate { location, .. }
             | Warning::AllFieldsRecordUpdate { location, .. }
             | Warning::UnusedType { location, .. }
             | Warning::UnusedConstructor { location, .. }
             | Warning::UnusedImportedValue { location, .. }
+            | Warning::ImportedConstructorOnlyUsedAsType { location, .. }
             | Warning::UnusedImportedModule { location, .. }
             | Warning::UnusedImportedModuleAlias { location, .. }
             | Warning::UnusedPrivateModuleConstant { location, .. }
@@ -1129,7 +1655,20 @@ impl Warning {
             | Warning::UnreachableCodeAfterPanic { location, .. }
             | Warning::RedundantPipeFunctionCapture { location, .. }
             | Warning::FeatureRequiresHigherGleamVersion { location, .. }
-            | Warning::JavaScriptIntUnsafe { location, .. } => *location,
+            | Warning::JavaScriptIntUnsafe { location, .. }
+            | Warning::IntegerOverflow { location }
+            | Warning::InefficientStringBuilding { location, .. }
+            | Warning::InefficientListBuilding { location, .. }
+            | Warning::NamingConvention { location, .. }
+            | Warning::UnusedLabel { location, .. }
+            | Warning::ConstantFunction { location }
+            | Warning::IdentityCase { location }
+            | Warning::TodoOrPanicInLibrary { location, .. }
+            | Warning::PipelineComposable { location }
+            | Warning::OverlySpecificAnnotation { location, .. }
+            | Warning::SingleArmCase { location }
+            | Warning::SimplifiableBool { location, .. }
+            | Warning::UnnecessaryFunctionWrapper { location, .. } => *location,
         }
     }
 
@@ -1149,17 +1688,34 @@ pub enum UnknownValueConstructorError {
         type_with_name_in_scope: bool,
     },
 
+    Disabled {
+        name: EcoString,
+        module: Option<EcoString>,
+        feature: EcoString,
+    },
+
     Module {
         name: EcoString,
         suggestions: Vec<ModuleSuggestion>,
     },
 
+    OtherTargetModule {
+        name: EcoString,
+        target: Target,
+    },
+
     ModuleValue {
         name: EcoString,
         module_name: EcoString,
         value_constructors: Vec<EcoString>,
         imported_value_as_type: bool,
     },
+
+    OpaqueConstructor {
+        name: EcoString,
+        module_name: EcoString,
+        type_name: EcoString,
+    },
 }
 
 pub fn convert_get_value_constructor_error(
@@ -1179,12 +1735,31 @@ pub fn convert_get_value_constructor_error(
             type_with_name_in_scope,
         },
 
+        UnknownValueConstructorError::Disabled {
+            name,
+            module,
+            feature,
+        } => Error::DisabledDefinition {
+            location,
+            name,
+            module,
+            feature,
+        },
+
         UnknownValueConstructorError::Module { name, suggestions } => Error::UnknownModule {
             location: module_location.unwrap_or(location),
             name,
             suggestions,
         },
 
+        UnknownValueConstructorError::OtherTargetModule { name, target } => {
+            Error::ModuleImportedForOtherTarget {
+                location: module_location.unwrap_or(location),
+                name,
+                target,
+            }
+        }
+
         UnknownValueConstructorError::ModuleValue {
             name,
             module_name,
@@ -1198,6 +1773,17 @@ pub fn convert_get_value_constructor_error(
             type_with_same_name: imported_value_as_type,
             context: ModuleValueUsageContext::ModuleAccess,
         },
+
+        UnknownValueConstructorError::OpaqueConstructor {
+            name,
+            module_name,
+            type_name,
+        } => Error::OpaqueConstructorOutsideModule {
+            location,
+            name,
+            module_name,
+            type_name,
+        },
     }
 }
 
@@ -1232,11 +1818,22 @@ pub enum UnknownTypeConstructorError {
         hint: UnknownTypeHint,
     },
 
+    Disabled {
+        name: EcoString,
+        module: Option<EcoString>,
+        feature: EcoString,
+    },
+
     Module {
         name: EcoString,
         suggestions: Vec<ModuleSuggestion>,
     },
 
+    OtherTargetModule {
+        name: EcoString,
+        target: Target,
+    },
+
     ModuleType {
         name: EcoString,
         module_name: EcoString,
@@ -1257,12 +1854,31 @@ pub fn convert_get_type_constructor_error(
             hint,
         },
 
+        UnknownTypeConstructorError::Disabled {
+            name,
+            module,
+            feature,
+        } => Error::DisabledDefinition {
+            location: *location,
+            name,
+            module,
+            feature,
+        },
+
         UnknownTypeConstructorError::Module { name, suggestions } => Error::UnknownModule {
             location: module_location.unwrap_or(*location),
             name,
             suggestions,
         },
 
+        UnknownTypeConstructorError::OtherTargetModule { name, target } => {
+            Error::ModuleImportedForOtherTarget {
+                location: module_location.unwrap_or(*location),
+                name,
+                target,
+            }
+        }
+
         UnknownTypeConstructorError::ModuleType {
             name,
             module_name,
@@ -1472,15 +2088,25 @@ pub fn unify_wrong_returns(
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UnifyErrorSituation {
     /// Clauses in a case expression were found to return different types.
-    CaseClauseMismatch {
-        clause_location: SrcSpan,
-    },
+    CaseClauseMismatch { clause_location: SrcSpan },
 
     /// A function was found to return a value that did not match its return
     /// annotation.
     ReturnAnnotationMismatch,
 
-    PipeTypeMismatch,
+    /// The function on the right hand side of a pipe `a |> b` can't take the
+    /// value on its left hand side as its first argument.
+    PipeTypeMismatch {
+        /// The label of the function's first parameter, if it has one.
+        label: Option<EcoString>,
+    },
+
+    /// The value piped into a call `a |> b(c)` doesn't have the type of the
+    /// argument it's passed as.
+    PipeArgumentMismatch {
+        /// The label of the parameter the value is passed to, if it has one.
+        label: Option<EcoString>,
+    },
 
     /// The operands of a binary operator were incorrect.
     Operator(BinOp),
@@ -1492,8 +2118,21 @@ pub enum UnifyErrorSituation {
     ListTailMismatch,
 
     /// When two functions cannot be unified.
-    FunctionsMismatch {
-        reason: FunctionsMismatchReason,
+    FunctionsMismatch { reason: FunctionsMismatchReason },
+
+    /// The callback of a `use` expression returns a value that doesn't match
+    /// the return type the function on the right hand side of `<-` expects
+    /// it to have.
+    UseCallbackResultMismatch {
+        /// The location of the function on the right hand side of `<-`.
+        function_location: SrcSpan,
+    },
+
+    /// A numeric literal was used where a number of the other type was
+    /// expected by an annotation, like `let x: Float = 5`.
+    NumericLiteralMismatch {
+        /// The literal written as a number of the expected type, like `5.0`.
+        replacement: EcoString,
     },
 }
 
@@ -1525,7 +2164,7 @@ one, but all case clauses must return the same type.",
                 "The type of this returned value doesn't match the return type
 annotation of this function.",
             ),
-            Self::PipeTypeMismatch => {
+            Self::PipeTypeMismatch { .. } | Self::PipeArgumentMismatch { .. } => {
                 Some("This function cannot handle the argument sent through the (|>) pipe:")
             }
             Self::Operator(_op) => None,
@@ -1541,6 +2180,13 @@ this list don't match the type of the elements being prepended to it.",
             ),
 
             Self::FunctionsMismatch { .. } => None,
+
+            Self::NumericLiteralMismatch { .. } => None,
+
+            Self::UseCallbackResultMismatch { .. } => Some(
+                "The value returned by this `use` callback doesn't match the type
+the function on the right hand side of `<-` expects it to return.",
+            ),
         }
     }
 }
@@ -1563,6 +2209,7 @@ pub enum UnifyError {
 
     DuplicateVarInPattern {
         name: EcoString,
+        first_location: SrcSpan,
     },
 
     RecursiveType,
@@ -1623,7 +2270,14 @@ impl UnifyError {
                 Error::MissingVarInAlternativePattern { location, name }
             }
 
-            Self::DuplicateVarInPattern { name } => Error::DuplicateVarInPattern { location, name },
+            Self::DuplicateVarInPattern {
+                name,
+                first_location,
+            } => Error::DuplicateVarInPattern {
+                location,
+                first_location,
+                name,
+            },
 
             Self::RecursiveType => Error::RecursiveType { location },
         }
@@ -1663,7 +2317,9 @@ impl UnifyError {
                     location: last_statement_location,
                     expected: one.clone(),
                     given: other.clone(),
-                    situation: None,
+                    situation: Some(UnifyErrorSituation::UseCallbackResultMismatch {
+                        function_location,
+                    }),
                 },
 
                 FunctionsMismatchReason::Arity {
@@ -1703,6 +2359,8 @@ pub fn convert_unify_call_error(e: UnifyError, location: SrcSpan, kind: Argument
             last_statement_location,
             location,
         ),
+        ArgumentKind::Pipe { label } => convert_unify_error(e, location)
+            .with_unify_error_situation(UnifyErrorSituation::PipeArgumentMismatch { label }),
         ArgumentKind::Regular => convert_unify_error(e, location),
     }
 }
@@ -1711,12 +2369,19 @@ pub fn convert_unify_call_error(e: UnifyError, location: SrcSpan, kind: Argument
 /// safe integers as defined by Number.MIN_SAFE_INTEGER and Number.MAX_SAFE_INTEGER.
 ///
 pub fn check_javascript_int_safety(int_value: &BigInt, location: SrcSpan, problems: &mut Problems) {
+    if !is_javascript_safe_int(int_value) {
+        problems.warning(Warning::JavaScriptIntUnsafe { location });
+    }
+}
+
+/// Returns true if the given Int value is in the range of safe integers as
+/// defined by Number.MIN_SAFE_INTEGER and Number.MAX_SAFE_INTEGER.
+///
+pub fn is_javascript_safe_int(int_value: &BigInt) -> bool {
     let js_min_safe_integer = -9007199254740991i64;
     let js_max_safe_integer = 9007199254740991i64;
 
-    if *int_value < js_min_safe_integer.into() || *int_value > js_max_safe_integer.into() {
-        problems.warning(Warning::JavaScriptIntUnsafe { location });
-    }
+    *int_value >= js_min_safe_integer.into() && *int_value <= js_max_safe_integer.into()
 }
 
 /// When targeting Erlang, adds an error if the given Float value is outside the range
@@ -2,13 +2,14 @@ use super::{pipe::PipeTyper, *};
 use crate::{
     analyse::{infer_bit_array_option, name::check_argument_names},
     ast::{
-        Arg, Assignment, AssignmentKind, BinOp, BitArrayOption, BitArraySegment, CallArg, Clause,
-        ClauseGuard, Constant, FunctionLiteralKind, HasLocation, ImplicitCallArgOrigin, Layer,
-        RECORD_UPDATE_VARIABLE, RecordBeingUpdated, SrcSpan, Statement, TodoKind, TypeAst,
-        TypedArg, TypedAssignment, TypedClause, TypedClauseGuard, TypedConstant, TypedExpr,
-        TypedMultiPattern, TypedStatement, USE_ASSIGNMENT_VARIABLE, UntypedArg, UntypedAssignment,
-        UntypedClause, UntypedClauseGuard, UntypedConstant, UntypedConstantBitArraySegment,
-        UntypedExpr, UntypedExprBitArraySegment, UntypedMultiPattern, UntypedStatement, UntypedUse,
+        Arg, Assert, Assignment, AssignmentKind, BinOp, BitArrayOption, BitArraySegment, CallArg,
+        Clause, ClauseGuard, ComprehensionQualifier, Constant, FunctionLiteralKind, HasLocation,
+        ImplicitCallArgOrigin, Layer, RECORD_UPDATE_VARIABLE, RecordBeingUpdated, SELF_QUALIFIER,
+        SrcSpan, Statement, TodoKind, TypeAst, TypedArg, TypedAssert, TypedAssignment, TypedClause,
+        TypedClauseGuard, TypedConstant, TypedExpr, TypedMultiPattern, TypedStatement,
+        USE_ASSIGNMENT_VARIABLE, UntypedArg, UntypedAssert, UntypedAssignment, UntypedClause,
+        UntypedClauseGuard, UntypedConstant, UntypedConstantBitArraySegment, UntypedExpr,
+        UntypedExprBitArraySegment, UntypedMultiPattern, UntypedStatement, UntypedUse,
         UntypedUseAssignment, Use, UseAssignment,
     },
     build::Target,
@@ -73,6 +74,9 @@ pub struct FunctionDefinition {
     pub has_erlang_external: bool,
     /// The function has @external(JavaScript, "...", "...")
     pub has_javascript_external: bool,
+    /// The function was generated by the compiler for a capability of a type,
+    /// so no deprecation warnings are reported for the values it refers to.
+    pub is_generated: bool,
 }
 
 impl FunctionDefinition {
@@ -106,6 +110,7 @@ impl Implementations {
             has_body: _,
             has_erlang_external,
             has_javascript_external,
+            is_generated: _,
         } = current_function_definition;
 
         // If a pure-Gleam function uses a function that doesn't have a pure
@@ -169,14 +174,20 @@ pub enum CallKind {
     },
 }
 
-/// This is used to tell apart regular call arguments and the callback that is
-/// implicitly passed to a `use` function call.
-/// Both are going to be typed as usual but we want to tell them apart in order
-/// to report better error messages for `use` expressions.
+/// This is used to tell apart regular call arguments from the callback that is
+/// implicitly passed to a `use` function call, and from the value implicitly
+/// passed by a pipe.
+/// All are going to be typed as usual but we want to tell them apart in order
+/// to report better error messages for `use` expressions and pipes.
 ///
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum ArgumentKind {
     Regular,
+    /// The value coming from the previous step of a pipeline, passed to the
+    /// parameter with the given label.
+    Pipe {
+        label: Option<EcoString>,
+    },
     UseCallback {
         function_location: SrcSpan,
         assignments_location: SrcSpan,
@@ -304,6 +315,8 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 expression,
             } => self.infer_echo(location, expression),
 
+            UntypedExpr::Hole { location, .. } => Ok(self.infer_hole(location)),
+
             UntypedExpr::Var { location, name, .. } => {
                 self.infer_var(name, location, ReferenceRegistration::RegisterReferences)
             }
@@ -348,7 +361,10 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 location, value, ..
             } => Ok(self.infer_string(value, location)),
 
-            UntypedExpr::PipeLine { expressions } => Ok(self.infer_pipeline(expressions)),
+            UntypedExpr::PipeLine {
+                expressions,
+                operator_locations,
+            } => Ok(self.infer_pipeline(expressions, operator_locations)),
 
             UntypedExpr::Fn {
                 location,
@@ -372,6 +388,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 tail,
             } => self.infer_list(elements, tail, location),
 
+            UntypedExpr::ListComprehension {
+                location,
+                element,
+                qualifiers,
+            } => self.infer_list_comprehension(*element, qualifiers, location),
+
             UntypedExpr::Call {
                 location,
                 fun,
@@ -379,6 +401,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 ..
             } => Ok(self.infer_call(*fun, args, location, CallKind::Function)),
 
+            UntypedExpr::TypeApplication {
+                location,
+                fun,
+                type_arguments,
+            } => self.infer_type_application(*fun, type_arguments, location),
+
             UntypedExpr::BinOp {
                 location,
                 name,
@@ -424,8 +452,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         }
     }
 
-    fn infer_pipeline(&mut self, expressions: Vec1<UntypedExpr>) -> TypedExpr {
-        PipeTyper::infer(self, expressions)
+    fn infer_pipeline(
+        &mut self,
+        expressions: Vec1<UntypedExpr>,
+        operator_locations: Vec<SrcSpan>,
+    ) -> TypedExpr {
+        PipeTyper::infer(self, expressions, operator_locations)
     }
 
     fn infer_todo(
@@ -439,7 +471,10 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
         // Emit a warning that there is a todo in the code.
         let warning_location = match kind {
-            TodoKind::Keyword | TodoKind::IncompleteUse | TodoKind::EmptyBlock => location,
+            TodoKind::Keyword
+            | TodoKind::IncompleteUse
+            | TodoKind::EmptyBlock
+            | TodoKind::PipelineStep => location,
             TodoKind::EmptyFunction { function_location } => function_location,
         };
         self.problems.warning(Warning::Todo {
@@ -555,8 +590,17 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
     /// Emit a warning if the given expressions should not be discarded.
     /// e.g. because it's a literal (why was it made in the first place?)
     /// e.g. because it's of the `Result` type (errors should be handled)
+    /// e.g. because it's a block ending with a `use` whose callback value is
+    /// lost
     fn expression_discarded(&mut self, discarded: &TypedExpr) {
-        if discarded.is_literal() {
+        self.register_discarded_call(discarded);
+
+        if let Some(location) = discarded_use_value(discarded) {
+            self.problems.warning(Warning::UnusedUseValue {
+                location,
+                type_: discarded.type_(),
+            });
+        } else if discarded.is_literal() {
             self.problems.warning(Warning::UnusedLiteral {
                 location: discarded.location(),
             });
@@ -571,6 +615,62 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         }
     }
 
+    /// If the discarded expression is a call to a module function, or a
+    /// pipeline ending with one, the reference to the function is registered
+    /// as one whose return value is not used.
+    fn register_discarded_call(&mut self, discarded: &TypedExpr) {
+        let call = match discarded {
+            TypedExpr::Pipeline { finally, .. } => finally.as_ref(),
+            _ => discarded,
+        };
+        let TypedExpr::Call { fun, .. } = call else {
+            return;
+        };
+
+        match fun.as_ref() {
+            TypedExpr::Var {
+                location,
+                constructor,
+                name: referenced_name,
+            } => {
+                let ValueConstructorVariant::ModuleFn { module, name, .. } = &constructor.variant
+                else {
+                    return;
+                };
+                let kind = if name == referenced_name {
+                    ReferenceKind::Unqualified
+                } else {
+                    ReferenceKind::Alias
+                };
+                self.environment
+                    .references
+                    .register_discarded_value_reference(
+                        module.clone(),
+                        name.clone(),
+                        *location,
+                        kind,
+                    );
+            }
+
+            TypedExpr::ModuleSelect {
+                location,
+                field_start,
+                constructor: ModuleValueConstructor::Fn { module, name, .. },
+                ..
+            } => self
+                .environment
+                .references
+                .register_discarded_value_reference(
+                    module.clone(),
+                    name.clone(),
+                    SrcSpan::new(*field_start, location.end),
+                    ReferenceKind::Qualified,
+                ),
+
+            _ => {}
+        }
+    }
+
     pub(crate) fn infer_statements(
         &mut self,
         untyped: Vec1<UntypedStatement>,
@@ -583,6 +683,20 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         self.infer_iter_statements(location, count, untyped.into_iter())
     }
 
+    // Reports a discard name used as an expression, returning an invalid
+    // expression in its place.
+    fn infer_hole(&mut self, location: SrcSpan) -> TypedExpr {
+        // The hole is typed as whatever it would need to be to type check, so
+        // once the rest of the function has been inferred its type is the
+        // one expected in this position.
+        let type_ = self.new_unbound_var();
+        self.problems.error(Error::TypedHole {
+            location,
+            type_: type_.clone(),
+        });
+        TypedExpr::Invalid { location, type_ }
+    }
+
     // Helper to create a new error expr.
     fn error_expr(&mut self, location: SrcSpan) -> TypedExpr {
         TypedExpr::Invalid {
@@ -644,6 +758,11 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                     let assignment = self.infer_assignment(assignment);
                     statements.push(Statement::Assignment(assignment));
                 }
+
+                Statement::Assert(assert) => {
+                    let assert = self.infer_assert(assert);
+                    statements.push(Statement::Assert(assert));
+                }
             }
         }
 
@@ -812,6 +931,17 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         self.already_warned_for_unreachable_code = already_warned_for_unreachable_code;
         self.previous_panics = false;
 
+        let wrapped_function = match kind {
+            FunctionLiteralKind::Anonymous { .. } => wrapped_function(&args, &body),
+            FunctionLiteralKind::Capture { .. }
+            | FunctionLiteralKind::Use { .. }
+            | FunctionLiteralKind::TrailingBlock { .. } => None,
+        };
+        if let Some(function) = wrapped_function {
+            self.problems
+                .warning(Warning::UnnecessaryFunctionWrapper { location, function });
+        }
+
         Ok(TypedExpr::Fn {
             location,
             type_,
@@ -875,13 +1005,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             _ => None,
         };
         if let Some((location, kind)) = todopanic {
-            let args_location = match (args.first(), args.last()) {
-                (Some(first), Some(last)) => Some(SrcSpan {
-                    start: first.location().start,
-                    end: last.location().end,
-                }),
-                _ => None,
-            };
+            let args_location = SrcSpan::union_all(args.iter().map(|arg| arg.location()));
             self.problems.warning(Warning::TodoOrPanicUsedAsFunction {
                 kind,
                 location,
@@ -937,6 +1061,35 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         })
     }
 
+    /// List comprehensions are sugar for calls to the functions of the
+    /// `gleam/list` module, so they are desugared and the result is inferred:
+    ///
+    /// ```gleam
+    /// [#(x, y) | x <- xs, x > 0, y <- ys]
+    /// // is the same as
+    /// list.flat_map(list.filter(xs, fn(x) { x > 0 }), fn(x) {
+    ///   list.map(ys, fn(y) { #(x, y) })
+    /// })
+    /// ```
+    ///
+    fn infer_list_comprehension(
+        &mut self,
+        element: UntypedExpr,
+        qualifiers: Vec<ComprehensionQualifier>,
+        location: SrcSpan,
+    ) -> Result<TypedExpr, Error> {
+        let list_module = self
+            .environment
+            .imported_modules
+            .iter()
+            .find(|(_, (_, module))| module.name == "gleam/list")
+            .map(|(alias, _)| alias.clone())
+            .ok_or(Error::ListComprehensionWithoutListModule { location })?;
+
+        let desugared = desugar_list_comprehension(&list_module, element, qualifiers, location);
+        self.infer(desugared)
+    }
+
     fn infer_tuple(
         &mut self,
         elements: Vec<UntypedExpr>,
@@ -1032,6 +1185,18 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         label_location: SrcSpan,
         usage: FieldAccessUsage,
     ) -> TypedExpr {
+        let is_self_qualified =
+            matches!(&container, UntypedExpr::Var { name, .. } if self.is_self_qualifier(name));
+        if is_self_qualified {
+            return match self.infer_self_qualified_value(label, location, label_location) {
+                Ok(value) => value,
+                Err(error) => {
+                    self.problems.error(error);
+                    self.error_expr(location)
+                }
+            };
+        }
+
         // Computes a potential module access. This will be used if a record access can't be used.
         // Computes both the inferred access and if it shadows a variable.
         let module_access = match &container {
@@ -1153,6 +1318,86 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         }
     }
 
+    /// `self` can be used to qualify the values of the current module, unless
+    /// it's the name of a variable or of an imported module.
+    fn is_self_qualifier(&self, name: &EcoString) -> bool {
+        name == SELF_QUALIFIER
+            && !self.environment.scope.contains_key(name)
+            && !self.environment.imported_modules.contains_key(name)
+    }
+
+    /// Infers a value of the current module qualified with `self`, like
+    /// `self.wibble`. This always refers to the module's own `wibble`, even if
+    /// a local variable with the same name shadows it.
+    fn infer_self_qualified_value(
+        &mut self,
+        label: EcoString,
+        location: SrcSpan,
+        label_location: SrcSpan,
+    ) -> Result<TypedExpr, Error> {
+        let current_module = &self.environment.current_module;
+        let is_module_value = |value: &&ValueConstructor| match &value.variant {
+            ValueConstructorVariant::ModuleFn { module, .. }
+            | ValueConstructorVariant::ModuleConstant { module, .. }
+            | ValueConstructorVariant::Record { module, .. } => module == current_module,
+            ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::LocalConstant { .. } => false,
+        };
+
+        // Values that are still being inferred are only in scope, while the
+        // ones that have already been inferred are also in the module values,
+        // where they can't be shadowed by a local variable.
+        let ValueConstructor {
+            publicity,
+            deprecation,
+            variant,
+            type_,
+        } = self
+            .environment
+            .scope
+            .get(&label)
+            .filter(is_module_value)
+            .or_else(|| {
+                self.environment
+                    .module_values
+                    .get(&label)
+                    .filter(is_module_value)
+            })
+            .cloned()
+            .ok_or_else(|| self.report_name_error(&label, &label_location))?;
+
+        self.environment.increment_module_usage(&label);
+
+        if let Deprecation::Deprecated { message } = &deprecation {
+            self.problems.warning(Warning::DeprecatedItem {
+                location: label_location,
+                message: message.clone(),
+                layer: Layer::Value,
+            })
+        }
+
+        self.narrow_implementations(label_location, &variant)?;
+
+        self.register_value_constructor_reference(
+            &label,
+            &variant,
+            label_location,
+            ReferenceKind::Qualified,
+        );
+
+        let type_ = self.instantiate(type_, &mut hashmap![]);
+        Ok(TypedExpr::Var {
+            location,
+            name: label,
+            constructor: ValueConstructor {
+                publicity,
+                deprecation,
+                variant,
+                type_,
+            },
+        })
+    }
+
     fn infer_tuple_index(
         &mut self,
         tuple: UntypedExpr,
@@ -1379,11 +1624,20 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         };
 
         let left = self.infer(left)?;
-        unify(input_type.clone(), left.type_()).map_err(|e| {
-            e.operator_situation(name)
-                .into_error(left.type_defining_location())
-        })?;
-        let right = self.infer(right)?;
+        // The right operand is inferred before the left one is checked, as
+        // its type is needed to tell if the wrong operator was used.
+        let right = self.infer(right);
+        if let Err(error) = unify(input_type.clone(), left.type_()) {
+            return Err(right
+                .ok()
+                .and_then(|right| incorrect_numeric_operator_error(name, &left, &right, location))
+                .unwrap_or_else(|| {
+                    error
+                        .operator_situation(name)
+                        .into_error(left.type_defining_location())
+                }));
+        }
+        let right = right?;
         unify(input_type, right.type_()).map_err(|e| {
             e.operator_situation(name)
                 .into_error(right.type_defining_location())
@@ -1391,13 +1645,56 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
         self.check_for_inefficient_empty_list_check(name, &left, &right, location);
 
-        Ok(TypedExpr::BinOp {
+        let binop = TypedExpr::BinOp {
             location,
             name,
             type_: output_type,
             left: Box::new(left),
             right: Box::new(right),
-        })
+        };
+
+        if self.environment.target == Target::JavaScript
+            && !self.current_function_definition.has_javascript_external
+        {
+            self.check_for_integer_overflow(&binop);
+        }
+
+        Ok(binop)
+    }
+
+    /// When targeting JavaScript, warns if an arithmetic expression made only
+    /// of `Int` literals evaluates to a value that is outside JavaScript's safe
+    /// integer range.
+    ///
+    /// If one of the operands is already unsafe we don't warn, as either that
+    /// literal or an inner expression will already have been reported.
+    ///
+    fn check_for_integer_overflow(&mut self, binop: &TypedExpr) {
+        let TypedExpr::BinOp {
+            location,
+            left,
+            right,
+            ..
+        } = binop
+        else {
+            return;
+        };
+
+        let Some(value) = binop.literal_int_value() else {
+            return;
+        };
+
+        let operand_is_safe = |operand: &TypedExpr| {
+            operand
+                .literal_int_value()
+                .is_some_and(|value| is_javascript_safe_int(&value))
+        };
+
+        if !is_javascript_safe_int(&value) && operand_is_safe(left) && operand_is_safe(right) {
+            self.problems.warning(Warning::IntegerOverflow {
+                location: *location,
+            });
+        }
     }
 
     /// Checks for inefficient usage of `list.length` for checking for the empty list.
@@ -1510,9 +1807,15 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 .map(|type_| self.instantiate(type_, &mut hashmap![]))
             {
                 Ok(annotated_type) => {
-                    if let Err(error) = unify(annotated_type, type_.clone())
+                    if let Err(error) = unify(annotated_type.clone(), type_.clone())
                         .map_err(|e| convert_unify_error(e, value.type_defining_location()))
                     {
+                        let error = match typed_numeric_literal(&value, &annotated_type) {
+                            Some(replacement) => error.with_unify_error_situation(
+                                UnifyErrorSituation::NumericLiteralMismatch { replacement },
+                            ),
+                            None => error,
+                        };
                         self.problems.error(error);
                     }
                 }
@@ -1556,6 +1859,26 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         }
     }
 
+    fn infer_assert(&mut self, assert: UntypedAssert) -> TypedAssert {
+        let Assert { location, value } = assert;
+        let value_location = value.location();
+        let value = match self.in_new_scope(|value_typer| value_typer.infer(value)) {
+            Ok(value) => value,
+            Err(error) => {
+                self.problems.error(error);
+                self.error_expr(value_location)
+            }
+        };
+
+        // The asserted value must be a boolean
+        if let Err(error) = unify(bool(), value.type_()) {
+            self.problems
+                .error(convert_unify_error(error, value.location()));
+        }
+
+        Assert { location, value }
+    }
+
     fn infer_assignment_kind(
         &mut self,
         kind: AssignmentKind<UntypedExpr>,
@@ -1659,8 +1982,9 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
         self.previous_panics = all_clauses_panic || any_subject_panics;
 
-        if let Err(e) = self.check_case_exhaustiveness(location, &subject_types, &typed_clauses) {
-            self.problems.error(e);
+        match self.check_case_exhaustiveness(location, &subject_types, &typed_clauses) {
+            Ok(()) => self.check_for_single_arm_case(location, &typed_subjects, &typed_clauses),
+            Err(e) => self.problems.error(e),
         };
 
         // We track if the case expression is used like an if: that is all its
@@ -2397,25 +2721,40 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 .environment
                 .imported_modules
                 .get(module_alias)
-                .ok_or_else(|| Error::UnknownModule {
-                    name: module_alias.clone(),
-                    location: *module_location,
-                    suggestions: self
-                        .environment
-                        .suggest_modules(module_alias, Imported::Value(label.clone())),
+                .ok_or_else(|| {
+                    self.environment.unknown_module_error(
+                        module_alias,
+                        *module_location,
+                        Imported::Value(label.clone()),
+                    )
                 })?;
 
-            let constructor =
-                module
-                    .get_public_value(&label)
-                    .ok_or_else(|| Error::UnknownModuleValue {
+            let constructor = module.get_public_value(&label).ok_or_else(|| {
+                if let Some(feature) = module.disabled_values.get(&label) {
+                    return Error::DisabledDefinition {
+                        location: select_location,
+                        name: label.clone(),
+                        module: Some(module.name.clone()),
+                        feature: feature.clone(),
+                    };
+                }
+                match module.opaque_type_of_constructor(&label) {
+                    Some(type_name) => Error::OpaqueConstructorOutsideModule {
+                        location: select_location,
+                        name: label.clone(),
+                        module_name: module.name.clone(),
+                        type_name: type_name.clone(),
+                    },
+                    None => Error::UnknownModuleValue {
                         name: label.clone(),
                         location: select_location,
                         module_name: module.name.clone(),
                         value_constructors: module.public_value_names(),
                         type_with_same_name: module.get_public_type(&label).is_some(),
                         context: ModuleValueUsageContext::ModuleAccess,
-                    })?;
+                    },
+                }
+            })?;
 
             // Emit a warning if the value being used is deprecated.
             if let Deprecation::Deprecated { message } = &constructor.deprecation {
@@ -2595,6 +2934,11 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
             TypedExpr::Var { name, .. } => (None, name),
 
+            // If the constructor could not be inferred its error has already
+            // been reported, so we don't also complain it's not a record
+            // constructor.
+            TypedExpr::Invalid { .. } => return Ok(self.error_expr(location)),
+
             constructor => {
                 return Err(Error::RecordUpdateInvalidConstructor {
                     location: constructor.location(),
@@ -2683,6 +3027,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             .map(
                 |arg @ UntypedRecordUpdateArg {
                      label,
+                     label_location,
                      value,
                      location,
                  }| {
@@ -2717,7 +3062,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                                 Err(self.unknown_field_error(
                                     variant.field_names(),
                                     record_type.clone(),
-                                    *location,
+                                    *label_location,
                                     label.clone(),
                                     FieldAccessUsage::RecordUpdate,
                                 ))
@@ -2838,6 +3183,25 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         unify(return_type_copy, record_type.clone())
             .map_err(|e| convert_unify_error(e, record.location()))?;
 
+        // Type parameters that are not used by any of the fields, like phantom
+        // types, can't be changed by the update. So the new record keeps the
+        // ones of the record being updated instead of getting fresh ones.
+        let fields_variables = args_types
+            .iter()
+            .flat_map(|argument| argument.unbound_type_variables())
+            .collect_vec();
+        if let (Some((_, _, parameters)), Some((_, _, record_parameters))) = (
+            return_type.named_type_information(),
+            record_type.named_type_information(),
+        ) {
+            for (parameter, record_parameter) in parameters.into_iter().zip(record_parameters) {
+                if parameter.is_unbound() && !fields_variables.contains(&parameter) {
+                    unify(parameter, record_parameter)
+                        .map_err(|e| convert_unify_error(e, record.location()))?;
+                }
+            }
+        }
+
         let record_index = record_type.custom_type_inferred_variant();
         // Updating a record with only one variant is always safe
         if variants_count == 1 {
@@ -2985,25 +3349,31 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                     .environment
                     .imported_modules
                     .get(module_name)
-                    .ok_or_else(|| Error::UnknownModule {
-                        location: *module_location,
-                        name: module_name.clone(),
-                        suggestions: self
-                            .environment
-                            .suggest_modules(module_name, Imported::Value(name.clone())),
+                    .ok_or_else(|| {
+                        self.environment.unknown_module_error(
+                            module_name,
+                            *module_location,
+                            Imported::Value(name.clone()),
+                        )
                     })?;
-                module
-                    .values
-                    .get(name)
-                    .cloned()
-                    .ok_or_else(|| Error::UnknownModuleValue {
-                        location: *location,
-                        module_name: module_name.clone(),
-                        name: name.clone(),
-                        value_constructors: module.public_value_names(),
-                        type_with_same_name: module.get_public_type(name).is_some(),
-                        context: ModuleValueUsageContext::ModuleAccess,
-                    })?
+                module.values.get(name).cloned().ok_or_else(|| {
+                    match module.disabled_values.get(name) {
+                        Some(feature) => Error::DisabledDefinition {
+                            location: *location,
+                            name: name.clone(),
+                            module: Some(module.name.clone()),
+                            feature: feature.clone(),
+                        },
+                        None => Error::UnknownModuleValue {
+                            location: *location,
+                            module_name: module_name.clone(),
+                            name: name.clone(),
+                            value_constructors: module.public_value_names(),
+                            type_with_same_name: module.get_public_type(name).is_some(),
+                            context: ModuleValueUsageContext::ModuleAccess,
+                        },
+                    }
+                })?
             }
         };
 
@@ -3015,12 +3385,17 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         } = constructor;
 
         // Emit a warning if the value being used is deprecated.
-        if let Deprecation::Deprecated { message } = &deprecation {
-            self.problems.warning(Warning::DeprecatedItem {
-                location: *location,
-                message: message.clone(),
-                layer: Layer::Value,
-            })
+        match &deprecation {
+            Deprecation::Deprecated { message }
+                if !self.current_function_definition.is_generated =>
+            {
+                self.problems.warning(Warning::DeprecatedItem {
+                    location: *location,
+                    message: message.clone(),
+                    layer: Layer::Value,
+                })
+            }
+            Deprecation::Deprecated { .. } | Deprecation::NotDeprecated => {}
         }
 
         self.narrow_implementations(*location, &variant)?;
@@ -3093,7 +3468,18 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
     }
 
     fn report_name_error(&mut self, name: &EcoString, location: &SrcSpan) -> Error {
-        // First try to see if this is a module alias:
+        // If the value is defined in this module but it requires a feature
+        // that is not enabled we want to let the user know.
+        if let Some(feature) = self.environment.disabled_values.get(name) {
+            return Error::DisabledDefinition {
+                location: *location,
+                name: name.clone(),
+                module: None,
+                feature: feature.clone(),
+            };
+        }
+
+        // Then try to see if this is a module alias:
         // `import gleam/io`
         // `io.debug(io)`
         // Show nice error message for this case.
@@ -3429,6 +3815,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                     .map_err(|e| convert_unify_error(e, inferred.location()))
                 {
                     Err(e) => {
+                        let e = match constant_numeric_literal(&inferred, &const_ann) {
+                            Some(replacement) => e.with_unify_error_situation(
+                                UnifyErrorSituation::NumericLiteralMismatch { replacement },
+                            ),
+                            None => e,
+                        };
                         self.problems.error(e);
                         Constant::Invalid {
                             location: loc,
@@ -3524,6 +3916,37 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             .field_map())
     }
 
+    /// The explicitly given type arguments are unified with the type
+    /// parameters of the value, in the order they first appear in its type.
+    /// So in `pair<Int, String>`, where `pair` has type `fn(a, b) -> #(a, b)`,
+    /// `a` is `Int` and `b` is `String`.
+    ///
+    fn infer_type_application(
+        &mut self,
+        fun: UntypedExpr,
+        type_arguments: Vec<TypeAst>,
+        location: SrcSpan,
+    ) -> Result<TypedExpr, Error> {
+        let fun = self.infer(fun)?;
+        let type_ = fun.type_();
+        let parameters = type_.unbound_type_variables();
+        if parameters.len() != type_arguments.len() {
+            return Err(Error::IncorrectTypeArgumentCount {
+                location,
+                type_,
+                expected: parameters.len(),
+                given: type_arguments.len(),
+            });
+        }
+
+        for (parameter, argument) in parameters.into_iter().zip(type_arguments) {
+            let argument_type = self.type_from_ast(&argument)?;
+            unify(parameter, argument_type)
+                .map_err(|error| convert_unify_error(error, argument.location()))?;
+        }
+        Ok(fun)
+    }
+
     pub fn do_infer_call(
         &mut self,
         fun: UntypedExpr,
@@ -3764,7 +4187,15 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                         assignments_location,
                         last_statement_location,
                     },
-                    CallKind::Use { .. } | CallKind::Function => ArgumentKind::Regular,
+                    CallKind::Use { .. } | CallKind::Function => match implicit {
+                        Some(ImplicitCallArgOrigin::Pipe) => ArgumentKind::Pipe {
+                            label: fun
+                                .field_map()
+                                .and_then(|field_map| field_map.label_at(i as u32))
+                                .cloned(),
+                        },
+                        Some(_) | None => ArgumentKind::Regular,
+                    },
                 };
 
                 // We don't want to emit a warning for unreachable function call if the
@@ -4054,6 +4485,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             return Err(Error::InexhaustiveCaseExpression {
                 location,
                 missing: output.missing_patterns(self.environment),
+                missing_string_catch_all: output.diagnostics.missing_string_catch_all,
             });
         }
 
@@ -4073,6 +4505,35 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Ok(())
     }
 
+    /// Once a case expression is known to be exhaustive, if it has a single
+    /// subject and a single clause with no guard then that clause's pattern
+    /// can't fail, and the case could be a `let` assignment instead. Only
+    /// reported if `single_arm_cases` is enabled in the `[lints]` section of
+    /// the package's config.
+    ///
+    fn check_for_single_arm_case(
+        &mut self,
+        location: SrcSpan,
+        subjects: &[TypedExpr],
+        clauses: &[TypedClause],
+    ) {
+        if !self.environment.lints.single_arm_cases {
+            return;
+        }
+        match (subjects, clauses) {
+            ([subject], [clause])
+                if clause.pattern.len() == 1
+                    && clause.alternative_patterns.is_empty()
+                    && clause.guard.is_none() =>
+            {
+                self.problems.warning(Warning::SingleArmCase {
+                    location: SrcSpan::new(location.start, subject.location().end),
+                })
+            }
+            _ => (),
+        }
+    }
+
     fn track_feature_usage(&mut self, feature_kind: FeatureKind, location: SrcSpan) {
         let minimum_required_version = feature_kind.required_version();
 
@@ -4133,7 +4594,7 @@ fn extract_typed_use_call_assignments(
         .iter()
         .take(assignments_count)
         .map(|statement| match statement {
-            Statement::Expression(_) | Statement::Use(_) => None,
+            Statement::Expression(_) | Statement::Use(_) | Statement::Assert(_) => None,
             Statement::Assignment(assignment) => Some(UseAssignment {
                 location: assignment.location,
                 pattern: assignment.pattern.clone(),
@@ -4240,6 +4701,64 @@ struct UseCall {
     arguments: Vec<CallArg<UntypedExpr>>,
 }
 
+/// If both operands of a numeric operator are of the other numeric type
+/// the programmer most likely used the wrong operator, like `1.0 + 2.0`
+/// or `1 +. 2`. In that case this returns an error suggesting the right
+/// operator to use.
+///
+fn incorrect_numeric_operator_error(
+    name: BinOp,
+    left: &TypedExpr,
+    right: &TypedExpr,
+    location: SrcSpan,
+) -> Option<Error> {
+    let (alternative, alternative_type) = match name {
+        BinOp::LtInt => (BinOp::LtFloat, float()),
+        BinOp::LtEqInt => (BinOp::LtEqFloat, float()),
+        BinOp::GtEqInt => (BinOp::GtEqFloat, float()),
+        BinOp::GtInt => (BinOp::GtFloat, float()),
+        BinOp::AddInt => (BinOp::AddFloat, float()),
+        BinOp::SubInt => (BinOp::SubFloat, float()),
+        BinOp::MultInt => (BinOp::MultFloat, float()),
+        BinOp::DivInt => (BinOp::DivFloat, float()),
+        BinOp::LtFloat => (BinOp::LtInt, int()),
+        BinOp::LtEqFloat => (BinOp::LtEqInt, int()),
+        BinOp::GtEqFloat => (BinOp::GtEqInt, int()),
+        BinOp::GtFloat => (BinOp::GtInt, int()),
+        BinOp::AddFloat => (BinOp::AddInt, int()),
+        BinOp::SubFloat => (BinOp::SubInt, int()),
+        BinOp::MultFloat => (BinOp::MultInt, int()),
+        BinOp::DivFloat => (BinOp::DivInt, int()),
+        BinOp::And
+        | BinOp::Or
+        | BinOp::Eq
+        | BinOp::NotEq
+        | BinOp::RemainderInt
+        | BinOp::Concatenate => return None,
+    };
+
+    let is_alternative_type = |type_: Arc<Type>| {
+        if alternative_type.is_int() {
+            type_.is_int()
+        } else {
+            type_.is_float()
+        }
+    };
+    if !is_alternative_type(left.type_()) {
+        return None;
+    }
+    if !is_alternative_type(right.type_()) {
+        return None;
+    }
+
+    Some(Error::IncorrectNumericOperator {
+        location,
+        operator: name,
+        alternative,
+        type_: alternative_type,
+    })
+}
+
 fn get_use_expression_call(call: UntypedExpr) -> UseCall {
     // Ensure that the use's call is of the right structure. i.e. it is a
     // call to a function.
@@ -4325,6 +4844,7 @@ impl UseAssignments {
                 | Pattern::Tuple { .. }
                 | Pattern::BitArray { .. }
                 | Pattern::StringPrefix { .. }
+                | Pattern::Constant { .. }
                 | Pattern::Invalid { .. }) => {
                     let name: EcoString = format!("{USE_ASSIGNMENT_VARIABLE}{index}").into();
                     assignments.function_arguments.push(Arg {
@@ -4354,6 +4874,184 @@ impl UseAssignments {
     }
 }
 
+/// If the value is a numeric literal of the other numeric type than the
+/// expected one, the same number written as a literal of the expected type.
+/// For example `5` is `5.0` when a `Float` is expected.
+///
+fn typed_numeric_literal(value: &TypedExpr, expected: &Type) -> Option<EcoString> {
+    match value {
+        TypedExpr::Int { value, .. } if expected.is_float() => int_literal_as_float(value),
+        TypedExpr::Float { value, .. } if expected.is_int() => float_literal_as_int(value),
+        _ => None,
+    }
+}
+
+/// The same as `typed_numeric_literal`, for constants.
+///
+fn constant_numeric_literal(value: &TypedConstant, expected: &Type) -> Option<EcoString> {
+    match value {
+        Constant::Int { value, .. } if expected.is_float() => int_literal_as_float(value),
+        Constant::Float { value, .. } if expected.is_int() => float_literal_as_int(value),
+        _ => None,
+    }
+}
+
+/// `5` and `1_000` can be written as the floats `5.0` and `1_000.0`. Ints
+/// written in another base have no float literal equivalent.
+///
+fn int_literal_as_float(value: &str) -> Option<EcoString> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if digits
+        .chars()
+        .all(|char| char.is_ascii_digit() || char == '_')
+    {
+        Some(format!("{value}.0").into())
+    } else {
+        None
+    }
+}
+
+/// Floats without a fractional part, like `5.0` or `5.`, can be written as
+/// the int `5`. Other floats can't be written as an int without changing
+/// their value.
+///
+fn float_literal_as_int(value: &str) -> Option<EcoString> {
+    let (integer, fraction) = value.split_once('.')?;
+    if fraction.chars().all(|char| char == '0' || char == '_') {
+        Some(integer.into())
+    } else {
+        None
+    }
+}
+
+/// If the discarded expression is a block ending with a `use` whose callback
+/// value is what the whole block evaluates to, this is the location of that
+/// `use`. This is only the case when the function called by `use` returns the
+/// same type as its callback, like with `result.try`; other functions, like
+/// `list.each`, don't return what the callback produces.
+///
+/// A block that evaluates to `Nil` isn't reported as there's nothing to use.
+///
+fn discarded_use_value(discarded: &TypedExpr) -> Option<SrcSpan> {
+    let TypedExpr::Block { statements, .. } = discarded else {
+        return None;
+    };
+    let Statement::Use(use_) = statements.last() else {
+        return None;
+    };
+    let callback_type = use_
+        .call
+        .call_arguments()
+        .and_then(|arguments| arguments.last())
+        .and_then(|callback| callback.value.type_().return_type())?;
+
+    let type_ = discarded.type_();
+    if type_.is_nil() || type_.is_unbound() || !type_.same_as(&callback_type) {
+        None
+    } else {
+        Some(use_.location)
+    }
+}
+
+/// Builds the calls to `gleam/list` a list comprehension stands for. Each
+/// generator maps over its list, or flat maps if it's followed by other
+/// generators, after filtering it with the guards that come right after it.
+///
+fn desugar_list_comprehension(
+    list_module: &EcoString,
+    element: UntypedExpr,
+    qualifiers: Vec<ComprehensionQualifier>,
+    location: SrcSpan,
+) -> UntypedExpr {
+    let mut generators: Vec<(EcoString, SrcSpan, UntypedExpr, Vec<UntypedExpr>)> = vec![];
+    for qualifier in qualifiers {
+        match qualifier {
+            ComprehensionQualifier::Generator {
+                name,
+                name_location,
+                list,
+                ..
+            } => generators.push((name, name_location, list, vec![])),
+            ComprehensionQualifier::Filter(condition) => match generators.last_mut() {
+                Some((_, _, _, guards)) => guards.push(condition),
+                None => panic!("list comprehension starting with a filter"),
+            },
+        }
+    }
+
+    let call = |function: &str, list: UntypedExpr, callback: UntypedExpr| {
+        let arguments = [list, callback]
+            .into_iter()
+            .map(|value| CallArg {
+                label: None,
+                location: value.location(),
+                value,
+                implicit: Some(ImplicitCallArgOrigin::ListComprehension),
+            })
+            .collect();
+        UntypedExpr::Call {
+            location,
+            fun: Box::new(UntypedExpr::FieldAccess {
+                location,
+                label_location: location,
+                label: function.into(),
+                container: Box::new(UntypedExpr::Var {
+                    location,
+                    name: list_module.clone(),
+                }),
+            }),
+            arguments,
+        }
+    };
+
+    let callback = |name: &EcoString, name_location: SrcSpan, body: UntypedExpr| {
+        let names = if name.starts_with('_') {
+            ArgNames::Discard {
+                name: name.clone(),
+                location: name_location,
+            }
+        } else {
+            ArgNames::Named {
+                name: name.clone(),
+                location: name_location,
+            }
+        };
+        UntypedExpr::Fn {
+            location: SrcSpan::new(name_location.start, body.location().end),
+            kind: FunctionLiteralKind::Anonymous {
+                head: name_location,
+            },
+            end_of_head_byte_index: name_location.end,
+            arguments: vec![Arg {
+                names,
+                location: name_location,
+                annotation: None,
+                type_: (),
+            }],
+            body: Vec1::new(Statement::Expression(body)),
+            return_annotation: None,
+        }
+    };
+
+    let mut body = element;
+    let mut function = "map";
+    for (name, name_location, list, guards) in generators.into_iter().rev() {
+        let condition = guards.into_iter().reduce(|left, right| UntypedExpr::BinOp {
+            location: SrcSpan::new(left.location().start, right.location().end),
+            name: BinOp::And,
+            left: Box::new(left),
+            right: Box::new(right),
+        });
+        let list = match condition {
+            Some(condition) => call("filter", list, callback(&name, name_location, condition)),
+            None => list,
+        };
+        body = call(function, list, callback(&name, name_location, body));
+        function = "flat_map";
+    }
+    body
+}
+
 /// Used during `infer_record_update` to return information about the updated variant.
 #[derive(Debug)]
 struct RecordUpdateVariant<'a> {
@@ -4378,3 +5076,74 @@ impl RecordUpdateVariant<'_> {
         self.fields.keys().cloned().collect()
     }
 }
+
+/// If an anonymous function's body is a single call to another function,
+/// passing it all of its arguments in the same order, returns the location of
+/// the function being called:
+///
+/// ```gleam
+/// fn(a, b) { wibble.wobble(a, b) }
+/// //         ^^^^^^^^^^^^^ This could be used instead
+/// ```
+///
+/// Only functions referenced by name are considered, as any other expression
+/// would be evaluated each time the anonymous function is called, rather than
+/// once.
+///
+fn wrapped_function(args: &[TypedArg], body: &Vec1<TypedStatement>) -> Option<SrcSpan> {
+    let [
+        Statement::Expression(TypedExpr::Call {
+            fun,
+            args: call_args,
+            ..
+        }),
+    ] = body.as_slice()
+    else {
+        return None;
+    };
+    if call_args.len() != args.len() {
+        return None;
+    }
+
+    let names = args
+        .iter()
+        .map(|arg| arg.get_variable_name())
+        .collect::<Option<Vec<_>>>()?;
+
+    match fun.as_ref() {
+        TypedExpr::Var { .. } | TypedExpr::ModuleSelect { .. } => (),
+        _ => return None,
+    }
+
+    // Only arguments passed positionally are looked at: labels can make the
+    // arguments look like they are in a different order than the one they
+    // are actually passed in, and the wrapper would be harder to spot.
+    let passes_arguments_in_order =
+        call_args
+            .iter()
+            .zip(names)
+            .all(|(call_arg, name)| match call_arg {
+                CallArg {
+                    label: None,
+                    implicit: None,
+                    value:
+                        TypedExpr::Var {
+                            name: value_name,
+                            constructor:
+                                ValueConstructor {
+                                    variant: ValueConstructorVariant::LocalVariable { .. },
+                                    ..
+                                },
+                            ..
+                        },
+                    ..
+                } => value_name == name,
+                _ => false,
+            });
+
+    if passes_arguments_in_order {
+        Some(fun.location())
+    } else {
+        None
+    }
+}
@@ -202,6 +202,14 @@ impl FieldMap {
             .collect_vec()
     }
 
+    /// The label of the argument at the given position, if it has one.
+    pub fn label_at(&self, index: u32) -> Option<&EcoString> {
+        self.fields
+            .iter()
+            .find(|(_, position)| **position == index)
+            .map(|(label, _)| label)
+    }
+
     pub fn indices_to_labels(&self) -> HashMap<u32, &EcoString> {
         self.fields
             .iter()
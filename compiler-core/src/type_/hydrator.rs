@@ -174,7 +174,7 @@ impl Hydrator {
                 // We do not track use of qualified type constructors as they may be
                 // used in another module.
                 if module.is_none() {
-                    environment.increment_usage(name);
+                    environment.increment_type_usage(name);
                 }
 
                 // Ensure that the correct number of arguments have been given to the constructor
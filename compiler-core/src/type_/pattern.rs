@@ -23,7 +23,9 @@ pub struct PatternTyper<'a, 'b> {
     current_function: &'a FunctionDefinition,
     hydrator: &'a Hydrator,
     mode: PatternMode,
-    initial_pattern_vars: HashSet<EcoString>,
+    /// The variables defined by the initial pattern, along with where they
+    /// were defined.
+    initial_pattern_vars: HashMap<EcoString, SrcSpan>,
     /// Variables which have been inferred to a specific variant of their type
     /// from this pattern-matching. Key is the variable name, Value is the inferred variant index.
     inferred_variant_variables: HashMap<EcoString, u16>,
@@ -52,7 +54,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             current_function,
             hydrator,
             mode: PatternMode::Initial,
-            initial_pattern_vars: HashSet::new(),
+            initial_pattern_vars: HashMap::new(),
             inferred_variant_variables: HashMap::new(),
             minimum_required_version: Version::new(0, 1, 0),
             problems,
@@ -80,8 +82,11 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                     self.problems,
                 );
                 // Ensure there are no duplicate variable names in the pattern
-                if self.initial_pattern_vars.contains(name) {
-                    return Err(UnifyError::DuplicateVarInPattern { name: name.into() });
+                if let Some(first_location) = self.initial_pattern_vars.get(name) {
+                    return Err(UnifyError::DuplicateVarInPattern {
+                        name: name.into(),
+                        first_location: *first_location,
+                    });
                 }
                 // We no longer have access to the variable from the subject of the pattern
                 // so it doesn't need to be inferred any more.
@@ -89,7 +94,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 // Record that this variable originated in this pattern so any
                 // following alternative patterns can be checked to ensure they
                 // have the same variables.
-                let _ = self.initial_pattern_vars.insert(name.into());
+                let _ = self.initial_pattern_vars.insert(name.into(), location);
                 // And now insert the variable for use in the code that comes
                 // after the pattern.
                 self.environment
@@ -100,7 +105,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             PatternMode::Alternative(assigned) => {
                 match self.environment.scope.get_mut(name) {
                     // This variable was defined in the Initial multi-pattern
-                    Some(initial) if self.initial_pattern_vars.contains(name) => {
+                    Some(initial) if self.initial_pattern_vars.contains_key(name) => {
                         assigned.push(name.into());
                         let initial_type = initial.type_.clone();
                         unify(initial_type, type_.clone())?;
@@ -120,7 +125,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             PatternMode::Initial => {
                 // If this name is reassigned in the pattern itself, we don't need to infer
                 // it, since it isn't accessible in this scope anymore.
-                if self.initial_pattern_vars.contains(&name) {
+                if self.initial_pattern_vars.contains_key(&name) {
                     return;
                 }
 
@@ -193,7 +198,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                     // It is safe to use expect here as we checked the length above
                     name: self
                         .initial_pattern_vars
-                        .iter()
+                        .keys()
                         .next()
                         .expect("Getting undefined pattern variable")
                         .clone(),
@@ -396,6 +401,42 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             }
             Pattern::Invalid { location, .. } => Ok(Pattern::Invalid { type_, location }),
 
+            Pattern::Constant {
+                location,
+                module: (module, module_location),
+                name,
+                ..
+            } => {
+                let constructor = self
+                    .environment
+                    .get_value_constructor(Some(&module), &name)
+                    .map_err(|e| {
+                        convert_get_value_constructor_error(e, location, Some(module_location))
+                    })?;
+
+                // A constant can be used as a pattern only if its value is
+                // a literal that can be matched on, in which case the
+                // constant is replaced by that literal.
+                let literal = match &constructor.variant {
+                    ValueConstructorVariant::ModuleConstant { literal, .. } => {
+                        constant_literal_pattern(literal, location)
+                    }
+                    ValueConstructorVariant::LocalVariable { .. }
+                    | ValueConstructorVariant::LocalConstant { .. }
+                    | ValueConstructorVariant::ModuleFn { .. }
+                    | ValueConstructorVariant::Record { .. } => None,
+                };
+
+                match literal {
+                    Some(pattern) => self.unify(pattern, type_, subject_variable),
+                    None => Err(Error::NonLiteralConstantPattern {
+                        location,
+                        module,
+                        name,
+                    }),
+                }
+            }
+
             Pattern::Variable {
                 name,
                 location,
@@ -825,6 +866,9 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
 
                 match constructor_deprecation {
                     Deprecation::NotDeprecated => {}
+                    // The functions generated by the compiler match on all
+                    // the variants of a type, even the deprecated ones.
+                    Deprecation::Deprecated { .. } if self.current_function.is_generated => {}
                     Deprecation::Deprecated { message } => {
                         self.problems.warning(Warning::DeprecatedItem {
                             location,
@@ -1023,3 +1067,48 @@ fn unify_constructor_variants(into: &mut Type, from: &Type) {
         _ => {}
     }
 }
+
+/// The literal pattern matching the value of a constant, if it is an `Int`,
+/// `Float` or `String` one. Constants referencing other constants are
+/// followed to their value.
+///
+fn constant_literal_pattern(constant: &TypedConstant, location: SrcSpan) -> Option<UntypedPattern> {
+    match constant {
+        Constant::Int {
+            value, int_value, ..
+        } => Some(Pattern::Int {
+            location,
+            value: value.clone(),
+            int_value: int_value.clone(),
+        }),
+        Constant::Float { value, .. } => Some(Pattern::Float {
+            location,
+            value: value.clone(),
+        }),
+        Constant::String { value, .. } => Some(Pattern::String {
+            location,
+            value: value.clone(),
+        }),
+        Constant::Var {
+            constructor: Some(constructor),
+            ..
+        } => match &constructor.variant {
+            ValueConstructorVariant::ModuleConstant { literal, .. } => {
+                constant_literal_pattern(literal, location)
+            }
+            ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::LocalConstant { .. }
+            | ValueConstructorVariant::ModuleFn { .. }
+            | ValueConstructorVariant::Record { .. } => None,
+        },
+        Constant::Var {
+            constructor: None, ..
+        }
+        | Constant::Tuple { .. }
+        | Constant::List { .. }
+        | Constant::Record { .. }
+        | Constant::BitArray { .. }
+        | Constant::StringConcatenation { .. }
+        | Constant::Invalid { .. } => None,
+    }
+}
@@ -3,7 +3,7 @@ use self::expression::CallKind;
 use super::*;
 use crate::ast::{
     FunctionLiteralKind, ImplicitCallArgOrigin, PIPE_VARIABLE, PipelineAssignmentKind, Statement,
-    TypedPipelineAssignment, UntypedExpr,
+    TodoKind, TypedPipelineAssignment, UntypedExpr,
 };
 use vec1::Vec1;
 
@@ -15,14 +15,22 @@ pub(crate) struct PipeTyper<'a, 'b, 'c> {
     location: SrcSpan,
     first_value: TypedPipelineAssignment,
     assignments: Vec<(TypedPipelineAssignment, PipelineAssignmentKind)>,
+    operator_locations: Vec<SrcSpan>,
     expr_typer: &'a mut ExprTyper<'b, 'c>,
 }
 
 impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
-    fn new(expr_typer: &'a mut ExprTyper<'b, 'c>, size: usize, first: TypedExpr, end: u32) -> Self {
+    fn new(
+        expr_typer: &'a mut ExprTyper<'b, 'c>,
+        size: usize,
+        first: TypedExpr,
+        end: u32,
+        operator_locations: Vec<SrcSpan>,
+    ) -> Self {
         let first_type = first.type_();
         let first_location = first.location();
-        let first_value = new_pipeline_assignment(expr_typer, first);
+        let first_operator_location = operator_locations.first().copied();
+        let first_value = new_pipeline_assignment(expr_typer, first, first_operator_location);
         Self {
             size,
             expr_typer,
@@ -33,6 +41,7 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
                 end,
             },
             assignments: Vec::with_capacity(size),
+            operator_locations,
             first_value,
         }
     }
@@ -40,17 +49,22 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
     pub fn infer(
         expr_typer: &'a mut ExprTyper<'b, 'c>,
         expressions: Vec1<UntypedExpr>,
+        operator_locations: Vec<SrcSpan>,
     ) -> TypedExpr {
         // The scope is reset as pipelines are rewritten into a series of
         // assignments, and we don't want these variables to leak out of the
         // pipeline.
         let scope = expr_typer.environment.scope.clone();
-        let result = PipeTyper::run(expr_typer, expressions);
+        let result = PipeTyper::run(expr_typer, expressions, operator_locations);
         expr_typer.environment.scope = scope;
         result
     }
 
-    fn run(expr_typer: &'a mut ExprTyper<'b, 'c>, expressions: Vec1<UntypedExpr>) -> TypedExpr {
+    fn run(
+        expr_typer: &'a mut ExprTyper<'b, 'c>,
+        expressions: Vec1<UntypedExpr>,
+        operator_locations: Vec<SrcSpan>,
+    ) -> TypedExpr {
         let size = expressions.len();
         let end = expressions.last().location().end;
         let mut expressions = expressions.into_iter();
@@ -67,7 +81,7 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
             }
         };
 
-        Self::new(expr_typer, size, first, end).infer_expressions(expressions)
+        Self::new(expr_typer, size, first, end, operator_locations).infer_expressions(expressions)
     }
 
     fn infer_expressions(
@@ -90,6 +104,7 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
         expressions: impl IntoIterator<Item = UntypedExpr>,
     ) -> (TypedExpr, PipelineAssignmentKind) {
         let mut finally = None;
+        let mut bare_function_steps = 0;
 
         for (i, call) in expressions.into_iter().enumerate() {
             if self.expr_typer.previous_panics {
@@ -99,6 +114,22 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
 
             self.warn_if_call_first_argument_is_hole(&call);
 
+            // A `todo` as the last step of a pipeline is a placeholder for
+            // the function that is still to be written, so it gets its own
+            // warning.
+            let call = match call {
+                UntypedExpr::Todo {
+                    location,
+                    kind: TodoKind::Keyword,
+                    message,
+                } if i + 2 == self.size => UntypedExpr::Todo {
+                    location,
+                    kind: TodoKind::PipelineStep,
+                    message,
+                },
+                call => call,
+            };
+
             let (kind, call) = match call {
                 func @ UntypedExpr::Fn { location, kind, .. } => {
                     let (func, args, return_type) = self.expr_typer.do_infer_call(
@@ -112,7 +143,9 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
                         FunctionLiteralKind::Capture { hole } => {
                             PipelineAssignmentKind::Hole { hole }
                         }
-                        FunctionLiteralKind::Anonymous { .. } | FunctionLiteralKind::Use { .. } => {
+                        FunctionLiteralKind::Anonymous { .. }
+                        | FunctionLiteralKind::Use { .. }
+                        | FunctionLiteralKind::TrailingBlock { .. } => {
                             PipelineAssignmentKind::FunctionCall
                         }
                     };
@@ -188,22 +221,51 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
                 }
 
                 // right(left)
-                call => (
-                    PipelineAssignmentKind::FunctionCall,
-                    self.infer_apply_pipe(call),
-                ),
+                call => {
+                    if let UntypedExpr::Var { .. } | UntypedExpr::FieldAccess { .. } = call {
+                        bare_function_steps += 1;
+                    }
+                    (
+                        PipelineAssignmentKind::FunctionCall,
+                        self.infer_apply_pipe(call),
+                    )
+                }
             };
 
             if i + 2 == self.size {
                 finally = Some((call, kind));
             } else {
-                self.push_assignment(call, kind);
+                // The first operator pipes the first value into this step, so
+                // the one piping this step into the next one comes after it.
+                let operator_location = self.operator_locations.get(i + 1).copied();
+                self.push_assignment(call, kind, operator_location);
             }
         }
 
+        self.warn_if_composable(bare_function_steps);
         finally.expect("Empty pipeline in typer")
     }
 
+    /// A pipeline of at least two steps where each step is just a function
+    /// being called with the previous value, like `x |> wibble |> wobble`,
+    /// could be written as a composition of those functions instead. Only
+    /// reported if `composable_pipelines` is enabled in the `[lints]`
+    /// section of the package's `gleam.toml`.
+    ///
+    fn warn_if_composable(&mut self, bare_function_steps: usize) {
+        let steps = self.size - 1;
+        if self.expr_typer.environment.lints.composable_pipelines
+            && steps >= 2
+            && bare_function_steps == steps
+        {
+            self.expr_typer
+                .problems
+                .warning(Warning::PipelineComposable {
+                    location: self.location,
+                });
+        }
+    }
+
     /// Create a call argument that can be used to refer to the value on the
     /// left hand side of the pipe
     fn typed_left_hand_value_variable_call_argument(&self) -> CallArg<TypedExpr> {
@@ -258,10 +320,15 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
     }
 
     /// Push an assignment for the value on the left hand side of the pipe
-    fn push_assignment(&mut self, expression: TypedExpr, kind: PipelineAssignmentKind) {
+    fn push_assignment(
+        &mut self,
+        expression: TypedExpr,
+        kind: PipelineAssignmentKind,
+        operator_location: Option<SrcSpan>,
+    ) {
         self.argument_type = expression.type_();
         self.argument_location = expression.location();
-        let assignment = new_pipeline_assignment(self.expr_typer, expression);
+        let assignment = new_pipeline_assignment(self.expr_typer, expression, operator_location);
         self.assignments.push((assignment, kind));
     }
 
@@ -285,11 +352,8 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
             fun: Box::new(function),
         };
         let args = vec![self.untyped_left_hand_value_variable_call_argument()];
-        // TODO: use `.with_unify_error_situation(UnifyErrorSituation::PipeTypeMismatch)`
-        // This will require the typing of the arguments to be lifted up out of
-        // the function below. If it is not we don't know if the error comes
-        // from incorrect usage of the pipe or if it originates from the
-        // argument expressions.
+        // The piped value is an implicit argument, so if it has the wrong type
+        // the call reports it as a pipe type mismatch.
         let (function, args, type_) = self.expr_typer.do_infer_call_with_known_fun(
             function,
             args,
@@ -312,11 +376,8 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
         location: SrcSpan,
     ) -> TypedExpr {
         arguments.insert(0, self.untyped_left_hand_value_variable_call_argument());
-        // TODO: use `.with_unify_error_situation(UnifyErrorSituation::PipeTypeMismatch)`
-        // This will require the typing of the arguments to be lifted up out of
-        // the function below. If it is not we don't know if the error comes
-        // from incorrect usage of the pipe or if it originates from the
-        // argument expressions.
+        // The piped value is an implicit argument, so if it has the wrong type
+        // the call reports it as a pipe type mismatch.
         let (fun, args, type_) = self.expr_typer.do_infer_call_with_known_fun(
             function,
             arguments,
@@ -358,8 +419,14 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
             Ok(_) => (),
             Err(error) => {
                 let error = if self.check_if_pipe_type_mismatch(&error) {
-                    convert_unify_error(error, function.location())
-                        .with_unify_error_situation(UnifyErrorSituation::PipeTypeMismatch)
+                    convert_unify_error(error, function.location()).with_unify_error_situation(
+                        UnifyErrorSituation::PipeTypeMismatch {
+                            label: function
+                                .field_map()
+                                .and_then(|field_map| field_map.label_at(0))
+                                .cloned(),
+                        },
+                    )
                 } else {
                     convert_unify_error(flip_unify_error(error), function.location())
                 };
@@ -422,6 +489,7 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
 fn new_pipeline_assignment(
     expr_typer: &mut ExprTyper<'_, '_>,
     expression: TypedExpr,
+    operator_location: Option<SrcSpan>,
 ) -> TypedPipelineAssignment {
     let location = expression.location();
     // Insert the variable for use in type checking the rest of the pipeline
@@ -435,5 +503,6 @@ fn new_pipeline_assignment(
         location,
         name: PIPE_VARIABLE.into(),
         value: Box::new(expression),
+        operator_location,
     }
 }
@@ -245,6 +245,9 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
         type_aliases: HashMap::new(),
         documentation: Vec::new(),
         contains_echo: false,
+        benchmarks: vec![],
+        disabled_values: HashMap::new(),
+        disabled_types: HashMap::new(),
         references: References::default(),
     };
 
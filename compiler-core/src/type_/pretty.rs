@@ -1,4 +1,7 @@
-use super::{Type, TypeVar};
+use super::{
+    Type, TypeVar,
+    printer::{self, Names},
+};
 use crate::{
     docvec,
     pretty::{nil, *},
@@ -16,6 +19,15 @@ use pretty_assertions::assert_eq;
 
 const INDENT: isize = 2;
 
+/// Renders a type exactly as the compiler shows it to users in its error
+/// messages: types and modules are printed using the names they have been
+/// imported with, and type variables that have no name are called `a`, `b`,
+/// `c`, and so on.
+///
+pub fn print(type_: &Type, names: &Names) -> String {
+    printer::Printer::new(names).print_type(type_).into()
+}
+
 #[derive(Debug, Default)]
 pub struct Printer {
     names: im::HashMap<u64, EcoString>,
@@ -465,3 +477,83 @@ fn function_test() {
 fn pretty_print(type_: Arc<Type>) -> String {
     Printer::new().pretty_print(&type_, 0)
 }
+
+#[cfg(test)]
+fn named(module: &str, name: &str, args: Vec<Arc<Type>>) -> Arc<Type> {
+    Arc::new(Type::Named {
+        publicity: Publicity::Public,
+        package: "".into(),
+        module: module.into(),
+        name: name.into(),
+        args,
+        inferred_variant: None,
+    })
+}
+
+#[test]
+fn print_unbound_type_variables() {
+    let names = Names::new();
+    let type_ = Type::Fn {
+        args: vec![unbound_var(1), unbound_var(2)],
+        return_: unbound_var(1),
+    };
+    assert_eq!(print(&type_, &names), "fn(a, b) -> a");
+}
+
+#[test]
+fn print_generic_type_variables_with_their_names() {
+    let mut names = Names::new();
+    names.type_variable_in_scope(1, "element".into());
+    let type_ = Type::Fn {
+        args: vec![generic_var(1), generic_var(2)],
+        return_: generic_var(2),
+    };
+    assert_eq!(print(&type_, &names), "fn(element, a) -> a");
+}
+
+#[test]
+fn print_type_in_scope() {
+    let mut names = Names::new();
+    names.named_type_in_scope("gleam".into(), "Int".into(), "Int".into());
+    names.named_type_in_scope("gleam".into(), "List".into(), "List".into());
+    let type_ = named("gleam", "List", vec![named("gleam", "Int", vec![])]);
+    assert_eq!(print(&type_, &names), "List(Int)");
+}
+
+#[test]
+fn print_qualified_type() {
+    let mut names = Names::new();
+    names.imported_module("gleam/dict".into(), "dict".into());
+    names.named_type_in_scope("gleam".into(), "Int".into(), "Int".into());
+    let type_ = named(
+        "gleam/dict",
+        "Dict",
+        vec![named("gleam", "Int", vec![]), unbound_var(1)],
+    );
+    assert_eq!(print(&type_, &names), "dict.Dict(Int, a)");
+}
+
+#[test]
+fn print_type_of_aliased_module() {
+    let mut names = Names::new();
+    names.imported_module("wibble/wobble".into(), "wabble".into());
+    let type_ = Type::Tuple {
+        elements: vec![named("wibble/wobble", "Wibble", vec![])],
+    };
+    assert_eq!(print(&type_, &names), "#(wabble.Wibble)");
+}
+
+#[test]
+fn print_matches_the_printer_used_in_diagnostics() {
+    let mut names = Names::new();
+    names.named_type_in_scope("gleam".into(), "String".into(), "String".into());
+    let type_ = Type::Fn {
+        args: vec![named("gleam", "String", vec![]), unbound_var(3)],
+        return_: named("wibble", "Wobble", vec![unbound_var(3)]),
+    };
+    assert_eq!(
+        print(&type_, &names),
+        printer::Printer::new(&names).print_type(&type_)
+    );
+    assert_eq!(print(&type_, &names), "fn(String, a) -> wibble.Wobble(a)");
+}
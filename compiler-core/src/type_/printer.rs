@@ -3,7 +3,7 @@ use ecow::{EcoString, eco_format};
 use im::HashMap;
 use std::{collections::HashSet, sync::Arc};
 
-use crate::type_::{Type, TypeVar};
+use crate::type_::{Type, TypeVar, collapse_links};
 
 /// This class keeps track of what names are used for modules in the current
 /// scope, so they can be printed in errors, etc.
@@ -333,26 +333,151 @@ impl<'a> Printer<'a> {
         buffer
     }
 
+    /// Prints two types that could not be unified, replacing the parts that
+    /// are the same in both with `_` so that only the parts that differ are
+    /// left.
+    ///
+    /// Returns `None` if the types differ as a whole, or if they are the same,
+    /// as then there's nothing to collapse.
+    ///
+    pub fn print_type_difference(
+        &mut self,
+        expected: &Type,
+        given: &Type,
+    ) -> Option<(EcoString, EcoString)> {
+        let mut expected_buffer = EcoString::new();
+        let mut given_buffer = EcoString::new();
+        let collapsed =
+            self.print_difference(expected, given, &mut expected_buffer, &mut given_buffer);
+        if collapsed && expected_buffer != "_" {
+            Some((expected_buffer, given_buffer))
+        } else {
+            None
+        }
+    }
+
+    /// Prints the difference between two types to the given buffers,
+    /// returning whether any part of them was collapsed because it was the
+    /// same in both.
+    fn print_difference(
+        &mut self,
+        expected: &Type,
+        given: &Type,
+        expected_buffer: &mut EcoString,
+        given_buffer: &mut EcoString,
+    ) -> bool {
+        if expected.same_as(given) {
+            expected_buffer.push('_');
+            given_buffer.push('_');
+            return true;
+        }
+
+        let expected = collapse_links(Arc::new(expected.clone()));
+        let given = collapse_links(Arc::new(given.clone()));
+        match (expected.as_ref(), given.as_ref()) {
+            (
+                Type::Named {
+                    module, name, args, ..
+                },
+                Type::Named {
+                    module: given_module,
+                    name: given_name,
+                    args: given_args,
+                    ..
+                },
+            ) if module == given_module && name == given_name && args.len() == given_args.len() => {
+                self.print_name(module, name, expected_buffer, PrintMode::Normal);
+                self.print_name(module, name, given_buffer, PrintMode::Normal);
+                if args.is_empty() {
+                    return false;
+                }
+                expected_buffer.push('(');
+                given_buffer.push('(');
+                let collapsed = self.print_arguments_difference(
+                    args,
+                    given_args,
+                    expected_buffer,
+                    given_buffer,
+                );
+                expected_buffer.push(')');
+                given_buffer.push(')');
+                collapsed
+            }
+
+            (
+                Type::Fn { args, return_ },
+                Type::Fn {
+                    args: given_args,
+                    return_: given_return,
+                },
+            ) if args.len() == given_args.len() => {
+                expected_buffer.push_str("fn(");
+                given_buffer.push_str("fn(");
+                let arguments_collapsed = self.print_arguments_difference(
+                    args,
+                    given_args,
+                    expected_buffer,
+                    given_buffer,
+                );
+                expected_buffer.push_str(") -> ");
+                given_buffer.push_str(") -> ");
+                let return_collapsed =
+                    self.print_difference(return_, given_return, expected_buffer, given_buffer);
+                arguments_collapsed || return_collapsed
+            }
+
+            (
+                Type::Tuple { elements, .. },
+                Type::Tuple {
+                    elements: given_elements,
+                    ..
+                },
+            ) if elements.len() == given_elements.len() => {
+                expected_buffer.push_str("#(");
+                given_buffer.push_str("#(");
+                let collapsed = self.print_arguments_difference(
+                    elements,
+                    given_elements,
+                    expected_buffer,
+                    given_buffer,
+                );
+                expected_buffer.push(')');
+                given_buffer.push(')');
+                collapsed
+            }
+
+            (_, _) => {
+                self.print(&expected, expected_buffer, PrintMode::Normal);
+                self.print(&given, given_buffer, PrintMode::Normal);
+                false
+            }
+        }
+    }
+
+    fn print_arguments_difference(
+        &mut self,
+        args: &[Arc<Type>],
+        given_args: &[Arc<Type>],
+        expected_buffer: &mut EcoString,
+        given_buffer: &mut EcoString,
+    ) -> bool {
+        let mut collapsed = false;
+        for (i, (arg, given_arg)) in args.iter().zip(given_args).enumerate() {
+            if i > 0 {
+                expected_buffer.push_str(", ");
+                given_buffer.push_str(", ");
+            }
+            collapsed |= self.print_difference(arg, given_arg, expected_buffer, given_buffer);
+        }
+        collapsed
+    }
+
     fn print(&mut self, type_: &Type, buffer: &mut EcoString, print_mode: PrintMode) {
         match type_ {
             Type::Named {
                 name, args, module, ..
             } => {
-                let (module, name) = match self.names.named_type(module, name, print_mode) {
-                    NameContextInformation::Qualified(m, n) => (Some(m), n),
-                    NameContextInformation::Unqualified(n) => (None, n),
-                    // TODO: indicate that the module is not import and as such
-                    // needs to be, as well as how.
-                    NameContextInformation::Unimported(n) => {
-                        (Some(module.split('/').last().unwrap_or(module)), n)
-                    }
-                };
-
-                if let Some(module) = module {
-                    buffer.push_str(module);
-                    buffer.push('.');
-                }
-                buffer.push_str(name);
+                self.print_name(module, name, buffer, print_mode);
 
                 if !args.is_empty() {
                     buffer.push('(');
@@ -383,6 +508,30 @@ impl<'a> Printer<'a> {
         }
     }
 
+    fn print_name(
+        &self,
+        module: &EcoString,
+        name: &EcoString,
+        buffer: &mut EcoString,
+        print_mode: PrintMode,
+    ) {
+        let (module, name) = match self.names.named_type(module, name, print_mode) {
+            NameContextInformation::Qualified(m, n) => (Some(m), n),
+            NameContextInformation::Unqualified(n) => (None, n),
+            // TODO: indicate that the module is not import and as such
+            // needs to be, as well as how.
+            NameContextInformation::Unimported(n) => {
+                (Some(module.split('/').last().unwrap_or(module)), n)
+            }
+        };
+
+        if let Some(module) = module {
+            buffer.push_str(module);
+            buffer.push('.');
+        }
+        buffer.push_str(name);
+    }
+
     pub fn print_constructor(&mut self, module: &EcoString, name: &EcoString) -> EcoString {
         let (module, name) = match self.names.named_constructor(module, name) {
             NameContextInformation::Qualified(module, name) => (Some(module), name),
@@ -799,3 +948,54 @@ fn test_variable_name_already_in_scope() {
     assert_eq!(printer.print_type(&type_(2)), "b");
     assert_eq!(printer.print_type(&type_(3)), "d");
 }
+
+#[test]
+fn test_type_difference() {
+    use crate::type_::prelude::{float, fn_, int, list, string, tuple};
+
+    let names = Names::new();
+    let mut printer = Printer::new(&names);
+
+    let expected = tuple(vec![int(), list(tuple(vec![int(), float()]))]);
+    let given = tuple(vec![int(), list(tuple(vec![int(), string()]))]);
+    assert_eq!(
+        printer.print_type_difference(&expected, &given),
+        Some((
+            "#(_, gleam.List(#(_, gleam.Float)))".into(),
+            "#(_, gleam.List(#(_, gleam.String)))".into()
+        ))
+    );
+
+    let expected = fn_(vec![int(), string()], int());
+    let given = fn_(vec![int(), string()], float());
+    assert_eq!(
+        printer.print_type_difference(&expected, &given),
+        Some((
+            "fn(_, _) -> gleam.Int".into(),
+            "fn(_, _) -> gleam.Float".into()
+        ))
+    );
+}
+
+#[test]
+fn test_type_difference_with_nothing_in_common() {
+    use crate::type_::prelude::{int, list, string, tuple};
+
+    let names = Names::new();
+    let mut printer = Printer::new(&names);
+
+    let tuple_and_list = (tuple(vec![int(), string()]), list(int()));
+    assert_eq!(
+        printer.print_type_difference(&tuple_and_list.0, &tuple_and_list.1),
+        None
+    );
+
+    let different_elements = (tuple(vec![int(), int()]), tuple(vec![string(), string()]));
+    assert_eq!(
+        printer.print_type_difference(&different_elements.0, &different_elements.1),
+        None
+    );
+
+    let same = (list(int()), list(int()));
+    assert_eq!(printer.print_type_difference(&same.0, &same.1), None);
+}
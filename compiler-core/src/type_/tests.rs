@@ -20,19 +20,39 @@ use camino::Utf8PathBuf;
 mod accessors;
 mod assert;
 mod assignments;
+mod capabilities;
+mod case_clause_blocks;
+mod composable_pipelines;
 mod conditional_compilation;
+mod constant_functions;
+mod constant_patterns;
 mod custom_types;
 mod echo;
 mod errors;
 mod exhaustiveness;
 mod externals;
+mod function_wrappers;
 mod functions;
 mod guards;
+mod identity_cases;
 mod imports;
+mod list_comprehensions;
+mod main_functions;
+mod naming_conventions;
+mod opaque_types;
+mod overly_specific_annotations;
 mod pipes;
 mod pretty;
+mod self_qualifier;
+mod simplifiable_bools;
+mod single_arm_cases;
 mod target_implementations;
+mod todo_and_panic_in_library;
+mod trailing_block;
 mod type_alias;
+mod type_application;
+mod typed_holes;
+mod unused_labels;
 mod use_;
 mod version_inference;
 mod warnings;
@@ -64,8 +84,11 @@ macro_rules! assert_infer_with_module {
         assert_eq!(($src, constructors), ($src, expected));
     };
     (($name:expr, $module_src:literal), $src:expr, $module:expr $(,)?) => {
+        $crate::assert_infer_with_module!(("thepackage", $name, $module_src), $src, $module);
+    };
+    (($package:expr, $name:expr, $module_src:literal), $src:expr, $module:expr $(,)?) => {
         let constructors =
-            $crate::type_::tests::infer_module($src, vec![("thepackage", $name, $module_src)]);
+            $crate::type_::tests::infer_module($src, vec![($package, $name, $module_src)]);
         let expected = $crate::type_::tests::stringify_tuple_strs($module);
 
         assert_eq!(($src, constructors), ($src, expected));
@@ -104,6 +127,24 @@ macro_rules! assert_module_error {
     };
 }
 
+#[macro_export]
+macro_rules! assert_module_infer_with_features {
+    ($features:expr, $src:expr, $module:expr $(,)?) => {{
+        let constructors = $crate::type_::tests::infer_module_with_features($src, &$features);
+        let expected = $crate::type_::tests::stringify_tuple_strs($module);
+        assert_eq!(($src, constructors), ($src, expected));
+    }};
+}
+
+#[macro_export]
+macro_rules! assert_module_error_with_features {
+    ($features:expr, $src:expr) => {
+        let error = $crate::type_::tests::module_error_with_features($src, &$features);
+        let output = format!("----- SOURCE CODE\n{}\n\n----- ERROR\n{}", $src, error);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
 #[macro_export]
 macro_rules! assert_internal_module_error {
     ($src:expr) => {
@@ -164,8 +205,11 @@ macro_rules! assert_error {
 #[macro_export]
 macro_rules! assert_with_module_error {
     (($name:expr, $module_src:literal), $src:expr $(,)?) => {
-        let error =
-            $crate::type_::tests::module_error($src, vec![("thepackage", $name, $module_src)]);
+        $crate::assert_with_module_error!(("thepackage", $name, $module_src), $src);
+    };
+
+    (($package:expr, $name:expr, $module_src:literal), $src:expr $(,)?) => {
+        let error = $crate::type_::tests::module_error($src, vec![($package, $name, $module_src)]);
         let output = format!(
             "----- SOURCE CODE
 -- {}.gleam
@@ -241,6 +285,30 @@ pub(crate) fn get_printed_warnings(
     print_warnings(get_warnings(src, deps, target, gleam_version))
 }
 
+pub(crate) fn get_printed_warnings_with_config(src: &str, config: PackageConfig) -> String {
+    get_printed_warnings_with_config_and_origin(src, config, Origin::Src)
+}
+
+pub(crate) fn get_printed_warnings_with_config_and_origin(
+    src: &str,
+    config: PackageConfig,
+    origin: Origin,
+) -> String {
+    let warnings = VectorWarningEmitterIO::default();
+    _ = compile_module_with_config_and_origin(
+        "test_module",
+        src,
+        Some(Rc::new(warnings.clone())),
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config,
+        origin,
+    )
+    .expect("Compilation should succeed");
+    print_warnings(warnings.take())
+}
+
 fn print_warnings(warnings: Vec<crate::warning::Warning>) -> String {
     let mut nocolor = termcolor::Buffer::no_color();
     for warning in warnings {
@@ -419,6 +487,7 @@ fn compile_statement_sequence(
             has_body: true,
             has_erlang_external: false,
             has_javascript_external: false,
+            is_generated: false,
         },
         &mut problems,
     )
@@ -482,6 +551,36 @@ pub fn infer_module_with_target(
         .collect()
 }
 
+pub fn infer_module_with_features(src: &str, features: &[&str]) -> Vec<(EcoString, String)> {
+    let ast = compile_module_with_config(
+        "test_module",
+        src,
+        None,
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config_with_features(features),
+    )
+    .expect("should successfully infer");
+    ast.type_info
+        .values
+        .iter()
+        .filter(|(_, v)| v.publicity.is_importable())
+        .map(|(k, v)| {
+            let mut printer = Printer::new();
+            (k.clone(), printer.pretty_print(&v.type_, 0))
+        })
+        .sorted()
+        .collect()
+}
+
+fn config_with_features(features: &[&str]) -> PackageConfig {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.features = features.iter().map(|feature| (*feature).into()).collect();
+    config
+}
+
 pub fn compile_module(
     module_name: &str,
     src: &str,
@@ -507,6 +606,52 @@ pub fn compile_module_with_opts(
     target: Target,
     target_support: TargetSupport,
     gleam_version: Option<Range<Version>>,
+) -> Result<TypedModule, (Vec<crate::type_::Error>, Names)> {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.gleam_version = gleam_version;
+    compile_module_with_config(
+        module_name,
+        src,
+        warnings,
+        dep,
+        target,
+        target_support,
+        config,
+    )
+}
+
+pub fn compile_module_with_config(
+    module_name: &str,
+    src: &str,
+    warnings: Option<Rc<dyn WarningEmitterIO>>,
+    dep: Vec<DependencyModule<'_>>,
+    target: Target,
+    target_support: TargetSupport,
+    config: PackageConfig,
+) -> Result<TypedModule, (Vec<crate::type_::Error>, Names)> {
+    compile_module_with_config_and_origin(
+        module_name,
+        src,
+        warnings,
+        dep,
+        target,
+        target_support,
+        config,
+        Origin::Src,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn compile_module_with_config_and_origin(
+    module_name: &str,
+    src: &str,
+    warnings: Option<Rc<dyn WarningEmitterIO>>,
+    dep: Vec<DependencyModule<'_>>,
+    target: Target,
+    target_support: TargetSupport,
+    config: PackageConfig,
+    origin: Origin,
 ) -> Result<TypedModule, (Vec<crate::type_::Error>, Names)> {
     let ids = UniqueIdGenerator::new();
     let mut modules = im::HashMap::new();
@@ -553,15 +698,12 @@ pub fn compile_module_with_opts(
         .expect("syntax error");
     let mut ast = parsed.module;
     ast.name = module_name.into();
-    let mut config = PackageConfig::default();
-    config.name = "thepackage".into();
-    config.gleam_version = gleam_version;
 
     let warnings = TypeWarningEmitter::new("/src/warning/wrn.gleam".into(), src.into(), emitter);
     let inference_result = crate::analyse::ModuleAnalyzerConstructor::<()> {
         target,
         ids: &ids,
-        origin: Origin::Src,
+        origin,
         importable_modules: &modules,
         warnings: &warnings,
         direct_dependencies: &direct_dependencies,
@@ -605,6 +747,26 @@ pub fn module_error_with_target(
     error.pretty_string()
 }
 
+pub fn module_error_with_features(src: &str, features: &[&str]) -> String {
+    let (error, names) = compile_module_with_config(
+        "themodule",
+        src,
+        None,
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config_with_features(features),
+    )
+    .expect_err("should infer an error");
+    let error = Error::Type {
+        names,
+        src: src.into(),
+        path: Utf8PathBuf::from("/src/one/two.gleam"),
+        errors: Vec1::try_from_vec(error).expect("should have at least one error"),
+    };
+    error.pretty_string()
+}
+
 pub fn internal_module_error(src: &str, deps: Vec<DependencyModule<'_>>) -> String {
     internal_module_error_with_target(src, deps, Target::Erlang)
 }
@@ -886,6 +1048,9 @@ fn infer_module_type_retention_test() {
             type_aliases: HashMap::new(),
             documentation: Vec::new(),
             contains_echo: false,
+            benchmarks: vec![],
+            disabled_values: HashMap::new(),
+            disabled_types: HashMap::new(),
             references: References::default()
         }
     );
@@ -1086,6 +1251,37 @@ fn pipe() {
     );
 }
 
+#[test]
+fn bit_array_rest_segment() {
+    assert_module_infer!(
+        r#"
+pub fn parse(packet) {
+  case packet {
+    <<version:8, length:8, payload:bytes-size(length), rest:bytes>> ->
+      #(version, payload, rest)
+    _ -> #(0, <<>>, <<>>)
+  }
+}
+"#,
+        vec![("parse", "fn(BitArray) -> #(Int, BitArray, BitArray)")],
+    );
+}
+
+#[test]
+fn bit_array_size_from_earlier_segment_with_unit() {
+    assert_module_infer!(
+        r#"
+pub fn parse(packet) {
+  case packet {
+    <<length:8, payload:bits-size(length)-unit(8), rest:bits>> -> #(payload, rest)
+    _ -> #(<<>>, <<>>)
+  }
+}
+"#,
+        vec![("parse", "fn(BitArray) -> #(BitArray, BitArray)")],
+    );
+}
+
 #[test]
 fn bit_array() {
     assert_infer!("let assert <<x>> = <<1>> x", "Int");
@@ -2469,6 +2665,50 @@ fn early_function_generalisation2() {
     );
 }
 
+// Top-level definitions are always generalised, so a type variable left
+// unconstrained by their body becomes a generic one rather than an error.
+#[test]
+fn unconstrained_function_return_is_generalised() {
+    assert_module_infer!("pub fn empty() { [] }", vec![("empty", "fn() -> List(a)")]);
+}
+
+#[test]
+fn unconstrained_positions_are_generalised_separately() {
+    assert_module_infer!(
+        "pub fn wibble(x) { #([], Error(x), todo) }",
+        vec![("wibble", "fn(a) -> #(List(b), Result(c, a), d)")],
+    );
+}
+
+#[test]
+fn unconstrained_constant_is_generalised() {
+    assert_module_infer!(
+        "pub const empty = []
+         pub fn ints() { [1, ..empty] }
+         pub fn floats() { [1.0, ..empty] }",
+        vec![
+            ("empty", "List(a)"),
+            ("floats", "fn() -> List(Float)"),
+            ("ints", "fn() -> List(Int)"),
+        ],
+    );
+}
+
+#[test]
+fn unconstrained_function_used_at_different_types() {
+    assert_module_infer!(
+        "pub fn nothing() { None }
+         pub type Option(a) { Some(a) None }
+         pub fn both() { #([Some(1), nothing()], [Some(1.0), nothing()]) }",
+        vec![
+            ("None", "Option(a)"),
+            ("Some", "fn(a) -> Option(a)"),
+            ("both", "fn() -> #(List(Option(Int)), List(Option(Float)))"),
+            ("nothing", "fn() -> Option(a)"),
+        ],
+    );
+}
+
 // https://github.com/gleam-lang/gleam/issues/970
 #[test]
 fn bit_array_pattern_unification() {
@@ -2728,6 +2968,7 @@ fn assert_suitable_main_function_not_module_function() {
         type_: fn_(vec![], int()),
         variant: ValueConstructorVariant::ModuleConstant {
             documentation: None,
+            since: None,
             location: Default::default(),
             module: "module".into(),
             literal: Constant::Int {
@@ -2755,10 +2996,12 @@ fn assert_suitable_main_function_wrong_arity() {
         deprecation: Deprecation::NotDeprecated,
         type_: fn_(vec![], int()),
         variant: ValueConstructorVariant::ModuleFn {
+            purity: Purity::Unknown,
             name: "name".into(),
             field_map: None,
             arity: 1,
             documentation: None,
+            since: None,
             location: Default::default(),
             module: "module".into(),
             external_erlang: None,
@@ -2782,10 +3025,12 @@ fn assert_suitable_main_function_ok() {
         deprecation: Deprecation::NotDeprecated,
         type_: fn_(vec![], int()),
         variant: ValueConstructorVariant::ModuleFn {
+            purity: Purity::Unknown,
             name: "name".into(),
             field_map: None,
             arity: 0,
             documentation: None,
+            since: None,
             location: Default::default(),
             module: "module".into(),
             external_erlang: None,
@@ -2809,10 +3054,12 @@ fn assert_suitable_main_function_erlang_not_supported() {
         deprecation: Deprecation::NotDeprecated,
         type_: fn_(vec![], int()),
         variant: ValueConstructorVariant::ModuleFn {
+            purity: Purity::Unknown,
             name: "name".into(),
             field_map: None,
             arity: 0,
             documentation: None,
+            since: None,
             location: Default::default(),
             module: "module".into(),
             external_erlang: Some(("wibble".into(), "wobble".into())),
@@ -2836,10 +3083,12 @@ fn assert_suitable_main_function_javascript_not_supported() {
         deprecation: Deprecation::NotDeprecated,
         type_: fn_(vec![], int()),
         variant: ValueConstructorVariant::ModuleFn {
+            purity: Purity::Unknown,
             name: "name".into(),
             field_map: None,
             arity: 0,
             documentation: None,
+            since: None,
             location: Default::default(),
             module: "module".into(),
             external_erlang: Some(("wibble".into(), "wobble".into())),
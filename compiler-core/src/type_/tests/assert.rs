@@ -105,3 +105,18 @@ inner
 fn non_string_message() {
     assert_error!("let assert 1 = 2 as 3");
 }
+
+#[test]
+fn assert_statement() {
+    assert_infer!("assert 1 == 1 2.0", "Float");
+}
+
+#[test]
+fn assert_statement_as_last_statement() {
+    assert_infer!("assert True", "Nil");
+}
+
+#[test]
+fn assert_statement_non_bool_value() {
+    assert_error!("assert 1 2.0");
+}
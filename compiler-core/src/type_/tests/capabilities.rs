@@ -0,0 +1,489 @@
+use crate::{
+    assert_infer_with_module, assert_module_error, assert_module_infer, assert_no_warnings,
+    assert_with_module_error,
+};
+
+#[test]
+fn comparable_generates_a_compare_function() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  Medium
+  Large
+}
+"#,
+        vec![
+            ("Large", "Size"),
+            ("Medium", "Size"),
+            ("Small", "Size"),
+            ("compare_size", "fn(Size, Size) -> Order"),
+        ],
+    );
+}
+
+#[test]
+fn comparable_function_name_is_snake_case() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type HttpMethod {
+  Get
+  Post
+}
+"#,
+        vec![
+            ("Get", "HttpMethod"),
+            ("Post", "HttpMethod"),
+            ("compare_http_method", "fn(HttpMethod, HttpMethod) -> Order"),
+        ],
+    );
+}
+
+#[test]
+fn comparable_on_a_generic_type() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Phantom(a) {
+  First
+  Second
+}
+"#,
+        vec![
+            ("First", "Phantom(a)"),
+            ("Second", "Phantom(a)"),
+            ("compare_phantom", "fn(Phantom(a), Phantom(a)) -> Order"),
+        ],
+    );
+}
+
+#[test]
+fn comparable_with_an_aliased_order_module() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order as ordering
+
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+"#,
+        vec![
+            ("Large", "Size"),
+            ("Small", "Size"),
+            ("compare_size", "fn(Size, Size) -> Order"),
+        ],
+    );
+}
+
+#[test]
+fn comparable_on_a_type_with_a_single_variant_has_no_warnings() {
+    assert_no_warnings!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Unit {
+  Unit
+}
+"#
+    );
+}
+
+#[test]
+fn comparable_on_a_type_with_deprecated_variants_has_no_warnings() {
+    assert_no_warnings!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  @deprecated("Use Small instead")
+  Tiny
+  Large
+}
+"#
+    );
+}
+
+#[test]
+fn compare_function_can_be_used_in_the_same_module() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+
+pub fn main() {
+  compare_size(Small, Large) == order.Lt
+}
+"#,
+        vec![
+            ("Large", "Size"),
+            ("Small", "Size"),
+            ("compare_size", "fn(Size, Size) -> Order"),
+            ("main", "fn() -> Bool"),
+        ],
+    );
+}
+
+#[test]
+fn compare_function_can_be_used_as_an_order_function() {
+    assert_infer_with_module!(
+        (
+            "gleam/order",
+            r#"
+pub type Order { Lt Eq Gt }
+
+pub fn max(a: a, b: a, compare: fn(a, a) -> Order) -> a {
+  case compare(a, b) {
+    Lt -> b
+    Eq | Gt -> a
+  }
+}
+"#
+        ),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+
+pub fn main() {
+  order.max(Small, Large, compare_size)
+}
+"#,
+        vec![
+            ("Large", "Size"),
+            ("Small", "Size"),
+            ("compare_size", "fn(Size, Size) -> Order"),
+            ("main", "fn() -> Size"),
+        ],
+    );
+}
+
+#[test]
+fn compare_function_can_be_used_from_another_module() {
+    assert_infer_with_module!(
+        ("gleam/order", "pub type Order { Lt Eq Gt }"),
+        (
+            "size",
+            r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+"#
+        ),
+        r#"
+import size
+
+pub fn main() {
+  size.compare_size(size.Small, size.Large)
+}
+"#,
+        vec![("main", "fn() -> Order")],
+    );
+}
+
+#[test]
+fn comparable_without_importing_the_order_module() {
+    assert_module_error!(
+        r#"
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+"#
+    );
+}
+
+#[test]
+fn comparable_on_a_type_with_fields() {
+    assert_module_error!(
+        r#"
+@implements(comparable)
+pub type Wibble {
+  Wibble
+  Wobble(Int)
+}
+"#
+    );
+}
+
+#[test]
+fn comparable_on_a_type_with_no_variants() {
+    assert_module_error!(
+        r#"
+@implements(comparable)
+pub type Wibble
+"#
+    );
+}
+
+#[test]
+fn compare_function_clashing_with_existing_function() {
+    assert_with_module_error!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+pub type Size {
+  Small
+  Large
+}
+
+pub fn compare_size(a, b) {
+  order.Eq
+}
+"#
+    );
+}
+
+#[test]
+fn unused_compare_function_of_private_type_does_not_warn() {
+    assert_no_warnings!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+type Size {
+  Small
+  Large
+}
+
+pub fn main() {
+  let _ = Small
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn json_generates_encoding_and_decoding_functions() {
+    assert_module_infer!(
+        r#"
+@derive(json)
+pub type Point {
+  Point(x: Int, y: Int)
+}
+"#,
+        vec![
+            ("Point", "fn(Int, Int) -> Point"),
+            ("point_from_json", "fn(String) -> Result(Point, Nil)"),
+            ("point_to_json", "fn(Point) -> String"),
+        ],
+    );
+}
+
+#[test]
+fn json_on_a_type_with_every_supported_field() {
+    assert_module_infer!(
+        r#"
+@derive(json)
+pub type Colour {
+  Red
+  Green
+}
+
+@derive(json)
+pub type Wibble {
+  Wibble(Int, Bool, name: String, colours: List(Colour), nested: List(List(Int)))
+  Wobble
+}
+"#,
+        vec![
+            ("Green", "Colour"),
+            ("Red", "Colour"),
+            (
+                "Wibble",
+                "fn(Int, Bool, String, List(Colour), List(List(Int))) -> Wibble"
+            ),
+            ("Wobble", "Wibble"),
+            ("colour_from_json", "fn(String) -> Result(Colour, Nil)"),
+            ("colour_to_json", "fn(Colour) -> String"),
+            ("wibble_from_json", "fn(String) -> Result(Wibble, Nil)"),
+            ("wibble_to_json", "fn(Wibble) -> String"),
+        ],
+    );
+}
+
+#[test]
+fn json_on_a_recursive_type() {
+    assert_module_infer!(
+        r#"
+@derive(json)
+pub type Tree {
+  Leaf(value: Int)
+  Node(children: List(Tree))
+}
+"#,
+        vec![
+            ("Leaf", "fn(Int) -> Tree"),
+            ("Node", "fn(List(Tree)) -> Tree"),
+            ("tree_from_json", "fn(String) -> Result(Tree, Nil)"),
+            ("tree_to_json", "fn(Tree) -> String"),
+        ],
+    );
+}
+
+#[test]
+fn json_functions_can_be_used_from_another_module() {
+    assert_infer_with_module!(
+        (
+            "point",
+            r#"
+@derive(json)
+pub type Point {
+  Point(x: Int, y: Int)
+}
+"#
+        ),
+        r#"
+import point
+
+pub fn main() {
+  point.point_from_json(point.point_to_json(point.Point(1, 2)))
+}
+"#,
+        vec![("main", "fn() -> Result(Point, Nil)")],
+    );
+}
+
+#[test]
+fn json_and_comparable_on_the_same_type() {
+    assert_infer_with_module!(
+        ("gleam_stdlib", "gleam/order", "pub type Order { Lt Eq Gt }"),
+        r#"
+import gleam/order
+
+@implements(comparable)
+@derive(json)
+pub type Size {
+  Small
+  Large
+}
+"#,
+        vec![
+            ("Large", "Size"),
+            ("Small", "Size"),
+            ("compare_size", "fn(Size, Size) -> Order"),
+            ("size_from_json", "fn(String) -> Result(Size, Nil)"),
+            ("size_to_json", "fn(Size) -> String"),
+        ],
+    );
+}
+
+#[test]
+fn unused_json_functions_of_private_type_do_not_warn() {
+    assert_no_warnings!(
+        r#"
+@derive(json)
+type Point {
+  Point(x: Int, name: String)
+}
+
+pub fn main() {
+  let _ = Point(1, "one")
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn json_on_a_type_with_no_variants() {
+    assert_module_error!(
+        r#"
+@derive(json)
+pub type Wibble
+"#
+    );
+}
+
+#[test]
+fn json_on_a_generic_type() {
+    assert_module_error!(
+        r#"
+@derive(json)
+pub type Box(a) {
+  Box(value: a)
+}
+"#
+    );
+}
+
+#[test]
+fn json_on_a_type_with_an_unsupported_field() {
+    assert_module_error!(
+        r#"
+@derive(json)
+pub type Wibble {
+  Wibble(ratio: Float)
+}
+"#
+    );
+}
+
+#[test]
+fn json_on_a_type_with_a_field_not_deriving_json() {
+    assert_module_error!(
+        r#"
+pub type Colour {
+  Red
+}
+
+@derive(json)
+pub type Wibble {
+  Wibble(List(Colour))
+}
+"#
+    );
+}
+
+#[test]
+fn json_function_clashing_with_existing_function() {
+    assert_module_error!(
+        r#"
+@derive(json)
+pub type Point {
+  Point(x: Int)
+}
+
+pub fn point_to_json(point: Point) -> String {
+  "{}"
+}
+"#
+    );
+}
@@ -0,0 +1,110 @@
+use crate::{
+    assert_infer, assert_module_error, assert_module_infer, assert_no_warnings, assert_warning,
+};
+
+#[test]
+fn arm_block_bindings_are_scoped_to_the_arm() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    1 -> {
+      let y = 1
+      y
+    }
+    _ -> y
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn arm_block_bindings_are_not_visible_after_the_case() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  let _ = case x {
+    1 -> {
+      let y = 1
+      y
+    }
+    _ -> 2
+  }
+  y
+}
+"#
+    );
+}
+
+#[test]
+fn arm_block_bindings_shadow_outer_variables_only_in_the_arm() {
+    assert_module_infer!(
+        r#"
+pub fn main(x) {
+  let y = "outer"
+  let _ = case x {
+    1 -> {
+      let y = 1
+      y + 1
+    }
+    _ -> 2
+  }
+  y
+}
+"#,
+        vec![("main", "fn(Int) -> String")]
+    );
+}
+
+#[test]
+fn arm_block_bindings_can_shadow_pattern_variables() {
+    assert_infer!(
+        r#"
+case Ok(1) {
+  Ok(x) -> {
+    let x = #(x, x)
+    x
+  }
+  Error(_) -> #(0, 0)
+}
+"#,
+        "#(Int, Int)"
+    );
+}
+
+#[test]
+fn unused_arm_block_binding_is_warned_about() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    1 -> {
+      let y = 1
+      2
+    }
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn shadowed_outer_variable_used_after_the_case_is_not_unused() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  let y = 1
+  let z = case x {
+    1 -> {
+      let y = 2
+      y
+    }
+    _ -> 3
+  }
+  y + z
+}
+"#
+    );
+}
@@ -0,0 +1,103 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn warnings(src: &str, composable_pipelines: bool) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        composable_pipelines,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config(src, config)
+}
+
+macro_rules! assert_composable_pipeline_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_composable_pipeline_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const COMPOSABLE_PIPELINE: &str = r#"
+fn double(x: Int) -> Int { x * 2 }
+fn increment(x: Int) -> Int { x + 1 }
+
+pub fn main(x: Int) {
+  x |> double |> increment
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(COMPOSABLE_PIPELINE, false), "");
+}
+
+#[test]
+fn pipeline_of_unary_functions() {
+    assert_composable_pipeline_warning!(COMPOSABLE_PIPELINE);
+}
+
+#[test]
+fn long_pipeline_of_unary_functions() {
+    assert_composable_pipeline_warning!(
+        r#"
+fn double(x: Int) -> Int { x * 2 }
+fn increment(x: Int) -> Int { x + 1 }
+
+pub fn main(x: Int) {
+  x
+  |> double
+  |> increment
+  |> double
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_with_a_call() {
+    assert_no_composable_pipeline_warning!(
+        r#"
+fn double(x: Int) -> Int { x * 2 }
+fn add(x: Int, y: Int) -> Int { x + y }
+
+pub fn main(x: Int) {
+  x |> double |> add(1)
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_with_an_anonymous_function() {
+    assert_no_composable_pipeline_warning!(
+        r#"
+fn double(x: Int) -> Int { x * 2 }
+
+pub fn main(x: Int) {
+  x |> double |> fn(y) { y + 1 }
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_with_a_single_step() {
+    assert_no_composable_pipeline_warning!(
+        r#"
+fn double(x: Int) -> Int { x * 2 }
+
+pub fn main(x: Int) {
+  x |> double
+}
+"#
+    );
+}
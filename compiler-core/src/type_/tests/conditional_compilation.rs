@@ -1,4 +1,7 @@
-use crate::assert_module_infer;
+use crate::{
+    assert_module_error_with_features, assert_module_infer, assert_module_infer_with_features,
+    assert_with_module_error, build::Target, type_::tests::infer_module_with_target,
+};
 
 #[test]
 fn excluded_error() {
@@ -105,3 +108,363 @@ pub fn main() -> Int
         vec![("main", "fn() -> Int")],
     );
 }
+
+#[test]
+fn enabled_feature() {
+    assert_module_infer_with_features!(
+        ["experimental"],
+        r#"
+@when(feature: "experimental")
+pub fn wibble() { 1 }
+
+pub fn main() { wibble() }
+"#,
+        vec![("main", "fn() -> Int"), ("wibble", "fn() -> Int")],
+    );
+}
+
+#[test]
+fn disabled_feature() {
+    assert_module_infer_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub fn wibble() { 1 }
+
+pub const x = 1
+"#,
+        vec![("x", "Int")],
+    );
+}
+
+#[test]
+fn disabled_feature_definitions_are_not_analysed() {
+    assert_module_infer_with_features!(
+        ["wobble"],
+        r#"
+@when(feature: "experimental")
+pub fn wibble() { this_does_not_exist() }
+
+@when(feature: "wobble")
+pub fn wobble() { 1 }
+"#,
+        vec![("wobble", "fn() -> Int")],
+    );
+}
+
+#[test]
+fn enabled_feature_type() {
+    assert_module_infer_with_features!(
+        ["experimental"],
+        r#"
+@when(feature: "experimental")
+pub type Wibble { Wibble }
+
+@when(feature: "experimental")
+pub fn wibble() -> Wibble { Wibble }
+"#,
+        vec![("Wibble", "Wibble"), ("wibble", "fn() -> Wibble")],
+    );
+}
+
+#[test]
+fn reference_to_disabled_function() {
+    assert_module_error_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub fn wibble() { 1 }
+
+pub fn main() { wibble() }
+"#
+    );
+}
+
+#[test]
+fn reference_to_disabled_constant() {
+    assert_module_error_with_features!(
+        ["wobble"],
+        r#"
+@when(feature: "experimental")
+const wibble = 1
+
+pub fn main() { wibble }
+"#
+    );
+}
+
+#[test]
+fn reference_to_disabled_type() {
+    assert_module_error_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub type Wibble
+
+pub fn main(x: Wibble) { x }
+"#
+    );
+}
+
+#[test]
+fn reference_to_disabled_type_alias() {
+    assert_module_error_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub type Wibble = Int
+
+pub fn main(x: Wibble) { x }
+"#
+    );
+}
+
+#[test]
+fn reference_to_disabled_constructor() {
+    assert_module_error_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub type Wibble { Wobble }
+
+pub fn main() { Wobble }
+"#
+    );
+}
+
+#[test]
+fn reference_to_disabled_constructor_in_pattern() {
+    assert_module_error_with_features!(
+        [],
+        r#"
+@when(feature: "experimental")
+pub type Wibble { Wobble }
+
+pub fn main(x) {
+  case x {
+    Wobble -> 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn qualified_reference_to_disabled_function_in_other_module() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub fn wobble() { 1 }"#
+        ),
+        "
+import wibble
+
+pub fn main() { wibble.wobble() }
+"
+    );
+}
+
+#[test]
+fn unqualified_import_of_disabled_function_in_other_module() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub fn wobble() { 1 }"#
+        ),
+        "
+import wibble.{wobble}
+
+pub fn main() { wobble() }
+"
+    );
+}
+
+#[test]
+fn qualified_reference_to_disabled_type_in_other_module() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub type Wobble"#
+        ),
+        "
+import wibble
+
+pub fn main(x: wibble.Wobble) { x }
+"
+    );
+}
+
+#[test]
+fn unqualified_import_of_disabled_type_in_other_module() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub type Wobble"#
+        ),
+        "
+import wibble.{type Wobble}
+
+pub fn main(x: Wobble) { x }
+"
+    );
+}
+
+#[test]
+fn disabled_constructor_in_other_module_in_pattern() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub type Wibble { Wobble }"#
+        ),
+        "
+import wibble
+
+pub fn main(x) {
+  case x {
+    wibble.Wobble -> 1
+  }
+}
+"
+    );
+}
+
+#[test]
+fn disabled_constant_in_other_module_used_in_constant() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+pub const wobble = 1"#
+        ),
+        "
+import wibble
+
+pub const x = wibble.wobble
+"
+    );
+}
+
+#[test]
+fn private_disabled_function_in_other_module_is_unknown() {
+    assert_with_module_error!(
+        (
+            "wibble",
+            r#"@when(feature: "experimental")
+fn wobble() { 1 }"#
+        ),
+        "
+import wibble
+
+pub fn main() { wibble.wobble() }
+"
+    );
+}
+
+const TARGET_SPECIFIC_IMPORTS: &str = r#"
+@target(erlang)
+import platform/erlang as platform
+
+@target(javascript)
+import platform/javascript as platform
+
+pub fn name() { platform.name() }
+"#;
+
+#[test]
+fn target_specific_imports_on_erlang() {
+    let module = infer_module_with_target(
+        "test_module",
+        TARGET_SPECIFIC_IMPORTS,
+        vec![
+            (
+                "thepackage",
+                "platform/erlang",
+                r#"pub fn name() { "erlang" }"#,
+            ),
+            ("thepackage", "platform/javascript", "pub fn name() { 1 }"),
+        ],
+        Target::Erlang,
+    );
+    assert_eq!(module, vec![("name".into(), "fn() -> String".into())]);
+}
+
+#[test]
+fn target_specific_imports_on_javascript() {
+    let module = infer_module_with_target(
+        "test_module",
+        TARGET_SPECIFIC_IMPORTS,
+        vec![
+            (
+                "thepackage",
+                "platform/erlang",
+                r#"pub fn name() { "erlang" }"#,
+            ),
+            ("thepackage", "platform/javascript", "pub fn name() { 1 }"),
+        ],
+        Target::JavaScript,
+    );
+    assert_eq!(module, vec![("name".into(), "fn() -> Int".into())]);
+}
+
+#[test]
+fn import_for_the_other_target_is_not_analysed() {
+    // The module doesn't need to exist when compiling for Erlang.
+    assert_module_infer!(
+        "
+@target(javascript)
+import only/on/javascript
+
+pub const x = 1
+",
+        vec![("x", "Int")],
+    );
+}
+
+#[test]
+fn value_from_module_imported_for_other_target() {
+    assert_with_module_error!(
+        ("wibble", "pub fn wobble() { 1 }"),
+        "
+@target(javascript)
+import wibble
+
+pub fn main() { wibble.wobble() }
+"
+    );
+}
+
+#[test]
+fn type_from_module_imported_for_other_target() {
+    assert_with_module_error!(
+        ("wibble", "pub type Wobble { Wobble }"),
+        "
+@target(javascript)
+import wibble
+
+pub fn main(x: wibble.Wobble) { x }
+"
+    );
+}
+
+#[test]
+fn constructor_from_module_imported_for_other_target_in_pattern() {
+    assert_with_module_error!(
+        ("wibble", "pub type Wobble { Wobble }"),
+        "
+@target(javascript)
+import wibble
+
+pub fn main(x) {
+  case x {
+    wibble.Wobble -> 1
+    _ -> 2
+  }
+}
+"
+    );
+}
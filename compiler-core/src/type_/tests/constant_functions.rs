@@ -0,0 +1,125 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn warnings(src: &str, constant_functions: bool) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        constant_functions,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config(src, config)
+}
+
+macro_rules! assert_constant_function_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_constant_function_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const CONSTANT_FUNCTION: &str = r#"
+pub fn main() {
+  fn(_, _) { 1 }
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(CONSTANT_FUNCTION, false), "");
+}
+
+#[test]
+fn function_discarding_all_arguments() {
+    assert_constant_function_warning!(CONSTANT_FUNCTION);
+}
+
+#[test]
+fn function_discarding_its_only_named_argument() {
+    assert_constant_function_warning!(
+        r#"
+pub fn main() {
+  fn(_wibble) { Nil }
+}
+"#
+    );
+}
+
+#[test]
+fn nested_function_discarding_all_arguments() {
+    assert_constant_function_warning!(
+        r#"
+pub fn main() {
+  fn(x) { fn(_) { x } }
+}
+"#
+    );
+}
+
+#[test]
+fn function_using_some_of_its_arguments() {
+    assert_no_constant_function_warning!(
+        r#"
+pub fn main() {
+  fn(_, x) { x }
+}
+"#
+    );
+}
+
+#[test]
+fn function_with_no_arguments() {
+    assert_no_constant_function_warning!(
+        r#"
+pub fn main() {
+  fn() { 1 }
+}
+"#
+    );
+}
+
+#[test]
+fn function_capture() {
+    assert_no_constant_function_warning!(
+        r#"
+fn wibble(a, b) { a + b }
+
+pub fn main() {
+  wibble(1, _)
+}
+"#
+    );
+}
+
+#[test]
+fn use_callback_discarding_its_argument() {
+    assert_no_constant_function_warning!(
+        r#"
+fn wibble(f) { f(1) }
+
+pub fn main() {
+  use _ <- wibble
+  2
+}
+"#
+    );
+}
+
+#[test]
+fn module_function_discarding_all_arguments() {
+    assert_no_constant_function_warning!(
+        r#"
+pub fn main(_) {
+  1
+}
+"#
+    );
+}
@@ -0,0 +1,198 @@
+use crate::{assert_infer_with_module, assert_no_warnings, assert_with_module_error};
+
+#[test]
+fn int_constant_pattern() {
+    assert_infer_with_module!(
+        ("numbers", "pub const zero = 0"),
+        "
+import numbers
+
+pub fn is_zero(x) {
+  case x {
+    numbers.zero -> True
+    _ -> False
+  }
+}
+",
+        vec![("is_zero", "fn(Int) -> Bool")],
+    );
+}
+
+#[test]
+fn float_constant_pattern() {
+    assert_infer_with_module!(
+        ("numbers", "pub const half = 0.5"),
+        "
+import numbers
+
+pub fn is_half(x) {
+  case x {
+    numbers.half -> True
+    _ -> False
+  }
+}
+",
+        vec![("is_half", "fn(Float) -> Bool")],
+    );
+}
+
+#[test]
+fn string_constant_pattern() {
+    assert_infer_with_module!(
+        ("greetings", r#"pub const hello = "Hello""#),
+        "
+import greetings
+
+pub fn is_hello(x) {
+  case x {
+    greetings.hello -> True
+    _ -> False
+  }
+}
+",
+        vec![("is_hello", "fn(String) -> Bool")],
+    );
+}
+
+#[test]
+fn constant_referencing_constant_pattern() {
+    assert_infer_with_module!(
+        (
+            "numbers",
+            "
+const zero = 0
+pub const nothing = zero
+"
+        ),
+        "
+import numbers
+
+pub fn is_nothing(x) {
+  case x {
+    numbers.nothing -> True
+    _ -> False
+  }
+}
+",
+        vec![("is_nothing", "fn(Int) -> Bool")],
+    );
+}
+
+#[test]
+fn constant_pattern_in_nested_pattern() {
+    assert_infer_with_module!(
+        ("numbers", "pub const zero = 0"),
+        "
+import numbers as n
+
+pub fn starts_with_zero(x) {
+  case x {
+    [n.zero, ..] -> True
+    _ -> False
+  }
+}
+",
+        vec![("starts_with_zero", "fn(List(Int)) -> Bool")],
+    );
+}
+
+#[test]
+fn constant_pattern_import_is_used() {
+    assert_no_warnings!(
+        ("thepackage", "numbers", "pub const zero = 0"),
+        "
+import numbers
+
+pub fn is_zero(x) {
+  case x {
+    numbers.zero -> True
+    _ -> False
+  }
+}
+"
+    );
+}
+
+#[test]
+fn constant_pattern_wrong_type() {
+    assert_with_module_error!(
+        ("numbers", "pub const zero = 0"),
+        r#"
+import numbers
+
+pub fn main() {
+  case "wibble" {
+    numbers.zero -> True
+    _ -> False
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn non_literal_constant_pattern() {
+    assert_with_module_error!(
+        ("numbers", "pub const small = [1, 2, 3]"),
+        "
+import numbers
+
+pub fn main() {
+  case [] {
+    numbers.small -> True
+    _ -> False
+  }
+}
+"
+    );
+}
+
+#[test]
+fn function_constant_pattern() {
+    assert_with_module_error!(
+        ("numbers", "pub fn zero() { 0 }"),
+        "
+import numbers
+
+pub fn main() {
+  case 1 {
+    numbers.zero -> True
+    _ -> False
+  }
+}
+"
+    );
+}
+
+#[test]
+fn unknown_constant_pattern() {
+    assert_with_module_error!(
+        ("numbers", "pub const zero = 0"),
+        "
+import numbers
+
+pub fn main() {
+  case 1 {
+    numbers.one -> True
+    _ -> False
+  }
+}
+"
+    );
+}
+
+#[test]
+fn constant_pattern_is_not_exhaustive() {
+    assert_with_module_error!(
+        ("numbers", "pub const zero = 0"),
+        "
+import numbers
+
+pub fn main() {
+  case 1 {
+    numbers.zero -> True
+  }
+}
+"
+    );
+}
@@ -1,4 +1,7 @@
-use crate::{assert_module_error, assert_module_infer, assert_warning, assert_with_module_error};
+use crate::{
+    assert_infer_with_module, assert_module_error, assert_module_infer, assert_warning,
+    assert_with_module_error,
+};
 
 // https://github.com/gleam-lang/gleam/issues/2215
 #[test]
@@ -6,10 +9,90 @@ fn generic_phantom() {
     assert_module_infer!(
         r#"
 pub type Test(a) {
-  MakeTest(field: Test(Int))
+  MakeTest(field: List(Test(Int)))
 }
 "#,
-        vec![("MakeTest", "fn(Test(Int)) -> Test(a)")]
+        vec![("MakeTest", "fn(List(Test(Int))) -> Test(a)")]
+    );
+}
+
+#[test]
+fn phantom_type_parameter_is_kept_through_functions() {
+    assert_module_infer!(
+        r#"
+pub type Meters
+pub type Tagged(tag, a) {
+  Tagged(value: a)
+}
+
+pub fn meters(value: Int) -> Tagged(Meters, Int) {
+  Tagged(value)
+}
+
+pub fn map(tagged: Tagged(tag, a), fun: fn(a) -> b) -> Tagged(tag, b) {
+  let Tagged(value) = tagged
+  Tagged(fun(value))
+}
+
+pub fn increment(tagged) {
+  Tagged(..tagged, value: tagged.value + 1)
+}
+
+pub fn main() {
+  meters(1)
+  |> map(fn(value) { value * 2 })
+  |> increment
+}
+"#,
+        vec![
+            ("Tagged", "fn(a) -> Tagged(b, a)"),
+            ("increment", "fn(Tagged(a, Int)) -> Tagged(a, Int)"),
+            ("main", "fn() -> Tagged(Meters, Int)"),
+            ("map", "fn(Tagged(a, b), fn(b) -> c) -> Tagged(a, c)"),
+            ("meters", "fn(Int) -> Tagged(Meters, Int)"),
+        ]
+    );
+}
+
+#[test]
+fn phantom_type_parameter_prevents_mixing_differently_tagged_values() {
+    assert_module_error!(
+        r#"
+pub type Meters
+pub type Feet
+pub type Tagged(tag, a) {
+  Tagged(value: a)
+}
+
+pub fn add(one: Tagged(tag, Int), other: Tagged(tag, Int)) -> Tagged(tag, Int) {
+  Tagged(one.value + other.value)
+}
+
+pub fn main() {
+  let meters: Tagged(Meters, Int) = Tagged(1)
+  let feet: Tagged(Feet, Int) = Tagged(2)
+  add(meters, feet)
+}
+"#
+    );
+}
+
+#[test]
+fn phantom_type_parameter_is_checked_in_annotations() {
+    assert_module_error!(
+        r#"
+pub type Meters
+pub type Feet
+pub type Tagged(tag, a) {
+  Tagged(value: a)
+}
+
+pub fn main() {
+  let meters: Tagged(Meters, Int) = Tagged(1)
+  let feet: Tagged(Feet, Int) = meters
+  feet
+}
+"#
     );
 }
 
@@ -182,3 +265,200 @@ pub fn main() {
         ]
     );
 }
+
+#[test]
+fn qualified_record_update() {
+    assert_infer_with_module!(
+        (
+            "person",
+            "pub type Person { Person(name: String, age: Int) }"
+        ),
+        "
+import person
+
+pub fn main(p: person.Person) {
+  person.Person(..p, age: 11)
+}
+",
+        vec![("main", "fn(Person) -> Person")]
+    );
+}
+
+#[test]
+fn qualified_record_update_with_aliased_module() {
+    assert_infer_with_module!(
+        (
+            "person",
+            "pub type Person { Person(name: String, age: Int) }"
+        ),
+        "
+import person as p
+
+pub fn main(x: p.Person) {
+  p.Person(..x, name: \"Nubi\")
+}
+",
+        vec![("main", "fn(Person) -> Person")]
+    );
+}
+
+#[test]
+fn unqualified_record_update_with_imported_constructor() {
+    assert_infer_with_module!(
+        (
+            "person",
+            "pub type Person { Person(name: String, age: Int) }"
+        ),
+        "
+import person.{type Person, Person}
+
+pub fn main(p: Person) {
+  Person(..p, age: 11)
+}
+",
+        vec![("main", "fn(Person) -> Person")]
+    );
+}
+
+#[test]
+fn unqualified_record_update_with_aliased_constructor() {
+    assert_infer_with_module!(
+        (
+            "person",
+            "pub type Person { Person(name: String, age: Int) }"
+        ),
+        "
+import person.{Person as Human}
+
+pub fn main(p: person.Person) {
+  Human(..p, age: 11)
+}
+",
+        vec![("main", "fn(Person) -> Person")]
+    );
+}
+
+#[test]
+fn directly_recursive_type() {
+    assert_module_error!(
+        "
+pub type Wibble {
+  Wibble(Wibble)
+}
+"
+    );
+}
+
+#[test]
+fn directly_recursive_type_with_many_variants() {
+    assert_module_error!(
+        "
+pub type Wibble {
+  Wibble(Int, Wibble)
+  Wobble(#(Wibble, String))
+}
+"
+    );
+}
+
+#[test]
+fn mutually_recursive_types() {
+    assert_module_error!(
+        "
+pub type Wibble {
+  Wibble(Wobble)
+}
+
+pub type Wobble {
+  Wobble(Wubble)
+}
+
+pub type Wubble {
+  Wubble(Wibble)
+}
+"
+    );
+}
+
+#[test]
+fn type_holding_a_recursive_type_is_not_reported() {
+    assert_module_error!(
+        "
+pub type Wibble {
+  Wibble(Wobble)
+}
+
+pub type Wobble {
+  Wobble(Wobble)
+}
+"
+    );
+}
+
+#[test]
+fn list_recursive_type() {
+    assert_module_infer!(
+        "
+pub type Tree {
+  Tree(children: List(Tree))
+}
+",
+        vec![("Tree", "fn(List(Tree)) -> Tree")]
+    );
+}
+
+#[test]
+fn function_recursive_type() {
+    assert_module_infer!(
+        "
+pub type Stream {
+  Stream(next: fn() -> #(Int, Stream))
+}
+",
+        vec![("Stream", "fn(fn() -> #(Int, Stream)) -> Stream")]
+    );
+}
+
+#[test]
+fn recursive_type_with_base_case() {
+    assert_module_infer!(
+        "
+pub type Nat {
+  Zero
+  Successor(Nat)
+}
+
+pub type Wibble {
+  Wibble(Nat, Wibble)
+  Wobble(Nat)
+}
+",
+        vec![
+            ("Successor", "fn(Nat) -> Nat"),
+            ("Wibble", "fn(Nat, Wibble) -> Wibble"),
+            ("Wobble", "fn(Nat) -> Wibble"),
+            ("Zero", "Nat"),
+        ]
+    );
+}
+
+#[test]
+fn recursive_type_through_generic_type() {
+    assert_module_infer!(
+        r#"
+pub type Option(a) {
+  Some(a)
+  None
+}
+
+pub type Wibble {
+  Wibble(Option(Wibble))
+}
+"#,
+        vec![
+            ("None", "Option(a)"),
+            ("Some", "fn(a) -> Option(a)"),
+            ("Wibble", "fn(Option(Wibble)) -> Wibble"),
+        ]
+    );
+}
@@ -65,6 +65,11 @@ fn bit_array_segment_nosize3() {
     assert_error!("case <<1>> { <<_:bytes, _:bits>> -> 1 }");
 }
 
+#[test]
+fn bit_array_rest_segment_followed_by_fixed_size_segment() {
+    assert_error!("case <<1, 2>> { <<_:8, rest:bytes, _:8>> -> rest _ -> <<>> }");
+}
+
 #[test]
 fn bit_array_segment_conflicting_options_int() {
     assert_error!("let x = <<1:int-bytes>> x");
@@ -206,6 +211,26 @@ fn add_f_int_float() {
     assert_error!("1 +. 1.0");
 }
 
+#[test]
+fn int_operator_used_with_floats() {
+    assert_error!("1.0 + 2.0");
+}
+
+#[test]
+fn float_operator_used_with_ints() {
+    assert_error!("1 +. 2");
+}
+
+#[test]
+fn int_comparison_used_with_floats() {
+    assert_error!("1.0 < 2.0");
+}
+
+#[test]
+fn int_operator_used_with_float_and_string() {
+    assert_error!(r#"1.0 + "wibble""#);
+}
+
 #[test]
 fn int_eq_float() {
     assert_error!("1 == 1.0");
@@ -884,6 +909,14 @@ fn x() { X(b: 1, a: 1, 1) }"#
     );
 }
 
+#[test]
+fn positional_argument_after_labelled_in_function_call() {
+    assert_module_error!(
+        r#"fn wibble(a a: Int, b b: Int, c c: Int) { a + b + c }
+fn x() { wibble(1, c: 1, 1) }"#
+    );
+}
+
 #[test]
 fn positional_argument_after_one_using_label_shorthand() {
     assert_module_error!(
@@ -948,6 +981,26 @@ pub fn main(box: Box(Box(Int))) { box.inner.unknown }"
     );
 }
 
+#[test]
+fn unknown_record_field_with_a_typo_suggests_the_closest_field() {
+    assert_module_error!(
+        "
+pub type Person { Person(name: String, age: Int, address: String) }
+pub fn main(person: Person) { person.adress }
+"
+    );
+}
+
+#[test]
+fn unknown_record_field_with_no_close_match_suggests_nothing() {
+    assert_module_error!(
+        "
+pub type Person { Person(name: String, age: Int, address: String) }
+pub fn main(person: Person) { person.favourite_colour }
+"
+    );
+}
+
 #[test]
 fn unnecessary_spread_operator() {
     assert_module_error!(
@@ -1616,6 +1669,20 @@ fn unknown_field_update() {
     );
 }
 
+#[test]
+fn unknown_raw_identifier_field_update() {
+    // The whole label is highlighted, including its backticks
+    assert_module_error!(
+        "
+pub type Person {
+  Person(name: String)
+}
+pub fn update_person(person: Person) {
+  Person(..person, `type`: 5)
+}"
+    );
+}
+
 #[test]
 fn unknown_field_update2() {
     // An unknown field given to a record update
@@ -1630,6 +1697,85 @@ fn unknown_field_update2() {
     );
 }
 
+#[test]
+fn unknown_field_in_nested_record_update() {
+    assert_module_error!(
+        "
+pub type Server {
+  Server(host: String, port: Int)
+}
+
+pub type Config {
+  Config(name: String, server: Server)
+}
+
+pub fn main(config: Config) {
+  Config(..config, server: Server(..config.server, prt: 8080))
+}"
+    );
+}
+
+#[test]
+fn unknown_field_in_outer_nested_record_update() {
+    assert_module_error!(
+        "
+pub type Server {
+  Server(host: String, port: Int)
+}
+
+pub type Config {
+  Config(name: String, server: Server)
+}
+
+pub fn main(config: Config) {
+  Config(..config, servr: Server(..config.server, port: 8080))
+}"
+    );
+}
+
+#[test]
+fn unknown_field_accessed_in_nested_record_update() {
+    assert_module_error!(
+        "
+pub type Server {
+  Server(host: String, port: Int)
+}
+
+pub type Config {
+  Config(name: String, server: Server)
+}
+
+pub fn main(config: Config) {
+  Config(..config, server: Server(..config.servr, port: 8080))
+}"
+    );
+}
+
+#[test]
+fn unknown_field_in_deeply_nested_record_update() {
+    assert_module_error!(
+        "
+pub type Port {
+  Port(number: Int)
+}
+
+pub type Server {
+  Server(host: String, port: Port)
+}
+
+pub type Config {
+  Config(name: String, server: Server)
+}
+
+pub fn main(config: Config) {
+  Config(
+    ..config,
+    server: Server(..config.server, port: Port(..config.server.port, numbr: 1)),
+  )
+}"
+    );
+}
+
 #[test]
 fn unknown_constructor_update() {
     // An unknown record constructor being used in a record update
@@ -2134,6 +2280,41 @@ fn mismatched_list_tail() {
     assert_error!("[\"wibble\", ..[1, 2]]");
 }
 
+#[test]
+fn int_literal_annotated_as_float() {
+    assert_error!("let x: Float = 5");
+}
+
+#[test]
+fn negative_int_literal_annotated_as_float() {
+    assert_error!("let x: Float = -1_000");
+}
+
+#[test]
+fn hexadecimal_int_literal_annotated_as_float() {
+    assert_error!("let x: Float = 0xff");
+}
+
+#[test]
+fn float_literal_annotated_as_int() {
+    assert_error!("let x: Int = 5.0");
+}
+
+#[test]
+fn float_literal_with_fraction_annotated_as_int() {
+    assert_error!("let x: Int = 5.5");
+}
+
+#[test]
+fn int_literal_constant_annotated_as_float() {
+    assert_module_error!("const x: Float = 5");
+}
+
+#[test]
+fn float_literal_constant_annotated_as_int() {
+    assert_module_error!("const x: Int = 5.");
+}
+
 #[test]
 fn leak_multiple_private_types() {
     assert_module_error!(
@@ -2971,3 +3152,205 @@ pub fn main() {
 "#
     );
 }
+
+#[test]
+fn qualified_unknown_constructor_update() {
+    assert_with_module_error!(
+        ("person", "pub type Person { Person(name: String) }"),
+        "
+import person
+
+pub fn main(p: person.Person) {
+  person.Human(..p, name: \"Lucy\")
+}
+"
+    );
+}
+
+#[test]
+fn qualified_function_update() {
+    assert_with_module_error!(
+        (
+            "person",
+            "pub type Person { Person(name: String) }
+pub fn new(name) { Person(name) }"
+        ),
+        "
+import person
+
+pub fn main(p: person.Person) {
+  person.new(..p, name: \"Lucy\")
+}
+"
+    );
+}
+
+#[test]
+fn qualified_constructor_without_labels_update() {
+    assert_with_module_error!(
+        ("pair", "pub type Pair { Pair(Int, Int) }"),
+        "
+import pair
+
+pub fn main(p: pair.Pair) {
+  pair.Pair(..p)
+}
+"
+    );
+}
+
+#[test]
+fn qualified_constant_update() {
+    assert_with_module_error!(
+        (
+            "person",
+            "pub type Person { Person(name: String) }
+pub const lucy = Person(\"Lucy\")"
+        ),
+        "
+import person
+
+pub fn main(p: person.Person) {
+  person.lucy(..p)
+}
+"
+    );
+}
+
+#[test]
+fn qualified_record_update_with_unknown_module() {
+    assert_module_error!(
+        "
+pub fn main(p) {
+  person.Person(..p, name: \"Lucy\")
+}
+"
+    );
+}
+
+#[test]
+fn qualified_opaque_constructor_update() {
+    assert_with_module_error!(
+        ("person", "pub opaque type Person { Person(name: String) }"),
+        "
+import person
+
+pub fn main(p: person.Person) {
+  person.Person(..p, name: \"Lucy\")
+}
+"
+    );
+}
+
+#[test]
+fn duplicate_var_in_list_tail() {
+    assert_error!("case [1, 2, 3] { [x, ..x] -> 1 }");
+}
+
+#[test]
+fn duplicate_var_in_nested_pattern() {
+    assert_module_error!(
+        r#"type Box { Box(inner: #(Int, Int)) }
+fn x() {
+  case [Box(#(1, 2))] { [Box(#(x, _)), Box(#(_, x))] -> 1 _ -> 0 }
+}"#
+    );
+}
+
+#[test]
+fn mismatched_large_nested_tuple_shows_difference() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let x: #(Int, String, List(#(Int, Float))) = #(1, "a", [#(1, "b")])
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn mismatched_large_custom_type_shows_difference() {
+    assert_module_error!(
+        r#"
+pub type Wibble(a, b, c) {
+  Wibble(a, b, c)
+}
+
+pub fn main() {
+  let x: Wibble(Int, List(Result(Int, String)), Bool) = Wibble(1, [Ok(1)], 1.0)
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn mismatched_large_function_argument_is_pointed_out() {
+    assert_module_error!(
+        r#"
+fn apply(f: fn(Int, String, List(Int)) -> Nil) -> Nil {
+  f(1, "a", [])
+}
+
+fn callback(a: Int, b: Int, c: List(Int)) -> Nil {
+  Nil
+}
+
+pub fn main() {
+  apply(callback)
+}
+"#
+    );
+}
+
+#[test]
+fn mismatched_large_function_return_is_pointed_out() {
+    assert_module_error!(
+        r#"
+fn apply(f: fn(Int, String, List(Int)) -> Nil) -> Nil {
+  f(1, "a", [])
+}
+
+pub fn main() {
+  apply(fn(a: Int, b: String, c: List(Int)) { a })
+}
+"#
+    );
+}
+
+#[test]
+fn mismatched_large_types_of_different_kinds_show_no_difference() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let x: #(Int, String, List(#(Int, Float))) = [#(1, "a", [#(1, 1.0)])]
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn bit_array_size_from_later_segment() {
+    assert_module_error!(
+        "pub fn parse(x) {
+  case x {
+    <<payload:bytes-size(length), length:8>> -> payload
+    _ -> <<>>
+  }
+}"
+    );
+}
+
+#[test]
+fn bit_array_size_from_non_int_segment() {
+    assert_module_error!(
+        "pub fn parse(x) {
+  case x {
+    <<length:bytes-size(1), payload:bytes-size(length)>> -> payload
+    _ -> <<>>
+  }
+}"
+    );
+}
@@ -1485,3 +1485,173 @@ fn multiple_unreachable_prefix_patterns_1() {
 }"#
     );
 }
+
+#[test]
+fn string_prefix_without_catch_all() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    "data:" <> rest -> rest
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_string_prefixes_without_catch_all() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    "data:" <> rest -> rest
+    "http:" <> rest -> rest
+    "" -> ""
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn string_prefix_in_tuple_without_catch_all() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    #("data:" <> rest, _) -> rest
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn string_prefix_with_guarded_catch_all() {
+    assert_module_error!(
+        r#"
+pub fn main(x, y) {
+  case x {
+    "data:" <> rest -> rest
+    _ if y -> ""
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn string_prefix_with_catch_all() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  case x {
+    "data:" <> rest -> rest
+    "http:" <> rest -> rest
+    _ -> ""
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn string_prefix_in_tuple_with_catch_all() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  case x {
+    #("data:" <> rest, _) -> rest
+    #(_, rest) -> rest
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn redundant_specific_pattern_after_wildcard() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    _ -> 1
+    Ok(_) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn redundant_nested_pattern_after_covering_pattern() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(_) -> 1
+    Error(_) -> 2
+    Ok(1) -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn redundant_clause_with_multiple_subjects() {
+    assert_warning!(
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    True, _ -> 1
+    _, _ -> 2
+    False, True -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn redundant_clause_covered_by_alternative_patterns() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    True | False -> 1
+    True -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn redundant_clause_with_guard_after_wildcard() {
+    assert_warning!(
+        r#"
+pub fn main(x, y) {
+  case x {
+    _ -> 1
+    _ if y -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn clause_after_guarded_wildcard_is_reachable() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x, y) {
+  case x {
+    _ if y -> 1
+    _ -> 2
+  }
+}
+"#
+    );
+}
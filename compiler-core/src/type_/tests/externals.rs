@@ -173,6 +173,78 @@ pub fn main() {
     );
 }
 
+#[test]
+fn javascript_only_function_reached_through_a_dependency_function() {
+    assert_with_module_error!(
+        (
+            "dependency",
+            "module",
+            r#"@external(javascript, "one", "two")
+fn javascript_only() -> Int
+
+pub fn wrapper() -> Int {
+  javascript_only() + 1
+}"#
+        ),
+        "import module
+pub fn main() {
+  module.wrapper()
+}",
+    );
+}
+
+#[test]
+fn javascript_only_function_reached_through_mutual_recursion_in_a_dependency() {
+    assert_with_module_error!(
+        (
+            "dependency",
+            "module",
+            r#"@external(javascript, "one", "two")
+fn javascript_only() -> Int
+
+pub fn wibble(x: Int) -> Int {
+  case x {
+    0 -> 0
+    _ -> wobble(x - 1)
+  }
+}
+
+fn wobble(x: Int) -> Int {
+  case x {
+    0 -> javascript_only()
+    _ -> wibble(x - 1)
+  }
+}"#
+        ),
+        "import module
+pub fn main() {
+  module.wibble(10)
+}",
+    );
+}
+
+#[test]
+fn javascript_only_function_reached_through_a_dependency_erlang_external() {
+    assert_infer_with_module!(
+        (
+            "dependency",
+            "module",
+            r#"@external(javascript, "one", "two")
+fn javascript_only() -> Int
+
+@external(erlang, "one", "two")
+pub fn wrapper() -> Int {
+  javascript_only()
+}"#
+        ),
+        "import module
+pub fn main() {
+  module.wrapper()
+}",
+        vec![("main", "fn() -> Int")],
+    );
+}
+
 #[test]
 fn javascript_only_constant() {
     assert_with_module_error!(
@@ -234,3 +306,71 @@ pub const wobble = wobble",
         vec![("wobble", "fn() -> Int")],
     );
 }
+
+#[test]
+fn erlang_external_with_gleam_fallback_is_supported_on_both_targets() {
+    let module = r#"@external(erlang, "math", "double")
+fn double(x: Int) -> Int {
+  x * 2
+}
+
+pub fn main() {
+  double(1)
+}
+"#;
+    assert_module_infer!(module, vec![("main", "fn() -> Int")]);
+    assert_js_module_infer!(module, vec![("main", "fn() -> Int")]);
+}
+
+#[test]
+fn gleam_fallback_must_match_external_signature() {
+    assert_module_error!(
+        r#"@external(erlang, "math", "double")
+pub fn double(x: Int) -> Int {
+  "wibble"
+}
+"#
+    );
+}
+
+#[test]
+fn external_with_reordered_arguments() {
+    assert_module_infer!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, initial, list])
+@external(javascript, "./ffi.mjs", "fold", [list, fun, initial])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#,
+        vec![("fold", "fn(List(a), b, fn(a, b) -> b) -> b")]
+    );
+}
+
+#[test]
+fn external_arguments_must_be_arguments_of_the_function() {
+    assert_module_error!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, acc, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+    );
+}
+
+#[test]
+fn external_arguments_cannot_be_listed_twice() {
+    assert_module_error!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, list, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+    );
+}
+
+#[test]
+fn external_arguments_must_include_all_arguments() {
+    assert_module_error!(
+        r#"
+@external(erlang, "lists", "foldl", [fun, list])
+pub fn fold(list: List(a), initial: b, fun: fn(a, b) -> b) -> b
+"#
+    );
+}
@@ -0,0 +1,173 @@
+use crate::{assert_no_warnings, assert_warning};
+
+#[test]
+fn wrapper_around_local_function() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  fn(x) { double(x) }
+}
+
+fn double(x) { x * 2 }
+"#
+    );
+}
+
+#[test]
+fn wrapper_around_imported_function() {
+    assert_warning!(
+        ("wibble", "pub fn wobble(a, b) { a + b }"),
+        r#"
+import wibble
+
+pub fn main() {
+  fn(a, b) { wibble.wobble(a, b) }
+}
+"#
+    );
+}
+
+#[test]
+fn wrapper_around_record_constructor() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  fn(x) { Ok(x) }
+}
+"#
+    );
+}
+
+#[test]
+fn wrapper_around_function_with_no_arguments() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  fn() { wibble() }
+}
+
+fn wibble() { 1 }
+"#
+    );
+}
+
+#[test]
+fn wrapper_with_arguments_in_a_different_order_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a, b) { wibble(b, a) }
+}
+
+fn wibble(a, b) { a - b }
+"#
+    );
+}
+
+#[test]
+fn wrapper_with_labelled_arguments_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a, b) { wibble(second: a, first: b) }
+}
+
+fn wibble(first a, second b) { a - b }
+"#
+    );
+}
+
+#[test]
+fn wrapper_partially_applying_a_function_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a) { wibble(a, 1) }
+}
+
+fn wibble(a, b) { a - b }
+"#
+    );
+}
+
+#[test]
+fn wrapper_not_passing_all_arguments_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a, _b) { wibble(a) }
+}
+
+fn wibble(a) { a }
+"#
+    );
+}
+
+#[test]
+fn wrapper_around_local_variable() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  let wibble = fn(a) { a + 1 }
+  fn(a) { wibble(a) }
+}
+"#
+    );
+}
+
+#[test]
+fn wrapper_calling_an_expression_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a) { wibble()(a) }
+}
+
+fn wibble() { fn(a) { a } }
+"#
+    );
+}
+
+#[test]
+fn wrapper_with_more_than_one_statement_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  fn(a) {
+    let b = a
+    wibble(b)
+  }
+}
+
+fn wibble(a) { a }
+"#
+    );
+}
+
+#[test]
+fn function_capture_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  wibble(_)
+}
+
+fn wibble(a) { a }
+"#
+    );
+}
+
+#[test]
+fn use_callback_has_no_warning() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  use a <- wibble
+  wobble(a)
+}
+
+fn wibble(f) { f(1) }
+fn wobble(a) { a }
+"#
+    );
+}
@@ -1,4 +1,7 @@
-use crate::{assert_module_error, assert_module_infer};
+use crate::{
+    assert_module_error, assert_module_infer, assert_no_warnings, type_::tests::compile_module,
+};
+use ecow::EcoString;
 
 // https://github.com/gleam-lang/gleam/issues/1860
 #[test]
@@ -43,6 +46,18 @@ fn all_labelled() {
     );
 }
 
+#[test]
+fn labelled_arguments_after_positional_ones_in_any_order() {
+    assert_no_warnings!(
+        r#"pub fn wibble(a a: Int, b b: Int, c c: Int) { a + b + c }
+
+pub fn main() {
+  wibble(1, c: 3, b: 2)
+}
+"#
+    );
+}
+
 // https://github.com/gleam-lang/gleam/issues/1814
 #[test]
 fn out_of_order_generalisation() {
@@ -572,3 +587,164 @@ pub fn main() {
 "#
     );
 }
+
+#[test]
+fn benchmark_functions_are_recorded_in_definition_order() {
+    let module = compile_module(
+        "test_module",
+        r#"
+@benchmark
+pub fn sort_vector() {
+  helper()
+}
+
+fn helper() {
+  1
+}
+
+@benchmark
+pub fn sort_list() {
+  2
+}
+
+pub fn main() {
+  3
+}
+"#,
+        None,
+        vec![],
+    )
+    .expect("module should compile");
+
+    assert_eq!(
+        module.type_info.benchmarks,
+        vec![EcoString::from("sort_vector"), EcoString::from("sort_list")]
+    );
+}
+
+#[test]
+fn private_benchmark_function() {
+    assert_module_error!(
+        r#"
+@benchmark
+fn sort_list() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn benchmark_function_with_arguments() {
+    assert_module_error!(
+        r#"
+@benchmark
+pub fn sort_list(list) {
+  list
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function() {
+    assert_module_infer!(
+        r#"
+@memoize
+pub fn wibble(a: Int, b: Float, c: String, d: Bool, e: Nil, _f: List(Int)) -> Int {
+  a
+}
+"#,
+        vec![(
+            "wibble",
+            "fn(Int, Float, String, Bool, Nil, List(Int)) -> Int"
+        )]
+    );
+}
+
+#[test]
+fn memoized_function_with_inferred_argument_types() {
+    assert_module_infer!(
+        r#"
+@memoize
+pub fn fib(n) {
+  case n {
+    0 | 1 -> n
+    _ -> fib(n - 1) + fib(n - 2)
+  }
+}
+"#,
+        vec![("fib", "fn(Int) -> Int")]
+    );
+}
+
+#[test]
+fn memoized_function_with_unmemoizable_argument() {
+    assert_module_error!(
+        r#"
+@memoize
+pub fn sum(list: List(Int)) -> Int {
+  0
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_generic_argument() {
+    assert_module_error!(
+        r#"
+@memoize
+pub fn identity(x) {
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_function_with_function_argument() {
+    assert_module_error!(
+        r#"
+@memoize
+pub fn apply(f: fn(Int) -> Int, x: Int) -> Int {
+  f(x)
+}
+"#
+    );
+}
+
+#[test]
+fn memoized_external_function() {
+    assert_module_error!(
+        r#"
+@memoize
+@external(erlang, "wibble", "wobble")
+pub fn wobble(x: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn raw_identifier_is_a_regular_name() {
+    assert_module_infer!(
+        r#"
+pub type Token {
+  Token(`type`: String)
+}
+
+pub fn `type`(token: Token) -> String {
+  token.`type`
+}
+
+pub fn main() {
+  `type`(Token(`type`: "wibble"))
+}
+"#,
+        vec![
+            ("Token", "fn(String) -> Token"),
+            ("main", "fn() -> String"),
+            ("type", "fn(Token) -> String"),
+        ]
+    );
+}
@@ -0,0 +1,117 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn warnings(src: &str, identity_cases: bool) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        identity_cases,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config(src, config)
+}
+
+macro_rules! assert_identity_case_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_identity_case_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const IDENTITY_CASE: &str = r#"
+pub fn main(x: Result(Int, Nil)) {
+  case x {
+    Ok(_) -> x
+    Error(_) -> x
+  }
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(IDENTITY_CASE, false), "");
+}
+
+#[test]
+fn case_returning_its_subject_from_every_clause() {
+    assert_identity_case_warning!(IDENTITY_CASE);
+}
+
+#[test]
+fn case_with_guards_returning_its_subject() {
+    assert_identity_case_warning!(
+        r#"
+pub fn main(x: Int) {
+  case x {
+    _ if x > 1 -> x
+    _ -> x
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_returning_its_subject_from_some_clauses() {
+    assert_no_identity_case_warning!(
+        r#"
+pub fn main(x: Result(Int, Nil)) {
+  case x {
+    Ok(_) -> x
+    Error(_) -> Ok(1)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_returning_a_shadowing_variable() {
+    assert_no_identity_case_warning!(
+        r#"
+pub fn main(x: Result(Int, Int)) {
+  case x {
+    Ok(x) -> x
+    Error(x) -> x
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_with_multiple_subjects() {
+    assert_no_identity_case_warning!(
+        r#"
+pub fn main(x: Int, y: Int) {
+  case x, y {
+    _, _ -> x
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_a_constant() {
+    assert_no_identity_case_warning!(
+        r#"
+const x = 1
+
+pub fn main() {
+  case x {
+    1 -> x
+    _ -> x
+  }
+}
+"#
+    );
+}
@@ -326,3 +326,144 @@ pub fn main() {
 "
     );
 }
+
+#[test]
+fn importing_all_types() {
+    assert_infer_with_module!(
+        (
+            "one",
+            "pub type Wibble { Wibble }
+pub type Wobble(a) { Wobble(a) }
+pub type Alias = Int"
+        ),
+        "
+import one.{type *}
+
+pub fn main(wibble: Wibble, wobble: Wobble(Int), alias: Alias) -> #(Wibble, Wobble(Int), Alias) {
+  #(wibble, wobble, alias)
+}
+",
+        vec![(
+            "main",
+            "fn(Wibble, Wobble(Int), Int) -> #(Wibble, Wobble(Int), Int)"
+        )],
+    );
+}
+
+#[test]
+fn importing_all_types_along_with_values() {
+    assert_infer_with_module!(
+        ("one", "pub type Wibble { Wibble }"),
+        "
+import one.{type *, Wibble}
+
+pub fn main() -> Wibble {
+  Wibble
+}
+",
+        vec![("main", "fn() -> Wibble")],
+    );
+}
+
+#[test]
+fn importing_all_types_along_with_an_aliased_type() {
+    assert_infer_with_module!(
+        ("one", "pub type Wibble { Wibble }"),
+        "
+import one.{type *, type Wibble as Wobble}
+
+pub fn main(wibble: Wibble) -> Wobble {
+  wibble
+}
+",
+        vec![("main", "fn(Wibble) -> Wibble")],
+    );
+}
+
+#[test]
+fn importing_all_types_does_not_import_values() {
+    assert_with_module_error!(
+        ("one", "pub type Wibble { Wibble }"),
+        "
+import one.{type *}
+
+pub fn main() -> Wibble {
+  Wibble
+}
+"
+    );
+}
+
+#[test]
+fn importing_all_types_does_not_import_private_types() {
+    assert_with_module_error!(
+        ("one", "type Wibble { Wibble }"),
+        "
+import one.{type *}
+
+pub fn main(wibble: Wibble) {
+  wibble
+}
+"
+    );
+}
+
+#[test]
+fn importing_all_types_from_two_modules_with_the_same_type() {
+    assert_with_module_error!(
+        ("one", "pub type Wibble { One }"),
+        ("two", "pub type Wibble { Two }"),
+        "
+import one.{type *}
+import two.{type *}
+
+pub fn main() {
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn importing_all_types_colliding_with_a_local_type() {
+    assert_with_module_error!(
+        ("one", "pub type Wibble { One }"),
+        "
+import one.{type *}
+
+pub type Wibble {
+  Two
+}
+"
+    );
+}
+
+#[test]
+fn importing_all_types_colliding_with_a_value_is_fine() {
+    assert_infer_with_module!(
+        ("one", "pub type Wibble { Wibble }"),
+        "
+import one.{type *}
+
+pub fn wibble() -> Wibble {
+  one.Wibble
+}
+",
+        vec![("wibble", "fn() -> Wibble")],
+    );
+}
+
+#[test]
+fn importing_all_types_along_with_the_same_type() {
+    assert_infer_with_module!(
+        ("one", "pub type Wibble { Wibble }"),
+        "
+import one.{type *, type Wibble}
+
+pub fn main(wibble: Wibble) -> Wibble {
+  wibble
+}
+",
+        vec![("main", "fn(Wibble) -> Wibble")],
+    );
+}
@@ -0,0 +1,165 @@
+use crate::{
+    assert_infer_with_module, assert_module_error, assert_module_syntax_error, assert_warning,
+    assert_with_module_error,
+};
+
+#[test]
+fn single_generator() {
+    assert_infer_with_module!(
+        (
+            "gleam/list",
+            "pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }"
+        ),
+        r#"
+import gleam/list
+
+pub fn main() {
+  [int_to_string(x) | x <- [1, 2, 3]]
+}
+
+fn int_to_string(x: Int) -> String { todo }
+"#,
+        vec![("main", "fn() -> List(String)")],
+    );
+}
+
+#[test]
+fn generator_with_filters() {
+    assert_infer_with_module!(
+        (
+            "gleam/list",
+            r#"
+pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+pub fn filter(list: List(a), f: fn(a) -> Bool) -> List(a) { todo }
+"#
+        ),
+        r#"
+import gleam/list
+
+pub fn main(items) {
+  [x * 2 | x <- items, x > 0, x < 10]
+}
+"#,
+        vec![("main", "fn(List(Int)) -> List(Int)")],
+    );
+}
+
+#[test]
+fn multiple_generators() {
+    assert_infer_with_module!(
+        (
+            "gleam/list",
+            r#"
+pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+pub fn filter(list: List(a), f: fn(a) -> Bool) -> List(a) { todo }
+pub fn flat_map(list: List(a), f: fn(a) -> List(b)) -> List(b) { todo }
+"#
+        ),
+        r#"
+import gleam/list
+
+pub fn main(xs, ys) {
+  [#(x, y) | x <- xs, y <- ys, x != y, y >. 0.0]
+}
+"#,
+        vec![(
+            "main",
+            "fn(List(Float), List(Float)) -> List(#(Float, Float))"
+        )],
+    );
+}
+
+#[test]
+fn later_generators_can_use_earlier_variables() {
+    assert_infer_with_module!(
+        (
+            "gleam/list",
+            r#"
+pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+pub fn flat_map(list: List(a), f: fn(a) -> List(b)) -> List(b) { todo }
+"#
+        ),
+        r#"
+import gleam/list
+
+pub fn main(lists) {
+  [x | list <- lists, x <- list]
+}
+"#,
+        vec![("main", "fn(List(List(a))) -> List(a)")],
+    );
+}
+
+#[test]
+fn list_module_can_be_aliased() {
+    assert_infer_with_module!(
+        (
+            "gleam/list",
+            "pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }"
+        ),
+        r#"
+import gleam/list as l
+
+pub fn main() {
+  [x | x <- [1, 2, 3]]
+}
+"#,
+        vec![("main", "fn() -> List(Int)")],
+    );
+}
+
+#[test]
+fn list_module_is_not_imported() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  [x * 2 | x <- [1, 2, 3]]
+}
+"#
+    );
+}
+
+#[test]
+fn generator_over_a_value_that_is_not_a_list() {
+    assert_with_module_error!(
+        (
+            "gleam/list",
+            "pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }"
+        ),
+        r#"
+import gleam/list
+
+pub fn main() {
+  [x | x <- 1]
+}
+"#
+    );
+}
+
+#[test]
+fn unused_generator_variable() {
+    assert_warning!(
+        (
+            "gleam/list",
+            "pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }"
+        ),
+        r#"
+import gleam/list
+
+pub fn main() {
+  [1 | x <- [1, 2, 3]]
+}
+"#
+    );
+}
+
+#[test]
+fn list_comprehension_must_start_with_a_generator() {
+    assert_module_syntax_error!(
+        r#"
+pub fn main() {
+  [x | x > 1]
+}
+"#
+    );
+}
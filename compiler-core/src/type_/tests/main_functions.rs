@@ -0,0 +1,82 @@
+use crate::{Error, build::Target, type_::ModuleInterface};
+
+use super::compile_module;
+
+fn interface(src: &str) -> ModuleInterface {
+    compile_module("wibble/wobble", src, None, vec![])
+        .expect("should successfully infer")
+        .type_info
+}
+
+#[test]
+fn module_with_main_function() {
+    let module = interface("pub fn main() { Nil }");
+    let main = module.get_main_function(Target::Erlang).unwrap();
+    assert_eq!(main.package, "thepackage");
+}
+
+#[test]
+fn module_with_main_function_for_javascript() {
+    let module = interface("pub fn main() { Nil }");
+    assert!(module.get_main_function(Target::JavaScript).is_ok());
+}
+
+#[test]
+fn module_without_main_function() {
+    let module = interface("pub fn wibble() { Nil }");
+    assert_eq!(
+        module.get_main_function(Target::Erlang).unwrap_err(),
+        Error::ModuleDoesNotHaveMainFunction {
+            module: "wibble/wobble".into()
+        }
+    );
+}
+
+#[test]
+fn module_with_private_main_function() {
+    let module = interface("fn main() { Nil }");
+    assert_eq!(
+        module.get_main_function(Target::Erlang).unwrap_err(),
+        Error::ModuleDoesNotHaveMainFunction {
+            module: "wibble/wobble".into()
+        }
+    );
+}
+
+#[test]
+fn module_with_main_constant() {
+    let module = interface("pub const main = 1");
+    assert_eq!(
+        module.get_main_function(Target::Erlang).unwrap_err(),
+        Error::ModuleDoesNotHaveMainFunction {
+            module: "wibble/wobble".into()
+        }
+    );
+}
+
+#[test]
+fn module_with_main_function_taking_arguments() {
+    let module = interface("pub fn main(arguments) { arguments }");
+    assert_eq!(
+        module.get_main_function(Target::Erlang).unwrap_err(),
+        Error::MainFunctionHasWrongArity {
+            module: "wibble/wobble".into(),
+            arity: 1
+        }
+    );
+}
+
+#[test]
+fn module_with_main_function_not_supporting_target() {
+    let module = interface(
+        r#"@external(erlang, "wibble", "main")
+pub fn main() -> Nil"#,
+    );
+    assert_eq!(
+        module.get_main_function(Target::JavaScript).unwrap_err(),
+        Error::MainFunctionDoesNotSupportTarget {
+            module: "wibble/wobble".into(),
+            target: Target::JavaScript,
+        }
+    );
+}
@@ -0,0 +1,177 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn config(lints: LintsConfig) -> PackageConfig {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = lints;
+    config
+}
+
+fn warnings(src: &str, lints: LintsConfig) -> String {
+    get_printed_warnings_with_config(src, config(lints))
+}
+
+fn snake_case() -> LintsConfig {
+    LintsConfig {
+        snake_case_function_names: true,
+        ..Default::default()
+    }
+}
+
+fn min_type_parameter_length(length: usize) -> LintsConfig {
+    LintsConfig {
+        min_type_parameter_length: Some(length),
+        ..Default::default()
+    }
+}
+
+fn max_identifier_length(length: usize) -> LintsConfig {
+    LintsConfig {
+        max_identifier_length: Some(length),
+        ..Default::default()
+    }
+}
+
+macro_rules! assert_naming_warning {
+    ($src:expr, $lints:expr $(,)?) => {
+        let warning = warnings($src, $lints);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+const EVERYTHING: &str = r#"
+pub type WibbleWobbleTypeWithAVeryLongName(a) {
+  WibbleWobbleVariantWithAVeryLongName(a___very___long___label: a)
+}
+
+pub type AliasWithAVeryLongName(b) = List(b)
+
+pub const a___very___long___constant = 1
+
+pub fn wibble__wobble(x: c, a___very___long___argument: c) -> #(c, c) {
+  #(x, a___very___long___argument)
+}
+
+pub fn wobble_(x) { x }
+"#;
+
+#[test]
+fn lints_are_disabled_by_default() {
+    assert_eq!(warnings(EVERYTHING, LintsConfig::default()), "");
+}
+
+#[test]
+fn function_name_with_repeated_underscores() {
+    assert_naming_warning!("pub fn wibble__wobble() { 1 }", snake_case());
+}
+
+#[test]
+fn function_name_with_trailing_underscore() {
+    assert_naming_warning!("pub fn wibble_() { 1 }", snake_case());
+}
+
+#[test]
+fn snake_case_function_names() {
+    assert_eq!(
+        warnings(
+            "pub fn wibble_wobble() { 1 } pub fn wobble1_2() { wibble_wobble() }",
+            snake_case()
+        ),
+        ""
+    );
+}
+
+#[test]
+fn short_custom_type_parameter() {
+    assert_naming_warning!("pub type Box(a) { Box(a) }", min_type_parameter_length(3),);
+}
+
+#[test]
+fn short_type_alias_parameter() {
+    assert_naming_warning!(
+        "pub type Pairs(a) = List(#(a, a))",
+        min_type_parameter_length(3)
+    );
+}
+
+#[test]
+fn short_function_type_variable_is_reported_once() {
+    assert_naming_warning!(
+        "pub fn twice(f: fn(a) -> a, x: a) -> a { f(f(x)) }",
+        min_type_parameter_length(3),
+    );
+}
+
+#[test]
+fn long_enough_type_parameters() {
+    assert_eq!(
+        warnings(
+            r#"
+pub type Box(inner) { Box(inner) }
+pub type Pairs(element) = List(#(element, element))
+pub fn twice(f: fn(value) -> value, x: value) -> value { f(f(x)) }
+"#,
+            min_type_parameter_length(3)
+        ),
+        ""
+    );
+}
+
+#[test]
+fn long_function_name() {
+    assert_naming_warning!(
+        "pub fn a_function_with_a_really_long_name() { 1 }",
+        max_identifier_length(20),
+    );
+}
+
+#[test]
+fn long_argument_and_label_names() {
+    assert_naming_warning!(
+        "pub fn wibble(a_really_really_long_label a_really_long_argument) { a_really_long_argument }",
+        max_identifier_length(20),
+    );
+}
+
+#[test]
+fn long_constant_name() {
+    assert_naming_warning!(
+        "pub const a_constant_with_a_really_long_name = 1",
+        max_identifier_length(20),
+    );
+}
+
+#[test]
+fn long_type_variant_and_label_names() {
+    assert_naming_warning!(
+        "pub type ATypeWithAReallyLongName { AVariantWithAReallyLongName(a_really_long_label: Int) }",
+        max_identifier_length(20),
+    );
+}
+
+#[test]
+fn long_type_alias_name() {
+    assert_naming_warning!(
+        "pub type AnAliasWithAReallyLongName = Int",
+        max_identifier_length(20),
+    );
+}
+
+#[test]
+fn short_enough_identifiers() {
+    assert_eq!(
+        warnings(
+            r#"
+pub type Wibble { Wobble(label: Int) }
+pub type Alias = Wibble
+pub const constant = 1
+pub fn function(label argument: Int) { argument }
+"#,
+            max_identifier_length(20)
+        ),
+        ""
+    );
+}
@@ -0,0 +1,162 @@
+use crate::{assert_infer_with_module, assert_module_infer, assert_with_module_error};
+
+#[test]
+fn opaque_type_can_be_constructed_in_its_own_module() {
+    assert_module_infer!(
+        r#"
+pub opaque type Wibble {
+  Wobble(Int)
+}
+
+pub fn new(n) {
+  n |> Wobble
+}
+
+pub fn unwrap(wibble) {
+  let Wobble(n) = wibble
+  n
+}
+"#,
+        vec![
+            ("new", "fn(Int) -> Wibble"),
+            ("unwrap", "fn(Wibble) -> Int")
+        ]
+    );
+}
+
+#[test]
+fn opaque_type_can_be_used_through_functions_of_its_module() {
+    assert_infer_with_module!(
+        (
+            "wibble",
+            r#"
+pub opaque type Wibble {
+  Wobble(Int)
+}
+
+pub fn new(n) {
+  Wobble(n)
+}
+"#
+        ),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.new(1)
+}
+"#,
+        vec![("main", "fn() -> Wibble")]
+    );
+}
+
+#[test]
+fn opaque_type_constructor_used_from_another_module() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        "import wibble
+
+pub fn main() {
+  wibble.Wobble(1)
+}",
+    );
+}
+
+#[test]
+fn opaque_type_constructor_used_from_another_module_in_a_pipe() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        "import wibble
+
+pub fn main() {
+  1 |> wibble.Wobble
+}",
+    );
+}
+
+#[test]
+fn opaque_type_constructor_used_from_another_module_in_a_pattern() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        "import wibble
+
+pub fn main(x) {
+  let wibble.Wobble(n) = x
+  n
+}",
+    );
+}
+
+#[test]
+fn opaque_type_constructor_imported_unqualified() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        "import wibble.{Wobble}
+
+pub fn main() {
+  Wobble(1)
+}",
+    );
+}
+
+#[test]
+fn reexported_opaque_type_does_not_expose_its_constructors() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        (
+            "facade",
+            "import wibble
+pub type Wibble = wibble.Wibble"
+        ),
+        "import facade
+
+pub fn main() {
+  facade.Wobble(1)
+}",
+    );
+}
+
+#[test]
+fn opaque_type_constructor_used_from_another_module_in_a_constant() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) }"),
+        "import wibble
+
+pub const wibble = wibble.Wobble(1)",
+    );
+}
+
+#[test]
+fn opaque_type_constructor_can_be_matched_on_in_its_own_module() {
+    assert_module_infer!(
+        r#"
+pub opaque type Wibble {
+  Wobble(Int)
+  Wubble
+}
+
+pub fn to_int(wibble) {
+  case wibble {
+    Wobble(n) -> n
+    Wubble -> 0
+  }
+}
+"#,
+        vec![("to_int", "fn(Wibble) -> Int")]
+    );
+}
+
+#[test]
+fn opaque_type_constructor_matched_on_from_another_module_in_a_case() {
+    assert_with_module_error!(
+        ("wibble", "pub opaque type Wibble { Wobble(Int) Wubble }"),
+        "import wibble
+
+pub fn main(x) {
+  case x {
+    wibble.Wubble -> 0
+    _ -> 1
+  }
+}",
+    );
+}
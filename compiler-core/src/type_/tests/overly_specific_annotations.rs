@@ -0,0 +1,155 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn warnings(src: &str, overly_specific_annotations: bool) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        overly_specific_annotations,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config(src, config)
+}
+
+macro_rules! assert_overly_specific_annotation_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_overly_specific_annotation_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const IDENTITY: &str = r#"
+pub fn identity(x: Int) -> Int {
+  x
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(IDENTITY, false), "");
+}
+
+#[test]
+fn identity_function_annotated_with_a_concrete_type() {
+    assert_overly_specific_annotation_warning!(IDENTITY);
+}
+
+#[test]
+fn only_return_annotation_is_overly_specific() {
+    assert_overly_specific_annotation_warning!(
+        r#"
+pub fn wrap(x) -> List(String) {
+  [x]
+}
+"#
+    );
+}
+
+#[test]
+fn generic_annotation_is_not_overly_specific() {
+    assert_no_overly_specific_annotation_warning!(
+        r#"
+pub fn identity(x: a) -> a {
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn annotation_of_concrete_function_is_not_overly_specific() {
+    assert_no_overly_specific_annotation_warning!(
+        r#"
+pub fn increment(x: Int) -> Int {
+  x + 1
+}
+"#
+    );
+}
+
+#[test]
+fn partial_annotation_is_not_overly_specific() {
+    assert_no_overly_specific_annotation_warning!(
+        r#"
+pub fn pair(x, y: b) {
+  #(x, y)
+}
+"#
+    );
+}
+
+#[test]
+fn annotation_of_todo_function_is_not_checked() {
+    let warnings = warnings(
+        r#"
+pub fn parse(_input: String) -> Int {
+  todo
+}
+"#,
+        true,
+    );
+    assert!(!warnings.contains("Overly specific annotation"));
+}
+
+#[test]
+fn recursive_function_is_checked_against_its_own_annotation() {
+    assert_no_overly_specific_annotation_warning!(
+        r#"
+pub fn length(list: List(a), acc: Int) -> Int {
+  case list {
+    [] -> acc
+    [_, ..rest] -> length(rest, acc + 1)
+  }
+}
+"#
+    );
+}
+
+// The other functions of the group get their types from the annotations, so
+// they are just as specific once inferred.
+#[test]
+fn mutually_recursive_functions_are_checked_after_the_group() {
+    assert_no_overly_specific_annotation_warning!(
+        r#"
+pub fn is_even(x: Int, value: String) -> String {
+  case x {
+    0 -> value
+    _ -> is_odd(x - 1, value)
+  }
+}
+
+fn is_odd(x, value) {
+  case x {
+    0 -> value
+    _ -> is_even(x - 1, value)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn re_inferring_does_not_duplicate_warnings() {
+    assert_eq!(
+        warnings(
+            r#"
+pub fn main(x: Int) -> Int {
+  let y = 1
+  x
+}
+"#,
+            true
+        )
+        .matches("Unused variable")
+        .count(),
+        1
+    );
+}
@@ -1,4 +1,8 @@
-use crate::{assert_module_error, assert_module_infer, assert_no_warnings};
+use crate::{
+    assert_module_error, assert_module_infer, assert_no_warnings,
+    ast::{Definition, SrcSpan, Statement, TypedExpr},
+    type_::tests::compile_module,
+};
 
 // https://github.com/gleam-lang/gleam/issues/2392
 #[test]
@@ -183,3 +187,128 @@ pub fn main() {
 "
     );
 }
+
+#[test]
+fn pipe_type_mismatch_shows_expected_first_argument() {
+    assert_module_error!(
+        r#"
+fn takes_string(x: String) -> String {
+  x
+}
+
+pub fn main() {
+  1 |> takes_string
+}
+"#
+    );
+}
+
+#[test]
+fn pipe_type_mismatch_in_capture_is_reported_on_the_hole() {
+    assert_module_error!(
+        r#"
+fn wibble(x: Int, y: String) -> String {
+  y
+}
+
+pub fn main() {
+  1 |> wibble(1, _)
+}
+"#
+    );
+}
+
+#[test]
+fn piping_into_function_with_mismatched_labelled_first_parameter() {
+    assert_module_error!(
+        r#"
+fn wibble(to to: String) -> String { to }
+
+pub fn main() {
+  1 |> wibble
+}
+"#
+    );
+}
+
+#[test]
+fn piping_into_call_with_mismatched_labelled_first_parameter() {
+    assert_module_error!(
+        r#"
+fn wibble(to to: String, times times: Int) -> String { to }
+
+pub fn main() {
+  1 |> wibble(times: 2)
+}
+"#
+    );
+}
+
+#[test]
+fn piping_into_call_with_mismatched_first_parameter() {
+    assert_module_error!(
+        r#"
+fn wibble(a: String, b: Int) -> String { a }
+
+pub fn main() {
+  1 |> wibble(2)
+}
+"#
+    );
+}
+
+#[test]
+fn pipeline_assignments_record_the_operator_location() {
+    let src = r#"
+fn add(a, b) { a + b }
+
+pub fn main() {
+  1
+  |> add(2)
+  |> add(3)
+  |> add(4)
+}
+"#;
+    let module = compile_module("test_module", src, None, vec![]).expect("module should compile");
+    let main = module
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Function(function)
+                if function
+                    .name
+                    .as_ref()
+                    .is_some_and(|(_, name)| name == "main") =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+        .expect("main function");
+
+    let Statement::Expression(TypedExpr::Pipeline {
+        first_value,
+        assignments,
+        ..
+    }) = main.body.first()
+    else {
+        panic!("expected a pipeline")
+    };
+
+    let operator_locations = std::iter::once(first_value)
+        .chain(assignments.iter().map(|(assignment, _)| assignment))
+        .map(|assignment| assignment.operator_location)
+        .collect::<Vec<_>>();
+
+    let expected = src
+        .match_indices("|>")
+        .map(|(start, _)| {
+            Some(SrcSpan {
+                start: start as u32,
+                end: start as u32 + 2,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(operator_locations, expected);
+}
@@ -0,0 +1,167 @@
+use crate::{
+    assert_infer_with_module, assert_module_error, assert_module_infer, assert_no_warnings,
+};
+
+#[test]
+fn self_qualified_function() {
+    assert_module_infer!(
+        r#"
+fn helper(x: Int) -> String {
+  todo
+}
+
+pub fn main() {
+  self.helper(1)
+}
+"#,
+        vec![("main", "fn() -> String")]
+    );
+}
+
+#[test]
+fn self_qualified_function_with_labels() {
+    assert_module_infer!(
+        r#"
+fn helper(wibble x: Int, wobble y: Float) -> Float {
+  todo
+}
+
+pub fn main() {
+  self.helper(wobble: 1.0, wibble: 1)
+}
+"#,
+        vec![("main", "fn() -> Float")]
+    );
+}
+
+#[test]
+fn self_qualified_constant_and_constructor() {
+    assert_module_infer!(
+        r#"
+pub type Wibble {
+  Wibble(Int)
+}
+
+const wobble = 1
+
+pub fn main() {
+  self.Wibble(self.wobble)
+}
+"#,
+        vec![("Wibble", "fn(Int) -> Wibble"), ("main", "fn() -> Wibble")]
+    );
+}
+
+#[test]
+fn self_qualified_function_defined_later() {
+    assert_module_infer!(
+        r#"
+pub fn main() {
+  self.helper()
+}
+
+fn helper() -> Int {
+  1
+}
+"#,
+        vec![("main", "fn() -> Int")]
+    );
+}
+
+#[test]
+fn self_qualified_recursive_function() {
+    assert_module_infer!(
+        r#"
+pub fn count(n) {
+  case n {
+    0 -> 0
+    _ -> self.count(n - 1)
+  }
+}
+"#,
+        vec![("count", "fn(Int) -> Int")]
+    );
+}
+
+#[test]
+fn self_qualifier_ignores_shadowing_local_variable() {
+    assert_module_infer!(
+        r#"
+fn helper() -> String {
+  todo
+}
+
+pub fn main() {
+  let helper = 1
+  #(helper, self.helper())
+}
+"#,
+        vec![("main", "fn() -> #(Int, String)")]
+    );
+}
+
+#[test]
+fn self_qualifier_refers_to_local_function_not_import() {
+    assert_infer_with_module!(
+        ("wibble", "pub fn helper() -> String { todo }"),
+        r#"
+import wibble
+
+fn helper() -> Int {
+  1
+}
+
+pub fn main() {
+  #(wibble.helper(), self.helper())
+}
+"#,
+        vec![("main", "fn() -> #(String, Int)")],
+    );
+}
+
+#[test]
+fn variable_named_self_is_not_a_qualifier() {
+    assert_module_infer!(
+        r#"
+pub type Wibble {
+  Wibble(helper: Int)
+}
+
+fn helper() -> String {
+  todo
+}
+
+pub fn main() {
+  let self = Wibble(1)
+  self.helper
+}
+"#,
+        vec![("Wibble", "fn(Int) -> Wibble"), ("main", "fn() -> Int")]
+    );
+}
+
+#[test]
+fn self_qualified_private_function_is_used() {
+    assert_no_warnings!(
+        r#"
+fn helper() -> Int {
+  1
+}
+
+pub fn main() {
+  self.helper()
+}
+"#
+    );
+}
+
+#[test]
+fn self_qualified_unknown_value() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  self.helper()
+}
+"#
+    );
+}
@@ -0,0 +1,149 @@
+use crate::{assert_no_warnings, assert_warning};
+
+#[test]
+fn comparison_with_true() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  x == True
+}
+"#
+    );
+}
+
+#[test]
+fn comparison_with_false() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  x == False
+}
+"#
+    );
+}
+
+#[test]
+fn not_equal_to_true() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  x != True
+}
+"#
+    );
+}
+
+#[test]
+fn not_equal_to_false() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  x != False
+}
+"#
+    );
+}
+
+#[test]
+fn literal_on_the_left() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  True == x
+}
+"#
+    );
+}
+
+#[test]
+fn negated_binary_operation_is_wrapped_in_a_block() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  x > 1 == False
+}
+"#
+    );
+}
+
+#[test]
+fn double_negation_without_block_is_only_reported_once() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  !!x
+}
+"#
+    );
+}
+
+#[test]
+fn double_negation_with_block() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  !{ !x }
+}
+"#
+    );
+}
+
+#[test]
+fn nested_double_negations_are_reported_once() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  !{ !{ !x } }
+}
+"#
+    );
+}
+
+#[test]
+fn comparing_two_literals_is_not_reported() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  True == False
+}
+"#
+    );
+}
+
+#[test]
+fn comparing_two_variables_is_not_reported() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x, y) {
+  x == y
+}
+"#
+    );
+}
+
+#[test]
+fn constructors_that_are_not_bools_are_not_reported() {
+    assert_no_warnings!(
+        r#"
+pub type Answer {
+  True
+  False
+}
+
+pub fn main(x) {
+  x == True
+}
+"#
+    );
+}
+
+#[test]
+fn single_negation_is_not_reported() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  !x
+}
+"#
+    );
+}
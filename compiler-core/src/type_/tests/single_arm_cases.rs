@@ -0,0 +1,134 @@
+use std::rc::Rc;
+
+use crate::analyse::TargetSupport;
+use crate::build::Target;
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::{compile_module_with_config, get_printed_warnings_with_config};
+use crate::warning::VectorWarningEmitterIO;
+
+fn config(single_arm_cases: bool) -> PackageConfig {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        single_arm_cases,
+        ..Default::default()
+    };
+    config
+}
+
+fn warnings(src: &str, single_arm_cases: bool) -> String {
+    get_printed_warnings_with_config(src, config(single_arm_cases))
+}
+
+macro_rules! assert_single_arm_case_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_single_arm_case_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const SINGLE_ARM_CASE: &str = r#"
+pub fn main(x) {
+  case x {
+    value -> value + 1
+  }
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(SINGLE_ARM_CASE, false), "");
+}
+
+#[test]
+fn single_arm_case_with_variable_pattern() {
+    assert_single_arm_case_warning!(SINGLE_ARM_CASE);
+}
+
+#[test]
+fn single_arm_case_with_tuple_pattern() {
+    assert_single_arm_case_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    #(a, b) -> a + b
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn single_arm_case_with_single_variant_record_pattern() {
+    assert_single_arm_case_warning!(
+        r#"
+pub type Wibble {
+  Wibble(wobble: Int)
+}
+
+pub fn main(x) {
+  case x {
+    Wibble(wobble:) -> wobble
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn single_arm_case_with_refutable_pattern_is_not_a_let() {
+    // This case is not exhaustive, so it's an error rather than something
+    // that could be written with `let`.
+    let warnings = VectorWarningEmitterIO::default();
+    let result = compile_module_with_config(
+        "test_module",
+        r#"
+pub fn main(x) {
+  case x {
+    [first, ..] -> first
+  }
+}
+"#,
+        Some(Rc::new(warnings.clone())),
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config(true),
+    );
+    assert!(result.is_err());
+    assert!(warnings.take().is_empty());
+}
+
+#[test]
+fn single_arm_case_with_multiple_subjects() {
+    assert_no_single_arm_case_warning!(
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    a, b -> a + b
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn single_arm_case_with_alternative_patterns() {
+    assert_no_single_arm_case_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    #(1, a) | #(_, a) -> a
+  }
+}
+"#
+    );
+}
@@ -0,0 +1,97 @@
+use crate::build::Origin;
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config_and_origin;
+
+fn warnings(src: &str, todo_and_panic_in_library: bool, origin: Origin) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        todo_and_panic_in_library,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config_and_origin(src, config, origin)
+}
+
+macro_rules! assert_todo_or_panic_in_library_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true, Origin::Src);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+const PANIC: &str = r#"
+pub fn main(x: Result(Int, Nil)) {
+  case x {
+    Ok(x) -> x
+    Error(_) -> panic as "not a number"
+  }
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(PANIC, false, Origin::Src), "");
+}
+
+#[test]
+fn panic_in_library_module() {
+    assert_todo_or_panic_in_library_warning!(PANIC);
+}
+
+#[test]
+fn todo_in_library_module() {
+    assert_todo_or_panic_in_library_warning!(
+        r#"
+pub fn main() -> Int {
+  let x = todo
+  x + 1
+}
+"#
+    );
+}
+
+#[test]
+fn empty_function_in_library_module() {
+    assert_todo_or_panic_in_library_warning!(
+        r#"
+pub fn main() -> Int {}
+"#
+    );
+}
+
+#[test]
+fn todo_in_nested_function_in_library_module() {
+    assert_todo_or_panic_in_library_warning!(
+        r#"
+pub fn main() {
+  fn(x) { [x, todo] }
+}
+"#
+    );
+}
+
+#[test]
+fn no_warning_in_test_module() {
+    assert_eq!(warnings(PANIC, true, Origin::Test), "");
+}
+
+#[test]
+fn no_warning_without_todo_or_panic() {
+    assert_eq!(
+        warnings(
+            r#"
+pub fn main(x: Result(Int, Nil)) {
+  case x {
+    Ok(x) -> x
+    Error(_) -> 0
+  }
+}
+"#,
+            true,
+            Origin::Src
+        ),
+        ""
+    );
+}
@@ -0,0 +1,91 @@
+use crate::{assert_infer, assert_module_error, assert_module_infer};
+
+#[test]
+fn trailing_block_is_last_argument() {
+    assert_module_infer!(
+        r#"
+fn map(list: List(a), f: fn(a) -> b) -> List(b) {
+  todo
+}
+
+pub fn main() {
+  map([1, 2, 3]) { x -> x + 1 }
+}
+"#,
+        vec![("main", "fn() -> List(Int)")]
+    );
+}
+
+#[test]
+fn trailing_block_with_multiple_arguments() {
+    assert_module_infer!(
+        r#"
+fn fold(list: List(a), from: b, with: fn(b, a) -> b) -> b {
+  todo
+}
+
+pub fn main() {
+  fold([1, 2, 3], "") { acc, x -> acc <> "wibble" }
+}
+"#,
+        vec![("main", "fn() -> String")]
+    );
+}
+
+#[test]
+fn trailing_block_without_arguments() {
+    assert_infer!("fn(f) { f() }() { -> 1.0 }", "Float");
+}
+
+#[test]
+fn trailing_block_arguments_can_use_record_access() {
+    assert_module_infer!(
+        r#"
+pub type Wibble {
+  Wibble(wobble: Int)
+}
+
+fn map(list: List(a), f: fn(a) -> b) -> List(b) {
+  todo
+}
+
+pub fn main() {
+  map([Wibble(1)]) { wibble -> wibble.wobble }
+}
+"#,
+        vec![
+            ("Wibble", "fn(Int) -> Wibble"),
+            ("main", "fn() -> List(Int)")
+        ]
+    );
+}
+
+#[test]
+fn trailing_block_for_non_function_argument() {
+    assert_module_error!(
+        r#"
+fn add(a: Int, b: Int) -> Int {
+  a + b
+}
+
+pub fn main() {
+  add(1) { x -> x }
+}
+"#
+    );
+}
+
+#[test]
+fn trailing_block_with_wrong_number_of_arguments() {
+    assert_module_error!(
+        r#"
+fn map(list: List(a), f: fn(a) -> b) -> List(b) {
+  todo
+}
+
+pub fn main() {
+  map([1, 2, 3]) { x, y -> x + y }
+}
+"#
+    );
+}
@@ -0,0 +1,175 @@
+use crate::{assert_infer_with_module, assert_module_error, assert_module_infer};
+
+#[test]
+fn type_application_on_a_call() {
+    assert_module_infer!(
+        r#"
+pub fn new() -> List(a) {
+  []
+}
+
+pub fn main() {
+  new<Int>()
+}
+"#,
+        vec![("main", "fn() -> List(Int)"), ("new", "fn() -> List(a)")],
+    );
+}
+
+#[test]
+fn type_application_on_a_reference() {
+    assert_module_infer!(
+        r#"
+pub fn identity(x) {
+  x
+}
+
+pub fn main() {
+  identity<String>
+}
+"#,
+        vec![
+            ("identity", "fn(a) -> a"),
+            ("main", "fn() -> fn(String) -> String"),
+        ],
+    );
+}
+
+#[test]
+fn type_arguments_are_in_order_of_first_appearance() {
+    assert_module_infer!(
+        r#"
+pub fn pair(first: a, second: b) -> #(a, b) {
+  #(first, second)
+}
+
+pub fn main() {
+  pair<Int, String>
+}
+"#,
+        vec![
+            ("main", "fn() -> fn(Int, String) -> #(Int, String)"),
+            ("pair", "fn(a, b) -> #(a, b)"),
+        ],
+    );
+}
+
+#[test]
+fn type_application_with_generic_type_arguments() {
+    assert_module_infer!(
+        r#"
+pub fn new() -> List(a) {
+  []
+}
+
+pub fn main() {
+  new<Result(Int, fn() -> #(String, Bool))>()
+}
+"#,
+        vec![
+            ("main", "fn() -> List(Result(Int, fn() -> #(String, Bool)))"),
+            ("new", "fn() -> List(a)"),
+        ],
+    );
+}
+
+#[test]
+fn type_application_on_an_imported_function() {
+    assert_infer_with_module!(
+        (
+            "list",
+            r#"
+pub fn new() -> List(a) {
+  []
+}
+"#
+        ),
+        r#"
+import list
+
+pub fn main() {
+  list.new<Float>()
+}
+"#,
+        vec![("main", "fn() -> List(Float)")],
+    );
+}
+
+#[test]
+fn type_application_with_labelled_arguments() {
+    assert_module_infer!(
+        r#"
+pub fn wrap(value value: a, times times: Int) -> List(a) {
+  [value]
+}
+
+pub fn main() {
+  wrap<Int>(times: 2, value: 1)
+}
+"#,
+        vec![
+            ("main", "fn() -> List(Int)"),
+            ("wrap", "fn(a, Int) -> List(a)"),
+        ],
+    );
+}
+
+#[test]
+fn type_application_with_a_mismatched_argument() {
+    assert_module_error!(
+        r#"
+pub fn identity(x) {
+  x
+}
+
+pub fn main() {
+  identity<Int>("hello")
+}
+"#
+    );
+}
+
+#[test]
+fn type_application_with_too_many_type_arguments() {
+    assert_module_error!(
+        r#"
+pub fn identity(x) {
+  x
+}
+
+pub fn main() {
+  identity<Int, String>(1)
+}
+"#
+    );
+}
+
+#[test]
+fn type_application_on_a_value_with_no_type_parameters() {
+    assert_module_error!(
+        r#"
+pub fn double(x: Int) -> Int {
+  x * 2
+}
+
+pub fn main() {
+  double<Int>(1)
+}
+"#
+    );
+}
+
+#[test]
+fn type_application_with_an_unknown_type() {
+    assert_module_error!(
+        r#"
+pub fn identity(x) {
+  x
+}
+
+pub fn main() {
+  identity<Wibble>(1)
+}
+"#
+    );
+}
@@ -0,0 +1,99 @@
+use crate::{assert_module_error, assert_module_infer};
+
+#[test]
+fn hole_in_argument_position() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  add(_name, 1)
+}
+
+fn add(x: Float, y: Int) -> Int {
+  y
+}
+"#
+    );
+}
+
+#[test]
+fn hole_in_nested_argument_position() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  wibble(#(_, 1))
+}
+
+fn wibble(x: #(String, Int)) -> Int {
+  x.1
+}
+"#
+    );
+}
+
+#[test]
+fn hole_in_return_position() {
+    assert_module_error!(
+        r#"
+pub fn main() -> List(#(Int, String)) {
+  _
+}
+"#
+    );
+}
+
+#[test]
+fn hole_with_unknown_type() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let x = _
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn holes_do_not_cause_other_errors() {
+    assert_module_error!(
+        r#"
+pub fn main() -> String {
+  let x = _wibble + 1
+  wobble(x, _extra)
+  |> _
+}
+
+fn wobble(a: Int, b: Float) -> Bool {
+  True
+}
+"#
+    );
+}
+
+#[test]
+fn discard_argument_is_still_a_function_capture() {
+    assert_module_infer!(
+        r#"
+pub fn main() {
+  add(_, 1)
+}
+
+fn add(x: Float, y: Int) -> Int {
+  y
+}
+"#,
+        vec![("main", "fn() -> fn(Float) -> Int")]
+    );
+}
+
+#[test]
+fn bound_discard_variable_cannot_be_referenced() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let _wibble = 1
+  _wibble + 1
+}
+"#
+    );
+}
@@ -0,0 +1,145 @@
+use crate::config::{LintsConfig, PackageConfig};
+use crate::type_::tests::get_printed_warnings_with_config;
+
+fn warnings(src: &str, unused_labels: bool) -> String {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints = LintsConfig {
+        unused_labels,
+        ..Default::default()
+    };
+    get_printed_warnings_with_config(src, config)
+}
+
+macro_rules! assert_unused_label_warning {
+    ($src:expr $(,)?) => {
+        let warning = warnings($src, true);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_no_unused_label_warning {
+    ($src:expr $(,)?) => {
+        assert_eq!(warnings($src, true), "");
+    };
+}
+
+const UNUSED_LABEL: &str = r#"
+fn wibble(wobble _) {
+  1
+}
+
+pub fn main() {
+  wibble(1)
+}
+"#;
+
+#[test]
+fn lint_is_disabled_by_default() {
+    assert_eq!(warnings(UNUSED_LABEL, false), "");
+}
+
+#[test]
+fn discarded_labelled_argument_of_private_function() {
+    assert_unused_label_warning!(UNUSED_LABEL);
+}
+
+#[test]
+fn discarded_labelled_argument_with_name() {
+    assert_unused_label_warning!(
+        r#"
+fn wibble(first a, second _b) {
+  a
+}
+
+pub fn main() {
+  wibble(1, 2)
+}
+"#
+    );
+}
+
+#[test]
+fn label_used_at_a_call_site() {
+    assert_no_unused_label_warning!(
+        r#"
+fn wibble(wobble _) {
+  1
+}
+
+pub fn main() {
+  wibble(wobble: 1)
+}
+"#
+    );
+}
+
+#[test]
+fn label_used_in_a_pipeline() {
+    assert_no_unused_label_warning!(
+        r#"
+fn wibble(value, wobble _) {
+  value
+}
+
+pub fn main() {
+  1 |> wibble(wobble: 2)
+}
+"#
+    );
+}
+
+#[test]
+fn label_used_in_a_function_capture() {
+    assert_no_unused_label_warning!(
+        r#"
+fn wibble(value, wobble _) {
+  value
+}
+
+pub fn main() {
+  wibble(_, wobble: 2)
+}
+"#
+    );
+}
+
+#[test]
+fn label_of_a_used_argument() {
+    assert_no_unused_label_warning!(
+        r#"
+fn wibble(wobble value) {
+  value
+}
+
+pub fn main() {
+  wibble(1)
+}
+"#
+    );
+}
+
+#[test]
+fn label_of_a_public_function() {
+    assert_no_unused_label_warning!(
+        r#"
+pub fn wibble(wobble _) {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn label_of_an_internal_function() {
+    assert_no_unused_label_warning!(
+        r#"
+@internal
+pub fn wibble(wobble _) {
+  1
+}
+"#
+    );
+}
@@ -1,4 +1,12 @@
-use crate::{assert_error, assert_infer, assert_module_error, assert_module_infer, assert_warning};
+use crate::{
+    assert_error, assert_infer, assert_module_error, assert_module_infer, assert_no_warnings,
+    assert_warning,
+    ast::SrcSpan,
+    type_::{
+        error::{Error, UnifyErrorSituation},
+        tests::compile_module,
+    },
+};
 
 #[test]
 fn arity_1() {
@@ -439,6 +447,72 @@ fn apply(arg, fun) {
     );
 }
 
+#[test]
+fn two_bound_variables() {
+    assert_module_infer!(
+        r#"
+pub fn main() {
+  use name, age <- with_two("Lucy")
+  #(name, age + 1)
+}
+
+fn with_two(name, f) {
+  f(name, 10)
+}
+"#,
+        vec![("main", "fn() -> #(String, Int)")],
+    );
+}
+
+#[test]
+fn three_bound_variables() {
+    assert_module_infer!(
+        r#"
+pub fn main() {
+  use name, age, height <- with_three()
+  #(height, age, name)
+}
+
+fn with_three(f) {
+  f("Lucy", 10, 1.5)
+}
+"#,
+        vec![("main", "fn() -> #(Float, Int, String)")],
+    );
+}
+
+#[test]
+fn fewer_bound_variables_than_callback_arguments() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  use name, age <- with_three()
+  #(name, age)
+}
+
+fn with_three(f) {
+  f("Lucy", 10, 1.5)
+}
+"#
+    );
+}
+
+#[test]
+fn more_bound_variables_than_callback_arguments() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  use name, age, height <- with_two()
+  #(name, age, height)
+}
+
+fn with_two(f) {
+  f("Lucy", 10)
+}
+"#
+    );
+}
+
 #[test]
 fn typed_pattern() {
     assert_module_infer!(
@@ -493,3 +567,162 @@ use <- x()
 "#
     );
 }
+
+#[test]
+fn callback_returning_wrong_type_points_at_last_expression() {
+    assert_module_error!(
+        r#"
+pub fn wibble(f: fn(Int) -> String) -> String { f(1) }
+
+pub fn main() {
+  use x <- wibble()
+  let y = x + 1
+  case y {
+    1 -> 2
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn callback_returning_wrong_type_for_generic_function() {
+    assert_module_error!(
+        r#"
+pub fn try(r: Result(a, e), f: fn(a) -> Result(b, e)) -> Result(b, e) {
+  case r { Ok(a) -> f(a) Error(e) -> Error(e) }
+}
+
+pub fn main() {
+  use x <- try(Ok(1))
+  x + 1
+}
+"#
+    );
+}
+
+#[test]
+fn callback_returning_wrong_type_from_block() {
+    assert_module_error!(
+        r#"
+pub fn wibble(f: fn(Int) -> String) -> String { f(1) }
+
+pub fn main() {
+  use x <- wibble()
+  {
+    x
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn callback_result_mismatch_is_located_at_the_callback_body() {
+    let src = r#"
+pub fn wibble(f: fn(Int) -> String) -> String { f(1) }
+
+pub fn main() {
+  use x <- wibble()
+  x + 1
+}
+"#;
+    let (errors, _) = compile_module("test_module", src, None, vec![]).expect_err("should error");
+    let body_start = src.find("x + 1").expect("body") as u32;
+    let function_start = src.find("wibble()").expect("function") as u32;
+    match errors.as_slice() {
+        [
+            Error::CouldNotUnify {
+                location,
+                situation:
+                    Some(UnifyErrorSituation::UseCallbackResultMismatch { function_location }),
+                ..
+            },
+        ] => {
+            assert_eq!(*location, SrcSpan::new(body_start, body_start + 5));
+            assert_eq!(
+                *function_location,
+                SrcSpan::new(function_start, function_start + 8)
+            );
+        }
+        errors => panic!("unexpected errors {errors:?}"),
+    }
+}
+
+#[test]
+fn discarded_block_ending_with_use_warns() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  {
+    use x <- apply(1)
+    x + 1
+  }
+  Nil
+}
+
+fn apply(x, f) {
+  f(x)
+}
+"#
+    );
+}
+
+#[test]
+fn discarded_block_ending_with_use_returning_a_result_warns() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  {
+    use x <- apply(1)
+    Ok(x)
+  }
+  Nil
+}
+
+fn apply(x, f) {
+  f(x)
+}
+"#
+    );
+}
+
+#[test]
+fn discarded_block_ending_with_use_returning_nil_does_not_warn() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  {
+    use x <- apply(1)
+    echo_int(x)
+  }
+  Nil
+}
+
+fn apply(x, f) {
+  f(x)
+}
+
+fn echo_int(_x: Int) -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn use_returned_from_function_does_not_warn() {
+    assert_no_warnings!(
+        r#"
+pub fn main() {
+  use x <- apply(1)
+  x + 1
+}
+
+fn apply(x, f) {
+  f(x)
+}
+"#
+    );
+}
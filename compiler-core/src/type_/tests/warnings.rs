@@ -38,6 +38,39 @@ fn todo_with_known_type() {
     );
 }
 
+#[test]
+fn todo_as_final_pipeline_step() {
+    assert_warning!(
+        "
+pub fn wibble(a) { a }
+pub fn main() {
+  1 |> wibble |> todo
+}"
+    );
+}
+
+#[test]
+fn todo_with_message_as_final_pipeline_step() {
+    assert_warning!(
+        r#"
+pub fn wibble(a) { a }
+pub fn main() {
+  1 |> wibble |> todo as "not written yet"
+}"#
+    );
+}
+
+#[test]
+fn todo_in_the_middle_of_a_pipeline() {
+    assert_warning!(
+        "
+pub fn wibble(a) { a }
+pub fn main() {
+  1 |> todo |> wibble
+}"
+    );
+}
+
 #[test]
 fn empty_func_warning_test() {
     assert_warning!(
@@ -299,6 +332,11 @@ fn used_variable_warnings_test2() {
     assert_no_warnings!("pub fn a() { let b = 1 b }");
 }
 
+#[test]
+fn unused_discard_variable_does_not_warn() {
+    assert_no_warnings!("pub fn a(_b) { let _c = 1 5 }");
+}
+
 #[test]
 fn unused_variable_shadowing_test() {
     assert_warning!("pub fn a() { let b = 1 let b = 2 b }");
@@ -385,6 +423,46 @@ fn unused_imported_module_no_warning_on_used_unqualified_type_test() {
     );
 }
 
+#[test]
+fn imported_constructor_only_used_as_a_type() {
+    assert_warning!(
+        ("gleam/wibble", "pub type Wibble { Wibble(Int) }"),
+        "import gleam/wibble.{type Wibble, Wibble}
+pub fn main(a: Wibble) { a }"
+    );
+}
+
+#[test]
+fn aliased_imported_constructor_only_used_as_a_type() {
+    assert_warning!(
+        ("gleam/wibble", "pub type Wibble { Wibble(Int) }"),
+        "import gleam/wibble.{type Wibble as Wobble, Wibble as Wobble}
+pub fn main(a: Wobble) { a }"
+    );
+}
+
+#[test]
+fn imported_type_only_used_as_a_constructor() {
+    assert_warning!(
+        ("gleam/wibble", "pub type Wibble { Wibble(Int) }"),
+        "import gleam/wibble.{type Wibble, Wibble}
+pub fn main() { Wibble(1) }"
+    );
+}
+
+#[test]
+fn imported_type_and_constructor_both_used() {
+    assert_no_warnings!(
+        (
+            "thepackage",
+            "gleam/wibble",
+            "pub type Wibble { Wibble(Int) }"
+        ),
+        "import gleam/wibble.{type Wibble, Wibble}
+pub fn main() -> Wibble { Wibble(1) }",
+    );
+}
+
 // https://github.com/gleam-lang/gleam/issues/3313
 #[test]
 fn imported_module_with_alias_no_warning_when_only_used_in_case_test() {
@@ -794,6 +872,17 @@ pub fn main() {
     assert!(warnings.take().is_empty());
 }
 
+#[test]
+fn right_operand_of_incorrect_numeric_operator_is_only_inferred_once() {
+    let src = r#"
+pub fn main() {
+  1.0 + todo
+}"#;
+    let warnings = VectorWarningEmitterIO::default();
+    _ = compile_module("test_module", src, Some(Rc::new(warnings.clone())), vec![]).unwrap_err();
+    assert_eq!(warnings.take().len(), 1);
+}
+
 #[test]
 fn deprecated_constant() {
     assert_warning!(
@@ -2683,6 +2772,72 @@ pub fn go() {
     );
 }
 
+#[test]
+fn javascript_integer_overflow_in_addition() {
+    assert_js_warning!(
+        r#"
+pub fn go() {
+  9_007_199_254_740_991 + 1
+}
+"#
+    );
+}
+
+#[test]
+fn javascript_integer_overflow_in_multiplication() {
+    assert_js_warning!(
+        r#"
+pub fn go() {
+  -1 * 4_294_967_296 * 4_294_967_296
+}
+"#
+    );
+}
+
+#[test]
+fn javascript_integer_overflow_is_reported_once() {
+    assert_js_warning!(
+        r#"
+pub fn go() {
+  9_007_199_254_740_991 + 1 + 1
+}
+"#
+    );
+}
+
+#[test]
+fn javascript_no_integer_overflow_for_safe_result() {
+    assert_js_no_warnings!(
+        r#"
+pub fn go() {
+  9_007_199_254_740_990 + 1 - 4_294_967_296 * 2 / 0
+}
+"#
+    );
+}
+
+#[test]
+fn javascript_no_integer_overflow_for_non_literal_operand() {
+    assert_js_no_warnings!(
+        r#"
+pub fn go(x) {
+  9_007_199_254_740_991 + x
+}
+"#
+    );
+}
+
+#[test]
+fn no_integer_overflow_on_erlang() {
+    assert_no_warnings!(
+        r#"
+pub fn go() {
+  9_007_199_254_740_991 + 1
+}
+"#
+    );
+}
+
 #[test]
 fn javascript_unsafe_int_with_external_implementation() {
     assert_js_no_warnings!(
@@ -3014,3 +3169,254 @@ pub fn main(a) {
 "
     );
 }
+
+#[test]
+fn inefficient_string_building_in_recursive_function() {
+    assert_warning!(
+        r#"
+pub fn join(list: List(String), acc: String) -> String {
+  case list {
+    [] -> acc
+    [first, ..rest] -> join(rest, acc <> first)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn inefficient_string_building_prepending_to_accumulator() {
+    assert_warning!(
+        r#"
+pub fn reverse(list: List(String), acc: String) -> String {
+  case list {
+    [] -> acc
+    [first, ..rest] -> reverse(rest, first <> acc)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_string_building_warning_if_disabled() {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints.inefficient_string_building = false;
+    let warnings = get_printed_warnings_with_config(
+        r#"
+pub fn join(list: List(String), acc: String) -> String {
+  case list {
+    [] -> acc
+    [first, ..rest] -> join(rest, acc <> first)
+  }
+}
+"#,
+        config,
+    );
+    assert_eq!(warnings, "");
+}
+
+#[test]
+fn no_inefficient_string_building_warning_if_not_accumulating() {
+    assert_no_warnings!(
+        r#"
+pub fn join(list: List(String)) -> String {
+  case list {
+    [] -> ""
+    [first, ..rest] -> first <> join(rest)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_string_building_warning_for_a_different_parameter() {
+    assert_no_warnings!(
+        r#"
+pub fn wibble(list: List(String), prefix: String, acc: List(String)) -> List(String) {
+  case list {
+    [] -> acc
+    [first, ..rest] -> wibble(rest, prefix, [prefix <> first, ..acc])
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_string_building_warning_for_shadowed_parameter() {
+    assert_no_warnings!(
+        r#"
+pub fn wibble(list: List(String), acc: String) -> String {
+  case list {
+    [] -> acc
+    [first, ..rest] -> {
+      let acc = "wobble"
+      wibble(rest, acc <> first)
+    }
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn inefficient_list_building_in_recursive_function() {
+    assert_warning!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list
+
+pub fn double(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> acc
+    [first, ..rest] -> double(rest, list.append(acc, [first * 2]))
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_list_building_warning_if_disabled() {
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+    config.lints.inefficient_list_building = false;
+    let warnings = VectorWarningEmitterIO::default();
+    _ = compile_module_with_config(
+        "test_module",
+        r#"
+import gleam/list
+
+pub fn double(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> acc
+    [first, ..rest] -> double(rest, list.append(acc, [first * 2]))
+  }
+}
+"#,
+        Some(Rc::new(warnings.clone())),
+        vec![(
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }",
+        )],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config,
+    )
+    .expect("Compilation should succeed");
+    assert_eq!(warnings.take(), vec![]);
+}
+
+#[test]
+fn inefficient_list_building_with_unqualified_append() {
+    assert_warning!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list.{append}
+
+pub fn double(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> acc
+    [first, ..rest] -> double(rest, append(acc, [first * 2]))
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_list_building_warning_when_appending_to_the_front() {
+    assert_no_warnings!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list
+
+pub fn double(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> acc
+    [first, ..rest] -> double(rest, list.append([first * 2], acc))
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_list_building_warning_for_a_single_append() {
+    assert_no_warnings!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list
+
+pub fn wibble(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> list.append(acc, [0])
+    [first, ..rest] -> wibble(rest, [first, ..acc])
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_list_building_warning_for_non_recursive_call() {
+    assert_no_warnings!(
+        (
+            "gleam_stdlib",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list
+
+pub fn wibble(items: List(Int), acc: List(Int)) -> List(Int) {
+  wobble(items, list.append(acc, items))
+}
+
+fn wobble(items: List(Int), acc: List(Int)) -> List(Int) {
+  list.append(items, acc)
+}
+"#
+    );
+}
+
+#[test]
+fn no_inefficient_list_building_warning_for_a_list_module_outside_stdlib() {
+    assert_no_warnings!(
+        (
+            "other_package",
+            "gleam/list",
+            "pub fn append(first: List(a), second: List(a)) -> List(a) { first }"
+        ),
+        r#"
+import gleam/list
+
+pub fn double(items: List(Int), acc: List(Int)) -> List(Int) {
+  case items {
+    [] -> acc
+    [first, ..rest] -> double(rest, list.append(acc, [first * 2]))
+  }
+}
+"#
+    );
+}
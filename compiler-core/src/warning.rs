@@ -3,11 +3,13 @@ use crate::{
     build::Target,
     diagnostic::{self, Diagnostic, Location},
     error::wrap,
+    lint::Lint,
+    reference::AlwaysIgnoredReturn,
     type_::{
         self,
         error::{
-            FeatureKind, LiteralCollectionKind, PanicPosition, TodoOrPanic,
-            UnreachableCaseClauseReason,
+            FeatureKind, LiteralCollectionKind, NamingConventionProblem, PanicPosition,
+            TodoOrPanic, UnreachableCaseClauseReason,
         },
         pretty::Printer,
     },
@@ -15,6 +17,7 @@ use crate::{
 use camino::Utf8PathBuf;
 use debug_ignore::DebugIgnore;
 use ecow::EcoString;
+use heck::ToSnakeCase;
 use std::{
     io::Write,
     sync::{Arc, atomic::Ordering},
@@ -22,6 +25,10 @@ use std::{
 use std::{rc::Rc, sync::atomic::AtomicUsize};
 use termcolor::Buffer;
 
+/// The sink that receives each warning as soon as the compiler emits it.
+/// Implement this to handle warnings as they are found, rather than
+/// collecting them all once compilation is done.
+///
 pub trait WarningEmitterIO {
     fn emit_warning(&self, warning: Warning);
 }
@@ -70,6 +77,27 @@ impl WarningEmitterIO for VectorWarningEmitterIO {
     }
 }
 
+/// A warning sink that calls the given function with each emitted warning.
+///
+#[derive(Debug)]
+pub struct CallbackWarningEmitterIO {
+    callback: DebugIgnore<Box<dyn Fn(Warning)>>,
+}
+
+impl CallbackWarningEmitterIO {
+    pub fn new(callback: impl Fn(Warning) + 'static) -> Self {
+        Self {
+            callback: DebugIgnore(Box::new(callback)),
+        }
+    }
+}
+
+impl WarningEmitterIO for CallbackWarningEmitterIO {
+    fn emit_warning(&self, warning: Warning) {
+        (self.callback)(warning)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct WarningEmitter {
     /// The number of warnings emitted.
@@ -105,6 +133,10 @@ impl WarningEmitter {
         self.emitter.emit_warning(warning);
     }
 
+    pub fn callback(callback: impl Fn(Warning) + 'static) -> Self {
+        Self::new(Rc::new(CallbackWarningEmitterIO::new(callback)))
+    }
+
     pub fn vector() -> (Self, Rc<VectorWarningEmitterIO>) {
         let io = Rc::new(VectorWarningEmitterIO::default());
         let emitter = Self::new(io.clone());
@@ -162,6 +194,23 @@ pub enum Warning {
         src: EcoString,
         warning: DeprecatedSyntaxWarning,
     },
+
+    /// A problem found by one of the custom lint rules registered with the
+    /// compiler.
+    Lint {
+        path: Utf8PathBuf,
+        src: EcoString,
+        rule: EcoString,
+        lint: Lint,
+    },
+
+    /// A function whose return value is discarded everywhere it is called,
+    /// found by the opt-in whole-project analysis.
+    AlwaysIgnoredReturn {
+        path: Utf8PathBuf,
+        src: EcoString,
+        function: AlwaysIgnoredReturn,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Copy)]
@@ -220,6 +269,57 @@ pub enum DeprecatedSyntaxWarning {
 impl Warning {
     pub fn to_diagnostic(&self) -> Diagnostic {
         match self {
+            Warning::Lint {
+                path,
+                src,
+                rule,
+                lint,
+            } => Diagnostic {
+                title: format!("Lint: {rule}"),
+                text: "".into(),
+                hint: lint.hint.as_ref().map(EcoString::to_string),
+                level: diagnostic::Level::Warning,
+                location: Some(Location {
+                    label: diagnostic::Label {
+                        text: Some(lint.message.to_string()),
+                        span: lint.location,
+                    },
+                    path: path.clone(),
+                    src: src.clone(),
+                    extra_labels: vec![],
+                }),
+            },
+
+            Warning::AlwaysIgnoredReturn {
+                path,
+                src,
+                function,
+            } => {
+                let calls = match function.calls {
+                    1 => "its only call".into(),
+                    calls => format!("all of its {calls} calls"),
+                };
+                Diagnostic {
+                    title: "Return value is never used".into(),
+                    text: wrap(&format!(
+                        "The value returned by `{}` is discarded by {calls} in this \
+project, so it may not be needed anymore.",
+                        function.name
+                    )),
+                    hint: Some("Return `Nil` instead, or use the returned value.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        label: diagnostic::Label {
+                            text: Some("The return value of this function is never used".into()),
+                            span: function.location,
+                        },
+                        path: path.clone(),
+                        src: src.clone(),
+                        extra_labels: vec![],
+                    }),
+                }
+            }
+
             Warning::InvalidSource { path } => Diagnostic {
                 title: "Invalid module name".into(),
                 text: "\
@@ -383,6 +483,13 @@ A use expression must always be followed by at least one expression.",
                             );
                             "Incomplete use expression"
                         }
+                        TodoKind::PipelineStep => {
+                            text.push_str(
+                                "
+The final step of this pipeline is yet to be written.",
+                            );
+                            "Unfinished pipeline"
+                        }
                     }
                     .into();
                     if !type_.is_variable() {
@@ -443,6 +550,35 @@ A use expression must always be followed by at least one expression.",
                     }),
                 },
 
+                type_::Warning::UnusedUseValue { location, type_ } => {
+                    let text = format!(
+                        "{}\n\n{}\n",
+                        wrap(
+                            "This `use` is the last statement of its block, so the \
+block evaluates to the value returned by the `use` callback. The block is \
+discarded, so this value is never used. Its type is:"
+                        ),
+                        Printer::new().pretty_print(type_, 4)
+                    );
+                    Diagnostic {
+                        title: "Unused `use` value".into(),
+                        text,
+                        hint: Some(
+                            "If you are sure you don't need it you can assign it to `_`.".into(),
+                        ),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some("The value of this `use` is never used".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
                 type_::Warning::NoFieldsRecordUpdate { location } => Diagnostic {
                     title: "Fieldless record update".into(),
                     text: "".into(),
@@ -598,6 +734,29 @@ Hint: You can safely remove it.
                     }),
                 },
 
+                type_::Warning::ImportedConstructorOnlyUsedAsType { location, name } => {
+                    let text = wrap(&format!(
+                        "Only the `{name}` type is used in this module, not its constructor."
+                    ));
+                    Diagnostic {
+                        title: "Unused imported constructor".into(),
+                        text,
+                        hint: Some(
+                            "You can remove it from the import, keeping only the type.".into(),
+                        ),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            src: src.clone(),
+                            path: path.to_path_buf(),
+                            label: diagnostic::Label {
+                                text: Some("This constructor is never used".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
                 type_::Warning::UnusedPrivateModuleConstant { location, .. } => Diagnostic {
                     title: "Unused private constant".into(),
                     text: "".into(),
@@ -1144,6 +1303,350 @@ information.",
                         extra_labels: Vec::new(),
                     }),
                 },
+
+                type_::Warning::IntegerOverflow { location } => Diagnostic {
+                    title: "Int is outside JavaScript's safe integer range".into(),
+                    text: wrap(
+                        "This expression evaluates to an integer value that is too \
+large to be represented accurately by JavaScript's number type, so the result will \
+lose precision. To avoid this warning integer values must be in the range \
+-(2^53 - 1) - (2^53 - 1).
+
+See JavaScript's Number.MAX_SAFE_INTEGER and Number.MIN_SAFE_INTEGER properties for more \
+information.",
+                    ),
+                    hint: None,
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some(
+                                "This overflows the safe integer range on JavaScript".into(),
+                            ),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::InefficientStringBuilding { location } => Diagnostic {
+                    title: "Inefficient string building".into(),
+                    text: wrap(
+                        "This recursive call grows a string by appending to it with `<>`. \
+On some targets the accumulated string is copied each time, so building a \
+long string this way can take time proportional to the square of its length.",
+                    ),
+                    hint: Some("Consider building it with the `gleam/string_tree` module.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This string is copied on every iteration".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::InefficientListBuilding { location } => Diagnostic {
+                    title: "Inefficient list building".into(),
+                    text: wrap(
+                        "This recursive call grows a list by appending to its end with \
+`list.append`. The accumulated list is copied each time, so building a long \
+list this way takes time proportional to the square of its length.",
+                    ),
+                    hint: Some(
+                        "Consider prepending to the list instead, and reversing it with \
+`list.reverse` once it's complete."
+                            .into(),
+                    ),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This list is copied on every iteration".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::NamingConvention {
+                    location,
+                    kind,
+                    name,
+                    problem,
+                } => {
+                    let kind = kind.as_str();
+                    let (title, text, hint, label) = match problem {
+                        NamingConventionProblem::NotSnakeCase => (
+                            "Name is not snake_case".into(),
+                            format!(
+                                "This project requires {kind} names to be written in \
+snake_case, with words separated by a single underscore."
+                            ),
+                            Some(format!("Rename it to `{}`.", name.to_snake_case())),
+                            "This is not snake_case",
+                        ),
+                        NamingConventionProblem::TooShort { minimum_length } => (
+                            "Name is too short".into(),
+                            format!(
+                                "This project requires {kind} names to be at least \
+{minimum_length} characters long."
+                            ),
+                            None,
+                            "This name is too short",
+                        ),
+                        NamingConventionProblem::TooLong { maximum_length } => (
+                            "Name is too long".into(),
+                            format!(
+                                "This project requires {kind} names to be at most \
+{maximum_length} characters long."
+                            ),
+                            None,
+                            "This name is too long",
+                        ),
+                    };
+                    Diagnostic {
+                        title,
+                        text: wrap(&text),
+                        hint,
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some(label.into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
+                type_::Warning::UnusedLabel {
+                    location,
+                    function,
+                    label,
+                } => Diagnostic {
+                    title: "Unused label".into(),
+                    text: wrap(&format!(
+                        "The argument labelled `{label}` is discarded by the private \
+`{function}` function, and none of its calls use the label."
+                    )),
+                    hint: Some("You can safely remove the label.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This label is never used".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::ConstantFunction { location } => Diagnostic {
+                    title: "Constant function".into(),
+                    text: wrap(
+                        "This anonymous function discards all of its arguments, \
+so the value it returns doesn't depend on any of them.",
+                    ),
+                    hint: Some(
+                        "If this is intended you can ignore this warning, otherwise \
+check the function is using the right arguments."
+                            .into(),
+                    ),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("All of its arguments are discarded".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::IdentityCase { location } => Diagnostic {
+                    title: "Identity case".into(),
+                    text: wrap(
+                        "Every clause of this case expression returns the value \
+being matched on unchanged, so it always evaluates to that same value.",
+                    ),
+                    hint: Some("Replace this case expression with its subject.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This always evaluates to its subject".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::TodoOrPanicInLibrary { location, kind } => {
+                    let (title, keyword, hint) = match kind {
+                        TodoOrPanic::Todo => (
+                            "Todo in library code",
+                            "todo",
+                            "Replace it with the code it stands for.",
+                        ),
+                        TodoOrPanic::Panic => (
+                            "Panic in library code",
+                            "panic",
+                            "Return an error the caller can handle instead.",
+                        ),
+                    };
+                    Diagnostic {
+                        title: title.into(),
+                        text: wrap(&format!(
+                            "This `{keyword}` is in the package's `src` directory, \
+so it would crash the programs of anyone using the package if it were run."
+                        )),
+                        hint: Some(hint.into()),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some("This crashes the program if run".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
+                type_::Warning::PipelineComposable { location } => Diagnostic {
+                    title: "Composable pipeline".into(),
+                    text: wrap(
+                        "Every step of this pipeline calls a single argument \
+function with the previous value, so it could be written as a composition of \
+those functions.",
+                    ),
+                    hint: Some(
+                        "Define a function composing these steps and call it instead.".into(),
+                    ),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This is a composition of functions".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::OverlySpecificAnnotation { location, inferred } => {
+                    let text = format!(
+                        "{}\n\n{}\n",
+                        wrap(
+                            "The annotations of this function make its type less \
+general than it could be. Without them its type would be:"
+                        ),
+                        Printer::new().pretty_print(inferred, 4)
+                    );
+                    Diagnostic {
+                        title: "Overly specific annotation".into(),
+                        text,
+                        hint: Some(
+                            "Remove or generalise the annotations if this is not intentional."
+                                .into(),
+                        ),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some("This type is more specific than needed".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
+                type_::Warning::SingleArmCase { location } => Diagnostic {
+                    title: "Single clause case".into(),
+                    text: wrap(
+                        "This case expression has a single clause with a pattern \
+that always matches, so it can be written as a `let` assignment.",
+                    ),
+                    hint: Some("Replace this case expression with a `let` assignment.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some("This could be a `let` assignment".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::SimplifiableBool {
+                    location,
+                    operand,
+                    simplified,
+                } => {
+                    let operand = src
+                        .get(operand.start as usize..operand.end as usize)
+                        .unwrap_or_default();
+                    let replacement = simplified.replacement(operand);
+                    Diagnostic {
+                        title: "Simplifiable boolean expression".into(),
+                        text: wrap(&format!(
+                            "This boolean expression can be written more simply as \
+`{replacement}`."
+                        )),
+                        hint: Some(format!("Replace it with `{replacement}`.")),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some("This can be simplified".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
+                type_::Warning::UnnecessaryFunctionWrapper { location, function } => {
+                    let function = src
+                        .get(function.start as usize..function.end as usize)
+                        .unwrap_or_default();
+                    Diagnostic {
+                        title: "Unnecessary function wrapper".into(),
+                        text: wrap(&format!(
+                            "This anonymous function only calls `{function}` with all of \
+its arguments, in the same order, so `{function}` can be used directly instead."
+                        )),
+                        hint: Some(format!("Replace it with `{function}`.")),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some("This can be replaced with the function".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
             },
         }
     }
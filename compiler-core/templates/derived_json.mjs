@@ -0,0 +1,15 @@
+let derived_json$decoder;
+
+function derived_json_bits_to_string(bits) {
+  derived_json$decoder ??= new TextDecoder();
+  if (bits.bitOffset === 0) {
+    return derived_json$decoder.decode(
+      bits.rawBuffer.subarray(0, bits.byteSize),
+    );
+  }
+  const bytes = new Uint8Array(bits.byteSize);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = bits.byteAt(i);
+  }
+  return derived_json$decoder.decode(bytes);
+}
@@ -1,11 +1,14 @@
 -module('{{ application }}@@main').
--export([run/1]).
+-export([run/1, bench/2]).
 
 -define(red, "\e[31;1m").
 -define(grey, "\e[90m").
 -define(reset_color, "\e[39m").
 -define(reset_all, "\e[0m").
 
+% How long each benchmark is run for, in nanoseconds.
+-define(benchmark_duration, 1000000000).
+
 run(Module) ->
     io:setopts(standard_io, [binary, {encoding, utf8}]),
     io:setopts(standard_error, [{encoding, utf8}]),
@@ -29,6 +32,62 @@ run_module(Module) ->
             init:stop(1)
     end.
 
+bench(Benchmarks, Format) ->
+    io:setopts(standard_io, [binary, {encoding, utf8}]),
+    io:setopts(standard_error, [{encoding, utf8}]),
+    process_flag(trap_exit, true),
+    Pid = spawn_link(fun() -> run_benchmarks(Benchmarks, Format) end),
+    receive
+        {'EXIT', Pid, {Reason, StackTrace}} ->
+            print_error(exit, Reason, StackTrace),
+            init:stop(1)
+    end.
+
+run_benchmarks(Benchmarks, Format) ->
+    try
+        {ok, _} = application:ensure_all_started('{{ application }}'),
+        erlang:process_flag(trap_exit, false),
+        Results = [measure(Module, Function) || {Module, Function} <- Benchmarks],
+        io:put_chars(format_results(Format, Results)),
+        erlang:halt(0)
+    catch
+        Class:Reason:StackTrace ->
+            print_error(Class, Reason, StackTrace),
+            init:stop(1)
+    end.
+
+% Each benchmark is run at least once, and then over and over until it has
+% been running for `benchmark_duration`.
+measure(Module, Function) ->
+    Start = erlang:monotonic_time(nanosecond),
+    measure(Module, Function, Start, 0).
+
+measure(Module, Function, Start, Runs) ->
+    Module:Function(),
+    Elapsed = erlang:monotonic_time(nanosecond) - Start,
+    case Elapsed >= ?benchmark_duration of
+        true -> {gleam_module_name(Module), Function, Runs + 1, Elapsed div (Runs + 1)};
+        false -> measure(Module, Function, Start, Runs + 1)
+    end.
+
+format_results(text, Results) ->
+    [
+        [Module, $., atom_to_binary(Function), ": ",
+         integer_to_binary(Mean), " ns/run (", integer_to_binary(Runs), " runs)\n"]
+     || {Module, Function, Runs, Mean} <- Results
+    ];
+format_results(json, Results) ->
+    Objects = [
+        ["{\"module\":\"", Module, "\",\"function\":\"", atom_to_binary(Function),
+         "\",\"runs\":", integer_to_binary(Runs),
+         ",\"mean_ns\":", integer_to_binary(Mean), "}"]
+     || {Module, Function, Runs, Mean} <- Results
+    ],
+    [$[, lists:join($,, Objects), "]\n"].
+
+gleam_module_name(Module) ->
+    string:replace(atom_to_binary(Module), "@", "/", all).
+
 print_error(Class, Error, Stacktrace) ->
     Printed = [
         ?red, "runtime error", ?reset_color, ": ", error_class(Class, Error), ?reset_all,
@@ -0,0 +1,11 @@
+memoize@lookup(Key, Compute) ->
+    DictionaryKey = {memoize@lookup, ?MODULE, Key},
+    case erlang:get(DictionaryKey) of
+        {memoized, Value} ->
+            Value;
+
+        undefined ->
+            Value = Compute(),
+            erlang:put(DictionaryKey, {memoized, Value}),
+            Value
+    end.
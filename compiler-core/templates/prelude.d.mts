@@ -85,3 +85,17 @@ export function remainderInt(a: number, b: number): number;
 export function divideInt(a: number, b: number): number;
 
 export function divideFloat(a: number, b: number): number;
+
+export function memoize<T>(
+  cache: Map<unknown, unknown>,
+  key: unknown[],
+  compute: () => T,
+): T;
+
+export function shouldPanic(
+  test: () => unknown,
+  expected: string | undefined,
+  module: string,
+  line: number,
+  fn: string,
+): undefined;
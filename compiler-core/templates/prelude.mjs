@@ -1512,3 +1512,37 @@ export function makeError(variant, module, line, fn, message, extra) {
   for (let k in extra) error[k] = extra[k];
   return error;
 }
+
+const memoizedValue = Symbol("memoizedValue");
+
+// @internal
+export function memoize(cache, key, compute) {
+  let node = cache;
+  for (const part of key) {
+    let next = node.get(part);
+    if (next === undefined) {
+      next = new Map();
+      node.set(part, next);
+    }
+    node = next;
+  }
+  if (node.has(memoizedValue)) return node.get(memoizedValue);
+  const value = compute();
+  node.set(memoizedValue, value);
+  return value;
+}
+
+// @internal
+export function shouldPanic(test, expected, module, line, fn) {
+  let failure;
+  try {
+    test();
+    failure = "Expected this test to panic";
+  } catch (error) {
+    if (expected === undefined) return undefined;
+    if (error?.gleam_error === undefined) throw error;
+    if (error.message.includes(expected)) return undefined;
+    failure = `Expected the panic message to contain "${expected}" but it was "${error.message}"`;
+  }
+  throw makeError("panic", module, line, fn, failure, {});
+}
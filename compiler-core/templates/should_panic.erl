@@ -0,0 +1,39 @@
+should_panic@run(Test, Expected, Module, Function, Line) ->
+    Outcome = try Test() of
+        _ ->
+            {error, <<"Expected this test to panic"/utf8>>}
+    catch
+        error:#{gleam_error := _, message := Message} when is_binary(Message) ->
+            should_panic@check_message(Message, Expected);
+
+        error:_ when Expected =:= none ->
+            ok
+    end,
+    case Outcome of
+        ok ->
+            nil;
+
+        {error, Reason} ->
+            erlang:error(#{
+                gleam_error => panic,
+                message => Reason,
+                module => Module,
+                function => Function,
+                line => Line
+            })
+    end.
+
+should_panic@check_message(_, none) ->
+    ok;
+should_panic@check_message(_, {some, <<>>}) ->
+    ok;
+should_panic@check_message(Message, {some, Expected}) ->
+    case binary:match(Message, Expected) of
+        nomatch ->
+            {error, <<"Expected the panic message to contain \""/utf8,
+                Expected/binary, "\" but it was \""/utf8, Message/binary,
+                "\""/utf8>>};
+
+        _ ->
+            ok
+    end.
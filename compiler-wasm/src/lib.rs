@@ -8,7 +8,8 @@ use gleam_core::{
     Error,
     analyse::TargetSupport,
     build::{
-        Mode, NullTelemetry, PackageCompiler, StaleTracker, Target, TargetCodegenConfiguration,
+        JavaScriptModuleFormat, Mode, NullTelemetry, PackageCompiler, StaleTracker, Target,
+        TargetCodegenConfiguration,
     },
     config::PackageConfig,
     io::{FileSystemReader, FileSystemWriter},
@@ -183,6 +184,7 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: false,
             prelude_location: Utf8PathBuf::from("./gleam_prelude.mjs"),
+            module_format: JavaScriptModuleFormat::EcmaScript,
         },
     };
 
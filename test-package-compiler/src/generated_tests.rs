@@ -144,6 +144,17 @@ fn import_cycle_multi() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn import_cycle_nested() {
+    let output = crate::prepare("./cases/import_cycle_nested");
+    insta::assert_snapshot!(
+        "import_cycle_nested",
+        output,
+        "./cases/import_cycle_nested",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn import_shadowed_name_warning() {
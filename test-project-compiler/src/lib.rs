@@ -4,7 +4,10 @@ mod generated_tests;
 use camino::Utf8PathBuf;
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, NullTelemetry, Options, ProjectCompiler, Telemetry},
+    build::{
+        Codegen, Compile, JavaScriptModuleFormat, Mode, NullTelemetry, Options, ProjectCompiler,
+        Telemetry,
+    },
     config::PackageConfig,
     io::{FileSystemReader, FileSystemWriter},
     paths::ProjectPaths,
@@ -30,6 +33,8 @@ pub fn prepare(path: &str, mode: Mode) -> String {
         warnings_as_errors: false,
         root_target_support: TargetSupport::Enforced,
         no_print_progress: true,
+        javascript_module_format: JavaScriptModuleFormat::EcmaScript,
+        whole_program: false,
     };
 
     let compiler = ProjectCompiler::new(
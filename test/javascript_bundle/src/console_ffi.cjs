@@ -0,0 +1,7 @@
+"use strict";
+
+function log(message) {
+  console.log(message);
+}
+
+module.exports = { log };
@@ -17,6 +17,9 @@ import {
   bitArraySlice,
   bitArraySliceToInt,
   bitArraySliceToFloat,
+  memoize,
+  makeError,
+  shouldPanic,
 } from "./prelude.mjs";
 
 let failures = 0;
@@ -1194,6 +1197,88 @@ assertEqual(divideFloat(-1.5, -0.0), 0.0);
 assertEqual(divideFloat(-1.5, -2.0), 0.75);
 assertEqual(divideFloat(-1.5, -2.5), 0.6);
 
+// Memoization
+
+{
+  const cache = new Map();
+  let calls = 0;
+  const add = (a, b) =>
+    memoize(cache, [a, b], () => {
+      calls++;
+      return a + b;
+    });
+  assertEqual(add(1, 2), 3);
+  assertEqual(add(1, 2), 3);
+  assertEqual(calls, 1);
+  assertEqual(add(2, 1), 3);
+  assertEqual(add(1, 3), 4);
+  assertEqual(calls, 3);
+}
+
+{
+  // Values that are all `undefined` in JavaScript are still cached.
+  const cache = new Map();
+  let calls = 0;
+  const nil = (n) =>
+    memoize(cache, [n], () => {
+      calls++;
+      return undefined;
+    });
+  assertEqual(nil(undefined), undefined);
+  assertEqual(nil(undefined), undefined);
+  assertEqual(calls, 1);
+}
+
+{
+  // Functions with no arguments are only ever evaluated once.
+  const cache = new Map();
+  let calls = 0;
+  const constant = () =>
+    memoize(cache, [], () => {
+      calls++;
+      return "wibble";
+    });
+  assertEqual(constant(), "wibble");
+  assertEqual(constant(), "wibble");
+  assertEqual(calls, 1);
+}
+
+// Tests expected to panic
+
+function panicMessage(test) {
+  try {
+    test();
+  } catch (error) {
+    return error.message;
+  }
+  return undefined;
+}
+
+const panicking = () => {
+  throw makeError("panic", "wibble", 1, "wobble", "Division by zero", {});
+};
+
+assertEqual(
+  shouldPanic(panicking, undefined, "wibble", 2, "wobble_test"),
+  undefined,
+);
+assertEqual(
+  shouldPanic(panicking, "by zero", "wibble", 2, "wobble_test"),
+  undefined,
+);
+assertEqual(
+  panicMessage(() =>
+    shouldPanic(() => 1, undefined, "wibble", 2, "wobble_test"),
+  ),
+  "Expected this test to panic",
+);
+assertEqual(
+  panicMessage(() =>
+    shouldPanic(panicking, "overflow", "wibble", 2, "wobble_test"),
+  ),
+  'Expected the panic message to contain "overflow" but it was "Division by zero"',
+);
+
 // Record updates
 
 assertEqual(new Ok(1).withFields({ 0: 2 }), new Ok(2));
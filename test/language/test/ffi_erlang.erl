@@ -1,7 +1,8 @@
 -module(ffi_erlang).
 
 -export([
-    to_string/1, append/2, print/1, file_exists/1, halt/1, to_dynamic/1
+    to_string/1, append/2, print/1, file_exists/1, halt/1, to_dynamic/1,
+    panics/1
 ]).
 
 append(A, B) ->
@@ -23,3 +24,10 @@ halt(Code) ->
 
 to_dynamic(X) ->
     X.
+
+panics(F) ->
+    try F() of
+        _ -> false
+    catch
+        error:_ -> true
+    end.
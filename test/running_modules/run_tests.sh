@@ -59,6 +59,9 @@ should_fail run --module src/doesnt_exist
 # No main function
 should_fail run --module module/no_main_function
 
+# Private main function
+should_fail run --module module/private_main_function
+
 # Main function with wrong arity
 should_fail run --module module/wrong_arity
 